@@ -0,0 +1,136 @@
+use gtk::prelude::{BoxExt, ButtonExt, Cast, GtkWindowExt, OrientableExt, WidgetExt};
+use relm4::factory::{DynamicIndex, FactoryComponent, FactorySender, RelmSignalListItemFactory};
+use relm4::{ComponentParts, ComponentSender, RelmApp, RelmWidgetExt, SimpleComponent};
+
+#[derive(Debug)]
+struct Row {
+    name: String,
+    clicks: u32,
+}
+
+#[derive(Debug)]
+enum RowMsg {
+    Click,
+}
+
+#[derive(Debug)]
+enum RowOutput {
+    Remove(DynamicIndex),
+}
+
+#[relm4::factory]
+impl FactoryComponent for Row {
+    type Init = String;
+    type Input = RowMsg;
+    type Output = RowOutput;
+    type CommandOutput = ();
+    type ParentWidget = gtk::Box;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Horizontal,
+            set_spacing: 10,
+            set_margin_all: 5,
+
+            gtk::Label {
+                #[watch]
+                set_label: &format!("{} ({} clicks)", self.name, self.clicks),
+                set_hexpand: true,
+                set_xalign: 0.0,
+            },
+
+            gtk::Button {
+                set_label: "Click",
+                connect_clicked => RowMsg::Click,
+            },
+
+            gtk::Button {
+                set_label: "Remove",
+                connect_clicked[sender, index] => move |_| {
+                    sender.output(RowOutput::Remove(index.clone())).unwrap();
+                }
+            }
+        }
+    }
+
+    fn init_model(name: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
+        Self { name, clicks: 0 }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: FactorySender<Self>) {
+        match msg {
+            RowMsg::Click => self.clicks += 1,
+        }
+    }
+}
+
+struct App {
+    model: gtk::StringList,
+    // Keeps the factory alive.
+    _rows: RelmSignalListItemFactory<Row>,
+}
+
+#[derive(Debug)]
+enum AppMsg {
+    Remove(DynamicIndex),
+}
+
+#[relm4::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("List item factory example"),
+            set_default_size: (300, 400),
+
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                gtk::ListView {
+                    set_model: Some(&gtk::NoSelection::new(Some(list.clone()))),
+                    set_factory: Some(rows.factory()),
+                }
+            }
+        }
+    }
+
+    fn init(_: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let names: Vec<String> = (1..=1000).map(|i| format!("Row {i}")).collect();
+        let list = gtk::StringList::new(&names.iter().map(String::as_str).collect::<Vec<_>>());
+
+        let rows = RelmSignalListItemFactory::builder()
+            .launch(|item| {
+                item.downcast_ref::<gtk::StringObject>()
+                    .unwrap()
+                    .string()
+                    .to_string()
+            })
+            .forward(sender.input_sender(), |output| match output {
+                RowOutput::Remove(index) => AppMsg::Remove(index),
+            });
+
+        let widgets = view_output!();
+        let model = App {
+            model: list,
+            _rows: rows,
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>) {
+        match msg {
+            AppMsg::Remove(index) => {
+                self.model.remove(index.current_index() as u32);
+            }
+        }
+    }
+}
+
+fn main() {
+    let app = RelmApp::new("relm4.example.list_item_factory");
+    app.run::<App>(());
+}
@@ -1,9 +1,13 @@
+use std::collections::HashSet;
+
 use gtk::prelude::*;
 use relm4::{
     binding::{Binding, U8Binding},
     prelude::*,
     typed_view::{
-        column::{LabelColumn, RelmColumn, TypedColumnView},
+        column::{
+            CellEdited, EditableColumn, LabelColumn, RelmColumn, SelectionColumn, TypedColumnView,
+        },
         OrdFn,
     },
     RelmObjectExt,
@@ -66,6 +70,39 @@ impl RelmColumn for Label2Column {
     }
 }
 
+struct ValueColumn;
+
+impl EditableColumn for ValueColumn {
+    type Item = MyListItem;
+    type Value = f64;
+    type Editor = gtk::SpinButton;
+
+    const COLUMN_NAME: &'static str = "editable value";
+
+    fn get_cell_value(item: &Self::Item) -> Self::Value {
+        f64::from(item.value)
+    }
+
+    fn set_cell_value(item: &mut Self::Item, value: Self::Value) {
+        item.value = value as u8;
+    }
+
+    fn create_editor() -> Self::Editor {
+        gtk::SpinButton::with_range(0.0, 255.0, 1.0)
+    }
+}
+
+struct SelectColumn;
+
+impl SelectionColumn for SelectColumn {
+    type Item = MyListItem;
+    type Key = u8;
+
+    fn key(item: &Self::Item) -> Self::Key {
+        item.value
+    }
+}
+
 struct ButtonColumn;
 
 impl RelmColumn for ButtonColumn {
@@ -95,6 +132,8 @@ enum Msg {
     Append,
     Remove,
     OnlyShowEven(bool),
+    Edited(CellEdited<f64>),
+    Selected(HashSet<u8>),
 }
 
 #[relm4::component]
@@ -130,6 +169,11 @@ impl SimpleComponent for App {
                     }
                 },
 
+                #[local_ref]
+                select_all -> gtk::CheckButton {
+                    set_label: Some("Select all"),
+                },
+
                 gtk::ScrolledWindow {
                     set_vexpand: true,
 
@@ -147,8 +191,12 @@ impl SimpleComponent for App {
     ) -> ComponentParts<Self> {
         // Initialize the ListView wrapper
         let mut view_wrapper = TypedColumnView::<MyListItem, gtk::SingleSelection>::new();
+        let selection = view_wrapper
+            .append_selection_column::<SelectColumn, _, _>(sender.input_sender(), Msg::Selected);
         view_wrapper.append_column::<Label1Column>();
         view_wrapper.append_column::<Label2Column>();
+        view_wrapper
+            .append_editable_column::<ValueColumn, _, _>(sender.input_sender(), Msg::Edited);
         view_wrapper.append_column::<ButtonColumn>();
 
         // Add a filter and disable it
@@ -161,6 +209,7 @@ impl SimpleComponent for App {
         };
 
         let my_view = &model.view_wrapper.view;
+        let select_all = selection.header_check_button();
 
         let widgets = view_output!();
 
@@ -190,6 +239,12 @@ impl SimpleComponent for App {
                 // Disable or enable the first filter
                 self.view_wrapper.set_filter_status(0, show_only_even);
             }
+            Msg::Edited(CellEdited { row, column, value }) => {
+                println!("Row {row} of column \"{column}\" was changed to {value}");
+            }
+            Msg::Selected(selected) => {
+                println!("Selected values: {selected:?}");
+            }
         }
     }
 }
@@ -37,6 +37,8 @@ impl RelmListItem for MyListItem {
     type Root = gtk::Box;
     type Widgets = Widgets;
 
+    const EXPANDABLE: bool = true;
+
     fn setup(_item: &gtk::ListItem) -> (gtk::Box, Widgets) {
         relm4::view! {
             my_box = gtk::Box {
@@ -71,6 +73,12 @@ impl RelmListItem for MyListItem {
         label2.add_write_only_binding(&self.binding, "label");
         button.set_active(self.value % 2 == 0);
     }
+
+    fn detail_widget(&mut self) -> Option<gtk::Widget> {
+        let label = gtk::Label::new(Some(&format!("Details of item {}", self.value)));
+        label.set_margin_all(5);
+        Some(label.upcast())
+    }
 }
 
 struct App {
@@ -137,6 +145,9 @@ impl SimpleComponent for App {
         let mut list_view_wrapper: TypedListView<MyListItem, gtk::SingleSelection> =
             TypedListView::with_sorting();
 
+        // Only show the details of one item at a time
+        list_view_wrapper.set_exclusive_expansion(true);
+
         // Add a filter and disable it
         list_view_wrapper.add_filter(|item| item.value % 2 == 0);
         list_view_wrapper.set_filter_status(0, false);
@@ -14,4 +14,5 @@ cargo test
 # Publish and pass all arguments to cargo
 cargo publish -p relm4-macros
 cargo publish -p relm4
-cargo publish -p relm4-components
\ No newline at end of file
+cargo publish -p relm4-components
+cargo publish -p relm4-cli
\ No newline at end of file
@@ -0,0 +1,426 @@
+use std::fs;
+use std::path::Path;
+
+use crate::Error;
+
+/// The kind of component to generate.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Kind {
+    pub(crate) is_async: bool,
+    pub(crate) factory: bool,
+}
+
+const COMPONENT: &str = r#"use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+#[derive(Debug)]
+pub struct {Name} {
+    counter: u8,
+}
+
+#[derive(Debug)]
+pub enum {Name}Msg {
+    Increment,
+}
+
+#[derive(Debug)]
+pub enum {Name}Output {
+    Changed(u8),
+}
+
+#[relm4::component(pub)]
+impl Component for {Name} {
+    type Init = u8;
+    type Input = {Name}Msg;
+    type Output = {Name}Output;
+    type CommandOutput = ();
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 5,
+
+            gtk::Label {
+                #[watch]
+                set_label: &model.counter.to_string(),
+            },
+
+            gtk::Button {
+                set_label: "Increment",
+                connect_clicked => {Name}Msg::Increment,
+            },
+        }
+    }
+
+    fn init(
+        counter: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = {Name} { counter };
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        match msg {
+            {Name}Msg::Increment => {
+                self.counter = self.counter.wrapping_add(1);
+                sender.output({Name}Output::Changed(self.counter)).ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use relm4::test::MockComponentSender;
+    use relm4::{gtk, Component};
+
+    use super::{{Name}, {Name}Msg, {Name}Output};
+
+    #[test]
+    fn increment() {
+        gtk::init().unwrap();
+        let mock = MockComponentSender::<{Name}>::new();
+        let root = {Name}::init_root();
+        let mut model = {Name} { counter: 0 };
+
+        model.update({Name}Msg::Increment, mock.sender(), &root);
+        assert_eq!(model.counter, 1);
+        assert!(matches!(mock.outputs()[..], [{Name}Output::Changed(1)]));
+    }
+}
+"#;
+
+const ASYNC_COMPONENT: &str = r#"use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+#[derive(Debug)]
+pub struct {Name} {
+    counter: u8,
+}
+
+#[derive(Debug)]
+pub enum {Name}Msg {
+    Increment,
+}
+
+#[derive(Debug)]
+pub enum {Name}Output {
+    Changed(u8),
+}
+
+#[relm4::component(pub async)]
+impl AsyncComponent for {Name} {
+    type Init = u8;
+    type Input = {Name}Msg;
+    type Output = {Name}Output;
+    type CommandOutput = ();
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 5,
+
+            gtk::Label {
+                #[watch]
+                set_label: &model.counter.to_string(),
+            },
+
+            gtk::Button {
+                set_label: "Increment",
+                connect_clicked => {Name}Msg::Increment,
+            },
+        }
+    }
+
+    async fn init(
+        counter: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        let model = {Name} { counter };
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(
+        &mut self,
+        msg: Self::Input,
+        sender: AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            {Name}Msg::Increment => {
+                self.counter = self.counter.wrapping_add(1);
+                sender.output({Name}Output::Changed(self.counter)).ok();
+            }
+        }
+    }
+}
+"#;
+
+const FACTORY: &str = r#"use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+#[derive(Debug)]
+pub struct {Name} {
+    counter: u8,
+    index: DynamicIndex,
+}
+
+#[derive(Debug)]
+pub enum {Name}Msg {
+    Increment,
+}
+
+#[derive(Debug)]
+pub enum {Name}Output {
+    Changed(DynamicIndex, u8),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for {Name} {
+    type Init = u8;
+    type Input = {Name}Msg;
+    type Output = {Name}Output;
+    type CommandOutput = ();
+    type ParentWidget = gtk::Box;
+
+    view! {
+        gtk::Box {
+            set_spacing: 5,
+
+            gtk::Label {
+                #[watch]
+                set_label: &self.counter.to_string(),
+            },
+
+            gtk::Button {
+                set_label: "Increment",
+                connect_clicked => {Name}Msg::Increment,
+            },
+        }
+    }
+
+    fn init_model(counter: Self::Init, index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
+        Self {
+            counter,
+            index: index.clone(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: FactorySender<Self>) {
+        match msg {
+            {Name}Msg::Increment => {
+                self.counter = self.counter.wrapping_add(1);
+                sender.output({Name}Output::Changed(self.index.clone(), self.counter)).ok();
+            }
+        }
+    }
+}
+"#;
+
+const ASYNC_FACTORY: &str = r#"use relm4::factory::{AsyncFactoryComponent, AsyncFactorySender};
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+#[derive(Debug)]
+pub struct {Name} {
+    counter: u8,
+    index: DynamicIndex,
+}
+
+#[derive(Debug)]
+pub enum {Name}Msg {
+    Increment,
+}
+
+#[derive(Debug)]
+pub enum {Name}Output {
+    Changed(DynamicIndex, u8),
+}
+
+#[relm4::factory(pub async)]
+impl AsyncFactoryComponent for {Name} {
+    type Init = u8;
+    type Input = {Name}Msg;
+    type Output = {Name}Output;
+    type CommandOutput = ();
+    type ParentWidget = gtk::Box;
+
+    view! {
+        gtk::Box {
+            set_spacing: 5,
+
+            gtk::Label {
+                #[watch]
+                set_label: &self.counter.to_string(),
+            },
+
+            gtk::Button {
+                set_label: "Increment",
+                connect_clicked => {Name}Msg::Increment,
+            },
+        }
+    }
+
+    async fn init_model(
+        counter: Self::Init,
+        index: &DynamicIndex,
+        _sender: AsyncFactorySender<Self>,
+    ) -> Self {
+        Self {
+            counter,
+            index: index.clone(),
+        }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncFactorySender<Self>) {
+        match msg {
+            {Name}Msg::Increment => {
+                self.counter = self.counter.wrapping_add(1);
+                sender.output({Name}Output::Changed(self.index.clone(), self.counter)).ok();
+            }
+        }
+    }
+}
+"#;
+
+/// Adds the component `name` to the crate in the current directory.
+pub(crate) fn run(name: &str, kind: Kind) -> Result<(), Error> {
+    if !is_type_name(name) {
+        return Err(Error::Usage(format!(
+            "`{name}` isn't a valid type name, use CamelCase"
+        )));
+    }
+
+    let module = to_snake_case(name);
+    let src = Path::new("src");
+    let crate_root = ["main.rs", "lib.rs"]
+        .into_iter()
+        .map(|file| src.join(file))
+        .find(|path| path.exists())
+        .ok_or_else(|| {
+            Error::Usage("No `src/main.rs` or `src/lib.rs` in the current directory".to_owned())
+        })?;
+
+    let path = src.join(format!("{module}.rs"));
+    if path.exists() {
+        return Err(Error::Usage(format!("`{}` already exists", path.display())));
+    }
+    fs::write(&path, render(name, kind))?;
+
+    let root = fs::read_to_string(&crate_root)?;
+    fs::write(&crate_root, add_module(&root, &module))?;
+
+    println!(
+        "Created `{}` and added it to `{}`",
+        path.display(),
+        crate_root.display()
+    );
+    Ok(())
+}
+
+fn render(name: &str, kind: Kind) -> String {
+    let template = match (kind.is_async, kind.factory) {
+        (false, false) => COMPONENT,
+        (true, false) => ASYNC_COMPONENT,
+        (false, true) => FACTORY,
+        (true, true) => ASYNC_FACTORY,
+    };
+    template.replace("{Name}", name)
+}
+
+/// Declares the module after the last module declaration
+/// or before the first item of the crate root.
+fn add_module(root: &str, module: &str) -> String {
+    let declaration = format!("mod {module};");
+    if root.lines().any(|line| line.trim() == declaration) {
+        return root.to_owned();
+    }
+
+    let lines: Vec<&str> = root.lines().collect();
+    let position = lines
+        .iter()
+        .rposition(|line| line.starts_with("mod ") || line.starts_with("pub mod "))
+        .map_or(0, |position| position + 1);
+
+    let mut output = String::new();
+    for line in &lines[..position] {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push_str(&declaration);
+    output.push('\n');
+    if position == 0 {
+        output.push('\n');
+    }
+    for line in &lines[position..] {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut output = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                output.push('_');
+            }
+            output.push(c.to_ascii_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_module, is_type_name, render, to_snake_case, Kind};
+
+    #[test]
+    fn names() {
+        assert_eq!(to_snake_case("Sidebar"), "sidebar");
+        assert_eq!(to_snake_case("FileRow"), "file_row");
+        assert!(is_type_name("FileRow"));
+        assert!(!is_type_name("file_row"));
+    }
+
+    #[test]
+    fn module_wiring() {
+        let root = "mod header;\n\nfn main() {}\n";
+        assert_eq!(
+            add_module(root, "sidebar"),
+            "mod header;\nmod sidebar;\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            add_module("fn main() {}\n", "sidebar"),
+            "mod sidebar;\n\nfn main() {}\n"
+        );
+        // Modules are only declared once.
+        assert_eq!(add_module(root, "header"), root);
+    }
+
+    #[test]
+    fn templates() {
+        let kind = Kind {
+            is_async: true,
+            factory: true,
+        };
+        let output = render("FileRow", kind);
+        assert!(output.contains("impl AsyncFactoryComponent for FileRow"));
+        assert!(!output.contains("{Name}"));
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::Error;
+
+/// How often the sources are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The feature of Relm4 that enables live reloading.
+const FEATURE: &str = "relm4/live_reload";
+
+/// Marks the start of a layout record in the executable.
+const RECORD_PREFIX: &[u8] = b"\0relm4-layout\0";
+
+/// Runs the application in the current directory and updates
+/// the layouts of its components whenever a source file changes.
+pub(crate) fn run(args: &[&str]) -> Result<(), Error> {
+    let src = Path::new("src");
+    if !src.is_dir() {
+        return Err(Error::Usage(
+            "No `src` directory in the current directory".to_owned(),
+        ));
+    }
+
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| "target".into(), PathBuf::from);
+    let dev_dir = std::env::current_dir()?.join(target).join("relm4-dev");
+    // The application writes the layouts of its build to missing files.
+    match fs::remove_dir_all(&dev_dir) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error.into()),
+        _ => (),
+    }
+
+    let mut app = Command::new(cargo())
+        .args(["run", "--features", FEATURE, "--"])
+        .args(args)
+        .env("RELM4_DEV_DIR", &dev_dir)
+        .spawn()?;
+
+    let mut modified = newest_modification(src);
+    loop {
+        if let Some(status) = app.try_wait()? {
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(Error::Io(std::io::Error::other(format!(
+                    "The application exited with {status}"
+                ))))
+            };
+        }
+
+        thread::sleep(POLL_INTERVAL);
+        let current = newest_modification(src);
+        if current > modified {
+            modified = current;
+            println!("Updating layouts");
+            update_layouts(&dev_dir)?;
+        }
+    }
+}
+
+/// Builds the application and writes the layouts of the new executable.
+fn update_layouts(dev_dir: &Path) -> Result<(), Error> {
+    // Errors are printed by cargo, the application keeps running anyway.
+    let output = Command::new(cargo())
+        .args([
+            "build",
+            "--quiet",
+            "--message-format=json",
+            "--features",
+            FEATURE,
+        ])
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    for executable in output
+        .stdout
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| executable(&line))
+    {
+        let binary = fs::read(executable)?;
+        for (component, layout) in layout_records(&binary) {
+            let path = dev_dir.join(format!("{}.layout", component.replace("::", ".")));
+            // Rewriting unchanged files would reload the layout.
+            if fs::read(&path).ok().as_deref() != Some(layout.as_bytes()) {
+                fs::create_dir_all(dev_dir)?;
+                fs::write(path, layout)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the path of the executable of a compiler artifact message of cargo.
+fn executable(message: &str) -> Option<PathBuf> {
+    if !message.contains(r#""reason":"compiler-artifact""#) {
+        return None;
+    }
+
+    let start = message.find(r#""executable":""#)? + r#""executable":""#.len();
+    let mut path = String::new();
+    let mut chars = message[start..].chars();
+    loop {
+        match chars.next()? {
+            '"' => return Some(path.into()),
+            '\\' => path.push(chars.next()?),
+            c => path.push(c),
+        }
+    }
+}
+
+/// Returns the path of the components and their layouts
+/// that the component macro embedded in an executable.
+fn layout_records(binary: &[u8]) -> Vec<(String, String)> {
+    let mut records = Vec::new();
+    let mut rest = binary;
+    while let Some(start) = find(rest, RECORD_PREFIX) {
+        rest = &rest[start + RECORD_PREFIX.len()..];
+        let mut fields = rest.splitn(3, |&byte| byte == 0);
+        let (Some(component), Some(layout), Some(_)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            break;
+        };
+        // Skip other data that happens to contain the prefix.
+        let is_path = !component.is_empty()
+            && component
+                .iter()
+                .all(|&byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b':');
+        if let (true, Ok(component), Ok(layout)) = (
+            is_path,
+            std::str::from_utf8(component),
+            std::str::from_utf8(layout),
+        ) {
+            records.push((component.to_owned(), layout.to_owned()));
+        }
+    }
+    records
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn cargo() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned())
+}
+
+/// Returns the time of the latest modification of the files in `dir`.
+fn newest_modification(dir: &Path) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                newest_modification(&path)
+            } else {
+                entry.metadata().and_then(|meta| meta.modified()).ok()
+            }
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn records() {
+        let binary = b"\x7fELF\0relm4-layout\0app::Counter\0label\tlabel\ts:Hi\n\0\x01\0relm4-layout\0app::Row\0\0\0relm4-layout\0\x02\0";
+        assert_eq!(
+            super::layout_records(binary),
+            [
+                ("app::Counter".to_owned(), "label\tlabel\ts:Hi\n".to_owned()),
+                ("app::Row".to_owned(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn artifact_executable() {
+        let message =
+            r#"{"reason":"compiler-artifact","executable":"C:\\app\\app.exe","fresh":false}"#;
+        assert_eq!(
+            super::executable(message),
+            Some(PathBuf::from(r"C:\app\app.exe"))
+        );
+        let message = r#"{"reason":"compiler-artifact","executable":null}"#;
+        assert_eq!(super::executable(message), None);
+        let message = r#"{"reason":"build-finished","success":true}"#;
+        assert_eq!(super::executable(message), None);
+    }
+
+    #[test]
+    fn modification() {
+        let dir = std::env::temp_dir().join(format!("relm4-cli-dev-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        assert_eq!(super::newest_modification(&dir), None);
+
+        fs::write(dir.join("nested/main.rs"), "fn main() {}").unwrap();
+        let modified = fs::metadata(dir.join("nested/main.rs"))
+            .and_then(|meta| meta.modified())
+            .ok();
+        assert_eq!(super::newest_modification(&dir), modified);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
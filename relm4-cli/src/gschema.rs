@@ -0,0 +1,101 @@
+use std::fmt::Write;
+
+use crate::Error;
+
+/// Renders a GSettings schema with the given keys.
+///
+/// Each key has the format `<name>:<type>[=<default>]`.
+/// Without default, the default value of the type is used.
+pub(crate) fn run(id: &str, keys: &[&str]) -> Result<String, Error> {
+    if id.is_empty() || id.split('.').any(str::is_empty) {
+        return Err(Error::Usage(format!("Invalid schema id `{id}`")));
+    }
+
+    let path = format!("/{}/", id.replace('.', "/"));
+    let mut output = String::new();
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(output, "<schemalist>").unwrap();
+    writeln!(output, r#"  <schema id="{id}" path="{path}">"#).unwrap();
+
+    for key in keys {
+        let (name, rest) = key
+            .split_once(':')
+            .ok_or_else(|| Error::Usage(format!("Missing type of key `{key}`")))?;
+        let (ty, default) = match rest.split_once('=') {
+            Some((ty, default)) => (ty, Some(default)),
+            None => (rest, None),
+        };
+
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(Error::Usage(format!(
+                "Invalid key name `{name}`, use lowercase letters, digits and dashes"
+            )));
+        }
+
+        let (signature, default) = match (ty, default) {
+            ("bool", default) => ("b", default.unwrap_or("false").to_owned()),
+            ("i32", default) => ("i", default.unwrap_or("0").to_owned()),
+            ("u32", default) => ("u", default.unwrap_or("0").to_owned()),
+            ("i64", default) => ("x", default.unwrap_or("0").to_owned()),
+            ("f64", default) => ("d", default.unwrap_or("0.0").to_owned()),
+            ("string", default) => ("s", quote(default.unwrap_or_default())),
+            ("strv", default) => ("as", strv(default.unwrap_or_default())),
+            _ => return Err(Error::Usage(format!("Unknown type `{ty}` of key `{name}`"))),
+        };
+
+        writeln!(output, r#"    <key name="{name}" type="{signature}">"#).unwrap();
+        writeln!(output, "      <default>{}</default>", escape(&default)).unwrap();
+        writeln!(output, "    </key>").unwrap();
+    }
+
+    writeln!(output, "  </schema>").unwrap();
+    writeln!(output, "</schemalist>").unwrap();
+    Ok(output)
+}
+
+/// Formats a string as GVariant text.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Formats a comma separated list as GVariant string array.
+fn strv(value: &str) -> String {
+    let items: Vec<String> = value
+        .split(',')
+        .filter(|item| !item.is_empty())
+        .map(quote)
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn schema() {
+        let schema = super::run(
+            "com.example.App",
+            &["dark-mode:bool", "title:string=It's", "tags:strv=a,b"],
+        )
+        .unwrap();
+
+        assert!(schema.contains(r#"<schema id="com.example.App" path="/com/example/App/">"#));
+        assert!(schema.contains(r#"<key name="dark-mode" type="b">"#));
+        assert!(schema.contains("<default>false</default>"));
+        assert!(schema.contains(r"<default>'It\'s'</default>"));
+        assert!(schema.contains("<default>['a', 'b']</default>"));
+
+        assert!(super::run("com.example.App", &["Dark:bool"]).is_err());
+        assert!(super::run("com.example.App", &["dark:float"]).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! Scaffolding for Relm4 projects as cargo subcommand.
+//!
+//! - `cargo relm new <name>` creates a new application.
+//! - `cargo relm add component <Name> [--async] [--factory]` adds a component
+//!   to the application in the current directory.
+//! - `cargo relm gschema <schema-id> <key>:<type>[=<default>]...` prints a GSettings schema.
+//! - `cargo relm dev [-- <args>...]` runs the application in the current directory
+//!   and applies changes of `view!` layouts while it's running.
+
+#![warn(
+    missing_debug_implementations,
+    missing_docs,
+    rust_2018_idioms,
+    unreachable_pub,
+    unused_qualifications,
+    clippy::cargo,
+    clippy::must_use_candidate
+)]
+
+mod component;
+mod dev;
+mod gschema;
+mod new;
+
+use std::fmt;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Scaffolding for Relm4 projects
+
+Usage:
+    cargo relm new <name>
+    cargo relm add component <Name> [--async] [--factory]
+    cargo relm gschema <schema-id> <key>:<type>[=<default>]...
+    cargo relm dev [-- <args>...]
+
+Types of settings: bool, i32, u32, i64, f64, string, strv";
+
+/// An error of a subcommand.
+#[derive(Debug)]
+enum Error {
+    /// The arguments are invalid.
+    Usage(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(message) => write!(f, "{message}\n\n{USAGE}"),
+            Self::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // Cargo passes the name of the subcommand as first argument.
+    if args.first().is_some_and(|arg| arg == "relm") {
+        args.remove(0);
+    }
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["new", name] => new::run(name),
+        ["add", "component", name, flags @ ..] => {
+            let mut kind = component::Kind::default();
+            for flag in flags {
+                match *flag {
+                    "--async" => kind.is_async = true,
+                    "--factory" => kind.factory = true,
+                    _ => return Err(Error::Usage(format!("Unknown flag `{flag}`"))),
+                }
+            }
+            component::run(name, kind)
+        }
+        ["gschema", id, keys @ ..] => {
+            print!("{}", gschema::run(id, keys)?);
+            Ok(())
+        }
+        ["dev"] => dev::run(&[]),
+        ["dev", "--", args @ ..] => dev::run(args),
+        [] | ["help" | "--help" | "-h"] => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(Error::Usage("Invalid arguments".to_owned())),
+    }
+}
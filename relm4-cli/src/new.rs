@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::Path;
+
+use crate::Error;
+
+const CARGO_TOML: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+relm4 = "{version}"
+"#;
+
+const MAIN_RS: &str = r#"use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+struct App {
+    counter: u8,
+}
+
+#[derive(Debug)]
+enum AppMsg {
+    Increment,
+    Decrement,
+}
+
+#[relm4::component]
+impl SimpleComponent for App {
+    type Init = u8;
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("{name}"),
+            set_default_size: (300, 100),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+                set_margin_all: 5,
+
+                gtk::Button {
+                    set_label: "Increment",
+                    connect_clicked => AppMsg::Increment,
+                },
+
+                gtk::Button {
+                    set_label: "Decrement",
+                    connect_clicked => AppMsg::Decrement,
+                },
+
+                gtk::Label {
+                    #[watch]
+                    set_label: &format!("Counter: {}", model.counter),
+                    set_margin_all: 5,
+                }
+            }
+        }
+    }
+
+    fn init(
+        counter: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = App { counter };
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>) {
+        match msg {
+            AppMsg::Increment => self.counter = self.counter.wrapping_add(1),
+            AppMsg::Decrement => self.counter = self.counter.wrapping_sub(1),
+        }
+    }
+}
+
+fn main() {
+    let app = RelmApp::new("{app_id}");
+    app.run::<App>(0);
+}
+"#;
+
+const GITIGNORE: &str = "/target\n";
+
+/// Creates a new application in the directory `name`.
+pub(crate) fn run(name: &str) -> Result<(), Error> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::Usage(format!("Invalid package name `{name}`")));
+    }
+
+    let root = Path::new(name);
+    if root.exists() {
+        return Err(Error::Usage(format!("`{name}` already exists")));
+    }
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("Cargo.toml"), cargo_toml(name))?;
+    fs::write(root.join("src/main.rs"), main_rs(name))?;
+    fs::write(root.join(".gitignore"), GITIGNORE)?;
+
+    println!("Created application `{name}`");
+    Ok(())
+}
+
+fn cargo_toml(name: &str) -> String {
+    // Use the version of Relm4 this tool was released with.
+    let version = env!("CARGO_PKG_VERSION")
+        .rsplit_once('.')
+        .map_or(env!("CARGO_PKG_VERSION"), |(version, _)| version);
+    CARGO_TOML
+        .replace("{name}", name)
+        .replace("{version}", version)
+}
+
+fn main_rs(name: &str) -> String {
+    let app_id = format!("com.example.{}", name.replace('-', "_"));
+    MAIN_RS.replace("{name}", name).replace("{app_id}", &app_id)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn templates() {
+        let cargo_toml = super::cargo_toml("my-app");
+        assert!(cargo_toml.contains("name = \"my-app\""));
+        assert!(cargo_toml.contains("relm4 = \"0.9\""));
+
+        let main_rs = super::main_rs("my-app");
+        assert!(main_rs.contains("RelmApp::new(\"com.example.my_app\")"));
+        // The format string of the label must not be replaced.
+        assert!(main_rs.contains("format!(\"Counter: {}\", model.counter)"));
+    }
+}
@@ -0,0 +1,88 @@
+use gtk::prelude::*;
+use relm4::{
+    gtk, Component, ComponentController, ComponentParts, ComponentSender, Controller, RelmApp,
+    SimpleComponent,
+};
+use relm4_components::map_view::{
+    Coordinate, MapMarker, MapMsg, MapOutput, MapSettings, MapView, TileCache,
+};
+
+#[derive(Debug)]
+enum AppMsg {
+    ZoomIn,
+    ZoomOut,
+    MarkerClicked(usize),
+}
+
+struct App {
+    map: Controller<MapView>,
+}
+
+#[relm4::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::ApplicationWindow {
+            set_default_size: (800, 600),
+
+            #[wrap(Some)]
+            set_titlebar = &gtk::HeaderBar {
+                pack_start = &gtk::Button {
+                    set_icon_name: "zoom-in-symbolic",
+                    connect_clicked => AppMsg::ZoomIn,
+                },
+                pack_start = &gtk::Button {
+                    set_icon_name: "zoom-out-symbolic",
+                    connect_clicked => AppMsg::ZoomOut,
+                }
+            },
+
+            #[local_ref]
+            map -> relm4::abstractions::SnapshotArea {}
+        }
+    }
+
+    fn update(&mut self, msg: Self::Input, _: ComponentSender<Self>) {
+        match msg {
+            AppMsg::ZoomIn => self.map.emit(MapMsg::ZoomIn),
+            AppMsg::ZoomOut => self.map.emit(MapMsg::ZoomOut),
+            AppMsg::MarkerClicked(id) => println!("Marker {id} was clicked"),
+        }
+    }
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let berlin = Coordinate::new(52.52, 13.405);
+        let map = MapView::builder()
+            .launch(MapSettings {
+                user_agent: "relm4-map-example".into(),
+                cache: Some(TileCache::new(std::env::temp_dir().join("relm4-map-tiles"))),
+                center: berlin,
+                zoom: 10,
+                ..MapSettings::default()
+            })
+            .forward_filtered(sender.input_sender(), |output| match output {
+                MapOutput::MarkerClicked(id) => Some(AppMsg::MarkerClicked(id)),
+                MapOutput::Moved { .. } => None,
+            });
+        map.emit(MapMsg::AddMarker(MapMarker::new(0, berlin)));
+
+        let model = App { map };
+
+        let map = model.map.widget();
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+}
+
+fn main() {
+    let app = RelmApp::new("relm4.example.map_view");
+    app.run::<App>(());
+}
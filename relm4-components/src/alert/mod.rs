@@ -4,7 +4,7 @@
 
 use gtk::prelude::{BoxExt, ButtonExt, GtkWindowExt, OrientableExt, WidgetExt};
 use once_cell::sync::Lazy;
-use relm4::{gtk, Component, ComponentParts, ComponentSender, RelmWidgetExt};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, MainWindowRegistry, RelmWidgetExt};
 
 const LIBADWAITA_ENABLED: bool = cfg!(feature = "libadwaita");
 const COMPONENT_CSS: &str = include_str!("style.css");
@@ -249,7 +249,7 @@ impl Component for Alert {
         widgets: &mut Self::Widgets,
         input: AlertMsg,
         sender: ComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         // Update the view to contain the extra component, by removing whatever's present in the UI and then adding what the caller's current widget is.
         if let Some(widget) = self.current_child.take() {
@@ -262,7 +262,10 @@ impl Component for Alert {
         }
 
         match input {
-            AlertMsg::Show => self.is_active = true,
+            AlertMsg::Show => {
+                MainWindowRegistry::set_transient_parent(root);
+                self.is_active = true;
+            }
             AlertMsg::Hide => self.is_active = false,
             AlertMsg::Response(resp) => {
                 self.is_active = false;
@@ -0,0 +1,472 @@
+//! A searchable history of the clipboard.
+//!
+//! The history is a [`gtk::Popover`] that lists recently copied texts and images.
+//! Recording is opt-in: nothing is stored until it's enabled with
+//! [`ClipboardHistorySettings::recording`] or [`ClipboardHistoryMsg::SetRecording`].
+//! The history is only kept in memory.
+//!
+//! Contents that password managers mark as sensitive with the
+//! `x-kde-passwordManagerHint` type are never recorded.
+//! Further contents can be excluded with [`ClipboardHistorySettings::exclude`].
+
+use std::fmt;
+use std::rc::Rc;
+
+use gtk::prelude::{
+    BoxExt, DisplayExt, EditableExt, ListModelExt, ObjectExt, PopoverExt, RootExt, StaticType,
+    TextureExt, WidgetExt,
+};
+use relm4::gtk::{gdk, glib};
+use relm4::typed_view::list::{RelmListItem, TypedListView};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// The type that password managers use to mark secrets on the clipboard.
+const PASSWORD_HINT: &str = "x-kde-passwordManagerHint";
+
+/// The size of image thumbnails in pixels.
+const THUMBNAIL_SIZE: i32 = 48;
+
+/// A text or image on the clipboard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardContent {
+    /// A text.
+    Text(String),
+    /// An image.
+    Image(gdk::Texture),
+}
+
+impl ClipboardContent {
+    /// The approximate size of the content in memory.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Text(text) => text.len(),
+            Self::Image(texture) => texture.width() as usize * texture.height() as usize * 4,
+        }
+    }
+}
+
+/// A function that returns `true` for contents that must not be recorded.
+pub type ExcludeFn = Rc<dyn Fn(&ClipboardContent) -> bool>;
+
+/// Configuration for the clipboard history component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `recording` is set to [`false`].
+/// - `record_images` is set to [`true`].
+/// - `max_entries` is set to 30.
+/// - `max_text_size` is set to 64 KiB.
+/// - `max_image_size` is set to 16 MiB.
+/// - `exclude` is set to [`None`].
+#[derive(Clone)]
+pub struct ClipboardHistorySettings {
+    /// Record the clipboard right away.
+    pub recording: bool,
+    /// Record images in addition to texts.
+    pub record_images: bool,
+    /// The number of entries that are kept. Older entries are removed first.
+    pub max_entries: usize,
+    /// Texts with more bytes aren't recorded.
+    pub max_text_size: usize,
+    /// Images that need more bytes in memory aren't recorded.
+    pub max_image_size: usize,
+    /// Excludes sensitive contents, like text that looks like a token.
+    pub exclude: Option<ExcludeFn>,
+}
+
+impl fmt::Debug for ClipboardHistorySettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClipboardHistorySettings")
+            .field("recording", &self.recording)
+            .field("record_images", &self.record_images)
+            .field("max_entries", &self.max_entries)
+            .field("max_text_size", &self.max_text_size)
+            .field("max_image_size", &self.max_image_size)
+            .field("exclude", &self.exclude.is_some())
+            .finish()
+    }
+}
+
+impl Default for ClipboardHistorySettings {
+    fn default() -> Self {
+        Self {
+            recording: false,
+            record_images: true,
+            max_entries: 30,
+            max_text_size: 64 * 1024,
+            max_image_size: 16 * 1024 * 1024,
+            exclude: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the clipboard history component.
+#[derive(Debug)]
+pub enum ClipboardHistoryMsg {
+    /// Show the history.
+    Popup,
+    /// Hide the history.
+    Popdown,
+    /// Start or stop recording the clipboard.
+    SetRecording(bool),
+    /// Remove all entries.
+    Clear,
+    /// Filter the texts of the history.
+    Search(String),
+    #[doc(hidden)]
+    ClipboardChanged,
+    #[doc(hidden)]
+    Copied(ClipboardContent),
+    #[doc(hidden)]
+    Activate(u32),
+}
+
+/// An entry of the history that was pasted by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardPaste {
+    /// The pasted content, which is on the clipboard again.
+    pub content: ClipboardContent,
+}
+
+#[derive(Debug)]
+struct HistoryItem {
+    /// Index of the entry in the model.
+    index: usize,
+    text: Option<String>,
+    image: Option<gdk::Texture>,
+}
+
+/// The widgets of a row of the history.
+#[derive(Debug)]
+struct HistoryRow {
+    picture: gtk::Picture,
+    label: gtk::Label,
+}
+
+impl RelmListItem for HistoryItem {
+    type Root = gtk::Box;
+    type Widgets = HistoryRow;
+
+    fn setup(_item: &gtk::ListItem) -> (gtk::Box, HistoryRow) {
+        let root = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        let picture = gtk::Picture::builder()
+            .width_request(THUMBNAIL_SIZE)
+            .height_request(THUMBNAIL_SIZE)
+            .can_shrink(true)
+            .build();
+        let label = gtk::Label::builder()
+            .xalign(0.0)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .single_line_mode(true)
+            .max_width_chars(40)
+            .build();
+        root.append(&picture);
+        root.append(&label);
+        (root, HistoryRow { picture, label })
+    }
+
+    fn bind(&mut self, widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        widgets.picture.set_paintable(self.image.as_ref());
+        widgets.picture.set_visible(self.image.is_some());
+        match &self.text {
+            // Only show the first line, so rows have the same height.
+            Some(text) => widgets.label.set_label(
+                text.lines()
+                    .find(|line| !line.trim().is_empty())
+                    .unwrap_or(""),
+            ),
+            None => widgets.label.set_label("Image"),
+        }
+    }
+}
+
+/// The widgets of the clipboard history component.
+#[derive(Debug)]
+pub struct ClipboardHistoryWidgets {
+    search: gtk::SearchEntry,
+}
+
+/// Clipboard history component.
+///
+/// The component is a [`gtk::Popover`] that can be used with
+/// [`gtk::MenuButton::set_popover()`] or attached to any widget.
+/// Selecting an entry puts it back on the clipboard, pastes it into the
+/// focused widget and sends it as [`ClipboardPaste`].
+pub struct ClipboardHistory {
+    /// The recorded contents, most recent first.
+    entries: Vec<ClipboardContent>,
+    list: TypedListView<HistoryItem, gtk::NoSelection>,
+    clipboard: gdk::Clipboard,
+    changed_handler: Option<glib::SignalHandlerId>,
+    query: String,
+    record_images: bool,
+    max_entries: usize,
+    max_text_size: usize,
+    max_image_size: usize,
+    exclude: Option<ExcludeFn>,
+}
+
+impl fmt::Debug for ClipboardHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClipboardHistory")
+            .field("entries", &self.entries.len())
+            .field("recording", &self.changed_handler.is_some())
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Component for ClipboardHistory {
+    type CommandOutput = ();
+    type Input = ClipboardHistoryMsg;
+    type Output = ClipboardPaste;
+    type Init = ClipboardHistorySettings;
+    type Root = gtk::Popover;
+    type Widgets = ClipboardHistoryWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Popover::new()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        root.set_child(Some(&content));
+
+        let search = gtk::SearchEntry::new();
+        let input = sender.input_sender().clone();
+        search.connect_search_changed(move |entry| {
+            input.emit(ClipboardHistoryMsg::Search(entry.text().into()));
+        });
+        content.append(&search);
+
+        let list = TypedListView::new();
+        list.view.set_single_click_activate(true);
+        let input = sender.input_sender().clone();
+        list.view.connect_activate(move |_, position| {
+            input.emit(ClipboardHistoryMsg::Activate(position));
+        });
+
+        let placeholder = gtk::Label::builder()
+            .label("Nothing copied yet")
+            .css_classes(["dim-label"])
+            .vexpand(true)
+            .build();
+        let stack = gtk::Stack::new();
+        let scrolled = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .min_content_height(300)
+            .propagate_natural_width(true)
+            .child(&list.view)
+            .build();
+        stack.add_named(&placeholder, Some("empty"));
+        stack.add_named(&scrolled, Some("list"));
+        content.append(&stack);
+        list.view
+            .model()
+            .expect("the list view has a model")
+            .connect_items_changed(move |model, _, _, _| {
+                stack.set_visible_child_name(if model.n_items() == 0 {
+                    "empty"
+                } else {
+                    "list"
+                });
+            });
+
+        let clipboard = root.display().clipboard();
+
+        let mut model = Self {
+            entries: Vec::new(),
+            list,
+            clipboard,
+            changed_handler: None,
+            query: String::new(),
+            record_images: settings.record_images,
+            max_entries: settings.max_entries,
+            max_text_size: settings.max_text_size,
+            max_image_size: settings.max_image_size,
+            exclude: settings.exclude,
+        };
+        model.set_recording(settings.recording, &sender);
+
+        let widgets = ClipboardHistoryWidgets { search };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match input {
+            ClipboardHistoryMsg::Popup => {
+                widgets.search.set_text("");
+                root.popup();
+                widgets.search.grab_focus();
+            }
+            ClipboardHistoryMsg::Popdown => root.popdown(),
+            ClipboardHistoryMsg::SetRecording(recording) => {
+                self.set_recording(recording, &sender);
+            }
+            ClipboardHistoryMsg::Clear => {
+                self.entries.clear();
+                self.fill_list();
+            }
+            ClipboardHistoryMsg::Search(query) => {
+                self.query = query.to_lowercase();
+                self.apply_filter();
+            }
+            ClipboardHistoryMsg::ClipboardChanged => self.read_clipboard(&sender),
+            ClipboardHistoryMsg::Copied(content) => self.record(content),
+            ClipboardHistoryMsg::Activate(position) => {
+                let Some(item) = self.list.get_visible(position) else {
+                    return;
+                };
+                let index = item.borrow().index;
+                let Some(content) = self.entries.get(index).cloned() else {
+                    return;
+                };
+
+                match &content {
+                    ClipboardContent::Text(text) => self.clipboard.set_text(text),
+                    ClipboardContent::Image(texture) => self.clipboard.set_texture(texture),
+                }
+                root.popdown();
+
+                // Paste once the focus returned to the widget the popover was opened from.
+                if let Some(window) = root.root() {
+                    glib::idle_add_local_once(move || {
+                        if let Some(focus) = window.focus() {
+                            focus.activate_action("clipboard.paste", None).ok();
+                        }
+                    });
+                }
+                sender.output(ClipboardPaste { content }).ok();
+            }
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        if let Some(handler) = self.changed_handler.take() {
+            self.clipboard.disconnect(handler);
+        }
+    }
+}
+
+impl ClipboardHistory {
+    /// The recorded contents, most recent first.
+    #[must_use]
+    pub fn entries(&self) -> &[ClipboardContent] {
+        &self.entries
+    }
+
+    fn set_recording(&mut self, recording: bool, sender: &ComponentSender<Self>) {
+        if recording == self.changed_handler.is_some() {
+            return;
+        }
+        if let Some(handler) = self.changed_handler.take() {
+            self.clipboard.disconnect(handler);
+        } else {
+            let input = sender.input_sender().clone();
+            self.changed_handler = Some(
+                self.clipboard
+                    .connect_changed(move |_| input.emit(ClipboardHistoryMsg::ClipboardChanged)),
+            );
+        }
+    }
+
+    /// Read the clipboard asynchronously and record its content.
+    fn read_clipboard(&self, sender: &ComponentSender<Self>) {
+        let formats = self.clipboard.formats();
+        if formats.contain_mime_type(PASSWORD_HINT) {
+            return;
+        }
+
+        let clipboard = self.clipboard.clone();
+        let read_image = self.record_images && formats.contains_type(gdk::Texture::static_type());
+        let input = sender.input_sender().clone();
+        relm4::spawn_local(async move {
+            let content = if read_image {
+                clipboard
+                    .read_texture_future()
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(ClipboardContent::Image)
+            } else {
+                clipboard
+                    .read_text_future()
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|text| ClipboardContent::Text(text.into()))
+            };
+            if let Some(content) = content {
+                input.emit(ClipboardHistoryMsg::Copied(content));
+            }
+        });
+    }
+
+    fn record(&mut self, content: ClipboardContent) {
+        let max_size = match &content {
+            ClipboardContent::Text(text) if text.trim().is_empty() => return,
+            ClipboardContent::Text(_) => self.max_text_size,
+            ClipboardContent::Image(_) => self.max_image_size,
+        };
+        if content.size() > max_size
+            || self
+                .exclude
+                .as_ref()
+                .is_some_and(|exclude| exclude(&content))
+        {
+            return;
+        }
+
+        // Copying an entry again moves it to the top.
+        self.entries.retain(|entry| *entry != content);
+        self.entries.insert(0, content);
+        self.entries.truncate(self.max_entries);
+        self.fill_list();
+    }
+
+    fn fill_list(&mut self) {
+        self.list.clear();
+        let items = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| match entry {
+                ClipboardContent::Text(text) => HistoryItem {
+                    index,
+                    text: Some(text.clone()),
+                    image: None,
+                },
+                ClipboardContent::Image(texture) => HistoryItem {
+                    index,
+                    text: None,
+                    image: Some(texture.clone()),
+                },
+            });
+        self.list.extend_from_iter(items);
+    }
+
+    /// Only show texts that contain the search query.
+    fn apply_filter(&mut self) {
+        self.list.clear_filters();
+        if !self.query.is_empty() {
+            let query = self.query.clone();
+            self.list.add_filter(move |item| {
+                item.text
+                    .as_ref()
+                    .is_some_and(|text| text.to_lowercase().contains(&query))
+            });
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Reusable and easily configurable color picker component.
+//!
+//! The component combines a [`gtk::ColorDialogButton`] with a palette of
+//! predefined colors, a list of recently picked colors and an eyedropper
+//! that picks a color from the screen with the screenshot portal.
+
+use std::collections::VecDeque;
+
+use relm4::gtk::prelude::{BoxExt, ButtonExt, DrawingAreaExt, DrawingAreaExtManual, WidgetExt};
+use relm4::gtk::{gdk, gio, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, Sender};
+
+const SWATCH_SIZE: i32 = 20;
+
+/// Configuration for the color picker component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `color` is set to opaque black.
+/// - `palette` is empty.
+/// - `max_recent` is set to 8.
+/// - `with_alpha` is set to [`true`].
+/// - `eyedropper` is set to [`true`].
+/// - `title` is set to [`None`].
+#[derive(Debug, Clone)]
+pub struct ColorPickerSettings {
+    /// The initially selected color.
+    pub color: gdk::RGBA,
+    /// Predefined colors that are always shown.
+    pub palette: Vec<gdk::RGBA>,
+    /// The number of recently picked colors that are shown.
+    pub max_recent: usize,
+    /// Allow choosing transparent colors in the color dialog.
+    pub with_alpha: bool,
+    /// Show a button that picks a color from the screen.
+    pub eyedropper: bool,
+    /// Title of the color dialog.
+    pub title: Option<String>,
+}
+
+impl Default for ColorPickerSettings {
+    fn default() -> Self {
+        Self {
+            color: gdk::RGBA::BLACK,
+            palette: Vec::new(),
+            max_recent: 8,
+            with_alpha: true,
+            eyedropper: true,
+            title: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the color picker component.
+#[derive(Debug)]
+pub enum ColorPickerMsg {
+    /// Select a color without emitting an output.
+    SetColor(gdk::RGBA),
+    /// Replace the palette of predefined colors.
+    SetPalette(Vec<gdk::RGBA>),
+    /// Pick a color from the screen.
+    PickFromScreen,
+    #[doc(hidden)]
+    Picked(gdk::RGBA),
+}
+
+/// Color picker component.
+///
+/// The selected color is sent as output whenever the user picks a color.
+#[derive(Debug)]
+pub struct ColorPicker {
+    color: gdk::RGBA,
+    recent: VecDeque<gdk::RGBA>,
+    max_recent: usize,
+}
+
+/// The widgets of the color picker component.
+#[derive(Debug)]
+pub struct ColorPickerWidgets {
+    button: gtk::ColorDialogButton,
+    palette: gtk::Box,
+    recent: gtk::Box,
+}
+
+impl Component for ColorPicker {
+    type CommandOutput = ();
+    type Input = ColorPickerMsg;
+    type Output = gdk::RGBA;
+    type Init = ColorPickerSettings;
+    type Root = gtk::Box;
+    type Widgets = ColorPickerWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Horizontal, 6)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let dialog = gtk::ColorDialog::new();
+        dialog.set_with_alpha(settings.with_alpha);
+        if let Some(title) = &settings.title {
+            dialog.set_title(title);
+        }
+
+        let button = gtk::ColorDialogButton::new(Some(dialog));
+        button.set_rgba(&settings.color);
+        let input = sender.input_sender().clone();
+        button.connect_rgba_notify(move |button| {
+            input.emit(ColorPickerMsg::Picked(button.rgba()));
+        });
+        root.append(&button);
+
+        let palette = gtk::Box::new(gtk::Orientation::Horizontal, 2);
+        root.append(&palette);
+        let recent = gtk::Box::new(gtk::Orientation::Horizontal, 2);
+        root.append(&recent);
+
+        let eyedropper = gtk::Button::from_icon_name("color-select-symbolic");
+        eyedropper.set_tooltip_text(Some("Pick a color from the screen"));
+        eyedropper.set_visible(settings.eyedropper);
+        let input = sender.input_sender().clone();
+        eyedropper.connect_clicked(move |_| input.emit(ColorPickerMsg::PickFromScreen));
+        root.append(&eyedropper);
+
+        let model = Self {
+            color: settings.color,
+            recent: VecDeque::new(),
+            max_recent: settings.max_recent,
+        };
+        let widgets = ColorPickerWidgets {
+            button,
+            palette,
+            recent,
+        };
+        fill_swatches(&widgets.palette, &settings.palette, sender.input_sender());
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match input {
+            ColorPickerMsg::SetColor(color) => {
+                self.color = color;
+                widgets.button.set_rgba(&color);
+            }
+            ColorPickerMsg::SetPalette(palette) => {
+                fill_swatches(&widgets.palette, &palette, sender.input_sender());
+            }
+            ColorPickerMsg::PickFromScreen => {
+                let input = sender.input_sender().clone();
+                relm4::spawn_local(async move {
+                    match pick_color().await {
+                        Ok(Some(color)) => input.emit(ColorPickerMsg::Picked(color)),
+                        Ok(None) => (),
+                        Err(error) => tracing::error!("Cannot pick color: {error}"),
+                    }
+                });
+            }
+            ColorPickerMsg::Picked(color) => {
+                // Ignore the notification caused by `SetColor`.
+                if color == self.color {
+                    return;
+                }
+                self.color = color;
+                if widgets.button.rgba() != color {
+                    widgets.button.set_rgba(&color);
+                }
+
+                self.recent.retain(|recent| *recent != color);
+                self.recent.push_front(color);
+                self.recent.truncate(self.max_recent);
+                fill_swatches(
+                    &widgets.recent,
+                    self.recent.make_contiguous(),
+                    sender.input_sender(),
+                );
+
+                sender.output(color).ok();
+            }
+        }
+    }
+}
+
+impl ColorPicker {
+    /// The currently selected color.
+    #[must_use]
+    pub fn color(&self) -> gdk::RGBA {
+        self.color
+    }
+
+    /// The recently picked colors, starting with the most recent one.
+    #[must_use]
+    pub fn recent(&self) -> Vec<gdk::RGBA> {
+        self.recent.iter().copied().collect()
+    }
+}
+
+/// Replace the children of `container` with buttons for `colors`.
+fn fill_swatches(container: &gtk::Box, colors: &[gdk::RGBA], input: &Sender<ColorPickerMsg>) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+
+    for color in colors.iter().copied() {
+        let area = gtk::DrawingArea::new();
+        area.set_content_width(SWATCH_SIZE);
+        area.set_content_height(SWATCH_SIZE);
+        area.set_draw_func(move |_, context, width, height| {
+            context.set_source_rgba(
+                f64::from(color.red()),
+                f64::from(color.green()),
+                f64::from(color.blue()),
+                f64::from(color.alpha()),
+            );
+            context.rectangle(0.0, 0.0, f64::from(width), f64::from(height));
+            context.fill().ok();
+        });
+
+        let button = gtk::Button::new();
+        button.set_child(Some(&area));
+        button.add_css_class("flat");
+        button.set_tooltip_text(Some(&color.to_str()));
+        let input = input.clone();
+        button.connect_clicked(move |_| input.emit(ColorPickerMsg::Picked(color)));
+        container.append(&button);
+    }
+}
+
+/// Pick a color from the screen with the screenshot portal.
+///
+/// Returns [`None`] if the user canceled the operation.
+async fn pick_color() -> Result<Option<gdk::RGBA>, glib::Error> {
+    const PORTAL: &str = "org.freedesktop.portal.Desktop";
+
+    let connection = gio::bus_get_future(gio::BusType::Session).await?;
+
+    // Subscribe to the response before the call to avoid missing it.
+    let token = format!("relm4_{}", glib::random_int());
+    let unique_name = connection.unique_name().unwrap_or_default();
+    let request_path = format!(
+        "/org/freedesktop/portal/desktop/request/{}/{token}",
+        unique_name.trim_start_matches(':').replace('.', "_")
+    );
+
+    let (response_sender, response_receiver) = futures::channel::oneshot::channel();
+    let response_sender = std::cell::Cell::new(Some(response_sender));
+    let subscription = connection.signal_subscribe(
+        Some(PORTAL),
+        Some("org.freedesktop.portal.Request"),
+        Some("Response"),
+        Some(&request_path),
+        None,
+        gio::DBusSignalFlags::NONE,
+        move |_, _, _, _, _, parameters| {
+            if let Some(sender) = response_sender.take() {
+                sender.send(parameters.clone()).ok();
+            }
+        },
+    );
+
+    let options = glib::VariantDict::new(None);
+    options.insert("handle_token", &token);
+    let call = connection
+        .call_future(
+            Some(PORTAL),
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Screenshot",
+            "PickColor",
+            // The portal doesn't require a parent window.
+            Some(&("", options.end()).into()),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await;
+
+    let response = match call {
+        Ok(_) => response_receiver.await.ok(),
+        Err(error) => {
+            connection.signal_unsubscribe(subscription);
+            return Err(error);
+        }
+    };
+    connection.signal_unsubscribe(subscription);
+
+    let Some(response) = response else {
+        return Ok(None);
+    };
+    // A response code other than 0 means that the user canceled the request.
+    if response.child_value(0).get::<u32>() != Some(0) {
+        return Ok(None);
+    }
+
+    let color = glib::VariantDict::new(Some(&response.child_value(1)))
+        .lookup_value("color", None)
+        .and_then(|color| color.get::<(f64, f64, f64)>())
+        .map(|(red, green, blue)| gdk::RGBA::new(red as f32, green as f32, blue as f32, 1.0));
+    Ok(color)
+}
@@ -0,0 +1,420 @@
+//! Line based diffs with word level changes.
+
+use std::fmt;
+use std::ops::Range;
+
+use similar::{ChangeTag, TextDiff};
+
+/// Pairs of lines with less similarity than this aren't highlighted word by word.
+const MIN_SIMILARITY: f32 = 0.4;
+
+/// The kind of a [`DiffLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffLineKind {
+    /// The line is the same in both texts.
+    Context,
+    /// The line was added to the new text.
+    Added,
+    /// The line was removed from the old text.
+    Removed,
+}
+
+impl DiffLineKind {
+    /// The prefix of the line in a unified diff.
+    #[must_use]
+    pub fn sign(self) -> char {
+        match self {
+            Self::Context => ' ',
+            Self::Added => '+',
+            Self::Removed => '-',
+        }
+    }
+}
+
+/// A line of a [`DiffHunk`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    /// Whether the line was added, removed or is unchanged.
+    pub kind: DiffLineKind,
+    /// The line number in the old text, starting at 1.
+    pub old_line: Option<usize>,
+    /// The line number in the new text, starting at 1.
+    pub new_line: Option<usize>,
+    /// The text of the line without line ending.
+    pub text: String,
+    /// Byte ranges of [`Self::text`] that changed compared
+    /// to the corresponding line on the other side.
+    pub changes: Vec<Range<usize>>,
+}
+
+impl DiffLine {
+    fn context(old_line: usize, new_line: usize, text: impl Into<String>) -> Self {
+        Self {
+            kind: DiffLineKind::Context,
+            old_line: Some(old_line),
+            new_line: Some(new_line),
+            text: text.into(),
+            changes: Vec::new(),
+        }
+    }
+}
+
+/// A group of changed lines with some unchanged lines around them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// The first line of the hunk in the old text.
+    pub old_start: usize,
+    /// The number of lines of the hunk in the old text.
+    pub old_len: usize,
+    /// The first line of the hunk in the new text.
+    pub new_start: usize,
+    /// The number of lines of the hunk in the new text.
+    pub new_len: usize,
+    /// Text after the range information of the hunk header,
+    /// usually the enclosing function.
+    pub section: String,
+    /// The lines of the hunk.
+    pub lines: Vec<DiffLine>,
+}
+
+impl DiffHunk {
+    /// The header of the hunk, like `@@ -1,3 +1,4 @@`.
+    #[must_use]
+    pub fn header(&self) -> String {
+        let header = format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        );
+        if self.section.is_empty() {
+            header
+        } else {
+            format!("{header} {}", self.section)
+        }
+    }
+
+    /// The hunk in unified diff format, for example to apply it with `git apply --cached`.
+    #[must_use]
+    pub fn to_unified(&self) -> String {
+        let mut text = self.header();
+        text.push('\n');
+        for line in &self.lines {
+            text.push(line.kind.sign());
+            text.push_str(&line.text);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// A part of a [`Diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffSection {
+    /// Changed lines.
+    Hunk(DiffHunk),
+    /// Unchanged lines between hunks that can be expanded.
+    Unchanged {
+        /// The first line in the old text.
+        old_start: usize,
+        /// The first line in the new text.
+        new_start: usize,
+        /// The unchanged lines.
+        lines: Vec<String>,
+    },
+}
+
+/// A line based diff of two texts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+    sections: Vec<DiffSection>,
+}
+
+impl Diff {
+    /// Compare two texts line by line.
+    ///
+    /// Changes are grouped into hunks with up to `context_lines` unchanged
+    /// lines around them. The remaining unchanged lines are kept in
+    /// [`DiffSection::Unchanged`], so they can be expanded.
+    #[must_use]
+    pub fn from_texts(old: &str, new: &str, context_lines: usize) -> Self {
+        let diff = TextDiff::from_lines(old, new);
+        let old_lines = diff.old_slices();
+
+        let mut sections = Vec::new();
+        let mut old_pos = 0;
+        let mut new_pos = 0;
+        for group in diff.grouped_ops(context_lines) {
+            let (Some(first), Some(last)) = (group.first(), group.last()) else {
+                continue;
+            };
+            let (old_range, new_range) = (first.old_range(), first.new_range());
+            if old_range.start > old_pos {
+                sections.push(DiffSection::Unchanged {
+                    old_start: old_pos + 1,
+                    new_start: new_pos + 1,
+                    lines: old_lines[old_pos..old_range.start]
+                        .iter()
+                        .map(|line| trim_line_ending(line).to_owned())
+                        .collect(),
+                });
+            }
+
+            let mut lines = Vec::new();
+            for op in &group {
+                for change in diff.iter_changes(op) {
+                    let kind = match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Context,
+                        ChangeTag::Insert => DiffLineKind::Added,
+                        ChangeTag::Delete => DiffLineKind::Removed,
+                    };
+                    lines.push(DiffLine {
+                        kind,
+                        old_line: change.old_index().map(|index| index + 1),
+                        new_line: change.new_index().map(|index| index + 1),
+                        text: trim_line_ending(change.value()).to_owned(),
+                        changes: Vec::new(),
+                    });
+                }
+            }
+            highlight_changes(&mut lines);
+
+            old_pos = last.old_range().end;
+            new_pos = last.new_range().end;
+            let old_len = old_pos - old_range.start;
+            let new_len = new_pos - new_range.start;
+            sections.push(DiffSection::Hunk(DiffHunk {
+                // Empty ranges start at the line before them.
+                old_start: old_range.start + usize::from(old_len > 0),
+                old_len,
+                new_start: new_range.start + usize::from(new_len > 0),
+                new_len,
+                section: String::new(),
+                lines,
+            }));
+        }
+
+        if old_pos < old_lines.len() {
+            sections.push(DiffSection::Unchanged {
+                old_start: old_pos + 1,
+                new_start: new_pos + 1,
+                lines: old_lines[old_pos..]
+                    .iter()
+                    .map(|line| trim_line_ending(line).to_owned())
+                    .collect(),
+            });
+        }
+
+        Self { sections }
+    }
+
+    /// Parse the hunks of a diff in unified format, like the output of `git diff`.
+    ///
+    /// File headers and other lines outside of hunks are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hunk header is malformed.
+    pub fn parse_unified(text: &str) -> Result<Self, DiffParseError> {
+        let mut sections = Vec::new();
+        let mut hunk: Option<DiffHunk> = None;
+        // Lines of the current hunk that are still expected.
+        let (mut old_left, mut new_left) = (0, 0);
+        let (mut old_line, mut new_line) = (0, 0);
+
+        for (index, line) in text.lines().enumerate() {
+            if old_left == 0 && new_left == 0 {
+                if let Some(mut finished) = hunk.take() {
+                    highlight_changes(&mut finished.lines);
+                    sections.push(DiffSection::Hunk(finished));
+                }
+                if line.starts_with("@@") {
+                    let parsed = parse_header(line).ok_or_else(|| DiffParseError {
+                        line: index + 1,
+                        message: format!("invalid hunk header `{line}`"),
+                    })?;
+                    (old_left, new_left) = (parsed.old_len, parsed.new_len);
+                    (old_line, new_line) = (parsed.old_start, parsed.new_start);
+                    hunk = Some(parsed);
+                }
+                continue;
+            }
+
+            let Some(hunk) = hunk.as_mut() else {
+                continue;
+            };
+            let (kind, text) = match line.chars().next() {
+                // Some tools strip the space of empty context lines.
+                None => (DiffLineKind::Context, ""),
+                Some(' ') => (DiffLineKind::Context, &line[1..]),
+                Some('+') => (DiffLineKind::Added, &line[1..]),
+                Some('-') => (DiffLineKind::Removed, &line[1..]),
+                // "\ No newline at end of file"
+                Some('\\') => continue,
+                Some(_) => {
+                    return Err(DiffParseError {
+                        line: index + 1,
+                        message: "hunk is shorter than its header says".into(),
+                    })
+                }
+            };
+
+            let mut diff_line = DiffLine::context(old_line, new_line, text);
+            diff_line.kind = kind;
+            match kind {
+                DiffLineKind::Context => {
+                    (old_line, new_line) = (old_line + 1, new_line + 1);
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+                DiffLineKind::Added => {
+                    diff_line.old_line = None;
+                    new_line += 1;
+                    new_left = new_left.saturating_sub(1);
+                }
+                DiffLineKind::Removed => {
+                    diff_line.new_line = None;
+                    old_line += 1;
+                    old_left = old_left.saturating_sub(1);
+                }
+            }
+            hunk.lines.push(diff_line);
+        }
+
+        if let Some(mut finished) = hunk {
+            highlight_changes(&mut finished.lines);
+            sections.push(DiffSection::Hunk(finished));
+        }
+        Ok(Self { sections })
+    }
+
+    /// The hunks and unchanged parts of the diff.
+    #[must_use]
+    pub fn sections(&self) -> &[DiffSection] {
+        &self.sections
+    }
+
+    /// The hunks of the diff.
+    pub fn hunks(&self) -> impl Iterator<Item = &DiffHunk> {
+        self.sections.iter().filter_map(|section| match section {
+            DiffSection::Hunk(hunk) => Some(hunk),
+            DiffSection::Unchanged { .. } => None,
+        })
+    }
+
+    /// The hunk at the given index.
+    #[must_use]
+    pub fn hunk(&self, index: usize) -> Option<&DiffHunk> {
+        self.hunks().nth(index)
+    }
+
+    /// Returns true if the diff has no changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hunks().next().is_none()
+    }
+}
+
+/// Error returned by [`Diff::parse_unified()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for DiffParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DiffParseError {}
+
+/// Parse a hunk header like `@@ -1,3 +1,4 @@ fn main() {`.
+fn parse_header(line: &str) -> Option<DiffHunk> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, section) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_len) = parse_range(old)?;
+    let (new_start, new_len) = parse_range(new)?;
+
+    Some(DiffHunk {
+        old_start,
+        old_len,
+        new_start,
+        new_len,
+        section: section.trim().to_owned(),
+        lines: Vec::new(),
+    })
+}
+
+fn trim_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Compare runs of removed lines with the added lines that follow them
+/// word by word and store the changed ranges in the lines.
+fn highlight_changes(lines: &mut [DiffLine]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let removed = lines[start..]
+            .iter()
+            .take_while(|line| line.kind == DiffLineKind::Removed)
+            .count();
+        if removed == 0 {
+            start += 1;
+            continue;
+        }
+        let added = lines[start + removed..]
+            .iter()
+            .take_while(|line| line.kind == DiffLineKind::Added)
+            .count();
+
+        for pair in 0..removed.min(added) {
+            let (old, new) = (start + pair, start + removed + pair);
+            let diff = TextDiff::from_words(lines[old].text.as_str(), lines[new].text.as_str());
+            if diff.ratio() < MIN_SIMILARITY {
+                continue;
+            }
+
+            let (mut old_changes, mut new_changes) = (Vec::new(), Vec::new());
+            let (mut old_pos, mut new_pos) = (0, 0);
+            for change in diff.iter_all_changes() {
+                let len = change.value().len();
+                match change.tag() {
+                    ChangeTag::Equal => {
+                        old_pos += len;
+                        new_pos += len;
+                    }
+                    ChangeTag::Delete => {
+                        push_range(&mut old_changes, old_pos..old_pos + len);
+                        old_pos += len;
+                    }
+                    ChangeTag::Insert => {
+                        push_range(&mut new_changes, new_pos..new_pos + len);
+                        new_pos += len;
+                    }
+                }
+            }
+            lines[old].changes = old_changes;
+            lines[new].changes = new_changes;
+        }
+        start += removed + added;
+    }
+}
+
+/// Push `range` and merge it with the previous range if they touch.
+fn push_range(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
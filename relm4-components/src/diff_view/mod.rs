@@ -0,0 +1,430 @@
+//! Reusable diff viewer component for git frontends and editors.
+//!
+//! The component shows a [`Diff`] in unified or side-by-side layout.
+//! Changed words are highlighted within changed lines, unchanged parts between
+//! hunks are collapsed and hunks can be selected or staged by the user.
+
+mod diff;
+
+pub use diff::{Diff, DiffHunk, DiffLine, DiffLineKind, DiffParseError, DiffSection};
+
+use std::rc::Rc;
+use std::sync::Once;
+
+use relm4::gtk::prelude::{BoxExt, ButtonExt, GestureSingleExt, GridExt, WidgetExt};
+use relm4::gtk::{gdk, pango};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, RelmWidgetExt};
+
+const CSS: &str = "
+.relm4-diff-added { background-color: rgba(46, 194, 126, 0.15); }
+.relm4-diff-removed { background-color: rgba(224, 27, 36, 0.15); }
+.relm4-diff-header { background-color: alpha(currentColor, 0.06); }
+.relm4-diff-selected .relm4-diff-header { background-color: rgba(53, 132, 228, 0.25); }
+";
+
+/// How a [`DiffView`] shows the old and the new text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffLayout {
+    /// Removed and added lines below each other.
+    #[default]
+    Unified,
+    /// The old text on the left and the new text on the right.
+    SideBySide,
+}
+
+/// Configuration for the diff view component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `diff` is empty.
+/// - `layout` is set to [`DiffLayout::Unified`].
+/// - `stage_label` is set to [`None`], so hunks can't be staged.
+#[derive(Debug, Clone, Default)]
+pub struct DiffViewSettings {
+    /// The diff that is shown initially.
+    pub diff: Diff,
+    /// The layout of the diff.
+    pub layout: DiffLayout,
+    /// The label of a button in each hunk header that requests staging the hunk.
+    pub stage_label: Option<String>,
+}
+
+/// Messages that can be sent to the diff view component.
+#[derive(Debug)]
+pub enum DiffViewMsg {
+    /// Show another diff.
+    SetDiff(Diff),
+    /// Change the layout.
+    SetLayout(DiffLayout),
+    #[doc(hidden)]
+    SelectHunk(usize),
+    #[doc(hidden)]
+    StageHunk(usize),
+}
+
+/// Messages that are sent by the diff view component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffViewOutput {
+    /// The user clicked the header of a hunk.
+    HunkSelected {
+        /// The index of the hunk in [`Diff::hunks()`].
+        index: usize,
+        /// The selected hunk.
+        hunk: DiffHunk,
+    },
+    /// The user clicked the stage button of a hunk.
+    StageRequested {
+        /// The index of the hunk in [`Diff::hunks()`].
+        index: usize,
+        /// The hunk that should be staged.
+        hunk: DiffHunk,
+    },
+}
+
+/// Diff viewer component.
+#[derive(Debug)]
+pub struct DiffView {
+    diff: Diff,
+    layout: DiffLayout,
+    stage_label: Option<String>,
+    selected: Option<usize>,
+    /// The containers of the hunks for the selection style.
+    hunks: Vec<gtk::Box>,
+}
+
+/// The widgets of the diff view component.
+#[derive(Debug)]
+pub struct DiffViewWidgets {
+    content: gtk::Box,
+}
+
+impl Component for DiffView {
+    type CommandOutput = ();
+    type Input = DiffViewMsg;
+    type Output = DiffViewOutput;
+    type Init = DiffViewSettings;
+    type Root = gtk::ScrolledWindow;
+    type Widgets = DiffViewWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::ScrolledWindow::new()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        static STYLE: Once = Once::new();
+        STYLE.call_once(|| {
+            relm4::set_global_css_with_priority(CSS, gtk::STYLE_PROVIDER_PRIORITY_FALLBACK);
+        });
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        content.add_css_class("monospace");
+        content.set_margin_all(6);
+        root.set_child(Some(&content));
+        root.set_vexpand(true);
+
+        let mut model = Self {
+            diff: settings.diff,
+            layout: settings.layout,
+            stage_label: settings.stage_label,
+            selected: None,
+            hunks: Vec::new(),
+        };
+        let widgets = DiffViewWidgets { content };
+        model.render(&widgets, &sender);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match input {
+            DiffViewMsg::SetDiff(diff) => {
+                self.diff = diff;
+                self.selected = None;
+                self.render(widgets, &sender);
+            }
+            DiffViewMsg::SetLayout(layout) => {
+                if layout != self.layout {
+                    self.layout = layout;
+                    self.render(widgets, &sender);
+                }
+            }
+            DiffViewMsg::SelectHunk(index) => {
+                let Some(hunk) = self.diff.hunk(index) else {
+                    return;
+                };
+                for (position, container) in self.hunks.iter().enumerate() {
+                    if position == index {
+                        container.add_css_class("relm4-diff-selected");
+                    } else {
+                        container.remove_css_class("relm4-diff-selected");
+                    }
+                }
+                self.selected = Some(index);
+                sender
+                    .output(DiffViewOutput::HunkSelected {
+                        index,
+                        hunk: hunk.clone(),
+                    })
+                    .ok();
+            }
+            DiffViewMsg::StageHunk(index) => {
+                if let Some(hunk) = self.diff.hunk(index) {
+                    sender
+                        .output(DiffViewOutput::StageRequested {
+                            index,
+                            hunk: hunk.clone(),
+                        })
+                        .ok();
+                }
+            }
+        }
+    }
+}
+
+impl DiffView {
+    /// The diff that is shown.
+    #[must_use]
+    pub fn diff(&self) -> &Diff {
+        &self.diff
+    }
+
+    /// The index of the selected hunk.
+    #[must_use]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    fn render(&mut self, widgets: &DiffViewWidgets, sender: &ComponentSender<Self>) {
+        let content = &widgets.content;
+        while let Some(child) = content.first_child() {
+            content.remove(&child);
+        }
+        self.hunks.clear();
+
+        if self.diff.is_empty() {
+            let label = gtk::Label::new(Some("No changes"));
+            label.add_css_class("dim-label");
+            content.append(&label);
+        }
+
+        for section in self.diff.sections() {
+            match section {
+                DiffSection::Hunk(hunk) => {
+                    let container = self.hunk_widget(self.hunks.len(), hunk, sender);
+                    content.append(&container);
+                    self.hunks.push(container);
+                }
+                DiffSection::Unchanged {
+                    old_start,
+                    new_start,
+                    lines,
+                } => {
+                    let lines: Vec<DiffLine> = lines
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, text)| DiffLine {
+                            kind: DiffLineKind::Context,
+                            old_line: Some(old_start + offset),
+                            new_line: Some(new_start + offset),
+                            text: text.clone(),
+                            changes: Vec::new(),
+                        })
+                        .collect();
+                    content.append(&unchanged_widget(lines, self.layout));
+                }
+            }
+        }
+    }
+
+    fn hunk_widget(
+        &self,
+        index: usize,
+        hunk: &DiffHunk,
+        sender: &ComponentSender<Self>,
+    ) -> gtk::Box {
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        let header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        header.add_css_class("relm4-diff-header");
+        let title = gtk::Label::new(Some(&hunk.header()));
+        title.set_hexpand(true);
+        title.set_xalign(0.0);
+        title.set_ellipsize(pango::EllipsizeMode::End);
+        title.set_margin_all(3);
+        header.append(&title);
+
+        if let Some(label) = &self.stage_label {
+            let stage = gtk::Button::with_label(label);
+            stage.add_css_class("flat");
+            let input = sender.input_sender().clone();
+            stage.connect_clicked(move |_| input.emit(DiffViewMsg::StageHunk(index)));
+            header.append(&stage);
+        }
+
+        let click = gtk::GestureClick::new();
+        click.set_button(gdk::BUTTON_PRIMARY);
+        let input = sender.input_sender().clone();
+        click.connect_released(move |_, _, _, _| input.emit(DiffViewMsg::SelectHunk(index)));
+        header.add_controller(click);
+
+        container.append(&header);
+        container.append(&lines_widget(&hunk.lines, self.layout));
+        if self.selected == Some(index) {
+            container.add_css_class("relm4-diff-selected");
+        }
+        container
+    }
+}
+
+/// An expander that builds the rows of unchanged lines once it is expanded.
+fn unchanged_widget(lines: Vec<DiffLine>, layout: DiffLayout) -> gtk::Expander {
+    let expander = gtk::Expander::new(Some(&format!("{} unchanged lines", lines.len())));
+    expander.add_css_class("dim-label");
+
+    let lines = Rc::new(lines);
+    expander.connect_expanded_notify(move |expander| {
+        if expander.is_expanded() && expander.child().is_none() {
+            expander.set_child(Some(&lines_widget(&lines, layout)));
+        }
+    });
+    expander
+}
+
+fn lines_widget(lines: &[DiffLine], layout: DiffLayout) -> gtk::Widget {
+    match layout {
+        DiffLayout::Unified => unified_lines(lines).into(),
+        DiffLayout::SideBySide => side_by_side_lines(lines).into(),
+    }
+}
+
+fn unified_lines(lines: &[DiffLine]) -> gtk::Box {
+    let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    for line in lines {
+        let row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        if let Some(class) = kind_css_class(line.kind) {
+            row.add_css_class(class);
+        }
+        row.append(&number_label(line.old_line));
+        row.append(&number_label(line.new_line));
+        row.append(&gtk::Label::new(Some(&line.kind.sign().to_string())));
+        row.append(&text_label(Some(line)));
+        container.append(&row);
+    }
+    container
+}
+
+fn side_by_side_lines(lines: &[DiffLine]) -> gtk::Grid {
+    let grid = gtk::Grid::new();
+    grid.set_column_spacing(6);
+    grid.set_column_homogeneous(false);
+
+    let mut row = 0;
+    let mut attach = |old: Option<&DiffLine>, new: Option<&DiffLine>| {
+        grid.attach(
+            &number_label(old.and_then(|line| line.old_line)),
+            0,
+            row,
+            1,
+            1,
+        );
+        grid.attach(&text_label(old), 1, row, 1, 1);
+        grid.attach(
+            &number_label(new.and_then(|line| line.new_line)),
+            2,
+            row,
+            1,
+            1,
+        );
+        grid.attach(&text_label(new), 3, row, 1, 1);
+        row += 1;
+    };
+
+    let mut start = 0;
+    while let Some(line) = lines.get(start) {
+        if line.kind == DiffLineKind::Context {
+            attach(Some(line), Some(line));
+            start += 1;
+            continue;
+        }
+
+        // Show removed lines next to the added lines that replace them.
+        let removed: Vec<&DiffLine> = lines[start..]
+            .iter()
+            .take_while(|line| line.kind == DiffLineKind::Removed)
+            .collect();
+        let added: Vec<&DiffLine> = lines[start + removed.len()..]
+            .iter()
+            .take_while(|line| line.kind == DiffLineKind::Added)
+            .collect();
+        for pair in 0..removed.len().max(added.len()) {
+            attach(removed.get(pair).copied(), added.get(pair).copied());
+        }
+        start += removed.len() + added.len();
+    }
+    grid
+}
+
+fn number_label(number: Option<usize>) -> gtk::Label {
+    let label = gtk::Label::new(number.map(|number| number.to_string()).as_deref());
+    label.add_css_class("dim-label");
+    label.set_width_chars(4);
+    label.set_xalign(1.0);
+    label.set_valign(gtk::Align::Start);
+    label
+}
+
+/// A label with the text of `line` and its changed words highlighted.
+fn text_label(line: Option<&DiffLine>) -> gtk::Label {
+    let label = gtk::Label::new(None);
+    label.set_hexpand(true);
+    label.set_xalign(0.0);
+    label.set_wrap(true);
+    label.set_wrap_mode(pango::WrapMode::WordChar);
+    label.set_selectable(true);
+
+    let Some(line) = line else {
+        return label;
+    };
+    label.set_label(&line.text);
+    if let Some(class) = kind_css_class(line.kind) {
+        label.add_css_class(class);
+    }
+
+    let (red, green, blue) = match line.kind {
+        DiffLineKind::Added => (0x2e2e, 0xc2c2, 0x7e7e),
+        _ => (0xe0e0, 0x1b1b, 0x2424),
+    };
+    let attributes = pango::AttrList::new();
+    for range in &line.changes {
+        let (start, end) = (range.start as u32, range.end as u32);
+        let mut color = pango::AttrColor::new_background(red, green, blue);
+        color.set_start_index(start);
+        color.set_end_index(end);
+        attributes.insert(color);
+
+        let mut alpha = pango::AttrInt::new_background_alpha(0x6000);
+        alpha.set_start_index(start);
+        alpha.set_end_index(end);
+        attributes.insert(alpha);
+    }
+    label.set_attributes(Some(&attributes));
+    label
+}
+
+fn kind_css_class(kind: DiffLineKind) -> Option<&'static str> {
+    match kind {
+        DiffLineKind::Context => None,
+        DiffLineKind::Added => Some("relm4-diff-added"),
+        DiffLineKind::Removed => Some("relm4-diff-removed"),
+    }
+}
@@ -0,0 +1,915 @@
+//! Dockable panels around a central widget, for IDE-like applications.
+//!
+//! [`Docking`] places panels at the [edges](DockArea) of the central widget.
+//! Panels in the same area are stacked and can be switched with buttons.
+//! Each panel has a header with a drag handle and a menu: it can be dragged
+//! to another area, dropped outside of the areas to float it in its own window,
+//! or hidden. The separators between the areas and the central widget can be
+//! dragged to resize the areas.
+//!
+//! Panels are any widgets, usually the root of a component. [`DockPanel::from_controller()`]
+//! keeps the component alive while the panel exists:
+//!
+//! ```no_run
+//! # use relm4::prelude::*;
+//! # use relm4_components::docking::{DockArea, DockPanel, Docking, DockingMsg, DockingSettings};
+//! # fn example<C: Component<Init = ()>>(center: &gtk::Widget) -> Controller<Docking>
+//! # where C::Root: IsA<gtk::Widget> {
+//! let docking = Docking::builder()
+//!     .launch(DockingSettings {
+//!         center: Some(center.clone()),
+//!         ..Default::default()
+//!     })
+//!     .detach();
+//!
+//! let outline = C::builder().launch(()).detach();
+//! docking.emit(DockingMsg::Add(
+//!     DockPanel::from_controller("outline", "Outline", outline),
+//!     DockArea::Left,
+//! ));
+//! # docking
+//! # }
+//! ```
+//!
+//! [`DockingOutput::LayoutChanged`] describes where all panels are and how
+//! large the areas are. Send the [`DockLayout`] back with [`DockingMsg::SetLayout`]
+//! to restore it in the next session. With the `serde` feature, the layout
+//! implements `Serialize` and `Deserialize`.
+
+use std::any::Any;
+use std::fmt;
+
+use relm4::actions::{RelmAction, RelmActionGroup};
+use relm4::gtk::prelude::{
+    BoxExt, ButtonExt, Cast, CastNone, GtkWindowExt, IsA, ListModelExt, OrientableExt, StaticType,
+    ToValue, WidgetExt, WidgetExtManual,
+};
+use relm4::gtk::{gdk, gio, glib};
+use relm4::{gtk, Component, ComponentController, ComponentParts, ComponentSender, Controller};
+
+relm4::new_action_group!(pub DockPanelActionGroup, "dock-panel");
+relm4::new_stateful_action!(pub DockPanelAction, DockPanelActionGroup, "dock", DockArea, ());
+relm4::new_stateless_action!(pub FloatPanelAction, DockPanelActionGroup, "float");
+relm4::new_stateless_action!(pub HidePanelAction, DockPanelActionGroup, "hide");
+
+/// An edge of the central widget that panels can be docked to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, relm4::VariantValue)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockArea {
+    /// Left of the central widget, at full height.
+    Left,
+    /// Right of the central widget, at full height.
+    Right,
+    /// Above the central widget.
+    Top,
+    /// Below the central widget.
+    Bottom,
+}
+
+impl DockArea {
+    const ALL: [Self; 4] = [Self::Left, Self::Right, Self::Top, Self::Bottom];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The area is the start child of its [`gtk::Paned`].
+    fn is_start(self) -> bool {
+        matches!(self, Self::Left | Self::Top)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Left => "Dock Left",
+            Self::Right => "Dock Right",
+            Self::Top => "Dock Top",
+            Self::Bottom => "Dock Bottom",
+        }
+    }
+}
+
+/// Where a panel is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanelPlacement {
+    /// Stacked with the other panels of an area.
+    Docked(DockArea),
+    /// In its own window. The panel returns to `area` when the window is closed.
+    Floating {
+        /// The area the panel returns to.
+        area: DockArea,
+        /// The width of the window.
+        width: i32,
+        /// The height of the window.
+        height: i32,
+    },
+    /// Not shown. The panel returns to `area` when it's shown again.
+    Hidden(DockArea),
+}
+
+impl PanelPlacement {
+    fn area(self) -> DockArea {
+        match self {
+            Self::Docked(area) | Self::Floating { area, .. } | Self::Hidden(area) => area,
+        }
+    }
+}
+
+/// The placement of a panel in a [`DockLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PanelLayout {
+    /// The [id](DockPanel::new) of the panel.
+    pub id: String,
+    /// Where the panel is shown.
+    pub placement: PanelPlacement,
+    /// The panel is the visible one of its area.
+    pub selected: bool,
+}
+
+/// The sizes of the areas in pixels.
+///
+/// The width is used for the left and right area, the height for the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockSizes {
+    /// The width of the left area.
+    pub left: i32,
+    /// The width of the right area.
+    pub right: i32,
+    /// The height of the top area.
+    pub top: i32,
+    /// The height of the bottom area.
+    pub bottom: i32,
+}
+
+impl Default for DockSizes {
+    fn default() -> Self {
+        Self {
+            left: 250,
+            right: 250,
+            top: 200,
+            bottom: 200,
+        }
+    }
+}
+
+impl DockSizes {
+    fn get(&self, area: DockArea) -> i32 {
+        match area {
+            DockArea::Left => self.left,
+            DockArea::Right => self.right,
+            DockArea::Top => self.top,
+            DockArea::Bottom => self.bottom,
+        }
+    }
+
+    fn set(&mut self, area: DockArea, size: i32) {
+        match area {
+            DockArea::Left => self.left = size,
+            DockArea::Right => self.right = size,
+            DockArea::Top => self.top = size,
+            DockArea::Bottom => self.bottom = size,
+        }
+    }
+}
+
+/// The arrangement of all panels, to restore it in the next session.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockLayout {
+    /// The panels, docked panels in the order of their area.
+    pub panels: Vec<PanelLayout>,
+    /// The sizes of the areas.
+    pub sizes: DockSizes,
+}
+
+/// A widget that can be docked, created by the application.
+pub struct DockPanel {
+    id: String,
+    title: String,
+    icon_name: Option<String>,
+    widget: gtk::Widget,
+    keep_alive: Option<Box<dyn Any>>,
+}
+
+impl fmt::Debug for DockPanel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DockPanel")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("icon_name", &self.icon_name)
+            .field("widget", &self.widget)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DockPanel {
+    /// Create a panel.
+    ///
+    /// The `id` identifies the panel in messages and in the [`DockLayout`],
+    /// so it should stay the same between sessions.
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        widget: &impl IsA<gtk::Widget>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            icon_name: None,
+            widget: widget.clone().upcast(),
+            keep_alive: None,
+        }
+    }
+
+    /// Create a panel that shows the root widget of a component
+    /// and keeps the component alive until the panel is removed.
+    pub fn from_controller<C>(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        controller: Controller<C>,
+    ) -> Self
+    where
+        C: Component,
+        C::Root: IsA<gtk::Widget>,
+    {
+        let mut panel = Self::new(id, title, controller.widget());
+        panel.keep_alive = Some(Box::new(controller));
+        panel
+    }
+
+    /// Show an icon instead of the title in the buttons that switch between panels.
+    #[must_use]
+    pub fn icon_name(mut self, icon_name: impl Into<String>) -> Self {
+        self.icon_name = Some(icon_name.into());
+        self
+    }
+}
+
+/// Configuration for the docking component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `center` is set to [`None`], so the center is empty.
+/// - `sizes` is set to 250 pixels for the left and right area and 200 pixels for the others.
+#[derive(Debug, Clone, Default)]
+pub struct DockingSettings {
+    /// The central widget, for example the editor.
+    pub center: Option<gtk::Widget>,
+    /// The initial sizes of the areas.
+    pub sizes: DockSizes,
+}
+
+/// Messages that can be sent to the docking component.
+#[derive(Debug)]
+pub enum DockingMsg {
+    /// Add a panel to an area and show it.
+    ///
+    /// If the last [layout](DockingMsg::SetLayout) contains the panel,
+    /// it's placed as in the layout instead.
+    Add(DockPanel, DockArea),
+    /// Remove a panel and drop it.
+    Remove(String),
+    /// Move a panel to an area.
+    Dock(String, DockArea),
+    /// Move a panel into its own window.
+    Float(String),
+    /// Hide a panel.
+    Hide(String),
+    /// Show a panel in its last area or window and make it the visible one.
+    Show(String),
+    /// Restore a layout.
+    SetLayout(DockLayout),
+    #[doc(hidden)]
+    DragBegin,
+    #[doc(hidden)]
+    DragEnd,
+    #[doc(hidden)]
+    WindowClosed(String),
+    #[doc(hidden)]
+    Resized(DockArea),
+    #[doc(hidden)]
+    Changed,
+}
+
+/// Messages that are sent by the docking component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DockingOutput {
+    /// Panels were added, moved, hidden or selected, or an area was resized.
+    LayoutChanged(DockLayout),
+}
+
+enum Location {
+    Docked,
+    Floating(gtk::Window),
+    Hidden,
+}
+
+struct Panel {
+    id: String,
+    title: String,
+    icon_name: Option<String>,
+    /// The header and the widget of the panel.
+    page: gtk::Box,
+    location: Location,
+    /// The area the panel is docked to or returns to.
+    area: DockArea,
+    _keep_alive: Option<Box<dyn Any>>,
+}
+
+#[derive(Debug)]
+struct Area {
+    container: gtk::Box,
+    switcher: gtk::StackSwitcher,
+    stack: gtk::Stack,
+    /// Shown while a panel is dragged and the area is empty.
+    placeholder: gtk::Label,
+}
+
+impl Area {
+    fn new(area: DockArea, sender: &ComponentSender<Docking>) -> Self {
+        let stack = gtk::Stack::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .visible(false)
+            .build();
+        let switcher = gtk::StackSwitcher::builder()
+            .stack(&stack)
+            .halign(gtk::Align::Center)
+            .visible(false)
+            .build();
+        let placeholder = gtk::Label::builder()
+            .label("Drop Panel Here")
+            .hexpand(true)
+            .vexpand(true)
+            .width_request(150)
+            .height_request(100)
+            .css_classes(["dim-label"])
+            .build();
+        let container = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .css_classes(["dock-area"])
+            .visible(false)
+            .build();
+        container.append(&switcher);
+        container.append(&stack);
+        container.append(&placeholder);
+
+        let drop_target = gtk::DropTarget::new(String::static_type(), gdk::DragAction::MOVE);
+        let input = sender.input_sender().clone();
+        drop_target.connect_drop(move |_, value, _, _| match value.get::<String>() {
+            Ok(id) => {
+                input.emit(DockingMsg::Dock(id, area));
+                true
+            }
+            Err(_) => false,
+        });
+        container.add_controller(drop_target);
+
+        let input = sender.input_sender().clone();
+        stack.connect_visible_child_notify(move |_| input.emit(DockingMsg::Changed));
+
+        Self {
+            container,
+            switcher,
+            stack,
+            placeholder,
+        }
+    }
+
+    fn children(&self) -> Vec<gtk::Widget> {
+        let pages = self.stack.pages();
+        (0..pages.n_items())
+            .filter_map(|position| pages.item(position).and_downcast::<gtk::StackPage>())
+            .map(|page| page.child())
+            .collect()
+    }
+
+    /// Update the visibility, returns `true` if the area got its first panel.
+    fn update(&self, dragging: bool) -> bool {
+        let panels = self.pages_len();
+        let was_shown = self.stack.is_visible();
+        self.container.set_visible(panels > 0 || dragging);
+        self.stack.set_visible(panels > 0);
+        self.switcher.set_visible(panels > 1);
+        self.placeholder.set_visible(panels == 0);
+        panels > 0 && !was_shown
+    }
+
+    fn pages_len(&self) -> u32 {
+        self.stack.pages().n_items()
+    }
+}
+
+/// The widgets of the docking component.
+#[derive(Debug)]
+pub struct DockingWidgets {
+    areas: [Area; 4],
+    /// The separators of the areas, in the order of [`DockArea`].
+    paneds: [gtk::Paned; 4],
+}
+
+/// Panels docked to the edges of a central widget.
+pub struct Docking {
+    panels: Vec<Panel>,
+    /// Placements of panels that weren't added yet.
+    pending: Vec<PanelLayout>,
+    sizes: DockSizes,
+    dragging: bool,
+    layout: DockLayout,
+}
+
+impl fmt::Debug for Docking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Docking")
+            .field("panels", &self.panels.len())
+            .field("dragging", &self.dragging)
+            .field("layout", &self.layout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Component for Docking {
+    type CommandOutput = ();
+    type Input = DockingMsg;
+    type Output = DockingOutput;
+    type Init = DockingSettings;
+    type Root = gtk::Box;
+    type Widgets = DockingWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::builder().hexpand(true).vexpand(true).build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let areas = DockArea::ALL.map(|area| Area::new(area, &sender));
+
+        let center = gtk::Box::builder().hexpand(true).vexpand(true).build();
+        if let Some(widget) = &settings.center {
+            center.append(widget);
+        }
+
+        // The left and right area have the full height,
+        // the top and bottom area only the width of the central widget.
+        let bottom = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .start_child(&center)
+            .end_child(&areas[DockArea::Bottom.index()].container)
+            .resize_end_child(false)
+            .shrink_end_child(false)
+            .build();
+        let top = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .start_child(&areas[DockArea::Top.index()].container)
+            .end_child(&bottom)
+            .resize_start_child(false)
+            .shrink_start_child(false)
+            .build();
+        let right = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .start_child(&top)
+            .end_child(&areas[DockArea::Right.index()].container)
+            .resize_end_child(false)
+            .shrink_end_child(false)
+            .build();
+        let left = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .hexpand(true)
+            .vexpand(true)
+            .start_child(&areas[DockArea::Left.index()].container)
+            .end_child(&right)
+            .resize_start_child(false)
+            .shrink_start_child(false)
+            .build();
+        root.append(&left);
+
+        let paneds = [left, right, top, bottom];
+        for (area, paned) in DockArea::ALL.into_iter().zip(&paneds) {
+            apply_size(paned, area, settings.sizes.get(area));
+            let input = sender.input_sender().clone();
+            paned.connect_position_notify(move |_| input.emit(DockingMsg::Resized(area)));
+        }
+
+        let model = Self {
+            panels: Vec::new(),
+            pending: Vec::new(),
+            sizes: settings.sizes,
+            dragging: false,
+            layout: DockLayout {
+                panels: Vec::new(),
+                sizes: settings.sizes,
+            },
+        };
+        let widgets = DockingWidgets { areas, paneds };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            DockingMsg::Add(panel, area) => {
+                let placement = self
+                    .pending
+                    .iter()
+                    .position(|layout| layout.id == panel.id)
+                    .map(|index| self.pending.remove(index));
+                self.remove(&panel.id, widgets);
+                let panel = self.create(panel, area, &sender);
+                self.panels.push(panel);
+                let index = self.panels.len() - 1;
+                match placement {
+                    Some(layout) => {
+                        self.place(index, layout.placement, widgets, root, &sender);
+                        if layout.selected {
+                            self.reveal(index, widgets);
+                        }
+                    }
+                    None => {
+                        self.place(index, PanelPlacement::Docked(area), widgets, root, &sender);
+                        self.reveal(index, widgets);
+                    }
+                }
+            }
+            DockingMsg::Remove(id) => self.remove(&id, widgets),
+            DockingMsg::Dock(id, area) => {
+                if let Some(index) = self.index(&id) {
+                    let panel = &self.panels[index];
+                    // Dropping a panel on its own area doesn't change anything.
+                    if !(matches!(panel.location, Location::Docked) && panel.area == area) {
+                        self.place(index, PanelPlacement::Docked(area), widgets, root, &sender);
+                    }
+                    self.reveal(index, widgets);
+                }
+            }
+            DockingMsg::Float(id) => {
+                if let Some(index) = self.index(&id) {
+                    if !matches!(self.panels[index].location, Location::Floating(_)) {
+                        let page = &self.panels[index].page;
+                        let placement = PanelPlacement::Floating {
+                            area: self.panels[index].area,
+                            width: page.width().max(300),
+                            height: page.height().max(300),
+                        };
+                        self.place(index, placement, widgets, root, &sender);
+                    }
+                }
+            }
+            DockingMsg::Hide(id) => {
+                if let Some(index) = self.index(&id) {
+                    let area = self.panels[index].area;
+                    self.place(index, PanelPlacement::Hidden(area), widgets, root, &sender);
+                }
+            }
+            DockingMsg::Show(id) => {
+                if let Some(index) = self.index(&id) {
+                    if matches!(self.panels[index].location, Location::Hidden) {
+                        let area = self.panels[index].area;
+                        self.place(index, PanelPlacement::Docked(area), widgets, root, &sender);
+                    }
+                    self.reveal(index, widgets);
+                }
+            }
+            DockingMsg::SetLayout(layout) => {
+                self.pending.clear();
+                for entry in layout.panels {
+                    match self.index(&entry.id) {
+                        Some(index) => {
+                            // Placing a panel again appends it, which restores the order.
+                            self.place(index, entry.placement, widgets, root, &sender);
+                            if entry.selected {
+                                self.reveal(index, widgets);
+                            }
+                        }
+                        None => self.pending.push(entry),
+                    }
+                }
+                self.sizes = layout.sizes;
+                for (area, paned) in DockArea::ALL.into_iter().zip(&widgets.paneds) {
+                    apply_size(paned, area, self.sizes.get(area));
+                }
+            }
+            DockingMsg::DragBegin => self.dragging = true,
+            DockingMsg::DragEnd => self.dragging = false,
+            DockingMsg::WindowClosed(id) => {
+                if let Some(index) = self.index(&id) {
+                    let area = self.panels[index].area;
+                    self.place(index, PanelPlacement::Docked(area), widgets, root, &sender);
+                }
+            }
+            DockingMsg::Resized(area) => {
+                let paned = &widgets.paneds[area.index()];
+                let extent = extent(paned);
+                // Hidden areas have no size.
+                if widgets.areas[area.index()].pages_len() > 0 && extent > 0 {
+                    let size = if area.is_start() {
+                        paned.position()
+                    } else {
+                        extent - paned.position()
+                    };
+                    self.sizes.set(area, size);
+                }
+            }
+            DockingMsg::Changed => {}
+        }
+
+        for (area, paned) in DockArea::ALL.into_iter().zip(&widgets.paneds) {
+            if widgets.areas[area.index()].update(self.dragging) {
+                // Hidden areas lose their size.
+                apply_size(paned, area, self.sizes.get(area));
+            }
+        }
+        self.update_layout(widgets, &sender);
+    }
+}
+
+impl Docking {
+    fn index(&self, id: &str) -> Option<usize> {
+        self.panels.iter().position(|panel| panel.id == id)
+    }
+
+    /// Wrap the widget of a panel in a page with a header.
+    fn create(&self, panel: DockPanel, area: DockArea, sender: &ComponentSender<Self>) -> Panel {
+        let id = panel.id;
+        let page = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .css_classes(["dock-panel"])
+            .build();
+
+        let header = gtk::Box::builder()
+            .spacing(6)
+            .margin_start(6)
+            .margin_end(6)
+            .css_classes(["dock-panel-header"])
+            .build();
+        let handle = gtk::Image::from_icon_name("list-drag-handle-symbolic");
+        handle.set_cursor_from_name(Some("grab"));
+        header.append(&handle);
+        header.append(
+            &gtk::Label::builder()
+                .label(&panel.title)
+                .hexpand(true)
+                .xalign(0.0)
+                .css_classes(["heading"])
+                .build(),
+        );
+        header.append(
+            &gtk::MenuButton::builder()
+                .icon_name("view-more-symbolic")
+                .tooltip_text("Panel Options")
+                .menu_model(&panel_menu())
+                .css_classes(["flat"])
+                .build(),
+        );
+        let hide = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Hide Panel")
+            .css_classes(["flat"])
+            .build();
+        let input = sender.input_sender().clone();
+        let panel_id = id.clone();
+        hide.connect_clicked(move |_| input.emit(DockingMsg::Hide(panel_id.clone())));
+        header.append(&hide);
+
+        page.append(&header);
+        page.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+        panel.widget.set_vexpand(true);
+        page.append(&panel.widget);
+
+        header.add_controller(drag_source(&id, &header, sender));
+
+        let mut group = RelmActionGroup::<DockPanelActionGroup>::new();
+        let input = sender.input_sender().clone();
+        let panel_id = id.clone();
+        group.add_action(RelmAction::<DockPanelAction>::new_with_target_value(
+            move |_, area| input.emit(DockingMsg::Dock(panel_id.clone(), area)),
+        ));
+        let input = sender.input_sender().clone();
+        let panel_id = id.clone();
+        group.add_action(RelmAction::<FloatPanelAction>::new_stateless(move |_| {
+            input.emit(DockingMsg::Float(panel_id.clone()));
+        }));
+        let input = sender.input_sender().clone();
+        let panel_id = id.clone();
+        group.add_action(RelmAction::<HidePanelAction>::new_stateless(move |_| {
+            input.emit(DockingMsg::Hide(panel_id.clone()));
+        }));
+        group.register_for_widget(&page);
+
+        Panel {
+            id,
+            title: panel.title,
+            icon_name: panel.icon_name,
+            page,
+            location: Location::Hidden,
+            area,
+            _keep_alive: panel.keep_alive,
+        }
+    }
+
+    fn remove(&mut self, id: &str, widgets: &DockingWidgets) {
+        if let Some(index) = self.index(id) {
+            let mut panel = self.panels.remove(index);
+            detach(&mut panel, widgets);
+        }
+    }
+
+    /// Move a panel to its new place.
+    fn place(
+        &mut self,
+        index: usize,
+        placement: PanelPlacement,
+        widgets: &DockingWidgets,
+        root: &gtk::Box,
+        sender: &ComponentSender<Self>,
+    ) {
+        let panel = &mut self.panels[index];
+        detach(panel, widgets);
+        panel.area = placement.area();
+
+        match placement {
+            PanelPlacement::Docked(area) => {
+                let stack = &widgets.areas[area.index()].stack;
+                let page = stack.add_titled(&panel.page, Some(panel.id.as_str()), &panel.title);
+                if let Some(icon_name) = &panel.icon_name {
+                    page.set_icon_name(icon_name);
+                }
+                panel.location = Location::Docked;
+            }
+            PanelPlacement::Floating { width, height, .. } => {
+                let window = gtk::Window::builder()
+                    .title(&panel.title)
+                    .default_width(width)
+                    .default_height(height)
+                    .child(&panel.page)
+                    .build();
+                let parent = root.root().and_downcast::<gtk::Window>();
+                window.set_transient_for(parent.as_ref());
+
+                let input = sender.input_sender().clone();
+                let id = panel.id.clone();
+                window.connect_close_request(move |_| {
+                    input.emit(DockingMsg::WindowClosed(id.clone()));
+                    // The panel is moved out of the window before it's destroyed.
+                    glib::Propagation::Stop
+                });
+                window.present();
+                panel.location = Location::Floating(window);
+            }
+            PanelPlacement::Hidden(_) => panel.location = Location::Hidden,
+        }
+    }
+
+    /// Make a panel the visible one of its area or raise its window.
+    fn reveal(&self, index: usize, widgets: &DockingWidgets) {
+        let panel = &self.panels[index];
+        match &panel.location {
+            Location::Docked => widgets.areas[panel.area.index()]
+                .stack
+                .set_visible_child(&panel.page),
+            Location::Floating(window) => window.present(),
+            Location::Hidden => {}
+        }
+    }
+
+    /// Send the layout if it changed.
+    fn update_layout(&mut self, widgets: &DockingWidgets, sender: &ComponentSender<Self>) {
+        let mut layout = DockLayout {
+            panels: Vec::new(),
+            sizes: self.sizes,
+        };
+
+        for area in DockArea::ALL {
+            let area_widgets = &widgets.areas[area.index()];
+            let visible = area_widgets.stack.visible_child();
+            for child in area_widgets.children() {
+                let Some(panel) = self.panels.iter().find(|panel| panel.page == child) else {
+                    continue;
+                };
+                layout.panels.push(PanelLayout {
+                    id: panel.id.clone(),
+                    placement: PanelPlacement::Docked(area),
+                    selected: visible.as_ref() == Some(&child),
+                });
+            }
+        }
+
+        for panel in &self.panels {
+            let placement = match &panel.location {
+                Location::Docked => continue,
+                Location::Floating(window) => {
+                    let (width, height) = window.default_size();
+                    PanelPlacement::Floating {
+                        area: panel.area,
+                        width,
+                        height,
+                    }
+                }
+                Location::Hidden => PanelPlacement::Hidden(panel.area),
+            };
+            layout.panels.push(PanelLayout {
+                id: panel.id.clone(),
+                placement,
+                selected: false,
+            });
+        }
+
+        if layout != self.layout {
+            self.layout = layout.clone();
+            // Ignore send errors because the component might be detached.
+            sender.output(DockingOutput::LayoutChanged(layout)).ok();
+        }
+    }
+}
+
+/// Take a panel out of its area or window.
+fn detach(panel: &mut Panel, widgets: &DockingWidgets) {
+    match std::mem::replace(&mut panel.location, Location::Hidden) {
+        Location::Docked => widgets.areas[panel.area.index()].stack.remove(&panel.page),
+        Location::Floating(window) => {
+            window.set_child(None::<&gtk::Widget>);
+            window.destroy();
+        }
+        Location::Hidden => {}
+    }
+}
+
+/// Drag the panel to an area, or anywhere else to float it.
+fn drag_source(id: &str, header: &gtk::Box, sender: &ComponentSender<Docking>) -> gtk::DragSource {
+    let source = gtk::DragSource::builder()
+        .actions(gdk::DragAction::MOVE)
+        .build();
+
+    let value = id.to_value();
+    source.connect_prepare(move |_, _, _| Some(gdk::ContentProvider::for_value(&value)));
+
+    let input = sender.input_sender().clone();
+    let header = header.clone();
+    source.connect_drag_begin(move |source, _| {
+        source.set_icon(Some(&gtk::WidgetPaintable::new(Some(&header))), 0, 0);
+        input.emit(DockingMsg::DragBegin);
+    });
+    let input = sender.input_sender().clone();
+    source.connect_drag_end(move |_, _, _| input.emit(DockingMsg::DragEnd));
+
+    let input = sender.input_sender().clone();
+    let id = id.to_owned();
+    source.connect_drag_cancel(move |_, _, reason| {
+        if reason == gdk::DragCancelReason::NoTarget {
+            input.emit(DockingMsg::Float(id.clone()));
+        }
+        false
+    });
+
+    source
+}
+
+/// Set the size of an area once its separator has been allocated.
+fn apply_size(paned: &gtk::Paned, area: DockArea, size: i32) {
+    paned.add_tick_callback(move |paned, _| {
+        let extent = extent(paned);
+        if extent <= 0 {
+            return glib::ControlFlow::Continue;
+        }
+        if area.is_start() {
+            paned.set_position(size);
+        } else {
+            paned.set_position((extent - size).max(0));
+        }
+        glib::ControlFlow::Break
+    });
+}
+
+fn extent(paned: &gtk::Paned) -> i32 {
+    match paned.orientation() {
+        gtk::Orientation::Horizontal => paned.width(),
+        _ => paned.height(),
+    }
+}
+
+fn panel_menu() -> gio::Menu {
+    let menu = gio::Menu::new();
+    let section = gio::Menu::new();
+    for area in DockArea::ALL {
+        section.append_item(
+            &RelmAction::<DockPanelAction>::to_menu_item_with_target_value(area.label(), &area),
+        );
+    }
+    menu.append_section(None, &section);
+
+    let section = gio::Menu::new();
+    section.append_item(&RelmAction::<FloatPanelAction>::to_menu_item("Float"));
+    section.append_item(&RelmAction::<HidePanelAction>::to_menu_item("Hide"));
+    menu.append_section(None, &section);
+    menu
+}
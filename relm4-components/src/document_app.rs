@@ -0,0 +1,1042 @@
+//! A scaffold for document based applications.
+//!
+//! [`DocumentApp`] is the main window of an application that edits documents,
+//! like a text editor or a drawing program. The application only implements
+//! [`Document`], which reads, writes and shows a single document.
+//! The scaffold provides the rest:
+//!
+//! + documents in tabs or in separate windows, see [`DocumentLayout`]
+//! + new, open, save, save as, export and close actions in the `doc` action group
+//!   with the usual keyboard shortcuts
+//! + a prompt to save unsaved changes when a document or the application is closed
+//! + an "Open Recent" menu backed by [`RecentFiles`]
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use relm4::gtk::{self, prelude::*};
+//! use relm4::RelmApp;
+//! use relm4_components::document_app::{
+//!     Document, DocumentApp, DocumentAppSettings, DocumentError,
+//! };
+//!
+//! struct TextDocument {
+//!     buffer: gtk::TextBuffer,
+//!     view: gtk::ScrolledWindow,
+//! }
+//!
+//! impl Document for TextDocument {
+//!     fn new() -> Self {
+//!         let buffer = gtk::TextBuffer::new(None);
+//!         let view = gtk::ScrolledWindow::builder()
+//!             .child(&gtk::TextView::with_buffer(&buffer))
+//!             .build();
+//!         Self { buffer, view }
+//!     }
+//!
+//!     fn open(path: &Path) -> Result<Self, DocumentError> {
+//!         let document = Self::new();
+//!         document.buffer.set_text(&std::fs::read_to_string(path)?);
+//!         document.buffer.set_modified(false);
+//!         Ok(document)
+//!     }
+//!
+//!     fn save(&mut self, path: &Path) -> Result<(), DocumentError> {
+//!         let (start, end) = self.buffer.bounds();
+//!         std::fs::write(path, self.buffer.text(&start, &end, true).as_str())?;
+//!         self.buffer.set_modified(false);
+//!         Ok(())
+//!     }
+//!
+//!     fn is_dirty(&self) -> bool {
+//!         self.buffer.is_modified()
+//!     }
+//!
+//!     fn widget(&self) -> gtk::Widget {
+//!         self.view.clone().upcast()
+//!     }
+//!
+//!     fn connect_changed(&self, callback: Box<dyn Fn()>) {
+//!         self.buffer.connect_modified_changed(move |_| callback());
+//!     }
+//! }
+//!
+//! let app = RelmApp::new("relm4.example.editor");
+//! app.run::<DocumentApp<TextDocument>>(DocumentAppSettings {
+//!     title: "Editor".into(),
+//!     ..Default::default()
+//! });
+//! ```
+
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use gtk::glib::Propagation;
+use gtk::prelude::{BoxExt, ButtonExt, GtkWindowExt};
+use relm4::actions::{AccelsPlus, ActionName, RelmAction, RelmActionGroup};
+use relm4::component::DialogController;
+use relm4::gtk::gio;
+use relm4::{gtk, Component, ComponentController, ComponentParts, ComponentSender, Controller};
+
+use crate::alert::{Alert, AlertResponse, AlertSettings};
+use crate::open_dialog::{OpenDialog, OpenDialogMsg, OpenDialogResponse, OpenDialogSettings};
+use crate::recent_files::{
+    recent_files_menu, RecentFile, RecentFiles, RecentFilesMsg, RecentFilesOutput,
+    RecentFilesSettings,
+};
+use crate::save_dialog::{SaveDialog, SaveDialogMsg, SaveDialogResponse, SaveDialogSettings};
+
+relm4::new_action_group!(
+    /// The action group of the document actions, named `doc`.
+    #[derive(Debug)]
+    pub DocumentActionGroup,
+    "doc"
+);
+relm4::new_stateless_action!(
+    /// Creates a new document.
+    #[derive(Debug)]
+    pub NewAction,
+    DocumentActionGroup,
+    "new"
+);
+relm4::new_stateless_action!(
+    /// Opens a document from a file.
+    #[derive(Debug)]
+    pub OpenAction,
+    DocumentActionGroup,
+    "open"
+);
+relm4::new_stateless_action!(
+    /// Saves the active document.
+    #[derive(Debug)]
+    pub SaveAction,
+    DocumentActionGroup,
+    "save"
+);
+relm4::new_stateless_action!(
+    /// Saves the active document to a new file.
+    #[derive(Debug)]
+    pub SaveAsAction,
+    DocumentActionGroup,
+    "save-as"
+);
+relm4::new_stateless_action!(
+    /// Closes the active document.
+    #[derive(Debug)]
+    pub CloseAction,
+    DocumentActionGroup,
+    "close"
+);
+relm4::new_stateless_action!(
+    /// Closes all documents and quits the application.
+    #[derive(Debug)]
+    pub QuitAction,
+    DocumentActionGroup,
+    "quit"
+);
+relm4::new_stateful_action!(
+    /// Exports the active document to the [`ExportFormat`] with the given id.
+    #[derive(Debug)]
+    pub ExportAction,
+    DocumentActionGroup,
+    "export",
+    String,
+    ()
+);
+relm4::new_stateful_action!(
+    /// Opens the recent file with the given path.
+    #[derive(Debug)]
+    pub OpenRecentAction,
+    DocumentActionGroup,
+    "open-recent",
+    String,
+    ()
+);
+
+/// The error type of [`Document`] operations, which is shown to the user.
+pub type DocumentError = Box<dyn Error + Send + Sync>;
+
+/// A file format that a document can be exported to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportFormat {
+    /// The identifier that is passed to [`Document::export()`].
+    pub id: String,
+    /// The name that is shown in the menu, like "PDF".
+    pub label: String,
+    /// The file extension without dot, like "pdf".
+    pub extension: String,
+}
+
+/// A document that can be edited with [`DocumentApp`].
+pub trait Document: Sized + 'static {
+    /// Create a new, empty document.
+    fn new() -> Self;
+
+    /// Read a document from a file.
+    fn open(path: &Path) -> Result<Self, DocumentError>;
+
+    /// Write the document to a file.
+    ///
+    /// Afterwards, [`Self::is_dirty()`] should return `false`.
+    fn save(&mut self, path: &Path) -> Result<(), DocumentError>;
+
+    /// Returns `true` if there are unsaved changes.
+    fn is_dirty(&self) -> bool;
+
+    /// The widget that shows and edits the document.
+    fn widget(&self) -> gtk::Widget;
+
+    /// Call `callback` whenever [`Self::is_dirty()`] might have changed,
+    /// so titles are kept up to date.
+    fn connect_changed(&self, callback: Box<dyn Fn()>) {
+        let _ = callback;
+    }
+
+    /// Filters for the open and save dialogs.
+    fn filters() -> Vec<gtk::FileFilter> {
+        Vec::new()
+    }
+
+    /// The formats that [`Self::export()`] supports.
+    fn export_formats() -> Vec<ExportFormat> {
+        Vec::new()
+    }
+
+    /// Write the document to a file in one of the [export formats](Self::export_formats).
+    fn export(&self, path: &Path, format: &str) -> Result<(), DocumentError> {
+        let _ = path;
+        Err(format!("Exporting to {format} is not supported").into())
+    }
+}
+
+/// How documents are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentLayout {
+    /// All documents are tabs of the main window.
+    #[default]
+    Tabs,
+    /// The first document is shown in the main window, the others in their own windows.
+    Windows,
+}
+
+/// Configuration for the document application.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `title` is set to "Documents".
+/// - `layout` is set to [`DocumentLayout::Tabs`].
+/// - `accelerators` is set to [`true`].
+/// - `recent_files` uses the [default settings](RecentFilesSettings::default).
+#[derive(Debug, Clone)]
+pub struct DocumentAppSettings {
+    /// The name of the application that is shown in window titles.
+    pub title: String,
+    /// How documents are arranged.
+    pub layout: DocumentLayout,
+    /// Set the usual keyboard shortcuts for the actions, like `Ctrl+S` to save.
+    pub accelerators: bool,
+    /// Configuration of the recent files.
+    pub recent_files: RecentFilesSettings,
+}
+
+impl Default for DocumentAppSettings {
+    fn default() -> Self {
+        Self {
+            title: String::from("Documents"),
+            layout: DocumentLayout::Tabs,
+            accelerators: true,
+            recent_files: RecentFilesSettings::default(),
+        }
+    }
+}
+
+/// An identifier of an open document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DocumentId(u64);
+
+/// Messages that can be sent to the document application.
+///
+/// The messages without document refer to the active document.
+#[derive(Debug)]
+pub enum DocumentAppMsg {
+    /// Create a new document.
+    New,
+    /// Show the open dialog.
+    Open,
+    /// Open a file or show it if it's already open.
+    OpenPath(PathBuf),
+    /// Save the active document, asking for a path if it has none.
+    Save,
+    /// Save the active document to a new path.
+    SaveAs,
+    /// Export the active document in the format with the given identifier.
+    Export(String),
+    /// Close the active document, asking to save unsaved changes.
+    Close,
+    /// Close a document, asking to save unsaved changes.
+    CloseDocument(DocumentId),
+    /// Close all documents and the application, asking to save unsaved changes.
+    Quit,
+    #[doc(hidden)]
+    Changed(DocumentId),
+    #[doc(hidden)]
+    Focused(Option<DocumentId>),
+    #[doc(hidden)]
+    SwitchedPage(u32),
+    #[doc(hidden)]
+    SaveTo(PathBuf),
+    #[doc(hidden)]
+    DialogCancelled,
+    #[doc(hidden)]
+    CloseResponse(DocumentId, Option<AlertResponse>),
+    #[doc(hidden)]
+    RecentChanged(Vec<RecentFile>),
+}
+
+/// Messages that are sent by the document application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentAppOutput {
+    /// A document was opened.
+    Opened(PathBuf),
+    /// A document was saved.
+    Saved(PathBuf),
+    /// A document was exported.
+    Exported(PathBuf),
+    /// A document was closed.
+    Closed(Option<PathBuf>),
+}
+
+/// What the save dialog is used for.
+#[derive(Debug)]
+enum SavePurpose {
+    Save { close: bool },
+    Export(String),
+}
+
+struct OpenDocument<D> {
+    id: DocumentId,
+    document: D,
+    path: Option<PathBuf>,
+    /// The number in "Untitled 2" for documents without path.
+    number: usize,
+    page: gtk::Widget,
+    tab_label: gtk::Label,
+    /// The own window in [`DocumentLayout::Windows`].
+    window: Option<gtk::Window>,
+}
+
+impl<D: Document> OpenDocument<D> {
+    fn name(&self) -> String {
+        match &self.path {
+            Some(path) => path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .into_owned(),
+            None if self.number > 1 => format!("Untitled {}", self.number),
+            None => String::from("Untitled"),
+        }
+    }
+
+    fn title(&self) -> String {
+        if self.document.is_dirty() {
+            format!("• {}", self.name())
+        } else {
+            self.name()
+        }
+    }
+}
+
+/// The widgets of the document application.
+#[derive(Debug)]
+pub struct DocumentAppWidgets {
+    notebook: gtk::Notebook,
+    recent_menu: gio::Menu,
+}
+
+/// The main window of a document based application.
+pub struct DocumentApp<D: Document> {
+    title: String,
+    layout: DocumentLayout,
+    documents: Vec<OpenDocument<D>>,
+    next_id: u64,
+    /// The extra window that has the focus, [`None`] for the main window.
+    focused: Option<DocumentId>,
+    pending_save: Option<(DocumentId, SavePurpose)>,
+    /// Documents are being closed to quit the application.
+    quitting: bool,
+    /// The main window may close without asking.
+    may_close: Rc<Cell<bool>>,
+    dialogs: DialogController,
+    open_dialog: Controller<OpenDialog>,
+    save_dialog: Controller<SaveDialog>,
+    recent_files: Controller<RecentFiles>,
+}
+
+impl<D: Document> fmt::Debug for DocumentApp<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DocumentApp")
+            .field("title", &self.title)
+            .field("layout", &self.layout)
+            .field("documents", &self.documents.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<D: Document> Component for DocumentApp<D> {
+    type CommandOutput = ();
+    type Input = DocumentAppMsg;
+    type Output = DocumentAppOutput;
+    type Init = DocumentAppSettings;
+    type Root = gtk::Window;
+    type Widgets = DocumentAppWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Window::builder()
+            .default_width(800)
+            .default_height(600)
+            .build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let recent_menu = gio::Menu::new();
+        root.set_titlebar(Some(&header_bar::<D>(&recent_menu)));
+
+        let notebook = gtk::Notebook::builder()
+            .scrollable(true)
+            .show_border(false)
+            .show_tabs(settings.layout == DocumentLayout::Tabs)
+            .build();
+        let input = sender.input_sender().clone();
+        notebook.connect_switch_page(move |_, _, index| {
+            input.emit(DocumentAppMsg::SwitchedPage(index));
+        });
+        root.set_child(Some(&notebook));
+
+        let input = sender.input_sender().clone();
+        root.connect_is_active_notify(move |window| {
+            if window.is_active() {
+                input.emit(DocumentAppMsg::Focused(None));
+            }
+        });
+
+        let may_close = Rc::new(Cell::new(false));
+        let input = sender.input_sender().clone();
+        let may_close_root = may_close.clone();
+        root.connect_close_request(move |_| {
+            if may_close_root.get() {
+                Propagation::Proceed
+            } else {
+                input.emit(DocumentAppMsg::Quit);
+                Propagation::Stop
+            }
+        });
+
+        register_actions(&root, &sender);
+        if settings.accelerators {
+            let app = relm4::main_application();
+            app.set_accelerators_for_action::<NewAction>(&["<primary>n"]);
+            app.set_accelerators_for_action::<OpenAction>(&["<primary>o"]);
+            app.set_accelerators_for_action::<SaveAction>(&["<primary>s"]);
+            app.set_accelerators_for_action::<SaveAsAction>(&["<primary><shift>s"]);
+            app.set_accelerators_for_action::<CloseAction>(&["<primary>w"]);
+            app.set_accelerators_for_action::<QuitAction>(&["<primary>q"]);
+        }
+
+        let open_dialog = OpenDialog::builder()
+            .transient_for_native(&root)
+            .launch(OpenDialogSettings {
+                filters: D::filters(),
+                ..Default::default()
+            })
+            .forward(sender.input_sender(), |response| match response {
+                OpenDialogResponse::Accept(path) => DocumentAppMsg::OpenPath(path),
+                OpenDialogResponse::Cancel => DocumentAppMsg::DialogCancelled,
+            });
+        let save_dialog = SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(SaveDialogSettings {
+                filters: D::filters(),
+                ..Default::default()
+            })
+            .forward(sender.input_sender(), |response| match response {
+                SaveDialogResponse::Accept(path) => DocumentAppMsg::SaveTo(path),
+                SaveDialogResponse::Cancel => DocumentAppMsg::DialogCancelled,
+            });
+        let recent_files = RecentFiles::builder()
+            .launch(settings.recent_files)
+            .forward(sender.input_sender(), |output| match output {
+                RecentFilesOutput::Changed(files) => DocumentAppMsg::RecentChanged(files),
+            });
+
+        let model = Self {
+            title: settings.title,
+            layout: settings.layout,
+            documents: Vec::new(),
+            next_id: 0,
+            focused: None,
+            pending_save: None,
+            quitting: false,
+            may_close,
+            dialogs: DialogController::new(&root),
+            open_dialog,
+            save_dialog,
+            recent_files,
+        };
+        let widgets = DocumentAppWidgets {
+            notebook,
+            recent_menu,
+        };
+
+        sender.input(DocumentAppMsg::New);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            DocumentAppMsg::New => self.add(D::new(), None, widgets, &sender),
+            DocumentAppMsg::Open => self.open_dialog.emit(OpenDialogMsg::Open),
+            DocumentAppMsg::OpenPath(path) => {
+                if let Some(index) = self.index_of_path(&path) {
+                    self.present(index, widgets);
+                } else {
+                    match D::open(&path) {
+                        Ok(document) => {
+                            // Replace the initial, empty document.
+                            let empty = match &self.documents[..] {
+                                [only] if only.path.is_none() && !only.document.is_dirty() => {
+                                    Some(only.id)
+                                }
+                                _ => None,
+                            };
+                            if let Some(id) = empty {
+                                self.remove(id, widgets, &sender);
+                            }
+                            self.add(document, Some(path.clone()), widgets, &sender);
+                            self.recent_files.emit(RecentFilesMsg::Add(path.clone()));
+                            sender.output(DocumentAppOutput::Opened(path)).ok();
+                        }
+                        Err(error) => {
+                            self.recent_files.emit(RecentFilesMsg::Refresh);
+                            self.show_error(format!("Could not open {}", path.display()), &error);
+                        }
+                    }
+                }
+            }
+            DocumentAppMsg::Save => {
+                if let Some(id) = self.active(widgets) {
+                    self.save(id, false, widgets, &sender);
+                }
+            }
+            DocumentAppMsg::SaveAs => {
+                if let Some(id) = self.active(widgets) {
+                    self.ask_for_path(id, SavePurpose::Save { close: false });
+                }
+            }
+            DocumentAppMsg::Export(format) => {
+                if let Some(id) = self.active(widgets) {
+                    self.ask_for_path(id, SavePurpose::Export(format));
+                }
+            }
+            DocumentAppMsg::Close => {
+                if let Some(id) = self.active(widgets) {
+                    self.close(id, widgets, &sender);
+                }
+            }
+            DocumentAppMsg::CloseDocument(id) => self.close(id, widgets, &sender),
+            DocumentAppMsg::Quit => {
+                self.quitting = true;
+                self.continue_quit(widgets, &sender, root);
+            }
+            DocumentAppMsg::Changed(id) => {
+                if let Some(index) = self.index_of(id) {
+                    self.update_title(index, widgets, root);
+                }
+            }
+            DocumentAppMsg::Focused(focused) => self.focused = focused,
+            // The title of the main window is updated below.
+            DocumentAppMsg::SwitchedPage(_) => {}
+            DocumentAppMsg::SaveTo(path) => {
+                if let Some((id, purpose)) = self.pending_save.take() {
+                    if let Some(index) = self.index_of(id) {
+                        match purpose {
+                            SavePurpose::Save { close } => {
+                                self.write(index, path, close, widgets, &sender);
+                            }
+                            SavePurpose::Export(format) => {
+                                match self.documents[index].document.export(&path, &format) {
+                                    Ok(()) => {
+                                        sender.output(DocumentAppOutput::Exported(path)).ok();
+                                    }
+                                    Err(error) => self.show_error(
+                                        format!("Could not export {}", path.display()),
+                                        &error,
+                                    ),
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            DocumentAppMsg::DialogCancelled => {
+                self.pending_save = None;
+                self.quitting = false;
+            }
+            DocumentAppMsg::CloseResponse(id, response) => match response {
+                Some(AlertResponse::Confirm) => self.save(id, true, widgets, &sender),
+                Some(AlertResponse::Option) => {
+                    self.remove(id, widgets, &sender);
+                    self.continue_quit(widgets, &sender, root);
+                }
+                Some(AlertResponse::Cancel) | None => self.quitting = false,
+            },
+            DocumentAppMsg::RecentChanged(files) => {
+                widgets.recent_menu.remove_all();
+                widgets
+                    .recent_menu
+                    .append_section(None, &recent_files_menu::<OpenRecentAction>(&files));
+            }
+        }
+
+        self.update_window_title(widgets, root);
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        for document in &self.documents {
+            if let Some(window) = &document.window {
+                window.destroy();
+            }
+        }
+    }
+}
+
+impl<D: Document> DocumentApp<D> {
+    fn add(
+        &mut self,
+        document: D,
+        path: Option<PathBuf>,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+    ) {
+        let id = DocumentId(self.next_id);
+        self.next_id += 1;
+
+        let input = sender.input_sender().clone();
+        document.connect_changed(Box::new(move || input.emit(DocumentAppMsg::Changed(id))));
+
+        let number = if path.is_some() {
+            0
+        } else {
+            (1..)
+                .find(|number| {
+                    !self
+                        .documents
+                        .iter()
+                        .any(|other| other.path.is_none() && other.number == *number)
+                })
+                .unwrap_or(1)
+        };
+
+        let page = document.widget();
+        let tab_label = gtk::Label::new(None);
+        let mut entry = OpenDocument {
+            id,
+            document,
+            path,
+            number,
+            page: page.clone(),
+            tab_label: tab_label.clone(),
+            window: None,
+        };
+
+        let in_main_window = self.layout == DocumentLayout::Tabs || widgets.notebook.n_pages() == 0;
+        if in_main_window {
+            let tab = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+            let close = gtk::Button::builder()
+                .icon_name("window-close-symbolic")
+                .tooltip_text("Close Document")
+                .css_classes(["flat", "circular"])
+                .build();
+            let input = sender.input_sender().clone();
+            close.connect_clicked(move |_| input.emit(DocumentAppMsg::CloseDocument(id)));
+            tab.append(&tab_label);
+            tab.append(&close);
+
+            let index = widgets.notebook.append_page(&page, Some(&tab));
+            widgets.notebook.set_tab_reorderable(&page, true);
+            widgets.notebook.set_current_page(Some(index));
+        } else {
+            let window = gtk::Window::builder()
+                .application(&relm4::main_application())
+                .default_width(800)
+                .default_height(600)
+                .child(&page)
+                .build();
+            window.set_titlebar(Some(&header_bar::<D>(&widgets.recent_menu)));
+            register_actions(&window, sender);
+
+            let input = sender.input_sender().clone();
+            window.connect_is_active_notify(move |window| {
+                if window.is_active() {
+                    input.emit(DocumentAppMsg::Focused(Some(id)));
+                }
+            });
+            let input = sender.input_sender().clone();
+            window.connect_close_request(move |_| {
+                input.emit(DocumentAppMsg::CloseDocument(id));
+                Propagation::Stop
+            });
+            window.present();
+            entry.window = Some(window);
+        }
+
+        self.documents.push(entry);
+        let index = self.documents.len() - 1;
+        let document = &self.documents[index];
+        document.tab_label.set_label(&document.title());
+        if let Some(window) = &document.window {
+            window.set_title(Some(&format!("{} – {}", document.title(), self.title)));
+        }
+    }
+
+    fn remove(
+        &mut self,
+        id: DocumentId,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+    ) {
+        let Some(index) = self.index_of(id) else {
+            return;
+        };
+        let document = self.documents.remove(index);
+        match &document.window {
+            Some(window) => window.destroy(),
+            None => {
+                if let Some(page) = widgets.notebook.page_num(&document.page) {
+                    widgets.notebook.remove_page(Some(page));
+                }
+            }
+        }
+        if self.focused == Some(id) {
+            self.focused = None;
+        }
+        sender.output(DocumentAppOutput::Closed(document.path)).ok();
+    }
+
+    fn index_of(&self, id: DocumentId) -> Option<usize> {
+        self.documents.iter().position(|document| document.id == id)
+    }
+
+    fn index_of_path(&self, path: &Path) -> Option<usize> {
+        self.documents
+            .iter()
+            .position(|document| document.path.as_deref() == Some(path))
+    }
+
+    /// The focused document window or the current tab of the main window.
+    fn active(&self, widgets: &DocumentAppWidgets) -> Option<DocumentId> {
+        if let Some(id) = self.focused {
+            return Some(id);
+        }
+        let page = widgets.notebook.nth_page(widgets.notebook.current_page())?;
+        self.documents
+            .iter()
+            .find(|document| document.page == page)
+            .map(|document| document.id)
+    }
+
+    fn present(&self, index: usize, widgets: &DocumentAppWidgets) {
+        let document = &self.documents[index];
+        match &document.window {
+            Some(window) => window.present(),
+            None => {
+                let page = widgets.notebook.page_num(&document.page);
+                widgets.notebook.set_current_page(page);
+            }
+        }
+    }
+
+    fn save(
+        &mut self,
+        id: DocumentId,
+        close: bool,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+    ) {
+        let Some(index) = self.index_of(id) else {
+            return;
+        };
+        match self.documents[index].path.clone() {
+            Some(path) => self.write(index, path, close, widgets, sender),
+            None => self.ask_for_path(id, SavePurpose::Save { close }),
+        }
+    }
+
+    fn ask_for_path(&mut self, id: DocumentId, purpose: SavePurpose) {
+        let Some(index) = self.index_of(id) else {
+            return;
+        };
+        let document = &self.documents[index];
+        let name = match &purpose {
+            SavePurpose::Save { .. } => document.name(),
+            SavePurpose::Export(format) => {
+                let extension = D::export_formats()
+                    .into_iter()
+                    .find(|export| &export.id == format)
+                    .map(|export| export.extension)
+                    .unwrap_or_default();
+                let name = document.name();
+                let stem = Path::new(&name)
+                    .file_stem()
+                    .map_or(name.clone(), |stem| stem.to_string_lossy().into_owned());
+                format!("{stem}.{extension}")
+            }
+        };
+        self.pending_save = Some((id, purpose));
+        self.save_dialog.emit(SaveDialogMsg::SaveAs(name));
+    }
+
+    fn write(
+        &mut self,
+        index: usize,
+        path: PathBuf,
+        close: bool,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+    ) {
+        let document = &mut self.documents[index];
+        match document.document.save(&path) {
+            Ok(()) => {
+                document.path = Some(path.clone());
+                let id = document.id;
+                self.recent_files.emit(RecentFilesMsg::Add(path.clone()));
+                sender.output(DocumentAppOutput::Saved(path)).ok();
+
+                if close {
+                    self.remove(id, widgets, sender);
+                    self.quitting_next(sender);
+                } else {
+                    sender.input(DocumentAppMsg::Changed(id));
+                }
+            }
+            Err(error) => {
+                self.quitting = false;
+                self.show_error(format!("Could not save {}", path.display()), &error);
+            }
+        }
+    }
+
+    fn close(
+        &mut self,
+        id: DocumentId,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+    ) {
+        let Some(index) = self.index_of(id) else {
+            return;
+        };
+        let document = &self.documents[index];
+        if !document.document.is_dirty() {
+            self.remove(id, widgets, sender);
+            return;
+        }
+
+        self.present(index, widgets);
+        let settings = AlertSettings {
+            text: Some(format!("Save changes to “{}”?", document.name())),
+            secondary_text: Some(String::from(
+                "Unsaved changes will be lost permanently if you don't save them.",
+            )),
+            confirm_label: Some(String::from("Save")),
+            cancel_label: Some(String::from("Cancel")),
+            option_label: Some(String::from("Discard")),
+            ..Default::default()
+        };
+        let dialogs = document
+            .window
+            .as_ref()
+            .map_or_else(|| self.dialogs.clone(), DialogController::new);
+        let input = sender.input_sender().clone();
+        relm4::spawn_local(async move {
+            let response = dialogs.open::<Alert>(settings).await;
+            input.emit(DocumentAppMsg::CloseResponse(id, response));
+        });
+    }
+
+    /// Continue quitting after a document was closed.
+    fn quitting_next(&self, sender: &ComponentSender<Self>) {
+        if self.quitting {
+            sender.input(DocumentAppMsg::Quit);
+        }
+    }
+
+    /// Ask to save the next document with unsaved changes or close the application.
+    fn continue_quit(
+        &mut self,
+        widgets: &DocumentAppWidgets,
+        sender: &ComponentSender<Self>,
+        root: &gtk::Window,
+    ) {
+        if !self.quitting {
+            return;
+        }
+
+        let dirty = self
+            .documents
+            .iter()
+            .find(|document| document.document.is_dirty())
+            .map(|document| document.id);
+        match dirty {
+            Some(id) => self.close(id, widgets, sender),
+            None => {
+                let ids: Vec<DocumentId> =
+                    self.documents.iter().map(|document| document.id).collect();
+                for id in ids {
+                    self.remove(id, widgets, sender);
+                }
+                self.may_close.set(true);
+                root.close();
+            }
+        }
+    }
+
+    fn update_title(&self, index: usize, widgets: &DocumentAppWidgets, root: &gtk::Window) {
+        let document = &self.documents[index];
+        let title = document.title();
+        document.tab_label.set_label(&title);
+        if let Some(window) = &document.window {
+            window.set_title(Some(&format!("{title} – {}", self.title)));
+        }
+        self.update_window_title(widgets, root);
+    }
+
+    fn update_window_title(&self, widgets: &DocumentAppWidgets, root: &gtk::Window) {
+        let page = widgets.notebook.nth_page(widgets.notebook.current_page());
+        let document = self
+            .documents
+            .iter()
+            .find(|document| Some(&document.page) == page.as_ref());
+        let title = match document {
+            Some(document) => format!("{} – {}", document.title(), self.title),
+            None => self.title.clone(),
+        };
+        root.set_title(Some(&title));
+    }
+
+    fn show_error(&self, text: String, error: &DocumentError) {
+        let settings = AlertSettings {
+            text: Some(text),
+            secondary_text: Some(error.to_string()),
+            confirm_label: Some(String::from("Close")),
+            ..Default::default()
+        };
+        let dialogs = self.dialogs.clone();
+        relm4::spawn_local(async move {
+            dialogs.open::<Alert>(settings).await;
+        });
+    }
+}
+
+/// Register the actions of the `doc` group for a window.
+fn register_actions<D: Document>(window: &gtk::Window, sender: &ComponentSender<DocumentApp<D>>) {
+    let mut group = RelmActionGroup::<DocumentActionGroup>::new();
+
+    macro_rules! stateless {
+        ($action:ty, $msg:expr) => {{
+            let input = sender.input_sender().clone();
+            group.add_action(RelmAction::<$action>::new_stateless(move |_| {
+                input.emit($msg);
+            }));
+        }};
+    }
+    stateless!(NewAction, DocumentAppMsg::New);
+    stateless!(OpenAction, DocumentAppMsg::Open);
+    stateless!(SaveAction, DocumentAppMsg::Save);
+    stateless!(SaveAsAction, DocumentAppMsg::SaveAs);
+    stateless!(CloseAction, DocumentAppMsg::Close);
+    stateless!(QuitAction, DocumentAppMsg::Quit);
+
+    let input = sender.input_sender().clone();
+    group.add_action(RelmAction::<ExportAction>::new_with_target_value(
+        move |_, format| input.emit(DocumentAppMsg::Export(format)),
+    ));
+    let input = sender.input_sender().clone();
+    group.add_action(RelmAction::<OpenRecentAction>::new_with_target_value(
+        move |_, path| input.emit(DocumentAppMsg::OpenPath(PathBuf::from(path))),
+    ));
+
+    group.register_for_widget(window);
+}
+
+fn header_bar<D: Document>(recent_menu: &gio::Menu) -> gtk::HeaderBar {
+    let header_bar = gtk::HeaderBar::new();
+
+    let open = gtk::Box::builder().css_classes(["linked"]).build();
+    let open_button = gtk::Button::builder()
+        .label("Open")
+        .action_name(OpenAction::action_name())
+        .build();
+    let recent_button = gtk::MenuButton::builder()
+        .menu_model(recent_menu)
+        .tooltip_text("Open Recent")
+        .build();
+    open.append(&open_button);
+    open.append(&recent_button);
+    header_bar.pack_start(&open);
+
+    let new_button = gtk::Button::builder()
+        .icon_name("tab-new-symbolic")
+        .tooltip_text("New Document")
+        .action_name(NewAction::action_name())
+        .build();
+    header_bar.pack_start(&new_button);
+
+    let menu = gio::Menu::new();
+    let file_section = gio::Menu::new();
+    file_section.append_item(&RelmAction::<SaveAsAction>::to_menu_item("Save As…"));
+    let formats = D::export_formats();
+    if !formats.is_empty() {
+        let export = gio::Menu::new();
+        for format in &formats {
+            export.append_item(&RelmAction::<ExportAction>::to_menu_item_with_target_value(
+                &format.label,
+                &format.id,
+            ));
+        }
+        file_section.append_submenu(Some("Export As"), &export);
+    }
+    menu.append_section(None, &file_section);
+    let close_section = gio::Menu::new();
+    close_section.append_item(&RelmAction::<CloseAction>::to_menu_item("Close Document"));
+    close_section.append_item(&RelmAction::<QuitAction>::to_menu_item("Quit"));
+    menu.append_section(None, &close_section);
+
+    let menu_button = gtk::MenuButton::builder()
+        .icon_name("open-menu-symbolic")
+        .tooltip_text("Main Menu")
+        .menu_model(&menu)
+        .build();
+    header_bar.pack_end(&menu_button);
+
+    let save_button = gtk::Button::builder()
+        .label("Save")
+        .action_name(SaveAction::action_name())
+        .build();
+    header_bar.pack_end(&save_button);
+
+    header_bar
+}
@@ -0,0 +1,348 @@
+//! The built-in set of emojis.
+
+use super::EmojiCategory::{self, *};
+
+/// Glyph, name, category and whether the emoji supports skin tones.
+pub(super) const EMOJIS: &[(&str, &str, EmojiCategory, bool)] = &[
+    // Smileys
+    ("😀", "grinning face", Smileys, false),
+    ("😃", "grinning face with big eyes", Smileys, false),
+    ("😄", "grinning face with smiling eyes", Smileys, false),
+    ("😁", "beaming face with smiling eyes", Smileys, false),
+    ("😆", "grinning squinting face", Smileys, false),
+    ("😅", "grinning face with sweat", Smileys, false),
+    ("🤣", "rolling on the floor laughing", Smileys, false),
+    ("😂", "face with tears of joy", Smileys, false),
+    ("🙂", "slightly smiling face", Smileys, false),
+    ("🙃", "upside-down face", Smileys, false),
+    ("😉", "winking face", Smileys, false),
+    ("😊", "smiling face with smiling eyes", Smileys, false),
+    ("😇", "smiling face with halo", Smileys, false),
+    ("🥰", "smiling face with hearts", Smileys, false),
+    ("😍", "smiling face with heart-eyes", Smileys, false),
+    ("🤩", "star-struck", Smileys, false),
+    ("😘", "face blowing a kiss", Smileys, false),
+    ("😋", "face savoring food", Smileys, false),
+    ("😛", "face with tongue", Smileys, false),
+    ("😜", "winking face with tongue", Smileys, false),
+    ("🤪", "zany face", Smileys, false),
+    ("🤗", "smiling face with open hands", Smileys, false),
+    ("🤔", "thinking face", Smileys, false),
+    ("🤐", "zipper-mouth face", Smileys, false),
+    ("😐", "neutral face", Smileys, false),
+    ("😑", "expressionless face", Smileys, false),
+    ("😶", "face without mouth", Smileys, false),
+    ("😏", "smirking face", Smileys, false),
+    ("😒", "unamused face", Smileys, false),
+    ("🙄", "face with rolling eyes", Smileys, false),
+    ("😬", "grimacing face", Smileys, false),
+    ("😌", "relieved face", Smileys, false),
+    ("😔", "pensive face", Smileys, false),
+    ("😪", "sleepy face", Smileys, false),
+    ("😴", "sleeping face", Smileys, false),
+    ("😷", "face with medical mask", Smileys, false),
+    ("🤒", "face with thermometer", Smileys, false),
+    ("🤢", "nauseated face", Smileys, false),
+    ("🥵", "hot face", Smileys, false),
+    ("🥶", "cold face", Smileys, false),
+    ("😵", "face with crossed-out eyes", Smileys, false),
+    ("🤯", "exploding head", Smileys, false),
+    ("🥳", "partying face", Smileys, false),
+    ("😎", "smiling face with sunglasses", Smileys, false),
+    ("🤓", "nerd face", Smileys, false),
+    ("😕", "confused face", Smileys, false),
+    ("😟", "worried face", Smileys, false),
+    ("😮", "face with open mouth", Smileys, false),
+    ("😲", "astonished face", Smileys, false),
+    ("😳", "flushed face", Smileys, false),
+    ("🥺", "pleading face", Smileys, false),
+    ("😢", "crying face", Smileys, false),
+    ("😭", "loudly crying face", Smileys, false),
+    ("😱", "face screaming in fear", Smileys, false),
+    ("😤", "face with steam from nose", Smileys, false),
+    ("😡", "enraged face", Smileys, false),
+    ("😠", "angry face", Smileys, false),
+    ("😈", "smiling face with horns", Smileys, false),
+    ("💀", "skull", Smileys, false),
+    ("💩", "pile of poo", Smileys, false),
+    ("🤡", "clown face", Smileys, false),
+    ("👻", "ghost", Smileys, false),
+    ("👽", "alien", Smileys, false),
+    ("🤖", "robot", Smileys, false),
+    ("😺", "grinning cat", Smileys, false),
+    ("❤️", "red heart", Smileys, false),
+    ("🧡", "orange heart", Smileys, false),
+    ("💛", "yellow heart", Smileys, false),
+    ("💚", "green heart", Smileys, false),
+    ("💙", "blue heart", Smileys, false),
+    ("💜", "purple heart", Smileys, false),
+    ("🖤", "black heart", Smileys, false),
+    ("💔", "broken heart", Smileys, false),
+    ("💯", "hundred points", Smileys, false),
+    ("💥", "collision", Smileys, false),
+    ("💤", "zzz", Smileys, false),
+    // People
+    ("👋", "waving hand", People, true),
+    ("🤚", "raised back of hand", People, true),
+    ("✋", "raised hand", People, true),
+    ("🖖", "vulcan salute", People, true),
+    ("👌", "OK hand", People, true),
+    ("🤌", "pinched fingers", People, true),
+    ("✌️", "victory hand", People, true),
+    ("🤞", "crossed fingers", People, true),
+    ("🤟", "love-you gesture", People, true),
+    ("🤘", "sign of the horns", People, true),
+    ("🤙", "call me hand", People, true),
+    ("👈", "backhand index pointing left", People, true),
+    ("👉", "backhand index pointing right", People, true),
+    ("👆", "backhand index pointing up", People, true),
+    ("👇", "backhand index pointing down", People, true),
+    ("☝️", "index pointing up", People, true),
+    ("👍", "thumbs up", People, true),
+    ("👎", "thumbs down", People, true),
+    ("✊", "raised fist", People, true),
+    ("👊", "oncoming fist", People, true),
+    ("👏", "clapping hands", People, true),
+    ("🙌", "raising hands", People, true),
+    ("👐", "open hands", People, true),
+    ("🤝", "handshake", People, false),
+    ("🙏", "folded hands", People, true),
+    ("✍️", "writing hand", People, true),
+    ("💪", "flexed biceps", People, true),
+    ("👀", "eyes", People, false),
+    ("🧠", "brain", People, false),
+    ("👶", "baby", People, true),
+    ("🧒", "child", People, true),
+    ("👦", "boy", People, true),
+    ("👧", "girl", People, true),
+    ("🧑", "person", People, true),
+    ("👨", "man", People, true),
+    ("👩", "woman", People, true),
+    ("🧓", "older person", People, true),
+    ("🧑‍💻", "technologist", People, true),
+    ("👩‍💻", "woman technologist", People, true),
+    ("👨‍💻", "man technologist", People, true),
+    ("🧑‍🎨", "artist", People, true),
+    ("🧑‍🚀", "astronaut", People, true),
+    ("🤷", "person shrugging", People, true),
+    ("🤦", "person facepalming", People, true),
+    ("🙋", "person raising hand", People, true),
+    ("🏃", "person running", People, true),
+    ("💃", "woman dancing", People, true),
+    // Animals
+    ("🐶", "dog face", Animals, false),
+    ("🐱", "cat face", Animals, false),
+    ("🐭", "mouse face", Animals, false),
+    ("🐰", "rabbit face", Animals, false),
+    ("🦊", "fox", Animals, false),
+    ("🐻", "bear", Animals, false),
+    ("🐼", "panda", Animals, false),
+    ("🐨", "koala", Animals, false),
+    ("🐯", "tiger face", Animals, false),
+    ("🦁", "lion", Animals, false),
+    ("🐮", "cow face", Animals, false),
+    ("🐷", "pig face", Animals, false),
+    ("🐸", "frog", Animals, false),
+    ("🐵", "monkey face", Animals, false),
+    ("🐔", "chicken", Animals, false),
+    ("🐧", "penguin", Animals, false),
+    ("🐦", "bird", Animals, false),
+    ("🦆", "duck", Animals, false),
+    ("🦉", "owl", Animals, false),
+    ("🐝", "honeybee", Animals, false),
+    ("🦋", "butterfly", Animals, false),
+    ("🐌", "snail", Animals, false),
+    ("🐢", "turtle", Animals, false),
+    ("🐍", "snake", Animals, false),
+    ("🦀", "crab", Animals, false),
+    ("🐙", "octopus", Animals, false),
+    ("🐬", "dolphin", Animals, false),
+    ("🐳", "spouting whale", Animals, false),
+    ("🌵", "cactus", Animals, false),
+    ("🌲", "evergreen tree", Animals, false),
+    ("🌻", "sunflower", Animals, false),
+    ("🌹", "rose", Animals, false),
+    ("🍀", "four leaf clover", Animals, false),
+    // Food
+    ("🍏", "green apple", Food, false),
+    ("🍎", "red apple", Food, false),
+    ("🍐", "pear", Food, false),
+    ("🍊", "tangerine", Food, false),
+    ("🍋", "lemon", Food, false),
+    ("🍌", "banana", Food, false),
+    ("🍉", "watermelon", Food, false),
+    ("🍇", "grapes", Food, false),
+    ("🍓", "strawberry", Food, false),
+    ("🍒", "cherries", Food, false),
+    ("🥑", "avocado", Food, false),
+    ("🥕", "carrot", Food, false),
+    ("🌽", "ear of corn", Food, false),
+    ("🥐", "croissant", Food, false),
+    ("🍞", "bread", Food, false),
+    ("🧀", "cheese wedge", Food, false),
+    ("🍔", "hamburger", Food, false),
+    ("🍟", "french fries", Food, false),
+    ("🍕", "pizza", Food, false),
+    ("🌮", "taco", Food, false),
+    ("🍣", "sushi", Food, false),
+    ("🍜", "steaming bowl", Food, false),
+    ("🍩", "doughnut", Food, false),
+    ("🍪", "cookie", Food, false),
+    ("🎂", "birthday cake", Food, false),
+    ("🍫", "chocolate bar", Food, false),
+    ("☕", "hot beverage", Food, false),
+    ("🍵", "teacup without handle", Food, false),
+    ("🍺", "beer mug", Food, false),
+    ("🍷", "wine glass", Food, false),
+    // Activities
+    ("⚽", "soccer ball", Activities, false),
+    ("🏀", "basketball", Activities, false),
+    ("🏈", "american football", Activities, false),
+    ("⚾", "baseball", Activities, false),
+    ("🎾", "tennis", Activities, false),
+    ("🏐", "volleyball", Activities, false),
+    ("🎱", "pool 8 ball", Activities, false),
+    ("🏓", "ping pong", Activities, false),
+    ("⛳", "flag in hole", Activities, false),
+    ("🎣", "fishing pole", Activities, false),
+    ("🎿", "skis", Activities, false),
+    ("🏆", "trophy", Activities, false),
+    ("🥇", "1st place medal", Activities, false),
+    ("🎮", "video game", Activities, false),
+    ("🎲", "game die", Activities, false),
+    ("🧩", "puzzle piece", Activities, false),
+    ("🎨", "artist palette", Activities, false),
+    ("🎸", "guitar", Activities, false),
+    ("🎹", "musical keyboard", Activities, false),
+    ("🎤", "microphone", Activities, false),
+    ("🎧", "headphone", Activities, false),
+    ("🎬", "clapper board", Activities, false),
+    ("🎉", "party popper", Activities, false),
+    ("🎁", "wrapped gift", Activities, false),
+    ("🎄", "christmas tree", Activities, false),
+    // Travel
+    ("🚗", "automobile", Travel, false),
+    ("🚕", "taxi", Travel, false),
+    ("🚌", "bus", Travel, false),
+    ("🚓", "police car", Travel, false),
+    ("🚑", "ambulance", Travel, false),
+    ("🚲", "bicycle", Travel, false),
+    ("🛵", "motor scooter", Travel, false),
+    ("🚂", "locomotive", Travel, false),
+    ("✈️", "airplane", Travel, false),
+    ("🚀", "rocket", Travel, false),
+    ("🛸", "flying saucer", Travel, false),
+    ("🚢", "ship", Travel, false),
+    ("⛵", "sailboat", Travel, false),
+    ("🏠", "house", Travel, false),
+    ("🏢", "office building", Travel, false),
+    ("🏥", "hospital", Travel, false),
+    ("🏰", "castle", Travel, false),
+    ("🗼", "Tokyo tower", Travel, false),
+    ("🗽", "Statue of Liberty", Travel, false),
+    ("⛰️", "mountain", Travel, false),
+    ("🏖️", "beach with umbrella", Travel, false),
+    ("🌍", "globe showing Europe-Africa", Travel, false),
+    ("🌙", "crescent moon", Travel, false),
+    ("☀️", "sun", Travel, false),
+    ("⭐", "star", Travel, false),
+    ("🌈", "rainbow", Travel, false),
+    ("☁️", "cloud", Travel, false),
+    ("⚡", "high voltage", Travel, false),
+    ("❄️", "snowflake", Travel, false),
+    ("🔥", "fire", Travel, false),
+    ("💧", "droplet", Travel, false),
+    // Objects
+    ("⌚", "watch", Objects, false),
+    ("📱", "mobile phone", Objects, false),
+    ("💻", "laptop", Objects, false),
+    ("⌨️", "keyboard", Objects, false),
+    ("🖥️", "desktop computer", Objects, false),
+    ("🖨️", "printer", Objects, false),
+    ("🖱️", "computer mouse", Objects, false),
+    ("💾", "floppy disk", Objects, false),
+    ("📷", "camera", Objects, false),
+    ("📺", "television", Objects, false),
+    ("🔋", "battery", Objects, false),
+    ("🔌", "electric plug", Objects, false),
+    ("💡", "light bulb", Objects, false),
+    ("🔦", "flashlight", Objects, false),
+    ("📚", "books", Objects, false),
+    ("📝", "memo", Objects, false),
+    ("✏️", "pencil", Objects, false),
+    ("📎", "paperclip", Objects, false),
+    ("📌", "pushpin", Objects, false),
+    ("📅", "calendar", Objects, false),
+    ("📈", "chart increasing", Objects, false),
+    ("📦", "package", Objects, false),
+    ("✉️", "envelope", Objects, false),
+    ("🔒", "locked", Objects, false),
+    ("🔑", "key", Objects, false),
+    ("🔨", "hammer", Objects, false),
+    ("🔧", "wrench", Objects, false),
+    ("⚙️", "gear", Objects, false),
+    ("🧪", "test tube", Objects, false),
+    ("🔭", "telescope", Objects, false),
+    ("💊", "pill", Objects, false),
+    ("🛒", "shopping cart", Objects, false),
+    ("💰", "money bag", Objects, false),
+    // Symbols
+    ("✅", "check mark button", Symbols, false),
+    ("✔️", "check mark", Symbols, false),
+    ("❌", "cross mark", Symbols, false),
+    ("❓", "red question mark", Symbols, false),
+    ("❗", "red exclamation mark", Symbols, false),
+    ("⚠️", "warning", Symbols, false),
+    ("🚫", "prohibited", Symbols, false),
+    ("⛔", "no entry", Symbols, false),
+    ("♻️", "recycling symbol", Symbols, false),
+    ("➕", "plus", Symbols, false),
+    ("➖", "minus", Symbols, false),
+    ("➗", "divide", Symbols, false),
+    ("✖️", "multiply", Symbols, false),
+    ("♾️", "infinity", Symbols, false),
+    ("™️", "trade mark", Symbols, false),
+    ("©️", "copyright", Symbols, false),
+    ("®️", "registered", Symbols, false),
+    ("➡️", "right arrow", Symbols, false),
+    ("⬅️", "left arrow", Symbols, false),
+    ("⬆️", "up arrow", Symbols, false),
+    ("⬇️", "down arrow", Symbols, false),
+    ("🔄", "counterclockwise arrows button", Symbols, false),
+    ("🔔", "bell", Symbols, false),
+    ("🔇", "muted speaker", Symbols, false),
+    ("🎵", "musical note", Symbols, false),
+    ("💬", "speech balloon", Symbols, false),
+    ("🔴", "red circle", Symbols, false),
+    ("🟢", "green circle", Symbols, false),
+    ("🔵", "blue circle", Symbols, false),
+    ("⚪", "white circle", Symbols, false),
+    ("⚫", "black circle", Symbols, false),
+    ("🟥", "red square", Symbols, false),
+    ("🟩", "green square", Symbols, false),
+    ("🟦", "blue square", Symbols, false),
+    // Flags
+    ("🏁", "chequered flag", Flags, false),
+    ("🚩", "triangular flag", Flags, false),
+    ("🏳️", "white flag", Flags, false),
+    ("🏴", "black flag", Flags, false),
+    ("🏳️‍🌈", "rainbow flag", Flags, false),
+    ("🇪🇺", "flag: European Union", Flags, false),
+    ("🇺🇳", "flag: United Nations", Flags, false),
+    ("🇧🇷", "flag: Brazil", Flags, false),
+    ("🇨🇦", "flag: Canada", Flags, false),
+    ("🇨🇳", "flag: China", Flags, false),
+    ("🇩🇪", "flag: Germany", Flags, false),
+    ("🇪🇸", "flag: Spain", Flags, false),
+    ("🇫🇷", "flag: France", Flags, false),
+    ("🇬🇧", "flag: United Kingdom", Flags, false),
+    ("🇮🇳", "flag: India", Flags, false),
+    ("🇮🇹", "flag: Italy", Flags, false),
+    ("🇯🇵", "flag: Japan", Flags, false),
+    ("🇰🇷", "flag: South Korea", Flags, false),
+    ("🇲🇽", "flag: Mexico", Flags, false),
+    ("🇳🇱", "flag: Netherlands", Flags, false),
+    ("🇵🇱", "flag: Poland", Flags, false),
+    ("🇺🇦", "flag: Ukraine", Flags, false),
+    ("🇺🇸", "flag: United States", Flags, false),
+];
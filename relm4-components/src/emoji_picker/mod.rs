@@ -0,0 +1,590 @@
+//! Reusable and easily configurable emoji picker component.
+//!
+//! The picker is a [`gtk::Popover`] with a searchable grid of emojis that is split into
+//! categories, a row of frequently used emojis and a skin tone selector.
+//! The usage of emojis can be persisted in a [`gio::Settings`] key.
+
+mod data;
+
+use gtk::prelude::{
+    BoxExt, ButtonExt, EditableExt, PopoverExt, SettingsExtManual, ToggleButtonExt, WidgetExt,
+};
+use relm4::gtk::gio;
+use relm4::typed_view::grid::{RelmGridItem, TypedGridView};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, Sender};
+
+/// The category of an emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmojiCategory {
+    /// Smileys and emotions
+    Smileys,
+    /// People and body parts
+    People,
+    /// Animals and nature
+    Animals,
+    /// Food and drink
+    Food,
+    /// Activities
+    Activities,
+    /// Travel, places and weather
+    Travel,
+    /// Objects
+    Objects,
+    /// Symbols
+    Symbols,
+    /// Flags
+    Flags,
+}
+
+impl EmojiCategory {
+    /// All categories in the order they are shown.
+    pub const ALL: [Self; 9] = [
+        Self::Smileys,
+        Self::People,
+        Self::Animals,
+        Self::Food,
+        Self::Activities,
+        Self::Travel,
+        Self::Objects,
+        Self::Symbols,
+        Self::Flags,
+    ];
+
+    /// The name of the category.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Smileys => "Smileys & Emotion",
+            Self::People => "People & Body",
+            Self::Animals => "Animals & Nature",
+            Self::Food => "Food & Drink",
+            Self::Activities => "Activities",
+            Self::Travel => "Travel & Places",
+            Self::Objects => "Objects",
+            Self::Symbols => "Symbols",
+            Self::Flags => "Flags",
+        }
+    }
+
+    /// The emoji that represents the category.
+    #[must_use]
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Smileys => "😀",
+            Self::People => "👋",
+            Self::Animals => "🐶",
+            Self::Food => "🍏",
+            Self::Activities => "⚽",
+            Self::Travel => "🚗",
+            Self::Objects => "💡",
+            Self::Symbols => "❤️",
+            Self::Flags => "🏁",
+        }
+    }
+}
+
+/// The skin tone of emojis that support skin tone modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SkinTone {
+    /// No modifier.
+    #[default]
+    Default,
+    /// Light skin tone.
+    Light,
+    /// Medium-light skin tone.
+    MediumLight,
+    /// Medium skin tone.
+    Medium,
+    /// Medium-dark skin tone.
+    MediumDark,
+    /// Dark skin tone.
+    Dark,
+}
+
+impl SkinTone {
+    /// All skin tones in the order they are shown.
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::Light,
+        Self::MediumLight,
+        Self::Medium,
+        Self::MediumDark,
+        Self::Dark,
+    ];
+
+    /// The unicode modifier of the skin tone.
+    #[must_use]
+    pub fn modifier(self) -> Option<char> {
+        match self {
+            Self::Default => None,
+            Self::Light => Some('\u{1F3FB}'),
+            Self::MediumLight => Some('\u{1F3FC}'),
+            Self::Medium => Some('\u{1F3FD}'),
+            Self::MediumDark => Some('\u{1F3FE}'),
+            Self::Dark => Some('\u{1F3FF}'),
+        }
+    }
+
+    /// Apply the skin tone to an emoji that supports skin tones.
+    ///
+    /// The modifier is inserted after the first code point
+    /// and replaces a variation selector if there is one.
+    #[must_use]
+    pub fn apply(self, glyph: &str) -> String {
+        let Some(modifier) = self.modifier() else {
+            return glyph.to_owned();
+        };
+
+        let mut chars = glyph.chars();
+        let mut result = String::with_capacity(glyph.len() + 4);
+        result.extend(chars.next());
+        result.push(modifier);
+        let rest = chars.as_str();
+        result.push_str(rest.strip_prefix('\u{FE0F}').unwrap_or(rest));
+        result
+    }
+}
+
+/// An emoji that can be picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emoji {
+    /// The emoji without skin tone modifier.
+    pub glyph: String,
+    /// The name that is used for searching.
+    pub name: String,
+    /// The category of the emoji.
+    pub category: EmojiCategory,
+    /// Whether the emoji supports skin tone modifiers.
+    pub skin_tones: bool,
+}
+
+impl Emoji {
+    /// Create a new emoji.
+    #[must_use]
+    pub fn new(
+        glyph: impl Into<String>,
+        name: impl Into<String>,
+        category: EmojiCategory,
+        skin_tones: bool,
+    ) -> Self {
+        Self {
+            glyph: glyph.into(),
+            name: name.into(),
+            category,
+            skin_tones,
+        }
+    }
+
+    /// The built-in set of emojis.
+    #[must_use]
+    pub fn builtin() -> Vec<Self> {
+        data::EMOJIS
+            .iter()
+            .map(|(glyph, name, category, skin_tones)| {
+                Self::new(*glyph, *name, *category, *skin_tones)
+            })
+            .collect()
+    }
+
+    /// The glyph with the given skin tone if the emoji supports skin tones.
+    #[must_use]
+    pub fn with_skin_tone(&self, skin_tone: SkinTone) -> String {
+        if self.skin_tones {
+            skin_tone.apply(&self.glyph)
+        } else {
+            self.glyph.clone()
+        }
+    }
+}
+
+/// The emoji that was picked by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmojiSelection {
+    /// The emoji including the skin tone modifier.
+    pub emoji: String,
+    /// The emoji without skin tone modifier.
+    pub base: String,
+    /// The name of the emoji.
+    pub name: String,
+    /// The selected skin tone. Emojis without skin tone support
+    /// always use [`SkinTone::Default`].
+    pub skin_tone: SkinTone,
+}
+
+/// Configuration for the emoji picker component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `emojis` is set to the [built-in set](Emoji::builtin).
+/// - `skin_tone` is set to [`SkinTone::Default`].
+/// - `max_frequent` is set to 8.
+/// - `gsettings` is set to [`None`].
+#[derive(Debug, Clone)]
+pub struct EmojiPickerSettings {
+    /// The emojis that can be picked.
+    pub emojis: Vec<Emoji>,
+    /// The initial skin tone.
+    pub skin_tone: SkinTone,
+    /// The number of frequently used emojis that are shown.
+    pub max_frequent: usize,
+    /// A string array key of [`gio::Settings`] that stores the usage of emojis.
+    pub gsettings: Option<(gio::Settings, String)>,
+}
+
+impl Default for EmojiPickerSettings {
+    fn default() -> Self {
+        Self {
+            emojis: Emoji::builtin(),
+            skin_tone: SkinTone::Default,
+            max_frequent: 8,
+            gsettings: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the emoji picker component.
+#[derive(Debug)]
+pub enum EmojiPickerMsg {
+    /// Show the picker.
+    Popup,
+    /// Hide the picker.
+    Popdown,
+    /// Filter the emojis by name.
+    Search(String),
+    /// Show the emojis of a category.
+    ShowCategory(EmojiCategory),
+    /// Change the skin tone.
+    SetSkinTone(SkinTone),
+    #[doc(hidden)]
+    Activate(u32),
+    #[doc(hidden)]
+    Select(usize),
+}
+
+#[derive(Debug)]
+struct EmojiItem {
+    /// Index of the emoji in the model.
+    index: usize,
+    glyph: String,
+    name: String,
+    category: EmojiCategory,
+}
+
+impl RelmGridItem for EmojiItem {
+    type Root = gtk::Label;
+    type Widgets = ();
+
+    fn setup(_item: &gtk::ListItem) -> (gtk::Label, ()) {
+        let label = gtk::Label::new(None);
+        label.add_css_class("title-2");
+        label.set_width_request(36);
+        label.set_height_request(36);
+        (label, ())
+    }
+
+    fn bind(&mut self, _widgets: &mut Self::Widgets, root: &mut Self::Root) {
+        root.set_label(&self.glyph);
+        root.set_tooltip_text(Some(&self.name));
+    }
+}
+
+/// Emoji picker component.
+///
+/// The component is a [`gtk::Popover`] that can be used with
+/// [`gtk::MenuButton::set_popover()`] or attached to any widget.
+/// Picked emojis are sent as [`EmojiSelection`].
+#[derive(Debug)]
+pub struct EmojiPicker {
+    emojis: Vec<Emoji>,
+    grid: TypedGridView<EmojiItem, gtk::NoSelection>,
+    skin_tone: SkinTone,
+    query: String,
+    category: EmojiCategory,
+    /// Base glyphs and how often they were used, most recent first.
+    usage: Vec<(String, u32)>,
+    max_frequent: usize,
+    gsettings: Option<(gio::Settings, String)>,
+}
+
+/// Widgets of the emoji picker component.
+#[derive(Debug)]
+pub struct EmojiPickerWidgets {
+    search: gtk::SearchEntry,
+    frequent: gtk::Box,
+}
+
+impl Component for EmojiPicker {
+    type CommandOutput = ();
+    type Input = EmojiPickerMsg;
+    type Output = EmojiSelection;
+    type Init = EmojiPickerSettings;
+    type Root = gtk::Popover;
+    type Widgets = EmojiPickerWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Popover::new()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        root.set_child(Some(&content));
+
+        let search = gtk::SearchEntry::new();
+        let input = sender.input_sender().clone();
+        search.connect_search_changed(move |entry| {
+            input.emit(EmojiPickerMsg::Search(entry.text().into()));
+        });
+        content.append(&search);
+
+        let frequent = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        content.append(&frequent);
+
+        let categories = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        categories.add_css_class("linked");
+        let mut first: Option<gtk::ToggleButton> = None;
+        for category in EmojiCategory::ALL {
+            let button = toggle_button(category.icon(), category.name(), first.as_ref());
+            let input = sender.input_sender().clone();
+            button.connect_toggled(move |button| {
+                if button.is_active() {
+                    input.emit(EmojiPickerMsg::ShowCategory(category));
+                }
+            });
+            categories.append(&button);
+            first.get_or_insert(button);
+        }
+        content.append(&categories);
+
+        let grid = TypedGridView::new();
+        grid.view.set_max_columns(8);
+        grid.view.set_min_columns(8);
+        grid.view.set_single_click_activate(true);
+        let input = sender.input_sender().clone();
+        grid.view
+            .connect_activate(move |_, position| input.emit(EmojiPickerMsg::Activate(position)));
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_hscrollbar_policy(gtk::PolicyType::Never);
+        scrolled.set_min_content_height(250);
+        scrolled.set_propagate_natural_width(true);
+        scrolled.set_child(Some(&grid.view));
+        content.append(&scrolled);
+
+        let skin_tones = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        skin_tones.set_halign(gtk::Align::End);
+        let mut first: Option<gtk::ToggleButton> = None;
+        for skin_tone in SkinTone::ALL {
+            let button = toggle_button(&skin_tone.apply("✋"), "", first.as_ref());
+            button.set_active(skin_tone == settings.skin_tone);
+            let input = sender.input_sender().clone();
+            button.connect_toggled(move |button| {
+                if button.is_active() {
+                    input.emit(EmojiPickerMsg::SetSkinTone(skin_tone));
+                }
+            });
+            skin_tones.append(&button);
+            first.get_or_insert(button);
+        }
+        content.append(&skin_tones);
+
+        let usage = settings
+            .gsettings
+            .as_ref()
+            .map(|(gsettings, key)| load_usage(&gsettings.strv(key)))
+            .unwrap_or_default();
+
+        let mut model = Self {
+            emojis: settings.emojis,
+            grid,
+            skin_tone: settings.skin_tone,
+            query: String::new(),
+            category: EmojiCategory::ALL[0],
+            usage,
+            max_frequent: settings.max_frequent,
+            gsettings: settings.gsettings,
+        };
+        model.fill_grid();
+        model.apply_filter();
+
+        let widgets = EmojiPickerWidgets { search, frequent };
+        model.fill_frequent(&widgets.frequent, sender.input_sender());
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match input {
+            EmojiPickerMsg::Popup => {
+                widgets.search.set_text("");
+                root.popup();
+                widgets.search.grab_focus();
+            }
+            EmojiPickerMsg::Popdown => root.popdown(),
+            EmojiPickerMsg::Search(query) => {
+                self.query = query.to_lowercase();
+                self.apply_filter();
+            }
+            EmojiPickerMsg::ShowCategory(category) => {
+                self.category = category;
+                self.apply_filter();
+            }
+            EmojiPickerMsg::SetSkinTone(skin_tone) => {
+                self.skin_tone = skin_tone;
+                self.fill_grid();
+                self.fill_frequent(&widgets.frequent, sender.input_sender());
+            }
+            EmojiPickerMsg::Activate(position) => {
+                if let Some(item) = self.grid.get_visible(position) {
+                    let index = item.borrow().index;
+                    sender.input(EmojiPickerMsg::Select(index));
+                }
+            }
+            EmojiPickerMsg::Select(index) => {
+                let Some(emoji) = self.emojis.get(index) else {
+                    return;
+                };
+                let selection = EmojiSelection {
+                    emoji: emoji.with_skin_tone(self.skin_tone),
+                    base: emoji.glyph.clone(),
+                    name: emoji.name.clone(),
+                    skin_tone: if emoji.skin_tones {
+                        self.skin_tone
+                    } else {
+                        SkinTone::Default
+                    },
+                };
+
+                self.record_usage(&selection.base);
+                self.fill_frequent(&widgets.frequent, sender.input_sender());
+                root.popdown();
+                sender.output(selection).ok();
+            }
+        }
+    }
+}
+
+impl EmojiPicker {
+    /// The currently selected skin tone.
+    #[must_use]
+    pub fn skin_tone(&self) -> SkinTone {
+        self.skin_tone
+    }
+
+    /// The most frequently used emojis without skin tone modifier.
+    #[must_use]
+    pub fn frequent(&self) -> Vec<String> {
+        let mut usage: Vec<&(String, u32)> = self.usage.iter().collect();
+        // The sort is stable, so recently used emojis come first on ties.
+        usage.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        usage
+            .into_iter()
+            .take(self.max_frequent)
+            .map(|(glyph, _)| glyph.clone())
+            .collect()
+    }
+
+    fn fill_grid(&mut self) {
+        self.grid.clear();
+        let items = self
+            .emojis
+            .iter()
+            .enumerate()
+            .map(|(index, emoji)| EmojiItem {
+                index,
+                glyph: emoji.with_skin_tone(self.skin_tone),
+                name: emoji.name.clone(),
+                category: emoji.category,
+            });
+        self.grid.extend_from_iter(items);
+    }
+
+    /// Show the emojis of the current category or all
+    /// emojis that match the search query.
+    fn apply_filter(&mut self) {
+        self.grid.clear_filters();
+        if self.query.is_empty() {
+            let category = self.category;
+            self.grid.add_filter(move |item| item.category == category);
+        } else {
+            let query = self.query.clone();
+            self.grid.add_filter(move |item| {
+                item.glyph == query || item.name.to_lowercase().contains(&query)
+            });
+        }
+    }
+
+    fn fill_frequent(&self, container: &gtk::Box, input: &Sender<EmojiPickerMsg>) {
+        while let Some(child) = container.first_child() {
+            container.remove(&child);
+        }
+
+        for glyph in self.frequent() {
+            let Some(index) = self.emojis.iter().position(|emoji| emoji.glyph == glyph) else {
+                continue;
+            };
+            let emoji = &self.emojis[index];
+            let button = gtk::Button::with_label(&emoji.with_skin_tone(self.skin_tone));
+            button.add_css_class("flat");
+            button.set_tooltip_text(Some(&emoji.name));
+            let input = input.clone();
+            button.connect_clicked(move |_| input.emit(EmojiPickerMsg::Select(index)));
+            container.append(&button);
+        }
+        container.set_visible(container.first_child().is_some());
+    }
+
+    fn record_usage(&mut self, glyph: &str) {
+        let count = self
+            .usage
+            .iter()
+            .position(|(used, _)| used == glyph)
+            .map_or(0, |index| self.usage.remove(index).1);
+        self.usage.insert(0, (glyph.to_owned(), count + 1));
+
+        if let Some((gsettings, key)) = &self.gsettings {
+            let entries: Vec<String> = self
+                .usage
+                .iter()
+                .map(|(glyph, count)| format!("{count} {glyph}"))
+                .collect();
+            let entries: Vec<&str> = entries.iter().map(String::as_str).collect();
+            // The usage is only a convenience, so failing to store it isn't fatal.
+            gsettings.set_strv(key, entries.as_slice()).ok();
+        }
+    }
+}
+
+/// Parse the usage entries stored as `"{count} {glyph}"`.
+fn load_usage(entries: &[gtk::glib::GStringPtr]) -> Vec<(String, u32)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (count, glyph) = entry.to_str().split_once(' ')?;
+            Some((glyph.to_owned(), count.parse().ok()?))
+        })
+        .collect()
+}
+
+fn toggle_button(
+    label: &str,
+    tooltip: &str,
+    group: Option<&gtk::ToggleButton>,
+) -> gtk::ToggleButton {
+    let button = gtk::ToggleButton::with_label(label);
+    button.add_css_class("flat");
+    if !tooltip.is_empty() {
+        button.set_tooltip_text(Some(tooltip));
+    }
+    button.set_group(group);
+    button
+}
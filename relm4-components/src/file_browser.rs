@@ -0,0 +1,652 @@
+//! Reusable and easily configurable file browser component.
+//!
+//! The component shows a tree of directories next to a list of the files in
+//! the selected directory. Directories are enumerated on a background thread
+//! when they are opened or expanded and are watched with file monitors,
+//! so the views stay up to date when files are changed by other applications.
+//!
+//! Hidden files are toggled with the stateful `file-browser.show-hidden` action
+//! that is registered on the root widget, so it can also be used from menus.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use relm4::actions::{RelmAction, RelmActionGroup};
+use relm4::gtk::prelude::{
+    ActionableExt, BoxExt, ButtonExt, Cast, CastNone, FileEnumeratorExt, FileExt, FileMonitorExt,
+    ListItemExt, ListModelExt, ObjectExt, SelectionModelExt, WidgetExt,
+};
+use relm4::gtk::{gio, glib};
+use relm4::typed_view::list::{RelmListItem, TypedListView};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, Sender};
+
+const ATTRIBUTES: &str = "standard::name,standard::display-name,standard::type,\
+    standard::is-hidden,standard::is-backup,standard::size,standard::content-type";
+
+relm4::new_action_group!(FileBrowserActionGroup, "file-browser");
+relm4::new_stateful_action!(
+    ShowHiddenAction,
+    FileBrowserActionGroup,
+    "show-hidden",
+    (),
+    bool
+);
+
+/// A file or directory in a [`FileBrowser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// The file itself.
+    pub file: gio::File,
+    /// The name of the file on disk.
+    pub name: String,
+    /// The name of the file that is shown to the user.
+    pub display_name: String,
+    /// Whether the file is a directory.
+    pub is_dir: bool,
+    /// Whether the file is hidden or a backup file.
+    pub is_hidden: bool,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The guessed content type of the file.
+    pub content_type: Option<String>,
+}
+
+/// Configuration for the file browser component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `root` is set to the home directory of the user.
+/// - `directory` is set to [`None`], so the root is opened.
+/// - `show_hidden` is set to [`false`].
+#[derive(Debug, Clone)]
+pub struct FileBrowserSettings {
+    /// The top-level directory of the directory tree.
+    pub root: gio::File,
+    /// The directory that is opened initially.
+    pub directory: Option<gio::File>,
+    /// Show hidden and backup files.
+    pub show_hidden: bool,
+}
+
+impl Default for FileBrowserSettings {
+    fn default() -> Self {
+        Self {
+            root: gio::File::for_path(glib::home_dir()),
+            directory: None,
+            show_hidden: false,
+        }
+    }
+}
+
+/// Messages that can be sent to the file browser component.
+#[derive(Debug)]
+pub enum FileBrowserMsg {
+    /// Open a directory without emitting an output.
+    SetDirectory(gio::File),
+    /// Open the parent of the current directory.
+    GoUp,
+    /// Enumerate the current directory again.
+    Reload,
+    /// Show or hide hidden and backup files.
+    SetShowHidden(bool),
+    #[doc(hidden)]
+    Navigate(gio::File),
+    #[doc(hidden)]
+    Load(gio::File),
+    #[doc(hidden)]
+    Activate(u32),
+    #[doc(hidden)]
+    SelectionChanged,
+}
+
+/// Messages that are sent by the file browser component.
+#[derive(Debug, Clone)]
+pub enum FileBrowserOutput {
+    /// The user opened a directory.
+    DirectoryChanged(gio::File),
+    /// The selected files in the file list changed.
+    Selected(Vec<gio::File>),
+    /// The user activated a file that isn't a directory.
+    Activated(gio::File),
+    /// A directory couldn't be enumerated.
+    LoadFailed {
+        /// The directory that was enumerated.
+        directory: gio::File,
+        /// The reason of the failure.
+        error: glib::Error,
+    },
+}
+
+#[derive(Debug)]
+struct FileItem {
+    entry: FileEntry,
+}
+
+#[derive(Debug)]
+struct FileItemWidgets {
+    icon: gtk::Image,
+    name: gtk::Label,
+    size: gtk::Label,
+}
+
+impl RelmListItem for FileItem {
+    type Root = gtk::Box;
+    type Widgets = FileItemWidgets;
+
+    fn setup(_item: &gtk::ListItem) -> (gtk::Box, FileItemWidgets) {
+        let root = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        let icon = gtk::Image::new();
+        let name = gtk::Label::new(None);
+        name.set_hexpand(true);
+        name.set_xalign(0.0);
+        name.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+        let size = gtk::Label::new(None);
+        size.add_css_class("dim-label");
+        size.add_css_class("numeric");
+
+        root.append(&icon);
+        root.append(&name);
+        root.append(&size);
+        (root, FileItemWidgets { icon, name, size })
+    }
+
+    fn bind(&mut self, widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        widgets.icon.set_from_gicon(&entry_icon(&self.entry));
+        widgets.name.set_label(&self.entry.display_name);
+        if self.entry.is_dir {
+            widgets.size.set_label("");
+        } else {
+            widgets.size.set_label(&glib::format_size(self.entry.size));
+        }
+    }
+}
+
+/// The stores of the directory tree by URI.
+type TreeStores = Rc<RefCell<HashMap<String, glib::WeakRef<gio::ListStore>>>>;
+
+/// File browser component.
+#[derive(Debug)]
+pub struct FileBrowser {
+    root_directory: gio::File,
+    directory: gio::File,
+    show_hidden: bool,
+    show_hidden_action: RelmAction<ShowHiddenAction>,
+    files: TypedListView<FileItem, gtk::MultiSelection>,
+    tree_stores: TreeStores,
+    /// The last known entries of directories by URI.
+    listings: HashMap<String, Vec<FileEntry>>,
+    monitors: HashMap<String, gio::FileMonitor>,
+    /// Directories that are currently enumerated.
+    pending: HashSet<String>,
+}
+
+/// The widgets of the file browser component.
+#[derive(Debug)]
+pub struct FileBrowserWidgets {
+    path: gtk::Label,
+    up: gtk::Button,
+}
+
+impl Component for FileBrowser {
+    type CommandOutput = (gio::File, Result<Vec<FileEntry>, glib::Error>);
+    type Input = FileBrowserMsg;
+    type Output = FileBrowserOutput;
+    type Init = FileBrowserSettings;
+    type Root = gtk::Box;
+    type Widgets = FileBrowserWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Vertical, 0)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        // Header with navigation and the hidden file toggle
+        let header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        header.set_margin_top(6);
+        header.set_margin_bottom(6);
+        header.set_margin_start(6);
+        header.set_margin_end(6);
+
+        let up = gtk::Button::from_icon_name("go-up-symbolic");
+        up.set_tooltip_text(Some("Open the parent directory"));
+        let input = sender.input_sender().clone();
+        up.connect_clicked(move |_| input.emit(FileBrowserMsg::GoUp));
+        header.append(&up);
+
+        let path = gtk::Label::new(None);
+        path.set_hexpand(true);
+        path.set_xalign(0.0);
+        path.set_ellipsize(gtk::pango::EllipsizeMode::Start);
+        header.append(&path);
+
+        let hidden = gtk::ToggleButton::new();
+        hidden.set_icon_name("view-reveal-symbolic");
+        hidden.set_tooltip_text(Some("Show hidden files"));
+        hidden.set_action_name(Some("file-browser.show-hidden"));
+        header.append(&hidden);
+        root.append(&header);
+
+        let input = sender.input_sender().clone();
+        let show_hidden_action =
+            RelmAction::<ShowHiddenAction>::new_stateful(&settings.show_hidden, move |_, show| {
+                *show = !*show;
+                input.emit(FileBrowserMsg::SetShowHidden(*show));
+            });
+        let mut group = RelmActionGroup::<FileBrowserActionGroup>::new();
+        group.add_action(show_hidden_action.clone());
+        group.register_for_widget(&root);
+
+        // Directory tree
+        let tree_root = gio::ListStore::new::<glib::BoxedAnyObject>();
+        let tree_stores = TreeStores::default();
+        tree_stores
+            .borrow_mut()
+            .insert(settings.root.uri().into(), tree_root.downgrade());
+
+        let input = sender.input_sender().clone();
+        let stores = tree_stores.clone();
+        let tree_model = gtk::TreeListModel::new(tree_root, false, false, move |item| {
+            let entry = item.downcast_ref::<glib::BoxedAnyObject>()?;
+            let file = entry.borrow::<FileEntry>().file.clone();
+
+            // The children are filled once the directory is enumerated.
+            let store = gio::ListStore::new::<glib::BoxedAnyObject>();
+            stores
+                .borrow_mut()
+                .insert(file.uri().into(), store.downgrade());
+            input.emit(FileBrowserMsg::Load(file));
+            Some(store.upcast())
+        });
+
+        let tree_selection = gtk::SingleSelection::new(Some(tree_model));
+        tree_selection.set_autoselect(false);
+        tree_selection.set_can_unselect(true);
+        let input = sender.input_sender().clone();
+        tree_selection.connect_selected_item_notify(move |selection| {
+            let file = selection
+                .selected_item()
+                .and_downcast::<gtk::TreeListRow>()
+                .and_then(|row| row.item())
+                .and_downcast::<glib::BoxedAnyObject>()
+                .map(|entry| entry.borrow::<FileEntry>().file.clone());
+            if let Some(file) = file {
+                input.emit(FileBrowserMsg::Navigate(file));
+            }
+        });
+
+        let tree = gtk::ListView::new(Some(tree_selection), Some(tree_factory()));
+        tree.add_css_class("navigation-sidebar");
+
+        // File list
+        let files = TypedListView::<FileItem, gtk::MultiSelection>::new();
+        let input = sender.input_sender().clone();
+        files
+            .view
+            .connect_activate(move |_, position| input.emit(FileBrowserMsg::Activate(position)));
+        let input = sender.input_sender().clone();
+        files
+            .selection_model
+            .connect_selection_changed(move |_, _, _| {
+                input.emit(FileBrowserMsg::SelectionChanged);
+            });
+
+        let paned = gtk::Paned::new(gtk::Orientation::Horizontal);
+        paned.set_vexpand(true);
+        paned.set_position(200);
+        paned.set_start_child(Some(&scrolled(&tree)));
+        paned.set_end_child(Some(&scrolled(&files.view)));
+        root.append(&paned);
+
+        let directory = settings.directory.unwrap_or_else(|| settings.root.clone());
+        let model = Self {
+            root_directory: settings.root.clone(),
+            directory: directory.clone(),
+            show_hidden: settings.show_hidden,
+            show_hidden_action,
+            files,
+            tree_stores,
+            listings: HashMap::new(),
+            monitors: HashMap::new(),
+            pending: HashSet::new(),
+        };
+        let widgets = FileBrowserWidgets { path, up };
+
+        sender.input(FileBrowserMsg::Load(settings.root));
+        sender.input(FileBrowserMsg::SetDirectory(directory));
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match input {
+            FileBrowserMsg::SetDirectory(directory) => {
+                self.open(widgets, &sender, directory);
+            }
+            FileBrowserMsg::Navigate(directory) => {
+                if !directory.equal(&self.directory) {
+                    self.open(widgets, &sender, directory.clone());
+                    sender
+                        .output(FileBrowserOutput::DirectoryChanged(directory))
+                        .ok();
+                }
+            }
+            FileBrowserMsg::GoUp => {
+                if let Some(parent) = self.directory.parent() {
+                    sender.input(FileBrowserMsg::Navigate(parent));
+                }
+            }
+            FileBrowserMsg::Reload => {
+                sender.input(FileBrowserMsg::Load(self.directory.clone()));
+            }
+            FileBrowserMsg::SetShowHidden(show_hidden) => {
+                if show_hidden != self.show_hidden {
+                    self.show_hidden = show_hidden;
+                    self.show_hidden_action.set_state(&show_hidden);
+                    let uris: Vec<String> = self.listings.keys().cloned().collect();
+                    for uri in uris {
+                        self.refresh(&uri);
+                    }
+                }
+            }
+            FileBrowserMsg::Load(directory) => {
+                // Coalesce bursts of file monitor events.
+                if self.pending.insert(directory.uri().into()) {
+                    sender.spawn_oneshot_command(move || {
+                        let entries = enumerate(&directory);
+                        (directory, entries)
+                    });
+                }
+            }
+            FileBrowserMsg::Activate(position) => {
+                let Some(item) = self.files.get_visible(position) else {
+                    return;
+                };
+                let entry = item.borrow().entry.clone();
+                if entry.is_dir {
+                    sender.input(FileBrowserMsg::Navigate(entry.file));
+                } else {
+                    sender.output(FileBrowserOutput::Activated(entry.file)).ok();
+                }
+            }
+            FileBrowserMsg::SelectionChanged => {
+                let selected = (0..self.files.selection_model.n_items())
+                    .filter(|position| self.files.selection_model.is_selected(*position))
+                    .filter_map(|position| self.files.get_visible(position))
+                    .map(|item| item.borrow().entry.file.clone())
+                    .collect();
+                sender.output(FileBrowserOutput::Selected(selected)).ok();
+            }
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        (directory, entries): Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        let uri: String = directory.uri().into();
+        self.pending.remove(&uri);
+
+        match entries {
+            Ok(entries) => {
+                self.listings.insert(uri.clone(), entries);
+                self.watch(&directory, &uri, sender.input_sender());
+                self.refresh(&uri);
+            }
+            Err(error) => {
+                sender
+                    .output(FileBrowserOutput::LoadFailed { directory, error })
+                    .ok();
+            }
+        }
+        self.prune();
+    }
+}
+
+impl FileBrowser {
+    /// The directory that is shown in the file list.
+    #[must_use]
+    pub fn directory(&self) -> &gio::File {
+        &self.directory
+    }
+
+    /// The top-level directory of the directory tree.
+    #[must_use]
+    pub fn root_directory(&self) -> &gio::File {
+        &self.root_directory
+    }
+
+    /// Whether hidden and backup files are shown.
+    #[must_use]
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    fn open(
+        &mut self,
+        widgets: &FileBrowserWidgets,
+        sender: &ComponentSender<Self>,
+        directory: gio::File,
+    ) {
+        let path = directory
+            .path()
+            .map_or_else(|| directory.uri().into(), |path| path.display().to_string());
+        widgets.path.set_label(&path);
+        widgets
+            .up
+            .set_sensitive(directory.has_parent(None::<&gio::File>));
+
+        self.directory = directory;
+        self.files.clear();
+        let uri: String = self.directory.uri().into();
+        if self.listings.contains_key(&uri) {
+            self.refresh(&uri);
+        }
+        sender.input(FileBrowserMsg::Load(self.directory.clone()));
+    }
+
+    /// Update the views that show the directory with the given URI.
+    fn refresh(&mut self, uri: &str) {
+        let Some(entries) = self.listings.get(uri) else {
+            return;
+        };
+        let visible = entries
+            .iter()
+            .filter(|entry| self.show_hidden || !entry.is_hidden);
+
+        let store = self
+            .tree_stores
+            .borrow()
+            .get(uri)
+            .and_then(|store| store.upgrade());
+        if let Some(store) = store {
+            merge_directories(&store, visible.clone().filter(|entry| entry.is_dir));
+        }
+
+        if uri == self.directory.uri() {
+            let items: Vec<FileItem> = visible.cloned().map(|entry| FileItem { entry }).collect();
+            self.files.clear();
+            self.files.extend_from_iter(items);
+        }
+    }
+
+    fn watch(&mut self, directory: &gio::File, uri: &str, input: &Sender<FileBrowserMsg>) {
+        if self.monitors.contains_key(uri) {
+            return;
+        }
+        let Ok(monitor) = directory.monitor_directory(
+            gio::FileMonitorFlags::WATCH_MOVES,
+            None::<&gio::Cancellable>,
+        ) else {
+            return;
+        };
+
+        let input = input.clone();
+        let directory = directory.clone();
+        monitor.connect_changed(move |_, _, _, event| {
+            if event != gio::FileMonitorEvent::ChangesDoneHint {
+                // The monitor might report events after the component was dropped.
+                input.send(FileBrowserMsg::Load(directory.clone())).ok();
+            }
+        });
+        self.monitors.insert(uri.to_owned(), monitor);
+    }
+
+    /// Stop watching directories that are no longer shown.
+    fn prune(&mut self) {
+        let stores = self.tree_stores.borrow();
+        let current: String = self.directory.uri().into();
+        self.monitors.retain(|uri, monitor| {
+            let shown = *uri == current
+                || stores
+                    .get(uri)
+                    .is_some_and(|store| store.upgrade().is_some());
+            if !shown {
+                monitor.cancel();
+            }
+            shown
+        });
+        drop(stores);
+
+        self.tree_stores
+            .borrow_mut()
+            .retain(|_, store| store.upgrade().is_some());
+        let monitors = &self.monitors;
+        self.listings.retain(|uri, _| monitors.contains_key(uri));
+    }
+}
+
+/// Wrap `child` in a [`gtk::ScrolledWindow`].
+fn scrolled(child: &impl gtk::prelude::IsA<gtk::Widget>) -> gtk::ScrolledWindow {
+    let window = gtk::ScrolledWindow::new();
+    window.set_hscrollbar_policy(gtk::PolicyType::Never);
+    window.set_child(Some(child));
+    window
+}
+
+/// Create the factory for the rows of the directory tree.
+fn tree_factory() -> gtk::SignalListItemFactory {
+    let factory = gtk::SignalListItemFactory::new();
+    factory.connect_setup(|_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        content.append(&gtk::Image::from_icon_name("folder-symbolic"));
+        content.append(&gtk::Label::new(None));
+
+        let expander = gtk::TreeExpander::new();
+        expander.set_child(Some(&content));
+        item.set_child(Some(&expander));
+    });
+    factory.connect_bind(|_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        let Some(expander) = item.child().and_downcast::<gtk::TreeExpander>() else {
+            return;
+        };
+        let row = item.item().and_downcast::<gtk::TreeListRow>();
+        let name = row
+            .as_ref()
+            .and_then(gtk::TreeListRow::item)
+            .and_downcast::<glib::BoxedAnyObject>()
+            .map(|entry| entry.borrow::<FileEntry>().display_name.clone())
+            .unwrap_or_default();
+
+        expander.set_list_row(row.as_ref());
+        if let Some(label) = expander
+            .child()
+            .and_then(|content| content.last_child())
+            .and_downcast::<gtk::Label>()
+        {
+            label.set_label(&name);
+        }
+    });
+    factory
+}
+
+/// Update `store` to contain `entries` while keeping unchanged rows,
+/// so expanded directories stay expanded.
+fn merge_directories<'a>(
+    store: &gio::ListStore,
+    entries: impl Iterator<Item = &'a FileEntry> + Clone,
+) {
+    let name_of = |obj: &glib::Object| {
+        obj.downcast_ref::<glib::BoxedAnyObject>()
+            .map(|entry| entry.borrow::<FileEntry>().name.clone())
+    };
+
+    let names: HashSet<&str> = entries.clone().map(|entry| entry.name.as_str()).collect();
+    for position in (0..store.n_items()).rev() {
+        let stale = store
+            .item(position)
+            .and_then(|obj| name_of(&obj))
+            .map_or(true, |name| !names.contains(name.as_str()));
+        if stale {
+            store.remove(position);
+        }
+    }
+
+    // The remaining rows are in the same order as the entries.
+    for (position, entry) in (0u32..).zip(entries) {
+        let kept = store
+            .item(position)
+            .and_then(|obj| name_of(&obj))
+            .is_some_and(|name| name == entry.name);
+        if !kept {
+            store.insert(position, &glib::BoxedAnyObject::new(entry.clone()));
+        }
+    }
+}
+
+/// The icon of a file from the icon theme.
+fn entry_icon(entry: &FileEntry) -> gio::Icon {
+    if entry.is_dir {
+        return gio::ThemedIcon::new("folder-symbolic").upcast();
+    }
+    let content_type = entry
+        .content_type
+        .as_deref()
+        .unwrap_or("application/octet-stream");
+    gio::content_type_get_symbolic_icon(content_type)
+}
+
+/// Enumerate the entries of `directory`, sorted with directories first.
+///
+/// This blocks, so it should run on a background thread.
+fn enumerate(directory: &gio::File) -> Result<Vec<FileEntry>, glib::Error> {
+    let enumerator = directory.enumerate_children(
+        ATTRIBUTES,
+        gio::FileQueryInfoFlags::NONE,
+        None::<&gio::Cancellable>,
+    )?;
+
+    let mut entries = Vec::new();
+    while let Some(info) = enumerator.next_file(None::<&gio::Cancellable>)? {
+        entries.push(FileEntry {
+            file: directory.child(info.name()),
+            name: info.name().to_string_lossy().into_owned(),
+            display_name: info.display_name().into(),
+            is_dir: info.file_type() == gio::FileType::Directory,
+            is_hidden: info.is_hidden() || info.is_backup(),
+            size: u64::try_from(info.size()).unwrap_or_default(),
+            content_type: info.content_type().map(Into::into),
+        });
+    }
+
+    entries.sort_by_cached_key(|entry| (!entry.is_dir, entry.display_name.to_lowercase()));
+    Ok(entries)
+}
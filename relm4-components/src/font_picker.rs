@@ -0,0 +1,188 @@
+//! Reusable and easily configurable font chooser component.
+//!
+//! The component opens a [`gtk::FontDialog`] and shows the selected font in a preview label.
+//! The font is also stored as string in a [`StringBinding`], so other widgets can bind to it,
+//! and can optionally be persisted in a [`gio::Settings`] key.
+use gtk::prelude::{ObjectExt, SettingsExt};
+use relm4::binding::{Binding, StringBinding};
+use relm4::gtk::{gio, glib, pango};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, MainWindowRegistry};
+
+#[derive(Clone, Debug)]
+/// Configuration for the font picker component
+pub struct FontPickerSettings {
+    /// Title of the font dialog
+    pub title: Option<String>,
+    /// Freeze other windows while the dialog is open
+    pub is_modal: bool,
+    /// The initially selected font
+    pub font: Option<pango::FontDescription>,
+    /// Text that is shown in the preview label
+    pub preview_text: String,
+    /// Binding that stores the selected font as string.
+    /// If [`None`], a new binding is created.
+    pub binding: Option<StringBinding>,
+    /// A string key of [`gio::Settings`] that stores the selected font.
+    /// The stored font replaces the initial font.
+    pub gsettings: Option<(gio::Settings, String)>,
+}
+
+impl Default for FontPickerSettings {
+    fn default() -> Self {
+        FontPickerSettings {
+            title: None,
+            is_modal: true,
+            font: None,
+            preview_text: String::from("The quick brown fox jumps over the lazy dog"),
+            binding: None,
+            gsettings: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Model for the font picker component
+pub struct FontPicker {
+    dialog: gtk::FontDialog,
+    font: Option<pango::FontDescription>,
+    binding: StringBinding,
+    gsettings: Option<(gio::Settings, String)>,
+}
+
+/// Messages that can be sent to the font picker component
+#[derive(Debug, Clone)]
+pub enum FontPickerMsg {
+    /// Show the dialog
+    Open,
+    /// Select a font without emitting an output
+    SetFont(pango::FontDescription),
+    #[doc(hidden)]
+    Response(Option<pango::FontDescription>),
+}
+
+/// Messages that can be sent from the font picker component
+#[derive(Debug, Clone)]
+pub enum FontPickerResponse {
+    /// User selected a font.
+    Accept(pango::FontDescription),
+    /// User canceled the dialog.
+    Cancel,
+}
+
+impl Component for FontPicker {
+    type CommandOutput = ();
+    type Input = FontPickerMsg;
+    type Output = FontPickerResponse;
+    type Init = FontPickerSettings;
+    type Root = gtk::Label;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::Label::default()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        _sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let dialog = gtk::FontDialog::new();
+        dialog.set_modal(settings.is_modal);
+        if let Some(title) = &settings.title {
+            dialog.set_title(title);
+        }
+
+        root.set_label(&settings.preview_text);
+        let binding = settings.binding.unwrap_or_default();
+        // Keep the preview in sync with the binding, even if others change it.
+        binding.connect_notify_local(
+            Some(StringBinding::property_name()),
+            glib::clone!(
+                #[weak]
+                root,
+                move |binding, _| set_preview_font(&root, &binding.get())
+            ),
+        );
+        set_preview_font(&root, &binding.get());
+
+        let stored = settings
+            .gsettings
+            .as_ref()
+            .map(|(gsettings, key)| gsettings.string(key))
+            .filter(|font| !font.is_empty())
+            .map(|font| pango::FontDescription::from_string(&font));
+
+        let mut model = FontPicker {
+            dialog,
+            font: None,
+            binding,
+            gsettings: settings.gsettings,
+        };
+        if let Some(font) = stored.or(settings.font) {
+            model.set_font(font);
+        }
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            FontPickerMsg::Open => {
+                let parent = MainWindowRegistry::transient_parent_for(root);
+                let input = sender.input_sender().clone();
+                self.dialog.choose_font(
+                    parent.as_ref(),
+                    self.font.as_ref(),
+                    None::<&gio::Cancellable>,
+                    move |result| input.emit(FontPickerMsg::Response(result.ok())),
+                );
+            }
+            FontPickerMsg::SetFont(font) => self.set_font(font),
+            FontPickerMsg::Response(Some(font)) => {
+                self.set_font(font.clone());
+                if let Some((gsettings, key)) = &self.gsettings {
+                    if let Err(error) = gsettings.set_string(key, &font.to_str()) {
+                        tracing::error!("Cannot store font in settings: {error}");
+                    }
+                }
+                sender.output(FontPickerResponse::Accept(font)).ok();
+            }
+            FontPickerMsg::Response(None) => {
+                sender.output(FontPickerResponse::Cancel).ok();
+            }
+        }
+    }
+}
+
+impl FontPicker {
+    /// The currently selected font.
+    #[must_use]
+    pub fn font(&self) -> Option<&pango::FontDescription> {
+        self.font.as_ref()
+    }
+
+    /// The binding that stores the selected font as string.
+    ///
+    /// Other widgets can bind to it to preview the font as well.
+    #[must_use]
+    pub fn binding(&self) -> &StringBinding {
+        &self.binding
+    }
+
+    fn set_font(&mut self, font: pango::FontDescription) {
+        // Updates the preview through the notify handler.
+        self.binding.set(font.to_str().into());
+        self.font = Some(font);
+    }
+}
+
+/// Show the text of `label` in the font described by `font`.
+fn set_preview_font(label: &gtk::Label, font: &str) {
+    let attributes = pango::AttrList::new();
+    if !font.is_empty() {
+        attributes.insert(pango::AttrFontDesc::new(
+            &pango::FontDescription::from_string(font),
+        ));
+    }
+    label.set_attributes(Some(&attributes));
+}
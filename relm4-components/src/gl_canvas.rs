@@ -0,0 +1,346 @@
+//! Reusable OpenGL canvas component based on [`gtk::GLArea`].
+//!
+//! The component takes care of the context setup and calls a [`GlRenderer`]
+//! whenever the area needs to be drawn. Rendering is triggered by messages of
+//! the component, so redrawing is tied to the state changes of the renderer:
+//!
+//! ```no_run
+//! use relm4_components::gl_canvas::{GlContext, GlRenderer};
+//!
+//! struct Triangle {
+//!     color: [f32; 3],
+//! }
+//!
+//! impl GlRenderer for Triangle {
+//!     type Message = [f32; 3];
+//!
+//!     fn update(&mut self, color: Self::Message) -> bool {
+//!         self.color = color;
+//!         true
+//!     }
+//!
+//!     fn render(&mut self, ctx: &GlContext, size: (i32, i32)) {
+//!         // Load the functions with the GL binding of your choice,
+//!         // for example `glow::Context::from_loader_function(|name| ctx.proc_address(name))`.
+//!     }
+//! }
+//! ```
+//!
+//! GL functions are resolved with `libepoxy`, which GTK itself uses for OpenGL.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use relm4::gtk::prelude::{GLAreaExt, GLContextExt, WidgetExt, WidgetExtManual};
+use relm4::gtk::{gdk, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const EPOXY: &str = "libepoxy.so.0";
+#[cfg(target_os = "macos")]
+const EPOXY: &str = "libepoxy.0.dylib";
+#[cfg(windows)]
+const EPOXY: &str = "libepoxy-0.dll";
+
+static LIBRARY: Lazy<Option<libloading::Library>> = Lazy::new(|| {
+    // SAFETY: GTK already loaded libepoxy, so no initialization routines are run.
+    unsafe { libloading::Library::new(EPOXY) }.ok()
+});
+
+/// The drawing state that is passed to a [`GlRenderer`].
+///
+/// The GL context is always current while the renderer is called.
+#[derive(Debug)]
+pub struct GlContext {
+    context: gdk::GLContext,
+    frame_time: Option<Duration>,
+}
+
+impl GlContext {
+    fn new(area: &gtk::GLArea) -> Option<Self> {
+        Some(Self {
+            context: area.context()?,
+            frame_time: area
+                .frame_clock()
+                .map(|clock| Duration::from_micros(clock.frame_time().max(0).unsigned_abs())),
+        })
+    }
+
+    /// Returns the address of an OpenGL function, like `glClear`,
+    /// or a null pointer if the function isn't available.
+    ///
+    /// This can be passed to the loader of any GL binding.
+    #[must_use]
+    pub fn proc_address(&self, name: &str) -> *const c_void {
+        let Some(library) = &*LIBRARY else {
+            return std::ptr::null();
+        };
+        let symbol = format!("epoxy_{name}\0");
+        // SAFETY: libepoxy exports each function as a global variable
+        // that holds a pointer to the implementation.
+        unsafe {
+            library
+                .get::<*const *const c_void>(symbol.as_bytes())
+                .map_or(std::ptr::null(), |variable| **variable)
+        }
+    }
+
+    /// Returns the OpenGL version as `(major, minor)`.
+    #[must_use]
+    pub fn version(&self) -> (i32, i32) {
+        self.context.version()
+    }
+
+    /// Returns `true` if the context uses OpenGL ES instead of desktop OpenGL.
+    #[must_use]
+    pub fn uses_es(&self) -> bool {
+        self.context.uses_es()
+    }
+
+    /// Returns the time of the frame that is drawn, if the area is shown.
+    ///
+    /// The time is monotonic, so it can be used for animations.
+    #[must_use]
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.frame_time
+    }
+
+    /// Returns the underlying GDK context.
+    #[must_use]
+    pub fn gdk_context(&self) -> &gdk::GLContext {
+        &self.context
+    }
+}
+
+/// The drawing code of a [`GlCanvas`].
+///
+/// The renderer lives as long as the component, but GL resources must be created
+/// in [`realize`](Self::realize) and released in [`unrealize`](Self::unrealize),
+/// because the context is recreated if the area is moved to another window.
+pub trait GlRenderer: 'static {
+    /// Messages that change the state of the renderer.
+    type Message: Debug + 'static;
+
+    /// Handles a message and returns `true` if the canvas needs to be redrawn.
+    fn update(&mut self, message: Self::Message) -> bool {
+        let _ = message;
+        true
+    }
+
+    /// Called after the GL context was created.
+    fn realize(&mut self, ctx: &GlContext) {
+        let _ = ctx;
+    }
+
+    /// Draws a frame. `size` is the size of the framebuffer in pixels.
+    fn render(&mut self, ctx: &GlContext, size: (i32, i32));
+
+    /// Called when the size of the framebuffer changed.
+    fn resize(&mut self, ctx: &GlContext, size: (i32, i32)) {
+        let _ = (ctx, size);
+    }
+
+    /// Called before the GL context is destroyed.
+    fn unrealize(&mut self, ctx: &GlContext) {
+        let _ = ctx;
+    }
+}
+
+/// Configuration for the GL canvas component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `required_version` is set to [`None`], so GTK picks the version.
+/// - `has_depth_buffer` is set to `false`.
+/// - `has_stencil_buffer` is set to `false`.
+/// - `auto_render` is set to `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlCanvasSettings {
+    /// The minimal OpenGL version as `(major, minor)`.
+    pub required_version: Option<(i32, i32)>,
+    /// Whether the framebuffer has a depth buffer.
+    pub has_depth_buffer: bool,
+    /// Whether the framebuffer has a stencil buffer.
+    pub has_stencil_buffer: bool,
+    /// Whether the canvas is redrawn after each resize.
+    /// If `false`, frames are only drawn when they are requested by messages.
+    pub auto_render: bool,
+}
+
+impl Default for GlCanvasSettings {
+    fn default() -> Self {
+        Self {
+            required_version: None,
+            has_depth_buffer: false,
+            has_stencil_buffer: false,
+            auto_render: true,
+        }
+    }
+}
+
+/// Messages that can be sent to the GL canvas component.
+#[derive(Debug)]
+pub enum GlCanvasMsg<M> {
+    /// Pass a message to the renderer and redraw if it returns `true`.
+    Update(M),
+    /// Draw a new frame.
+    QueueRender,
+    /// Draw a frame on each refresh of the display while `true`.
+    SetAnimating(bool),
+}
+
+/// Messages that are sent by the GL canvas component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlCanvasOutput {
+    /// The GL context was created.
+    Realized {
+        /// The OpenGL version as `(major, minor)`.
+        version: (i32, i32),
+        /// Whether the context uses OpenGL ES.
+        uses_es: bool,
+    },
+    /// The size of the framebuffer changed.
+    Resized {
+        /// The width in pixels.
+        width: i32,
+        /// The height in pixels.
+        height: i32,
+    },
+    /// The GL context couldn't be created.
+    Error(String),
+}
+
+/// OpenGL canvas component.
+pub struct GlCanvas<R: GlRenderer> {
+    renderer: Rc<RefCell<R>>,
+    tick: Option<gtk::TickCallbackId>,
+}
+
+impl<R: GlRenderer> Debug for GlCanvas<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlCanvas")
+            .field("animating", &self.tick.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: GlRenderer> Component for GlCanvas<R> {
+    type CommandOutput = ();
+    type Input = GlCanvasMsg<R::Message>;
+    type Output = GlCanvasOutput;
+    type Init = (R, GlCanvasSettings);
+    type Root = gtk::GLArea;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        let area = gtk::GLArea::new();
+        area.set_hexpand(true);
+        area.set_vexpand(true);
+        area
+    }
+
+    fn init(
+        (renderer, settings): Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        if let Some((major, minor)) = settings.required_version {
+            root.set_required_version(major, minor);
+        }
+        root.set_has_depth_buffer(settings.has_depth_buffer);
+        root.set_has_stencil_buffer(settings.has_stencil_buffer);
+        root.set_auto_render(settings.auto_render);
+
+        let renderer = Rc::new(RefCell::new(renderer));
+
+        {
+            let renderer = renderer.clone();
+            let sender = sender.clone();
+            root.connect_realize(move |area| {
+                area.make_current();
+                if let Some(error) = area.error() {
+                    // The parent might not listen to the output, so ignore send errors.
+                    sender.output(GlCanvasOutput::Error(error.to_string())).ok();
+                    return;
+                }
+                if let Some(ctx) = GlContext::new(area) {
+                    renderer.borrow_mut().realize(&ctx);
+                    sender
+                        .output(GlCanvasOutput::Realized {
+                            version: ctx.version(),
+                            uses_es: ctx.uses_es(),
+                        })
+                        .ok();
+                }
+            });
+        }
+        {
+            let renderer = renderer.clone();
+            root.connect_unrealize(move |area| {
+                area.make_current();
+                if area.error().is_some() {
+                    return;
+                }
+                if let Some(ctx) = GlContext::new(area) {
+                    renderer.borrow_mut().unrealize(&ctx);
+                }
+            });
+        }
+        {
+            let renderer = renderer.clone();
+            root.connect_render(move |area, _| {
+                if let Some(ctx) = GlContext::new(area) {
+                    let scale = area.scale_factor();
+                    let size = (area.width() * scale, area.height() * scale);
+                    renderer.borrow_mut().render(&ctx, size);
+                }
+                glib::Propagation::Stop
+            });
+        }
+        {
+            let renderer = renderer.clone();
+            root.connect_resize(move |area, width, height| {
+                if let Some(ctx) = GlContext::new(area) {
+                    renderer.borrow_mut().resize(&ctx, (width, height));
+                }
+                sender
+                    .output(GlCanvasOutput::Resized { width, height })
+                    .ok();
+            });
+        }
+
+        let model = Self {
+            renderer,
+            tick: None,
+        };
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            GlCanvasMsg::Update(message) => {
+                if self.renderer.borrow_mut().update(message) {
+                    root.queue_render();
+                }
+            }
+            GlCanvasMsg::QueueRender => root.queue_render(),
+            GlCanvasMsg::SetAnimating(true) => {
+                if self.tick.is_none() {
+                    self.tick = Some(root.add_tick_callback(|area, _| {
+                        area.queue_render();
+                        glib::ControlFlow::Continue
+                    }));
+                }
+            }
+            GlCanvasMsg::SetAnimating(false) => {
+                if let Some(tick) = self.tick.take() {
+                    tick.remove();
+                }
+            }
+        }
+    }
+}
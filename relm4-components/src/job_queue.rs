@@ -0,0 +1,699 @@
+//! Reusable panel for long-running jobs, like downloads or exports.
+//!
+//! Jobs are futures that run on the async runtime of Relm4 or blocking
+//! closures that run on its blocking thread pool. The panel limits how many
+//! jobs run at the same time, shows their progress with buttons to cancel
+//! and retry them and summarizes the queue in a bar at the top.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use relm4::factory::{FactoryComponent, FactoryHashMap, FactorySender};
+use relm4::gtk::prelude::{BoxExt, ButtonExt, OrientableExt, WidgetExt};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, RelmWidgetExt, Sender};
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type JobFn = Arc<dyn Fn(JobContext) -> JobFuture + Send + Sync>;
+
+/// The unique identifier of a [`Job`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobId(u64);
+
+impl JobId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Handle that is passed to a running job to report its progress.
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    id: JobId,
+    attempt: u32,
+    input: Sender<JobQueueMsg>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobContext {
+    /// The identifier of the job.
+    #[must_use]
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// The number of the current attempt, starting at 0.
+    #[must_use]
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Report the progress of the job between 0 and 1.
+    pub fn set_progress(&self, fraction: f64) {
+        // The job might outlive the component.
+        self.input
+            .send(JobQueueMsg::Progress(self.id, self.attempt, fraction))
+            .ok();
+    }
+
+    /// Show a short status text, like the remaining time.
+    pub fn set_status(&self, status: impl Into<String>) {
+        self.input
+            .send(JobQueueMsg::Status(self.id, self.attempt, status.into()))
+            .ok();
+    }
+
+    /// Returns true if the job was cancelled.
+    ///
+    /// Futures are dropped when they are cancelled, but blocking
+    /// jobs should check this regularly and return early.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A job that can be added to a [`JobQueue`].
+pub struct Job {
+    id: JobId,
+    title: String,
+    max_retries: u32,
+    run: JobFn,
+}
+
+impl fmt::Debug for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("max_retries", &self.max_retries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Job {
+    /// Create a job from a function that returns a future.
+    ///
+    /// The function is called again for every retry.
+    pub fn new<F, Fut>(title: impl Into<String>, run: F) -> Self
+    where
+        F: Fn(JobContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        Self {
+            id: JobId::next(),
+            title: title.into(),
+            max_retries: 0,
+            run: Arc::new(move |context| Box::pin(run(context))),
+        }
+    }
+
+    /// Create a job from a blocking function that runs on a background thread.
+    ///
+    /// The function is called again for every retry.
+    pub fn blocking<F>(title: impl Into<String>, run: F) -> Self
+    where
+        F: Fn(JobContext) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let run = Arc::new(run);
+        Self::new(title, move |context| {
+            let run = run.clone();
+            async move {
+                relm4::spawn_blocking(move || run(context))
+                    .await
+                    .unwrap_or_else(|error| Err(error.to_string()))
+            }
+        })
+    }
+
+    /// Retry the job automatically up to `max_retries` times if it fails.
+    #[must_use]
+    pub fn with_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// The identifier of the job, which can be used to cancel it.
+    #[must_use]
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// The title of the job.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+/// The state of a job in a [`JobQueue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobState {
+    /// The job waits for other jobs to finish.
+    Queued,
+    /// The job is running.
+    Running,
+    /// The job finished successfully.
+    Succeeded,
+    /// The job failed with the given error.
+    Failed(String),
+    /// The job was cancelled.
+    Cancelled,
+}
+
+impl JobState {
+    /// Returns true if the job is neither queued nor running.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// Configuration for the job queue component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `max_concurrent` is set to 2.
+/// - `remove_succeeded` is set to [`false`].
+#[derive(Debug, Clone)]
+pub struct JobQueueSettings {
+    /// The number of jobs that run at the same time.
+    pub max_concurrent: usize,
+    /// Remove jobs from the panel as soon as they succeed.
+    pub remove_succeeded: bool,
+}
+
+impl Default for JobQueueSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 2,
+            remove_succeeded: false,
+        }
+    }
+}
+
+/// Messages that can be sent to the job queue component.
+#[derive(Debug)]
+pub enum JobQueueMsg {
+    /// Add a job to the end of the queue.
+    Add(Job),
+    /// Cancel a queued or running job.
+    Cancel(JobId),
+    /// Run a failed or cancelled job again.
+    Retry(JobId),
+    /// Remove a finished job from the panel.
+    Remove(JobId),
+    /// Cancel all queued and running jobs.
+    CancelAll,
+    /// Remove all finished jobs from the panel.
+    ClearFinished,
+    /// Change the number of jobs that run at the same time.
+    SetMaxConcurrent(usize),
+    #[doc(hidden)]
+    Progress(JobId, u32, f64),
+    #[doc(hidden)]
+    Status(JobId, u32, String),
+    #[doc(hidden)]
+    Finished(JobId, u32, Result<(), String>),
+}
+
+/// Messages that are sent by the job queue component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobQueueOutput {
+    /// A job started running.
+    Started(JobId),
+    /// A job finished successfully.
+    Succeeded(JobId),
+    /// A job failed after all retries.
+    Failed(JobId, String),
+    /// A job was cancelled.
+    Cancelled(JobId),
+    /// All jobs are finished.
+    Idle,
+}
+
+struct JobEntry {
+    job: Job,
+    state: JobState,
+    attempt: u32,
+    /// Automatic retries that are left after a failure.
+    retries_left: u32,
+    cancelled: Arc<AtomicBool>,
+    handle: Option<relm4::JoinHandle<()>>,
+}
+
+impl fmt::Debug for JobEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobEntry")
+            .field("job", &self.job)
+            .field("state", &self.state)
+            .field("attempt", &self.attempt)
+            .field("retries_left", &self.retries_left)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Job queue component.
+#[derive(Debug)]
+pub struct JobQueue {
+    jobs: HashMap<JobId, JobEntry>,
+    queue: VecDeque<JobId>,
+    rows: FactoryHashMap<JobId, JobRow>,
+    max_concurrent: usize,
+    remove_succeeded: bool,
+}
+
+/// The widgets of the job queue component.
+#[derive(Debug)]
+pub struct JobQueueWidgets {
+    revealer: gtk::Revealer,
+    summary: gtk::Label,
+    progress: gtk::ProgressBar,
+}
+
+impl Component for JobQueue {
+    type CommandOutput = ();
+    type Input = JobQueueMsg;
+    type Output = JobQueueOutput;
+    type Init = JobQueueSettings;
+    type Root = gtk::Box;
+    type Widgets = JobQueueWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Vertical, 0)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let bar = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        bar.set_margin_all(6);
+
+        let summary = gtk::Label::new(None);
+        summary.set_xalign(0.0);
+        let progress = gtk::ProgressBar::new();
+        progress.set_hexpand(true);
+        progress.set_valign(gtk::Align::Center);
+
+        let clear = gtk::Button::with_label("Clear");
+        clear.set_tooltip_text(Some("Remove finished jobs"));
+        let input = sender.input_sender().clone();
+        clear.connect_clicked(move |_| input.emit(JobQueueMsg::ClearFinished));
+
+        let cancel = gtk::Button::with_label("Cancel All");
+        let input = sender.input_sender().clone();
+        cancel.connect_clicked(move |_| input.emit(JobQueueMsg::CancelAll));
+
+        bar.append(&summary);
+        bar.append(&progress);
+        bar.append(&clear);
+        bar.append(&cancel);
+
+        let revealer = gtk::Revealer::new();
+        revealer.set_child(Some(&bar));
+        root.append(&revealer);
+
+        let list = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_hscrollbar_policy(gtk::PolicyType::Never);
+        scrolled.set_propagate_natural_height(true);
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&list));
+        root.append(&scrolled);
+
+        let rows = FactoryHashMap::builder()
+            .launch(list)
+            .forward(sender.input_sender(), |msg| msg);
+
+        let model = Self {
+            jobs: HashMap::new(),
+            queue: VecDeque::new(),
+            rows,
+            max_concurrent: settings.max_concurrent.max(1),
+            remove_succeeded: settings.remove_succeeded,
+        };
+        let widgets = JobQueueWidgets {
+            revealer,
+            summary,
+            progress,
+        };
+        model.update_summary(&widgets);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        let was_busy = self.running() > 0 || !self.queue.is_empty();
+
+        match input {
+            JobQueueMsg::Add(job) => {
+                let id = job.id;
+                self.rows.insert(id, job.title.clone());
+                self.jobs.insert(
+                    id,
+                    JobEntry {
+                        retries_left: job.max_retries,
+                        job,
+                        state: JobState::Queued,
+                        attempt: 0,
+                        cancelled: Arc::default(),
+                        handle: None,
+                    },
+                );
+                self.queue.push_back(id);
+            }
+            JobQueueMsg::Cancel(id) => self.cancel(id, &sender),
+            JobQueueMsg::Retry(id) => {
+                if let Some(entry) = self.jobs.get_mut(&id) {
+                    if entry.state.is_finished() && entry.state != JobState::Succeeded {
+                        entry.attempt += 1;
+                        entry.retries_left = entry.job.max_retries;
+                        self.set_state(id, JobState::Queued);
+                        self.queue.push_back(id);
+                    }
+                }
+            }
+            JobQueueMsg::Remove(id) => {
+                if self
+                    .jobs
+                    .get(&id)
+                    .is_some_and(|entry| entry.state.is_finished())
+                {
+                    self.jobs.remove(&id);
+                    self.rows.remove(&id);
+                }
+            }
+            JobQueueMsg::CancelAll => {
+                let ids: Vec<JobId> = self.jobs.keys().copied().collect();
+                for id in ids {
+                    self.cancel(id, &sender);
+                }
+            }
+            JobQueueMsg::ClearFinished => {
+                let finished: Vec<JobId> = self
+                    .jobs
+                    .iter()
+                    .filter(|(_, entry)| entry.state.is_finished())
+                    .map(|(id, _)| *id)
+                    .collect();
+                for id in finished {
+                    self.jobs.remove(&id);
+                    self.rows.remove(&id);
+                }
+            }
+            JobQueueMsg::SetMaxConcurrent(max_concurrent) => {
+                self.max_concurrent = max_concurrent.max(1);
+            }
+            JobQueueMsg::Progress(id, attempt, fraction) => {
+                if self.is_running(id, attempt) {
+                    if let Some(mut row) = self.rows.get_mut(&id) {
+                        row.progress = Some(fraction.clamp(0.0, 1.0));
+                    }
+                }
+            }
+            JobQueueMsg::Status(id, attempt, status) => {
+                if self.is_running(id, attempt) {
+                    if let Some(mut row) = self.rows.get_mut(&id) {
+                        row.status = status;
+                    }
+                }
+            }
+            JobQueueMsg::Finished(id, attempt, result) => {
+                if !self.is_running(id, attempt) {
+                    return;
+                }
+                match result {
+                    Ok(()) => {
+                        self.set_state(id, JobState::Succeeded);
+                        if self.remove_succeeded {
+                            self.jobs.remove(&id);
+                            self.rows.remove(&id);
+                        }
+                        sender.output(JobQueueOutput::Succeeded(id)).ok();
+                    }
+                    Err(error) => {
+                        let Some(entry) = self.jobs.get_mut(&id) else {
+                            return;
+                        };
+                        if entry.retries_left > 0 {
+                            entry.retries_left -= 1;
+                            entry.attempt += 1;
+                            self.set_state(id, JobState::Queued);
+                            // Retries don't have to wait for the whole queue.
+                            self.queue.push_front(id);
+                        } else {
+                            self.set_state(id, JobState::Failed(error.clone()));
+                            sender.output(JobQueueOutput::Failed(id, error)).ok();
+                        }
+                    }
+                }
+            }
+        }
+
+        self.start_jobs(&sender);
+        if was_busy && self.running() == 0 && self.queue.is_empty() {
+            sender.output(JobQueueOutput::Idle).ok();
+        }
+        self.update_summary(widgets);
+    }
+}
+
+impl JobQueue {
+    /// The state of a job, or [`None`] if the job was removed.
+    #[must_use]
+    pub fn state(&self, id: JobId) -> Option<&JobState> {
+        self.jobs.get(&id).map(|entry| &entry.state)
+    }
+
+    /// The number of jobs that are currently running.
+    #[must_use]
+    pub fn running(&self) -> usize {
+        self.jobs
+            .values()
+            .filter(|entry| entry.state == JobState::Running)
+            .count()
+    }
+
+    /// The number of jobs that wait for other jobs.
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn is_running(&self, id: JobId, attempt: u32) -> bool {
+        self.jobs
+            .get(&id)
+            .is_some_and(|entry| entry.state == JobState::Running && entry.attempt == attempt)
+    }
+
+    fn set_state(&mut self, id: JobId, state: JobState) {
+        if let Some(mut row) = self.rows.get_mut(&id) {
+            if state == JobState::Queued {
+                row.progress = None;
+                row.status.clear();
+            }
+            row.state = state.clone();
+        }
+        if let Some(entry) = self.jobs.get_mut(&id) {
+            entry.state = state;
+        }
+    }
+
+    fn cancel(&mut self, id: JobId, sender: &ComponentSender<Self>) {
+        let Some(entry) = self.jobs.get_mut(&id) else {
+            return;
+        };
+        if entry.state.is_finished() {
+            return;
+        }
+
+        entry.cancelled.store(true, Ordering::Relaxed);
+        if let Some(handle) = entry.handle.take() {
+            handle.abort();
+        }
+        self.queue.retain(|queued| *queued != id);
+        self.set_state(id, JobState::Cancelled);
+        sender.output(JobQueueOutput::Cancelled(id)).ok();
+    }
+
+    /// Start queued jobs until the concurrency limit is reached.
+    fn start_jobs(&mut self, sender: &ComponentSender<Self>) {
+        while self.running() < self.max_concurrent {
+            let Some(id) = self.queue.pop_front() else {
+                break;
+            };
+            let Some(entry) = self.jobs.get_mut(&id) else {
+                continue;
+            };
+
+            entry.cancelled = Arc::default();
+            let context = JobContext {
+                id,
+                attempt: entry.attempt,
+                input: sender.input_sender().clone(),
+                cancelled: entry.cancelled.clone(),
+            };
+            let input = sender.input_sender().clone();
+            let attempt = entry.attempt;
+            let future = (entry.job.run)(context);
+            entry.handle = Some(relm4::spawn(async move {
+                let result = future.await;
+                input.send(JobQueueMsg::Finished(id, attempt, result)).ok();
+            }));
+
+            self.set_state(id, JobState::Running);
+            sender.output(JobQueueOutput::Started(id)).ok();
+        }
+    }
+
+    fn update_summary(&self, widgets: &JobQueueWidgets) {
+        let failed = self
+            .jobs
+            .values()
+            .filter(|entry| matches!(entry.state, JobState::Failed(_)))
+            .count();
+
+        let mut parts = vec![format!("{} running", self.running())];
+        if !self.queue.is_empty() {
+            parts.push(format!("{} queued", self.queue.len()));
+        }
+        if failed > 0 {
+            parts.push(format!("{failed} failed"));
+        }
+        widgets.summary.set_label(&parts.join(", "));
+
+        // The overall progress of all jobs that are shown.
+        let total: f64 = self
+            .rows
+            .values()
+            .map(|row| match row.state {
+                JobState::Running | JobState::Queued => row.progress.unwrap_or_default(),
+                _ => 1.0,
+            })
+            .sum();
+        let fraction = if self.rows.is_empty() {
+            0.0
+        } else {
+            total / self.rows.len() as f64
+        };
+        widgets.progress.set_fraction(fraction);
+        widgets.revealer.set_reveal_child(!self.jobs.is_empty());
+    }
+}
+
+#[derive(Debug)]
+struct JobRow {
+    id: JobId,
+    title: String,
+    state: JobState,
+    progress: Option<f64>,
+    status: String,
+}
+
+impl JobRow {
+    fn status_text(&self) -> String {
+        match &self.state {
+            JobState::Queued => "Queued".into(),
+            JobState::Running if self.status.is_empty() => "Running".into(),
+            JobState::Running => self.status.clone(),
+            JobState::Succeeded => "Done".into(),
+            JobState::Failed(error) => format!("Failed: {error}"),
+            JobState::Cancelled => "Cancelled".into(),
+        }
+    }
+}
+
+#[relm4::factory]
+impl FactoryComponent for JobRow {
+    type Init = String;
+    type Input = ();
+    type Output = JobQueueMsg;
+    type CommandOutput = ();
+    type ParentWidget = gtk::Box;
+    type Index = JobId;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Horizontal,
+            set_spacing: 6,
+            set_margin_all: 6,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 3,
+                set_hexpand: true,
+
+                gtk::Label {
+                    set_xalign: 0.0,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                    set_label: &self.title,
+                },
+                gtk::ProgressBar {
+                    #[watch]
+                    set_visible: self.state == JobState::Running,
+                    #[watch]
+                    set_fraction: self.progress.unwrap_or_default(),
+                },
+                gtk::Label {
+                    set_xalign: 0.0,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                    add_css_class: "dim-label",
+                    #[watch]
+                    set_label: &self.status_text(),
+                },
+            },
+            gtk::Button {
+                set_icon_name: "view-refresh-symbolic",
+                set_tooltip_text: Some("Retry"),
+                set_valign: gtk::Align::Center,
+                #[watch]
+                set_visible: matches!(self.state, JobState::Failed(_) | JobState::Cancelled),
+                connect_clicked[sender, id = self.id] => move |_| {
+                    sender.output(JobQueueMsg::Retry(id)).ok();
+                },
+            },
+            gtk::Button {
+                set_icon_name: "process-stop-symbolic",
+                set_tooltip_text: Some("Cancel"),
+                set_valign: gtk::Align::Center,
+                #[watch]
+                set_visible: !self.state.is_finished(),
+                connect_clicked[sender, id = self.id] => move |_| {
+                    sender.output(JobQueueMsg::Cancel(id)).ok();
+                },
+            },
+            gtk::Button {
+                set_icon_name: "window-close-symbolic",
+                set_tooltip_text: Some("Remove"),
+                set_valign: gtk::Align::Center,
+                #[watch]
+                set_visible: self.state.is_finished(),
+                connect_clicked[sender, id = self.id] => move |_| {
+                    sender.output(JobQueueMsg::Remove(id)).ok();
+                },
+            },
+        }
+    }
+
+    fn init_model(title: Self::Init, id: &Self::Index, _sender: FactorySender<Self>) -> Self {
+        Self {
+            id: *id,
+            title,
+            state: JobState::Queued,
+            progress: None,
+            status: String::new(),
+        }
+    }
+}
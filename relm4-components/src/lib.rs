@@ -35,13 +35,71 @@
 #![allow(deprecated)]
 
 pub mod alert;
+pub mod clipboard_history;
+#[cfg(feature = "color_picker")]
+#[cfg_attr(docsrs, doc(cfg(feature = "color_picker")))]
+pub mod color_picker;
+#[cfg(feature = "diff_view")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diff_view")))]
+pub mod diff_view;
+pub mod docking;
+pub mod document_app;
+pub mod emoji_picker;
+pub mod file_browser;
+#[cfg(feature = "font_picker")]
+#[cfg_attr(docsrs, doc(cfg(feature = "font_picker")))]
+pub mod font_picker;
+#[cfg(feature = "gl_canvas")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gl_canvas")))]
+pub mod gl_canvas;
+pub mod job_queue;
+#[cfg(feature = "log_view")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log_view")))]
+pub mod log_view;
+#[cfg(feature = "map")]
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+pub mod map_view;
+pub mod marked_scale;
+#[cfg(feature = "oauth")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oauth")))]
+pub mod oauth_login;
+pub mod offline_banner;
 pub mod open_button;
 pub mod open_dialog;
+#[cfg(feature = "password_field")]
+#[cfg_attr(docsrs, doc(cfg(feature = "password_field")))]
+pub mod password_field;
+pub mod recent_files;
 pub mod save_dialog;
+#[cfg(all(feature = "screen_recorder", unix))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "screen_recorder", unix))))]
+pub mod screen_recorder;
+pub mod searchable_list;
+pub mod shortcut_editor;
 #[cfg(feature = "libadwaita")]
 pub mod simple_adw_combo_row;
 pub mod simple_combo_box;
+#[cfg(feature = "spellcheck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "spellcheck")))]
+pub mod spell_language;
+pub mod star_rating;
+pub mod status_bar;
+#[cfg(feature = "libadwaita")]
+#[cfg_attr(docsrs, doc(cfg(feature = "libadwaita")))]
+pub mod tab_manager;
+
+#[cfg(feature = "vte")]
+#[cfg_attr(docsrs, doc(cfg(feature = "vte")))]
+pub mod terminal;
 
 #[cfg(feature = "web")]
 #[cfg_attr(docsrs, doc(cfg(feature = "web")))]
 pub mod web_image;
+
+#[cfg(feature = "websocket")]
+#[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+pub mod websocket;
+
+#[cfg(feature = "wgpu_canvas")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wgpu_canvas")))]
+pub mod wgpu_canvas;
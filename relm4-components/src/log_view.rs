@@ -0,0 +1,501 @@
+//! Reusable log viewer component for developer facing applications.
+//!
+//! Lines are kept in a ring buffer with a fixed capacity and only the visible
+//! rows are rendered, so appending thousands of lines stays cheap.
+//! Lines can be filtered by severity and searched for text or regular
+//! expressions, which highlights the matches.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use regex::{Regex, RegexBuilder};
+use relm4::gtk::pango;
+use relm4::gtk::prelude::{
+    AdjustmentExt, BoxExt, ButtonExt, EditableExt, ToggleButtonExt, WidgetExt,
+};
+use relm4::typed_view::list::{RelmListItem, TypedListView};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, RelmWidgetExt};
+
+/// The severity of a [`LogLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Very verbose diagnostic messages.
+    Trace,
+    /// Diagnostic messages.
+    Debug,
+    /// Informational messages.
+    Info,
+    /// Potential problems.
+    Warning,
+    /// Errors.
+    Error,
+}
+
+impl LogLevel {
+    /// All levels from the lowest to the highest severity.
+    pub const ALL: [Self; 5] = [
+        Self::Trace,
+        Self::Debug,
+        Self::Info,
+        Self::Warning,
+        Self::Error,
+    ];
+
+    /// The name of the level.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warning => "WARN",
+            Self::Error => "ERROR",
+        }
+    }
+
+    fn css_class(self) -> &'static str {
+        match self {
+            Self::Trace | Self::Debug => "dim-label",
+            Self::Info => "accent",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A line of a [`LogView`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// The severity of the line.
+    pub level: LogLevel,
+    /// The text of the line.
+    pub message: String,
+}
+
+impl LogLine {
+    /// Create a new line.
+    #[must_use]
+    pub fn new(level: LogLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+}
+
+/// Configuration for the log view component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `capacity` is set to 10000.
+/// - `min_level` is set to [`LogLevel::Trace`].
+/// - `follow` is set to [`true`].
+#[derive(Debug, Clone)]
+pub struct LogViewSettings {
+    /// The number of lines that are kept. Older lines are dropped.
+    pub capacity: usize,
+    /// The lowest level that is shown.
+    pub min_level: LogLevel,
+    /// Scroll to new lines as they are appended.
+    pub follow: bool,
+}
+
+impl Default for LogViewSettings {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            min_level: LogLevel::Trace,
+            follow: true,
+        }
+    }
+}
+
+/// Messages that can be sent to the log view component.
+#[derive(Debug)]
+pub enum LogViewMsg {
+    /// Append a line.
+    Append(LogLine),
+    /// Append several lines at once, which is faster than appending them one by one.
+    AppendMany(Vec<LogLine>),
+    /// Remove all lines.
+    Clear,
+    /// Only show lines with at least the given level.
+    SetMinLevel(LogLevel),
+    /// Only show lines that contain the given text.
+    Search(String),
+    /// Interpret the search text as regular expression.
+    SetRegex(bool),
+    /// Scroll to new lines as they are appended.
+    SetFollow(bool),
+    /// Write the visible lines to a file.
+    Export(PathBuf),
+}
+
+/// Messages that are sent by the log view component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogViewOutput {
+    /// The visible lines were written to a file.
+    Exported(PathBuf),
+    /// Writing the visible lines to a file failed.
+    ExportFailed(PathBuf, String),
+    /// The search text isn't a valid regular expression.
+    InvalidPattern(String),
+}
+
+#[derive(Debug)]
+struct LogItem {
+    line: Rc<LogLine>,
+    highlight: Option<Rc<Regex>>,
+}
+
+#[derive(Debug)]
+struct LogItemWidgets {
+    level: gtk::Label,
+    message: gtk::Label,
+}
+
+impl RelmListItem for LogItem {
+    type Root = gtk::Box;
+    type Widgets = LogItemWidgets;
+
+    fn setup(_item: &gtk::ListItem) -> (gtk::Box, LogItemWidgets) {
+        let root = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        root.add_css_class("monospace");
+
+        let level = gtk::Label::new(None);
+        level.set_width_chars(5);
+        level.set_xalign(0.0);
+        let message = gtk::Label::new(None);
+        message.set_xalign(0.0);
+        message.set_hexpand(true);
+        message.set_wrap(true);
+        message.set_wrap_mode(pango::WrapMode::WordChar);
+        message.set_selectable(true);
+
+        root.append(&level);
+        root.append(&message);
+        (root, LogItemWidgets { level, message })
+    }
+
+    fn bind(&mut self, widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        let level = self.line.level;
+        widgets.level.set_label(level.name());
+        widgets.level.set_css_classes(&[level.css_class()]);
+
+        widgets.message.set_label(&self.line.message);
+        let attributes = pango::AttrList::new();
+        if let Some(pattern) = &self.highlight {
+            for found in pattern.find_iter(&self.line.message) {
+                let mut background = pango::AttrColor::new_background(0xffff, 0xe0e0, 0x4040);
+                background.set_start_index(found.start() as u32);
+                background.set_end_index(found.end() as u32);
+                attributes.insert(background);
+
+                let mut foreground = pango::AttrColor::new_foreground(0, 0, 0);
+                foreground.set_start_index(found.start() as u32);
+                foreground.set_end_index(found.end() as u32);
+                attributes.insert(foreground);
+            }
+        }
+        widgets.message.set_attributes(Some(&attributes));
+    }
+}
+
+/// Log view component.
+#[derive(Debug)]
+pub struct LogView {
+    lines: VecDeque<Rc<LogLine>>,
+    view: TypedListView<LogItem, gtk::NoSelection>,
+    capacity: usize,
+    min_level: LogLevel,
+    query: String,
+    regex: bool,
+    pattern: Option<Rc<Regex>>,
+    follow: Rc<Cell<bool>>,
+}
+
+/// The widgets of the log view component.
+#[derive(Debug)]
+pub struct LogViewWidgets {
+    search: gtk::SearchEntry,
+    regex: gtk::ToggleButton,
+    level: gtk::DropDown,
+    follow: gtk::ToggleButton,
+    adjustment: gtk::Adjustment,
+}
+
+impl Component for LogView {
+    type CommandOutput = (PathBuf, Result<(), String>);
+    type Input = LogViewMsg;
+    type Output = LogViewOutput;
+    type Init = LogViewSettings;
+    type Root = gtk::Box;
+    type Widgets = LogViewWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Vertical, 0)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let toolbar = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        toolbar.set_margin_all(6);
+
+        let search = gtk::SearchEntry::new();
+        search.set_hexpand(true);
+        let input = sender.input_sender().clone();
+        search.connect_search_changed(move |entry| {
+            input.emit(LogViewMsg::Search(entry.text().into()));
+        });
+
+        let regex = gtk::ToggleButton::with_label(".*");
+        regex.set_tooltip_text(Some("Regular expression"));
+        let input = sender.input_sender().clone();
+        regex.connect_toggled(move |button| input.emit(LogViewMsg::SetRegex(button.is_active())));
+
+        let names: Vec<&str> = LogLevel::ALL.iter().map(|level| level.name()).collect();
+        let level = gtk::DropDown::from_strings(&names);
+        level.set_tooltip_text(Some("Minimum level"));
+        level.set_selected(settings.min_level as u32);
+        let input = sender.input_sender().clone();
+        level.connect_selected_notify(move |dropdown| {
+            if let Some(level) = LogLevel::ALL.get(dropdown.selected() as usize) {
+                input.emit(LogViewMsg::SetMinLevel(*level));
+            }
+        });
+
+        let follow = gtk::ToggleButton::new();
+        follow.set_icon_name("go-bottom-symbolic");
+        follow.set_tooltip_text(Some("Follow new lines"));
+        follow.set_active(settings.follow);
+        let input = sender.input_sender().clone();
+        follow.connect_toggled(move |button| input.emit(LogViewMsg::SetFollow(button.is_active())));
+
+        let clear = gtk::Button::from_icon_name("edit-clear-all-symbolic");
+        clear.set_tooltip_text(Some("Clear"));
+        let input = sender.input_sender().clone();
+        clear.connect_clicked(move |_| input.emit(LogViewMsg::Clear));
+
+        toolbar.append(&search);
+        toolbar.append(&regex);
+        toolbar.append(&level);
+        toolbar.append(&follow);
+        toolbar.append(&clear);
+        root.append(&toolbar);
+
+        let view = TypedListView::<LogItem, gtk::NoSelection>::new();
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_hscrollbar_policy(gtk::PolicyType::Never);
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&view.view));
+        root.append(&scrolled);
+
+        // Stay at the bottom while new lines are added.
+        let following = Rc::new(Cell::new(settings.follow));
+        let adjustment = scrolled.vadjustment();
+        let is_following = following.clone();
+        adjustment.connect_upper_notify(move |adjustment| {
+            if is_following.get() {
+                adjustment.set_value(adjustment.upper() - adjustment.page_size());
+            }
+        });
+
+        let mut model = Self {
+            lines: VecDeque::new(),
+            view,
+            capacity: settings.capacity.max(1),
+            min_level: settings.min_level,
+            query: String::new(),
+            regex: false,
+            pattern: None,
+            follow: following,
+        };
+        model.apply_filters();
+
+        let widgets = LogViewWidgets {
+            search,
+            regex,
+            level,
+            follow,
+            adjustment,
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match input {
+            LogViewMsg::Append(line) => self.append(vec![line]),
+            LogViewMsg::AppendMany(lines) => self.append(lines),
+            LogViewMsg::Clear => {
+                self.lines.clear();
+                self.view.clear();
+            }
+            LogViewMsg::SetMinLevel(level) => {
+                if level != self.min_level {
+                    self.min_level = level;
+                    widgets.level.set_selected(level as u32);
+                    self.apply_filters();
+                }
+            }
+            LogViewMsg::Search(query) => {
+                if query != self.query {
+                    if widgets.search.text() != query {
+                        widgets.search.set_text(&query);
+                    }
+                    self.query = query;
+                    self.search(widgets, &sender);
+                }
+            }
+            LogViewMsg::SetRegex(regex) => {
+                if regex != self.regex {
+                    self.regex = regex;
+                    widgets.regex.set_active(regex);
+                    self.search(widgets, &sender);
+                }
+            }
+            LogViewMsg::SetFollow(follow) => {
+                self.follow.set(follow);
+                widgets.follow.set_active(follow);
+                if follow {
+                    let adjustment = &widgets.adjustment;
+                    adjustment.set_value(adjustment.upper() - adjustment.page_size());
+                }
+            }
+            LogViewMsg::Export(path) => {
+                let text = self.text();
+                sender.spawn_oneshot_command(move || {
+                    let result = std::fs::write(&path, text).map_err(|error| error.to_string());
+                    (path, result)
+                });
+            }
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        (path, result): Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        let output = match result {
+            Ok(()) => LogViewOutput::Exported(path),
+            Err(error) => LogViewOutput::ExportFailed(path, error),
+        };
+        sender.output(output).ok();
+    }
+}
+
+impl LogView {
+    /// The number of stored lines, including the hidden ones.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns true if no lines are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The visible lines as text, one line per row.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for line in self.lines.iter().filter(|line| self.is_visible(line)) {
+            writeln!(text, "{:<5} {}", line.level.name(), line.message).ok();
+        }
+        text
+    }
+
+    fn is_visible(&self, line: &LogLine) -> bool {
+        line.level >= self.min_level
+            && self
+                .pattern
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(&line.message))
+    }
+
+    fn append(&mut self, lines: Vec<LogLine>) {
+        let lines: Vec<Rc<LogLine>> = lines.into_iter().map(Rc::new).collect();
+
+        // Drop the oldest lines to make room for the new ones.
+        let overflow = (self.lines.len() + lines.len()).saturating_sub(self.capacity);
+        let dropped = overflow.min(self.lines.len());
+        self.lines.drain(..dropped);
+        for _ in 0..dropped {
+            self.view.remove(0);
+        }
+
+        let skipped = lines.len().saturating_sub(self.capacity);
+        let mut items = Vec::with_capacity(lines.len() - skipped);
+        for line in lines.into_iter().skip(skipped) {
+            self.lines.push_back(line.clone());
+            items.push(LogItem {
+                line,
+                highlight: self.pattern.clone(),
+            });
+        }
+        self.view.extend_from_iter(items);
+    }
+
+    fn search(&mut self, widgets: &LogViewWidgets, sender: &ComponentSender<Self>) {
+        widgets.search.remove_css_class("error");
+        self.pattern = if self.query.is_empty() {
+            None
+        } else {
+            let pattern = if self.regex {
+                self.query.clone()
+            } else {
+                regex::escape(&self.query)
+            };
+            match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                Ok(regex) => Some(Rc::new(regex)),
+                Err(error) => {
+                    widgets.search.add_css_class("error");
+                    sender
+                        .output(LogViewOutput::InvalidPattern(error.to_string()))
+                        .ok();
+                    None
+                }
+            }
+        };
+
+        // Rebuild the rows, so the matches are highlighted.
+        self.view.clear();
+        let items: Vec<LogItem> = self
+            .lines
+            .iter()
+            .map(|line| LogItem {
+                line: line.clone(),
+                highlight: self.pattern.clone(),
+            })
+            .collect();
+        self.view.extend_from_iter(items);
+        self.apply_filters();
+    }
+
+    fn apply_filters(&mut self) {
+        self.view.clear_filters();
+        let min_level = self.min_level;
+        self.view
+            .add_filter(move |item| item.line.level >= min_level);
+        if let Some(pattern) = self.pattern.clone() {
+            self.view
+                .add_filter(move |item| pattern.is_match(&item.line.message));
+        }
+    }
+}
@@ -0,0 +1,769 @@
+//! Reusable and easily configurable component for showing slippy maps from a tile server.
+//!
+//! Tiles are downloaded from any server that uses the common `{z}/{x}/{y}` scheme,
+//! kept in memory and optionally stored in a [`TileCache`] directory, so
+//! previously visited areas can be shown offline.
+//! Markers and overlays are drawn on top of the tiles with a
+//! [`SnapshotHandler`](relm4::abstractions::SnapshotHandler).
+
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::f64::consts::PI;
+use std::fmt::{self, Debug};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use relm4::abstractions::{SnapshotArea, SnapshotHandler};
+use relm4::gtk::prelude::{GestureDragExt, GestureSingleExt, SnapshotExt, WidgetExt};
+use relm4::gtk::{gdk, graphene, gsk};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// Size of a tile in pixels.
+const TILE_SIZE: f64 = 256.0;
+/// Radius of a marker in pixels.
+const MARKER_RADIUS: f32 = 8.0;
+/// Number of tiles kept in memory before the oldest tiles that aren't visible are dropped.
+const MEMORY_CACHE_SIZE: usize = 512;
+/// The highest supported zoom level, the tiles of higher levels can't be addressed.
+const MAX_ZOOM: u8 = 30;
+/// The distance in pixels the pointer can move between press and release of a click.
+const CLICK_THRESHOLD: f64 = 4.0;
+/// Latitudes beyond this value can't be displayed with the web mercator projection.
+const MAX_LATITUDE: f64 = 85.051_128_78;
+
+/// A geographic coordinate in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Coordinate {
+    /// Latitude in degrees.
+    pub latitude: f64,
+    /// Longitude in degrees.
+    pub longitude: f64,
+}
+
+impl Coordinate {
+    /// Create a new coordinate.
+    #[must_use]
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+/// Identifies a tile of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileId {
+    /// The zoom level.
+    pub zoom: u8,
+    /// The column of the tile.
+    pub x: u32,
+    /// The row of the tile.
+    pub y: u32,
+}
+
+/// Directory that stores downloaded tiles as `relm4-tiles/{z}/{x}/{y}.png`.
+///
+/// The tiles are kept in their own subdirectory, so the cache can share
+/// a directory like the user cache directory of the application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileCache {
+    dir: PathBuf,
+}
+
+impl TileCache {
+    /// Create a new tile cache in `dir`.
+    ///
+    /// The `relm4-tiles` subdirectory is created once the first tile is stored.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Get the directory of the cache that was passed to [`TileCache::new`].
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of a cached tile.
+    #[must_use]
+    pub fn path(&self, tile: TileId) -> PathBuf {
+        self.tiles_dir()
+            .join(tile.zoom.to_string())
+            .join(tile.x.to_string())
+            .join(format!("{}.png", tile.y))
+    }
+
+    /// Read a tile from the cache.
+    pub fn load(&self, tile: TileId) -> io::Result<Vec<u8>> {
+        std::fs::read(self.path(tile))
+    }
+
+    /// Store a tile in the cache.
+    pub fn store(&self, tile: TileId, data: &[u8]) -> io::Result<()> {
+        let path = self.path(tile);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, data)
+    }
+
+    /// Get the total size of all cached tiles in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        fn dir_size(dir: &Path) -> io::Result<u64> {
+            let mut size = 0;
+            for entry in std::fs::read_dir(dir)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_dir() {
+                    size += dir_size(&entry.path())?;
+                } else {
+                    size += metadata.len();
+                }
+            }
+            Ok(size)
+        }
+
+        match dir_size(&self.tiles_dir()) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+            result => result,
+        }
+    }
+
+    /// Remove all cached tiles.
+    ///
+    /// Other files in the directory of the cache are kept.
+    pub fn clear(&self) -> io::Result<()> {
+        match std::fs::remove_dir_all(self.tiles_dir()) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    fn tiles_dir(&self) -> PathBuf {
+        self.dir.join("relm4-tiles")
+    }
+}
+
+/// A marker on the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapMarker {
+    /// The id that is reported by [`MapOutput::MarkerClicked`].
+    pub id: usize,
+    /// The position of the marker.
+    pub position: Coordinate,
+    /// The color of the marker.
+    pub color: gdk::RGBA,
+}
+
+impl MapMarker {
+    /// Create a new blue marker.
+    #[must_use]
+    pub fn new(id: usize, position: Coordinate) -> Self {
+        Self {
+            id,
+            position,
+            color: gdk::RGBA::new(0.21, 0.52, 0.89, 1.0),
+        }
+    }
+}
+
+/// Converts between coordinates and positions in the map widget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapProjection {
+    zoom: u8,
+    /// The left edge of the widget in world pixels.
+    left: f64,
+    /// The top edge of the widget in world pixels.
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl MapProjection {
+    fn new(center: Coordinate, zoom: u8, width: i32, height: i32) -> Self {
+        let (x, y) = world_position(center, zoom);
+        let (width, height) = (f64::from(width), f64::from(height));
+        Self {
+            zoom,
+            left: x - width / 2.0,
+            top: y - height / 2.0,
+            width,
+            height,
+        }
+    }
+
+    /// Get the position of `coordinate` in the widget in pixels.
+    #[must_use]
+    pub fn to_widget(&self, coordinate: Coordinate) -> (f32, f32) {
+        let (x, y) = world_position(coordinate, self.zoom);
+        ((x - self.left) as f32, (y - self.top) as f32)
+    }
+
+    /// Get the coordinate at a position in the widget.
+    #[must_use]
+    pub fn to_coordinate(&self, x: f64, y: f64) -> Coordinate {
+        coordinate_at(self.left + x, self.top + y, self.zoom)
+    }
+
+    /// Get the current zoom level.
+    #[must_use]
+    pub fn zoom(&self) -> u8 {
+        self.zoom
+    }
+
+    /// Returns the tiles that are visible and their position in the widget.
+    fn visible_tiles(&self) -> Vec<(TileId, f32, f32)> {
+        let count = 1_i64 << self.zoom;
+        let first_x = (self.left / TILE_SIZE).floor() as i64;
+        let last_x = ((self.left + self.width) / TILE_SIZE).floor() as i64;
+        let first_y = (self.top / TILE_SIZE).floor().max(0.0) as i64;
+        let last_y = (((self.top + self.height) / TILE_SIZE).floor() as i64).min(count - 1);
+
+        let mut tiles = Vec::new();
+        for y in first_y..=last_y {
+            for x in first_x..=last_x {
+                let id = TileId {
+                    zoom: self.zoom,
+                    x: x.rem_euclid(count) as u32,
+                    y: y as u32,
+                };
+                let position = (
+                    (x as f64 * TILE_SIZE - self.left) as f32,
+                    (y as f64 * TILE_SIZE - self.top) as f32,
+                );
+                tiles.push((id, position.0, position.1));
+            }
+        }
+        tiles
+    }
+}
+
+/// Position of a coordinate in world pixels of the web mercator projection.
+fn world_position(coordinate: Coordinate, zoom: u8) -> (f64, f64) {
+    let size = TILE_SIZE * 2_f64.powi(i32::from(zoom));
+    let latitude = coordinate
+        .latitude
+        .clamp(-MAX_LATITUDE, MAX_LATITUDE)
+        .to_radians();
+    let x = (coordinate.longitude + 180.0) / 360.0 * size;
+    let y = (1.0 - (latitude.tan() + 1.0 / latitude.cos()).ln() / PI) / 2.0 * size;
+    (x, y)
+}
+
+/// Coordinate of a position in world pixels of the web mercator projection.
+fn coordinate_at(x: f64, y: f64, zoom: u8) -> Coordinate {
+    let size = TILE_SIZE * 2_f64.powi(i32::from(zoom));
+    let longitude = (x / size * 360.0).rem_euclid(360.0) - 180.0;
+    let latitude = (PI * (1.0 - 2.0 * y / size)).sinh().atan().to_degrees();
+    Coordinate::new(latitude.clamp(-MAX_LATITUDE, MAX_LATITUDE), longitude)
+}
+
+type DrawOverlay = dyn Fn(&gtk::Snapshot, &MapProjection);
+
+/// A named overlay that is drawn on top of the tiles and markers.
+#[derive(Clone)]
+pub struct MapOverlay {
+    name: String,
+    draw: Rc<DrawOverlay>,
+}
+
+impl MapOverlay {
+    /// Create a new overlay.
+    ///
+    /// `draw` is called whenever the visible area of the map changes.
+    /// Setting an overlay with the same name replaces the previous one.
+    pub fn new<F>(name: impl Into<String>, draw: F) -> Self
+    where
+        F: Fn(&gtk::Snapshot, &MapProjection) + 'static,
+    {
+        Self {
+            name: name.into(),
+            draw: Rc::new(draw),
+        }
+    }
+
+    /// Get the name of the overlay.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn layer(&self) -> String {
+        format!("overlay:{}", self.name)
+    }
+}
+
+impl Debug for MapOverlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapOverlay")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Configuration for the map component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `tile_url` is set to the OpenStreetMap tile server.
+/// - `user_agent` is set to "relm4-components".
+/// - `cache` is set to [`None`].
+/// - `offline` is set to [`false`].
+/// - `center` is set to latitude and longitude 0.
+/// - `zoom` is set to 2.
+/// - `max_zoom` is set to 19.
+///
+/// Please respect the usage policy of the tile server, which usually requires
+/// a meaningful user agent and caching of tiles.
+#[derive(Debug, Clone)]
+pub struct MapSettings {
+    /// URL of the tile server with `{z}`, `{x}` and `{y}` placeholders.
+    pub tile_url: String,
+    /// User agent that is sent to the tile server.
+    pub user_agent: String,
+    /// Optional directory to store downloaded tiles.
+    pub cache: Option<TileCache>,
+    /// Only show cached tiles and don't download new ones.
+    pub offline: bool,
+    /// The initial center of the map.
+    pub center: Coordinate,
+    /// The initial zoom level.
+    pub zoom: u8,
+    /// The maximum zoom level of the tile server.
+    ///
+    /// Values above 30 are treated as 30.
+    pub max_zoom: u8,
+}
+
+impl Default for MapSettings {
+    fn default() -> Self {
+        Self {
+            tile_url: "https://tile.openstreetmap.org/{z}/{x}/{y}.png".into(),
+            user_agent: "relm4-components".into(),
+            cache: None,
+            offline: false,
+            center: Coordinate::default(),
+            zoom: 2,
+            max_zoom: 19,
+        }
+    }
+}
+
+/// Messages that can be sent to the map component.
+#[derive(Debug)]
+pub enum MapMsg {
+    /// Move the center of the map.
+    SetCenter(Coordinate),
+    /// Set the zoom level.
+    SetZoom(u8),
+    /// Zoom in by one level.
+    ZoomIn,
+    /// Zoom out by one level.
+    ZoomOut,
+    /// Add a marker or replace the marker with the same id.
+    AddMarker(MapMarker),
+    /// Remove the marker with the given id.
+    RemoveMarker(usize),
+    /// Remove all markers.
+    ClearMarkers,
+    /// Add an overlay or replace the overlay with the same name.
+    SetOverlay(MapOverlay),
+    /// Remove the overlay with the given name.
+    RemoveOverlay(String),
+    /// Enable or disable the offline mode.
+    SetOffline(bool),
+    /// Remove all tiles from the memory and the tile cache.
+    ClearCache,
+    #[doc(hidden)]
+    Resize,
+    #[doc(hidden)]
+    DragBegin,
+    #[doc(hidden)]
+    DragUpdate(f64, f64),
+    #[doc(hidden)]
+    DragEnd,
+    #[doc(hidden)]
+    Click(f64, f64),
+}
+
+/// Messages that are sent by the map component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapOutput {
+    /// A marker was clicked.
+    MarkerClicked(usize),
+    /// The visible area was changed by the user.
+    Moved {
+        /// The new center.
+        center: Coordinate,
+        /// The new zoom level.
+        zoom: u8,
+    },
+}
+
+/// Map component that shows tiles of a tile server along with markers and overlays.
+#[derive(Debug)]
+pub struct MapView {
+    settings: MapSettings,
+    client: reqwest::Client,
+    handler: SnapshotHandler,
+    center: Coordinate,
+    zoom: u8,
+    markers: Vec<MapMarker>,
+    overlays: Vec<MapOverlay>,
+    tiles: HashMap<TileId, gdk::Texture>,
+    /// The tiles in memory in the order they were loaded.
+    tile_order: VecDeque<TileId>,
+    pending: HashSet<TileId>,
+    /// The center in world pixels when the current drag began.
+    drag_origin: Option<(f64, f64)>,
+}
+
+impl Component for MapView {
+    type CommandOutput = (TileId, Option<Vec<u8>>);
+    type Input = MapMsg;
+    type Output = MapOutput;
+    type Init = MapSettings;
+    type Root = SnapshotArea;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        let area = SnapshotArea::default();
+        area.set_hexpand(true);
+        area.set_vexpand(true);
+        area.set_overflow(gtk::Overflow::Hidden);
+        area
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(settings.user_agent.clone())
+            .build()
+            .unwrap_or_default();
+
+        let mut handler = SnapshotHandler::new_with_area(root.clone());
+        // Create the layers in their drawing order.
+        handler.update_layer("tiles", |_, _, _| {});
+        handler.update_layer("markers", |_, _, _| {});
+
+        let input = sender.input_sender().clone();
+        root.connect_resize(move |_, _, _| input.emit(MapMsg::Resize));
+
+        let drag = gtk::GestureDrag::new();
+        let input = sender.input_sender().clone();
+        drag.connect_drag_begin(move |_, _, _| input.emit(MapMsg::DragBegin));
+        let input = sender.input_sender().clone();
+        drag.connect_drag_update(move |_, x, y| input.emit(MapMsg::DragUpdate(x, y)));
+        let input = sender.input_sender().clone();
+        drag.connect_drag_end(move |_, _, _| input.emit(MapMsg::DragEnd));
+        root.add_controller(drag);
+
+        let click = gtk::GestureClick::new();
+        click.set_button(gdk::BUTTON_PRIMARY);
+        let press = Rc::new(Cell::new((0.0, 0.0)));
+        let pressed = press.clone();
+        click.connect_pressed(move |_, _, x, y| pressed.set((x, y)));
+        let input = sender.input_sender().clone();
+        click.connect_released(move |_, _, x, y| {
+            // Ignore the end of a drag.
+            let (press_x, press_y) = press.get();
+            if (x - press_x).hypot(y - press_y) <= CLICK_THRESHOLD {
+                input.emit(MapMsg::Click(x, y));
+            }
+        });
+        root.add_controller(click);
+
+        // Smooth scrolling of touchpads is accumulated, so each zoom step needs a whole scroll unit.
+        let scroll = gtk::EventControllerScroll::new(
+            gtk::EventControllerScrollFlags::VERTICAL | gtk::EventControllerScrollFlags::DISCRETE,
+        );
+        let input = sender.input_sender().clone();
+        scroll.connect_scroll(move |_, _, y| {
+            if y < 0.0 {
+                input.emit(MapMsg::ZoomIn);
+            } else if y > 0.0 {
+                input.emit(MapMsg::ZoomOut);
+            }
+            gtk::glib::Propagation::Stop
+        });
+        root.add_controller(scroll);
+
+        let model = Self {
+            center: settings.center,
+            zoom: settings.zoom.min(settings.max_zoom).min(MAX_ZOOM),
+            settings,
+            client,
+            handler,
+            markers: Vec::new(),
+            overlays: Vec::new(),
+            tiles: HashMap::new(),
+            tile_order: VecDeque::new(),
+            pending: HashSet::new(),
+            drag_origin: None,
+        };
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        match input {
+            MapMsg::SetCenter(center) => {
+                self.center = center;
+                self.redraw(&sender);
+            }
+            MapMsg::SetZoom(zoom) => {
+                self.zoom = zoom.min(self.max_zoom());
+                self.redraw(&sender);
+            }
+            MapMsg::ZoomIn | MapMsg::ZoomOut => {
+                self.zoom = if matches!(input, MapMsg::ZoomIn) {
+                    (self.zoom + 1).min(self.max_zoom())
+                } else {
+                    self.zoom.saturating_sub(1)
+                };
+                self.redraw(&sender);
+                self.send_moved(&sender);
+            }
+            MapMsg::AddMarker(marker) => {
+                self.markers.retain(|m| m.id != marker.id);
+                self.markers.push(marker);
+                self.draw_markers();
+            }
+            MapMsg::RemoveMarker(id) => {
+                self.markers.retain(|m| m.id != id);
+                self.draw_markers();
+            }
+            MapMsg::ClearMarkers => {
+                self.markers.clear();
+                self.draw_markers();
+            }
+            MapMsg::SetOverlay(overlay) => {
+                self.overlays.retain(|o| o.name != overlay.name);
+                self.draw_overlay(&overlay);
+                self.overlays.push(overlay);
+            }
+            MapMsg::RemoveOverlay(name) => {
+                if let Some(index) = self.overlays.iter().position(|o| o.name == name) {
+                    let overlay = self.overlays.remove(index);
+                    self.handler.remove_layer(&overlay.layer());
+                }
+            }
+            MapMsg::SetOffline(offline) => {
+                self.settings.offline = offline;
+                self.redraw(&sender);
+            }
+            MapMsg::ClearCache => {
+                self.tiles.clear();
+                self.tile_order.clear();
+                if let Some(cache) = &self.settings.cache {
+                    if let Err(error) = cache.clear() {
+                        tracing::error!("Cannot clear tile cache: {error}");
+                    }
+                }
+                self.redraw(&sender);
+            }
+            MapMsg::Resize => self.redraw(&sender),
+            MapMsg::DragBegin => {
+                self.drag_origin = Some(world_position(self.center, self.zoom));
+            }
+            MapMsg::DragUpdate(x, y) => {
+                if let Some((origin_x, origin_y)) = self.drag_origin {
+                    self.center = coordinate_at(origin_x - x, origin_y - y, self.zoom);
+                    self.redraw(&sender);
+                }
+            }
+            MapMsg::DragEnd => {
+                self.drag_origin = None;
+                self.send_moved(&sender);
+            }
+            MapMsg::Click(x, y) => {
+                let projection = self.projection();
+                let clicked = self.markers.iter().rev().find(|marker| {
+                    let (marker_x, marker_y) = projection.to_widget(marker.position);
+                    let (dx, dy) = (x as f32 - marker_x, y as f32 - marker_y);
+                    dx * dx + dy * dy <= MARKER_RADIUS * MARKER_RADIUS
+                });
+                if let Some(marker) = clicked {
+                    sender.output(MapOutput::MarkerClicked(marker.id)).ok();
+                }
+            }
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        (tile, data): Self::CommandOutput,
+        _sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        self.pending.remove(&tile);
+        let Some(data) = data else {
+            return;
+        };
+
+        match gtk::gdk_pixbuf::Pixbuf::from_read(io::Cursor::new(data)) {
+            Ok(pixbuf) => {
+                self.insert_tile(tile, gdk::Texture::for_pixbuf(&pixbuf));
+                if tile.zoom == self.zoom {
+                    self.draw_tiles();
+                }
+            }
+            Err(error) => tracing::error!("Cannot decode tile {tile:?}: {error}"),
+        }
+    }
+}
+
+impl MapView {
+    fn max_zoom(&self) -> u8 {
+        self.settings.max_zoom.min(MAX_ZOOM)
+    }
+
+    /// Keep a tile in memory and drop the oldest tiles that aren't visible if the memory is full.
+    fn insert_tile(&mut self, tile: TileId, texture: gdk::Texture) {
+        let visible: HashSet<TileId> = self
+            .projection()
+            .visible_tiles()
+            .into_iter()
+            .map(|(id, _, _)| id)
+            .collect();
+        while self.tiles.len() >= MEMORY_CACHE_SIZE {
+            let Some(index) = self.tile_order.iter().position(|id| !visible.contains(id)) else {
+                break;
+            };
+            if let Some(old) = self.tile_order.remove(index) {
+                self.tiles.remove(&old);
+            }
+        }
+
+        if self.tiles.insert(tile, texture).is_none() {
+            self.tile_order.push_back(tile);
+        }
+    }
+
+    fn projection(&self) -> MapProjection {
+        MapProjection::new(
+            self.center,
+            self.zoom,
+            self.handler.width(),
+            self.handler.height(),
+        )
+    }
+
+    fn send_moved(&self, sender: &ComponentSender<Self>) {
+        sender
+            .output(MapOutput::Moved {
+                center: self.center,
+                zoom: self.zoom,
+            })
+            .ok();
+    }
+
+    /// Redraw all layers and load missing tiles.
+    fn redraw(&mut self, sender: &ComponentSender<Self>) {
+        for (tile, _, _) in self.projection().visible_tiles() {
+            if !self.tiles.contains_key(&tile) && self.pending.insert(tile) {
+                sender.oneshot_command(load_tile(self.client.clone(), self.settings.clone(), tile));
+            }
+        }
+
+        self.draw_tiles();
+        self.draw_markers();
+        for overlay in self.overlays.clone() {
+            self.draw_overlay(&overlay);
+        }
+    }
+
+    fn draw_tiles(&mut self) {
+        let projection = self.projection();
+        let tiles = &self.tiles;
+        self.handler
+            .update_layer("tiles", |snapshot, width, height| {
+                snapshot.append_color(
+                    &gdk::RGBA::new(0.85, 0.85, 0.85, 1.0),
+                    &graphene::Rect::new(0.0, 0.0, width, height),
+                );
+                for (tile, x, y) in projection.visible_tiles() {
+                    if let Some(texture) = tiles.get(&tile) {
+                        let size = TILE_SIZE as f32;
+                        snapshot.append_texture(texture, &graphene::Rect::new(x, y, size, size));
+                    }
+                }
+            });
+    }
+
+    fn draw_markers(&mut self) {
+        let projection = self.projection();
+        let markers = &self.markers;
+        self.handler.update_layer("markers", |snapshot, _, _| {
+            for marker in markers {
+                let (x, y) = projection.to_widget(marker.position);
+                let rect = graphene::Rect::new(
+                    x - MARKER_RADIUS,
+                    y - MARKER_RADIUS,
+                    2.0 * MARKER_RADIUS,
+                    2.0 * MARKER_RADIUS,
+                );
+                snapshot.push_rounded_clip(&gsk::RoundedRect::from_rect(rect, MARKER_RADIUS));
+                snapshot.append_color(&marker.color, &rect);
+                snapshot.pop();
+            }
+        });
+    }
+
+    fn draw_overlay(&mut self, overlay: &MapOverlay) {
+        let projection = self.projection();
+        self.handler
+            .update_layer(&overlay.layer(), |snapshot, _, _| {
+                (overlay.draw)(snapshot, &projection);
+            });
+    }
+}
+
+/// Load a tile from the cache or the tile server.
+async fn load_tile(
+    client: reqwest::Client,
+    settings: MapSettings,
+    tile: TileId,
+) -> (TileId, Option<Vec<u8>>) {
+    if let Some(data) = settings
+        .cache
+        .as_ref()
+        .and_then(|cache| cache.load(tile).ok())
+    {
+        return (tile, Some(data));
+    }
+    if settings.offline {
+        return (tile, None);
+    }
+
+    let url = settings
+        .tile_url
+        .replace("{z}", &tile.zoom.to_string())
+        .replace("{x}", &tile.x.to_string())
+        .replace("{y}", &tile.y.to_string());
+    let data = match fetch(&client, &url).await {
+        Ok(data) => data,
+        Err(error) => {
+            tracing::error!("Cannot load tile {tile:?}: {error}");
+            return (tile, None);
+        }
+    };
+
+    if let Some(cache) = &settings.cache {
+        if let Err(error) = cache.store(tile, &data) {
+            tracing::error!("Cannot store tile {tile:?}: {error}");
+        }
+    }
+    (tile, Some(data))
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> reqwest::Result<Vec<u8>> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
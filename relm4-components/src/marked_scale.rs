@@ -0,0 +1,281 @@
+//! A wrapper around [`gtk::Scale`] with marks that are declared from a typed list.
+//!
+//! Each mark carries a value of your own type, so the selected mark can
+//! be matched directly instead of comparing floating point values.
+
+use std::fmt::Debug;
+
+use relm4::gtk::prelude::{AdjustmentExt, OrientableExt, RangeExt, ScaleExt, WidgetExt};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// A mark on a [`MarkedScale`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleMark<T> {
+    /// The position of the mark on the scale.
+    pub value: f64,
+    /// The label that is shown next to the mark.
+    pub label: Option<String>,
+    /// The data that belongs to the mark.
+    pub item: T,
+}
+
+impl<T> ScaleMark<T> {
+    /// Create a new mark without a label.
+    #[must_use]
+    pub fn new(value: f64, item: T) -> Self {
+        Self {
+            value,
+            label: None,
+            item,
+        }
+    }
+
+    /// Create a new mark with a label.
+    #[must_use]
+    pub fn with_label(value: f64, label: impl Into<String>, item: T) -> Self {
+        Self {
+            value,
+            label: Some(label.into()),
+            item,
+        }
+    }
+}
+
+/// Configuration for the marked scale component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `marks` is empty.
+/// - `range` is set to the range of the marks, or 0 to 100 without marks.
+/// - `value` is set to the start of the range.
+/// - `step` is set to 1.
+/// - `snap_to_marks` is set to [`false`].
+/// - `orientation` is set to [`gtk::Orientation::Horizontal`].
+/// - `position` is set to [`gtk::PositionType::Bottom`].
+/// - `digits` is set to 0.
+#[derive(Debug, Clone)]
+pub struct MarkedScaleSettings<T> {
+    /// The marks of the scale.
+    pub marks: Vec<ScaleMark<T>>,
+    /// The lower and upper bound of the scale.
+    /// If [`None`], the range of the marks is used.
+    pub range: Option<(f64, f64)>,
+    /// The initial value. If [`None`], the start of the range is used.
+    pub value: Option<f64>,
+    /// The step of the scale.
+    pub step: f64,
+    /// Only allow values of marks.
+    pub snap_to_marks: bool,
+    /// The orientation of the scale.
+    pub orientation: gtk::Orientation,
+    /// The side of the scale the marks are drawn on.
+    pub position: gtk::PositionType,
+    /// The number of decimal places that are shown, if the value is drawn.
+    pub digits: i32,
+}
+
+impl<T> Default for MarkedScaleSettings<T> {
+    fn default() -> Self {
+        Self {
+            marks: Vec::new(),
+            range: None,
+            value: None,
+            step: 1.0,
+            snap_to_marks: false,
+            orientation: gtk::Orientation::Horizontal,
+            position: gtk::PositionType::Bottom,
+            digits: 0,
+        }
+    }
+}
+
+/// Messages that can be sent to the marked scale component.
+#[derive(Debug)]
+pub enum MarkedScaleMsg<T> {
+    /// Set the value without emitting an output.
+    SetValue(f64),
+    /// Select the first mark with the given item without emitting an output.
+    SelectItem(T),
+    /// Replace the marks.
+    SetMarks(Vec<ScaleMark<T>>),
+    #[doc(hidden)]
+    Changed(f64),
+}
+
+/// A value that was selected by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkedScaleOutput<T> {
+    /// The new value of the scale.
+    pub value: f64,
+    /// The item of the mark at the new value, if any.
+    pub item: Option<T>,
+}
+
+/// A [`gtk::Scale`] with typed marks.
+#[derive(Debug)]
+pub struct MarkedScale<T> {
+    marks: Vec<ScaleMark<T>>,
+    value: f64,
+    range: Option<(f64, f64)>,
+    snap_to_marks: bool,
+    position: gtk::PositionType,
+    /// Value that was set programmatically and must not emit an output.
+    ignored: Option<f64>,
+}
+
+impl<T> Component for MarkedScale<T>
+where
+    T: Clone + PartialEq + Debug + 'static,
+{
+    type CommandOutput = ();
+    type Input = MarkedScaleMsg<T>;
+    type Output = MarkedScaleOutput<T>;
+    type Init = MarkedScaleSettings<T>;
+    type Root = gtk::Scale;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::Scale::default()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        root.set_orientation(settings.orientation);
+        root.set_digits(settings.digits);
+        root.set_draw_value(false);
+        root.set_increments(settings.step, settings.step * 10.0);
+        if settings.orientation == gtk::Orientation::Horizontal {
+            root.set_hexpand(true);
+        } else {
+            root.set_vexpand(true);
+        }
+
+        let input = sender.input_sender().clone();
+        root.connect_value_changed(move |scale| {
+            input.emit(MarkedScaleMsg::Changed(scale.value()));
+        });
+
+        let mut model = Self {
+            marks: Vec::new(),
+            value: 0.0,
+            range: settings.range,
+            snap_to_marks: settings.snap_to_marks,
+            position: settings.position,
+            ignored: None,
+        };
+        model.set_marks(&root, settings.marks);
+        let value = settings.value.unwrap_or_else(|| root.adjustment().lower());
+        model.set_value(&root, value);
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match input {
+            MarkedScaleMsg::SetValue(value) => self.set_value(root, value),
+            MarkedScaleMsg::SelectItem(item) => {
+                if let Some(value) = self
+                    .marks
+                    .iter()
+                    .find(|mark| mark.item == item)
+                    .map(|mark| mark.value)
+                {
+                    self.set_value(root, value);
+                }
+            }
+            MarkedScaleMsg::SetMarks(marks) => {
+                self.set_marks(root, marks);
+                self.set_value(root, self.value);
+            }
+            MarkedScaleMsg::Changed(value) => {
+                if self.ignored.take() == Some(value) {
+                    return;
+                }
+
+                let snapped = self.snap(value);
+                if snapped != value {
+                    // Emits another change with the snapped value.
+                    root.set_value(snapped);
+                    return;
+                }
+                if snapped == self.value {
+                    return;
+                }
+
+                self.value = snapped;
+                sender
+                    .output(MarkedScaleOutput {
+                        value: snapped,
+                        item: self.item().cloned(),
+                    })
+                    .ok();
+            }
+        }
+    }
+}
+
+impl<T> MarkedScale<T> {
+    /// The current value of the scale.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The item of the mark at the current value, if any.
+    #[must_use]
+    pub fn item(&self) -> Option<&T> {
+        self.marks
+            .iter()
+            .find(|mark| mark.value == self.value)
+            .map(|mark| &mark.item)
+    }
+
+    /// The marks of the scale.
+    #[must_use]
+    pub fn marks(&self) -> &[ScaleMark<T>] {
+        &self.marks
+    }
+
+    /// Returns the value of the closest mark if the scale snaps to marks.
+    fn snap(&self, value: f64) -> f64 {
+        if !self.snap_to_marks {
+            return value;
+        }
+        self.marks
+            .iter()
+            .map(|mark| mark.value)
+            .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+            .unwrap_or(value)
+    }
+
+    fn set_value(&mut self, root: &gtk::Scale, value: f64) {
+        let adjustment = root.adjustment();
+        let value = self.snap(value.clamp(adjustment.lower(), adjustment.upper()));
+        self.value = value;
+        if root.value() != value {
+            self.ignored = Some(value);
+            root.set_value(value);
+        }
+    }
+
+    fn set_marks(&mut self, root: &gtk::Scale, mut marks: Vec<ScaleMark<T>>) {
+        marks.sort_by(|a, b| a.value.total_cmp(&b.value));
+
+        let (lower, upper) = self
+            .range
+            .unwrap_or_else(|| match (marks.first(), marks.last()) {
+                (Some(first), Some(last)) if first.value < last.value => (first.value, last.value),
+                _ => (0.0, 100.0),
+            });
+        root.set_range(lower, upper);
+
+        root.clear_marks();
+        for mark in &marks {
+            root.add_mark(mark.value, self.position, mark.label.as_deref());
+        }
+        self.marks = marks;
+    }
+}
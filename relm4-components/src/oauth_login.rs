@@ -0,0 +1,648 @@
+//! Log in with OAuth 2.0 or OpenID Connect.
+//!
+//! The [`OAuthLogin`] component implements the authorization code flow with
+//! [PKCE](https://datatracker.ietf.org/doc/html/rfc7636) for native applications:
+//!
+//! 1. The authorization URL is opened in the browser of the user.
+//!    In sandboxed applications this goes through the OpenURI portal.
+//! 2. The provider redirects the browser back to the application, either to a
+//!    listener on the loopback interface or to a custom URI scheme of the application
+//!    that is passed on with [`OAuthMsg::Redirected`].
+//! 3. The authorization code is exchanged for tokens in a command.
+//! 4. Tokens are refreshed shortly before they expire, if the provider returned a refresh token.
+//!
+//! The tokens are sent as [`OAuthOutput`], so they can be persisted,
+//! for example with `relm4::secrets`.
+
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::time::{Duration, SystemTime};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::future::{AbortHandle, Abortable};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use relm4::gtk::gio;
+use relm4::gtk::prelude::{ButtonExt, WidgetExt};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+/// How long before the access token expires it is refreshed.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// How long the loopback listener waits for the browser to log in.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How long a connection to the loopback listener may take to send its request line.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximal length of a request line, which easily fits the longest redirects.
+const MAX_REQUEST_LINE: u64 = 8 * 1024;
+
+/// The page shown in the browser after the redirect to the loopback listener.
+const DONE_PAGE: &str = "<!DOCTYPE html><html><body><p>You can close this window and return to the application.</p></body></html>";
+
+/// The response to requests that aren't the expected redirect.
+const BAD_REQUEST: &[u8] =
+    b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// Where the provider redirects the browser to after the user logged in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedirectMode {
+    /// Listen on `http://127.0.0.1:<port>/` for the redirect.
+    ///
+    /// Use port 0 to pick a free port, if the provider allows any port.
+    Loopback {
+        /// The port of the listener.
+        port: u16,
+    },
+    /// Redirect to a URI like `org.example.App:/oauth` that is handled by the application.
+    ///
+    /// The application must forward the URI it's opened with as [`OAuthMsg::Redirected`].
+    Custom(String),
+}
+
+/// Configuration of the OAuth provider.
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    /// The authorization endpoint, like `https://accounts.example.com/authorize`.
+    pub authorization_url: String,
+    /// The token endpoint, like `https://accounts.example.com/token`.
+    pub token_url: String,
+    /// The client ID of the application.
+    pub client_id: String,
+    /// The client secret, which is only used by some providers for native applications.
+    pub client_secret: Option<String>,
+    /// The requested scopes, like `openid` and `email`.
+    pub scopes: Vec<String>,
+    /// Where the browser is redirected to.
+    pub redirect: RedirectMode,
+}
+
+/// Tokens returned by the provider.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Tokens {
+    /// The access token for requests to the API.
+    pub access_token: String,
+    /// The token to get a new access token, if the provider returned one.
+    pub refresh_token: Option<String>,
+    /// The ID token of OpenID Connect providers.
+    pub id_token: Option<String>,
+    /// When the access token expires, if the provider told.
+    pub expires_at: Option<SystemTime>,
+    /// The granted scopes, if they differ from the requested ones.
+    pub scope: Option<String>,
+}
+
+impl fmt::Debug for Tokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokens")
+            .field("access_token", &"***")
+            .field("refresh_token", &self.refresh_token.as_ref().map(|_| "***"))
+            .field("id_token", &self.id_token.as_ref().map(|_| "***"))
+            .field("expires_at", &self.expires_at)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+/// The response of the token endpoint.
+#[derive(serde::Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    id_token: Option<String>,
+    expires_in: Option<u64>,
+    scope: Option<String>,
+}
+
+impl TokenResponse {
+    fn into_tokens(self, previous_refresh_token: Option<String>) -> Tokens {
+        Tokens {
+            access_token: self.access_token,
+            // Providers may keep the refresh token when refreshing.
+            refresh_token: self.refresh_token.or(previous_refresh_token),
+            id_token: self.id_token,
+            expires_at: self
+                .expires_in
+                .map(|seconds| SystemTime::now() + Duration::from_secs(seconds)),
+            scope: self.scope,
+        }
+    }
+}
+
+/// Error returned if logging in or refreshing the tokens fails.
+#[derive(Debug)]
+pub enum OAuthError {
+    /// The loopback listener couldn't be started or the user didn't log in in time,
+    /// in which case the error kind is [`io::ErrorKind::TimedOut`].
+    Listener(io::Error),
+    /// The browser couldn't be opened.
+    Browser(gtk::glib::Error),
+    /// The provider returned an error, for example because the user denied access.
+    Provider {
+        /// The error code, like `access_denied`.
+        error: String,
+        /// A description for developers.
+        description: Option<String>,
+    },
+    /// The redirect didn't belong to the current login attempt.
+    StateMismatch,
+    /// The redirect had no authorization code.
+    MissingCode,
+    /// The token request failed.
+    Http(reqwest::Error),
+}
+
+impl fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Listener(error) => write!(f, "redirect listener failed: {error}"),
+            Self::Browser(error) => write!(f, "couldn't open the browser: {error}"),
+            Self::Provider { error, description } => {
+                write!(f, "provider returned {error}")?;
+                if let Some(description) = description {
+                    write!(f, ": {description}")?;
+                }
+                Ok(())
+            }
+            Self::StateMismatch => f.write_str("the redirect belongs to another login attempt"),
+            Self::MissingCode => f.write_str("the redirect has no authorization code"),
+            Self::Http(error) => write!(f, "token request failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Listener(error) => Some(error),
+            Self::Browser(error) => Some(error),
+            Self::Http(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Messages that can be sent to the login component.
+#[derive(Debug)]
+pub enum OAuthMsg {
+    /// Start logging in, which is also done when the button is clicked.
+    Login,
+    /// Pass on the URI the application was opened with for [`RedirectMode::Custom`].
+    Redirected(String),
+    /// Continue with tokens that were stored earlier, refreshing them when needed.
+    Restore(Tokens),
+    /// Refresh the tokens now.
+    Refresh,
+    /// Forget the tokens and cancel a running login.
+    Logout,
+}
+
+/// Messages that are sent by the login component.
+#[derive(Debug)]
+pub enum OAuthOutput {
+    /// The user logged in.
+    LoggedIn(Tokens),
+    /// The tokens were refreshed.
+    Refreshed(Tokens),
+    /// Logging in or refreshing failed.
+    Error(OAuthError),
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum OAuthCommand {
+    Redirected(Result<String, OAuthError>),
+    Tokens {
+        refresh: bool,
+        result: Result<Tokens, OAuthError>,
+    },
+    RefreshDue,
+}
+
+/// A login attempt that waits for the redirect.
+#[derive(Debug)]
+struct Attempt {
+    verifier: String,
+    state: String,
+    redirect_uri: String,
+    listener: Option<AbortHandle>,
+}
+
+/// A button that logs in with OAuth 2.0.
+#[derive(Debug)]
+pub struct OAuthLogin {
+    config: OAuthConfig,
+    client: reqwest::Client,
+    attempt: Option<Attempt>,
+    tokens: Option<Tokens>,
+    refresh_timer: Option<AbortHandle>,
+}
+
+impl Component for OAuthLogin {
+    type CommandOutput = OAuthCommand;
+    type Input = OAuthMsg;
+    type Output = OAuthOutput;
+    type Init = OAuthConfig;
+    type Root = gtk::Button;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::Button::with_label("Log In")
+    }
+
+    fn init(
+        config: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let input = sender.input_sender().clone();
+        root.connect_clicked(move |_| input.emit(OAuthMsg::Login));
+
+        let model = Self {
+            config,
+            client: reqwest::Client::new(),
+            attempt: None,
+            tokens: None,
+            refresh_timer: None,
+        };
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            OAuthMsg::Login => {
+                self.cancel_attempt();
+                match self.start(&sender) {
+                    Ok(()) => root.set_sensitive(false),
+                    Err(error) => {
+                        self.cancel_attempt();
+                        sender.output(OAuthOutput::Error(error)).ok();
+                    }
+                }
+            }
+            OAuthMsg::Redirected(uri) => {
+                if self.attempt.is_some() {
+                    let result = self.parse_redirect(&uri);
+                    self.update_cmd(OAuthCommand::Redirected(result), sender, root);
+                }
+            }
+            OAuthMsg::Restore(tokens) => {
+                self.set_tokens(tokens, &sender);
+            }
+            OAuthMsg::Refresh => self.refresh(&sender),
+            OAuthMsg::Logout => {
+                self.cancel_attempt();
+                self.cancel_refresh();
+                self.tokens = None;
+                root.set_sensitive(true);
+            }
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            OAuthCommand::Redirected(Ok(code)) => {
+                let Some(attempt) = self.attempt.take() else {
+                    return;
+                };
+                let mut form = vec![
+                    ("grant_type", "authorization_code".to_owned()),
+                    ("code", code),
+                    ("redirect_uri", attempt.redirect_uri),
+                    ("code_verifier", attempt.verifier),
+                ];
+                form.extend(self.client_form());
+                let request = self.client.post(&self.config.token_url).form(&form);
+                sender.oneshot_command(async move {
+                    OAuthCommand::Tokens {
+                        refresh: false,
+                        result: request_tokens(request, None).await,
+                    }
+                });
+            }
+            OAuthCommand::Redirected(Err(error)) => {
+                self.cancel_attempt();
+                root.set_sensitive(true);
+                sender.output(OAuthOutput::Error(error)).ok();
+            }
+            OAuthCommand::Tokens { refresh, result } => {
+                // The user logged out while the tokens were refreshed.
+                if refresh && self.tokens.is_none() {
+                    return;
+                }
+                root.set_sensitive(true);
+                match result {
+                    Ok(tokens) => {
+                        self.set_tokens(tokens.clone(), &sender);
+                        let output = if refresh {
+                            OAuthOutput::Refreshed(tokens)
+                        } else {
+                            OAuthOutput::LoggedIn(tokens)
+                        };
+                        sender.output(output).ok();
+                    }
+                    Err(error) => {
+                        sender.output(OAuthOutput::Error(error)).ok();
+                    }
+                }
+            }
+            OAuthCommand::RefreshDue => self.refresh(&sender),
+        }
+    }
+}
+
+impl OAuthLogin {
+    /// Open the browser and wait for the redirect.
+    fn start(&mut self, sender: &ComponentSender<Self>) -> Result<(), OAuthError> {
+        let verifier = random_string(64);
+        let state = random_string(32);
+
+        let (redirect_uri, listener) = match &self.config.redirect {
+            RedirectMode::Loopback { port } => {
+                let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, *port)))
+                    .and_then(|listener| {
+                        listener.set_nonblocking(true)?;
+                        Ok(listener)
+                    })
+                    .map_err(OAuthError::Listener)?;
+                let port = listener.local_addr().map_err(OAuthError::Listener)?.port();
+                (format!("http://127.0.0.1:{port}/"), Some(listener))
+            }
+            RedirectMode::Custom(uri) => (uri.clone(), None),
+        };
+
+        let mut url = reqwest::Url::parse(&self.config.authorization_url).map_err(|error| {
+            OAuthError::Provider {
+                error: "invalid_request".to_owned(),
+                description: Some(format!("invalid authorization URL: {error}")),
+            }
+        })?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &code_challenge(&verifier))
+            .append_pair("code_challenge_method", "S256");
+
+        let listener = listener.map(|listener| {
+            let (handle, registration) = AbortHandle::new_pair();
+            let state = state.clone();
+            sender.command(move |out, shutdown| {
+                shutdown
+                    .register(async move {
+                        let redirect =
+                            Abortable::new(wait_for_redirect(listener, state), registration);
+                        // Aborted listeners belong to canceled attempts.
+                        if let Ok(result) = redirect.await {
+                            out.emit(OAuthCommand::Redirected(result));
+                        }
+                    })
+                    .drop_on_shutdown()
+            });
+            handle
+        });
+
+        self.attempt = Some(Attempt {
+            verifier,
+            state,
+            redirect_uri,
+            listener,
+        });
+
+        gio::AppInfo::launch_default_for_uri(url.as_str(), None::<&gio::AppLaunchContext>)
+            .map_err(OAuthError::Browser)
+    }
+
+    fn parse_redirect(&self, uri: &str) -> Result<String, OAuthError> {
+        let state = self
+            .attempt
+            .as_ref()
+            .map(|attempt| attempt.state.as_str())
+            .unwrap_or_default();
+        let query = uri.split_once('?').map_or("", |(_, query)| query);
+        parse_query(query, state)
+    }
+
+    fn set_tokens(&mut self, tokens: Tokens, sender: &ComponentSender<Self>) {
+        self.cancel_refresh();
+
+        if let (Some(_), Some(expires_at)) = (&tokens.refresh_token, tokens.expires_at) {
+            let delay = expires_at
+                .duration_since(SystemTime::now())
+                .unwrap_or_default()
+                .saturating_sub(REFRESH_MARGIN);
+            let (handle, registration) = AbortHandle::new_pair();
+            self.refresh_timer = Some(handle);
+            sender.command(move |out, shutdown| {
+                shutdown
+                    .register(async move {
+                        let timer = Abortable::new(tokio::time::sleep(delay), registration);
+                        if timer.await.is_ok() {
+                            out.emit(OAuthCommand::RefreshDue);
+                        }
+                    })
+                    .drop_on_shutdown()
+            });
+        }
+        self.tokens = Some(tokens);
+    }
+
+    fn refresh(&mut self, sender: &ComponentSender<Self>) {
+        let Some(refresh_token) = self
+            .tokens
+            .as_ref()
+            .and_then(|tokens| tokens.refresh_token.clone())
+        else {
+            return;
+        };
+        self.cancel_refresh();
+
+        let mut form = vec![
+            ("grant_type", "refresh_token".to_owned()),
+            ("refresh_token", refresh_token.clone()),
+        ];
+        form.extend(self.client_form());
+        let request = self.client.post(&self.config.token_url).form(&form);
+        sender.oneshot_command(async move {
+            OAuthCommand::Tokens {
+                refresh: true,
+                result: request_tokens(request, Some(refresh_token)).await,
+            }
+        });
+    }
+
+    /// The form fields that identify the client.
+    fn client_form(&self) -> Vec<(&'static str, String)> {
+        let mut form = vec![("client_id", self.config.client_id.clone())];
+        if let Some(secret) = &self.config.client_secret {
+            form.push(("client_secret", secret.clone()));
+        }
+        form
+    }
+
+    fn cancel_attempt(&mut self) {
+        if let Some(listener) = self.attempt.take().and_then(|attempt| attempt.listener) {
+            listener.abort();
+        }
+    }
+
+    fn cancel_refresh(&mut self) {
+        if let Some(timer) = self.refresh_timer.take() {
+            timer.abort();
+        }
+    }
+}
+
+async fn request_tokens(
+    request: reqwest::RequestBuilder,
+    previous_refresh_token: Option<String>,
+) -> Result<Tokens, OAuthError> {
+    let response = request.send().await.map_err(OAuthError::Http)?;
+    if response.status().is_client_error() {
+        // Token endpoints report errors like `invalid_grant` as JSON.
+        #[derive(serde::Deserialize)]
+        struct ErrorResponse {
+            error: String,
+            error_description: Option<String>,
+        }
+        if let Ok(error) = response.json::<ErrorResponse>().await {
+            return Err(OAuthError::Provider {
+                error: error.error,
+                description: error.error_description,
+            });
+        }
+        return Err(OAuthError::Provider {
+            error: "invalid_request".to_owned(),
+            description: None,
+        });
+    }
+    let response: TokenResponse = response
+        .error_for_status()
+        .map_err(OAuthError::Http)?
+        .json()
+        .await
+        .map_err(OAuthError::Http)?;
+    Ok(response.into_tokens(previous_refresh_token))
+}
+
+/// Accept connections until the browser is redirected to the listener.
+///
+/// Gives up after [`LOGIN_TIMEOUT`].
+async fn wait_for_redirect(listener: TcpListener, state: String) -> Result<String, OAuthError> {
+    let listener = tokio::net::TcpListener::from_std(listener).map_err(OAuthError::Listener)?;
+
+    tokio::time::timeout(LOGIN_TIMEOUT, accept_redirect(&listener, &state))
+        .await
+        .unwrap_or_else(|_| {
+            Err(OAuthError::Listener(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the browser wasn't redirected in time",
+            )))
+        })
+}
+
+async fn accept_redirect(
+    listener: &tokio::net::TcpListener,
+    state: &str,
+) -> Result<String, OAuthError> {
+    loop {
+        let (mut stream, _) = listener.accept().await.map_err(OAuthError::Listener)?;
+
+        // Other local processes can connect as well, so slow or
+        // endless requests must not block the listener.
+        let mut request_line = String::new();
+        let read = tokio::time::timeout(
+            REQUEST_TIMEOUT,
+            BufReader::new((&mut stream).take(MAX_REQUEST_LINE)).read_line(&mut request_line),
+        )
+        .await;
+        if !matches!(read, Ok(Ok(_))) || !request_line.ends_with('\n') {
+            stream.write_all(BAD_REQUEST).await.ok();
+            continue;
+        }
+
+        // Like `GET /?code=...&state=... HTTP/1.1`
+        let Some(target) = request_line.split_whitespace().nth(1) else {
+            stream.write_all(BAD_REQUEST).await.ok();
+            continue;
+        };
+        let Some((_, query)) = target.split_once('?') else {
+            // Browsers also request things like `/favicon.ico`.
+            stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await
+                .ok();
+            continue;
+        };
+
+        let result = parse_query(query, state);
+        if let Err(OAuthError::StateMismatch) = result {
+            // Not the redirect of this attempt, so keep waiting for it.
+            stream.write_all(BAD_REQUEST).await.ok();
+            continue;
+        }
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{DONE_PAGE}",
+            DONE_PAGE.len()
+        );
+        stream.write_all(response.as_bytes()).await.ok();
+        return result;
+    }
+}
+
+/// Read the authorization code from the query of the redirect.
+fn parse_query(query: &str, expected_state: &str) -> Result<String, OAuthError> {
+    let mut code = None;
+    let mut state = None;
+    let mut error = None;
+    let mut description = None;
+    for (key, value) in form_urlencoded(query) {
+        match key.as_str() {
+            "code" => code = Some(value),
+            "state" => state = Some(value),
+            "error" => error = Some(value),
+            "error_description" => description = Some(value),
+            _ => {}
+        }
+    }
+
+    if state.as_deref() != Some(expected_state) {
+        Err(OAuthError::StateMismatch)
+    } else if let Some(error) = error {
+        Err(OAuthError::Provider { error, description })
+    } else {
+        code.ok_or(OAuthError::MissingCode)
+    }
+}
+
+fn form_urlencoded(query: &str) -> Vec<(String, String)> {
+    reqwest::Url::parse(&format!("http://localhost/?{query}"))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default()
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
@@ -0,0 +1,153 @@
+//! A banner that is shown while the internet isn't reachable.
+//!
+//! The banner follows [`relm4::network::NETWORK_STATUS`] and slides in
+//! when the connection drops. Captive portals get their own message,
+//! because the user can usually fix them by logging in.
+
+use relm4::gtk::prelude::{BoxExt, ButtonExt};
+use relm4::network::{self, Connectivity, NetworkStatus};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// Configuration for the offline banner.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `offline_message` is set to "You are offline".
+/// - `portal_message` is set to "Log in to the network to go online".
+/// - `retry_label` is set to [`None`], so there is no retry button.
+#[derive(Debug, Clone)]
+pub struct OfflineBannerSettings {
+    /// The message shown while the network is unreachable.
+    pub offline_message: String,
+    /// The message shown while a captive portal blocks the internet.
+    pub portal_message: String,
+    /// The label of a button that sends [`OfflineBannerOutput::Retry`].
+    pub retry_label: Option<String>,
+}
+
+impl Default for OfflineBannerSettings {
+    fn default() -> Self {
+        Self {
+            offline_message: String::from("You are offline"),
+            portal_message: String::from("Log in to the network to go online"),
+            retry_label: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the offline banner.
+#[derive(Debug)]
+pub enum OfflineBannerMsg {
+    /// Change the message shown while the network is unreachable.
+    SetOfflineMessage(String),
+    #[doc(hidden)]
+    StatusChanged(NetworkStatus),
+    #[doc(hidden)]
+    Retry,
+}
+
+/// Messages that are sent by the offline banner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfflineBannerOutput {
+    /// The network status changed.
+    StatusChanged(NetworkStatus),
+    /// The retry button was clicked.
+    Retry,
+}
+
+/// The widgets of the offline banner.
+#[derive(Debug)]
+pub struct OfflineBannerWidgets {
+    label: gtk::Label,
+}
+
+/// A revealer with a message that is shown while the internet isn't reachable.
+#[derive(Debug)]
+pub struct OfflineBanner {
+    offline_message: String,
+    portal_message: String,
+    status: NetworkStatus,
+}
+
+impl Component for OfflineBanner {
+    type CommandOutput = ();
+    type Input = OfflineBannerMsg;
+    type Output = OfflineBannerOutput;
+    type Init = OfflineBannerSettings;
+    type Root = gtk::Revealer;
+    type Widgets = OfflineBannerWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Revealer::builder()
+            .transition_type(gtk::RevealerTransitionType::SlideDown)
+            .build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let bar = gtk::Box::builder()
+            .spacing(12)
+            .css_classes(["toolbar", "osd"])
+            .build();
+        let label = gtk::Label::builder().hexpand(true).wrap(true).build();
+        bar.append(&label);
+
+        if let Some(retry_label) = &settings.retry_label {
+            let retry = gtk::Button::with_label(retry_label);
+            let input = sender.input_sender().clone();
+            retry.connect_clicked(move |_| input.emit(OfflineBannerMsg::Retry));
+            bar.append(&retry);
+        }
+        root.set_child(Some(&bar));
+
+        let model = Self {
+            offline_message: settings.offline_message,
+            portal_message: settings.portal_message,
+            status: NetworkStatus::default(),
+        };
+        let widgets = OfflineBannerWidgets { label };
+
+        network::subscribe(sender.input_sender(), |status| {
+            OfflineBannerMsg::StatusChanged(*status)
+        });
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            OfflineBannerMsg::SetOfflineMessage(message) => {
+                self.offline_message = message;
+            }
+            OfflineBannerMsg::StatusChanged(status) => {
+                if status != self.status {
+                    self.status = status;
+                    // Ignore send errors because the component might be detached.
+                    sender
+                        .output(OfflineBannerOutput::StatusChanged(status))
+                        .ok();
+                }
+            }
+            OfflineBannerMsg::Retry => {
+                sender.output(OfflineBannerOutput::Retry).ok();
+            }
+        }
+
+        let message = if self.status.connectivity == Connectivity::Portal {
+            &self.portal_message
+        } else {
+            &self.offline_message
+        };
+        widgets.label.set_label(message);
+        root.set_reveal_child(!self.status.is_online());
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! **[Example implementation](https://github.com/Relm4/Relm4/blob/main/relm4-components/examples/file_dialogs.rs)**
 use gtk::prelude::{Cast, FileChooserExt, FileExt, ListModelExt, NativeDialogExt};
-use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+use relm4::{gtk, ComponentParts, ComponentSender, MainWindowRegistry, SimpleComponent};
 
 use std::{fmt::Debug, marker::PhantomData, path::PathBuf};
 
@@ -121,6 +121,7 @@ impl<S: Select + 'static> SimpleComponent for OpenDialogInner<S> {
     type Output = OpenDialogResponse<S>;
 
     view! {
+        #[name(dialog)]
         gtk::FileChooserNative {
             set_action: if settings.folder_mode {
                 gtk::FileChooserAction::SelectFolder
@@ -168,6 +169,12 @@ impl<S: Select + 'static> SimpleComponent for OpenDialogInner<S> {
         ComponentParts { model, widgets }
     }
 
+    fn pre_view() {
+        if self.visible {
+            MainWindowRegistry::set_transient_parent_native(dialog);
+        }
+    }
+
     fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
         match message {
             OpenDialogMsg::Open => {
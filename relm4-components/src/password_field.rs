@@ -0,0 +1,356 @@
+//! A password entry with a peek button, a caps lock warning and an optional strength meter.
+//!
+//! The text is kept in a [`gtk::PasswordEntryBuffer`], which stores it in non-pageable memory.
+//! It's only read when the password is submitted, by pressing enter or with
+//! [`PasswordFieldMsg::Submit`], and copied straight into a [`Password`],
+//! which overwrites its memory when it's dropped.
+//!
+//! The memory of the GTK buffer itself can't be wiped from Rust. GTK only clears it
+//! when the buffer is freed, so [`PasswordFieldMsg::Clear`] removes the text
+//! without overwriting it.
+//!
+//! With the `password_strength` feature, the strength of the password is estimated with
+//! [zxcvbn](https://docs.rs/zxcvbn) and shown in a level bar below the entry.
+
+use std::ffi::CStr;
+use std::fmt;
+
+use relm4::gtk::glib::translate::ToGlibPtr;
+use relm4::gtk::prelude::{
+    BoxExt, Cast, CastNone, DeviceExt, DisplayExt, EditableExt, ObjectExt, SeatExt, WidgetExt,
+};
+use relm4::gtk::{gdk, glib};
+use relm4::validation::{grapheme_len, ValidationError};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+use zeroize::Zeroizing;
+
+/// A password that is overwritten with zeros when it's dropped.
+///
+/// The [`Debug`] output hides the password.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password(Zeroizing<String>);
+
+impl Password {
+    /// The text of the password.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Password(***)")
+    }
+}
+
+impl From<&str> for Password {
+    fn from(text: &str) -> Self {
+        Self(Zeroizing::new(text.to_owned()))
+    }
+}
+
+impl From<&gtk::PasswordEntryBuffer> for Password {
+    /// Copies the text of `buffer` without leaving other copies behind.
+    fn from(buffer: &gtk::PasswordEntryBuffer) -> Self {
+        // `EntryBufferExtManual::text()` would return a copy that GLib frees without wiping it,
+        // so the text is borrowed from the buffer instead.
+        // SAFETY: The buffer owns the text and doesn't change it while it's borrowed here.
+        let text = unsafe {
+            let buffer: *mut gtk::ffi::GtkEntryBuffer =
+                buffer.upcast_ref::<gtk::EntryBuffer>().to_glib_none().0;
+            CStr::from_ptr(gtk::ffi::gtk_entry_buffer_get_text(buffer))
+        };
+        // Reserve the exact size, so the string never reallocates and leaves unwiped memory behind.
+        let mut password = Zeroizing::new(String::with_capacity(text.to_bytes().len()));
+        // The text of GTK buffers is always valid UTF-8, so this doesn't copy.
+        password.push_str(&text.to_string_lossy());
+        Self(password)
+    }
+}
+
+/// The estimated strength of a password.
+#[cfg(feature = "password_strength")]
+#[cfg_attr(docsrs, doc(cfg(feature = "password_strength")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordStrength {
+    /// The score from 0 (too guessable) to 4 (very unguessable).
+    pub score: u8,
+    /// Why the password is weak, if it is.
+    pub warning: Option<String>,
+}
+
+/// Estimate the strength of `password`.
+///
+/// `user_inputs` are words that are easy to guess for this user, like their name or email.
+#[cfg(feature = "password_strength")]
+#[cfg_attr(docsrs, doc(cfg(feature = "password_strength")))]
+#[must_use]
+pub fn strength(password: &str, user_inputs: &[&str]) -> PasswordStrength {
+    let entropy = zxcvbn::zxcvbn(password, user_inputs);
+    PasswordStrength {
+        score: entropy.score().into(),
+        warning: entropy
+            .feedback()
+            .and_then(|feedback| feedback.warning())
+            .map(|warning| warning.to_string()),
+    }
+}
+
+/// Configuration for the password field.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `placeholder` is set to "Password".
+/// - `min_length` is set to 8.
+/// - `min_score` is set to 3. It's only used with the `password_strength` feature.
+/// - `user_inputs` is empty.
+#[derive(Debug, Clone)]
+pub struct PasswordFieldSettings {
+    /// The text shown while the entry is empty.
+    pub placeholder: String,
+    /// The minimal number of characters, counted as grapheme clusters.
+    pub min_length: usize,
+    /// The minimal strength score from 0 to 4.
+    pub min_score: u8,
+    /// Words that make a password weak, like the name or email of the user.
+    pub user_inputs: Vec<String>,
+}
+
+impl Default for PasswordFieldSettings {
+    fn default() -> Self {
+        Self {
+            placeholder: String::from("Password"),
+            min_length: 8,
+            min_score: 3,
+            user_inputs: Vec::new(),
+        }
+    }
+}
+
+/// Messages that can be sent to the password field.
+#[derive(Debug)]
+pub enum PasswordFieldMsg {
+    /// Remove the password from the entry.
+    Clear,
+    /// Show or hide the peek button.
+    SetPeekable(bool),
+    /// Read and validate the password, like pressing enter does.
+    Submit,
+    #[doc(hidden)]
+    Changed,
+    #[doc(hidden)]
+    CapsLock(bool),
+}
+
+/// Messages that are sent by the password field.
+#[derive(Debug)]
+pub enum PasswordFieldOutput {
+    /// The user edited the password. It isn't read until it's submitted.
+    Changed,
+    /// The password was submitted. It's only [`Ok`] if it's long and strong enough.
+    Submitted(Result<Password, ValidationError>),
+}
+
+/// The widgets of the password field.
+#[derive(Debug)]
+pub struct PasswordFieldWidgets {
+    entry: gtk::PasswordEntry,
+    buffer: gtk::PasswordEntryBuffer,
+    caps_lock: gtk::Label,
+    #[cfg(feature = "password_strength")]
+    meter: gtk::LevelBar,
+    error: gtk::Label,
+}
+
+/// A password entry that validates its text.
+#[derive(Debug)]
+pub struct PasswordField {
+    min_length: usize,
+    #[cfg_attr(not(feature = "password_strength"), allow(dead_code))]
+    min_score: u8,
+    #[cfg_attr(not(feature = "password_strength"), allow(dead_code))]
+    user_inputs: Vec<String>,
+    /// The keyboard and the caps lock handler, which is disconnected on shutdown.
+    caps_lock_handler: Option<(gdk::Device, glib::SignalHandlerId)>,
+}
+
+impl Component for PasswordField {
+    type CommandOutput = ();
+    type Input = PasswordFieldMsg;
+    type Output = PasswordFieldOutput;
+    type Init = PasswordFieldSettings;
+    type Root = gtk::Box;
+    type Widgets = PasswordFieldWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Vertical, 6)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let entry = gtk::PasswordEntry::builder()
+            .show_peek_icon(true)
+            .placeholder_text(&settings.placeholder)
+            .activates_default(false)
+            .build();
+        root.append(&entry);
+
+        // Make sure the text is stored in secure memory, even if GTK changes the default buffer.
+        let buffer = gtk::PasswordEntryBuffer::new();
+        entry
+            .delegate()
+            .and_downcast::<gtk::Text>()
+            .expect("the delegate of a password entry is a text widget")
+            .set_buffer(&buffer);
+
+        let caps_lock = gtk::Label::builder()
+            .label("Caps Lock is on")
+            .xalign(0.0)
+            .visible(false)
+            .css_classes(["caption", "warning"])
+            .build();
+        root.append(&caps_lock);
+
+        #[cfg(feature = "password_strength")]
+        let meter = {
+            let meter = gtk::LevelBar::builder()
+                .min_value(0.0)
+                .max_value(4.0)
+                .mode(gtk::LevelBarMode::Discrete)
+                .build();
+            meter.add_offset_value(gtk::LEVEL_BAR_OFFSET_LOW, 2.0);
+            meter.add_offset_value(gtk::LEVEL_BAR_OFFSET_HIGH, 3.0);
+            meter.add_offset_value(gtk::LEVEL_BAR_OFFSET_FULL, 4.0);
+            root.append(&meter);
+            meter
+        };
+
+        let error = gtk::Label::builder()
+            .xalign(0.0)
+            .wrap(true)
+            .visible(false)
+            .css_classes(["caption", "error"])
+            .build();
+        root.append(&error);
+
+        let input = sender.input_sender().clone();
+        entry.connect_changed(move |_| input.emit(PasswordFieldMsg::Changed));
+        let input = sender.input_sender().clone();
+        entry.connect_activate(move |_| input.emit(PasswordFieldMsg::Submit));
+
+        let keyboard = gdk::Display::default()
+            .and_then(|display| display.default_seat())
+            .and_then(|seat| seat.keyboard());
+        let caps_lock_handler = keyboard.map(|keyboard| {
+            caps_lock.set_visible(keyboard.is_caps_locked());
+            let input = sender.input_sender().clone();
+            let handler = keyboard.connect_caps_lock_state_notify(move |keyboard| {
+                input.emit(PasswordFieldMsg::CapsLock(keyboard.is_caps_locked()));
+            });
+            (keyboard, handler)
+        });
+
+        let model = Self {
+            min_length: settings.min_length,
+            min_score: settings.min_score.min(4),
+            user_inputs: settings.user_inputs,
+            caps_lock_handler,
+        };
+        let widgets = PasswordFieldWidgets {
+            entry,
+            buffer,
+            caps_lock,
+            #[cfg(feature = "password_strength")]
+            meter,
+            error,
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match message {
+            PasswordFieldMsg::Clear => {
+                widgets.entry.set_text("");
+            }
+            PasswordFieldMsg::SetPeekable(peekable) => {
+                widgets.entry.set_show_peek_icon(peekable);
+            }
+            PasswordFieldMsg::Submit => {
+                let password = Password::from(&widgets.buffer);
+                let result = self.validate(widgets, password);
+
+                if let Err(error) = &result {
+                    widgets.error.set_label(error.message());
+                }
+                widgets.error.set_visible(result.is_err());
+                // Ignore send errors because the component might be detached.
+                sender.output(PasswordFieldOutput::Submitted(result)).ok();
+            }
+            PasswordFieldMsg::Changed => {
+                // The result of the last submission is outdated now.
+                widgets.error.set_visible(false);
+                #[cfg(feature = "password_strength")]
+                widgets.meter.set_value(0.0);
+                sender.output(PasswordFieldOutput::Changed).ok();
+            }
+            PasswordFieldMsg::CapsLock(locked) => {
+                widgets.caps_lock.set_visible(locked);
+            }
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        if let Some((keyboard, handler)) = self.caps_lock_handler.take() {
+            keyboard.disconnect(handler);
+        }
+    }
+}
+
+impl PasswordField {
+    /// Check the length and strength of `password` and update the strength meter.
+    #[cfg_attr(not(feature = "password_strength"), allow(unused_variables))]
+    fn validate(
+        &self,
+        widgets: &PasswordFieldWidgets,
+        password: Password,
+    ) -> Result<Password, ValidationError> {
+        let length = grapheme_len(password.expose());
+
+        #[cfg(feature = "password_strength")]
+        {
+            let user_inputs: Vec<&str> = self.user_inputs.iter().map(String::as_str).collect();
+            let strength = strength(password.expose(), &user_inputs);
+            widgets.meter.set_value(if length == 0 {
+                0.0
+            } else {
+                f64::from(strength.score)
+            });
+
+            if length >= self.min_length && strength.score < self.min_score {
+                return Err(ValidationError::new(strength.warning.unwrap_or_else(
+                    || String::from("This password is too easy to guess"),
+                )));
+            }
+        }
+
+        if length < self.min_length {
+            Err(ValidationError::new(format!(
+                "Use at least {} characters",
+                self.min_length
+            )))
+        } else {
+            Ok(password)
+        }
+    }
+}
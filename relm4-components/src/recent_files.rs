@@ -0,0 +1,337 @@
+//! Recently used files of the application.
+//!
+//! [`RecentFiles`] wraps [`gtk::RecentManager`], so the files also show up
+//! in the file chooser and other applications. Pinned files are listed first
+//! and never expire. Whenever the list changes, files that don't exist anymore
+//! are filtered out in the background and [`RecentFilesOutput::Changed`] is sent.
+//!
+//! [`recent_files_menu()`] turns the list into a menu for an "Open Recent" submenu:
+//!
+//! ```no_run
+//! # use relm4::gtk::gio;
+//! # use relm4_components::recent_files::{recent_files_menu, RecentFile};
+//! relm4::new_action_group!(WindowActionGroup, "win");
+//! // The target is the path of the file.
+//! relm4::new_stateful_action!(OpenRecentAction, WindowActionGroup, "open-recent", String, ());
+//!
+//! fn update_menu(submenu: &gio::Menu, files: &[RecentFile]) {
+//!     submenu.remove_all();
+//!     submenu.append_section(None, &recent_files_menu::<OpenRecentAction>(files));
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use relm4::actions::{ActionName, RelmAction};
+use relm4::gtk::prelude::{FileExt, ObjectExt, RecentManagerExt};
+use relm4::gtk::{gio, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// A recently used file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentFile {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The name that should be shown to the user.
+    pub display_name: String,
+    /// When the file was used the last time.
+    pub modified: Option<glib::DateTime>,
+    /// The file was pinned by the user.
+    pub pinned: bool,
+}
+
+/// Configuration for the recent files component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `max_entries` is set to 10, pinned files don't count.
+/// - `application_only` is set to [`true`].
+/// - `pins_file` is set to [`None`], so pins are only kept in memory.
+#[derive(Debug, Clone)]
+pub struct RecentFilesSettings {
+    /// The maximum number of files that aren't pinned.
+    pub max_entries: usize,
+    /// Only list files that were used by this application.
+    pub application_only: bool,
+    /// A file that stores the pinned files, one path per line.
+    pub pins_file: Option<PathBuf>,
+}
+
+impl Default for RecentFilesSettings {
+    fn default() -> Self {
+        Self {
+            max_entries: 10,
+            application_only: true,
+            pins_file: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the recent files component.
+#[derive(Debug)]
+pub enum RecentFilesMsg {
+    /// Add a file or move it to the top of the list.
+    Add(PathBuf),
+    /// Remove a file from the list and unpin it.
+    Remove(PathBuf),
+    /// Pin a file, so it's listed first and doesn't expire.
+    Pin(PathBuf),
+    /// Unpin a file.
+    Unpin(PathBuf),
+    /// Remove all files that aren't pinned.
+    Clear,
+    /// Read the list again, for example after files were deleted.
+    Refresh,
+}
+
+/// Messages that are sent by the recent files component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecentFilesOutput {
+    /// The list of existing files changed, pinned files come first.
+    Changed(Vec<RecentFile>),
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Validated {
+    generation: u64,
+    files: Vec<RecentFile>,
+}
+
+/// A component without widgets that manages the recent files of the application.
+#[derive(Debug)]
+pub struct RecentFiles {
+    manager: gtk::RecentManager,
+    /// The `changed` handler of the shared manager, which is disconnected on shutdown.
+    changed_handler: Option<glib::SignalHandlerId>,
+    application_name: Option<glib::GString>,
+    max_entries: usize,
+    pins_file: Option<PathBuf>,
+    pinned: Vec<PathBuf>,
+    /// Results of older validations are ignored.
+    generation: u64,
+    files: Vec<RecentFile>,
+}
+
+impl Component for RecentFiles {
+    type CommandOutput = Validated;
+    type Input = RecentFilesMsg;
+    type Output = RecentFilesOutput;
+    type Init = RecentFilesSettings;
+    type Root = ();
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {}
+
+    fn init(
+        settings: Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let manager = gtk::RecentManager::default();
+        let input = sender.input_sender().clone();
+        let changed_handler = manager.connect_changed(move |_| input.emit(RecentFilesMsg::Refresh));
+
+        let pinned = settings
+            .pins_file
+            .as_deref()
+            .map(load_pins)
+            .unwrap_or_default();
+
+        let mut model = Self {
+            manager,
+            changed_handler: Some(changed_handler),
+            application_name: settings
+                .application_only
+                .then(glib::application_name)
+                .flatten(),
+            max_entries: settings.max_entries,
+            pins_file: settings.pins_file,
+            pinned,
+            generation: 0,
+            files: Vec::new(),
+        };
+        model.refresh(&sender);
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        match message {
+            RecentFilesMsg::Add(path) => {
+                // The manager emits `changed`, which refreshes the list.
+                self.manager.add_item(&uri(&path));
+                return;
+            }
+            RecentFilesMsg::Remove(path) => {
+                if self.unpin(&path) {
+                    self.save_pins();
+                }
+                // Files that aren't in the list anymore can't be removed.
+                self.manager.remove_item(&uri(&path)).ok();
+            }
+            RecentFilesMsg::Pin(path) => {
+                if !self.pinned.contains(&path) {
+                    self.pinned.push(path);
+                    self.save_pins();
+                }
+            }
+            RecentFilesMsg::Unpin(path) => {
+                if self.unpin(&path) {
+                    self.save_pins();
+                }
+            }
+            RecentFilesMsg::Clear => {
+                for file in self.files.iter().filter(|file| !file.pinned) {
+                    self.manager.remove_item(&uri(&file.path)).ok();
+                }
+            }
+            RecentFilesMsg::Refresh => {}
+        }
+        self.refresh(&sender);
+    }
+
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        if message.generation == self.generation && message.files != self.files {
+            self.files = message.files;
+            // Ignore send errors because the component might be detached.
+            sender
+                .output(RecentFilesOutput::Changed(self.files.clone()))
+                .ok();
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        if let Some(handler) = self.changed_handler.take() {
+            self.manager.disconnect(handler);
+        }
+    }
+}
+
+impl RecentFiles {
+    /// Collect the files and check in the background whether they still exist.
+    fn refresh(&mut self, sender: &ComponentSender<Self>) {
+        let mut items: Vec<gtk::RecentInfo> = self
+            .manager
+            .items()
+            .into_iter()
+            .filter(|info| info.is_local())
+            .filter(|info| {
+                self.application_name
+                    .as_ref()
+                    .map_or(true, |name| info.has_application(name))
+            })
+            .collect();
+        // Newest first
+        items.sort_by_key(|info| std::cmp::Reverse(info.modified().to_unix()));
+
+        let mut seen = HashSet::new();
+        let mut files: Vec<RecentFile> = self
+            .pinned
+            .iter()
+            .map(|path| {
+                let info = items
+                    .iter()
+                    .find(|info| gio::File::for_uri(&info.uri()).path().as_ref() == Some(path));
+                RecentFile {
+                    path: path.clone(),
+                    display_name: info
+                        .map_or_else(|| file_name(path), |info| info.display_name().to_string()),
+                    modified: info.map(gtk::RecentInfo::modified),
+                    pinned: true,
+                }
+            })
+            .collect();
+        seen.extend(self.pinned.iter().cloned());
+
+        let recent = items.iter().filter_map(|info| {
+            let path = gio::File::for_uri(&info.uri()).path()?;
+            seen.insert(path.clone()).then(|| RecentFile {
+                path,
+                display_name: info.display_name().to_string(),
+                modified: Some(info.modified()),
+                pinned: false,
+            })
+        });
+        files.extend(recent.take(self.max_entries));
+
+        self.generation += 1;
+        let generation = self.generation;
+        sender.spawn_oneshot_command(move || {
+            files.retain(|file| file.path.exists());
+            Validated { generation, files }
+        });
+    }
+
+    fn unpin(&mut self, path: &Path) -> bool {
+        let len = self.pinned.len();
+        self.pinned.retain(|pinned| pinned != path);
+        self.pinned.len() != len
+    }
+
+    fn save_pins(&self) {
+        let Some(pins_file) = &self.pins_file else {
+            return;
+        };
+        let contents: String = self
+            .pinned
+            .iter()
+            .filter_map(|path| path.to_str().map(|path| format!("{path}\n")))
+            .collect();
+        // The pins are still kept in memory if they can't be saved.
+        let _ = fs::write(pins_file, contents);
+    }
+}
+
+/// Create a menu with one item per file that activates the action `Name`
+/// with the path of the file as target. Pinned files get their own section.
+pub fn recent_files_menu<Name>(files: &[RecentFile]) -> gio::Menu
+where
+    Name: ActionName<Target = String>,
+{
+    let section = |pinned: bool| {
+        let menu = gio::Menu::new();
+        for file in files.iter().filter(|file| file.pinned == pinned) {
+            let target = file.path.to_string_lossy().into_owned();
+            let item =
+                RelmAction::<Name>::to_menu_item_with_target_value(&file.display_name, &target);
+            menu.append_item(&item);
+        }
+        menu
+    };
+
+    let menu = gio::Menu::new();
+    menu.append_section(None, &section(true));
+    menu.append_section(None, &section(false));
+    menu
+}
+
+fn uri(path: &Path) -> glib::GString {
+    gio::File::for_path(path).uri()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn load_pins(path: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -2,7 +2,7 @@
 //!
 //! **[Example implementation](https://github.com/Relm4/Relm4/blob/main/relm4-components/examples/file_dialogs.rs)**
 use gtk::prelude::{FileChooserExt, FileExt, NativeDialogExt};
-use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+use relm4::{gtk, ComponentParts, ComponentSender, MainWindowRegistry, SimpleComponent};
 
 use std::path::PathBuf;
 
@@ -69,6 +69,7 @@ impl SimpleComponent for SaveDialog {
     type Output = SaveDialogResponse;
 
     view! {
+        #[name(dialog)]
         gtk::FileChooserNative {
             set_action: gtk::FileChooserAction::Save,
 
@@ -118,6 +119,12 @@ impl SimpleComponent for SaveDialog {
         ComponentParts { model, widgets }
     }
 
+    fn pre_view() {
+        if self.visible {
+            MainWindowRegistry::set_transient_parent_native(dialog);
+        }
+    }
+
     fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
         match message {
             SaveDialogMsg::Save => {
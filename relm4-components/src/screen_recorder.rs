@@ -0,0 +1,286 @@
+//! Reusable component that records the screen into a video file.
+//!
+//! The [`ScreenRecorder`] asks the user to choose a monitor or window through the
+//! [ScreenCast portal](relm4::screen_capture) and records the PipeWire stream with GStreamer.
+//! Recording stops after [`ScreenRecorderMsg::Stop`] once the file was completely written:
+//!
+//! ```no_run
+//! use relm4::Component;
+//! use relm4_components::screen_recorder::{ScreenRecorder, ScreenRecorderMsg};
+//!
+//! let recorder = ScreenRecorder::builder()
+//!     .launch(Default::default())
+//!     .detach();
+//! recorder.emit(ScreenRecorderMsg::Start("recording.webm".into()));
+//! ```
+//!
+//! The default pipeline needs the `pipewiresrc` element of PipeWire's GStreamer plugin
+//! and the VP8 encoder of `gst-plugins-good`.
+
+use std::error::Error;
+use std::fmt;
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
+
+use gstreamer as gst;
+use gstreamer::prelude::{Cast, ElementExt, GstBinExt, ObjectExt};
+use relm4::gtk::glib;
+use relm4::screen_capture::{self, CaptureError, Screencast, ScreencastOptions};
+use relm4::{Component, ComponentParts, ComponentSender};
+
+/// Configuration for the screen recorder component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `options` let the user choose a single monitor and embed the cursor.
+/// - `encoder` is set to `"videoconvert ! queue ! vp8enc deadline=1 ! webmmux"`, which writes WebM files.
+#[derive(Debug, Clone)]
+pub struct ScreenRecorderSettings {
+    /// The sources the user can choose.
+    pub options: ScreencastOptions,
+    /// The part of the GStreamer pipeline between the PipeWire source and the file sink.
+    pub encoder: String,
+}
+
+impl Default for ScreenRecorderSettings {
+    fn default() -> Self {
+        Self {
+            options: ScreencastOptions::default(),
+            encoder: "videoconvert ! queue ! vp8enc deadline=1 ! webmmux".into(),
+        }
+    }
+}
+
+/// An error that stopped a recording.
+#[derive(Debug)]
+pub enum ScreenRecorderError {
+    /// The screencast couldn't be started.
+    Capture(CaptureError),
+    /// The GStreamer pipeline failed.
+    Pipeline(glib::Error),
+}
+
+impl fmt::Display for ScreenRecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capture(error) => write!(f, "can't capture the screen: {error}"),
+            Self::Pipeline(error) => write!(f, "the recording failed: {error}"),
+        }
+    }
+}
+
+impl Error for ScreenRecorderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Capture(error) => Some(error),
+            Self::Pipeline(error) => Some(error),
+        }
+    }
+}
+
+/// Messages that can be sent to the screen recorder component.
+#[derive(Debug)]
+pub enum ScreenRecorderMsg {
+    /// Let the user choose a source and record it into the file at this path.
+    ///
+    /// Ignored while a recording is running.
+    Start(PathBuf),
+    /// Stop the recording.
+    Stop,
+    #[doc(hidden)]
+    Screencast(Result<Screencast, CaptureError>),
+    #[doc(hidden)]
+    Finished,
+    #[doc(hidden)]
+    Failed(glib::Error),
+}
+
+/// Messages that are sent by the screen recorder component.
+#[derive(Debug)]
+pub enum ScreenRecorderOutput {
+    /// The recording started.
+    Started,
+    /// The recording was written to the file at this path.
+    Finished(PathBuf),
+    /// The recording couldn't be started or failed.
+    Error(ScreenRecorderError),
+}
+
+/// A running recording.
+struct Recording {
+    pipeline: gst::Pipeline,
+    path: PathBuf,
+    /// The recording stops when the bus watch is removed.
+    _bus_watch: gst::bus::BusWatchGuard,
+    /// The screencast has to stay alive while the stream is read.
+    _screencast: Screencast,
+}
+
+impl fmt::Debug for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        self.pipeline.set_state(gst::State::Null).ok();
+    }
+}
+
+/// A component without widgets that records the screen.
+#[derive(Debug)]
+pub struct ScreenRecorder {
+    settings: ScreenRecorderSettings,
+    /// The path of a recording that waits for the user to choose a source.
+    pending: Option<PathBuf>,
+    recording: Option<Recording>,
+}
+
+impl Component for ScreenRecorder {
+    type CommandOutput = ();
+    type Input = ScreenRecorderMsg;
+    type Output = ScreenRecorderOutput;
+    type Init = ScreenRecorderSettings;
+    type Root = ();
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {}
+
+    fn init(
+        settings: Self::Init,
+        _root: Self::Root,
+        _sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Self {
+            settings,
+            pending: None,
+            recording: None,
+        };
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        match message {
+            ScreenRecorderMsg::Start(path) => {
+                if self.pending.is_some() || self.recording.is_some() {
+                    return;
+                }
+                self.pending = Some(path);
+                screen_capture::request_screencast(
+                    self.settings.options,
+                    sender.input_sender(),
+                    ScreenRecorderMsg::Screencast,
+                );
+            }
+            ScreenRecorderMsg::Stop => {
+                if let Some(recording) = &self.recording {
+                    // Let the muxer finish the file, `Finished` is sent once it's written.
+                    recording.pipeline.send_event(gst::event::Eos::new());
+                }
+            }
+            ScreenRecorderMsg::Screencast(result) => {
+                let Some(path) = self.pending.take() else {
+                    return;
+                };
+                let result = result
+                    .map_err(ScreenRecorderError::Capture)
+                    .and_then(|screencast| {
+                        self.record(screencast, path, &sender)
+                            .map_err(ScreenRecorderError::Pipeline)
+                    });
+                match result {
+                    Ok(recording) => {
+                        self.recording = Some(recording);
+                        sender.output(ScreenRecorderOutput::Started).ok();
+                    }
+                    Err(error) => {
+                        sender.output(ScreenRecorderOutput::Error(error)).ok();
+                    }
+                }
+            }
+            ScreenRecorderMsg::Finished => {
+                if let Some(recording) = self.recording.take() {
+                    let path = recording.path.clone();
+                    drop(recording);
+                    sender.output(ScreenRecorderOutput::Finished(path)).ok();
+                }
+            }
+            ScreenRecorderMsg::Failed(error) => {
+                if self.recording.take().is_some() {
+                    sender
+                        .output(ScreenRecorderOutput::Error(ScreenRecorderError::Pipeline(
+                            error,
+                        )))
+                        .ok();
+                }
+            }
+        }
+    }
+}
+
+impl ScreenRecorder {
+    /// Record the first stream of `screencast` into the file at `path`.
+    fn record(
+        &self,
+        screencast: Screencast,
+        path: PathBuf,
+        sender: &ComponentSender<Self>,
+    ) -> Result<Recording, glib::Error> {
+        gst::init()?;
+
+        let stream = screencast.streams().first().ok_or_else(|| {
+            glib::Error::new(gst::ResourceError::NotFound, "No stream was selected")
+        })?;
+
+        let description = format!(
+            "pipewiresrc name=source do-timestamp=true ! {} ! filesink name=sink",
+            self.settings.encoder
+        );
+        let pipeline = gst::parse::launch(&description)?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| glib::Error::new(gst::CoreError::Failed, "Not a pipeline"))?;
+
+        // Set the properties here instead of the description, so paths don't need quoting.
+        let source = pipeline
+            .by_name("source")
+            .expect("source is in the pipeline");
+        source.set_property("fd", screencast.pipewire_fd().as_raw_fd());
+        source.set_property("path", stream.node_id.to_string());
+        let sink = pipeline.by_name("sink").expect("sink is in the pipeline");
+        sink.set_property("location", path.to_string_lossy().as_ref());
+
+        let input = sender.input_sender().clone();
+        let bus_watch = pipeline
+            .bus()
+            .expect("pipelines have a bus")
+            .add_watch_local(move |_, message| {
+                match message.view() {
+                    gst::MessageView::Eos(_) => input.emit(ScreenRecorderMsg::Finished),
+                    gst::MessageView::Error(error) => {
+                        input.emit(ScreenRecorderMsg::Failed(error.error()));
+                    }
+                    _ => (),
+                }
+                glib::ControlFlow::Continue
+            })
+            .map_err(|error| glib::Error::new(gst::CoreError::Failed, &error.to_string()))?;
+
+        let recording = Recording {
+            pipeline,
+            path,
+            _bus_watch: bus_watch,
+            _screencast: screencast,
+        };
+        recording
+            .pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|_| {
+                glib::Error::new(gst::CoreError::StateChange, "Can't start the pipeline")
+            })?;
+
+        Ok(recording)
+    }
+}
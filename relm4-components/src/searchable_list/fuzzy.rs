@@ -0,0 +1,127 @@
+//! Fuzzy matching with scoring similar to skim.
+
+/// Score of every matched character.
+const SCORE_MATCH: i64 = 16;
+/// Penalty for every skipped character between two matched characters.
+const PENALTY_GAP: i64 = 1;
+/// Bonus for a character that directly follows the previous match.
+const BONUS_CONSECUTIVE: i64 = 8;
+/// Bonus for a character at the start of a word.
+const BONUS_BOUNDARY: i64 = 10;
+/// Bonus for an uppercase character after a lowercase one.
+const BONUS_CAMEL: i64 = 8;
+
+/// The result of [`fuzzy_match()`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FuzzyMatch {
+    /// The score of the match, higher is better.
+    pub score: i64,
+    /// The byte offsets of the matched characters in the text.
+    pub indices: Vec<usize>,
+}
+
+/// Matches `pattern` against `text` and returns the best scoring match.
+///
+/// All characters of the pattern have to appear in the text in the same order,
+/// but not necessarily next to each other. Matches at the start of words and
+/// consecutive matches are preferred, while gaps between matched characters
+/// are penalized.
+///
+/// Matching is case-insensitive unless the pattern contains uppercase characters.
+/// Whitespace in the pattern is ignored. An empty pattern matches every text
+/// with a score of zero.
+#[must_use]
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let normalize = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(normalize)
+        .collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    let text: Vec<(usize, char)> = text.char_indices().collect();
+    if text.len() < pattern.len() {
+        return None;
+    }
+    let bonuses: Vec<i64> = (0..text.len())
+        .map(|j| {
+            let current = text[j].1;
+            match j.checked_sub(1).map(|prev| text[prev].1) {
+                None => BONUS_BOUNDARY,
+                Some(prev) if !prev.is_alphanumeric() && current.is_alphanumeric() => {
+                    BONUS_BOUNDARY
+                }
+                Some(prev) if prev.is_lowercase() && current.is_uppercase() => BONUS_CAMEL,
+                Some(_) => 0,
+            }
+        })
+        .collect();
+
+    // `scores[i][j]` is the best score of matching `pattern[..=i]`
+    // with `pattern[i]` matched at `text[j]`, `previous[i][j]` the position
+    // of `pattern[i - 1]` in that match.
+    let mut scores: Vec<Vec<Option<i64>>> = vec![vec![None; text.len()]; pattern.len()];
+    let mut previous: Vec<Vec<usize>> = vec![vec![0; text.len()]; pattern.len()];
+
+    for (i, &wanted) in pattern.iter().enumerate() {
+        // The best score of the previous row including the gap penalty,
+        // for matches that are not directly before the current position.
+        let mut best_gap: Option<(i64, usize)> = None;
+        for j in i..text.len() {
+            if j >= 2 && i > 0 {
+                if let Some(score) = scores[i - 1][j - 2] {
+                    let candidate = score - PENALTY_GAP;
+                    if best_gap.map_or(true, |(best, _)| candidate > best) {
+                        best_gap = Some((candidate, j - 2));
+                    }
+                }
+            }
+
+            if normalize(text[j].1) == wanted {
+                let own = SCORE_MATCH + bonuses[j];
+                if i == 0 {
+                    scores[i][j] = Some(own);
+                } else {
+                    let consecutive =
+                        scores[i - 1][j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+                    let best = match (consecutive, best_gap) {
+                        (Some(a), Some(b)) => Some(if b.0 > a.0 { b } else { a }),
+                        (a, b) => a.or(b),
+                    };
+                    if let Some((score, position)) = best {
+                        scores[i][j] = Some(score + own);
+                        previous[i][j] = position;
+                    }
+                }
+            }
+
+            best_gap = best_gap.map(|(score, position)| (score - PENALTY_GAP, position));
+        }
+    }
+
+    let last = pattern.len() - 1;
+    let (mut position, score) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+
+    let mut indices = vec![0; pattern.len()];
+    for i in (0..pattern.len()).rev() {
+        indices[i] = text[position].0;
+        position = previous[i][position];
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
@@ -0,0 +1,309 @@
+//! Reusable searchable list component for settings pages and command palettes.
+//!
+//! The component combines a [`gtk::SearchEntry`] with a list of items that is
+//! filtered and ranked with [`fuzzy_match()`] while the user types.
+//! Matched characters are highlighted in the item titles and pressing
+//! <kbd>Enter</kbd> in the search entry activates the best match.
+
+mod fuzzy;
+
+pub use fuzzy::{fuzzy_match, FuzzyMatch};
+
+use std::cmp::Reverse;
+use std::fmt::Debug;
+
+use relm4::gtk::pango;
+use relm4::gtk::prelude::{BoxExt, EditableExt, WidgetExt};
+use relm4::typed_view::list::{RelmListItem, TypedListView};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// An item that can be shown in a [`SearchableList`].
+pub trait SearchableItem: Debug + Clone + 'static {
+    /// The title of the item that is matched against the search query.
+    fn title(&self) -> String;
+
+    /// An optional description that is shown below the title.
+    fn subtitle(&self) -> Option<String> {
+        None
+    }
+}
+
+impl SearchableItem for String {
+    fn title(&self) -> String {
+        self.clone()
+    }
+}
+
+/// Configuration for the searchable list component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `items` is empty.
+/// - `placeholder` is set to "Search".
+#[derive(Debug, Clone)]
+pub struct SearchableListSettings<T> {
+    /// The items of the list.
+    pub items: Vec<T>,
+    /// The placeholder text of the search entry.
+    pub placeholder: String,
+}
+
+impl<T> Default for SearchableListSettings<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            placeholder: String::from("Search"),
+        }
+    }
+}
+
+/// Messages that can be sent to the searchable list component.
+#[derive(Debug)]
+pub enum SearchableListMsg<T> {
+    /// Replace the items of the list.
+    SetItems(Vec<T>),
+    /// Replace the search query.
+    SetQuery(String),
+    #[doc(hidden)]
+    QueryChanged,
+    #[doc(hidden)]
+    ActivateBest,
+    #[doc(hidden)]
+    ActivateRow(u32),
+}
+
+/// Messages that are sent by the searchable list component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchableListOutput<T> {
+    /// An item was activated, either by the user
+    /// or because it was the best match when <kbd>Enter</kbd> was pressed.
+    Activated {
+        /// The index of the item in the list of all items.
+        index: usize,
+        /// The activated item.
+        item: T,
+    },
+}
+
+#[derive(Debug)]
+struct SearchRow<T> {
+    index: usize,
+    item: T,
+    /// The byte offsets of the matched characters in the title.
+    indices: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct SearchRowWidgets {
+    title: gtk::Label,
+    subtitle: gtk::Label,
+}
+
+impl<T: SearchableItem> RelmListItem for SearchRow<T> {
+    type Root = gtk::Box;
+    type Widgets = SearchRowWidgets;
+
+    fn setup(_item: &gtk::ListItem) -> (gtk::Box, SearchRowWidgets) {
+        let root = gtk::Box::new(gtk::Orientation::Vertical, 2);
+        let title = gtk::Label::new(None);
+        title.set_xalign(0.0);
+        title.set_ellipsize(pango::EllipsizeMode::End);
+        let subtitle = gtk::Label::new(None);
+        subtitle.set_xalign(0.0);
+        subtitle.set_ellipsize(pango::EllipsizeMode::End);
+        subtitle.add_css_class("dim-label");
+        subtitle.add_css_class("caption");
+
+        root.append(&title);
+        root.append(&subtitle);
+        (root, SearchRowWidgets { title, subtitle })
+    }
+
+    fn bind(&mut self, widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        let title = self.item.title();
+        widgets.title.set_label(&title);
+        widgets
+            .title
+            .set_attributes(Some(&highlight_attributes(&title, &self.indices)));
+
+        let subtitle = self.item.subtitle();
+        widgets.subtitle.set_visible(subtitle.is_some());
+        widgets
+            .subtitle
+            .set_label(subtitle.as_deref().unwrap_or(""));
+    }
+}
+
+/// Searchable list component.
+#[derive(Debug)]
+pub struct SearchableList<T: SearchableItem> {
+    items: Vec<T>,
+    query: String,
+    list: TypedListView<SearchRow<T>, gtk::SingleSelection>,
+}
+
+/// The widgets of the searchable list component.
+#[derive(Debug)]
+pub struct SearchableListWidgets {
+    entry: gtk::SearchEntry,
+}
+
+impl<T: SearchableItem> Component for SearchableList<T> {
+    type CommandOutput = ();
+    type Input = SearchableListMsg<T>;
+    type Output = SearchableListOutput<T>;
+    type Init = SearchableListSettings<T>;
+    type Root = gtk::Box;
+    type Widgets = SearchableListWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::new(gtk::Orientation::Vertical, 6)
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let entry = gtk::SearchEntry::new();
+        entry.set_placeholder_text(Some(&settings.placeholder));
+        let input = sender.input_sender().clone();
+        entry.connect_search_changed(move |_| input.emit(SearchableListMsg::QueryChanged));
+        let input = sender.input_sender().clone();
+        entry.connect_activate(move |_| input.emit(SearchableListMsg::ActivateBest));
+
+        let list = TypedListView::<SearchRow<T>, gtk::SingleSelection>::new();
+        let input = sender.input_sender().clone();
+        list.view.connect_activate(move |_, position| {
+            input.emit(SearchableListMsg::ActivateRow(position))
+        });
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_hscrollbar_policy(gtk::PolicyType::Never);
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&list.view));
+
+        root.append(&entry);
+        root.append(&scrolled);
+
+        let mut model = Self {
+            items: settings.items,
+            query: String::new(),
+            list,
+        };
+        model.refresh();
+
+        ComponentParts {
+            model,
+            widgets: SearchableListWidgets { entry },
+        }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        input: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match input {
+            SearchableListMsg::SetItems(items) => {
+                self.items = items;
+                self.refresh();
+            }
+            SearchableListMsg::SetQuery(query) => {
+                // Updates the list through the search-changed signal.
+                widgets.entry.set_text(&query);
+            }
+            SearchableListMsg::QueryChanged => {
+                let query = widgets.entry.text().to_string();
+                if query != self.query {
+                    self.query = query;
+                    self.refresh();
+                }
+            }
+            SearchableListMsg::ActivateBest => self.activate(0, &sender),
+            SearchableListMsg::ActivateRow(position) => self.activate(position, &sender),
+        }
+    }
+}
+
+impl<T: SearchableItem> SearchableList<T> {
+    /// The items of the list.
+    #[must_use]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The current search query.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Rebuilds the list from the items that match the query, best matches first.
+    fn refresh(&mut self) {
+        let mut rows: Vec<(i64, SearchRow<T>)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                fuzzy_match(&self.query, &item.title()).map(|matched| {
+                    let row = SearchRow {
+                        index,
+                        item: item.clone(),
+                        indices: matched.indices,
+                    };
+                    (matched.score, row)
+                })
+            })
+            .collect();
+        // The sort is stable, so equally good matches keep their order.
+        rows.sort_by_key(|(score, _)| Reverse(*score));
+
+        self.list.clear();
+        self.list
+            .extend_from_iter(rows.into_iter().map(|(_, row)| row));
+    }
+
+    fn activate(&self, position: u32, sender: &ComponentSender<Self>) {
+        if let Some(row) = self.list.get_visible(position) {
+            let row = row.borrow();
+            sender
+                .output(SearchableListOutput::Activated {
+                    index: row.index,
+                    item: row.item.clone(),
+                })
+                .ok();
+        }
+    }
+}
+
+/// Highlights the matched characters at the byte offsets `indices` in `text`.
+fn highlight_attributes(text: &str, indices: &[usize]) -> pango::AttrList {
+    let attributes = pango::AttrList::new();
+    let mut indices = indices.iter().copied().peekable();
+    while let Some(start) = indices.next() {
+        let mut end = start + char_len(text, start);
+        // Merge consecutive characters into a single range.
+        while indices.peek() == Some(&end) {
+            indices.next();
+            end += char_len(text, end);
+        }
+
+        let mut weight = pango::AttrInt::new_weight(pango::Weight::Bold);
+        weight.set_start_index(start as u32);
+        weight.set_end_index(end as u32);
+        attributes.insert(weight);
+
+        let mut underline = pango::AttrInt::new_underline(pango::Underline::Single);
+        underline.set_start_index(start as u32);
+        underline.set_end_index(end as u32);
+        attributes.insert(underline);
+    }
+    attributes
+}
+
+fn char_len(text: &str, index: usize) -> usize {
+    text[index..].chars().next().map_or(0, char::len_utf8)
+}
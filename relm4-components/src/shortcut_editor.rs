@@ -0,0 +1,535 @@
+//! Let users change the keyboard shortcuts of actions.
+//!
+//! [`ShortcutEditor`] lists the registered actions with their accelerators.
+//! Activating a row asks for a new shortcut, which is applied to the
+//! [main application](relm4::main_application()) right away. Shortcuts that are
+//! already used by another action are rejected. The changed shortcuts can be
+//! stored in a [`gio::Settings`] key, they are applied again when the editor
+//! is initialized.
+//!
+//! ```no_run
+//! # use relm4::prelude::*;
+//! # use relm4_components::shortcut_editor::{ShortcutEditor, ShortcutEditorSettings, ShortcutEntry};
+//! relm4::new_action_group!(WindowActionGroup, "win");
+//! relm4::new_stateless_action!(SaveAction, WindowActionGroup, "save", label = "Save");
+//!
+//! # fn example() {
+//! let editor = ShortcutEditor::builder()
+//!     .launch(ShortcutEditorSettings {
+//!         entries: vec![ShortcutEntry::new::<SaveAction>(&["<Control>s"]).section("File")],
+//!         ..Default::default()
+//!     })
+//!     .detach();
+//! # }
+//! ```
+
+use relm4::actions::{ActionName, EmptyType};
+use relm4::gtk::prelude::{
+    BoxExt, ButtonExt, EditableExt, GtkApplicationExt, GtkWindowExt, ListBoxRowExt,
+    SettingsExtManual, WidgetExt,
+};
+use relm4::gtk::{gdk, gio, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, MainWindowRegistry};
+
+/// An action whose shortcuts can be changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutEntry {
+    action_name: String,
+    title: String,
+    section: Option<String>,
+    defaults: Vec<String>,
+}
+
+impl ShortcutEntry {
+    /// Register the action `A` with its default accelerators.
+    ///
+    /// The title is the [label](ActionName::LABEL) of the action, or its name.
+    #[must_use]
+    pub fn new<A>(defaults: &[&str]) -> Self
+    where
+        A: ActionName,
+        A::Target: EmptyType,
+    {
+        Self {
+            action_name: A::action_name(),
+            title: A::LABEL.unwrap_or(A::NAME).to_owned(),
+            section: None,
+            defaults: defaults.iter().map(|&accel| accel.to_owned()).collect(),
+        }
+    }
+
+    /// Change the title that is shown to the user.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Show the section, like "File" or "Edit", next to the title.
+    #[must_use]
+    pub fn section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+}
+
+/// Configuration for the shortcut editor component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `entries` is empty.
+/// - `gsettings` is set to [`None`].
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutEditorSettings {
+    /// The actions that can be changed, in the order they are listed.
+    pub entries: Vec<ShortcutEntry>,
+    /// A string array key of [`gio::Settings`] that stores the changed shortcuts.
+    pub gsettings: Option<(gio::Settings, String)>,
+}
+
+/// Messages that can be sent to the shortcut editor component.
+#[derive(Debug)]
+pub enum ShortcutEditorMsg {
+    /// Ask the user for a new shortcut of an action.
+    Edit(String),
+    /// Remove all shortcuts of an action.
+    Clear(String),
+    /// Restore the default shortcuts of an action.
+    Reset(String),
+    /// Restore the default shortcuts of all actions.
+    ResetAll,
+    #[doc(hidden)]
+    EditRow(usize),
+    #[doc(hidden)]
+    Search(String),
+    #[doc(hidden)]
+    KeyPressed(gdk::Key, gdk::ModifierType),
+    #[doc(hidden)]
+    CancelCapture,
+}
+
+/// Messages that are sent by the shortcut editor component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutEditorOutput {
+    /// The shortcuts of an action changed.
+    Changed {
+        /// The full name of the action, like `win.save`.
+        action_name: String,
+        /// The new accelerators.
+        accels: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+struct Row {
+    entry: ShortcutEntry,
+    accels: Vec<String>,
+    row: gtk::ListBoxRow,
+    label: gtk::ShortcutLabel,
+    reset: gtk::Button,
+}
+
+/// A searchable list of actions whose shortcuts can be changed.
+#[derive(Debug)]
+pub struct ShortcutEditor {
+    rows: Vec<Row>,
+    gsettings: Option<(gio::Settings, String)>,
+    /// Shows why a shortcut was rejected.
+    error: gtk::Label,
+    /// The row that gets a new shortcut and the window that captures it.
+    capture: Option<(usize, gtk::Window)>,
+}
+
+impl Component for ShortcutEditor {
+    type CommandOutput = ();
+    type Input = ShortcutEditorMsg;
+    type Output = ShortcutEditorOutput;
+    type Init = ShortcutEditorSettings;
+    type Root = gtk::Box;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let search = gtk::SearchEntry::builder()
+            .placeholder_text("Search Shortcuts")
+            .build();
+        let input = sender.input_sender().clone();
+        search.connect_search_changed(move |search| {
+            input.emit(ShortcutEditorMsg::Search(search.text().to_string()));
+        });
+
+        let error = gtk::Label::builder()
+            .wrap(true)
+            .visible(false)
+            .css_classes(["error"])
+            .build();
+
+        let list = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        let input = sender.input_sender().clone();
+        list.connect_row_activated(move |_, row| {
+            input.emit(ShortcutEditorMsg::EditRow(row.index() as usize));
+        });
+
+        let reset_all = gtk::Button::builder()
+            .label("Reset All")
+            .halign(gtk::Align::End)
+            .build();
+        let input = sender.input_sender().clone();
+        reset_all.connect_clicked(move |_| input.emit(ShortcutEditorMsg::ResetAll));
+
+        root.append(&search);
+        root.append(&error);
+        root.append(
+            &gtk::ScrolledWindow::builder()
+                .hscrollbar_policy(gtk::PolicyType::Never)
+                .vexpand(true)
+                .child(&list)
+                .build(),
+        );
+        root.append(&reset_all);
+
+        let stored = settings
+            .gsettings
+            .as_ref()
+            .map(|(gsettings, key)| parse_overrides(&gsettings.strv(key)))
+            .unwrap_or_default();
+
+        let app = relm4::main_application();
+        let rows = settings
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let accels = stored
+                    .iter()
+                    .find(|(action_name, _)| *action_name == entry.action_name)
+                    .map_or_else(|| entry.defaults.clone(), |(_, accels)| accels.clone());
+                set_accels(&app, &entry.action_name, &accels);
+
+                let row = create_row(&entry, &sender);
+                list.append(&row.row);
+                let row = Row { accels, ..row };
+                update_row(&row);
+                row
+            })
+            .collect();
+
+        let model = Self {
+            rows,
+            gsettings: settings.gsettings,
+            error,
+            capture: None,
+        };
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            ShortcutEditorMsg::Edit(action_name) => {
+                if let Some(index) = self.index(&action_name) {
+                    self.start_capture(index, root, &sender);
+                }
+            }
+            ShortcutEditorMsg::EditRow(index) => {
+                if index < self.rows.len() {
+                    self.start_capture(index, root, &sender);
+                }
+            }
+            ShortcutEditorMsg::Clear(action_name) => {
+                if let Some(index) = self.index(&action_name) {
+                    self.set(index, Vec::new(), &sender);
+                }
+            }
+            ShortcutEditorMsg::Reset(action_name) => {
+                if let Some(index) = self.index(&action_name) {
+                    let defaults = self.rows[index].entry.defaults.clone();
+                    self.set(index, defaults, &sender);
+                }
+            }
+            ShortcutEditorMsg::ResetAll => {
+                for index in 0..self.rows.len() {
+                    let defaults = self.rows[index].entry.defaults.clone();
+                    self.set(index, defaults, &sender);
+                }
+            }
+            ShortcutEditorMsg::Search(query) => {
+                let query = query.to_lowercase();
+                for row in &self.rows {
+                    row.row.set_visible(matches(row, &query));
+                }
+            }
+            ShortcutEditorMsg::KeyPressed(key, modifiers) => {
+                self.key_pressed(key, modifiers, &sender)
+            }
+            ShortcutEditorMsg::CancelCapture => self.stop_capture(),
+        }
+    }
+}
+
+impl ShortcutEditor {
+    fn index(&self, action_name: &str) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| row.entry.action_name == action_name)
+    }
+
+    /// Show a small window that waits for the new shortcut.
+    fn start_capture(&mut self, index: usize, root: &gtk::Box, sender: &ComponentSender<Self>) {
+        self.stop_capture();
+        self.error.set_visible(false);
+
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(12)
+            .margin_top(24)
+            .margin_bottom(24)
+            .margin_start(24)
+            .margin_end(24)
+            .build();
+        content.append(
+            &gtk::Label::builder()
+                .label(format!(
+                    "Enter a new shortcut for “{}”",
+                    self.rows[index].entry.title
+                ))
+                .css_classes(["heading"])
+                .build(),
+        );
+        content.append(
+            &gtk::Label::builder()
+                .label("Press Escape to cancel or Backspace to disable the shortcut.")
+                .css_classes(["dim-label"])
+                .build(),
+        );
+
+        // The window doesn't belong to the application,
+        // so the current shortcuts don't activate their actions.
+        let window = gtk::Window::builder()
+            .title("Set Shortcut")
+            .modal(true)
+            .resizable(false)
+            .child(&content)
+            .build();
+        window.set_transient_for(MainWindowRegistry::transient_parent_for(root).as_ref());
+
+        let keys = gtk::EventControllerKey::new();
+        let input = sender.input_sender().clone();
+        keys.connect_key_pressed(move |_, key, _, modifiers| {
+            input.emit(ShortcutEditorMsg::KeyPressed(key, modifiers));
+            glib::Propagation::Stop
+        });
+        window.add_controller(keys);
+
+        let input = sender.input_sender().clone();
+        window.connect_close_request(move |_| {
+            input.emit(ShortcutEditorMsg::CancelCapture);
+            glib::Propagation::Proceed
+        });
+
+        window.present();
+        self.capture = Some((index, window));
+    }
+
+    fn stop_capture(&mut self) {
+        if let Some((_, window)) = self.capture.take() {
+            window.destroy();
+        }
+    }
+
+    fn key_pressed(
+        &mut self,
+        key: gdk::Key,
+        modifiers: gdk::ModifierType,
+        sender: &ComponentSender<Self>,
+    ) {
+        let Some((index, _)) = self.capture else {
+            return;
+        };
+        let key = key.to_lower();
+        let modifiers = modifiers & gtk::accelerator_get_default_mod_mask();
+
+        if modifiers.is_empty() {
+            if key == gdk::Key::Escape {
+                self.stop_capture();
+                return;
+            }
+            if key == gdk::Key::BackSpace {
+                self.stop_capture();
+                self.set(index, Vec::new(), sender);
+                return;
+            }
+        }
+        // Wait for a key that isn't a modifier.
+        if !gtk::accelerator_valid(key, modifiers) {
+            return;
+        }
+        self.stop_capture();
+
+        let accel = gtk::accelerator_name(key, modifiers).to_string();
+        let action_name = &self.rows[index].entry.action_name;
+        let conflict = relm4::main_application()
+            .actions_for_accel(&accel)
+            .into_iter()
+            .find(|other| other.as_str() != action_name.as_str());
+        if let Some(other) = conflict {
+            let title = self.index(&other).map_or_else(
+                || other.to_string(),
+                |other| self.rows[other].entry.title.clone(),
+            );
+            self.error.set_label(&format!(
+                "{} is already used by “{title}”",
+                gtk::accelerator_get_label(key, modifiers)
+            ));
+            self.error.set_visible(true);
+            return;
+        }
+
+        self.set(index, vec![accel], sender);
+    }
+
+    /// Apply, show, store and send the new accelerators of a row.
+    fn set(&mut self, index: usize, accels: Vec<String>, sender: &ComponentSender<Self>) {
+        let row = &mut self.rows[index];
+        if row.accels == accels {
+            return;
+        }
+        row.accels = accels;
+        set_accels(
+            &relm4::main_application(),
+            &row.entry.action_name,
+            &row.accels,
+        );
+        update_row(row);
+
+        let output = ShortcutEditorOutput::Changed {
+            action_name: row.entry.action_name.clone(),
+            accels: row.accels.clone(),
+        };
+        self.store();
+        // Ignore send errors because the component might be detached.
+        sender.output(output).ok();
+    }
+
+    /// Store the shortcuts that differ from the defaults.
+    fn store(&self) {
+        if let Some((gsettings, key)) = &self.gsettings {
+            let entries: Vec<String> = self
+                .rows
+                .iter()
+                .filter(|row| row.accels != row.entry.defaults)
+                .map(|row| format!("{}={}", row.entry.action_name, row.accels.join(",")))
+                .collect();
+            let entries: Vec<&str> = entries.iter().map(String::as_str).collect();
+            // The shortcuts still work until the application quits if they can't be stored.
+            gsettings.set_strv(key, entries.as_slice()).ok();
+        }
+    }
+}
+
+fn create_row(entry: &ShortcutEntry, sender: &ComponentSender<ShortcutEditor>) -> Row {
+    let content = gtk::Box::builder()
+        .spacing(12)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(12)
+        .margin_end(6)
+        .build();
+
+    let titles = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .hexpand(true)
+        .valign(gtk::Align::Center)
+        .build();
+    titles.append(
+        &gtk::Label::builder()
+            .label(&entry.title)
+            .xalign(0.0)
+            .build(),
+    );
+    if let Some(section) = &entry.section {
+        titles.append(
+            &gtk::Label::builder()
+                .label(section)
+                .xalign(0.0)
+                .css_classes(["dim-label", "caption"])
+                .build(),
+        );
+    }
+    content.append(&titles);
+
+    let label = gtk::ShortcutLabel::builder()
+        .disabled_text("Disabled")
+        .valign(gtk::Align::Center)
+        .build();
+    content.append(&label);
+
+    let reset = gtk::Button::builder()
+        .icon_name("edit-undo-symbolic")
+        .tooltip_text("Reset to Default")
+        .valign(gtk::Align::Center)
+        .css_classes(["flat"])
+        .build();
+    let input = sender.input_sender().clone();
+    let action_name = entry.action_name.clone();
+    reset.connect_clicked(move |_| input.emit(ShortcutEditorMsg::Reset(action_name.clone())));
+    content.append(&reset);
+
+    Row {
+        entry: entry.clone(),
+        accels: Vec::new(),
+        row: gtk::ListBoxRow::builder().child(&content).build(),
+        label,
+        reset,
+    }
+}
+
+fn update_row(row: &Row) {
+    // The label shows multiple accelerators separated by spaces.
+    row.label.set_accelerator(&row.accels.join(" "));
+    row.reset.set_visible(row.accels != row.entry.defaults);
+}
+
+fn matches(row: &Row, query: &str) -> bool {
+    let accels = row.accels.iter().filter_map(|accel| {
+        gtk::accelerator_parse(accel.as_str())
+            .map(|(key, modifiers)| gtk::accelerator_get_label(key, modifiers).to_string())
+    });
+    std::iter::once(row.entry.title.clone())
+        .chain(row.entry.section.clone())
+        .chain(accels)
+        .any(|text| text.to_lowercase().contains(query))
+}
+
+fn set_accels(app: &gtk::Application, action_name: &str, accels: &[String]) {
+    let accels: Vec<&str> = accels.iter().map(String::as_str).collect();
+    app.set_accels_for_action(action_name, &accels);
+}
+
+/// Parse the entries stored as `"{action}={accel},{accel}"`.
+fn parse_overrides(entries: &[glib::GStringPtr]) -> Vec<(String, Vec<String>)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (action_name, accels) = entry.as_str().split_once('=')?;
+            let accels = accels
+                .split(',')
+                .filter(|accel| !accel.is_empty())
+                .map(str::to_owned)
+                .collect();
+            Some((action_name.to_owned(), accels))
+        })
+        .collect()
+}
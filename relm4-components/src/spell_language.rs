@@ -0,0 +1,104 @@
+//! A drop down that selects the language of spell checking.
+//!
+//! The component lists the dictionaries that are installed and changes
+//! the language of the text inputs that were added with
+//! [`SpellLanguageMsg::AddTarget`], see [`relm4::spell`].
+
+use relm4::gtk::prelude::ObjectExt;
+use relm4::spell::{self, AUTO};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// Messages that can be sent to the language picker.
+#[derive(Debug)]
+pub enum SpellLanguageMsg {
+    /// Select a language like `en_US` or [`AUTO`].
+    SetLanguage(String),
+    /// Change the language of a widget whenever a language is selected.
+    ///
+    /// The widget must have been passed to [`spell::attach()`].
+    AddTarget(gtk::Widget),
+    #[doc(hidden)]
+    Selected(u32),
+}
+
+/// A drop down with the installed dictionaries.
+///
+/// Sends the selected language as output.
+#[derive(Debug)]
+pub struct SpellLanguagePicker {
+    /// [`AUTO`] followed by the installed languages.
+    languages: Vec<String>,
+    targets: Vec<gtk::glib::WeakRef<gtk::Widget>>,
+}
+
+impl Component for SpellLanguagePicker {
+    type CommandOutput = ();
+    type Input = SpellLanguageMsg;
+    type Output = String;
+    /// The initially selected language.
+    type Init = String;
+    type Root = gtk::DropDown;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::DropDown::default()
+    }
+
+    fn init(
+        language: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut languages = vec![AUTO.to_owned()];
+        languages.extend(spell::languages());
+
+        let mut labels = vec!["Automatic"];
+        labels.extend(languages[1..].iter().map(String::as_str));
+        root.set_model(Some(&gtk::StringList::new(&labels)));
+
+        let model = Self {
+            languages,
+            targets: Vec::new(),
+        };
+        model.select(&root, &language);
+
+        root.connect_selected_notify(move |drop_down| {
+            sender.input(SpellLanguageMsg::Selected(drop_down.selected()));
+        });
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            SpellLanguageMsg::SetLanguage(language) => self.select(root, &language),
+            SpellLanguageMsg::AddTarget(widget) => {
+                if let Some(language) = self.languages.get(root.selected() as usize) {
+                    spell::set_language(&widget, language);
+                }
+                self.targets.push(widget.downgrade());
+            }
+            SpellLanguageMsg::Selected(index) => {
+                let Some(language) = self.languages.get(index as usize) else {
+                    return;
+                };
+                self.targets.retain(|target| target.upgrade().is_some());
+                for target in &self.targets {
+                    if let Some(target) = target.upgrade() {
+                        spell::set_language(&target, language);
+                    }
+                }
+                // Ignore send errors because the component might be detached.
+                sender.output(language.clone()).ok();
+            }
+        }
+    }
+}
+
+impl SpellLanguagePicker {
+    fn select(&self, root: &gtk::DropDown, language: &str) {
+        if let Some(index) = self.languages.iter().position(|l| l == language) {
+            root.set_selected(index as u32);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Reusable and easily configurable star rating component.
+//!
+//! The rating can be changed with the pointer and the keyboard and is
+//! exposed to assistive technologies as a slider. Values that don't fall
+//! on a step, like averages of several ratings, are drawn as partially
+//! filled stars.
+
+use std::cell::Cell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use relm4::gtk::prelude::{
+    AccessibleExtManual, DrawingAreaExt, DrawingAreaExtManual, EventControllerExt,
+    GestureSingleExt, StyleContextExt, WidgetExt,
+};
+use relm4::gtk::{gdk, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+/// Configuration for the star rating component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `value` is set to 0.
+/// - `max` is set to 5.
+/// - `step` is set to 1, so only whole stars can be selected.
+/// - `star_size` is set to 24.
+/// - `editable` is set to [`true`].
+#[derive(Debug, Clone)]
+pub struct StarRatingSettings {
+    /// The initial rating. It may be fractional, for example to show an average.
+    pub value: f64,
+    /// The number of stars.
+    pub max: u32,
+    /// The step the user can change the rating by, for example 0.5 for half stars.
+    pub step: f64,
+    /// The size of a star in pixels.
+    pub star_size: i32,
+    /// Allow the user to change the rating.
+    pub editable: bool,
+}
+
+impl Default for StarRatingSettings {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            max: 5,
+            step: 1.0,
+            star_size: 24,
+            editable: true,
+        }
+    }
+}
+
+/// Messages that can be sent to the star rating component.
+#[derive(Debug)]
+pub enum StarRatingMsg {
+    /// Set the rating without emitting an output.
+    SetValue(f64),
+    /// Allow or forbid changing the rating.
+    SetEditable(bool),
+    #[doc(hidden)]
+    Pick(f64),
+    #[doc(hidden)]
+    Hover(Option<f64>),
+    #[doc(hidden)]
+    Step(f64),
+}
+
+/// A change of the rating by the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingChange {
+    /// The rating before the change.
+    pub previous: f64,
+    /// The new rating.
+    pub value: f64,
+}
+
+/// Star rating component.
+///
+/// Changes by the user are sent as [`RatingChange`].
+#[derive(Debug)]
+pub struct StarRating {
+    value: f64,
+    max: u32,
+    step: f64,
+    star_size: i32,
+    editable: bool,
+    /// The rating that is drawn, which is the hovered rating while
+    /// the pointer is above the widget.
+    shown: Rc<Cell<f64>>,
+}
+
+impl Component for StarRating {
+    type CommandOutput = ();
+    type Input = StarRatingMsg;
+    type Output = RatingChange;
+    type Init = StarRatingSettings;
+    type Root = gtk::DrawingArea;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        glib::Object::builder()
+            .property("accessible-role", gtk::AccessibleRole::Slider)
+            .build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut model = Self {
+            value: 0.0,
+            max: settings.max.max(1),
+            step: if settings.step > 0.0 {
+                settings.step
+            } else {
+                1.0
+            },
+            star_size: settings.star_size,
+            editable: settings.editable,
+            shown: Rc::default(),
+        };
+
+        root.set_content_width(model.star_size * model.max as i32);
+        root.set_content_height(model.star_size);
+        root.set_halign(gtk::Align::Start);
+        root.set_valign(gtk::Align::Center);
+        root.set_focusable(true);
+        root.set_tooltip_text(Some("Rating"));
+
+        let shown = model.shown.clone();
+        let max = model.max;
+        root.set_draw_func(move |area, context, width, height| {
+            #[allow(deprecated)]
+            let color = area.style_context().color();
+            draw_stars(context, shown.get(), max, width, height, color).ok();
+        });
+
+        // Pointer input
+        let click = gtk::GestureClick::new();
+        click.set_button(gdk::BUTTON_PRIMARY);
+        let input = sender.input_sender().clone();
+        click.connect_released(move |gesture, _, x, _| {
+            if let Some(widget) = gesture.widget() {
+                widget.grab_focus();
+                input.emit(StarRatingMsg::Pick(x / f64::from(widget.width())));
+            }
+        });
+        root.add_controller(click);
+
+        let motion = gtk::EventControllerMotion::new();
+        let input = sender.input_sender().clone();
+        motion.connect_motion(move |controller, x, _| {
+            if let Some(widget) = controller.widget() {
+                input.emit(StarRatingMsg::Hover(Some(x / f64::from(widget.width()))));
+            }
+        });
+        let input = sender.input_sender().clone();
+        motion.connect_leave(move |_| input.emit(StarRatingMsg::Hover(None)));
+        root.add_controller(motion);
+
+        // Keyboard input
+        let keys = gtk::EventControllerKey::new();
+        let input = sender.input_sender().clone();
+        let step = model.step;
+        keys.connect_key_pressed(move |_, key, _, _| {
+            let message = match key {
+                gdk::Key::Right | gdk::Key::Up | gdk::Key::plus | gdk::Key::KP_Add => {
+                    StarRatingMsg::Step(step)
+                }
+                gdk::Key::Left | gdk::Key::Down | gdk::Key::minus | gdk::Key::KP_Subtract => {
+                    StarRatingMsg::Step(-step)
+                }
+                gdk::Key::Home => StarRatingMsg::Pick(0.0),
+                gdk::Key::End => StarRatingMsg::Pick(1.0),
+                key => match key.to_unicode().and_then(|c| c.to_digit(10)) {
+                    Some(digit) if digit <= max => {
+                        StarRatingMsg::Pick(f64::from(digit) / f64::from(max))
+                    }
+                    _ => return glib::Propagation::Proceed,
+                },
+            };
+            input.emit(message);
+            glib::Propagation::Stop
+        });
+        root.add_controller(keys);
+
+        model.set_value(&root, settings.value);
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        let new_value = match input {
+            StarRatingMsg::SetValue(value) => {
+                self.set_value(root, value);
+                return;
+            }
+            StarRatingMsg::SetEditable(editable) => {
+                self.editable = editable;
+                self.set_value(root, self.value);
+                return;
+            }
+            StarRatingMsg::Hover(fraction) => {
+                if self.editable {
+                    let shown = fraction.map_or(self.value, |fraction| self.snap(fraction));
+                    self.show(root, shown);
+                }
+                return;
+            }
+            StarRatingMsg::Pick(fraction) => self.snap(fraction),
+            StarRatingMsg::Step(delta) => {
+                let value = (self.value / self.step).round() * self.step + delta;
+                value.clamp(0.0, f64::from(self.max))
+            }
+        };
+
+        if !self.editable || new_value == self.value {
+            return;
+        }
+        let previous = self.value;
+        self.set_value(root, new_value);
+        sender
+            .output(RatingChange {
+                previous,
+                value: new_value,
+            })
+            .ok();
+    }
+}
+
+impl StarRating {
+    /// The current rating.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Whether the user can change the rating.
+    #[must_use]
+    pub fn is_editable(&self) -> bool {
+        self.editable
+    }
+
+    /// Convert a fraction of the width to a rating rounded up to the next step.
+    fn snap(&self, fraction: f64) -> f64 {
+        let value = fraction.clamp(0.0, 1.0) * f64::from(self.max);
+        ((value / self.step).ceil() * self.step).min(f64::from(self.max))
+    }
+
+    fn set_value(&mut self, root: &gtk::DrawingArea, value: f64) {
+        self.value = value.clamp(0.0, f64::from(self.max));
+        root.update_property(&[
+            gtk::accessible::Property::ValueMin(0.0),
+            gtk::accessible::Property::ValueMax(f64::from(self.max)),
+            gtk::accessible::Property::ValueNow(self.value),
+            gtk::accessible::Property::ValueText(&format!("{} of {} stars", self.value, self.max)),
+            gtk::accessible::Property::ReadOnly(!self.editable),
+        ]);
+        self.show(root, self.value);
+    }
+
+    fn show(&self, root: &gtk::DrawingArea, value: f64) {
+        if self.shown.get() != value {
+            self.shown.set(value);
+            root.queue_draw();
+        }
+    }
+}
+
+/// Draw `max` stars, filled up to `value`.
+fn draw_stars(
+    context: &gtk::cairo::Context,
+    value: f64,
+    max: u32,
+    width: i32,
+    height: i32,
+    color: gdk::RGBA,
+) -> Result<(), gtk::cairo::Error> {
+    let size = f64::from(width) / f64::from(max);
+    let radius = size.min(f64::from(height)) / 2.0 * 0.9;
+    let center_y = f64::from(height) / 2.0;
+
+    context.set_source_rgba(
+        f64::from(color.red()),
+        f64::from(color.green()),
+        f64::from(color.blue()),
+        f64::from(color.alpha()),
+    );
+    context.set_line_width(1.0);
+
+    for index in 0..max {
+        let center_x = size * (f64::from(index) + 0.5);
+        star_path(context, center_x, center_y, radius);
+        context.stroke()?;
+
+        let fill = (value - f64::from(index)).clamp(0.0, 1.0);
+        if fill > 0.0 {
+            context.save()?;
+            context.rectangle(
+                center_x - radius,
+                center_y - radius,
+                radius * 2.0 * fill,
+                radius * 2.0,
+            );
+            context.clip();
+            star_path(context, center_x, center_y, radius);
+            context.fill()?;
+            context.restore()?;
+        }
+    }
+    Ok(())
+}
+
+/// Add the outline of a five-pointed star to the path of `context`.
+fn star_path(context: &gtk::cairo::Context, x: f64, y: f64, radius: f64) {
+    context.new_path();
+    for point in 0..10 {
+        let radius = if point % 2 == 0 { radius } else { radius * 0.4 };
+        let angle = PI * f64::from(point) / 5.0 - PI / 2.0;
+        context.line_to(x + radius * angle.cos(), y + radius * angle.sin());
+    }
+    context.close_path();
+}
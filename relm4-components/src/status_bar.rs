@@ -0,0 +1,570 @@
+//! A status bar that other components fill with indicators.
+//!
+//! Components don't need a reference to the [`StatusBar`]. They send messages
+//! to the [`MessageBroker`] it was launched with, usually [`STATUS_BAR`]:
+//!
+//! ```no_run
+//! # use relm4::prelude::*;
+//! # use relm4_components::status_bar::{Indicator, StatusBar, StatusBarMsg, STATUS_BAR};
+//! # #[derive(Debug)]
+//! # enum Msg { ShowBranches }
+//! # fn example(input: relm4::Sender<Msg>) {
+//! // In the main window
+//! let status_bar = StatusBar::builder()
+//!     .launch_with_broker((), &STATUS_BAR)
+//!     .detach();
+//!
+//! // In any other component
+//! STATUS_BAR.send(StatusBarMsg::Set(
+//!     Indicator::new("branch")
+//!         .icon_name("branch-symbolic")
+//!         .text("main")
+//!         .priority(10)
+//!         .on_event(move |_| input.emit(Msg::ShowBranches)),
+//! ));
+//! STATUS_BAR.send(StatusBarMsg::SetText("branch".into(), Some("develop".into())));
+//! # }
+//! ```
+//!
+//! Indicators that don't fit move into a popover at the end of the bar,
+//! the ones with the lowest [priority](Indicator::priority) first.
+
+use std::fmt;
+
+use relm4::actions::{RelmAction, RelmActionGroup};
+use relm4::gtk::gio;
+use relm4::gtk::prelude::{
+    AdjustmentExt, BoxExt, ButtonExt, Cast, CastNone, PopoverExt, WidgetExt,
+};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, MessageBroker};
+
+/// The default broker of the status bar.
+pub static STATUS_BAR: MessageBroker<StatusBarMsg> = MessageBroker::new();
+
+relm4::new_action_group!(StatusBarActionGroup, "status-bar");
+// The target is the id of the indicator and the id of the menu item.
+relm4::new_stateful_action!(
+    IndicatorMenuAction,
+    StatusBarActionGroup,
+    "menu-item",
+    (String, String),
+    ()
+);
+
+const SPACING: i32 = 12;
+
+/// The side of the status bar an indicator is shown on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IndicatorSide {
+    /// At the start, usually left.
+    Start,
+    /// At the end, usually right.
+    #[default]
+    End,
+}
+
+/// An interaction with an indicator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndicatorEvent {
+    /// An indicator without menu was clicked.
+    Clicked,
+    /// An item of the menu of an indicator was activated, contains the id of the item.
+    MenuItem(String),
+}
+
+/// An item of the menu of an indicator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndicatorMenuItem {
+    /// The id that's sent with [`IndicatorEvent::MenuItem`].
+    pub id: String,
+    /// The label of the item.
+    pub label: String,
+}
+
+type EventCallback = Box<dyn Fn(IndicatorEvent) + Send>;
+
+/// An indicator of the status bar, for example the cursor position or a sync state.
+///
+/// An indicator can show text, an icon and a progress bar.
+/// Indicators with a menu open it when clicked.
+pub struct Indicator {
+    id: String,
+    text: Option<String>,
+    icon_name: Option<String>,
+    tooltip: Option<String>,
+    progress: Option<f64>,
+    menu: Vec<IndicatorMenuItem>,
+    priority: i32,
+    side: IndicatorSide,
+    on_event: Option<EventCallback>,
+}
+
+impl fmt::Debug for Indicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Indicator")
+            .field("id", &self.id)
+            .field("text", &self.text)
+            .field("icon_name", &self.icon_name)
+            .field("tooltip", &self.tooltip)
+            .field("progress", &self.progress)
+            .field("menu", &self.menu)
+            .field("priority", &self.priority)
+            .field("side", &self.side)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Indicator {
+    /// Create an empty indicator.
+    ///
+    /// The `id` identifies the indicator in messages and outputs.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: None,
+            icon_name: None,
+            tooltip: None,
+            progress: None,
+            menu: Vec::new(),
+            priority: 0,
+            side: IndicatorSide::default(),
+            on_event: None,
+        }
+    }
+
+    /// Show a text.
+    #[must_use]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Show an icon before the text.
+    #[must_use]
+    pub fn icon_name(mut self, icon_name: impl Into<String>) -> Self {
+        self.icon_name = Some(icon_name.into());
+        self
+    }
+
+    /// Show a tooltip.
+    #[must_use]
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Show a progress bar, `fraction` is between 0 and 1.
+    #[must_use]
+    pub fn progress(mut self, fraction: f64) -> Self {
+        self.progress = Some(fraction);
+        self
+    }
+
+    /// Add an item to the menu of the indicator.
+    #[must_use]
+    pub fn menu_item(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.menu.push(IndicatorMenuItem {
+            id: id.into(),
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Indicators with a higher priority stay in the bar longer
+    /// if there's not enough space. The default is 0.
+    #[must_use]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the side of the status bar.
+    #[must_use]
+    pub fn side(mut self, side: IndicatorSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Call a function when the indicator is clicked or a menu item is activated.
+    ///
+    /// The events are also sent as [`StatusBarOutput::Indicator`].
+    #[must_use]
+    pub fn on_event<F>(mut self, on_event: F) -> Self
+    where
+        F: Fn(IndicatorEvent) + Send + 'static,
+    {
+        self.on_event = Some(Box::new(on_event));
+        self
+    }
+}
+
+/// Messages that can be sent to the status bar.
+#[derive(Debug)]
+pub enum StatusBarMsg {
+    /// Add an indicator or replace the indicator with the same id.
+    Set(Indicator),
+    /// Change the text of an indicator.
+    SetText(String, Option<String>),
+    /// Change the icon of an indicator.
+    SetIcon(String, Option<String>),
+    /// Change the progress of an indicator.
+    SetProgress(String, Option<f64>),
+    /// Remove an indicator.
+    Remove(String),
+    #[doc(hidden)]
+    Activated(String, IndicatorEvent),
+    #[doc(hidden)]
+    Resized,
+}
+
+/// Messages that are sent by the status bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusBarOutput {
+    /// An indicator was clicked or one of its menu items was activated.
+    Indicator(String, IndicatorEvent),
+}
+
+struct Slot {
+    id: String,
+    priority: i32,
+    side: IndicatorSide,
+    on_event: Option<EventCallback>,
+    widget: gtk::Widget,
+    label: gtk::Label,
+    icon: gtk::Image,
+    progress: gtk::ProgressBar,
+    /// The menu of the indicator.
+    popover: Option<gtk::PopoverMenu>,
+    /// The indicator is shown in the popover.
+    overflow: bool,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        if let Some(popover) = &self.popover {
+            popover.unparent();
+        }
+    }
+}
+
+/// The widgets of the status bar.
+#[derive(Debug)]
+pub struct StatusBarWidgets {
+    start: gtk::Box,
+    end: gtk::Box,
+    overflow_button: gtk::MenuButton,
+    overflow_box: gtk::Box,
+}
+
+/// A bar with indicators, usually at the bottom of a window.
+pub struct StatusBar {
+    /// The indicators in the order they were added.
+    slots: Vec<Slot>,
+}
+
+impl fmt::Debug for StatusBar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids: Vec<&str> = self.slots.iter().map(|slot| slot.id.as_str()).collect();
+        f.debug_struct("StatusBar")
+            .field("indicators", &ids)
+            .finish()
+    }
+}
+
+impl Component for StatusBar {
+    type CommandOutput = ();
+    type Input = StatusBarMsg;
+    type Output = StatusBarOutput;
+    type Init = ();
+    type Root = gtk::Box;
+    type Widgets = StatusBarWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::builder()
+            .spacing(SPACING)
+            .margin_start(6)
+            .margin_end(6)
+            .css_classes(["status-bar"])
+            .build()
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let start = gtk::Box::builder()
+            .spacing(SPACING)
+            .hexpand(true)
+            .halign(gtk::Align::Start)
+            .build();
+        let end = gtk::Box::builder().spacing(SPACING).build();
+        let content = gtk::Box::builder().spacing(SPACING).build();
+        content.append(&start);
+        content.append(&end);
+
+        // The bar can become smaller than the indicators, so they can be moved to the popover.
+        let scrolled = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::External)
+            .vscrollbar_policy(gtk::PolicyType::Never)
+            .propagate_natural_width(true)
+            .propagate_natural_height(true)
+            .hexpand(true)
+            .child(&content)
+            .build();
+        let input = sender.input_sender().clone();
+        scrolled
+            .hadjustment()
+            .connect_page_size_notify(move |_| input.emit(StatusBarMsg::Resized));
+
+        let overflow_box = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .build();
+        let overflow_button = gtk::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text("More Indicators")
+            .css_classes(["flat"])
+            .visible(false)
+            .popover(&gtk::Popover::builder().child(&overflow_box).build())
+            .build();
+
+        root.append(&scrolled);
+        root.append(&overflow_button);
+
+        let input = sender.input_sender().clone();
+        let mut group = RelmActionGroup::<StatusBarActionGroup>::new();
+        group.add_action(RelmAction::<IndicatorMenuAction>::new_with_target_value(
+            move |_, (id, item)| {
+                input.emit(StatusBarMsg::Activated(id, IndicatorEvent::MenuItem(item)))
+            },
+        ));
+        group.register_for_widget(&root);
+
+        let model = Self { slots: Vec::new() };
+        let widgets = StatusBarWidgets {
+            start,
+            end,
+            overflow_button,
+            overflow_box,
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            StatusBarMsg::Set(indicator) => {
+                let slot = create_slot(indicator, &sender);
+                match self.slots.iter().position(|old| old.id == slot.id) {
+                    Some(index) => {
+                        detach(&self.slots[index].widget);
+                        self.slots[index] = slot;
+                    }
+                    None => self.slots.push(slot),
+                }
+                self.arrange(widgets);
+            }
+            StatusBarMsg::SetText(id, text) => {
+                if let Some(slot) = self.slot(&id) {
+                    set_text(&slot.label, text.as_deref());
+                }
+            }
+            StatusBarMsg::SetIcon(id, icon_name) => {
+                if let Some(slot) = self.slot(&id) {
+                    set_icon(&slot.icon, icon_name.as_deref());
+                }
+            }
+            StatusBarMsg::SetProgress(id, progress) => {
+                if let Some(slot) = self.slot(&id) {
+                    set_progress(&slot.progress, progress);
+                }
+            }
+            StatusBarMsg::Remove(id) => {
+                if let Some(index) = self.slots.iter().position(|slot| slot.id == id) {
+                    let slot = self.slots.remove(index);
+                    detach(&slot.widget);
+                    self.arrange(widgets);
+                }
+            }
+            StatusBarMsg::Activated(id, event) => {
+                if let Some(on_event) = self.slot(&id).and_then(|slot| slot.on_event.as_ref()) {
+                    on_event(event.clone());
+                }
+                // Ignore send errors because the component might be detached.
+                sender.output(StatusBarOutput::Indicator(id, event)).ok();
+                return;
+            }
+            StatusBarMsg::Resized => {}
+        }
+
+        self.update_overflow(widgets, root);
+    }
+}
+
+impl StatusBar {
+    fn slot(&self, id: &str) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.id == id)
+    }
+
+    /// Move the indicators that don't fit into the popover.
+    fn update_overflow(&mut self, widgets: &StatusBarWidgets, root: &gtk::Box) {
+        let available = root.width();
+        // Not allocated yet
+        if available <= 0 {
+            return;
+        }
+
+        let width = |widget: &gtk::Widget| widget.measure(gtk::Orientation::Horizontal, -1).1;
+        let total: i32 = self
+            .slots
+            .iter()
+            .map(|slot| width(&slot.widget) + SPACING)
+            .sum();
+
+        let mut overflow = vec![false; self.slots.len()];
+        if total - SPACING > available {
+            let mut by_priority: Vec<usize> = (0..self.slots.len()).collect();
+            // Stable, so earlier indicators win between equal priorities.
+            by_priority.sort_by_key(|&index| std::cmp::Reverse(self.slots[index].priority));
+
+            let mut used = width(widgets.overflow_button.upcast_ref());
+            let mut full = false;
+            for index in by_priority {
+                let needed = width(&self.slots[index].widget) + SPACING;
+                // Lower priorities never stay in the bar while higher ones overflow.
+                full = full || used + needed > available;
+                if full {
+                    overflow[index] = true;
+                } else {
+                    used += needed;
+                }
+            }
+        }
+
+        let changed = self
+            .slots
+            .iter()
+            .zip(&overflow)
+            .any(|(slot, overflow)| slot.overflow != *overflow);
+        if changed {
+            for (slot, overflow) in self.slots.iter_mut().zip(overflow) {
+                slot.overflow = overflow;
+            }
+            self.arrange(widgets);
+        }
+    }
+
+    /// Put the widgets of all indicators into their boxes.
+    fn arrange(&self, widgets: &StatusBarWidgets) {
+        for slot in &self.slots {
+            detach(&slot.widget);
+            let container = if slot.overflow {
+                &widgets.overflow_box
+            } else {
+                match slot.side {
+                    IndicatorSide::Start => &widgets.start,
+                    IndicatorSide::End => &widgets.end,
+                }
+            };
+            container.append(&slot.widget);
+        }
+        widgets
+            .overflow_button
+            .set_visible(self.slots.iter().any(|slot| slot.overflow));
+        if !widgets.overflow_button.is_visible() {
+            widgets.overflow_button.popdown();
+        }
+    }
+}
+
+fn create_slot(indicator: Indicator, sender: &ComponentSender<StatusBar>) -> Slot {
+    let content = gtk::Box::builder().spacing(6).build();
+    let icon = gtk::Image::new();
+    set_icon(&icon, indicator.icon_name.as_deref());
+    let label = gtk::Label::new(None);
+    set_text(&label, indicator.text.as_deref());
+    let progress = gtk::ProgressBar::builder()
+        .width_request(60)
+        .valign(gtk::Align::Center)
+        .build();
+    set_progress(&progress, indicator.progress);
+    content.append(&icon);
+    content.append(&label);
+    content.append(&progress);
+
+    let id = indicator.id;
+    let button = gtk::Button::builder()
+        .child(&content)
+        .css_classes(["flat"])
+        .build();
+    button.set_tooltip_text(indicator.tooltip.as_deref());
+
+    let popover = (!indicator.menu.is_empty()).then(|| {
+        let menu = gio::Menu::new();
+        for item in &indicator.menu {
+            let target = (id.clone(), item.id.clone());
+            menu.append_item(
+                &RelmAction::<IndicatorMenuAction>::to_menu_item_with_target_value(
+                    &item.label,
+                    &target,
+                ),
+            );
+        }
+        let popover = gtk::PopoverMenu::from_model(Some(&menu));
+        popover.set_position(gtk::PositionType::Top);
+        popover.set_parent(&button);
+        popover
+    });
+
+    let input = sender.input_sender().clone();
+    let indicator_id = id.clone();
+    let menu = popover.clone();
+    button.connect_clicked(move |_| match &menu {
+        Some(menu) => menu.popup(),
+        None => input.emit(StatusBarMsg::Activated(
+            indicator_id.clone(),
+            IndicatorEvent::Clicked,
+        )),
+    });
+
+    Slot {
+        id,
+        priority: indicator.priority,
+        side: indicator.side,
+        on_event: indicator.on_event,
+        widget: button.upcast(),
+        popover,
+        label,
+        icon,
+        progress,
+        overflow: false,
+    }
+}
+
+fn detach(widget: &gtk::Widget) {
+    if let Some(parent) = widget.parent().and_downcast::<gtk::Box>() {
+        parent.remove(widget);
+    }
+}
+
+fn set_text(label: &gtk::Label, text: Option<&str>) {
+    label.set_label(text.unwrap_or_default());
+    label.set_visible(text.is_some());
+}
+
+fn set_icon(icon: &gtk::Image, icon_name: Option<&str>) {
+    icon.set_icon_name(icon_name);
+    icon.set_visible(icon_name.is_some());
+}
+
+fn set_progress(progress: &gtk::ProgressBar, fraction: Option<f64>) {
+    progress.set_fraction(fraction.unwrap_or_default());
+    progress.set_visible(fraction.is_some());
+}
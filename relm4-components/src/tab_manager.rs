@@ -0,0 +1,582 @@
+//! Tabs with one child component each, built on [`adw::TabView`].
+//!
+//! Child components implement [`TabPage`] to provide the title of their tab,
+//! to confirm closing and to be restored in the next session.
+//! [`TabManager`] shows an [`adw::TabBar`] above the tabs and
+//! forwards the outputs of the children together with their [`TabId`].
+//!
+//! The context menu of the tabs contains the actions of [`TabActionGroup`]
+//! and an optional section of the application, for example from [`relm4::menu!`].
+//! Right before the menu opens, [`TabManagerOutput::MenuTarget`] tells which tab
+//! the menu belongs to.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use relm4::actions::{ActionName, EmptyType, RelmAction, RelmActionGroup};
+use relm4::gtk::gio;
+use relm4::gtk::glib::Propagation;
+use relm4::gtk::prelude::{BoxExt, Cast, IsA, ToVariant};
+use relm4::{
+    adw, gtk, Component, ComponentController, ComponentParts, ComponentSender, Controller,
+};
+
+relm4::new_action_group!(pub TabActionGroup, "tab");
+relm4::new_stateless_action!(pub PinTabAction, TabActionGroup, "pin");
+relm4::new_stateless_action!(pub UnpinTabAction, TabActionGroup, "unpin");
+relm4::new_stateless_action!(pub CloseTabAction, TabActionGroup, "close");
+relm4::new_stateless_action!(pub CloseOtherTabsAction, TabActionGroup, "close-others");
+
+/// A future that resolves to `true` if a tab may be closed.
+pub type CloseFuture = Pin<Box<dyn Future<Output = bool>>>;
+
+/// A component that can be shown in a tab of a [`TabManager`].
+pub trait TabPage: Component
+where
+    Self::Root: IsA<gtk::Widget>,
+{
+    /// The title of the tab.
+    ///
+    /// It's read when the tab is opened and after the component sent an output.
+    fn title(&self) -> String;
+
+    /// Show a spinner in the tab.
+    fn is_loading(&self) -> bool {
+        false
+    }
+
+    /// Called before the tab is closed.
+    ///
+    /// Return a future to ask the user first, for example
+    /// whether unsaved changes should be discarded.
+    /// The tab is closed if the future resolves to `true`.
+    fn confirm_close(&self) -> Option<CloseFuture> {
+        None
+    }
+
+    /// A description of the tab for the next session, like a path or a URL.
+    ///
+    /// Tabs without a key are not restored.
+    fn session_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Create the init value of a tab from its [session key](Self::session_key).
+    fn restore(key: &str) -> Option<Self::Init> {
+        let _ = key;
+        None
+    }
+}
+
+/// An identifier of a tab of a [`TabManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TabId(u64);
+
+/// A tab that can be restored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabSessionEntry {
+    /// The [session key](TabPage::session_key) of the tab.
+    pub key: String,
+    /// The tab is pinned.
+    pub pinned: bool,
+}
+
+/// The open tabs, to restore them in the next session.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TabSession {
+    /// The tabs in their order.
+    pub tabs: Vec<TabSessionEntry>,
+    /// The index of the selected tab.
+    pub selected: Option<usize>,
+}
+
+/// Configuration for the tab manager.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `autohide` is set to [`false`].
+/// - `menu` is set to [`None`].
+#[derive(Debug, Clone, Default)]
+pub struct TabManagerSettings {
+    /// Hide the tab bar while only one tab is open.
+    pub autohide: bool,
+    /// An additional section for the context menu of the tabs.
+    pub menu: Option<gio::MenuModel>,
+}
+
+/// Messages that can be sent to the tab manager.
+pub enum TabManagerMsg<C: Component> {
+    /// Open a tab and select it.
+    Open(C::Init),
+    /// Open a tab in the background.
+    OpenInBackground(C::Init),
+    /// Close a tab after it [confirmed](TabPage::confirm_close) it.
+    Close(TabId),
+    /// Select a tab.
+    Select(TabId),
+    /// Move a tab to a position, pinned tabs always stay in front.
+    Reorder(TabId, usize),
+    /// Pin or unpin a tab.
+    SetPinned(TabId, bool),
+    /// Send a message to the component of a tab.
+    Send(TabId, C::Input),
+    /// Open the tabs of a previous session.
+    RestoreSession(TabSession),
+    #[doc(hidden)]
+    CloseRequested(adw::TabPage),
+    #[doc(hidden)]
+    Detached(adw::TabPage),
+    #[doc(hidden)]
+    Changed,
+    #[doc(hidden)]
+    ChildOutput(TabId, C::Output),
+    #[doc(hidden)]
+    MenuPage(Option<adw::TabPage>),
+    #[doc(hidden)]
+    MenuAction(MenuAction),
+}
+
+impl<C: Component> fmt::Debug for TabManagerMsg<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open(_) => f.write_str("Open(..)"),
+            Self::OpenInBackground(_) => f.write_str("OpenInBackground(..)"),
+            Self::Close(id) => f.debug_tuple("Close").field(id).finish(),
+            Self::Select(id) => f.debug_tuple("Select").field(id).finish(),
+            Self::Reorder(id, position) => {
+                f.debug_tuple("Reorder").field(id).field(position).finish()
+            }
+            Self::SetPinned(id, pinned) => {
+                f.debug_tuple("SetPinned").field(id).field(pinned).finish()
+            }
+            Self::Send(id, input) => f.debug_tuple("Send").field(id).field(input).finish(),
+            Self::RestoreSession(session) => {
+                f.debug_tuple("RestoreSession").field(session).finish()
+            }
+            Self::CloseRequested(page) => f.debug_tuple("CloseRequested").field(page).finish(),
+            Self::Detached(page) => f.debug_tuple("Detached").field(page).finish(),
+            Self::Changed => f.write_str("Changed"),
+            Self::ChildOutput(id, output) => f
+                .debug_tuple("ChildOutput")
+                .field(id)
+                .field(output)
+                .finish(),
+            Self::MenuPage(page) => f.debug_tuple("MenuPage").field(page).finish(),
+            Self::MenuAction(action) => f.debug_tuple("MenuAction").field(action).finish(),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum MenuAction {
+    Pin,
+    Unpin,
+    Close,
+    CloseOthers,
+}
+
+/// Messages that are sent by the tab manager.
+pub enum TabManagerOutput<C: Component> {
+    /// The component of a tab sent an output.
+    Child(TabId, C::Output),
+    /// Another tab or no tab was selected.
+    Selected(Option<TabId>),
+    /// A tab was closed.
+    Closed(TabId),
+    /// The context menu is opened for a tab.
+    MenuTarget(Option<TabId>),
+    /// Tabs were opened, closed, moved, pinned or selected.
+    SessionChanged(TabSession),
+}
+
+impl<C: Component> fmt::Debug for TabManagerOutput<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Child(id, output) => f.debug_tuple("Child").field(id).field(output).finish(),
+            Self::Selected(id) => f.debug_tuple("Selected").field(id).finish(),
+            Self::Closed(id) => f.debug_tuple("Closed").field(id).finish(),
+            Self::MenuTarget(id) => f.debug_tuple("MenuTarget").field(id).finish(),
+            Self::SessionChanged(session) => {
+                f.debug_tuple("SessionChanged").field(session).finish()
+            }
+        }
+    }
+}
+
+struct Tab<C: Component> {
+    id: TabId,
+    page: adw::TabPage,
+    controller: Controller<C>,
+}
+
+/// The widgets of the tab manager.
+#[derive(Debug)]
+pub struct TabManagerWidgets {
+    view: adw::TabView,
+    pin_action: gio::SimpleAction,
+    unpin_action: gio::SimpleAction,
+}
+
+/// Manages an [`adw::TabView`] with one component per tab.
+pub struct TabManager<C>
+where
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    tabs: Vec<Tab<C>>,
+    next_id: u64,
+    selected: Option<TabId>,
+    menu_page: Option<adw::TabPage>,
+    session: TabSession,
+}
+
+impl<C> fmt::Debug for TabManager<C>
+where
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TabManager")
+            .field("tabs", &self.tabs.len())
+            .field("selected", &self.selected)
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> Component for TabManager<C>
+where
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    type CommandOutput = ();
+    type Input = TabManagerMsg<C>;
+    type Output = TabManagerOutput<C>;
+    type Init = TabManagerSettings;
+    type Root = gtk::Box;
+    type Widgets = TabManagerWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .build()
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let view = adw::TabView::builder().vexpand(true).build();
+        let bar = adw::TabBar::builder()
+            .view(&view)
+            .autohide(settings.autohide)
+            .build();
+        root.append(&bar);
+        root.append(&view);
+
+        let input = sender.input_sender().clone();
+        view.connect_close_page(move |_, page| {
+            input.emit(TabManagerMsg::CloseRequested(page.clone()));
+            // The tab is closed once it confirmed it.
+            Propagation::Stop
+        });
+        let input = sender.input_sender().clone();
+        view.connect_page_detached(move |_, page, _| {
+            input.emit(TabManagerMsg::Detached(page.clone()));
+        });
+        let input = sender.input_sender().clone();
+        view.connect_page_reordered(move |_, _, _| input.emit(TabManagerMsg::Changed));
+        let input = sender.input_sender().clone();
+        view.connect_selected_page_notify(move |_| input.emit(TabManagerMsg::Changed));
+        let input = sender.input_sender().clone();
+        view.connect_setup_menu(move |_, page| {
+            input.emit(TabManagerMsg::MenuPage(page.cloned()));
+        });
+
+        let pin = menu_action::<PinTabAction, C>(&sender, MenuAction::Pin);
+        let unpin = menu_action::<UnpinTabAction, C>(&sender, MenuAction::Unpin);
+        let pin_action = pin.gio_action().clone();
+        let unpin_action = unpin.gio_action().clone();
+
+        let mut group = RelmActionGroup::<TabActionGroup>::new();
+        group.add_action(pin);
+        group.add_action(unpin);
+        group.add_action(menu_action::<CloseTabAction, C>(&sender, MenuAction::Close));
+        group.add_action(menu_action::<CloseOtherTabsAction, C>(
+            &sender,
+            MenuAction::CloseOthers,
+        ));
+        group.register_for_widget(&root);
+
+        view.set_menu_model(Some(&tab_menu(settings.menu.as_ref())));
+
+        let model = Self {
+            tabs: Vec::new(),
+            next_id: 0,
+            selected: None,
+            menu_page: None,
+            session: TabSession::default(),
+        };
+        let widgets = TabManagerWidgets {
+            view,
+            pin_action,
+            unpin_action,
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        let view = &widgets.view;
+        match message {
+            TabManagerMsg::Open(init) => {
+                let id = self.open(init, false, view, &sender);
+                self.select(id, view);
+            }
+            TabManagerMsg::OpenInBackground(init) => {
+                self.open(init, false, view, &sender);
+            }
+            TabManagerMsg::Close(id) => {
+                if let Some(tab) = self.tab(id) {
+                    view.close_page(&tab.page);
+                }
+            }
+            TabManagerMsg::Select(id) => self.select(id, view),
+            TabManagerMsg::Reorder(id, position) => {
+                if let Some(tab) = self.tab(id) {
+                    let position = position.min(view.n_pages().saturating_sub(1) as usize);
+                    view.reorder_page(&tab.page, position as i32);
+                }
+            }
+            TabManagerMsg::SetPinned(id, pinned) => {
+                if let Some(tab) = self.tab(id) {
+                    view.set_page_pinned(&tab.page, pinned);
+                }
+            }
+            TabManagerMsg::Send(id, input) => {
+                if let Some(tab) = self.tab(id) {
+                    tab.controller.emit(input);
+                }
+            }
+            TabManagerMsg::RestoreSession(session) => {
+                let mut selected = None;
+                for (index, entry) in session.tabs.iter().enumerate() {
+                    let Some(init) = C::restore(&entry.key) else {
+                        continue;
+                    };
+                    let id = self.open(init, entry.pinned, view, &sender);
+                    if session.selected == Some(index) {
+                        selected = Some(id);
+                    }
+                }
+                if let Some(id) = selected {
+                    self.select(id, view);
+                }
+            }
+            TabManagerMsg::CloseRequested(page) => {
+                let confirmation = self
+                    .tabs
+                    .iter()
+                    .find(|tab| tab.page == page)
+                    .and_then(|tab| tab.controller.model().confirm_close());
+                match confirmation {
+                    Some(confirmation) => {
+                        let view = view.clone();
+                        relm4::spawn_local(async move {
+                            let confirmed = confirmation.await;
+                            view.close_page_finish(&page, confirmed);
+                        });
+                    }
+                    None => view.close_page_finish(&page, true),
+                }
+            }
+            TabManagerMsg::Detached(page) => {
+                if let Some(index) = self.tabs.iter().position(|tab| tab.page == page) {
+                    let tab = self.tabs.remove(index);
+                    // Ignore send errors because the component might be detached.
+                    sender.output(TabManagerOutput::Closed(tab.id)).ok();
+                }
+            }
+            TabManagerMsg::Changed => {}
+            TabManagerMsg::ChildOutput(id, output) => {
+                if let Some(tab) = self.tab(id) {
+                    update_page(tab);
+                }
+                sender.output(TabManagerOutput::Child(id, output)).ok();
+            }
+            TabManagerMsg::MenuPage(page) => {
+                let pinned = page.as_ref().map(adw::TabPage::is_pinned);
+                widgets.pin_action.set_enabled(pinned == Some(false));
+                widgets.unpin_action.set_enabled(pinned == Some(true));
+
+                let id = page
+                    .as_ref()
+                    .and_then(|page| self.tabs.iter().find(|tab| &tab.page == page))
+                    .map(|tab| tab.id);
+                // Keep the page while the menu is closing, so its actions still work.
+                if page.is_some() {
+                    self.menu_page = page;
+                }
+                sender.output(TabManagerOutput::MenuTarget(id)).ok();
+            }
+            TabManagerMsg::MenuAction(action) => {
+                if let Some(page) = self.menu_page.take() {
+                    match action {
+                        MenuAction::Pin => view.set_page_pinned(&page, true),
+                        MenuAction::Unpin => view.set_page_pinned(&page, false),
+                        MenuAction::Close => view.close_page(&page),
+                        MenuAction::CloseOthers => view.close_other_pages(&page),
+                    }
+                }
+            }
+        }
+
+        self.update_session(view, &sender);
+    }
+}
+
+impl<C> TabManager<C>
+where
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    fn open(
+        &mut self,
+        init: C::Init,
+        pinned: bool,
+        view: &adw::TabView,
+        sender: &ComponentSender<Self>,
+    ) -> TabId {
+        let id = TabId(self.next_id);
+        self.next_id += 1;
+
+        let controller = C::builder()
+            .launch(init)
+            .forward(sender.input_sender(), move |output| {
+                TabManagerMsg::ChildOutput(id, output)
+            });
+        let child = controller.widget().clone().upcast::<gtk::Widget>();
+        let page = if pinned {
+            view.append_pinned(&child)
+        } else {
+            view.append(&child)
+        };
+
+        let tab = Tab {
+            id,
+            page,
+            controller,
+        };
+        update_page(&tab);
+        self.tabs.push(tab);
+        id
+    }
+
+    fn tab(&self, id: TabId) -> Option<&Tab<C>> {
+        self.tabs.iter().find(|tab| tab.id == id)
+    }
+
+    fn select(&self, id: TabId, view: &adw::TabView) {
+        if let Some(tab) = self.tab(id) {
+            view.set_selected_page(&tab.page);
+        }
+    }
+
+    /// Send the selection and the session if they changed.
+    fn update_session(&mut self, view: &adw::TabView, sender: &ComponentSender<Self>) {
+        let selected_page = view.selected_page();
+        let selected = selected_page
+            .as_ref()
+            .and_then(|page| self.tabs.iter().find(|tab| &tab.page == page))
+            .map(|tab| tab.id);
+        if selected != self.selected {
+            self.selected = selected;
+            sender.output(TabManagerOutput::Selected(selected)).ok();
+        }
+
+        let mut session = TabSession::default();
+        for position in 0..view.n_pages() {
+            let page = view.nth_page(position);
+            let Some(tab) = self.tabs.iter().find(|tab| tab.page == page) else {
+                continue;
+            };
+            let Some(key) = tab.controller.model().session_key() else {
+                continue;
+            };
+            if Some(&page) == selected_page.as_ref() {
+                session.selected = Some(session.tabs.len());
+            }
+            session.tabs.push(TabSessionEntry {
+                key,
+                pinned: page.is_pinned(),
+            });
+        }
+        if session != self.session {
+            self.session = session.clone();
+            sender
+                .output(TabManagerOutput::SessionChanged(session))
+                .ok();
+        }
+    }
+}
+
+fn update_page<C>(tab: &Tab<C>)
+where
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    let model = tab.controller.model();
+    tab.page.set_title(&model.title());
+    tab.page.set_loading(model.is_loading());
+}
+
+fn menu_action<A, C>(sender: &ComponentSender<TabManager<C>>, action: MenuAction) -> RelmAction<A>
+where
+    A: ActionName,
+    A::Target: EmptyType,
+    A::State: EmptyType,
+    C: TabPage,
+    C::Root: IsA<gtk::Widget>,
+{
+    let input = sender.input_sender().clone();
+    RelmAction::new_stateless(move |_| input.emit(TabManagerMsg::MenuAction(action)))
+}
+
+fn tab_menu(extra: Option<&gio::MenuModel>) -> gio::Menu {
+    let hidden_when_disabled = |item: gio::MenuItem| {
+        item.set_attribute_value("hidden-when", Some(&"action-disabled".to_variant()));
+        item
+    };
+
+    let menu = gio::Menu::new();
+    let section = gio::Menu::new();
+    section.append_item(&hidden_when_disabled(
+        RelmAction::<PinTabAction>::to_menu_item("Pin Tab"),
+    ));
+    section.append_item(&hidden_when_disabled(
+        RelmAction::<UnpinTabAction>::to_menu_item("Unpin Tab"),
+    ));
+    menu.append_section(None, &section);
+
+    if let Some(extra) = extra {
+        menu.append_section(None, extra);
+    }
+
+    let section = gio::Menu::new();
+    section.append_item(&RelmAction::<CloseOtherTabsAction>::to_menu_item(
+        "Close Other Tabs",
+    ));
+    section.append_item(&RelmAction::<CloseTabAction>::to_menu_item("Close Tab"));
+    menu.append_section(None, &section);
+
+    menu
+}
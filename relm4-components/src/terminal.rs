@@ -0,0 +1,326 @@
+//! Reusable and easily configurable terminal emulator component based on VTE.
+//!
+//! The component wraps a [`vte4::Terminal`], spawns commands in it and reports
+//! the exit of the child process as well as title and working directory changes
+//! that are announced with OSC escape sequences.
+
+use std::path::PathBuf;
+
+use relm4::gtk::prelude::{ObjectExt, WidgetExt};
+use relm4::gtk::{gdk, gio, glib, pango};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+use vte4::{TerminalExt, TerminalExtManual};
+
+/// Colors of a terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalTheme {
+    /// The default text color.
+    pub foreground: gdk::RGBA,
+    /// The background color.
+    pub background: gdk::RGBA,
+    /// The palette of 0, 8, 16, 232 or 256 colors.
+    pub palette: Vec<gdk::RGBA>,
+}
+
+/// Configuration for the terminal component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `command` is set to [`None`], so the shell of the user is spawned.
+/// - `working_directory` is set to [`None`].
+/// - `font` is set to [`None`].
+/// - `scrollback_lines` is set to 10000.
+/// - `light_theme` and `dark_theme` are set to [`None`].
+#[derive(Debug, Clone)]
+pub struct TerminalSettings {
+    /// The command that is spawned on startup.
+    /// If [`None`], the shell of the user is spawned.
+    pub command: Option<Vec<String>>,
+    /// The directory the command is spawned in.
+    pub working_directory: Option<PathBuf>,
+    /// A font description like "Monospace 11".
+    pub font: Option<String>,
+    /// The number of lines that are kept in the scrollback buffer.
+    pub scrollback_lines: i64,
+    /// Colors that are used while the application uses a light style.
+    pub light_theme: Option<TerminalTheme>,
+    /// Colors that are used while the application uses a dark style.
+    pub dark_theme: Option<TerminalTheme>,
+}
+
+impl Default for TerminalSettings {
+    fn default() -> Self {
+        Self {
+            command: None,
+            working_directory: None,
+            font: None,
+            scrollback_lines: 10_000,
+            light_theme: None,
+            dark_theme: None,
+        }
+    }
+}
+
+/// Messages that can be sent to the terminal component.
+#[derive(Debug)]
+pub enum TerminalMsg {
+    /// Spawn a command in the terminal.
+    Spawn {
+        /// The command and its arguments.
+        argv: Vec<String>,
+        /// The working directory. If [`None`], the last known
+        /// working directory of the terminal is used.
+        working_directory: Option<PathBuf>,
+        /// Additional environment variables as `KEY=VALUE`.
+        env: Vec<String>,
+    },
+    /// Send input to the child process, as if it was typed by the user.
+    FeedChild(String),
+    /// Show text in the terminal without sending it to the child process.
+    Feed(String),
+    /// Copy the selection to the clipboard.
+    Copy,
+    /// Paste the clipboard into the terminal.
+    Paste,
+    /// Select all text.
+    SelectAll,
+    /// Reset the terminal and clear the scrollback buffer.
+    Reset,
+    /// Set the font from a description like "Monospace 11".
+    SetFont(String),
+    /// Use `theme` regardless of the application style.
+    SetTheme(TerminalTheme),
+    #[doc(hidden)]
+    Spawned(Result<glib::Pid, glib::Error>),
+    #[doc(hidden)]
+    ChildExited(i32),
+    #[doc(hidden)]
+    TitleChanged,
+    #[doc(hidden)]
+    DirectoryChanged,
+    #[doc(hidden)]
+    StyleChanged,
+}
+
+/// Messages that are sent by the terminal component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalOutput {
+    /// A command was spawned.
+    Spawned(glib::Pid),
+    /// Spawning a command failed.
+    SpawnFailed(String),
+    /// The child process exited with the given wait status.
+    ChildExited(i32),
+    /// The title was changed by the child process.
+    TitleChanged(String),
+    /// The working directory was announced by the child process.
+    WorkingDirectoryChanged(PathBuf),
+}
+
+/// Terminal emulator component.
+#[derive(Debug)]
+pub struct Terminal {
+    settings: TerminalSettings,
+    working_directory: Option<PathBuf>,
+    /// Theme that overrides the light and dark themes.
+    theme: Option<TerminalTheme>,
+    #[cfg(feature = "libadwaita")]
+    style_handler: Option<glib::SignalHandlerId>,
+    settings_handler: Option<(gtk::Settings, glib::SignalHandlerId)>,
+}
+
+impl Component for Terminal {
+    type CommandOutput = ();
+    type Input = TerminalMsg;
+    type Output = TerminalOutput;
+    type Init = TerminalSettings;
+    type Root = vte4::Terminal;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        let terminal = vte4::Terminal::new();
+        terminal.set_hexpand(true);
+        terminal.set_vexpand(true);
+        terminal
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        root.set_scrollback_lines(settings.scrollback_lines);
+        if let Some(font) = &settings.font {
+            root.set_font(Some(&pango::FontDescription::from_string(font)));
+        }
+
+        let input = sender.input_sender().clone();
+        root.connect_child_exited(move |_, status| input.emit(TerminalMsg::ChildExited(status)));
+        let input = sender.input_sender().clone();
+        root.connect_window_title_changed(move |_| input.emit(TerminalMsg::TitleChanged));
+        let input = sender.input_sender().clone();
+        root.connect_current_directory_uri_changed(move |_| {
+            input.emit(TerminalMsg::DirectoryChanged);
+        });
+
+        #[cfg(feature = "libadwaita")]
+        let style_handler = {
+            let input = sender.input_sender().clone();
+            Some(
+                relm4::adw::StyleManager::default().connect_dark_notify(move |_| {
+                    input.send(TerminalMsg::StyleChanged).ok();
+                }),
+            )
+        };
+        let settings_handler = gtk::Settings::default().map(|gtk_settings| {
+            let input = sender.input_sender().clone();
+            let handler = gtk_settings.connect_notify_local(
+                Some("gtk-application-prefer-dark-theme"),
+                move |_, _| {
+                    input.send(TerminalMsg::StyleChanged).ok();
+                },
+            );
+            (gtk_settings, handler)
+        });
+
+        let model = Self {
+            working_directory: settings.working_directory.clone(),
+            theme: None,
+            settings,
+            #[cfg(feature = "libadwaita")]
+            style_handler,
+            settings_handler,
+        };
+        model.apply_theme(&root);
+
+        let argv = model.settings.command.clone().unwrap_or_else(|| {
+            vec![glib::getenv("SHELL")
+                .and_then(|shell| shell.into_string().ok())
+                .unwrap_or_else(|| "/bin/sh".into())]
+        });
+        sender.input(TerminalMsg::Spawn {
+            argv,
+            working_directory: None,
+            env: Vec::new(),
+        });
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match input {
+            TerminalMsg::Spawn {
+                argv,
+                working_directory,
+                env,
+            } => {
+                let working_directory =
+                    working_directory.or_else(|| self.working_directory.clone());
+                let working_directory = working_directory.as_deref().and_then(|path| path.to_str());
+                let argv: Vec<&str> = argv.iter().map(String::as_str).collect();
+                let env: Vec<&str> = env.iter().map(String::as_str).collect();
+
+                let input = sender.input_sender().clone();
+                root.spawn_async(
+                    vte4::PtyFlags::DEFAULT,
+                    working_directory,
+                    &argv,
+                    &env,
+                    glib::SpawnFlags::SEARCH_PATH,
+                    || {},
+                    -1,
+                    None::<&gio::Cancellable>,
+                    move |result| input.emit(TerminalMsg::Spawned(result)),
+                );
+            }
+            TerminalMsg::FeedChild(text) => root.feed_child(text.as_bytes()),
+            TerminalMsg::Feed(text) => root.feed(text.as_bytes()),
+            TerminalMsg::Copy => root.copy_clipboard_format(vte4::Format::Text),
+            TerminalMsg::Paste => root.paste_clipboard(),
+            TerminalMsg::SelectAll => root.select_all(),
+            TerminalMsg::Reset => root.reset(true, true),
+            TerminalMsg::SetFont(font) => {
+                root.set_font(Some(&pango::FontDescription::from_string(&font)));
+            }
+            TerminalMsg::SetTheme(theme) => {
+                self.theme = Some(theme);
+                self.apply_theme(root);
+            }
+            TerminalMsg::Spawned(Ok(pid)) => {
+                sender.output(TerminalOutput::Spawned(pid)).ok();
+            }
+            TerminalMsg::Spawned(Err(error)) => {
+                sender
+                    .output(TerminalOutput::SpawnFailed(error.to_string()))
+                    .ok();
+            }
+            TerminalMsg::ChildExited(status) => {
+                sender.output(TerminalOutput::ChildExited(status)).ok();
+            }
+            TerminalMsg::TitleChanged => {
+                let title = root.window_title().unwrap_or_default();
+                sender
+                    .output(TerminalOutput::TitleChanged(title.into()))
+                    .ok();
+            }
+            TerminalMsg::DirectoryChanged => {
+                let path = root
+                    .current_directory_uri()
+                    .and_then(|uri| glib::filename_from_uri(&uri).ok())
+                    .map(|(path, _)| path);
+                if let Some(path) = path {
+                    self.working_directory = Some(path.clone());
+                    sender
+                        .output(TerminalOutput::WorkingDirectoryChanged(path))
+                        .ok();
+                }
+            }
+            TerminalMsg::StyleChanged => self.apply_theme(root),
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        // The style manager and the settings outlive the component.
+        #[cfg(feature = "libadwaita")]
+        if let Some(handler) = self.style_handler.take() {
+            relm4::adw::StyleManager::default().disconnect(handler);
+        }
+        if let Some((gtk_settings, handler)) = self.settings_handler.take() {
+            gtk_settings.disconnect(handler);
+        }
+    }
+}
+
+impl Terminal {
+    /// The last working directory that was announced by the child process.
+    #[must_use]
+    pub fn working_directory(&self) -> Option<&PathBuf> {
+        self.working_directory.as_ref()
+    }
+
+    fn apply_theme(&self, root: &vte4::Terminal) {
+        let theme = self.theme.as_ref().or(if is_dark() {
+            self.settings.dark_theme.as_ref()
+        } else {
+            self.settings.light_theme.as_ref()
+        });
+
+        if let Some(theme) = theme {
+            let palette: Vec<&gdk::RGBA> = theme.palette.iter().collect();
+            root.set_colors(Some(&theme.foreground), Some(&theme.background), &palette);
+        }
+    }
+}
+
+/// Returns whether the application uses a dark style.
+fn is_dark() -> bool {
+    #[cfg(feature = "libadwaita")]
+    {
+        relm4::adw::StyleManager::default().is_dark()
+    }
+    #[cfg(not(feature = "libadwaita"))]
+    {
+        gtk::Settings::default()
+            .is_some_and(|settings| settings.is_gtk_application_prefer_dark_theme())
+    }
+}
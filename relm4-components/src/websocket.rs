@@ -0,0 +1,271 @@
+//! Reusable worker for WebSocket connections.
+//!
+//! The [`WebSocketWorker`] keeps a single connection alive in the background
+//! and reconnects automatically according to a [`ReconnectPolicy`].
+//!
+//! Secure `wss://` connections need either the `native-tls` or the `rustls` feature.
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use relm4::{ComponentSender, Sender, Worker};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+/// Defines if and how often a lost connection is re-established.
+///
+/// The delay between two attempts starts at `initial_delay` and
+/// is multiplied by `multiplier` after each failed attempt
+/// until it reaches `max_delay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of consecutive attempts or [`None`] to try forever.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first attempt.
+    pub initial_delay: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_delay: Duration,
+    /// Factor by which the delay grows after each failed attempt.
+    pub multiplier: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Never reconnect after the connection was lost or couldn't be established.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the delay before the given attempt (starting at `0`)
+    /// or [`None`] if no more attempts should be made.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| attempt >= max) {
+            return None;
+        }
+
+        let factor = self.multiplier.max(1.0).powf(f64::from(attempt));
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        Some(Duration::from_secs_f64(
+            delay.min(self.max_delay.as_secs_f64()),
+        ))
+    }
+}
+
+/// A message sent or received over a WebSocket connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebSocketMessage {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+impl From<WebSocketMessage> for Message {
+    fn from(message: WebSocketMessage) -> Self {
+        match message {
+            WebSocketMessage::Text(text) => Message::Text(text),
+            WebSocketMessage::Binary(data) => Message::Binary(data),
+        }
+    }
+}
+
+/// Inputs of the [`WebSocketWorker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebSocketInput {
+    /// Connect to the given URL.
+    ///
+    /// An existing connection will be closed first.
+    Connect(String),
+    /// Send a message over the current connection.
+    ///
+    /// Messages that are sent while the worker waits to reconnect
+    /// are queued until the connection is re-established.
+    Send(WebSocketMessage),
+    /// Close the current connection without reconnecting.
+    Close,
+}
+
+/// Outputs of the [`WebSocketWorker`].
+#[derive(Debug)]
+pub enum WebSocketOutput {
+    /// The connection was established.
+    Connected,
+    /// A message was received.
+    Message(WebSocketMessage),
+    /// The connection was closed or lost.
+    Disconnected,
+    /// An error occurred while connecting, sending or receiving.
+    Error(tungstenite::Error),
+}
+
+/// Commands sent from the worker to the connection task.
+#[derive(Debug)]
+enum Command {
+    Send(Message),
+    Close,
+}
+
+/// Worker that manages a WebSocket connection on the runtime.
+///
+/// The worker is initialized with a [`ReconnectPolicy`] and connects
+/// once it receives [`WebSocketInput::Connect`].
+#[derive(Debug)]
+pub struct WebSocketWorker {
+    policy: ReconnectPolicy,
+    connection: Option<mpsc::UnboundedSender<Command>>,
+}
+
+impl Worker for WebSocketWorker {
+    type Init = ReconnectPolicy;
+    type Input = WebSocketInput;
+    type Output = WebSocketOutput;
+
+    fn init(policy: Self::Init, _sender: ComponentSender<Self>) -> Self {
+        Self {
+            policy,
+            connection: None,
+        }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+        match message {
+            WebSocketInput::Connect(url) => {
+                self.close();
+
+                let (tx, rx) = mpsc::unbounded_channel();
+                self.connection = Some(tx);
+
+                let policy = self.policy.clone();
+                let output = sender.output_sender().clone();
+                sender.command(move |_, shutdown| {
+                    shutdown
+                        .register(run_connection(url, policy, rx, output))
+                        .drop_on_shutdown()
+                });
+            }
+            WebSocketInput::Send(message) => {
+                let sent = self
+                    .connection
+                    .as_ref()
+                    .is_some_and(|tx| tx.send(Command::Send(message.into())).is_ok());
+                if !sent {
+                    sender
+                        .output(WebSocketOutput::Error(tungstenite::Error::AlreadyClosed))
+                        .ok();
+                }
+            }
+            WebSocketInput::Close => self.close(),
+        }
+    }
+}
+
+impl WebSocketWorker {
+    fn close(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            connection.send(Command::Close).ok();
+        }
+    }
+}
+
+/// Keeps the connection alive until it's closed by the worker
+/// or the reconnect policy gives up.
+async fn run_connection(
+    url: String,
+    policy: ReconnectPolicy,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    output: Sender<WebSocketOutput>,
+) {
+    let mut attempt = 0;
+    // Messages that were sent while reconnecting.
+    let mut queued = VecDeque::new();
+
+    loop {
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((mut stream, _)) => {
+                attempt = 0;
+                output.emit(WebSocketOutput::Connected);
+
+                while let Some(message) = queued.pop_front() {
+                    if let Err(err) = stream.send(message).await {
+                        output.emit(WebSocketOutput::Error(err));
+                    }
+                }
+
+                loop {
+                    tokio::select! {
+                        command = commands.recv() => match command {
+                            Some(Command::Send(message)) => {
+                                if let Err(err) = stream.send(message).await {
+                                    output.emit(WebSocketOutput::Error(err));
+                                }
+                            }
+                            Some(Command::Close) | None => {
+                                stream.close(None).await.ok();
+                                output.emit(WebSocketOutput::Disconnected);
+                                return;
+                            }
+                        },
+                        message = stream.next() => match message {
+                            Some(Ok(Message::Text(text))) => {
+                                output.emit(WebSocketOutput::Message(WebSocketMessage::Text(text)));
+                            }
+                            Some(Ok(Message::Binary(data))) => {
+                                output.emit(WebSocketOutput::Message(WebSocketMessage::Binary(data)));
+                            }
+                            // Pings are answered by tungstenite.
+                            Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => (),
+                            Some(Ok(Message::Close(_))) | None => break,
+                            Some(Err(err)) => {
+                                output.emit(WebSocketOutput::Error(err));
+                                break;
+                            }
+                        },
+                    }
+                }
+
+                output.emit(WebSocketOutput::Disconnected);
+            }
+            Err(err) => output.emit(WebSocketOutput::Error(err)),
+        }
+
+        let Some(delay) = policy.delay(attempt) else {
+            // Queued messages can't be sent anymore.
+            for _ in queued.drain(..) {
+                output.emit(WebSocketOutput::Error(tungstenite::Error::AlreadyClosed));
+            }
+            return;
+        };
+        attempt += 1;
+
+        // Wait before the next attempt unless the connection is closed meanwhile.
+        // Messages sent during the delay are queued and don't shorten it.
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+        loop {
+            tokio::select! {
+                () = &mut sleep => break,
+                command = commands.recv() => match command {
+                    Some(Command::Close) | None => return,
+                    Some(Command::Send(message)) => queued.push_back(message),
+                },
+            }
+        }
+    }
+}
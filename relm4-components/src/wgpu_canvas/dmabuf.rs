@@ -0,0 +1,366 @@
+//! Sharing the frames of a [`WgpuCanvas`](super::WgpuCanvas) with GTK as dmabuf.
+//!
+//! wgpu can't export its textures, so the Vulkan device is opened with the
+//! external memory extensions enabled and the images are created with ash,
+//! before they are wrapped as wgpu textures.
+
+use std::ffi::CStr;
+use std::fmt::{self, Debug};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use ash::extensions::khr::ExternalMemoryFd;
+use ash::vk;
+use relm4::gtk::gdk;
+use wgpu::hal::api::Vulkan;
+
+use super::TEXTURE_FORMAT;
+
+/// `DRM_FORMAT_ARGB8888`, which matches the memory layout of
+/// [`TEXTURE_FORMAT`] on little endian machines.
+const DRM_FORMAT_ARGB8888: u32 = u32::from_le_bytes(*b"AR24");
+/// `DRM_FORMAT_MOD_LINEAR`.
+const DRM_FORMAT_MOD_LINEAR: u64 = 0;
+/// How many released images are kept for the following frames.
+const POOL_SIZE: usize = 3;
+
+fn required_extensions() -> [&'static CStr; 2] {
+    [
+        vk::KhrExternalMemoryFdFn::name(),
+        vk::ExtExternalMemoryDmaBufFn::name(),
+    ]
+}
+
+/// Opens a device that can export images, if the adapter uses Vulkan
+/// and supports the extensions.
+pub(super) fn request_device(
+    adapter: &wgpu::Adapter,
+    descriptor: &wgpu::DeviceDescriptor<'_>,
+) -> Option<(wgpu::Device, wgpu::Queue, Exporter)> {
+    if !cfg!(target_endian = "little") {
+        return None;
+    }
+
+    // SAFETY: The raw device is created like wgpu does it and handed over to wgpu.
+    let (open_device, exporter) = unsafe {
+        adapter.as_hal::<Vulkan, _, _>(|adapter| {
+            adapter.and_then(|adapter| open_device(adapter, descriptor.required_features))
+        })
+    }?;
+    // SAFETY: The device was opened from this adapter.
+    let (device, queue) =
+        unsafe { adapter.create_device_from_hal(open_device, descriptor, None) }.ok()?;
+    Some((device, queue, exporter))
+}
+
+/// Opens a device like wgpu does, with the extensions for dmabuf enabled.
+unsafe fn open_device(
+    adapter: &wgpu::hal::vulkan::Adapter,
+    features: wgpu::Features,
+) -> Option<(wgpu::hal::OpenDevice<Vulkan>, Exporter)> {
+    let instance = adapter.shared_instance().raw_instance();
+    let physical_device = adapter.raw_physical_device();
+
+    let supported = instance
+        .enumerate_device_extension_properties(physical_device)
+        .ok()?;
+    let is_supported = |name: &CStr| {
+        supported
+            .iter()
+            .any(|extension| CStr::from_ptr(extension.extension_name.as_ptr()) == name)
+    };
+    if !required_extensions().into_iter().all(is_supported) {
+        return None;
+    }
+
+    let mut extensions = adapter.required_device_extensions(features);
+    for name in required_extensions() {
+        if !extensions.contains(&name) {
+            extensions.push(name);
+        }
+    }
+    let mut physical_features = adapter.physical_device_features(&extensions, features);
+
+    // wgpu always uses the first queue family.
+    let family_index = 0;
+    let priorities = [1.0];
+    let queue_infos = [vk::DeviceQueueCreateInfo::builder()
+        .queue_family_index(family_index)
+        .queue_priorities(&priorities)
+        .build()];
+    let extension_names: Vec<_> = extensions.iter().map(|name| name.as_ptr()).collect();
+    let info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(&queue_infos)
+        .enabled_extension_names(&extension_names);
+    let info = physical_features.add_to_device_create_builder(info);
+    let raw_device = instance.create_device(physical_device, &info, None).ok()?;
+
+    let exporter = Exporter {
+        memory_fd: ExternalMemoryFd::new(instance, &raw_device),
+        memory_properties: instance.get_physical_device_memory_properties(physical_device),
+        device: raw_device.clone(),
+        pool: Arc::default(),
+    };
+    let open_device = adapter
+        .device_from_raw(raw_device, true, &extensions, features, family_index, 0)
+        .ok()?;
+    Some((open_device, exporter))
+}
+
+/// Creates images that are shared with GTK.
+pub(super) struct Exporter {
+    device: ash::Device,
+    memory_fd: ExternalMemoryFd,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    /// Images that GTK released and that can be rendered into again.
+    pool: Arc<Mutex<Vec<Image>>>,
+}
+
+impl Debug for Exporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exporter").finish_non_exhaustive()
+    }
+}
+
+impl Exporter {
+    /// Returns a released image of the given size or creates a new one.
+    pub(super) fn image(
+        &self,
+        device: &wgpu::Device,
+        size: (u32, u32),
+    ) -> Result<Image, vk::Result> {
+        let released = {
+            let mut pool = self.pool.lock().unwrap();
+            pool.retain(|image| image.size == size);
+            pool.pop()
+        };
+        match released {
+            Some(image) => Ok(image),
+            // SAFETY: The image is created on the device of `device`.
+            None => unsafe { self.create_image(device, size) },
+        }
+    }
+
+    unsafe fn create_image(
+        &self,
+        device: &wgpu::Device,
+        size: (u32, u32),
+    ) -> Result<Image, vk::Result> {
+        let mut external = vk::ExternalMemoryImageCreateInfo::builder()
+            .handle_types(vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT);
+        // Linear images can be imported without negotiating modifiers.
+        let info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(vk::Format::B8G8R8A8_UNORM)
+            .extent(vk::Extent3D {
+                width: size.0,
+                height: size.1,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::LINEAR)
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .push_next(&mut external);
+        let mut resources = Resources {
+            device: self.device.clone(),
+            image: self.device.create_image(&info, None)?,
+            memory: vk::DeviceMemory::null(),
+        };
+
+        let requirements = self.device.get_image_memory_requirements(resources.image);
+        let memory_type = self
+            .memory_type(requirements.memory_type_bits)
+            .ok_or(vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)?;
+        let mut export = vk::ExportMemoryAllocateInfo::builder()
+            .handle_types(vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT);
+        let mut dedicated = vk::MemoryDedicatedAllocateInfo::builder().image(resources.image);
+        let allocate = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type)
+            .push_next(&mut export)
+            .push_next(&mut dedicated);
+        resources.memory = self.device.allocate_memory(&allocate, None)?;
+        self.device
+            .bind_image_memory(resources.image, resources.memory, 0)?;
+
+        let layout = self.device.get_image_subresource_layout(
+            resources.image,
+            vk::ImageSubresource {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                array_layer: 0,
+            },
+        );
+        let stride =
+            u32::try_from(layout.row_pitch).map_err(|_| vk::Result::ERROR_FORMAT_NOT_SUPPORTED)?;
+        let offset =
+            u32::try_from(layout.offset).map_err(|_| vk::Result::ERROR_FORMAT_NOT_SUPPORTED)?;
+        let fd = self.memory_fd.get_memory_fd(
+            &vk::MemoryGetFdInfoKHR::builder()
+                .memory(resources.memory)
+                .handle_type(vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT),
+        )?;
+        let fd = OwnedFd::from_raw_fd(fd);
+
+        let extent = wgpu::Extent3d {
+            width: size.0,
+            height: size.1,
+            depth_or_array_layers: 1,
+        };
+        let image = resources.image;
+        let hal_texture = wgpu::hal::vulkan::Device::texture_from_raw(
+            image,
+            &wgpu::hal::TextureDescriptor {
+                label: Some("relm4-wgpu-canvas"),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: TEXTURE_FORMAT,
+                usage: wgpu::hal::TextureUses::COLOR_TARGET | wgpu::hal::TextureUses::COPY_SRC,
+                memory_flags: wgpu::hal::MemoryFlags::empty(),
+                view_formats: Vec::new(),
+            },
+            Some(Box::new(resources)),
+        );
+        let texture = device.create_texture_from_hal::<Vulkan>(
+            hal_texture,
+            &wgpu::TextureDescriptor {
+                label: Some("relm4-wgpu-canvas"),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: TEXTURE_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            },
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Ok(Image {
+            _texture: texture,
+            view,
+            fd,
+            size,
+            stride,
+            offset,
+            pool: Arc::downgrade(&self.pool),
+        })
+    }
+
+    /// Prefers device local memory, which can be slower to map but is faster to render into.
+    fn memory_type(&self, type_bits: u32) -> Option<u32> {
+        let count = self.memory_properties.memory_type_count as usize;
+        let types = &self.memory_properties.memory_types[..count];
+        let allowed = |index: &usize| type_bits & (1 << index) != 0;
+        (0..count)
+            .filter(allowed)
+            .find(|&index| {
+                types[index]
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            })
+            .or_else(|| (0..count).find(allowed))
+            .and_then(|index| u32::try_from(index).ok())
+    }
+}
+
+/// The Vulkan objects of an image, which are destroyed with its wgpu texture.
+struct Resources {
+    device: ash::Device,
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+}
+
+impl Drop for Resources {
+    fn drop(&mut self) {
+        // SAFETY: wgpu doesn't use the image anymore.
+        unsafe {
+            self.device.destroy_image(self.image, None);
+            if self.memory != vk::DeviceMemory::null() {
+                self.device.free_memory(self.memory, None);
+            }
+        }
+    }
+}
+
+/// An image whose memory is exported as dmabuf.
+pub(super) struct Image {
+    /// Keeps the image alive while GTK reads it.
+    _texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    fd: OwnedFd,
+    size: (u32, u32),
+    stride: u32,
+    offset: u32,
+    pool: Weak<Mutex<Vec<Image>>>,
+}
+
+impl Image {
+    pub(super) fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Returns the image to the pool of its exporter, if it still exists.
+    fn release(self) {
+        if let Some(pool) = self.pool.upgrade() {
+            let mut pool = pool.lock().unwrap();
+            if pool.len() < POOL_SIZE {
+                pool.push(self);
+            }
+        }
+    }
+}
+
+/// A frame that is rendered into an [`Image`].
+pub(super) struct Frame {
+    image: Option<Image>,
+    done: Arc<AtomicBool>,
+}
+
+impl Frame {
+    /// Tracks the work that was submitted to `queue` last.
+    pub(super) fn new(queue: &wgpu::Queue, image: Image) -> Self {
+        let done = Arc::new(AtomicBool::new(false));
+        {
+            let done = done.clone();
+            queue.on_submitted_work_done(move || done.store(true, Ordering::Release));
+        }
+        Self {
+            image: Some(image),
+            done,
+        }
+    }
+
+    /// Whether the GPU finished rendering the frame.
+    pub(super) fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+
+    /// Wraps the image into a texture, or returns [`None`] if GTK can't import it.
+    ///
+    /// The image is rendered into again after GTK released the texture.
+    pub(super) fn texture(&mut self) -> Option<gdk::Texture> {
+        let image = self.image.take()?;
+        let builder = gdk::DmabufTextureBuilder::new();
+        builder.set_display(&gdk::Display::default()?);
+        builder.set_width(image.size.0);
+        builder.set_height(image.size.1);
+        builder.set_fourcc(DRM_FORMAT_ARGB8888);
+        builder.set_modifier(DRM_FORMAT_MOD_LINEAR);
+        builder.set_premultiplied(true);
+        builder.set_n_planes(1);
+        builder.set_fd(0, image.fd.as_raw_fd());
+        builder.set_stride(0, image.stride);
+        builder.set_offset(0, image.offset);
+
+        // SAFETY: The fd stays open until GTK releases the texture.
+        unsafe { builder.build_with_release_func(move || image.release()) }.ok()
+    }
+}
@@ -0,0 +1,842 @@
+//! Reusable GPU canvas component based on [`wgpu`].
+//!
+//! The component creates a [`wgpu::Device`] in the background and renders
+//! each frame of a [`WgpuRenderer`] into an offscreen texture. The frame is
+//! read back asynchronously and shown as [`gdk::MemoryTexture`] by the
+//! [`gdk::Paintable`] of a [`gtk::Picture`], so the main loop never waits for
+//! the GPU. This works with every GDK backend, because no native surface of
+//! GTK is shared with wgpu.
+//!
+//! With the `wgpu_canvas_dmabuf` feature, the Vulkan backend on Linux renders
+//! into images that are passed to GTK as dmabuf textures instead,
+//! which avoids the copy through system memory. If the driver doesn't
+//! support this, the canvas falls back to reading back the frames.
+//!
+//! Pointer and keyboard events of the canvas are passed to
+//! [`WgpuRenderer::map_input()`], which turns them into messages of the renderer:
+//!
+//! ```no_run
+//! use relm4_components::wgpu_canvas::{WgpuContext, WgpuInput, WgpuRenderer};
+//!
+//! struct Background {
+//!     color: wgpu::Color,
+//! }
+//!
+//! impl WgpuRenderer for Background {
+//!     type Message = (f64, f64);
+//!
+//!     fn map_input(&self, event: &WgpuInput) -> Option<Self::Message> {
+//!         match event {
+//!             WgpuInput::PointerMotion { x, y } => Some((*x, *y)),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn update(&mut self, (x, y): Self::Message) -> bool {
+//!         self.color.r = x / 1000.0;
+//!         self.color.g = y / 1000.0;
+//!         true
+//!     }
+//!
+//!     fn render(
+//!         &mut self,
+//!         _ctx: &WgpuContext,
+//!         encoder: &mut wgpu::CommandEncoder,
+//!         target: &wgpu::TextureView,
+//!         _size: (u32, u32),
+//!     ) {
+//!         encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+//!             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+//!                 view: target,
+//!                 resolve_target: None,
+//!                 ops: wgpu::Operations {
+//!                     load: wgpu::LoadOp::Clear(self.color),
+//!                     store: wgpu::StoreOp::Store,
+//!                 },
+//!             })],
+//!             ..Default::default()
+//!         });
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::{Rc, Weak};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use relm4::gtk::prelude::{
+    Cast, EventControllerExt, GestureSingleExt, PaintableExt, WidgetExt, WidgetExtManual,
+};
+use relm4::gtk::subclass::prelude::ObjectSubclassIsExt;
+use relm4::gtk::{gdk, glib};
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+#[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+mod dmabuf;
+
+/// How often the device is polled while a frame is in flight.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The format of the texture a [`WgpuRenderer`] draws into.
+///
+/// Colors are expected to have premultiplied alpha.
+pub const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Bgra8Unorm;
+
+/// The device of a [`WgpuCanvas`].
+#[derive(Debug)]
+pub struct WgpuContext {
+    /// The device that is used for rendering.
+    pub device: wgpu::Device,
+    /// The queue of the device.
+    pub queue: wgpu::Queue,
+    /// Information about the adapter of the device.
+    pub adapter_info: wgpu::AdapterInfo,
+    /// Shares frames with GTK as dmabuf, if the device supports it.
+    #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+    exporter: Option<dmabuf::Exporter>,
+}
+
+/// Pointer and keyboard events of a [`WgpuCanvas`].
+///
+/// Positions are in logical pixels, relative to the top left corner of the canvas.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WgpuInput {
+    /// The pointer moved over the canvas.
+    PointerMotion {
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// The pointer left the canvas.
+    PointerLeave,
+    /// A mouse button was pressed.
+    ButtonPressed {
+        /// The button, starting with 1 for the primary button.
+        button: u32,
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// A mouse button was released.
+    ButtonReleased {
+        /// The button, starting with 1 for the primary button.
+        button: u32,
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// The canvas was scrolled.
+    Scroll {
+        /// The horizontal delta.
+        dx: f64,
+        /// The vertical delta.
+        dy: f64,
+    },
+    /// A key was pressed while the canvas had the focus.
+    KeyPressed {
+        /// The key.
+        key: gdk::Key,
+        /// The active modifiers.
+        modifiers: gdk::ModifierType,
+    },
+    /// A key was released while the canvas had the focus.
+    KeyReleased {
+        /// The key.
+        key: gdk::Key,
+        /// The active modifiers.
+        modifiers: gdk::ModifierType,
+    },
+}
+
+/// The drawing code of a [`WgpuCanvas`].
+pub trait WgpuRenderer: 'static {
+    /// Messages that change the state of the renderer.
+    type Message: Debug + 'static;
+
+    /// Called once the device was created, before the first frame is rendered.
+    fn init(&mut self, ctx: &WgpuContext) {
+        let _ = ctx;
+    }
+
+    /// Handles a message and returns `true` if the canvas needs to be redrawn.
+    fn update(&mut self, message: Self::Message) -> bool {
+        let _ = message;
+        true
+    }
+
+    /// Turns an input event into a message, that is passed to [`update`](Self::update).
+    ///
+    /// By default, all events are ignored.
+    fn map_input(&self, event: &WgpuInput) -> Option<Self::Message> {
+        let _ = event;
+        None
+    }
+
+    /// Called when the size of the target texture changed.
+    fn resize(&mut self, ctx: &WgpuContext, size: (u32, u32)) {
+        let _ = (ctx, size);
+    }
+
+    /// Records the commands that draw a frame into `target`.
+    ///
+    /// `target` has the format [`TEXTURE_FORMAT`] and `size` is its size in pixels.
+    fn render(
+        &mut self,
+        ctx: &WgpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        size: (u32, u32),
+    );
+}
+
+/// Configuration for the wgpu canvas component.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `backends` is set to [`wgpu::Backends::all()`].
+/// - `power_preference` is set to [`wgpu::PowerPreference::None`].
+/// - `required_features` is set to [`wgpu::Features::empty()`].
+/// - `required_limits` is set to [`wgpu::Limits::downlevel_defaults()`].
+#[derive(Debug, Clone)]
+pub struct WgpuCanvasSettings {
+    /// The backends wgpu may use.
+    pub backends: wgpu::Backends,
+    /// Whether a low power or a high performance adapter is preferred.
+    pub power_preference: wgpu::PowerPreference,
+    /// The features the device must support.
+    pub required_features: wgpu::Features,
+    /// The limits the device must support.
+    pub required_limits: wgpu::Limits,
+}
+
+impl Default for WgpuCanvasSettings {
+    fn default() -> Self {
+        Self {
+            backends: wgpu::Backends::all(),
+            power_preference: wgpu::PowerPreference::None,
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::downlevel_defaults(),
+        }
+    }
+}
+
+/// Messages that can be sent to the wgpu canvas component.
+#[derive(Debug)]
+pub enum WgpuCanvasMsg<M> {
+    /// Pass a message to the renderer and redraw if it returns `true`.
+    Update(M),
+    /// An input event, that is mapped by the renderer.
+    Input(WgpuInput),
+    /// Draw a new frame.
+    QueueRender,
+    /// Draw a frame on each refresh of the display while `true`.
+    SetAnimating(bool),
+}
+
+/// Messages that are sent by the wgpu canvas component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WgpuCanvasOutput {
+    /// The device was created.
+    Ready(wgpu::AdapterInfo),
+    /// No device could be created or a frame couldn't be read back.
+    Error(String),
+}
+
+/// GPU canvas component.
+///
+/// The root is a [`gtk::Picture`]. Its [`paintable`](gtk::Picture::paintable)
+/// always shows the latest frame and can also be used by other widgets.
+pub struct WgpuCanvas<R: WgpuRenderer> {
+    state: Rc<RefCell<State<R>>>,
+    tick: Option<gtk::TickCallbackId>,
+}
+
+struct State<R> {
+    renderer: R,
+    context: Option<WgpuContext>,
+    /// The size the renderer was last resized to.
+    size: Option<(u32, u32)>,
+    target: Option<Target>,
+    paintable: CanvasPaintable,
+    output: relm4::Sender<WgpuCanvasOutput>,
+    /// A frame is being rendered, so new frames have to wait.
+    pending: bool,
+    /// Another frame was requested while one was pending.
+    dirty: bool,
+}
+
+impl<R: WgpuRenderer> Debug for WgpuCanvas<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WgpuCanvas")
+            .field("animating", &self.tick.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: WgpuRenderer> Component for WgpuCanvas<R> {
+    type CommandOutput = Result<WgpuContext, String>;
+    type Input = WgpuCanvasMsg<R::Message>;
+    type Output = WgpuCanvasOutput;
+    type Init = (R, WgpuCanvasSettings);
+    type Root = gtk::Picture;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        let picture = gtk::Picture::new();
+        picture.set_can_shrink(true);
+        picture.set_hexpand(true);
+        picture.set_vexpand(true);
+        picture.set_focusable(true);
+        picture
+    }
+
+    fn init(
+        (renderer, settings): Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        sender.oneshot_command(create_context(settings));
+
+        let paintable = {
+            let input = sender.input_sender().clone();
+            CanvasPaintable::new(move || {
+                input.send(WgpuCanvasMsg::QueueRender).ok();
+            })
+        };
+        paintable.set_scale(root.scale_factor());
+        root.connect_scale_factor_notify({
+            let paintable = paintable.clone();
+            move |picture| paintable.set_scale(picture.scale_factor())
+        });
+        root.set_paintable(Some(&paintable));
+
+        connect_input(&root, sender.input_sender());
+
+        let state = Rc::new(RefCell::new(State {
+            renderer,
+            context: None,
+            size: None,
+            target: None,
+            paintable,
+            output: sender.output_sender().clone(),
+            pending: false,
+            dirty: false,
+        }));
+
+        let model = Self { state, tick: None };
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            WgpuCanvasMsg::Update(message) => {
+                if self.state.borrow_mut().renderer.update(message) {
+                    queue_render(&self.state);
+                }
+            }
+            WgpuCanvasMsg::Input(event) => {
+                let mut state = self.state.borrow_mut();
+                if let Some(message) = state.renderer.map_input(&event) {
+                    if state.renderer.update(message) {
+                        drop(state);
+                        queue_render(&self.state);
+                    }
+                }
+            }
+            WgpuCanvasMsg::QueueRender => queue_render(&self.state),
+            WgpuCanvasMsg::SetAnimating(true) => {
+                if self.tick.is_none() {
+                    let state = Rc::downgrade(&self.state);
+                    self.tick = Some(root.add_tick_callback(move |_, _| {
+                        if let Some(state) = state.upgrade() {
+                            queue_render(&state);
+                            glib::ControlFlow::Continue
+                        } else {
+                            glib::ControlFlow::Break
+                        }
+                    }));
+                }
+            }
+            WgpuCanvasMsg::SetAnimating(false) => {
+                if let Some(tick) = self.tick.take() {
+                    tick.remove();
+                }
+            }
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        context: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match context {
+            Ok(context) => {
+                let mut state = self.state.borrow_mut();
+                state.renderer.init(&context);
+                sender
+                    .output(WgpuCanvasOutput::Ready(context.adapter_info.clone()))
+                    .ok();
+                state.context = Some(context);
+                drop(state);
+                queue_render(&self.state);
+            }
+            Err(error) => {
+                sender.output(WgpuCanvasOutput::Error(error)).ok();
+            }
+        }
+    }
+}
+
+async fn create_context(settings: WgpuCanvasSettings) -> Result<WgpuContext, String> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: settings.backends,
+        ..Default::default()
+    });
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: settings.power_preference,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        })
+        .await
+        .ok_or_else(|| "No suitable GPU adapter found".to_owned())?;
+    let descriptor = wgpu::DeviceDescriptor {
+        label: Some("relm4-wgpu-canvas"),
+        required_features: settings.required_features,
+        required_limits: settings.required_limits,
+    };
+
+    #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+    if let Some((device, queue, exporter)) = dmabuf::request_device(&adapter, &descriptor) {
+        return Ok(WgpuContext {
+            device,
+            queue,
+            adapter_info: adapter.get_info(),
+            exporter: Some(exporter),
+        });
+    }
+
+    let (device, queue) = adapter
+        .request_device(&descriptor, None)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    Ok(WgpuContext {
+        device,
+        queue,
+        adapter_info: adapter.get_info(),
+        #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+        exporter: None,
+    })
+}
+
+/// Renders a new frame, or a single one after the pending frame is done.
+fn queue_render<R: WgpuRenderer>(state: &Rc<RefCell<State<R>>>) {
+    let mut guard = state.borrow_mut();
+    if guard.pending {
+        guard.dirty = true;
+        return;
+    }
+    let mut frame = match guard.render() {
+        Ok(Some(frame)) => frame,
+        Ok(None) => return,
+        Err(error) => {
+            guard.output.send(WgpuCanvasOutput::Error(error)).ok();
+            return;
+        }
+    };
+    guard.pending = true;
+    drop(guard);
+
+    let state = Rc::downgrade(state);
+    glib::timeout_add_local(POLL_INTERVAL, move || poll_frame(&state, &mut frame));
+}
+
+/// Shows the frame once the GPU is done with it.
+fn poll_frame<R: WgpuRenderer>(
+    state: &Weak<RefCell<State<R>>>,
+    frame: &mut Frame,
+) -> glib::ControlFlow {
+    let Some(state) = state.upgrade() else {
+        return glib::ControlFlow::Break;
+    };
+    let mut guard = state.borrow_mut();
+    let Some(ctx) = &guard.context else {
+        return glib::ControlFlow::Break;
+    };
+    ctx.device.poll(wgpu::Maintain::Poll);
+
+    // `Ok(None)` means that the frame is dropped and a new one is rendered.
+    let result = match frame {
+        Frame::Readback(receiver) => match receiver.try_recv() {
+            Ok(Ok(())) => Ok(guard.target.as_ref().map(Target::texture)),
+            Ok(Err(error)) => Err(error.to_string()),
+            Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err("The frame was dropped by the device".to_owned())
+            }
+        },
+        #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+        Frame::Dmabuf(frame) => {
+            if !frame.is_done() {
+                return glib::ControlFlow::Continue;
+            }
+            match frame.texture() {
+                Some(texture) => Ok(Some(texture)),
+                None => {
+                    // GTK can't import the image, so read back
+                    // this and all following frames instead.
+                    if let Some(ctx) = &mut guard.context {
+                        ctx.exporter = None;
+                    }
+                    guard.dirty = true;
+                    Ok(None)
+                }
+            }
+        }
+    };
+
+    match result {
+        Ok(Some(texture)) => guard.paintable.set_texture(texture),
+        Ok(None) => {}
+        Err(error) => {
+            guard.output.send(WgpuCanvasOutput::Error(error)).ok();
+        }
+    }
+    guard.pending = false;
+    if std::mem::take(&mut guard.dirty) {
+        drop(guard);
+        queue_render(&state);
+    }
+    glib::ControlFlow::Break
+}
+
+fn connect_input<M: Debug + 'static>(
+    picture: &gtk::Picture,
+    input: &relm4::Sender<WgpuCanvasMsg<M>>,
+) {
+    // The controllers outlive the component, so send errors are ignored.
+    let motion = gtk::EventControllerMotion::new();
+    {
+        let input = input.clone();
+        motion.connect_motion(move |_, x, y| {
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::PointerMotion { x, y }))
+                .ok();
+        });
+    }
+    {
+        let input = input.clone();
+        motion.connect_leave(move |_| {
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::PointerLeave))
+                .ok();
+        });
+    }
+    picture.add_controller(motion);
+
+    let click = gtk::GestureClick::new();
+    click.set_button(0);
+    {
+        let input = input.clone();
+        click.connect_pressed(move |gesture, _, x, y| {
+            if let Some(widget) = gesture.widget() {
+                widget.grab_focus();
+            }
+            let button = gesture.current_button();
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::ButtonPressed {
+                    button,
+                    x,
+                    y,
+                }))
+                .ok();
+        });
+    }
+    {
+        let input = input.clone();
+        click.connect_released(move |gesture, _, x, y| {
+            let button = gesture.current_button();
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::ButtonReleased {
+                    button,
+                    x,
+                    y,
+                }))
+                .ok();
+        });
+    }
+    picture.add_controller(click);
+
+    let scroll = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::BOTH_AXES);
+    {
+        let input = input.clone();
+        scroll.connect_scroll(move |_, dx, dy| {
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::Scroll { dx, dy }))
+                .ok();
+            glib::Propagation::Stop
+        });
+    }
+    picture.add_controller(scroll);
+
+    let key = gtk::EventControllerKey::new();
+    {
+        let input = input.clone();
+        key.connect_key_pressed(move |_, key, _, modifiers| {
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::KeyPressed {
+                    key,
+                    modifiers,
+                }))
+                .ok();
+            // Keep keyboard navigation working.
+            glib::Propagation::Proceed
+        });
+    }
+    {
+        let input = input.clone();
+        key.connect_key_released(move |_, key, _, modifiers| {
+            input
+                .send(WgpuCanvasMsg::Input(WgpuInput::KeyReleased {
+                    key,
+                    modifiers,
+                }))
+                .ok();
+        });
+    }
+    picture.add_controller(key);
+}
+
+/// A frame that was submitted to the GPU.
+enum Frame {
+    /// The target is copied to its buffer, which is mapped afterwards.
+    Readback(mpsc::Receiver<Result<(), wgpu::BufferAsyncError>>),
+    /// The frame is rendered into an image that is shared with GTK.
+    #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+    Dmabuf(dmabuf::Frame),
+}
+
+impl<R: WgpuRenderer> State<R> {
+    /// Renders a frame, if the device is ready and the size of the canvas is known.
+    fn render(&mut self) -> Result<Option<Frame>, String> {
+        let Self {
+            renderer,
+            context,
+            size,
+            target,
+            paintable,
+            ..
+        } = self;
+        let (Some(ctx), Some(frame_size)) = (context.as_mut(), paintable.pixel_size()) else {
+            return Ok(None);
+        };
+
+        if *size != Some(frame_size) {
+            *size = Some(frame_size);
+            renderer.resize(ctx, frame_size);
+        }
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        #[cfg(all(feature = "wgpu_canvas_dmabuf", target_os = "linux"))]
+        if let Some(exporter) = &ctx.exporter {
+            match exporter.image(&ctx.device, frame_size) {
+                Ok(image) => {
+                    renderer.render(ctx, &mut encoder, image.view(), frame_size);
+                    ctx.queue.submit(Some(encoder.finish()));
+                    return Ok(Some(Frame::Dmabuf(dmabuf::Frame::new(&ctx.queue, image))));
+                }
+                Err(_) => ctx.exporter = None,
+            }
+        }
+
+        if target.as_ref().map(|target| target.size) != Some(frame_size) {
+            *target = Some(Target::new(&ctx.device, frame_size));
+        }
+        let target = target.as_ref().unwrap();
+
+        renderer.render(ctx, &mut encoder, &target.view, frame_size);
+        target.copy_to_buffer(&mut encoder);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let (sender, receiver) = mpsc::channel();
+        target
+            .buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                sender.send(result).ok();
+            });
+        Ok(Some(Frame::Readback(receiver)))
+    }
+}
+
+/// The offscreen texture and the buffer it's copied to.
+struct Target {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    buffer: wgpu::Buffer,
+    size: (u32, u32),
+    /// The bytes per row of the buffer, which must be aligned for copies.
+    padded_row: u32,
+}
+
+impl Target {
+    fn new(device: &wgpu::Device, size: (u32, u32)) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("relm4-wgpu-canvas"),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let padded_row = (size.0 * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("relm4-wgpu-canvas"),
+            size: u64::from(padded_row) * u64::from(size.1),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            texture,
+            view,
+            buffer,
+            size,
+            padded_row,
+        }
+    }
+
+    fn copy_to_buffer(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.copy_texture_to_buffer(
+            self.texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &self.buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(self.padded_row),
+                    rows_per_image: None,
+                },
+            },
+            self.texture.size(),
+        );
+    }
+
+    /// Copies the mapped buffer into a texture and unmaps it.
+    fn texture(&self) -> gdk::Texture {
+        let bytes = glib::Bytes::from(&*self.buffer.slice(..).get_mapped_range());
+        self.buffer.unmap();
+
+        let (width, height) = self.size;
+        gdk::MemoryTexture::new(
+            i32::try_from(width).unwrap_or(i32::MAX),
+            i32::try_from(height).unwrap_or(i32::MAX),
+            gdk::MemoryFormat::B8g8r8a8Premultiplied,
+            &bytes,
+            self.padded_row as usize,
+        )
+        .upcast()
+    }
+}
+
+glib::wrapper! {
+    /// The paintable that shows the latest frame of a [`WgpuCanvas`].
+    struct CanvasPaintable(ObjectSubclass<imp::CanvasPaintable>)
+        @implements gdk::Paintable;
+}
+
+impl CanvasPaintable {
+    /// Creates a paintable that calls `on_resize` when its size in pixels changed.
+    fn new(on_resize: impl Fn() + 'static) -> Self {
+        let paintable: Self = glib::Object::new();
+        paintable.imp().on_resize.replace(Some(Box::new(on_resize)));
+        paintable
+    }
+
+    /// The size in pixels, once the paintable was drawn.
+    fn pixel_size(&self) -> Option<(u32, u32)> {
+        self.imp().size.get()
+    }
+
+    fn set_scale(&self, scale: i32) {
+        if self.imp().scale.replace(scale) != scale {
+            self.invalidate_contents();
+        }
+    }
+
+    fn set_texture(&self, texture: gdk::Texture) {
+        self.imp().texture.replace(Some(texture));
+        self.invalidate_contents();
+    }
+}
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use relm4::gtk::prelude::{Cast, SnapshotExt};
+    use relm4::gtk::subclass::prelude::{ObjectImpl, ObjectSubclass, PaintableImpl};
+    use relm4::gtk::{self, gdk, glib, graphene};
+
+    #[derive(Default)]
+    pub(super) struct CanvasPaintable {
+        /// The latest frame.
+        pub(super) texture: RefCell<Option<gdk::Texture>>,
+        /// The scale factor of the picture that shows the paintable.
+        pub(super) scale: Cell<i32>,
+        /// The size in pixels it was last drawn with.
+        pub(super) size: Cell<Option<(u32, u32)>>,
+        pub(super) on_resize: RefCell<Option<Box<dyn Fn()>>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for CanvasPaintable {
+        const NAME: &'static str = "RelmWgpuCanvasPaintable";
+        type Type = super::CanvasPaintable;
+        type Interfaces = (gdk::Paintable,);
+    }
+
+    impl ObjectImpl for CanvasPaintable {}
+
+    impl PaintableImpl for CanvasPaintable {
+        fn snapshot(&self, snapshot: &gdk::Snapshot, width: f64, height: f64) {
+            let scale = f64::from(self.scale.get().max(1));
+            let size = (
+                ((width * scale).ceil() as u32).max(1),
+                ((height * scale).ceil() as u32).max(1),
+            );
+            if self.size.replace(Some(size)) != Some(size) {
+                if let Some(on_resize) = &*self.on_resize.borrow() {
+                    on_resize();
+                }
+            }
+
+            // Until the next frame is ready, the current one is stretched.
+            if let (Some(texture), Some(snapshot)) = (
+                &*self.texture.borrow(),
+                snapshot.downcast_ref::<gtk::Snapshot>(),
+            ) {
+                let bounds = graphene::Rect::new(0.0, 0.0, width as f32, height as f32);
+                snapshot.append_texture(texture, &bounds);
+            }
+        }
+    }
+}
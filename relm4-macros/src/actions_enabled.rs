@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Path, Result, Token};
+
+#[derive(Debug)]
+pub(super) struct ActionsEnabled {
+    inner: Punctuated<ActionPredicate, Token![,]>,
+}
+
+#[derive(Debug)]
+struct ActionPredicate {
+    action: Path,
+    predicate: Expr,
+}
+
+impl Parse for ActionsEnabled {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        Ok(ActionsEnabled {
+            inner: input.parse_terminated(ActionPredicate::parse, Token![,])?,
+        })
+    }
+}
+
+impl Parse for ActionPredicate {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let action = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let predicate = input.parse()?;
+        Ok(ActionPredicate { action, predicate })
+    }
+}
+
+impl ActionsEnabled {
+    /// Enable or disable the actions in the scope of the root widget.
+    pub(super) fn update_stream(&self, root_name: &TokenStream2) -> TokenStream2 {
+        let actions = self.inner.iter().map(|item| &item.action);
+        let predicates = self.inner.iter().map(|item| &item.predicate);
+
+        quote! {
+            {
+                use relm4::actions::ActionGroupPlus as _;
+                #(
+                    #root_name.set_relm_action_enabled::<#actions>(#predicates);
+                )*
+            }
+        }
+    }
+}
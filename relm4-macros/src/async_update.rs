@@ -0,0 +1,362 @@
+//! Support for `#[update(async)]` in the component macro.
+//!
+//! The update function is split at every `.await`. The code before runs as usual,
+//! the awaited future runs as command and the code after it is moved into an
+//! `UpdateContinuation` that is resumed with the output of the future.
+
+use proc_macro2::{Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{parse_quote, Error, Expr, FnArg, ImplItem, ItemImpl, Pat, Result, Stmt, Token};
+
+const MODEL_NAME: &str = "__relm4_model";
+
+/// Names of the update function parameters that are available after an `.await`.
+struct UpdateParams {
+    sender: Ident,
+    root: Ident,
+}
+
+/// Expand `#[update(async)]` on the update function of a component.
+pub(super) fn expand(component_impl: &mut ItemImpl, errors: &mut Vec<Error>) {
+    let Some(attr_span) = take_update_attr(component_impl, errors) else {
+        return;
+    };
+
+    let is_component = component_impl
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+        .is_some_and(|segment| segment.ident == "Component");
+    if !is_component {
+        errors.push(Error::new(
+            attr_span,
+            "`#[update(async)]` is only supported for `Component`",
+        ));
+        return;
+    }
+
+    for item in &component_impl.items {
+        let conflict = match item {
+            ImplItem::Type(ty) if ty.ident == "CommandOutput" => Some(ty.ident.span()),
+            ImplItem::Fn(func)
+                if func.sig.ident == "update_cmd" || func.sig.ident == "update_cmd_with_view" =>
+            {
+                Some(func.sig.ident.span())
+            }
+            _ => None,
+        };
+        if let Some(span) = conflict {
+            errors.push(Error::new(
+                span,
+                "commands are generated by `#[update(async)]` and can't be used manually",
+            ));
+            return;
+        }
+    }
+
+    let Some(func) = component_impl.items.iter_mut().find_map(|item| match item {
+        ImplItem::Fn(func) if func.sig.ident == "update" => Some(func),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    let result = update_params(func).and_then(|params| {
+        let stmts = std::mem::take(&mut func.block.stmts);
+        transform_stmts(stmts, &params)
+    });
+    match result {
+        Ok(stmts) => func.block.stmts = stmts,
+        Err(err) => {
+            errors.push(err);
+            return;
+        }
+    }
+
+    component_impl.items.push(parse_quote! {
+        type CommandOutput = relm4::component::UpdateContinuation<Self>;
+    });
+    component_impl.items.push(parse_quote! {
+        fn update_cmd(
+            &mut self,
+            continuation: Self::CommandOutput,
+            sender: relm4::ComponentSender<Self>,
+            root: &Self::Root,
+        ) {
+            continuation.resume(self, sender, root);
+        }
+    });
+}
+
+/// Removes `#[update(async)]` from the update function and returns its span.
+fn take_update_attr(component_impl: &mut ItemImpl, errors: &mut Vec<Error>) -> Option<Span> {
+    let mut span = None;
+    for item in &mut component_impl.items {
+        let ImplItem::Fn(func) = item else {
+            continue;
+        };
+
+        let mut kept = Vec::with_capacity(func.attrs.len());
+        for attr in std::mem::take(&mut func.attrs) {
+            if !attr.path().is_ident("update") {
+                kept.push(attr);
+            } else if func.sig.ident != "update" {
+                errors.push(Error::new_spanned(
+                    &attr,
+                    "`#[update(async)]` can only be used on the `update` function",
+                ));
+            } else if let Err(err) = attr.parse_args::<Token![async]>() {
+                errors.push(Error::new(err.span(), "expected `#[update(async)]`"));
+            } else {
+                span = Some(attr.span());
+            }
+        }
+        func.attrs = kept;
+    }
+    span
+}
+
+fn update_params(func: &syn::ImplItemFn) -> Result<UpdateParams> {
+    let ident = |index: usize, name: &str| match func.sig.inputs.iter().nth(index) {
+        Some(FnArg::Typed(arg)) => match &*arg.pat {
+            Pat::Ident(pat) => Ok(pat.ident.clone()),
+            pat => Err(Error::new_spanned(
+                pat,
+                format!("the {name} of an async update function must be bound to a name"),
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            &func.sig,
+            format!("expected the {name} as parameter of the update function"),
+        )),
+    };
+
+    Ok(UpdateParams {
+        sender: ident(2, "sender")?,
+        root: ident(3, "root")?,
+    })
+}
+
+/// Splits the statements of a block at the first `.await`.
+///
+/// An `.await` may also appear inside of the last statement if it's a block,
+/// `if` or `match` expression, because then no code runs after the await
+/// in the original order that would run early after the split.
+fn transform_stmts(mut stmts: Vec<Stmt>, params: &UpdateParams) -> Result<Vec<Stmt>> {
+    let len = stmts.len();
+    for index in 0..len {
+        if awaited(&stmts[index]).is_some() {
+            let rest = transform_stmts(stmts.split_off(index + 1), params)?;
+            let stmt = stmts.pop().expect("statement exists");
+            let (local, base) = awaited(&stmt).expect("statement awaits a future");
+            let continuation = continuation(local, base, rest, params)?;
+            stmts.push(Stmt::Expr(Expr::Verbatim(continuation), None));
+            return Ok(stmts);
+        }
+
+        if find_await(|finder| finder.visit_stmt(&stmts[index])).is_some() {
+            let is_last = index + 1 == len;
+            match &mut stmts[index] {
+                Stmt::Expr(expr, _) if is_last => transform_expr(expr, params)?,
+                stmt => {
+                    return Err(Error::new_spanned(
+                        stmt,
+                        "`.await` in nested expressions is only supported in the last statement of a block",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(stmts)
+}
+
+fn transform_expr(expr: &mut Expr, params: &UpdateParams) -> Result<()> {
+    match expr {
+        Expr::Block(block) => {
+            let stmts = std::mem::take(&mut block.block.stmts);
+            block.block.stmts = transform_stmts(stmts, params)?;
+        }
+        Expr::If(expr_if) => {
+            check_no_await(&expr_if.cond)?;
+            let stmts = std::mem::take(&mut expr_if.then_branch.stmts);
+            expr_if.then_branch.stmts = transform_stmts(stmts, params)?;
+            if let Some((_, else_branch)) = &mut expr_if.else_branch {
+                transform_expr(else_branch, params)?;
+            }
+        }
+        Expr::Match(expr_match) => {
+            check_no_await(&expr_match.expr)?;
+            for arm in &mut expr_match.arms {
+                if let Some((_, guard)) = &arm.guard {
+                    check_no_await(guard)?;
+                }
+                if find_await(|finder| finder.visit_expr(&arm.body)).is_some() {
+                    if !matches!(*arm.body, Expr::Block(_)) {
+                        let body = arm.body.clone();
+                        *arm.body = parse_quote!({ #body });
+                    }
+                    transform_expr(&mut arm.body, params)?;
+                }
+            }
+        }
+        expr => check_no_await(expr)?,
+    }
+    Ok(())
+}
+
+/// Returns the pattern and the future of `let PAT = FUTURE.await;` or `FUTURE.await;`.
+fn awaited(stmt: &Stmt) -> Option<(Option<&syn::Local>, &Expr)> {
+    match stmt {
+        Stmt::Local(local) => match local.init.as_ref().map(|init| &*init.expr) {
+            Some(Expr::Await(expr)) => Some((Some(local), &*expr.base)),
+            _ => None,
+        },
+        Stmt::Expr(Expr::Await(expr), _) => Some((None, &*expr.base)),
+        _ => None,
+    }
+}
+
+fn continuation(
+    local: Option<&syn::Local>,
+    base: &Expr,
+    rest: Vec<Stmt>,
+    params: &UpdateParams,
+) -> Result<TokenStream2> {
+    check_no_await(base)?;
+    if let Some(self_token) = find_self(base.to_token_stream()) {
+        return Err(Error::new(
+            self_token.span(),
+            "`self` can't be used in an awaited expression, \
+            move the required values into local variables before the `.await`",
+        ));
+    }
+
+    let UpdateParams { sender, root } = params;
+    let (await_stmt, bind_stmt) = if let Some(local) = local {
+        let attrs = &local.attrs;
+        let pat = &local.pat;
+        let diverge = local
+            .init
+            .as_ref()
+            .and_then(|init| init.diverge.as_ref())
+            .map(|(else_token, diverge)| quote! { #else_token #diverge });
+        (
+            quote! { let __relm4_value = #base.await; },
+            replace_self(quote! { #(#attrs)* let #pat = __relm4_value #diverge; }),
+        )
+    } else {
+        (quote! { #base.await; }, TokenStream2::new())
+    };
+
+    let rest = replace_self(rest.into_iter().map(ToTokens::into_token_stream).collect());
+    let model = Ident::new(MODEL_NAME, Span::call_site());
+
+    Ok(quote! {
+        {
+            // The root is only used after the `.await`.
+            let _ = #root;
+            let __relm4_command_sender = ::std::clone::Clone::clone(&#sender);
+            __relm4_command_sender.oneshot_command(async move {
+                #await_stmt
+                relm4::component::UpdateContinuation::new(
+                    move |#model: &mut Self,
+                          __relm4_sender: relm4::ComponentSender<Self>,
+                          __relm4_root: &<Self as relm4::Component>::Root| {
+                        #[allow(unused_variables)]
+                        let #sender = __relm4_sender;
+                        #[allow(unused_variables)]
+                        let #root = __relm4_root;
+                        #bind_stmt
+                        #rest
+                    },
+                )
+            });
+        }
+    })
+}
+
+fn check_no_await(expr: &Expr) -> Result<()> {
+    match find_await(|finder| finder.visit_expr(expr)) {
+        Some(span) => Err(Error::new(
+            span,
+            "`.await` is only supported in `let` and expression statements of an async update function",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn find_await(visit: impl FnOnce(&mut AwaitFinder)) -> Option<Span> {
+    let mut finder = AwaitFinder::default();
+    visit(&mut finder);
+    finder.found
+}
+
+/// Finds `.await` expressions that aren't part of an async block or closure.
+#[derive(Default)]
+struct AwaitFinder {
+    found: Option<Span>,
+}
+
+impl<'ast> Visit<'ast> for AwaitFinder {
+    fn visit_expr_await(&mut self, expr: &'ast syn::ExprAwait) {
+        self.found.get_or_insert(expr.await_token.span);
+    }
+
+    fn visit_expr_async(&mut self, _: &'ast syn::ExprAsync) {}
+
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+    fn visit_item(&mut self, _: &'ast syn::Item) {}
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if self.found.is_none() {
+            visit::visit_expr(self, expr);
+        }
+    }
+}
+
+/// Returns the first `self` token that refers to the model.
+fn find_self(tokens: TokenStream2) -> Option<Ident> {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "self" && !is_path_separator(tokens.peek()) => {
+                return Some(ident);
+            }
+            TokenTree::Group(group) => {
+                if let Some(ident) = find_self(group.stream()) {
+                    return Some(ident);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Replaces `self` with the model parameter of the continuation.
+fn replace_self(tokens: TokenStream2) -> TokenStream2 {
+    let mut output = TokenStream2::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let token = match token {
+            TokenTree::Ident(ident) if ident == "self" && !is_path_separator(tokens.peek()) => {
+                TokenTree::Ident(Ident::new(MODEL_NAME, ident.span()))
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), replace_self(group.stream()));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            token => token,
+        };
+        output.extend([token]);
+    }
+    output
+}
+
+fn is_path_separator(token: Option<&TokenTree>) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == ':')
+}
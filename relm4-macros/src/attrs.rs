@@ -1,13 +1,15 @@
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::token::Async;
-use syn::{Error, Result, Token, Visibility};
+use syn::{Error, Ident, Result, Token, Visibility};
 
 pub(super) struct Attrs {
     /// Keeps information about visibility of the widget
     pub(super) visibility: Option<Visibility>,
     /// Whether an async trait is used or not
     pub(super) asyncness: Option<Async>,
+    /// Whether all named widgets are public
+    pub(super) pub_widgets: Option<Ident>,
 }
 
 pub(super) struct SyncOnlyAttrs {
@@ -20,6 +22,7 @@ impl Parse for SyncOnlyAttrs {
         let Attrs {
             visibility,
             asyncness,
+            pub_widgets,
         } = input.parse()?;
 
         if let Some(async_token) = asyncness {
@@ -27,6 +30,11 @@ impl Parse for SyncOnlyAttrs {
                 async_token.span,
                 "this macro doesn't support async traits",
             ))
+        } else if let Some(pub_widgets) = pub_widgets {
+            Err(Error::new(
+                pub_widgets.span(),
+                "this macro doesn't support `pub_widgets`",
+            ))
         } else {
             Ok(Self { visibility })
         }
@@ -38,6 +46,7 @@ impl Parse for Attrs {
         let mut attrs = Attrs {
             visibility: None,
             asyncness: None,
+            pub_widgets: None,
         };
 
         while !input.is_empty() {
@@ -51,6 +60,16 @@ impl Parse for Attrs {
                 } else {
                     attrs.asyncness = Some(new_asyncness);
                 }
+            } else if input.peek(Ident) && input.fork().parse::<Ident>()? == "pub_widgets" {
+                let new_pub_widgets: Ident = input.parse()?;
+                if attrs.pub_widgets.is_some() {
+                    return Err(Error::new(
+                        new_pub_widgets.span(),
+                        "cannot specify `pub_widgets` twice",
+                    ));
+                } else {
+                    attrs.pub_widgets = Some(new_pub_widgets);
+                }
             } else {
                 let new_vis: Visibility = input.parse()?;
                 if attrs.visibility.is_some() {
@@ -67,7 +86,10 @@ impl Parse for Attrs {
                 let comma: Token![,] = input.parse()?;
                 if input.is_empty() {
                     // We've just consumed last token in stream (which is comma) and that's wrong
-                    return Err(Error::new(comma.span, "expected visibility or `async`"));
+                    return Err(Error::new(
+                        comma.span,
+                        "expected visibility, `async` or `pub_widgets`",
+                    ));
                 }
             }
         }
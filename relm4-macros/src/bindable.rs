@@ -0,0 +1,193 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Field, Fields, Ident, Path};
+
+pub(crate) fn generate_tokens(input: DeriveInput) -> TokenStream2 {
+    match try_generate_tokens(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn try_generate_tokens(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        data,
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            generics.span(),
+            "`Bindable` can't be derived for generic types.",
+        ));
+    }
+
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(Error::new(
+                    fields.span(),
+                    "`Bindable` can only be derived for structs with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "`Bindable` can only be derived for structs.",
+            ))
+        }
+    };
+
+    let mut widgets: Option<Path> = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("bindable")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("widgets") {
+                widgets = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `widgets`"))
+            }
+        })?;
+    }
+    // The widgets struct generated by `#[component]` for the model.
+    let widgets = match widgets {
+        Some(path) => quote! { #path },
+        None => {
+            let widgets_ident = format_ident!("{ident}Widgets");
+            quote! { #widgets_ident }
+        }
+    };
+
+    let binding = quote! { relm4::binding };
+    let field_enum = format_ident!("{ident}Field");
+
+    let mut variants = TokenStream2::new();
+    let mut connect_stream = TokenStream2::new();
+    let mut update_stream = TokenStream2::new();
+    let mut sync_stream = TokenStream2::new();
+
+    for field in &fields {
+        let Some(widget) = bound_widget(field)? else {
+            continue;
+        };
+        let field_ident = field
+            .ident
+            .as_ref()
+            .expect("named fields always have an ident");
+        let ty = &field.ty;
+        let variant = format_ident!("{}", to_camel_case(&field_ident.to_string()));
+        let doc = format!("The `{field_ident}` field.");
+
+        variants.extend(quote! {
+            #[doc = #doc]
+            #variant,
+        });
+        connect_stream.extend(quote! {
+            {
+                let sender = ::std::clone::Clone::clone(sender);
+                let f = ::std::clone::Clone::clone(&f);
+                #binding::FormWidget::<#ty>::connect_form_changed(&widgets.#widget, move || {
+                    // Ignore send errors because the component might be shut down.
+                    sender.send(f(#field_enum::#variant)).ok();
+                });
+            }
+        });
+        update_stream.extend(quote! {
+            #field_enum::#variant => {
+                self.#field_ident = #binding::FormWidget::<#ty>::form_value(&widgets.#widget);
+            }
+        });
+        sync_stream.extend(quote! {
+            #binding::FormWidget::<#ty>::sync_form_value(&widgets.#widget, &self.#field_ident);
+        });
+    }
+
+    if variants.is_empty() {
+        return Err(Error::new(
+            ident.span(),
+            "`Bindable` needs at least one field with `#[bind]`.",
+        ));
+    }
+
+    let enum_doc = format!("Identifies a bound field of [`{ident}`].");
+
+    Ok(quote! {
+        #[doc = #enum_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #field_enum {
+            #variants
+        }
+
+        impl #binding::Bindable for #ident {
+            type Widgets = #widgets;
+            type Field = #field_enum;
+
+            fn bind_to_widgets<Msg, F>(
+                &self,
+                widgets: &Self::Widgets,
+                sender: &relm4::Sender<Msg>,
+                f: F,
+            )
+            where
+                F: Fn(Self::Field) -> Msg + 'static,
+                Msg: 'static,
+            {
+                self.sync_widgets(widgets);
+                let f = ::std::rc::Rc::new(f);
+                #connect_stream
+            }
+
+            fn update_field(&mut self, field: Self::Field, widgets: &Self::Widgets) {
+                match field {
+                    #update_stream
+                }
+            }
+
+            fn sync_widgets(&self, widgets: &Self::Widgets) {
+                #sync_stream
+            }
+        }
+    })
+}
+
+/// Returns the name of the widget of a field with `#[bind]` or `#[bind(widget = name)]`.
+fn bound_widget(field: &Field) -> syn::Result<Option<Ident>> {
+    let mut widget = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("bind"))
+    {
+        widget = field.ident.clone();
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("widget") {
+                widget = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `widget`"))
+            }
+        })?;
+    }
+    Ok(widget)
+}
+
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -3,6 +3,7 @@ use quote::{quote, ToTokens};
 use syn::parse_quote;
 use syn::visit_mut::VisitMut;
 
+use crate::async_update;
 use crate::attrs::Attrs;
 use crate::token_streams::{TokenStreams, TraitImplDetails};
 use crate::util;
@@ -15,10 +16,13 @@ pub(crate) fn generate_tokens(
     let Attrs {
         visibility,
         asyncness,
+        pub_widgets,
     } = global_attributes;
 
     let mut errors = vec![];
 
+    async_update::expand(&mut component_impl, &mut errors);
+
     let mut component_visitor = ComponentVisitor::new(&mut errors);
 
     component_visitor.visit_item_impl_mut(&mut component_impl);
@@ -30,6 +34,8 @@ pub(crate) fn generate_tokens(
         .take()
         .map(|menus| menus.menus_stream());
 
+    let actions_enabled = component_visitor.actions_enabled.take();
+
     let mut struct_fields = None;
 
     match &component_visitor.view_widgets {
@@ -42,7 +48,7 @@ pub(crate) fn generate_tokens(
     }
 
     if let ComponentVisitor {
-        view_widgets: Some(Ok(view_widgets)),
+        view_widgets: Some(Ok(mut view_widgets)),
         model_name: Some(model_name),
         root_name: Some(root_name),
         sender_name: Some(sender_name),
@@ -55,8 +61,13 @@ pub(crate) fn generate_tokens(
             model_name,
             sender_name,
             root_name: Some(root_name),
+            pub_widgets: pub_widgets.is_some(),
         };
 
+        if actions_enabled.is_some() {
+            view_widgets.keep_root_field();
+        }
+
         let TokenStreams {
             error,
             init_root,
@@ -73,6 +84,8 @@ pub(crate) fn generate_tokens(
 
         struct_fields = Some(struct_fields_stream);
         let root_widget_type = view_widgets.root_type();
+        let actions_enabled_stream =
+            actions_enabled.map(|actions| actions.update_stream(&view_widgets.root_name()));
 
         // Extract identifiers from additional fields for struct initialization: "test: u8" => "test"
         let additional_fields_return_stream = if let Some(fields) = &additional_fields {
@@ -86,10 +99,16 @@ pub(crate) fn generate_tokens(
             TokenStream2::new()
         };
 
+        #[cfg(feature = "live_reload")]
+        let live_reload = view_widgets.live_reload_stream(&component_impl.self_ty);
+        #[cfg(not(feature = "live_reload"))]
+        let live_reload = TokenStream2::new();
+
         let view_code = quote! {
             #rename_root
             #menus_stream
             #init_widgets
+            #live_reload
             #assign
             {
                 #error
@@ -113,6 +132,23 @@ pub(crate) fn generate_tokens(
         let init_root = util::verbatim_impl_item_fn("init_root", Vec::new(), ty, init_root);
         component_impl.items.push(init_root);
 
+        // Keep `root_widget()` if the user implemented it.
+        let has_root_widget = component_impl
+            .items
+            .iter()
+            .any(|item| matches!(item, syn::ImplItem::Fn(func) if func.sig.ident == "root_widget"));
+        if !has_root_widget {
+            component_impl.items.push(parse_quote! {
+                fn root_widget(root: &Self::Root) -> ::std::option::Option<relm4::gtk::Widget> {
+                    #[allow(unused_imports)]
+                    use relm4::component::root_widget::{
+                        RootDerefsToObject as _, RootIsObject as _, RootIsOther as _,
+                    };
+                    (&&&relm4::component::root_widget::RootWidgetProbe(root)).root_widget()
+                }
+            });
+        }
+
         let PreAndPostView {
             pre_view,
             post_view,
@@ -146,6 +182,7 @@ pub(crate) fn generate_tokens(
 
                     #(#pre_view)*
                     #update_view
+                    #actions_enabled_stream
                     // In post_view returning early is ok
                     (move || { #(#post_view)* })();
 
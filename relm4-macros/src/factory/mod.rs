@@ -15,6 +15,7 @@ pub(crate) fn generate_tokens(
     let Attrs {
         visibility,
         asyncness,
+        pub_widgets,
     } = global_attributes;
 
     let mut errors = vec![];
@@ -71,6 +72,7 @@ pub(crate) fn generate_tokens(
                     root_name.unwrap_or_else(|| Ident::new("root", Span2::call_site())),
                 ),
                 sender_name: Ident::new("sender", Span2::call_site()),
+                pub_widgets: pub_widgets.is_some(),
             },
             false,
         );
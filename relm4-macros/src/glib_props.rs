@@ -0,0 +1,136 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Visibility};
+
+pub(crate) fn generate_tokens(input: DeriveInput) -> TokenStream2 {
+    match try_generate_tokens(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn try_generate_tokens(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            generics.span(),
+            "`GlibProps` can't be derived for generic types.",
+        ));
+    }
+
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(Error::new(
+                    fields.span(),
+                    "`GlibProps` can only be derived for structs with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "`GlibProps` can only be derived for structs.",
+            ))
+        }
+    };
+
+    let gtk_import = crate::gtk_import();
+    let props_ident = format_ident!("{ident}Props");
+    let imp_mod = format_ident!("__{}_props", ident.to_string().to_lowercase());
+    let type_name = format!("Relm4{props_ident}");
+
+    // The inner type lives in a nested module and must be
+    // at least as visible as the wrapper type.
+    let imp_vis = match &vis {
+        Visibility::Inherited => quote! { pub(super) },
+        Visibility::Public(_) => quote! { pub },
+        Visibility::Restricted(_) => quote! { pub(crate) },
+    };
+
+    let mut imp_fields = TokenStream2::new();
+    let mut sync_stream = TokenStream2::new();
+
+    for field in fields {
+        if !field.attrs.iter().any(|attr| attr.path().is_ident("prop")) {
+            continue;
+        }
+
+        let field_ident = field.ident.expect("named fields always have an ident");
+        let ty = field.ty;
+        let prop_name = field_ident.to_string().replace('_', "-");
+
+        imp_fields.extend(quote! {
+            #[property(get, set)]
+            pub(super) #field_ident: ::std::cell::RefCell<#ty>,
+        });
+
+        // Only notify about properties that actually changed.
+        sync_stream.extend(quote! {
+            if *imp.#field_ident.borrow() != self.#field_ident {
+                props.set_property_from_value(
+                    #prop_name,
+                    &#gtk_import::glib::prelude::ToValue::to_value(&self.#field_ident),
+                );
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[allow(missing_docs, unreachable_pub)]
+        #[doc(hidden)]
+        mod #imp_mod {
+            use super::*;
+            use #gtk_import::glib;
+            use glib::prelude::*;
+            use glib::subclass::prelude::*;
+
+            #[derive(Default, glib::Properties)]
+            #[properties(wrapper_type = super::#props_ident)]
+            #imp_vis struct #props_ident {
+                #imp_fields
+            }
+
+            #[glib::object_subclass]
+            impl ObjectSubclass for #props_ident {
+                const NAME: &'static str = #type_name;
+                // Structs with the same name in different modules get unique type names.
+                const ALLOW_NAME_CONFLICT: bool = true;
+                type Type = super::#props_ident;
+            }
+
+            #[glib::derived_properties]
+            impl ObjectImpl for #props_ident {}
+        }
+
+        #gtk_import::glib::wrapper! {
+            #[doc = concat!("GObject exposing the properties of [`", stringify!(#ident), "`].")]
+            #vis struct #props_ident(ObjectSubclass<#imp_mod::#props_ident>);
+        }
+
+        impl relm4::binding::GlibProps for #ident {
+            type Props = #props_ident;
+
+            fn props(&self) -> Self::Props {
+                let props: #props_ident = #gtk_import::glib::Object::new();
+                self.sync_props(&props);
+                props
+            }
+
+            fn sync_props(&self, props: &Self::Props) {
+                use #gtk_import::glib::prelude::ObjectExt;
+                #[allow(unused_variables)]
+                let imp = #gtk_import::glib::subclass::prelude::ObjectSubclassIsExt::imp(props);
+                #sync_stream
+            }
+        }
+    })
+}
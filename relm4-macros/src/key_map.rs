@@ -0,0 +1,154 @@
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Expr, Ident, LitStr, Result, Token};
+
+/// Key bindings like `Escape => Msg::Close, "Ctrl+Return" => Msg::Submit`.
+#[derive(Debug)]
+pub(crate) struct KeyBindings {
+    bindings: Vec<KeyBinding>,
+}
+
+#[derive(Debug)]
+struct KeyBinding {
+    key: Ident,
+    modifiers: Vec<Ident>,
+    msg: Expr,
+}
+
+impl Parse for KeyBindings {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let bindings: Punctuated<KeyBinding, Token![,]> = Punctuated::parse_terminated(input)?;
+        Ok(Self {
+            bindings: bindings.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for KeyBinding {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let (key, modifiers) = if input.peek(LitStr) {
+            let accel: LitStr = input.parse()?;
+            parse_accelerator(&accel.value(), accel.span())?
+        } else {
+            let key: Ident = input.parse()?;
+            (key, Vec::new())
+        };
+
+        let _arrow: Token![=>] = input.parse()?;
+        let msg = input.parse()?;
+
+        Ok(Self {
+            key,
+            modifiers,
+            msg,
+        })
+    }
+}
+
+/// Parse accelerators like `Ctrl+Shift+a` or `<Ctrl><Shift>a`.
+fn parse_accelerator(accel: &str, span: Span2) -> Result<(Ident, Vec<Ident>)> {
+    let mut parts: Vec<&str> = Vec::new();
+
+    let mut rest = accel.trim();
+    while let Some(stripped) = rest.strip_prefix('<') {
+        let Some((modifier, tail)) = stripped.split_once('>') else {
+            return Err(Error::new(span, "Expected `>` after modifier."));
+        };
+        parts.push(modifier);
+        rest = tail;
+    }
+
+    // `+` itself can be used as key, as in `Ctrl++`.
+    let (prefix, key) = if let Some(prefix) = rest.strip_suffix("++") {
+        (prefix, "+")
+    } else if rest == "+" {
+        ("", "+")
+    } else {
+        rest.rsplit_once('+').unwrap_or(("", rest))
+    };
+    if !prefix.is_empty() {
+        parts.extend(prefix.split('+').map(str::trim));
+    }
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(Error::new(span, "Expected a key name after the modifiers."));
+    }
+
+    let mut modifiers = Vec::new();
+    for modifier in parts {
+        let mask = match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" | "primary" => "CONTROL_MASK",
+            "shift" => "SHIFT_MASK",
+            "alt" => "ALT_MASK",
+            "super" => "SUPER_MASK",
+            "meta" => "META_MASK",
+            "hyper" => "HYPER_MASK",
+            _ => {
+                return Err(Error::new(
+                    span,
+                    format!("Unknown modifier `{modifier}`, expected `Ctrl`, `Shift`, `Alt`, `Super`, `Meta` or `Hyper`."),
+                ))
+            }
+        };
+        modifiers.push(Ident::new(mask, span));
+    }
+
+    Ok((key_ident(key, span)?, modifiers))
+}
+
+/// Convert a key name into the name of its `gdk::Key` constant.
+fn key_ident(key: &str, span: Span2) -> Result<Ident> {
+    let key = match key {
+        "+" => "plus",
+        "-" => "minus",
+        "," => "comma",
+        "." => "period",
+        " " => "space",
+        key => key,
+    };
+
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(Error::new(
+            span,
+            format!("Invalid key name `{key}`, expected a name like `Return` or `a`."),
+        ));
+    }
+
+    // Constants of keys starting with a digit are prefixed with `_`.
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        Ok(Ident::new(&format!("_{key}"), span))
+    } else {
+        Ok(Ident::new(key, span))
+    }
+}
+
+impl KeyBindings {
+    /// Generate an expression that creates a `relm4::abstractions::KeyMap`.
+    pub(crate) fn key_map_stream(&self) -> TokenStream2 {
+        let gtk_import = crate::gtk_import();
+
+        let bindings = self.bindings.iter().map(|binding| {
+            let KeyBinding {
+                key,
+                modifiers,
+                msg,
+            } = binding;
+            let modifiers = if modifiers.is_empty() {
+                quote! { #gtk_import::gdk::ModifierType::empty() }
+            } else {
+                quote! { #(#gtk_import::gdk::ModifierType::#modifiers)|* }
+            };
+
+            quote_spanned! { key.span() =>
+                .bind(#gtk_import::gdk::Key::#key, #modifiers, move || #msg)
+            }
+        });
+
+        quote! {
+            relm4::abstractions::KeyMap::new() #(#bindings)*
+        }
+    }
+}
@@ -28,13 +28,21 @@
 )]
 
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, ItemImpl};
+use syn::{parse_macro_input, DeriveInput, ItemImpl};
 
+mod actions_enabled;
 mod additional_fields;
 mod args;
+mod async_update;
 mod attrs;
+mod bindable;
 mod component;
+mod glib_props;
+mod key_map;
 mod menu;
+mod persistent;
+mod state_machine;
+mod variant_value;
 mod view;
 mod visitors;
 mod widgets;
@@ -63,6 +71,53 @@ fn gtk_import() -> syn::Path {
 ///
 /// To create public struct use `#[component(pub)]` or `#[component(visibility = pub)]`.
 ///
+/// # Public widgets
+///
+/// The fields of the widgets struct have the same visibility as the struct.
+/// To make widgets accessible from other modules, use `#[component(pub, pub_widgets)]`
+/// to make all named widgets `pub` or add the `#[public]` attribute to single widgets.
+///
+/// Only widgets with a name given by you (`#[name(...)]`, `name = Widget`
+/// or `#[local]`) can be public. Their fields are part of the API of the
+/// component and don't change unless you rename the widgets.
+/// Unnamed widgets, the stacks of conditional widgets, returned widgets and signal
+/// handlers have generated or internal names that may change between versions,
+/// so they always stay private.
+///
+/// ```ignore
+/// view! {
+///     gtk::Window {
+///         #[public]
+///         #[name(label)]
+///         gtk::Label {},
+///     }
+/// }
+/// ```
+///
+/// # Objects
+///
+/// Objects that aren't widgets, such as size groups, adjustments, menus or
+/// event controllers, can be declared in the view with the `#[object]` attribute.
+/// Instead of being added as child, they are associated with their parent widget
+/// through `relm4::RelmAssociateExt`, for example controllers are added to the
+/// widget and adjustments are set as adjustment of the widget.
+/// Like widgets, objects can have properties and a name to reference them.
+///
+/// ```ignore
+/// view! {
+///     gtk::Scale {
+///         #[object]
+///         #[name(adjustment)]
+///         gtk::Adjustment::new(5.0, 0.0, 10.0, 1.0, 1.0, 0.0) {},
+///
+///         #[object]
+///         gtk::GestureClick {
+///             connect_pressed => move |_, _, _, _| { ... },
+///         },
+///     }
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```
@@ -139,6 +194,52 @@ fn gtk_import() -> syn::Path {
 /// }
 /// ```
 ///
+/// # Enabling actions
+///
+/// The `actions_enabled!` macro inside the component enables or disables
+/// actions after each update, depending on the model. The actions are
+/// looked up in the scope of the root widget, see
+/// `relm4::actions::ActionGroupPlus::relm_action()`.
+///
+/// ```ignore
+/// actions_enabled! {
+///     SaveAction => model.dirty,
+///     UndoAction => !model.history.is_empty(),
+/// }
+/// ```
+///
+/// # Async update functions
+///
+/// Components that only need an occasional `.await` don't have to be converted
+/// to `AsyncComponent`. Annotate the `update` function of a `Component`
+/// with `#[update(async)]` to use `.await` in it.
+///
+/// The function is split at each `.await`: the awaited future runs as command
+/// and the code after it runs once the future has finished. Therefore, the
+/// macro generates `CommandOutput` and `update_cmd`, so they can't be used manually.
+///
+/// Because the future runs in the background, it can't borrow `self`,
+/// so move the required values into local variables first.
+/// `.await` is supported in `let` statements and expression statements.
+/// Inside of blocks, `if` and `match` expressions it's only supported
+/// if they are the last statement, so no other code runs before the
+/// future has finished.
+///
+/// ```ignore
+/// #[update(async)]
+/// fn update(&mut self, msg: Msg, sender: ComponentSender<Self>, root: &Self::Root) {
+///     match msg {
+///         Msg::Load(url) => {
+///             self.loading = true;
+///             let client = self.client.clone();
+///             let response = client.get(url).await;
+///             self.loading = false;
+///             self.response = Some(response);
+///         }
+///     }
+/// }
+/// ```
+///
 /// # Notes on `pre_view`
 ///
 /// Using `return` in `pre_view` will cause a compiler warning.
@@ -430,6 +531,43 @@ pub fn menu(input: TokenStream) -> TokenStream {
     menus.menus_stream().into()
 }
 
+/// Create a `relm4::abstractions::KeyMap` that maps keys to messages.
+///
+/// Keys are either names of [`gdk::Key`] constants or accelerator strings
+/// like `"Ctrl+Shift+a"` or `"<Ctrl>Return"`. The syntax of accelerators
+/// and the key names are checked at compile time.
+///
+/// The same syntax is used by the `#[keys(...)]` attribute of widgets in the [`view!`] macro.
+///
+/// # Example
+///
+/// ```
+/// # use relm4::gtk;
+/// # use gtk::prelude::WidgetExt;
+/// #[derive(Debug)]
+/// enum Msg {
+///     Close,
+///     Submit,
+/// }
+///
+/// # gtk::init().unwrap();
+/// # let (sender, _receiver) = relm4::channel::<Msg>();
+/// let key_map = relm4::keymap! {
+///     Escape => Msg::Close,
+///     "Ctrl+Return" => Msg::Submit,
+/// };
+///
+/// let entry = gtk::Entry::default();
+/// entry.add_controller(key_map.into_controller(&sender));
+/// ```
+///
+/// [`gdk::Key`]: https://gtk-rs.org/gtk4-rs/git/docs/gdk4/struct.Key.html
+#[proc_macro]
+pub fn keymap(input: TokenStream) -> TokenStream {
+    let bindings = parse_macro_input!(input as key_map::KeyBindings);
+    bindings.key_map_stream().into()
+}
+
 /// The [`view!`] macro allows you to construct your UI easily and cleanly.
 ///
 /// It does the same as inside the [`macro@component`] attribute macro,
@@ -636,6 +774,219 @@ pub fn widget_template(attributes: TokenStream, input: TokenStream) -> TokenStre
     widget_template::generate_tokens(visibility, item_impl).into()
 }
 
+/// Derive macro that generates a companion GObject for a model.
+///
+/// All fields marked with `#[prop]` are exposed as properties of a
+/// new type called `<Name>Props`. The property names are the field
+/// names in kebab-case, for example `user_name` becomes `user-name`.
+/// This allows binding the model to widgets or libraries that only
+/// understand property bindings or [`gtk::Expression`]s.
+///
+/// The macro implements `relm4::binding::GlibProps`. Call `sync_props()`
+/// after each update, for example in `post_view()`, to keep the properties
+/// in sync with the model. Only properties whose value changed are notified.
+///
+/// # Example
+///
+/// ```
+/// use relm4::binding::GlibProps;
+///
+/// #[derive(relm4::GlibProps)]
+/// struct App {
+///     #[prop]
+///     counter: u8,
+///     #[prop]
+///     user_name: String,
+///     // Not exposed
+///     cache: Vec<u8>,
+/// }
+///
+/// let mut app = App { counter: 0, user_name: String::new(), cache: Vec::new() };
+/// let props = app.props();
+///
+/// app.counter += 1;
+/// app.sync_props(&props);
+/// assert_eq!(props.counter(), 1);
+/// ```
+///
+/// [`gtk::Expression`]: https://gtk-rs.org/gtk4-rs/stable/latest/docs/gtk4/struct.Expression.html
+#[proc_macro_derive(GlibProps, attributes(prop))]
+pub fn glib_props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    glib_props::generate_tokens(input).into()
+}
+
+/// Derive `relm4::binding::Bindable` to connect fields of a model with form widgets.
+///
+/// Each field with `#[bind]` is shown in the widget of the same name,
+/// which is usually given with `#[name]` in `view!`. Use `#[bind(widget = name)]`
+/// for a widget with another name. Entries edit [`String`] fields, switches and
+/// check buttons [`bool`] fields and spin buttons [`f64`] or [`i32`] fields,
+/// see the implementers of `relm4::binding::FormWidget`.
+///
+/// The widgets are looked up in the `<Model>Widgets` struct generated by `#[component]`,
+/// `#[bindable(widgets = Path)]` uses another struct.
+/// The macro also generates a `<Model>Field` enum with a variant per bound field,
+/// which is sent whenever the user edits a field.
+///
+/// # Example
+///
+/// ```
+/// use relm4::binding::Bindable;
+/// use relm4::gtk::prelude::OrientableExt;
+/// use relm4::{gtk, Component, ComponentParts, ComponentSender};
+///
+/// #[derive(Default, relm4::Bindable)]
+/// struct Profile {
+///     #[bind]
+///     name: String,
+///     #[bind]
+///     newsletter: bool,
+///     #[bind(widget = age_button)]
+///     age: i32,
+/// }
+///
+/// #[derive(Debug)]
+/// enum Msg {
+///     FieldChanged(ProfileField),
+///     Reset,
+/// }
+///
+/// #[relm4::component]
+/// impl Component for Profile {
+///     type Init = ();
+///     type Input = Msg;
+///     type Output = ();
+///     type CommandOutput = ();
+///
+///     view! {
+///         gtk::Box {
+///             set_orientation: gtk::Orientation::Vertical,
+///
+///             #[name = "name"]
+///             gtk::Entry {},
+///             #[name = "newsletter"]
+///             gtk::Switch {},
+///             #[name = "age_button"]
+///             gtk::SpinButton::with_range(0.0, 150.0, 1.0) {},
+///         }
+///     }
+///
+///     fn init(_: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+///         let model = Self::default();
+///         let widgets = view_output!();
+///         model.bind_to_widgets(&widgets, sender.input_sender(), Msg::FieldChanged);
+///         ComponentParts { model, widgets }
+///     }
+///
+///     fn update_with_view(
+///         &mut self,
+///         widgets: &mut Self::Widgets,
+///         msg: Msg,
+///         _sender: ComponentSender<Self>,
+///         _root: &Self::Root,
+///     ) {
+///         match msg {
+///             Msg::FieldChanged(field) => self.update_field(field, widgets),
+///             Msg::Reset => {
+///                 *self = Self::default();
+///                 self.sync_widgets(widgets);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Bindable, attributes(bindable, bind))]
+pub fn bindable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    bindable::generate_tokens(input).into()
+}
+
+/// Derive [`StaticVariantType`], [`ToVariant`] and [`FromVariant`]
+/// so a type can be used as target or state of actions.
+///
+/// Structs are stored as tuple of their fields. Enums that only
+/// have unit variants are stored as string of the variant name.
+/// Other enums are stored as `(sv)`, the variant name and a
+/// boxed tuple of the fields.
+///
+/// All fields must implement the traits as well, which includes
+/// basic types, [`String`], [`Option`], [`Vec`] and other types
+/// with this derive.
+///
+/// # Example
+///
+/// ```
+/// use relm4::gtk::glib::variant::{FromVariant, StaticVariantType, ToVariant};
+///
+/// #[derive(Debug, PartialEq, relm4::VariantValue)]
+/// enum Zoom {
+///     Fit,
+///     Level(f64),
+///     Region { x: i32, y: i32, label: Option<String> },
+/// }
+///
+/// #[derive(Debug, PartialEq, relm4::VariantValue)]
+/// struct View {
+///     page: u32,
+///     zoom: Zoom,
+/// }
+///
+/// let view = View { page: 3, zoom: Zoom::Level(1.5) };
+/// let variant = view.to_variant();
+/// assert_eq!(variant.type_(), View::static_variant_type());
+/// assert_eq!(View::from_variant(&variant), Some(view));
+/// ```
+///
+/// [`StaticVariantType`]: https://gtk-rs.org/gtk-rs-core/stable/latest/docs/glib/variant/trait.StaticVariantType.html
+/// [`ToVariant`]: https://gtk-rs.org/gtk-rs-core/stable/latest/docs/glib/variant/trait.ToVariant.html
+/// [`FromVariant`]: https://gtk-rs.org/gtk-rs-core/stable/latest/docs/glib/variant/trait.FromVariant.html
+#[proc_macro_derive(VariantValue)]
+pub fn variant_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    variant_value::generate_tokens(input).into()
+}
+
+/// Derive `relm4::fsm::StateMachine` for an enum of states.
+///
+/// The allowed transitions of each state are listed in the
+/// `#[transitions(...)]` attribute of the variant, `#[transitions(*)]`
+/// allows all transitions. States without the attribute are final.
+///
+/// For each state, a `to_<state>()` method that performs the transition
+/// and an `is_<state>()` method are generated.
+/// The arguments of `to_<state>()` are the fields of the state.
+///
+/// See the `relm4::fsm` module for an example.
+#[proc_macro_derive(StateMachine, attributes(transitions))]
+pub fn state_machine(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    state_machine::generate_tokens(input).into()
+}
+
+/// Derive `relm4::persist::Persistent` for a struct with named fields,
+/// so it can be saved and restored by newer versions of the application.
+///
+/// Fields added after the first version need `#[since(version)]`.
+/// When older data is restored, they are set to their [`Default`] value or
+/// the result of `#[since(version, default = function)]`.
+/// Fields with `#[persistent(skip)]` are not stored at all.
+///
+/// The version of the data is the highest `since` version, unless
+/// `#[persistent(version = N)]` is set on the struct.
+/// `#[persistent(migrate = function)]` calls
+/// `fn(version: u32, data: &mut serde_json::Value) -> Result<(), PersistError>`
+/// with older data before the fields are restored, for example to rename fields.
+/// The data is always a JSON object, other data is rejected before it's migrated.
+/// Migrations should report unexpected data as `PersistError::Migration` instead of panicking.
+///
+/// See the `relm4::persist` module for an example.
+#[proc_macro_derive(Persistent, attributes(persistent, since))]
+pub fn persistent(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    persistent::generate_tokens(input).into()
+}
+
 #[cfg(test)]
 #[rustversion::all(stable, since(1.72))]
 mod test {
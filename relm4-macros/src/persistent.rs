@@ -0,0 +1,201 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Field, Fields, Ident, LitInt, Path, Token};
+
+pub(crate) fn generate_tokens(input: DeriveInput) -> TokenStream2 {
+    match try_generate_tokens(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// How a field is restored.
+enum FieldKind {
+    /// The field exists since the first version.
+    Required,
+    /// `#[since(version)]` or `#[since(version, default = path)]`
+    Since { version: u32, default: Option<Path> },
+    /// `#[persistent(skip)]`
+    Skip,
+}
+
+fn try_generate_tokens(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            generics.span(),
+            "`Persistent` can't be derived for generic types.",
+        ));
+    }
+
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(Error::new(
+                    fields.span(),
+                    "`Persistent` can only be derived for structs with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "`Persistent` can only be derived for structs.",
+            ))
+        }
+    };
+
+    let mut version = None;
+    let mut migrate: Option<Path> = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("persistent"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("version") {
+                version = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u32>()?);
+                Ok(())
+            } else if meta.path.is_ident("migrate") {
+                migrate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `version` or `migrate`"))
+            }
+        })?;
+    }
+
+    let persist = quote! { relm4::persist };
+    let private = quote! { #persist::__private };
+
+    let mut latest = 1;
+    let mut serialize_stream = TokenStream2::new();
+    let mut deserialize_stream = TokenStream2::new();
+
+    for field in &fields {
+        let kind = field_kind(field)?;
+        let field_ident = field
+            .ident
+            .as_ref()
+            .expect("named fields always have an ident");
+        let name = field_ident.to_string();
+
+        let value = match kind {
+            FieldKind::Required => quote! { #private::required(&mut object, #name)? },
+            FieldKind::Since {
+                version: since,
+                default,
+            } => {
+                latest = latest.max(since);
+                let default = match default {
+                    Some(path) => quote! { #path },
+                    None => quote! { ::std::default::Default::default },
+                };
+                quote! { #private::since(&mut object, #name, version, #since, #default)? }
+            }
+            FieldKind::Skip => {
+                deserialize_stream.extend(quote! {
+                    #field_ident: ::std::default::Default::default(),
+                });
+                continue;
+            }
+        };
+
+        serialize_stream.extend(quote! {
+            #private::serialize(&mut object, #name, &self.#field_ident)?;
+        });
+        deserialize_stream.extend(quote! {
+            #field_ident: #value,
+        });
+    }
+
+    let version = match version {
+        Some(version) if version < latest => {
+            return Err(Error::new(
+                ident.span(),
+                format!("`version` must be at least {latest}, the highest `#[since]` version."),
+            ))
+        }
+        Some(version) => version,
+        None => latest,
+    };
+
+    // Only objects are passed to the migration, so indexing them can't panic.
+    let migrate_stream = migrate.map(|migrate| {
+        quote! {
+            if version < #version {
+                value = #persist::serde_json::Value::Object(#private::into_object(value)?);
+                #migrate(version, &mut value)?;
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #persist::Persistent for #ident {
+            const VERSION: u32 = #version;
+
+            fn to_value(&self) -> ::std::result::Result<#persist::serde_json::Value, #persist::PersistError> {
+                #[allow(unused_mut)]
+                let mut object = #persist::serde_json::Map::new();
+                #serialize_stream
+                Ok(#persist::serde_json::Value::Object(object))
+            }
+
+            fn from_value(
+                version: u32,
+                #[allow(unused_mut)]
+                mut value: #persist::serde_json::Value,
+            ) -> ::std::result::Result<Self, #persist::PersistError> {
+                #migrate_stream
+                #[allow(unused_mut, unused_variables)]
+                let mut object = #private::into_object(value)?;
+                Ok(Self {
+                    #deserialize_stream
+                })
+            }
+        }
+    })
+}
+
+fn field_kind(field: &Field) -> syn::Result<FieldKind> {
+    let mut kind = FieldKind::Required;
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("since") {
+            // `#[since(2)]` or `#[since(2, default = path)]`
+            let (version, default) = attr.parse_args_with(|input: ParseStream<'_>| {
+                let version = input.parse::<LitInt>()?.base10_parse::<u32>()?;
+                let mut default = None;
+                if input.parse::<Option<Token![,]>>()?.is_some() {
+                    let key: Ident = input.parse()?;
+                    if key != "default" {
+                        return Err(Error::new(key.span(), "expected `default`"));
+                    }
+                    input.parse::<Token![=]>()?;
+                    default = Some(input.parse()?);
+                }
+                Ok((version, default))
+            })?;
+            kind = FieldKind::Since { version, default };
+        } else if attr.path().is_ident("persistent") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    kind = FieldKind::Skip;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip`"))
+                }
+            })?;
+        }
+    }
+
+    Ok(kind)
+}
@@ -0,0 +1,206 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Token, Variant};
+
+pub(crate) fn generate_tokens(input: DeriveInput) -> TokenStream2 {
+    match try_generate_tokens(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// The targets of the `#[transitions(...)]` attribute.
+enum Targets {
+    /// `#[transitions(*)]`
+    Any,
+    List(Vec<Ident>),
+}
+
+fn try_generate_tokens(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            generics.span(),
+            "`StateMachine` can't be derived for generic types.",
+        ));
+    }
+
+    let Data::Enum(data) = data else {
+        return Err(Error::new(
+            ident.span(),
+            "`StateMachine` can only be derived for enums.",
+        ));
+    };
+
+    let variants: Vec<&Variant> = data.variants.iter().collect();
+    let names: Vec<&Ident> = variants.iter().map(|variant| &variant.ident).collect();
+
+    let mut transition_arms = TokenStream2::new();
+    for variant in &variants {
+        let from = &variant.ident;
+        let targets = transitions(variant)?;
+
+        let patterns = match targets {
+            Targets::Any => quote! { _ },
+            Targets::List(targets) => {
+                for target in &targets {
+                    if !names.contains(&target) {
+                        return Err(Error::new(
+                            target.span(),
+                            format!("`{ident}` has no variant `{target}`."),
+                        ));
+                    }
+                }
+                if targets.is_empty() {
+                    continue;
+                }
+                quote! { #(Self::#targets { .. })|* }
+            }
+        };
+
+        transition_arms.extend(quote! {
+            (Self::#from { .. }, #patterns) => true,
+        });
+    }
+
+    let name_strings = names.iter().map(ToString::to_string);
+
+    let mut methods = TokenStream2::new();
+    for variant in &variants {
+        let name = &variant.ident;
+        let snake_name = to_snake_case(&name.to_string());
+        let to_fn = format_ident!("to_{snake_name}");
+        let is_fn = format_ident!("is_{snake_name}");
+
+        let (args, construct) = match &variant.fields {
+            Fields::Named(fields) => {
+                let idents: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+                let types = fields.named.iter().map(|f| &f.ty);
+                (
+                    quote! { #(#idents: #types),* },
+                    quote! { Self::#name { #(#idents),* } },
+                )
+            }
+            Fields::Unnamed(fields) => {
+                let idents: Vec<_> = (0..fields.unnamed.len())
+                    .map(|index| format_ident!("field_{index}"))
+                    .collect();
+                let types = fields.unnamed.iter().map(|f| &f.ty);
+                (
+                    quote! { #(#idents: #types),* },
+                    quote! { Self::#name ( #(#idents),* ) },
+                )
+            }
+            Fields::Unit => (TokenStream2::new(), quote! { Self::#name }),
+        };
+
+        let to_doc = format!("Transition to [`Self::{name}`] if allowed.");
+        let is_doc = format!("Returns [`true`] if the current state is [`Self::{name}`].");
+        methods.extend(quote! {
+            #[doc = #to_doc]
+            #vis fn #to_fn(&mut self, #args) -> ::std::result::Result<(), relm4::fsm::InvalidTransition> {
+                relm4::fsm::StateMachine::transition(self, #construct)
+            }
+
+            #[doc = #is_doc]
+            #[must_use]
+            #vis fn #is_fn(&self) -> bool {
+                matches!(self, Self::#name { .. })
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl relm4::fsm::StateMachine for #ident {
+            fn state_name(&self) -> &'static str {
+                match self {
+                    #(Self::#names { .. } => #name_strings,)*
+                }
+            }
+
+            fn can_transition_to(&self, next: &Self) -> bool {
+                #[allow(unreachable_patterns)]
+                match (self, next) {
+                    #transition_arms
+                    _ => false,
+                }
+            }
+        }
+
+        impl #ident {
+            #methods
+        }
+    })
+}
+
+/// Parse the `#[transitions(...)]` attribute of a variant.
+fn transitions(variant: &Variant) -> syn::Result<Targets> {
+    let mut targets = None;
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("transitions") {
+            continue;
+        }
+        if targets.is_some() {
+            return Err(Error::new(
+                attr.span(),
+                "Duplicate `transitions` attribute.",
+            ));
+        }
+
+        targets = Some(attr.parse_args_with(|input: syn::parse::ParseStream<'_>| {
+            if input.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                Ok(Targets::Any)
+            } else {
+                let list = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+                Ok(Targets::List(list.into_iter().collect()))
+            }
+        })?);
+    }
+
+    // States without the attribute are final.
+    Ok(targets.unwrap_or(Targets::List(Vec::new())))
+}
+
+/// Convert a variant name to snake case, keeping acronyms together (`HTTPServer` => `http_server`).
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (index, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && index != 0 {
+            let prev = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("Idle"), "idle");
+        assert_eq!(to_snake_case("LoadingData"), "loading_data");
+        assert_eq!(to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(to_snake_case("IOError"), "io_error");
+        assert_eq!(to_snake_case("ParseJSON"), "parse_json");
+        assert_eq!(to_snake_case("Step2Done"), "step2_done");
+    }
+}
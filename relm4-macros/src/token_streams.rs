@@ -35,6 +35,8 @@ pub(super) struct TraitImplDetails {
     pub(super) root_name: Option<Ident>,
     /// The name of the sender used in the init function.
     pub(super) sender_name: Ident,
+    /// Whether all named widgets are public.
+    pub(super) pub_widgets: bool,
 }
 
 impl ViewWidgets {
@@ -108,6 +110,7 @@ impl Widget {
             model_name,
             root_name,
             sender_name,
+            pub_widgets,
         } = trait_impl_details;
 
         let name = &self.name;
@@ -129,7 +132,7 @@ impl Widget {
         self.error_stream(&mut streams.error);
         self.start_assign_stream(&mut streams.assign, sender_name);
         self.init_conditional_init_stream(&mut streams.assign, model_name);
-        self.struct_fields_stream(&mut streams.struct_fields, vis);
+        self.struct_fields_stream(&mut streams.struct_fields, vis, *pub_widgets);
         self.return_stream(&mut streams.return_fields);
         self.destructure_stream(&mut streams.destructure_fields);
         self.init_update_view_stream(&mut streams.update_view, model_name);
@@ -0,0 +1,251 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Type};
+
+pub(crate) fn generate_tokens(input: DeriveInput) -> TokenStream2 {
+    match try_generate_tokens(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn try_generate_tokens(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            generics.span(),
+            "`VariantValue` can't be derived for generic types.",
+        ));
+    }
+
+    let gtk_import = crate::gtk_import();
+    let glib = quote! { #gtk_import::glib };
+
+    let (variant_type, to_variant, from_variant) = match data {
+        Data::Struct(data) => struct_tokens(&glib, &data.fields),
+        Data::Enum(data) => {
+            let variants: Vec<(Ident, Fields)> = data
+                .variants
+                .into_iter()
+                .map(|variant| (variant.ident, variant.fields))
+                .collect();
+            if variants.is_empty() {
+                return Err(Error::new(
+                    ident.span(),
+                    "`VariantValue` can't be derived for enums without variants.",
+                ));
+            }
+            enum_tokens(&glib, &variants)
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                ident.span(),
+                "`VariantValue` can only be derived for structs and enums.",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #glib::variant::StaticVariantType for #ident {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #glib::VariantTy> {
+                #variant_type
+            }
+        }
+
+        impl #glib::variant::ToVariant for #ident {
+            fn to_variant(&self) -> #glib::Variant {
+                #to_variant
+            }
+        }
+
+        impl #glib::variant::FromVariant for #ident {
+            fn from_variant(variant: &#glib::Variant) -> ::std::option::Option<Self> {
+                if !variant.is_type(&<Self as #glib::variant::StaticVariantType>::static_variant_type()) {
+                    return ::std::option::Option::None;
+                }
+                #from_variant
+            }
+        }
+
+        impl ::std::convert::From<#ident> for #glib::Variant {
+            fn from(value: #ident) -> Self {
+                #glib::variant::ToVariant::to_variant(&value)
+            }
+        }
+    })
+}
+
+/// Returns the types and the names of the bindings of the fields.
+fn field_bindings(fields: &Fields) -> (Vec<&Type>, Vec<Ident>) {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| (&field.ty, format_ident!("field_{index}")))
+        .unzip()
+}
+
+/// A tuple type made of the variant types of all fields.
+fn tuple_type(glib: &TokenStream2, types: &[&Type]) -> TokenStream2 {
+    let len = types.len();
+    quote! {{
+        let types: [::std::borrow::Cow<'static, #glib::VariantTy>; #len] = [
+            #(<#types as #glib::variant::StaticVariantType>::static_variant_type(),)*
+        ];
+        #glib::VariantType::new_tuple(types)
+    }}
+}
+
+/// A tuple variant of the values of all bindings.
+fn tuple_value(glib: &TokenStream2, bindings: &[Ident]) -> TokenStream2 {
+    let len = bindings.len();
+    quote! {{
+        let values: [#glib::Variant; #len] = [
+            #(#glib::variant::ToVariant::to_variant(#bindings),)*
+        ];
+        #glib::Variant::tuple_from_iter(values)
+    }}
+}
+
+/// Destructure `Self` or one of its variants into bindings.
+fn pattern(path: &TokenStream2, fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path ( #(#bindings),* ) },
+        Fields::Unit => quote! { #path },
+    }
+}
+
+/// Read the fields from the children of the `tuple` variant.
+fn construct(
+    glib: &TokenStream2,
+    path: &TokenStream2,
+    fields: &Fields,
+    tuple: &TokenStream2,
+) -> TokenStream2 {
+    let values = (0..fields.len()).map(|index| {
+        quote! { #glib::variant::FromVariant::from_variant(&#tuple.child_value(#index))? }
+    });
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path ( #(#values),* ) },
+        Fields::Unit => quote! { #path },
+    }
+}
+
+/// Structs are stored as tuples of their fields.
+fn struct_tokens(
+    glib: &TokenStream2,
+    fields: &Fields,
+) -> (TokenStream2, TokenStream2, TokenStream2) {
+    let (types, bindings) = field_bindings(fields);
+    let path = quote! { Self };
+
+    let tuple_type = tuple_type(glib, &types);
+    let variant_type = quote! { ::std::borrow::Cow::Owned(#tuple_type) };
+
+    let pattern = pattern(&path, fields, &bindings);
+    let tuple_value = tuple_value(glib, &bindings);
+    let to_variant = quote! {
+        let #pattern = self;
+        #tuple_value
+    };
+
+    let construct = construct(glib, &path, fields, &quote! { variant });
+    let from_variant = quote! { ::std::option::Option::Some(#construct) };
+
+    (variant_type, to_variant, from_variant)
+}
+
+/// Enums with unit variants only are stored as string of the variant name.
+/// Other enums are stored as `(sv)` with the variant name and a tuple of the fields.
+fn enum_tokens(
+    glib: &TokenStream2,
+    variants: &[(Ident, Fields)],
+) -> (TokenStream2, TokenStream2, TokenStream2) {
+    let names: Vec<String> = variants
+        .iter()
+        .map(|(ident, _)| ident.to_string())
+        .collect();
+    let idents: Vec<&Ident> = variants.iter().map(|(ident, _)| ident).collect();
+
+    if variants
+        .iter()
+        .all(|(_, fields)| matches!(fields, Fields::Unit))
+    {
+        let variant_type = quote! {
+            ::std::borrow::Cow::Borrowed(#glib::VariantTy::STRING)
+        };
+        let to_variant = quote! {
+            let name = match self {
+                #(Self::#idents => #names,)*
+            };
+            #glib::variant::ToVariant::to_variant(name)
+        };
+        let from_variant = quote! {
+            match variant.str()? {
+                #(#names => ::std::option::Option::Some(Self::#idents),)*
+                _ => ::std::option::Option::None,
+            }
+        };
+        return (variant_type, to_variant, from_variant);
+    }
+
+    let mut to_arms = TokenStream2::new();
+    let mut from_arms = TokenStream2::new();
+    for ((ident, fields), name) in variants.iter().zip(&names) {
+        let (types, bindings) = field_bindings(fields);
+        let path = quote! { Self::#ident };
+
+        let pattern = pattern(&path, fields, &bindings);
+        let tuple_value = tuple_value(glib, &bindings);
+        to_arms.extend(quote! {
+            #pattern => (#name, #tuple_value),
+        });
+
+        let tuple_type = tuple_type(glib, &types);
+        let construct = construct(glib, &path, fields, &quote! { fields });
+        from_arms.extend(quote! {
+            #name => {
+                if !fields.is_type(&#tuple_type) {
+                    return ::std::option::Option::None;
+                }
+                ::std::option::Option::Some(#construct)
+            }
+        });
+    }
+
+    let variant_type = quote! {
+        ::std::borrow::Cow::Borrowed(#glib::VariantTy::new("(sv)").unwrap())
+    };
+    let to_variant = quote! {
+        let (name, fields) = match self {
+            #to_arms
+        };
+        #glib::Variant::tuple_from_iter([
+            #glib::variant::ToVariant::to_variant(name),
+            #glib::Variant::from_variant(&fields),
+        ])
+    };
+    let from_variant = quote! {
+        let name: ::std::string::String = variant.child_value(0).get()?;
+        let fields = variant.child_value(1).as_variant()?;
+        match name.as_str() {
+            #from_arms
+            _ => ::std::option::Option::None,
+        }
+    };
+
+    (variant_type, to_variant, from_variant)
+}
@@ -20,6 +20,7 @@ pub(super) fn generate_tokens(input: TokenStream) -> TokenStream {
             model_name: Ident::new("_", Span2::mixed_site()),
             sender_name: Ident::new("sender", Span2::call_site()),
             root_name: None,
+            pub_widgets: false,
         },
         true,
     );
@@ -6,6 +6,7 @@ use syn::visit::{self, Visit};
 use syn::visit_mut::{self, VisitMut};
 use syn::LocalInit;
 
+use crate::actions_enabled::ActionsEnabled;
 use crate::additional_fields::AdditionalFields;
 use crate::menu::Menus;
 use crate::util;
@@ -20,6 +21,7 @@ pub(super) struct ComponentVisitor<'errors> {
     pub(super) sender_name: Option<syn::Ident>,
     pub(super) additional_fields: Option<AdditionalFields>,
     pub(super) menus: Option<Menus>,
+    pub(super) actions_enabled: Option<ActionsEnabled>,
     pub(super) errors: &'errors mut Vec<syn::Error>,
 }
 
@@ -33,6 +35,7 @@ impl<'errors> ComponentVisitor<'errors> {
             sender_name: None,
             additional_fields: None,
             menus: None,
+            actions_enabled: None,
             errors,
         }
     }
@@ -90,6 +93,24 @@ impl VisitMut for ComponentVisitor<'_> {
                         };
                         remove = true;
                     }
+                    Some("actions_enabled") => {
+                        match mac.mac.parse_body::<ActionsEnabled>() {
+                            Ok(actions) => {
+                                let existing = self.actions_enabled.replace(actions);
+
+                                if existing.is_some() {
+                                    self.errors.push(syn::Error::new_spanned(
+                                        mac,
+                                        "duplicate actions_enabled macro",
+                                    ));
+                                }
+                            }
+                            Err(e) => {
+                                self.errors.push(e);
+                            }
+                        };
+                        remove = true;
+                    }
                     _ => (),
                 }
             }
@@ -94,6 +94,7 @@ fn try_generate_tokens(
                 model_name: Ident::new("_", Span2::mixed_site()),
                 sender_name: Ident::new("sender", Span2::call_site()),
                 root_name: None,
+                pub_widgets: false,
             },
             true,
         );
@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::Expr;
+use syn::{Expr, ExprLit, Ident, Lit};
 
 use crate::widgets::{AssignProperty, AssignPropertyAttr, PropertyName};
 
@@ -21,7 +21,9 @@ impl AssignProperty {
         if !info.is_conditional
             || !matches!(
                 self.attr,
-                AssignPropertyAttr::Track { .. } | AssignPropertyAttr::Watch { .. }
+                AssignPropertyAttr::Track { .. }
+                    | AssignPropertyAttr::Watch { .. }
+                    | AssignPropertyAttr::AsyncWatch { .. }
             )
         {
             self.assign_stream(info, p_name, init);
@@ -38,6 +40,22 @@ impl AssignProperty {
             return;
         }
 
+        if let AssignPropertyAttr::AsyncWatch {
+            pending, handle, ..
+        } = &self.attr
+        {
+            self.async_assign_stream(info, p_name, pending.as_deref(), handle);
+            return;
+        }
+
+        if let AssignPropertyAttr::BindExpr { map } = &self.attr {
+            // Expressions are only bound once and update the property by themselves.
+            if init {
+                self.bind_expr_stream(info, p_name, map.as_deref());
+            }
+            return;
+        }
+
         let assign_fn = p_name.assign_fn_stream(info);
         let self_assign_args = p_name.assign_args_stream(info.widget_name);
         let span = p_name.span();
@@ -131,4 +149,178 @@ impl AssignProperty {
                 }
             });
     }
+
+    /// Spawn the future of an `async_watch` property on the main context
+    /// and assign its output once it resolves.
+    ///
+    /// The handle of the previous future is stored in the widgets struct,
+    /// so it can be aborted if the property is updated before it resolved.
+    fn async_assign_stream(
+        &self,
+        info: &mut AssignInfo<'_>,
+        p_name: &PropertyName,
+        pending: Option<&Expr>,
+        handle: &Ident,
+    ) {
+        let span = p_name.span();
+        let gtk_import = crate::gtk_import();
+        let w_name = info.widget_name;
+        let expr = &self.expr;
+
+        let args = self.args.as_ref().map(|args| {
+            quote! {
+                , #args
+            }
+        });
+
+        let chain = self.chain.as_ref().map(|chain| {
+            quote_spanned! {
+                chain.span() => .#chain
+            }
+        });
+
+        let pending_stream = pending.map(|pending| {
+            let assign_fn = p_name.assign_fn_stream(info);
+            let self_assign_args = p_name.assign_args_stream(w_name);
+            quote_spanned! { span =>
+                #assign_fn(#self_assign_args #pending #args) #chain;
+            }
+        });
+
+        // Template children are moved into the future by their full path
+        // and assigned like regular widgets from there on.
+        let widget = if let Some(template_path) = &info.template_path {
+            quote! { #template_path }
+        } else {
+            quote! { #w_name }
+        };
+
+        let mut inner_stream = TokenStream2::new();
+        let mut inner_info = AssignInfo {
+            stream: &mut inner_stream,
+            widget_name: w_name,
+            template_path: None,
+            is_conditional: info.is_conditional,
+        };
+        let assign_fn = p_name.assign_fn_stream(&mut inner_info);
+        let self_assign_args = p_name.assign_args_stream(w_name);
+
+        let assign_value = if self.optional_assign {
+            quote! {
+                if let Some(assign) = value {
+                    #assign_fn(#self_assign_args assign #args) #chain;
+                }
+            }
+        } else {
+            quote_spanned! { span =>
+                #assign_fn(#self_assign_args value #args) #chain;
+            }
+        };
+
+        info.stream.extend(quote_spanned! { span =>
+            {
+                #pending_stream
+                let future = #expr;
+                #[allow(clippy::redundant_clone)]
+                let #w_name = #widget.clone();
+                let handle = #gtk_import::glib::MainContext::ref_thread_default().spawn_local(async move {
+                    let value = future.await;
+                    #assign_value
+                });
+                if let Some(previous) = #handle.replace(handle) {
+                    previous.abort();
+                }
+            }
+        });
+    }
+
+    /// Build a [`gtk::Expression`] from the value of a `bind_expr` property
+    /// and bind it to the property of the widget.
+    ///
+    /// Indexing an object with string literals, like `entry["buffer"]["text"]`,
+    /// creates a chain of property expressions. Other values are used as
+    /// expression directly.
+    fn bind_expr_stream(
+        &self,
+        info: &mut AssignInfo<'_>,
+        p_name: &PropertyName,
+        map: Option<&Expr>,
+    ) {
+        let span = p_name.span();
+        let gtk_import = crate::gtk_import();
+
+        let property = match p_name {
+            PropertyName::Ident(ident) => {
+                let name = ident.to_string();
+                let name = name.strip_prefix("set_").unwrap_or(&name);
+                name.replace('_', "-")
+            }
+            PropertyName::Path(_) | PropertyName::RelmContainerExtAssign(_) => {
+                info.stream.extend(quote_spanned! { span =>
+                    compile_error!("`bind_expr` can only be used with property names like `set_label`.");
+                });
+                return;
+            }
+        };
+
+        let mut properties = Vec::new();
+        let mut base = &self.expr;
+        while let Expr::Index(index) = base {
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Str(name),
+                ..
+            }) = &*index.index
+            {
+                properties.push(name);
+                base = &index.expr;
+            } else {
+                break;
+            }
+        }
+        properties.reverse();
+
+        let mut expression_stream = TokenStream2::new();
+        let mut properties = properties.into_iter();
+        if let Some(first) = properties.next() {
+            expression_stream.extend(quote_spanned! { base.span() =>
+                let expression = #gtk_import::prelude::GObjectPropertyExpressionExt::property_expression_weak(&#base, #first);
+            });
+        } else {
+            expression_stream.extend(quote_spanned! { base.span() =>
+                let expression = #base;
+            });
+        }
+        for name in properties {
+            expression_stream.extend(quote_spanned! { name.span() =>
+                let expression = ::std::convert::AsRef::<#gtk_import::Expression>::as_ref(&expression);
+                let expression = #gtk_import::PropertyExpression::new(expression.value_type(), Some(expression), #name);
+            });
+        }
+
+        let map_stream = map.map(|map| {
+            quote_spanned! { map.span() =>
+                let map = #map;
+                let expression = expression.chain_closure_with_callback(move |values| {
+                    map(values[1].get().expect("Expression returned a value of an unexpected type"))
+                });
+                let expression = ::std::convert::AsRef::<#gtk_import::Expression>::as_ref(&expression);
+            }
+        });
+
+        let widget = if let Some(template_path) = &info.template_path {
+            quote! { #template_path }
+        } else {
+            let w_name = info.widget_name;
+            quote! { #w_name }
+        };
+
+        info.stream.extend(quote_spanned! { span =>
+            {
+                #expression_stream
+                let expression = ::std::convert::AsRef::<#gtk_import::Expression>::as_ref(&expression);
+                #map_stream
+                expression.bind(&#widget, #property, None::<&#gtk_import::glib::Object>);
+            }
+        });
+    }
 }
@@ -0,0 +1,71 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::Ident;
+
+use crate::widgets::{Gesture, PropertyName};
+
+use super::AssignInfo;
+
+impl Gesture {
+    /// Create the gesture controller, connect its signal to
+    /// send the message and attach it to the widget.
+    pub(super) fn connect_stream(
+        &self,
+        info: &mut AssignInfo<'_>,
+        p_name: &PropertyName,
+        sender_name: &Ident,
+    ) {
+        let span = p_name.span();
+        let gtk_import = crate::gtk_import();
+        let msg = &self.msg;
+
+        let phase = self.phase.as_ref().map(ToString::to_string);
+        let (ty, signal, args) = match (self.kind.to_string().as_str(), phase.as_deref()) {
+            ("click", None | Some("pressed")) => {
+                ("GestureClick", "pressed", &["n_press", "x", "y"][..])
+            }
+            ("click", Some("released")) => ("GestureClick", "released", &["n_press", "x", "y"][..]),
+            ("drag", Some("begin")) => ("GestureDrag", "drag_begin", &["x", "y"][..]),
+            ("drag", None | Some("update")) => ("GestureDrag", "drag_update", &["x", "y"][..]),
+            ("drag", Some("end")) => ("GestureDrag", "drag_end", &["x", "y"][..]),
+            ("long_press", None) => ("GestureLongPress", "pressed", &["x", "y"][..]),
+            ("swipe", None) => ("GestureSwipe", "swipe", &["x", "y"][..]),
+            ("zoom", None) => ("GestureZoom", "scale_changed", &["scale"][..]),
+            _ => unreachable!("gesture kinds and phases are validated while parsing"),
+        };
+
+        let ty = format_ident!("{ty}", span = self.kind.span());
+        let connect = format_ident!("connect_{signal}", span = self.kind.span());
+        let args: Vec<Ident> = args
+            .iter()
+            .map(|arg| Ident::new(arg, self.kind.span()))
+            .collect();
+
+        let options = self.options.iter().map(|(name, value)| {
+            let setter = format_ident!("set_{name}", span = name.span());
+            quote_spanned! { name.span() => gesture.#setter(#value); }
+        });
+
+        let widget = if let Some(template_path) = &info.template_path {
+            quote! { #template_path }
+        } else {
+            let w_name = info.widget_name;
+            quote! { #w_name }
+        };
+
+        info.stream.extend(quote_spanned! { span =>
+            {
+                use #gtk_import::prelude::*;
+
+                let gesture = #gtk_import::#ty::new();
+                #(#options)*
+                #[allow(clippy::redundant_clone)]
+                let sender = #sender_name.clone();
+                gesture.#connect(move |gesture, #(#args),*| {
+                    let _ = (gesture, #(#args),*);
+                    sender.input(#msg);
+                });
+                #widget.add_controller(gesture);
+            }
+        });
+    }
+}
@@ -5,6 +5,7 @@ use crate::widgets::{Property, PropertyType};
 
 mod assign_property;
 mod conditional_widget;
+mod gesture;
 mod properties;
 mod signal_handler;
 mod widgets;
@@ -31,6 +32,9 @@ impl Property {
             PropertyType::SignalHandler(signal_handler) => {
                 signal_handler.connect_signals_stream(info, &self.name, sender_name);
             }
+            PropertyType::Gesture(gesture) => {
+                gesture.connect_stream(info, &self.name, sender_name);
+            }
             PropertyType::ParseError(_) => (),
         }
     }
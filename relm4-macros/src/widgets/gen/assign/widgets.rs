@@ -37,6 +37,79 @@ impl Widget {
             is_conditional: false,
         };
         self.properties.assign_stream(&mut info, sender_name);
+        self.keys_stream(&mut info, sender_name);
+        self.spellcheck_stream(&mut info);
+        self.validation_stream(&mut info, sender_name);
+    }
+
+    /// Attach the key bindings of the `#[keys(...)]` attribute.
+    fn keys_stream(&self, info: &mut AssignInfo<'_>, sender_name: &Ident) {
+        if let Some(keys) = &self.keys {
+            let gtk_import = crate::gtk_import();
+            let key_map = keys.key_map_stream();
+            let widget = if let Some(template_path) = &info.template_path {
+                quote! { #template_path }
+            } else {
+                let w_name = info.widget_name;
+                quote! { #w_name }
+            };
+
+            info.stream.extend(quote! {
+                #gtk_import::prelude::WidgetExt::add_controller(
+                    &#widget,
+                    #key_map.into_controller(#sender_name.input_sender()),
+                );
+            });
+        }
+    }
+
+    /// Attach spell checking of the `#[spellcheck(...)]` attribute.
+    fn spellcheck_stream(&self, info: &mut AssignInfo<'_>) {
+        if let Some(lang) = &self.spellcheck {
+            let widget = if let Some(template_path) = &info.template_path {
+                quote! { #template_path }
+            } else {
+                let w_name = info.widget_name;
+                quote! { #w_name }
+            };
+
+            info.stream.extend(quote! {
+                relm4::spell::attach(&#widget, #lang);
+            });
+        }
+    }
+
+    /// Attach the input mask and validator of the `#[mask(...)]` and `#[validate(...)]` attributes.
+    fn validation_stream(&self, info: &mut AssignInfo<'_>, sender_name: &Ident) {
+        if self.mask.is_none() && self.validate.is_none() {
+            return;
+        }
+
+        let widget = if let Some(template_path) = &info.template_path {
+            quote! { #template_path }
+        } else {
+            let w_name = info.widget_name;
+            quote! { #w_name }
+        };
+
+        if let Some(mask) = &self.mask {
+            info.stream.extend(quote! {
+                relm4::validation::RelmEntryExt::set_input_mask(&#widget, #mask);
+            });
+        }
+        if let Some(validate) = &self.validate {
+            let (validator, message) = &**validate;
+            info.stream.extend(quote! {
+                {
+                    let sender = #sender_name.input_sender().clone();
+                    relm4::validation::RelmEntryExt::connect_validated(
+                        &#widget,
+                        #validator,
+                        move |result| sender.emit((#message)(result)),
+                    );
+                }
+            });
+        }
     }
 
     pub(super) fn assign_stream<'a>(
@@ -57,10 +130,27 @@ impl Widget {
                 is_conditional: info.is_conditional,
             };
             self.properties.assign_stream(&mut info, sender_name);
+            self.keys_stream(&mut info, sender_name);
+            self.spellcheck_stream(&mut info);
+            self.validation_stream(&mut info, sender_name);
         }
 
-        // Template children are already assigned by the template.
-        if self.template_attr != WidgetTemplateAttr::TemplateChild {
+        // Objects are associated with their parent instead of being added as child.
+        if self.object && matches!(p_name, PropertyName::RelmContainerExtAssign(_)) {
+            let parent = if let Some(template_path) = &info.template_path {
+                quote! { #template_path }
+            } else {
+                let w_name = info.widget_name;
+                quote! { #w_name }
+            };
+            let assign = self.widget_assignment();
+            let span = p_name.span();
+
+            info.stream.extend(quote_spanned! {
+                span => relm4::RelmAssociateExt::associate(#assign, &#parent);
+            });
+        } else if self.template_attr != WidgetTemplateAttr::TemplateChild {
+            // Template children are already assigned by the template.
             let assign_fn = p_name.assign_fn_stream(info);
             let self_assign_args = p_name.assign_args_stream(info.widget_name);
             let assign = self.widget_assignment();
@@ -32,7 +32,9 @@ impl Property {
             PropertyType::ConditionalWidget(cond_widget) => {
                 cond_widget.conditional_init_stream(stream, model_name);
             }
-            PropertyType::SignalHandler(_) | PropertyType::ParseError(_) => (),
+            PropertyType::SignalHandler(_)
+            | PropertyType::Gesture(_)
+            | PropertyType::ParseError(_) => (),
         }
     }
 }
@@ -165,8 +167,9 @@ impl AssignProperty {
         // Unconditional code is handled in the "normal" init stream
         if is_conditional {
             match &self.attr {
-                AssignPropertyAttr::None => (),
-                AssignPropertyAttr::Watch { skip_init } => {
+                AssignPropertyAttr::None | AssignPropertyAttr::BindExpr { .. } => (),
+                AssignPropertyAttr::Watch { skip_init }
+                | AssignPropertyAttr::AsyncWatch { skip_init, .. } => {
                     if skip_init.is_none() {
                         let mut info = AssignInfo {
                             stream,
@@ -2,8 +2,8 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
 use crate::widgets::{
-    ConditionalBranches, ConditionalWidget, Properties, Property, PropertyType, ReturnedWidget,
-    SignalHandler, Widget,
+    AssignProperty, AssignPropertyAttr, ConditionalBranches, ConditionalWidget, Properties,
+    Property, PropertyType, ReturnedWidget, SignalHandler, Widget,
 };
 
 impl Property {
@@ -16,7 +16,8 @@ impl Property {
             PropertyType::ConditionalWidget(cond_widget) => {
                 cond_widget.destructure_stream(stream);
             }
-            PropertyType::Assign(_) | PropertyType::ParseError(_) => (),
+            PropertyType::Assign(assign) => assign.destructure_stream(stream),
+            PropertyType::Gesture(_) | PropertyType::ParseError(_) => (),
         }
     }
 }
@@ -84,3 +85,13 @@ impl SignalHandler {
         }
     }
 }
+
+impl AssignProperty {
+    pub(super) fn destructure_stream(&self, stream: &mut TokenStream2) {
+        if let AssignPropertyAttr::AsyncWatch { handle, .. } = &self.attr {
+            stream.extend(quote! {
+                #handle,
+            });
+        }
+    }
+}
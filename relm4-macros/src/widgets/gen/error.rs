@@ -11,9 +11,10 @@ impl Property {
     fn error_stream(&self, stream: &mut TokenStream2, w_name: &Ident) {
         match &self.ty {
             PropertyType::ParseError(error) => error.error_stream(stream, w_name),
-            PropertyType::SignalHandler(_) | PropertyType::Assign(_) => (),
             PropertyType::Widget(widget) => widget.error_stream(stream),
             PropertyType::ConditionalWidget(cond_widget) => cond_widget.error_stream(stream),
+            PropertyType::SignalHandler(_) | PropertyType::Gesture(_) | PropertyType::Assign(_) => {
+            }
         }
     }
 }
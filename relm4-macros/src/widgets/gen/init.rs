@@ -2,8 +2,8 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, quote_spanned};
 
 use crate::widgets::{
-    ConditionalBranches, ConditionalWidget, Properties, Property, PropertyType, Widget, WidgetAttr,
-    WidgetTemplateAttr,
+    AssignProperty, AssignPropertyAttr, ConditionalBranches, ConditionalWidget, Properties,
+    Property, PropertyType, ReturnedWidget, Widget, WidgetAttr, WidgetTemplateAttr,
 };
 
 impl Property {
@@ -15,6 +15,9 @@ impl Property {
             PropertyType::ConditionalWidget(cond_widget) => {
                 cond_widget.init_stream(stream);
             }
+            PropertyType::Assign(assign) => {
+                assign.init_stream(stream);
+            }
             _ => (),
         }
     }
@@ -31,6 +34,7 @@ impl Properties {
 impl Widget {
     pub(crate) fn init_stream(&self, stream: &mut TokenStream2) {
         self.self_init_stream(stream);
+        self.view_name_stream(stream);
         self.other_init_stream(stream);
     }
 
@@ -47,9 +51,36 @@ impl Widget {
             WidgetTemplateAttr::Template => self.func.widget_template_init(),
         });
 
+        self.view_name_stream(init_stream);
         self.other_init_stream(init_stream);
     }
 
+    /// Remember the names of widgets with `#[name]`, so tests can find them.
+    fn view_name_stream(&self, stream: &mut TokenStream2) {
+        // Template children aren't bound to a variable.
+        if !self.name_assigned_by_user
+            || self.template_attr == WidgetTemplateAttr::TemplateChild
+            || !cfg!(feature = "relm4")
+        {
+            return;
+        }
+
+        let name = &self.name;
+        let name_str = name.to_string();
+        let widget = if self.attr == WidgetAttr::LocalRef {
+            quote! { &*#name }
+        } else {
+            quote! { &#name }
+        };
+        stream.extend(quote! {
+            {
+                #[allow(unused_imports)]
+                use relm4::test::{ViewNameOther as _, ViewNameWidget as _};
+                (&&relm4::test::ViewNameProbe(#widget)).set_view_name(#name_str);
+            }
+        });
+    }
+
     fn self_init_stream(&self, stream: &mut TokenStream2) {
         let mutability = &self.mutable;
         let name = &self.name;
@@ -77,6 +108,9 @@ impl Widget {
 
     fn other_init_stream(&self, stream: &mut TokenStream2) {
         self.properties.init_stream(stream);
+        if let Some(returned_widget) = &self.returned_widget {
+            returned_widget.init_stream(stream);
+        }
     }
 }
 
@@ -111,3 +145,27 @@ impl ConditionalWidget {
         }
     }
 }
+
+impl ReturnedWidget {
+    fn init_stream(&self, stream: &mut TokenStream2) {
+        // Returned widgets are initialized by their parent,
+        // only the state of their properties is needed.
+        for prop in &self.properties.properties {
+            if let PropertyType::Assign(assign) = &prop.ty {
+                assign.init_stream(stream);
+            }
+        }
+    }
+}
+
+impl AssignProperty {
+    fn init_stream(&self, stream: &mut TokenStream2) {
+        if let AssignPropertyAttr::AsyncWatch { handle, .. } = &self.attr {
+            let gtk_import = crate::gtk_import();
+            stream.extend(quote! {
+                #[allow(unused_mut, unused_variables)]
+                let mut #handle: Option<#gtk_import::glib::JoinHandle<()>> = None;
+            });
+        }
+    }
+}
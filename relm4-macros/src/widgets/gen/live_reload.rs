@@ -0,0 +1,179 @@
+//! Layout descriptions for the live reload mode of Relm4.
+//!
+//! The layout of a view is described by the properties that are
+//! assigned to literal values, one per line:
+//! `<widget name>\t<property name>\t<value>`.
+//! Values are prefixed by their kind, for example `s:Increment`,
+//! `i:5`, `f:0.5`, `b:true` or `e:center` for enum variants.
+//!
+//! The layout is embedded in the binary as a record
+//! `\0relm4-layout\0<module path>::<type>\0<layout>\0`,
+//! so `cargo relm dev` can extract the layouts of a new build.
+
+use std::fmt::Write;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{Expr, Lit, UnOp};
+
+use crate::widgets::{
+    AssignPropertyAttr, PropertyName, PropertyType, ViewWidgets, Widget, WidgetTemplateAttr,
+};
+
+impl ViewWidgets {
+    /// Generates the code that registers the widgets and the layout
+    /// of the view for live reloading.
+    ///
+    /// The widgets are registered before any properties are assigned,
+    /// because assigned widgets might be moved.
+    pub(crate) fn live_reload_stream(&self, component: &syn::Type) -> TokenStream2 {
+        let mut widgets = Vec::new();
+        let mut layout = String::new();
+        for widget in &self.top_level_widgets {
+            widget.inner.collect_layout(&mut widgets, &mut layout);
+        }
+
+        let type_name = type_name(component);
+        let names = widgets.iter().map(ToString::to_string);
+        quote! {
+            relm4::live_reload::register(
+                ::std::concat!(
+                    "\0relm4-layout\0",
+                    ::std::module_path!(),
+                    "::",
+                    #type_name,
+                    "\0",
+                    #layout,
+                    "\0",
+                ),
+                &[#(
+                    (#names, {
+                        #[allow(unused_imports)]
+                        use relm4::component::root_widget::{
+                            RootDerefsToObject as _, RootIsObject as _, RootIsOther as _,
+                        };
+                        (&&&relm4::component::root_widget::RootWidgetProbe(&#widgets)).root_widget()
+                    }),
+                )*],
+            );
+        }
+    }
+}
+
+impl Widget {
+    fn collect_layout(&self, widgets: &mut Vec<syn::Ident>, layout: &mut String) {
+        if self.template_attr == WidgetTemplateAttr::TemplateChild {
+            return;
+        }
+        widgets.push(self.name.clone());
+
+        for property in &self.properties.properties {
+            match &property.ty {
+                PropertyType::Assign(assign) => {
+                    let PropertyName::Ident(ident) = &property.name else {
+                        continue;
+                    };
+                    let is_plain = matches!(assign.attr, AssignPropertyAttr::None)
+                        && assign.args.is_none()
+                        && assign.chain.is_none()
+                        && !assign.optional_assign
+                        && !assign.iterative;
+                    let name = ident.to_string();
+                    let Some(property_name) = name.strip_prefix("set_") else {
+                        continue;
+                    };
+
+                    if let (true, Some(value)) = (is_plain, literal_value(&assign.expr)) {
+                        writeln!(
+                            layout,
+                            "{}\t{}\t{value}",
+                            self.name,
+                            property_name.replace('_', "-")
+                        )
+                        .unwrap();
+                    }
+                }
+                PropertyType::Widget(widget) => widget.collect_layout(widgets, layout),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns the value of literals and enum variants.
+fn literal_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(value) => Some(format!("s:{}", escape(&value.value()))),
+            Lit::Int(value) => Some(format!("i:{}", value.base10_digits())),
+            Lit::Float(value) => Some(format!("f:{}", value.base10_digits())),
+            Lit::Bool(value) => Some(format!("b:{}", value.value)),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => literal_value(&unary.expr)
+            .and_then(|value| {
+                let (kind, number) = value.split_once(':')?;
+                matches!(kind, "i" | "f").then(|| format!("{kind}:-{number}"))
+            }),
+        Expr::Reference(reference) => literal_value(&reference.expr),
+        Expr::Call(call) if call.args.len() == 1 && is_some(&call.func) => {
+            literal_value(&call.args[0])
+        }
+        // Enum variants like `gtk::Align::Center`.
+        Expr::Path(path) if path.qself.is_none() && path.path.segments.len() > 1 => {
+            let variant = path.path.segments.last()?.ident.to_string();
+            variant
+                .starts_with(|c: char| c.is_ascii_uppercase())
+                .then(|| format!("e:{}", to_nick(&variant)))
+        }
+        _ => None,
+    }
+}
+
+fn is_some(func: &Expr) -> bool {
+    matches!(func, Expr::Path(path) if path.path.is_ident("Some"))
+}
+
+/// Converts a variant name to the nick of the GLib enum value,
+/// for example `SpaceBetween` to `space-between`.
+fn to_nick(variant: &str) -> String {
+    let mut nick = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                nick.push('-');
+            }
+            nick.push(c.to_ascii_lowercase());
+        } else {
+            nick.push(c);
+        }
+    }
+    nick
+}
+
+/// Escapes characters that separate the fields, lines and records of the layout.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\0', "\\0")
+}
+
+/// Returns the name of the type of the component without path and generics.
+fn type_name(component: &syn::Type) -> String {
+    match component {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        _ => component
+            .to_token_stream()
+            .to_string()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '_')
+            .collect(),
+    }
+}
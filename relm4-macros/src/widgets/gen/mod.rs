@@ -18,6 +18,8 @@ mod conditional_init;
 mod destructure_fields;
 mod error;
 mod init;
+#[cfg(feature = "live_reload")]
+mod live_reload;
 mod update_view;
 
 impl Widget {
@@ -12,7 +12,8 @@ impl Property {
             PropertyType::Widget(widget) => widget.return_stream(stream),
             PropertyType::SignalHandler(signal_handler) => signal_handler.return_stream(stream),
             PropertyType::ConditionalWidget(cond_widget) => cond_widget.return_stream(stream),
-            PropertyType::Assign(_) | PropertyType::ParseError(_) => (),
+            PropertyType::Assign(assign) => assign.destructure_stream(stream),
+            PropertyType::Gesture(_) | PropertyType::ParseError(_) => (),
         }
     }
 }
@@ -4,38 +4,64 @@ use syn::Visibility;
 
 use super::{ReturnedWidget, Widget};
 use crate::widgets::{
-    ConditionalBranches, ConditionalWidget, Properties, Property, PropertyType, SignalHandler,
+    AssignProperty, AssignPropertyAttr, ConditionalBranches, ConditionalWidget, Properties,
+    Property, PropertyType, SignalHandler,
 };
 
 impl Property {
-    fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+    fn struct_fields_stream(
+        &self,
+        stream: &mut TokenStream2,
+        vis: &Option<Visibility>,
+        pub_widgets: bool,
+    ) {
         match &self.ty {
-            PropertyType::Widget(widget) => widget.struct_fields_stream(stream, vis),
+            PropertyType::Widget(widget) => widget.struct_fields_stream(stream, vis, pub_widgets),
             PropertyType::SignalHandler(signal_handler) => {
                 signal_handler.struct_fields_stream(stream, vis);
             }
             PropertyType::ConditionalWidget(cond_widget) => {
-                cond_widget.struct_fields_stream(stream, vis);
+                cond_widget.struct_fields_stream(stream, vis, pub_widgets);
             }
-            PropertyType::Assign(_) | PropertyType::ParseError(_) => (),
+            PropertyType::Assign(assign) => assign.struct_fields_stream(stream, vis),
+            PropertyType::Gesture(_) | PropertyType::ParseError(_) => (),
         }
     }
 }
 
 impl Properties {
-    fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+    fn struct_fields_stream(
+        &self,
+        stream: &mut TokenStream2,
+        vis: &Option<Visibility>,
+        pub_widgets: bool,
+    ) {
         for prop in &self.properties {
-            prop.struct_fields_stream(stream, vis);
+            prop.struct_fields_stream(stream, vis, pub_widgets);
         }
     }
 }
 
 impl Widget {
-    pub(crate) fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+    pub(crate) fn struct_fields_stream(
+        &self,
+        stream: &mut TokenStream2,
+        vis: &Option<Visibility>,
+        pub_widgets: bool,
+    ) {
         if self.has_struct_field() {
             let name = &self.name;
             let ty = self.func_type_token_stream();
 
+            // Only widgets with a name given by the user can be public,
+            // generated names may change between versions.
+            let public = self.public || (pub_widgets && self.name_assigned_by_user);
+            let vis = if public {
+                Some(Visibility::Public(Default::default()))
+            } else {
+                vis.clone()
+            };
+
             stream.extend(if let Some(docs) = &self.doc_attr {
                 quote! {
                     #[doc = #docs]
@@ -49,15 +75,21 @@ impl Widget {
             });
         }
 
-        self.properties.struct_fields_stream(stream, vis);
+        self.properties
+            .struct_fields_stream(stream, vis, pub_widgets);
         if let Some(returned_widget) = &self.returned_widget {
-            returned_widget.struct_fields_stream(stream, vis);
+            returned_widget.struct_fields_stream(stream, vis, pub_widgets);
         }
     }
 }
 
 impl ConditionalWidget {
-    fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+    fn struct_fields_stream(
+        &self,
+        stream: &mut TokenStream2,
+        vis: &Option<Visibility>,
+        pub_widgets: bool,
+    ) {
         let name = &self.name;
         let gtk_import = crate::gtk_import();
 
@@ -78,12 +110,12 @@ impl ConditionalWidget {
         match &self.branches {
             ConditionalBranches::If(if_branches) => {
                 for branch in if_branches {
-                    branch.widget.struct_fields_stream(stream, vis);
+                    branch.widget.struct_fields_stream(stream, vis, pub_widgets);
                 }
             }
             ConditionalBranches::Match((_, _, match_arms)) => {
                 for arm in match_arms {
-                    arm.widget.struct_fields_stream(stream, vis);
+                    arm.widget.struct_fields_stream(stream, vis, pub_widgets);
                 }
             }
         }
@@ -91,7 +123,12 @@ impl ConditionalWidget {
 }
 
 impl ReturnedWidget {
-    fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+    fn struct_fields_stream(
+        &self,
+        stream: &mut TokenStream2,
+        vis: &Option<Visibility>,
+        pub_widgets: bool,
+    ) {
         if let Some(ty) = &self.ty {
             let name = &self.name;
             stream.extend(quote! {
@@ -99,7 +136,8 @@ impl ReturnedWidget {
                 #vis #name: #ty,
             });
         }
-        self.properties.struct_fields_stream(stream, vis);
+        self.properties
+            .struct_fields_stream(stream, vis, pub_widgets);
     }
 }
 
@@ -115,3 +153,15 @@ impl SignalHandler {
         }
     }
 }
+
+impl AssignProperty {
+    fn struct_fields_stream(&self, stream: &mut TokenStream2, vis: &Option<Visibility>) {
+        if let AssignPropertyAttr::AsyncWatch { handle, .. } = &self.attr {
+            let gtk_import = crate::gtk_import();
+            stream.extend(quote! {
+                #[allow(missing_docs)]
+                #vis #handle: Option<#gtk_import::glib::JoinHandle<()>>,
+            });
+        }
+    }
+}
@@ -38,7 +38,9 @@ impl Property {
             PropertyType::ConditionalWidget(cond_widget) => {
                 cond_widget.update_view_stream(stream, model_name);
             }
-            PropertyType::SignalHandler(_) | PropertyType::ParseError(_) => (),
+            PropertyType::SignalHandler(_)
+            | PropertyType::Gesture(_)
+            | PropertyType::ParseError(_) => (),
         }
     }
 }
@@ -188,8 +190,8 @@ impl AssignProperty {
         conditional_branch: bool,
     ) {
         match &self.attr {
-            AssignPropertyAttr::None => (),
-            AssignPropertyAttr::Watch { .. } => {
+            AssignPropertyAttr::None | AssignPropertyAttr::BindExpr { .. } => (),
+            AssignPropertyAttr::Watch { .. } | AssignPropertyAttr::AsyncWatch { .. } => {
                 let mut info = AssignInfo {
                     stream,
                     widget_name,
@@ -1,4 +1,20 @@
-use crate::widgets::{AssignPropertyAttr, Properties, PropertyType, Widget, WidgetTemplateAttr};
+use crate::widgets::{
+    AssignPropertyAttr, Properties, PropertyType, ViewWidgets, Widget, WidgetTemplateAttr,
+};
+
+impl ViewWidgets {
+    /// Keep the root widget in the widgets struct,
+    /// so it can be used while updating the view.
+    pub(crate) fn keep_root_field(&mut self) {
+        if let Some(root) = self
+            .top_level_widgets
+            .iter_mut()
+            .find(|w| w.root_attr.is_some())
+        {
+            root.inner.name_assigned_by_user = true;
+        }
+    }
+}
 
 impl Widget {
     /// Don't generate any fields if the widget wasn't named by the user and
@@ -20,7 +36,9 @@ impl Properties {
         self.properties.iter().any(|prop| match &prop.ty {
             PropertyType::Assign(assign_prop) => matches!(
                 &assign_prop.attr,
-                AssignPropertyAttr::Track { .. } | AssignPropertyAttr::Watch { .. }
+                AssignPropertyAttr::Track { .. }
+                    | AssignPropertyAttr::Watch { .. }
+                    | AssignPropertyAttr::AsyncWatch { .. }
             ),
             _ => false,
         })
@@ -4,6 +4,7 @@ use syn::token::{Else, FatArrow, If, Match, Mut};
 use syn::{token, AngleBracketedGenericArguments, Expr, ExprClosure, Ident, Pat, Path, Type};
 
 use crate::args::Args;
+use crate::key_map::KeyBindings;
 
 mod gen;
 mod parse;
@@ -26,6 +27,7 @@ pub(super) struct TopLevelWidget {
 enum PropertyType {
     Assign(AssignProperty),
     SignalHandler(SignalHandler),
+    Gesture(Gesture),
     Widget(Widget),
     ConditionalWidget(ConditionalWidget),
     ParseError(ParseError),
@@ -49,6 +51,17 @@ enum AssignPropertyAttr {
         skip_init: Option<Ident>,
         paste_model: bool,
     },
+    AsyncWatch {
+        skip_init: Option<Ident>,
+        /// Value assigned while the future is still pending
+        pending: Option<Box<Expr>>,
+        /// Name of the field storing the handle of the running future
+        handle: Ident,
+    },
+    BindExpr {
+        /// Closure that maps the value of the expression
+        map: Option<Box<Expr>>,
+    },
 }
 
 #[derive(Debug)]
@@ -71,6 +84,18 @@ struct SignalHandler {
     handler_id: Option<Ident>,
 }
 
+/// A gesture controller that sends a message, for example
+/// `#[gesture(click(button = 3))] => Msg::ContextMenu(x, y)`.
+#[derive(Debug)]
+struct Gesture {
+    kind: Ident,
+    /// The signal of the gesture, for example `end` for drag gestures
+    phase: Option<Ident>,
+    /// Setters like `button = 3`
+    options: Vec<(Ident, Expr)>,
+    msg: Expr,
+}
+
 #[derive(Debug)]
 enum SignalHandlerVariant {
     Expr(Expr),
@@ -135,6 +160,19 @@ pub(super) struct Widget {
     ref_token: Option<token::And>,
     deref_token: Option<token::Star>,
     returned_widget: Option<ReturnedWidget>,
+    /// Key bindings from the `#[keys(...)]` attribute
+    keys: Option<Box<KeyBindings>>,
+    /// Language from the `#[spellcheck(...)]` attribute
+    spellcheck: Option<Box<Expr>>,
+    /// Validator and message from the `#[validate(...)]` attribute
+    validate: Option<Box<(Expr, Expr)>>,
+    /// Input mask from the `#[mask(...)]` attribute
+    mask: Option<Box<Expr>>,
+    /// The field is `pub` because of the `#[public]` attribute
+    public: bool,
+    /// The item isn't a widget, but an object associated with its parent
+    /// because of the `#[object]` attribute
+    object: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -204,8 +242,17 @@ enum Attr {
     Iterate(Ident),
     Watch(Ident, Option<Ident>),
     Track(Ident, Option<Ident>, Option<Box<Expr>>),
+    AsyncWatch(Ident, Option<Ident>, Option<Box<Expr>>),
+    BindExpr(Ident, Option<Box<Expr>>),
     BlockSignal(Ident, Vec<Ident>),
+    Gesture(Ident, Box<Expr>),
+    Keys(Ident, Box<KeyBindings>),
+    Spellcheck(Ident, Box<Expr>),
+    Validate(Ident, Box<Expr>, Box<Expr>),
+    Mask(Ident, Box<Expr>),
     Name(Ident, Ident),
+    Object(Ident),
+    Public(Ident),
     Transition(Ident, Ident),
     Wrap(Ident, Path),
     Chain(Ident, Box<Expr>),
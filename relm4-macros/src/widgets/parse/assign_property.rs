@@ -5,7 +5,7 @@ use syn::spanned::Spanned;
 use syn::{Error, Expr, ExprCall, ExprField, Ident, Member, Result, Token};
 
 use crate::args::Args;
-use crate::widgets::parse_util::attr_twice_error;
+use crate::widgets::parse_util::{self, attr_twice_error};
 use crate::widgets::{AssignProperty, AssignPropertyAttr, Attr, Attrs};
 
 struct ProcessedAttrs {
@@ -58,6 +58,8 @@ impl AssignProperty {
             let mut watch = AssignPropertyAttr::None;
             let mut block_signals = Vec::with_capacity(0);
             let mut chain = None;
+            let mut async_span = None;
+            let mut bind_span = None;
 
             for attr in attrs.inner {
                 let span = attr.span();
@@ -94,6 +96,26 @@ impl AssignProperty {
                             return Err(attr_twice_error(span));
                         }
                     }
+                    Attr::AsyncWatch(_, skip_init, pending) => {
+                        if watch == AssignPropertyAttr::None {
+                            watch = AssignPropertyAttr::AsyncWatch {
+                                skip_init,
+                                pending,
+                                handle: parse_util::unique_ident_from_parts(["async_watch"]),
+                            };
+                            async_span = Some(span);
+                        } else {
+                            return Err(attr_twice_error(span));
+                        }
+                    }
+                    Attr::BindExpr(_, map) => {
+                        if watch == AssignPropertyAttr::None {
+                            watch = AssignPropertyAttr::BindExpr { map };
+                            bind_span = Some(span);
+                        } else {
+                            return Err(attr_twice_error(span));
+                        }
+                    }
                     Attr::BlockSignal(_, idents) => {
                         if block_signals.is_empty() {
                             block_signals = idents;
@@ -111,11 +133,29 @@ impl AssignProperty {
                     _ => {
                         return Err(Error::new(
                             attr.span(),
-                            "Properties can only have `watch`, `track` or `iterative` as attribute.",
+                            "Properties can only have `watch`, `track`, `async_watch`, `bind_expr` or `iterative` as attribute.",
                         ));
                     }
                 }
             }
+
+            if let Some(span) = bind_span {
+                if iterative || !block_signals.is_empty() || chain.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "`bind_expr` can't be combined with `iterate`, `block_signal` or `chain`.",
+                    ));
+                }
+            }
+
+            if let Some(span) = async_span {
+                if iterative || !block_signals.is_empty() {
+                    return Err(Error::new(
+                        span,
+                        "`async_watch` can't be combined with `iterate` or `block_signal`.",
+                    ));
+                }
+            }
             Ok(ProcessedAttrs {
                 watch,
                 iterative,
@@ -2,7 +2,10 @@ use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{bracketed, parenthesized, token, Error, Expr, Ident, Lit, LitStr, Path, Result, Token};
+use syn::{
+    bracketed, parenthesized, parse_quote, token, Error, Expr, Ident, Lit, LitStr, Path, Result,
+    Token,
+};
 
 use crate::widgets::{Attr, Attrs};
 
@@ -29,12 +32,22 @@ impl Parse for Attrs {
                         Attr::Watch(ident.clone(), None)
                     } else if ident == "track" {
                         Attr::Track(ident.clone(), None, None)
+                    } else if ident == "async_watch" {
+                        Attr::AsyncWatch(ident.clone(), None, None)
+                    } else if ident == "bind_expr" {
+                        Attr::BindExpr(ident.clone(), None)
                     } else if ident == "iterate" {
                         Attr::Iterate(ident.clone())
                     } else if ident == "template" {
                         Attr::Template(ident.clone())
                     } else if ident == "template_child" {
                         Attr::TemplateChild(ident.clone())
+                    } else if ident == "public" {
+                        Attr::Public(ident.clone())
+                    } else if ident == "object" {
+                        Attr::Object(ident.clone())
+                    } else if ident == "spellcheck" {
+                        Attr::Spellcheck(ident.clone(), Box::new(parse_quote! { "auto" }))
                     } else {
                         return Err(unexpected_attr_name(ident));
                     }
@@ -42,6 +55,13 @@ impl Parse for Attrs {
                     return Err(Error::new(path.span(), "Expected identifier."));
                 }
 
+            // Key bindings: `#[keys(Escape => Msg::Close)]`
+            } else if attr_tokens.peek(token::Paren) && path.is_ident("keys") {
+                let paren_input;
+                parenthesized!(paren_input in attr_tokens);
+                let ident = path.get_ident().unwrap().clone();
+                Attr::Keys(ident, Box::new(paren_input.parse()?))
+
             // List attribute: `#[name(item1, item2)]
             } else if attr_tokens.peek(token::Paren) {
                 let paren_input;
@@ -67,6 +87,16 @@ impl Parse for Attrs {
                     } else if ident == "track" {
                         let (skip_init, expr) = parse_track(&nested)?;
                         Attr::Track(ident.clone(), skip_init, expr.map(Box::new))
+                    } else if ident == "async_watch" {
+                        let (skip_init, pending) = parse_async_watch(&nested)?;
+                        Attr::AsyncWatch(ident.clone(), skip_init, pending.map(Box::new))
+                    } else if ident == "bind_expr" {
+                        let expr = expect_one_nested_expr(&nested)?;
+                        let map = expect_named_arg(expr, "map")?;
+                        Attr::BindExpr(ident.clone(), Some(Box::new(map)))
+                    } else if ident == "gesture" {
+                        let expr = expect_one_nested_expr(&nested)?;
+                        Attr::Gesture(ident.clone(), Box::new(expr.clone()))
                     } else if ident == "transition" {
                         let expr = expect_one_nested_expr(&nested)?;
                         let ident = expect_ident_from_expr(expr)?;
@@ -79,6 +109,20 @@ impl Parse for Attrs {
                         let expr = expect_one_nested_expr(&nested)?;
                         let path = expect_path_from_expr(expr)?;
                         Attr::Wrap(ident.clone(), path)
+                    } else if ident == "spellcheck" {
+                        let expr = expect_one_nested_expr(&nested)?;
+                        let lang = expect_named_arg(expr, "lang")?;
+                        Attr::Spellcheck(ident.clone(), Box::new(lang))
+                    } else if ident == "validate" {
+                        let (validator, message) = expect_two_nested_exprs(&nested)?;
+                        Attr::Validate(
+                            ident.clone(),
+                            Box::new(validator.clone()),
+                            Box::new(message.clone()),
+                        )
+                    } else if ident == "mask" {
+                        let expr = expect_one_nested_expr(&nested)?;
+                        Attr::Mask(ident.clone(), Box::new(expr.clone()))
                     } else if ident == "chain" {
                         let expr = expect_one_nested_expr(&nested)?;
                         Attr::Chain(ident.clone(), Box::new(expr.clone()))
@@ -150,7 +194,7 @@ fn expect_path_from_expr(expr: &Expr) -> Result<Path> {
     }
 }
 
-fn expect_ident_from_expr(expr: &Expr) -> Result<Ident> {
+pub(super) fn expect_ident_from_expr(expr: &Expr) -> Result<Ident> {
     if let Expr::Path(path) = expr {
         expect_ident_from_path(&path.path)
     } else {
@@ -177,6 +221,17 @@ fn expect_one_nested_expr(nested: &Punctuated<Expr, token::Comma>) -> Result<&Ex
     }
 }
 
+fn expect_two_nested_exprs(nested: &Punctuated<Expr, token::Comma>) -> Result<(&Expr, &Expr)> {
+    if nested.len() == 2 {
+        Ok((&nested[0], &nested[1]))
+    } else {
+        Err(Error::new(
+            nested.span(),
+            "Expected a validator and a message. Try this: `#[validate(Numeric::<u32>::new(), Msg::Changed)]`.",
+        ))
+    }
+}
+
 fn parse_track(nested: &Punctuated<Expr, token::Comma>) -> Result<(Option<Ident>, Option<Expr>)> {
     let len = nested.len();
     if len == 1 {
@@ -202,6 +257,48 @@ fn parse_track(nested: &Punctuated<Expr, token::Comma>) -> Result<(Option<Ident>
     }
 }
 
+/// Parse an argument like `name = value`.
+fn expect_named_arg(expr: &Expr, name: &str) -> Result<Expr> {
+    if let Expr::Assign(assign) = expr {
+        let ident = expect_ident_from_expr(&assign.left)?;
+        if ident == name {
+            return Ok(*assign.right.clone());
+        }
+    }
+    Err(Error::new(
+        expr.span(),
+        format!("Expected `{name} = VALUE`."),
+    ))
+}
+
+fn parse_async_watch(
+    nested: &Punctuated<Expr, token::Comma>,
+) -> Result<(Option<Ident>, Option<Expr>)> {
+    let mut skip_init = None;
+    let mut pending = None;
+
+    for expr in nested {
+        if let Some(skip_ident) = expr_to_skip_init_ident(expr) {
+            if skip_init.is_some() {
+                return Err(Error::new(expr.span(), "`skip_init` was already set."));
+            }
+            skip_init = Some(skip_ident);
+        } else if let Expr::Assign(_) = expr {
+            if pending.is_some() {
+                return Err(Error::new(expr.span(), "`pending` was already set."));
+            }
+            pending = Some(expect_named_arg(expr, "pending")?);
+        } else {
+            return Err(Error::new(
+                expr.span(),
+                "Expected `skip_init` or `pending = VALUE`.",
+            ));
+        }
+    }
+
+    Ok((skip_init, pending))
+}
+
 fn expr_to_skip_init_ident(expr: &Expr) -> Option<Ident> {
     if let Expr::Path(path) = &expr {
         if let Some(ident) = path.path.get_ident() {
@@ -0,0 +1,73 @@
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{Error, Expr, Result, Token};
+
+use super::attributes::expect_ident_from_expr;
+use crate::widgets::{Attr, Attrs, Gesture};
+
+impl Gesture {
+    pub(super) fn parse(input: ParseStream<'_>, attributes: Option<Attrs>) -> Result<Self> {
+        let arrow: Token![=>] = input.parse()?;
+
+        let mut gesture = None;
+        for attr in attributes.into_iter().flat_map(|attrs| attrs.inner) {
+            match attr {
+                Attr::Gesture(_, expr) if gesture.is_none() => gesture = Some(expr),
+                attr => {
+                    return Err(Error::new(
+                        attr.span(),
+                        "Expected exactly one `#[gesture(...)]` attribute.",
+                    ))
+                }
+            }
+        }
+        let Some(gesture) = gesture else {
+            return Err(Error::new(
+                arrow.span(),
+                "Expected a `#[gesture(...)]` attribute before `=>`.",
+            ));
+        };
+
+        let (kind, args) = match *gesture {
+            Expr::Call(call) => (expect_ident_from_expr(&call.func)?, call.args),
+            expr => (expect_ident_from_expr(&expr)?, Default::default()),
+        };
+
+        let phases: &[&str] = match kind.to_string().as_str() {
+            "click" => &["pressed", "released"],
+            "drag" => &["begin", "update", "end"],
+            "long_press" | "swipe" | "zoom" => &[],
+            _ => {
+                return Err(Error::new(
+                    kind.span(),
+                    "Expected `click`, `drag`, `long_press`, `swipe` or `zoom`.",
+                ))
+            }
+        };
+
+        let mut phase = None;
+        let mut options = Vec::new();
+        for arg in args {
+            if let Expr::Assign(assign) = arg {
+                let name = expect_ident_from_expr(&assign.left)?;
+                options.push((name, *assign.right));
+            } else {
+                let ident = expect_ident_from_expr(&arg)?;
+                if phase.is_some() || !phases.iter().any(|phase| ident == phase) {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("Expected one of {phases:?} or an option like `button = 3`."),
+                    ));
+                }
+                phase = Some(ident);
+            }
+        }
+
+        Ok(Self {
+            kind,
+            phase,
+            options,
+            msg: input.parse()?,
+        })
+    }
+}
@@ -2,6 +2,7 @@ mod assign_property;
 mod attributes;
 mod conditional_branches;
 mod conditional_widget;
+mod gesture;
 mod if_branch;
 mod if_condition;
 mod match_arm;
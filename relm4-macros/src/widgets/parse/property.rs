@@ -3,8 +3,8 @@ use syn::spanned::Spanned;
 use syn::{token, Error, Ident, Token};
 
 use crate::widgets::{
-    parse_util, AssignProperty, Attrs, ConditionalWidget, ParseError, Property, PropertyName,
-    PropertyType, SignalHandler, Widget, WidgetFunc,
+    parse_util, AssignProperty, Attrs, ConditionalWidget, Gesture, ParseError, Property,
+    PropertyName, PropertyType, SignalHandler, Widget, WidgetFunc,
 };
 
 impl Property {
@@ -42,6 +42,16 @@ impl Property {
             });
         }
 
+        // `#[gesture(click)] => Msg::Clicked`
+        if input.peek(Token![=>]) {
+            let span = input.span();
+            let gesture = Gesture::parse(input, attributes.take())?;
+            return Ok(Property {
+                name: PropertyName::RelmContainerExtAssign(span),
+                ty: PropertyType::Gesture(gesture),
+            });
+        }
+
         // Parse path, ident or function
         let func = WidgetFunc::parse(input)?;
 
@@ -58,6 +58,12 @@ impl TopLevelWidget {
                 ref_token: None,
                 deref_token: None,
                 returned_widget: None,
+                keys: None,
+                spellcheck: None,
+                validate: None,
+                mask: None,
+                public: false,
+                object: false,
             },
         };
 
@@ -5,6 +5,7 @@ use syn::token::{And, Star};
 use syn::{Error, Expr, Ident, Path, Token};
 
 use crate::args::Args;
+use crate::key_map::KeyBindings;
 use crate::widgets::parse_util::{self, attr_twice_error};
 use crate::widgets::{
     Attr, Attrs, ParseError, Properties, PropertyType, Widget, WidgetAttr, WidgetFunc,
@@ -19,6 +20,12 @@ type AttributeInfo = (
     Option<Ident>,
     Option<Path>,
     WidgetTemplateAttr,
+    Option<Box<KeyBindings>>,
+    Option<Box<Expr>>,
+    Option<Box<(Expr, Expr)>>,
+    Option<Box<Expr>>,
+    Option<Ident>,
+    Option<Ident>,
 );
 
 impl Widget {
@@ -27,8 +34,19 @@ impl Widget {
         attributes: Option<Attrs>,
         args: Option<Args<Expr>>,
     ) -> Result<Self, ParseError> {
-        let (attr, doc_attr, new_name, assign_wrapper, template_attr) =
-            Self::process_attributes(attributes)?;
+        let (
+            attr,
+            doc_attr,
+            new_name,
+            assign_wrapper,
+            template_attr,
+            keys,
+            spellcheck,
+            validate,
+            mask,
+            public,
+            object,
+        ) = Self::process_attributes(attributes)?;
         // Check if first token is `mut`
         let mutable = input.parse().ok();
 
@@ -69,6 +87,16 @@ impl Widget {
             (func.snake_case_name(), false)
         };
 
+        if let Some(public) = &public {
+            if !name_assigned_by_user {
+                return Err(Error::new(
+                    public.span(),
+                    "The `public` attribute requires a widget name because generated names aren't stable.",
+                )
+                .into());
+            }
+        }
+
         let returned_widget = if input.peek(Token![->]) {
             let _arrow: Token![->] = input.parse()?;
             Some(input.parse()?)
@@ -92,6 +120,12 @@ impl Widget {
             ref_token,
             deref_token,
             returned_widget,
+            keys,
+            spellcheck,
+            validate,
+            mask,
+            public: public.is_some(),
+            object: object.is_some(),
         })
     }
 
@@ -100,8 +134,19 @@ impl Widget {
         func: WidgetFunc,
         attributes: Option<Attrs>,
     ) -> Result<Self, ParseError> {
-        let (attr, doc_attr, new_name, assign_wrapper, template_attr) =
-            Self::process_attributes(attributes)?;
+        let (
+            attr,
+            doc_attr,
+            new_name,
+            assign_wrapper,
+            template_attr,
+            keys,
+            spellcheck,
+            validate,
+            mask,
+            public,
+            object,
+        ) = Self::process_attributes(attributes)?;
 
         if let Some(wrapper) = assign_wrapper {
             return Err(Error::new(
@@ -134,6 +179,16 @@ impl Widget {
             (func.snake_case_name(), false)
         };
 
+        if let Some(public) = &public {
+            if !name_assigned_by_user {
+                return Err(Error::new(
+                    public.span(),
+                    "The `public` attribute requires a widget name because generated names aren't stable.",
+                )
+                .into());
+            }
+        }
+
         let ref_token = Some(And::default());
 
         Self::check_props(&properties, &template_attr)?;
@@ -152,6 +207,12 @@ impl Widget {
             ref_token,
             deref_token: None,
             returned_widget: None,
+            keys,
+            spellcheck,
+            validate,
+            mask,
+            public: public.is_some(),
+            object: object.is_some(),
         })
     }
 
@@ -185,6 +246,12 @@ impl Widget {
             let mut name = None;
             let mut assign_wrapper = None;
             let mut template_attr = WidgetTemplateAttr::None;
+            let mut keys = None;
+            let mut spellcheck = None;
+            let mut validate = None;
+            let mut mask = None;
+            let mut public = None;
+            let mut object = None;
 
             for attr in attrs.inner {
                 let span = attr.span();
@@ -234,18 +301,93 @@ impl Widget {
                         }
                         template_attr = WidgetTemplateAttr::TemplateChild;
                     }
+                    Attr::Keys(_, bindings) => {
+                        if keys.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        keys = Some(bindings);
+                    }
+                    Attr::Spellcheck(_, lang) => {
+                        if spellcheck.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        spellcheck = Some(lang);
+                    }
+                    Attr::Validate(_, validator, message) => {
+                        if validate.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        validate = Some(Box::new((*validator, *message)));
+                    }
+                    Attr::Mask(_, mask_expr) => {
+                        if mask.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        mask = Some(mask_expr);
+                    }
+                    Attr::Public(ident) => {
+                        if public.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        public = Some(ident);
+                    }
+                    Attr::Object(ident) => {
+                        if object.is_some() {
+                            return Err(attr_twice_error(span).into());
+                        }
+                        object = Some(ident);
+                    }
                     _ => {
                         return Err(Error::new(
                             attr.span(),
-                            "Widgets can only have docs and `local`, `local_ref`, `wrap`, `name`, `template`, `template_child` or `root` as attribute.",
+                            "Widgets can only have docs and `local`, `local_ref`, `wrap`, `name`, `template`, `template_child`, `keys`, `spellcheck`, `validate`, `mask`, `public`, `object` or `root` as attribute.",
                         ).into());
                     }
                 }
             }
 
-            Ok((widget_attr, doc_attr, name, assign_wrapper, template_attr))
+            if let Some(object) = &object {
+                if template_attr != WidgetTemplateAttr::None
+                    || keys.is_some()
+                    || spellcheck.is_some()
+                    || validate.is_some()
+                    || mask.is_some()
+                {
+                    return Err(Error::new(
+                        object.span(),
+                        "Objects can't be combined with the `template`, `template_child`, `keys`, `spellcheck`, `validate` or `mask` attributes.",
+                    )
+                    .into());
+                }
+            }
+
+            Ok((
+                widget_attr,
+                doc_attr,
+                name,
+                assign_wrapper,
+                template_attr,
+                keys,
+                spellcheck,
+                validate,
+                mask,
+                public,
+                object,
+            ))
         } else {
-            Ok((WidgetAttr::None, None, None, None, WidgetTemplateAttr::None))
+            Ok((
+                WidgetAttr::None,
+                None,
+                None,
+                None,
+                WidgetTemplateAttr::None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ))
         }
     }
 
@@ -70,8 +70,10 @@ impl WidgetAttr {
 impl AssignPropertyAttr {
     pub(super) fn should_skip_init(&self) -> bool {
         match self {
-            Self::None => false,
-            Self::Watch { skip_init } | Self::Track { skip_init, .. } => skip_init.is_some(),
+            Self::None | Self::BindExpr { .. } => false,
+            Self::Watch { skip_init }
+            | Self::Track { skip_init, .. }
+            | Self::AsyncWatch { skip_init, .. } => skip_init.is_some(),
         }
     }
 }
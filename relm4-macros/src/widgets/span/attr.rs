@@ -13,7 +13,16 @@ impl Attr {
             | Self::Iterate(ident)
             | Self::Watch(ident, _)
             | Self::Track(ident, _, _)
+            | Self::AsyncWatch(ident, _, _)
+            | Self::BindExpr(ident, _)
             | Self::BlockSignal(ident, _)
+            | Self::Gesture(ident, _)
+            | Self::Keys(ident, _)
+            | Self::Spellcheck(ident, _)
+            | Self::Validate(ident, _, _)
+            | Self::Mask(ident, _)
+            | Self::Object(ident)
+            | Self::Public(ident)
             | Self::Name(ident, _)
             | Self::Transition(ident, _)
             | Self::Chain(ident, _)
@@ -0,0 +1,86 @@
+use relm4::actions::{ActionGroupPlus, RelmAction, RelmActionGroup};
+use relm4::gtk::prelude::*;
+use relm4::{
+    gtk, Component, ComponentController, ComponentParts, ComponentSender, SimpleComponent,
+};
+
+relm4::new_action_group!(WindowActionGroup, "win");
+relm4::new_stateless_action!(SaveAction, WindowActionGroup, "save");
+relm4::new_stateless_action!(UndoAction, WindowActionGroup, "undo");
+
+#[derive(Default)]
+struct App {
+    dirty: bool,
+    history: Vec<String>,
+}
+
+#[derive(Debug)]
+enum AppMsg {
+    Edit(String),
+    Save,
+}
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            gtk::Label {},
+        }
+    }
+
+    actions_enabled! {
+        SaveAction => model.dirty,
+        UndoAction => !model.history.is_empty(),
+    }
+
+    fn init(_init: (), root: Self::Root, _sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self::default();
+
+        let widgets = view_output!();
+
+        let mut group = RelmActionGroup::<WindowActionGroup>::new();
+        group.add_action(RelmAction::<SaveAction>::new_stateless(|_| ()));
+        group.add_action(RelmAction::<UndoAction>::new_stateless(|_| ()));
+        root.insert_relm_action_group(group);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: AppMsg, _sender: ComponentSender<Self>) {
+        match msg {
+            AppMsg::Edit(text) => {
+                self.dirty = true;
+                self.history.push(text);
+            }
+            AppMsg::Save => self.dirty = false,
+        }
+    }
+}
+
+fn process_events() {
+    let context = gtk::glib::MainContext::default();
+    while context.iteration(false) {}
+}
+
+#[test]
+fn actions_follow_model() {
+    gtk::init().unwrap();
+
+    let app = App::builder().launch(()).detach();
+    let root = app.widget().clone();
+    let is_enabled = |root: &gtk::Window| root.relm_action::<SaveAction>().unwrap().is_enabled();
+
+    app.emit(AppMsg::Edit("text".into()));
+    process_events();
+    assert!(is_enabled(&root));
+    assert!(root.relm_action::<UndoAction>().unwrap().is_enabled());
+
+    app.emit(AppMsg::Save);
+    process_events();
+    assert!(!is_enabled(&root));
+    assert!(root.relm_action::<UndoAction>().unwrap().is_enabled());
+}
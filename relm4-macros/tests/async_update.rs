@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use gtk::prelude::{BoxExt, ButtonExt, GtkWindowExt, OrientableExt, WidgetExt};
+use relm4::{gtk, Component, ComponentParts, ComponentSender, RelmWidgetExt};
+
+#[derive(Default)]
+struct App {
+    loading: bool,
+    text: String,
+    history: Vec<String>,
+}
+
+#[derive(Debug)]
+enum AppMsg {
+    Load(u32),
+    Save,
+    Wait,
+}
+
+async fn fetch(id: u32) -> Option<String> {
+    relm4::tokio::time::sleep(Duration::from_millis(10)).await;
+    Some(format!("Item {id}"))
+}
+
+#[relm4_macros::component]
+impl Component for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Async update"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 5,
+
+                append = &gtk::Button {
+                    set_label: "Load",
+                    connect_clicked => AppMsg::Load(1),
+                },
+                append = &gtk::Label {
+                    #[watch]
+                    set_label: &model.text,
+                    #[watch]
+                    set_sensitive: !model.loading,
+                }
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self::default();
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    #[update(async)]
+    fn update(&mut self, msg: AppMsg, sender: ComponentSender<Self>, root: &Self::Root) {
+        match msg {
+            AppMsg::Load(id) => {
+                self.loading = true;
+                let Some(text) = fetch(id).await else {
+                    self.loading = false;
+                    return;
+                };
+                let previous = std::mem::replace(&mut self.text, text);
+                self.history.push(previous);
+                if self.history.len() > 1 {
+                    let next: Option<String> = fetch(id + 1).await;
+                    self.history.extend(next);
+                    self.loading = false;
+                } else {
+                    self.loading = false;
+                }
+            }
+            AppMsg::Save => {
+                let text = self.text.clone();
+                relm4::tokio::time::sleep(Duration::from_millis(text.len() as u64)).await;
+                root.set_title(Some(&text));
+                sender.input(AppMsg::Wait);
+            }
+            AppMsg::Wait => relm4::tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use gtk::prelude::{BoxExt, ButtonExt, GtkWindowExt, OrientableExt};
+use relm4::{gtk, ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent};
+
+#[derive(Default)]
+struct App {
+    counter: u8,
+}
+
+#[derive(Debug)]
+enum AppMsg {
+    Increment,
+}
+
+async fn fetch_width(counter: u8) -> i32 {
+    relm4::tokio::time::sleep(Duration::from_millis(100)).await;
+    i32::from(counter)
+}
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = u8;
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Async watch"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 5,
+                set_spacing: 5,
+
+                append = &gtk::Button {
+                    set_label: "Increment",
+                    connect_clicked => AppMsg::Increment,
+                },
+                append = &gtk::Label {
+                    #[async_watch(pending = -1)]
+                    set_width_chars: fetch_width(model.counter),
+                    #[async_watch(skip_init)]
+                    set_label: {
+                        let counter = model.counter;
+                        async move { if counter % 2 == 0 { "even" } else { "odd" } }
+                    },
+                    #[async_watch]
+                    set_max_width_chars?: async { Some(10) },
+                }
+            },
+        }
+    }
+
+    fn init(
+        counter: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Self { counter };
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: AppMsg, _sender: ComponentSender<Self>) {
+        match msg {
+            AppMsg::Increment => {
+                self.counter = self.counter.wrapping_add(1);
+            }
+        }
+    }
+}
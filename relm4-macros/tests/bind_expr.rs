@@ -0,0 +1,46 @@
+use gtk::prelude::{BoxExt, GtkWindowExt, OrientableExt};
+use relm4::{gtk, ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent};
+
+struct App;
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = ();
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Bind expression"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 5,
+                set_spacing: 5,
+
+                #[name = "entry"]
+                gtk::Entry {},
+
+                gtk::Label {
+                    #[bind_expr]
+                    set_label: entry["buffer"]["text"],
+                    #[bind_expr(map = |text: String| text.len() as i32)]
+                    set_width_chars: entry["text"],
+                },
+
+                gtk::Label {
+                    #[bind_expr]
+                    set_label: gtk::ConstantExpression::new("constant"),
+                },
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, _sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+}
@@ -0,0 +1,87 @@
+use gtk::prelude::{GtkWindowExt, OrientableExt};
+use relm4::binding::Bindable;
+use relm4::{gtk, Component, ComponentParts, ComponentSender};
+
+#[derive(Default, relm4_macros::Bindable)]
+struct Settings {
+    #[bind]
+    user_name: String,
+    #[bind]
+    notifications: bool,
+    #[bind(widget = retries_button)]
+    retries: i32,
+    #[bind]
+    scale: f64,
+    #[allow(dead_code)]
+    dirty: bool,
+}
+
+#[derive(Debug)]
+enum Msg {
+    FieldChanged(SettingsField),
+}
+
+#[relm4_macros::component]
+impl Component for Settings {
+    type Init = ();
+    type Input = Msg;
+    type Output = ();
+    type CommandOutput = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Settings"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[name = "user_name"]
+                gtk::Entry {},
+                #[name = "notifications"]
+                gtk::Switch {},
+                #[name = "retries_button"]
+                gtk::SpinButton::with_range(0.0, 10.0, 1.0) {},
+                #[name = "scale"]
+                gtk::SpinButton::with_range(0.5, 4.0, 0.5) {},
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self::default();
+
+        let widgets = view_output!();
+        model.bind_to_widgets(&widgets, sender.input_sender(), Msg::FieldChanged);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Msg,
+        _sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            Msg::FieldChanged(field) => {
+                self.update_field(field, widgets);
+                // Clamp the value and show it again.
+                self.retries = self.retries.min(5);
+                self.sync_widgets(widgets);
+            }
+        }
+    }
+}
+
+fn assert_bindable<B: Bindable<Widgets = SettingsWidgets, Field = SettingsField>>() {}
+
+#[test]
+fn bindable_fields() {
+    assert_bindable::<Settings>();
+    assert_ne!(SettingsField::UserName, SettingsField::Retries);
+    assert_eq!(
+        format!("{:?}", SettingsField::Notifications),
+        "Notifications"
+    );
+}
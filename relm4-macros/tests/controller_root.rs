@@ -0,0 +1,39 @@
+use gtk::prelude::GestureSingleExt;
+use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+struct ClickModel;
+
+// The root of this component is an event controller instead of a widget.
+#[relm4_macros::component]
+impl SimpleComponent for ClickModel {
+    type Init = u32;
+    type Input = ();
+    type Output = ();
+
+    view! {
+        gtk::GestureClick {
+            set_button: button,
+
+            connect_pressed[sender] => move |_, _, _, _| {
+                sender.output(()).ok();
+            },
+        }
+    }
+
+    fn init(
+        button: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Self;
+        let widgets = view_output!();
+        ComponentParts { model, widgets }
+    }
+}
+
+fn assert_root<C: relm4::Component<Root = gtk::GestureClick>>() {}
+
+#[test]
+fn assert_controller_root() {
+    assert_root::<ClickModel>();
+}
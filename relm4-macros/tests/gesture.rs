@@ -0,0 +1,58 @@
+use gtk::prelude::{GtkWindowExt, OrientableExt};
+use relm4::{gtk, ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent};
+
+#[derive(Default)]
+struct App;
+
+#[derive(Debug)]
+enum AppMsg {
+    ContextMenu(f64, f64),
+    DoubleClick(i32),
+    Dragged(f64, f64),
+    Swiped(f64),
+    Zoomed(f64),
+}
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Gestures"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 5,
+
+                #[gesture(click(button = 3))]
+                => AppMsg::ContextMenu(x, y),
+                #[gesture(click(released))]
+                => AppMsg::DoubleClick(n_press),
+                #[gesture(drag(end, button = 1))]
+                => AppMsg::Dragged(x, y),
+                #[gesture(swipe)]
+                => AppMsg::Swiped(x),
+                #[gesture(zoom)]
+                => AppMsg::Zoomed(scale),
+
+                gtk::Label {
+                    #[gesture(long_press(delay_factor = 2.0))]
+                    => AppMsg::ContextMenu(x, y),
+                },
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, _msg: AppMsg, _sender: ComponentSender<Self>) {}
+}
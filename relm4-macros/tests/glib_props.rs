@@ -0,0 +1,52 @@
+use relm4::binding::GlibProps;
+use relm4::gtk::prelude::ObjectExt;
+
+#[derive(relm4_macros::GlibProps)]
+struct App {
+    #[prop]
+    counter: u8,
+    #[prop]
+    user_name: String,
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+}
+
+#[test]
+fn glib_props() {
+    let mut app = App {
+        counter: 1,
+        user_name: "Relm4".into(),
+        cache: Vec::new(),
+    };
+
+    let props = app.props();
+    assert_eq!(props.counter(), 1);
+    assert_eq!(props.property::<String>("user-name"), "Relm4");
+
+    app.counter = 2;
+    app.sync_props(&props);
+    assert_eq!(props.counter(), 2);
+}
+
+mod other {
+    #[derive(relm4_macros::GlibProps)]
+    pub(super) struct App {
+        #[prop]
+        pub(super) visible: bool,
+    }
+}
+
+#[test]
+fn same_name_in_other_module() {
+    let app = App {
+        counter: 1,
+        user_name: "Relm4".into(),
+        cache: Vec::new(),
+    };
+    let other = other::App { visible: true };
+
+    let props = app.props();
+    let other_props = other.props();
+    assert_ne!(props.type_(), other_props.type_());
+    assert!(other_props.visible());
+}
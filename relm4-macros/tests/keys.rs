@@ -0,0 +1,50 @@
+use gtk::prelude::{GtkWindowExt, OrientableExt, WidgetExt};
+use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+#[derive(Default)]
+struct App;
+
+#[derive(Debug)]
+enum AppMsg {
+    Close,
+    Submit,
+    ZoomIn,
+    Save,
+}
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        #[keys(Escape => AppMsg::Close, "<Ctrl>s" => AppMsg::Save)]
+        gtk::Window {
+            set_title: Some("Keys"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[keys("Ctrl+Return" => AppMsg::Submit, "Ctrl++" => AppMsg::ZoomIn)]
+                gtk::Entry {},
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        let key_map = relm4::keymap! {
+            F5 => AppMsg::Save,
+            "Ctrl+Shift+W" => AppMsg::Close,
+        };
+        root.add_controller(key_map.into_controller(sender.input_sender()));
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, _msg: AppMsg, _sender: ComponentSender<Self>) {}
+}
@@ -0,0 +1,78 @@
+use gtk::prelude::{GestureSingleExt, GtkWindowExt, OrientableExt};
+use relm4::gtk::gio;
+use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+#[derive(Default)]
+struct App;
+
+#[derive(Debug)]
+enum AppMsg {
+    ContextMenu,
+}
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = AppMsg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Objects"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[object]
+                gtk::GestureClick {
+                    set_button: 3,
+                    connect_pressed[sender] => move |_, _, _, _| {
+                        sender.input(AppMsg::ContextMenu);
+                    },
+                },
+
+                #[object]
+                #[name = "size_group"]
+                gtk::SizeGroup {
+                    set_mode: gtk::SizeGroupMode::Horizontal,
+                    add_widget: &label,
+                },
+
+                #[name = "label"]
+                gtk::Label {
+                    set_label: "Volume",
+                },
+
+                gtk::Scale {
+                    #[object]
+                    #[name = "adjustment"]
+                    gtk::Adjustment::new(5.0, 0.0, 10.0, 1.0, 1.0, 0.0) {},
+                },
+
+                gtk::MenuButton {
+                    #[object]
+                    gio::Menu {
+                        append[Some("app.quit")]: Some("Quit"),
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, _msg: AppMsg, _sender: ComponentSender<Self>) {}
+}
+
+// Named objects are stored in the widgets struct like widgets.
+#[allow(dead_code)]
+fn access_objects(widgets: &AppWidgets) {
+    let _: &gtk::SizeGroup = &widgets.size_group;
+    let _: &gtk::Adjustment = &widgets.adjustment;
+}
@@ -0,0 +1,111 @@
+use relm4::persist::serde_json::{json, Value};
+use relm4::persist::{self, PersistError, Persistent};
+
+#[derive(Debug, PartialEq, relm4_macros::Persistent)]
+#[persistent(version = 4, migrate = migrate)]
+struct Session {
+    user: String,
+    #[since(2)]
+    tabs: Vec<String>,
+    #[since(3, default = default_zoom)]
+    zoom: f64,
+    #[persistent(skip)]
+    dirty: bool,
+}
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+fn migrate(version: u32, data: &mut Value) -> Result<(), PersistError> {
+    // Version 3 called the user `name`.
+    if version <= 3 {
+        if let Some(name) = data.as_object_mut().and_then(|data| data.remove("name")) {
+            data["user"] = name;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn round_trip() {
+    let session = Session {
+        user: "Ada".into(),
+        tabs: vec!["inbox".into()],
+        zoom: 1.5,
+        dirty: true,
+    };
+    let text = persist::to_string(&session).unwrap();
+    let restored: Session = persist::from_str(&text).unwrap();
+
+    assert_eq!(
+        restored,
+        Session {
+            dirty: false,
+            ..session
+        }
+    );
+    assert_eq!(Session::VERSION, 4);
+}
+
+#[test]
+fn old_versions() {
+    let text = json!({ "version": 1, "data": { "name": "Ada" } }).to_string();
+    let restored: Session = persist::from_str(&text).unwrap();
+    assert_eq!(
+        restored,
+        Session {
+            user: "Ada".into(),
+            tabs: Vec::new(),
+            zoom: 1.0,
+            dirty: false,
+        }
+    );
+}
+
+#[test]
+fn errors() {
+    let newer = json!({ "version": 5, "data": {} }).to_string();
+    assert!(matches!(
+        persist::from_str::<Session>(&newer),
+        Err(PersistError::UnsupportedVersion {
+            found: 5,
+            supported: 4
+        })
+    ));
+
+    let missing = json!({ "version": 4, "data": { "user": "Ada", "zoom": 1.0 } }).to_string();
+    assert!(matches!(
+        persist::from_str::<Session>(&missing),
+        Err(PersistError::MissingField("tabs"))
+    ));
+
+    let invalid = json!({ "version": 4, "data": { "user": 1, "tabs": [], "zoom": 1.0 } });
+    assert!(matches!(
+        persist::from_str::<Session>(&invalid.to_string()),
+        Err(PersistError::Field { name: "user", .. })
+    ));
+
+    assert!(matches!(
+        persist::from_str::<Session>("not json"),
+        Err(PersistError::Json(_))
+    ));
+}
+
+#[test]
+fn malformed_old_data() {
+    // Old data that isn't an object is rejected before it reaches the migration.
+    for data in [json!("Ada"), json!(["Ada"]), json!(null)] {
+        let text = json!({ "version": 1, "data": data }).to_string();
+        assert!(matches!(
+            persist::from_str::<Session>(&text),
+            Err(PersistError::Json(_))
+        ));
+    }
+
+    let text = json!({ "version": 1, "data": { "name": ["Ada"] } }).to_string();
+    assert!(matches!(
+        persist::from_str::<Session>(&text),
+        Err(PersistError::Field { name: "user", .. })
+    ));
+}
@@ -0,0 +1,73 @@
+use relm4::gtk;
+
+mod app {
+    use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+    pub struct AppModel;
+
+    #[relm4_macros::component(pub, pub_widgets)]
+    impl SimpleComponent for AppModel {
+        type Init = ();
+        type Input = ();
+        type Output = ();
+
+        view! {
+            gtk::Window {
+                gtk::Box {
+                    #[name(label)]
+                    gtk::Label {},
+
+                    gtk::Button {},
+                }
+            }
+        }
+
+        fn init(
+            _: Self::Init,
+            root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            let model = Self;
+            let widgets = view_output!();
+            ComponentParts { model, widgets }
+        }
+    }
+}
+
+mod single {
+    use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+    pub struct SingleModel;
+
+    #[relm4_macros::component(pub)]
+    impl SimpleComponent for SingleModel {
+        type Init = ();
+        type Input = ();
+        type Output = ();
+
+        view! {
+            gtk::Window {
+                #[public]
+                #[name(entry)]
+                gtk::Entry {},
+            }
+        }
+
+        fn init(
+            _: Self::Init,
+            root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            let model = Self;
+            let widgets = view_output!();
+            ComponentParts { model, widgets }
+        }
+    }
+}
+
+// The named widgets can be accessed from outside the modules.
+#[allow(dead_code)]
+fn access_widgets(app: &app::AppModelWidgets, single: &single::SingleModelWidgets) {
+    let _: &gtk::Label = &app.label;
+    let _: &gtk::Entry = &single.entry;
+}
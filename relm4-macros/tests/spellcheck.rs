@@ -0,0 +1,36 @@
+use gtk::prelude::{GtkWindowExt, OrientableExt};
+use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+#[derive(Default)]
+struct App;
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = ();
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Spell checking"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[spellcheck]
+                gtk::Entry {},
+
+                #[spellcheck(lang = "en_US")]
+                gtk::TextView {},
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, _sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+}
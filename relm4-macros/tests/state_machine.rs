@@ -0,0 +1,42 @@
+use relm4::fsm::{InvalidTransition, StateMachine};
+
+#[derive(Debug, PartialEq, relm4_macros::StateMachine)]
+enum Download {
+    #[transitions(Running)]
+    Idle,
+    #[transitions(Paused, Finished, Failed)]
+    Running {
+        progress: f64,
+    },
+    #[transitions(Running, Failed)]
+    Paused(f64),
+    Finished,
+    #[transitions(*)]
+    Failed(String),
+}
+
+#[test]
+fn transitions() {
+    let mut state = Download::Idle;
+    assert_eq!(state.state_name(), "Idle");
+
+    state.to_running(0.5).unwrap();
+    state.to_paused(0.5).unwrap();
+    assert_eq!(
+        state.to_finished(),
+        Err(InvalidTransition {
+            from: "Paused",
+            to: "Finished"
+        })
+    );
+    assert!(state.is_paused());
+
+    state.to_failed("Network error".into()).unwrap();
+    state.to_idle().unwrap();
+    state.to_running(0.0).unwrap();
+    state.to_finished().unwrap();
+
+    assert!(!state.can_transition_to(&Download::Idle));
+    assert!(state.transition(Download::Idle).is_err());
+    assert_eq!(state, Download::Finished);
+}
@@ -0,0 +1,44 @@
+use gtk::prelude::{GtkWindowExt, OrientableExt};
+use relm4::validation::{InputMask, MaxLength, Numeric, ValidationError, Validator};
+use relm4::{gtk, ComponentParts, ComponentSender, SimpleComponent};
+
+#[derive(Debug)]
+enum Msg {
+    Age(Result<u8, ValidationError>),
+    Phone(Result<String, ValidationError>),
+}
+
+#[derive(Default)]
+struct App;
+
+#[relm4_macros::component]
+impl SimpleComponent for App {
+    type Init = ();
+    type Input = Msg;
+    type Output = ();
+
+    view! {
+        gtk::Window {
+            set_title: Some("Validation"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[validate(MaxLength::new(3).then(Numeric::<u8>::new()), Msg::Age)]
+                gtk::Entry {},
+
+                #[mask(InputMask::phone())]
+                #[validate(InputMask::phone(), Msg::Phone)]
+                gtk::Entry {},
+            },
+        }
+    }
+
+    fn init(_init: (), root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+}
@@ -0,0 +1,62 @@
+use relm4::gtk::glib::variant::{FromVariant, StaticVariantType, ToVariant};
+
+#[derive(Debug, PartialEq, relm4_macros::VariantValue)]
+enum Mode {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, PartialEq, relm4_macros::VariantValue)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, relm4_macros::VariantValue)]
+struct Point(i32, i32);
+
+#[derive(Debug, PartialEq, relm4_macros::VariantValue)]
+struct Unit;
+
+#[derive(Debug, PartialEq, relm4_macros::VariantValue)]
+struct State {
+    mode: Mode,
+    shapes: Vec<Shape>,
+    origin: Option<Point>,
+    unit: Unit,
+}
+
+#[test]
+fn variant_types() {
+    assert_eq!(Mode::static_variant_type().as_str(), "s");
+    assert_eq!(Shape::static_variant_type().as_str(), "(sv)");
+    assert_eq!(Point::static_variant_type().as_str(), "(ii)");
+    assert_eq!(Unit::static_variant_type().as_str(), "()");
+    assert_eq!(State::static_variant_type().as_str(), "(sa(sv)m(ii)())");
+}
+
+#[test]
+fn round_trip() {
+    let state = State {
+        mode: Mode::Dark,
+        shapes: vec![
+            Shape::Empty,
+            Shape::Circle(2.0),
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+        ],
+        origin: Some(Point(-1, 1)),
+        unit: Unit,
+    };
+
+    let variant = state.to_variant();
+    assert_eq!(variant.type_(), State::static_variant_type());
+    assert_eq!(State::from_variant(&variant), Some(state));
+
+    assert_eq!(Mode::from_variant(&"Light".to_variant()), Some(Mode::Light));
+    assert_eq!(Mode::from_variant(&"Dim".to_variant()), None);
+    assert_eq!(Point::from_variant(&(1u32, 2u32).to_variant()), None);
+}
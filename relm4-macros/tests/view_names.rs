@@ -0,0 +1,27 @@
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+use relm4::test::UserSim;
+
+#[test]
+fn find_named_widgets() {
+    gtk::init().unwrap();
+
+    let local_label = &gtk::Label::new(None);
+    relm4_macros::view! {
+        root = gtk::Box {
+            #[name = "entry"]
+            gtk::Entry {},
+            #[local_ref]
+            local_label -> gtk::Label {},
+            gtk::Button {
+                set_widget_name: "button",
+            },
+        }
+    }
+
+    let sim = UserSim::new(&root);
+    assert_eq!(sim.find("entry"), Some(entry.upcast()));
+    assert_eq!(sim.find("local_label"), Some(local_label.clone().upcast()));
+    assert!(sim.find("button").is_some());
+    assert!(sim.find("missing").is_none());
+}
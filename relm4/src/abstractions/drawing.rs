@@ -1,35 +1,157 @@
 //! Utility to help drawing on a [`gtk::DrawingArea`] in a Relm4 application.
 //! Create a [`DrawHandler`], initialize it, and get its context when handling a message (that could be
 //! sent from the draw signal).
+//!
+//! Redraws are coalesced, so the [`gtk::DrawingArea`] is painted at most once per frame
+//! regardless of how many contexts were used in between.
 
 use std::cell::RefCell;
 use std::ops::Deref;
 use std::rc::Rc;
 
-use gtk::cairo::{Context, Format, ImageSurface};
+use gtk::cairo::{Context, Error, Format, ImageSurface, Operator, RectangleInt, Region};
 use gtk::prelude::{DrawingAreaExtManual, WidgetExt};
 
+#[derive(Debug)]
+struct State {
+    /// The surface the last [`DrawContext`] has drawn on.
+    surface: ImageSurface,
+    /// The surface that is presented if the cache is enabled.
+    cache: Option<ImageSurface>,
+    /// The regions that changed since the last frame in logical pixels.
+    damage: Region,
+    /// Whether the whole surface changed since the last frame.
+    full_damage: bool,
+    /// Whether a redraw was already queued for the next frame.
+    draw_queued: bool,
+}
+
 #[derive(Clone, Debug)]
 struct Surface {
-    surface: Rc<RefCell<ImageSurface>>,
+    state: Rc<RefCell<State>>,
 }
 
 impl Surface {
     fn new(surface: ImageSurface) -> Self {
         Self {
-            surface: Rc::new(RefCell::new(surface)),
+            state: Rc::new(RefCell::new(State {
+                surface,
+                cache: None,
+                damage: Region::create(),
+                full_damage: true,
+                draw_queued: false,
+            })),
+        }
+    }
+
+    /// Store the surface and its damage and queue a redraw
+    /// unless one is already queued for the next frame.
+    fn set(&self, surface: &ImageSurface, damage: &Region, drawing_area: &gtk::DrawingArea) {
+        let mut state = self.state.borrow_mut();
+        if !same_surface(&state.surface, surface) {
+            state.full_damage = true;
+        }
+        state.surface = surface.clone();
+
+        if damage.is_empty() {
+            state.full_damage = true;
+        } else if let Err(error) = state.damage.union(damage) {
+            tracing::error!("Cannot add damage: {:?}", error);
+            state.full_damage = true;
+        }
+
+        if !state.draw_queued {
+            state.draw_queued = true;
+            drawing_area.queue_draw();
         }
     }
 
-    fn get(&self) -> ImageSurface {
-        self.surface.borrow().clone()
+    fn set_cache_enabled(&self, enabled: bool) {
+        let mut state = self.state.borrow_mut();
+        if !enabled {
+            state.cache = None;
+        } else if state.cache.is_none() {
+            state.cache = Some(state.surface.clone());
+            // Force a copy on the next frame.
+            state.full_damage = true;
+        }
     }
 
-    fn set(&self, surface: &ImageSurface) {
-        *self.surface.borrow_mut() = surface.clone();
+    fn cache_enabled(&self) -> bool {
+        self.state.borrow().cache.is_some()
+    }
+
+    /// Paint the surface on the `context` of the draw function.
+    fn draw(&self, context: &Context) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        state.draw_queued = false;
+
+        let source = if let Some(cache) = &mut state.cache {
+            let surface = &state.surface;
+            if (cache.width(), cache.height()) != (surface.width(), surface.height())
+                || same_surface(cache, surface)
+            {
+                match ImageSurface::create(Format::ARgb32, surface.width(), surface.height()) {
+                    Ok(new_cache) => {
+                        let (x_scale, y_scale) = surface.device_scale();
+                        new_cache.set_device_scale(x_scale, y_scale);
+                        *cache = new_cache;
+                        state.full_damage = true;
+                    }
+                    Err(error) => tracing::error!("Cannot resize cache surface: {:?}", error),
+                }
+            }
+
+            if let Err(error) = update_cache(cache, surface, &state.damage, state.full_damage) {
+                tracing::error!("Cannot update cache surface: {:?}", error);
+            }
+            cache.clone()
+        } else {
+            state.surface.clone()
+        };
+        state.damage = Region::create();
+        state.full_damage = false;
+
+        if let Err(error) = context.set_source_surface(&source, 0.0, 0.0) {
+            tracing::error!("Cannot set source surface: {:?}", error);
+        }
+
+        if let Err(error) = context.paint() {
+            tracing::error!("Cannot paint: {:?}", error);
+        }
     }
 }
 
+fn same_surface(a: &ImageSurface, b: &ImageSurface) -> bool {
+    a.to_raw_none() == b.to_raw_none()
+}
+
+/// Copy the damaged regions of `surface` to `cache`.
+fn update_cache(
+    cache: &ImageSurface,
+    surface: &ImageSurface,
+    damage: &Region,
+    full_damage: bool,
+) -> Result<(), Error> {
+    let context = Context::new(cache)?;
+    if !full_damage {
+        for index in 0..damage.num_rectangles() {
+            let rect = damage.rectangle(index);
+            context.rectangle(
+                f64::from(rect.x()),
+                f64::from(rect.y()),
+                f64::from(rect.width()),
+                f64::from(rect.height()),
+            );
+        }
+        context.clip();
+    }
+    context.set_source_surface(surface, 0.0, 0.0)?;
+    context.set_operator(Operator::Source);
+    context.paint()
+}
+
 #[derive(Debug)]
 /// Context returned by [`DrawHandler`] that stores a [`Context`] along
 /// with additional data required for drawing.
@@ -38,6 +160,7 @@ pub struct DrawContext {
     draw_surface: Surface,
     edit_surface: ImageSurface,
     drawing_area: gtk::DrawingArea,
+    damage: Region,
 }
 
 impl DrawContext {
@@ -51,6 +174,22 @@ impl DrawContext {
             draw_surface: draw_surface.clone(),
             edit_surface: edit_surface.clone(),
             drawing_area: drawing_area.clone(),
+            damage: Region::create(),
+        }
+    }
+
+    /// Mark a rectangle in pixels as changed.
+    ///
+    /// If no damage is marked, the whole surface is considered changed
+    /// once the context is dropped.
+    /// With an [enabled cache](DrawHandler::set_cache_enabled), only the
+    /// damaged regions are presented on the next frame.
+    pub fn damage(&self, x: i32, y: i32, width: i32, height: i32) {
+        if let Err(error) = self
+            .damage
+            .union_rectangle(&RectangleInt::new(x, y, width, height))
+        {
+            tracing::error!("Cannot add damage: {:?}", error);
         }
     }
 }
@@ -65,8 +204,8 @@ impl Deref for DrawContext {
 
 impl Drop for DrawContext {
     fn drop(&mut self) {
-        self.draw_surface.set(&self.edit_surface);
-        self.drawing_area.queue_draw();
+        self.draw_surface
+            .set(&self.edit_surface, &self.damage, &self.drawing_area);
     }
 }
 
@@ -93,22 +232,15 @@ impl DrawHandler {
 
     /// Create a new [`DrawHandler`] with an existing [`gtk::DrawingArea`].
     pub fn new_with_drawing_area(drawing_area: gtk::DrawingArea) -> Self {
-        let draw_surface = Surface::new(ImageSurface::create(Format::ARgb32, 100, 100).unwrap());
         let edit_surface = ImageSurface::create(Format::ARgb32, 100, 100).unwrap();
+        let draw_surface = Surface::new(edit_surface.clone());
 
         use gtk::glib;
         drawing_area.set_draw_func(glib::clone!(
             #[strong]
             draw_surface,
             move |_, context, _, _| {
-                // TODO: only copy the area that was exposed?
-                if let Err(error) = context.set_source_surface(&draw_surface.get(), 0.0, 0.0) {
-                    tracing::error!("Cannot set source surface: {:?}", error);
-                }
-
-                if let Err(error) = context.paint() {
-                    tracing::error!("Cannot paint: {:?}", error);
-                }
+                draw_surface.draw(context);
             }
         ));
 
@@ -141,6 +273,41 @@ impl DrawHandler {
         DrawContext::new(&self.draw_surface, &self.edit_surface, &self.drawing_area)
     }
 
+    /// Get a drawing context that is clipped to the given rectangle in pixels.
+    ///
+    /// The rectangle is [marked as damaged](DrawContext::damage), so large
+    /// canvases can redraw only the parts that changed.
+    pub fn get_context_for_area(&mut self, x: i32, y: i32, width: i32, height: i32) -> DrawContext {
+        let context = self.get_context();
+        context.damage(x, y, width, height);
+        context.rectangle(
+            f64::from(x),
+            f64::from(y),
+            f64::from(width),
+            f64::from(height),
+        );
+        context.clip();
+        context
+    }
+
+    /// Enable or disable the offscreen cache surface.
+    ///
+    /// With the cache enabled, the presented image is kept in a separate
+    /// surface and only the [damaged](DrawContext::damage) regions are
+    /// copied to it on the next frame. Changes outside of the damaged
+    /// regions aren't visible until they are damaged as well.
+    ///
+    /// The cache is disabled by default.
+    pub fn set_cache_enabled(&mut self, enabled: bool) {
+        self.draw_surface.set_cache_enabled(enabled);
+    }
+
+    /// Returns whether the offscreen cache surface is enabled.
+    #[must_use]
+    pub fn cache_enabled(&self) -> bool {
+        self.draw_surface.cache_enabled()
+    }
+
     /// Get the width and height of the [`DrawHandler`] in pixels.
     #[must_use]
     pub fn size(&self) -> (i32, i32) {
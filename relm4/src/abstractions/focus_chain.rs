@@ -0,0 +1,327 @@
+//! Keyboard navigation among the children of custom containers.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use gtk::gdk;
+use gtk::glib::Propagation;
+use gtk::prelude::{EventControllerExt, IsA, RootExt, WidgetExt};
+
+use crate::Sender;
+
+/// Type-ahead input is reset after this many milliseconds without a key press.
+const TYPE_AHEAD_TIMEOUT: u32 = 1000;
+
+type LabelFn = dyn Fn(&gtk::Widget) -> Option<String>;
+type ActivateFn = dyn Fn(usize);
+
+#[derive(Default)]
+struct Inner {
+    wrap: Cell<bool>,
+    last_focused: Cell<Option<usize>>,
+    type_ahead: RefCell<String>,
+    last_key_time: Cell<u32>,
+    label_fn: RefCell<Option<Box<LabelFn>>>,
+    activate_fn: RefCell<Option<Box<ActivateFn>>>,
+}
+
+impl Debug for Inner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Inner")
+            .field("wrap", &self.wrap)
+            .field("last_focused", &self.last_focused)
+            .field("type_ahead", &self.type_ahead)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Adds keyboard navigation similar to [`gtk::ListView`] to
+/// containers such as the widget of a [`FactoryVecDeque`].
+///
+/// The items of the chain are the visible and sensitive children of the container.
+/// Arrow keys move the focus to the previous or next item, `Home` and `End`
+/// to the first or last item. If a function for type-ahead labels is set,
+/// typing focuses the next item whose label starts with the typed text.
+/// `Return` and `Space` activate the focused item, unless the focused widget
+/// handles the keys itself, like [`gtk::Button`] does.
+///
+/// Once the container receives the focus itself, the focus is
+/// routed to the item that was focused most recently.
+///
+/// ```no_run
+/// # use relm4::gtk;
+/// # use gtk::prelude::Cast;
+/// # use relm4::abstractions::FocusChain;
+/// # #[derive(Debug)]
+/// # enum Msg { Activated(usize) }
+/// # let (sender, _) = relm4::channel::<Msg>();
+/// let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+/// let chain = FocusChain::new(&container, gtk::Orientation::Vertical);
+/// chain.set_type_ahead(|widget| {
+///     widget.downcast_ref::<gtk::Label>().map(|label| label.text().into())
+/// });
+/// chain.forward_activated(&sender, Msg::Activated);
+/// ```
+///
+/// [`FactoryVecDeque`]: crate::factory::FactoryVecDeque
+#[derive(Debug)]
+pub struct FocusChain {
+    container: gtk::Widget,
+    key_controller: gtk::EventControllerKey,
+    focus_controller: gtk::EventControllerFocus,
+    inner: Rc<Inner>,
+}
+
+impl FocusChain {
+    /// Enable keyboard navigation among the children of `container`.
+    ///
+    /// The `orientation` defines which arrow keys move the focus.
+    /// Dropping the [`FocusChain`] disables the navigation again.
+    #[must_use]
+    pub fn new(container: &impl IsA<gtk::Widget>, orientation: gtk::Orientation) -> Self {
+        let container = container.as_ref().clone();
+        let inner = Rc::new(Inner::default());
+
+        let key_controller = gtk::EventControllerKey::new();
+        {
+            let container = container.clone();
+            let inner = inner.clone();
+            key_controller.connect_key_pressed(move |controller, key, _, modifiers| {
+                handle_key(
+                    &container,
+                    &inner,
+                    orientation,
+                    controller.current_event_time(),
+                    key,
+                    modifiers,
+                )
+            });
+        }
+        container.add_controller(key_controller.clone());
+
+        let focus_controller = gtk::EventControllerFocus::new();
+        {
+            let container = container.clone();
+            let inner = inner.clone();
+            focus_controller.connect_enter(move |_| {
+                if container.has_focus() {
+                    // Route the focus to the most recent item.
+                    let index = inner.last_focused.get().unwrap_or(0);
+                    focus_index(&container, &inner, index);
+                } else if let Some(index) = current_index(&container) {
+                    inner.last_focused.set(Some(index));
+                }
+            });
+        }
+        container.add_controller(focus_controller.clone());
+
+        Self {
+            container,
+            key_controller,
+            focus_controller,
+            inner,
+        }
+    }
+
+    /// Move the focus to the first item after the last item
+    /// and the other way around.
+    ///
+    /// Disabled by default.
+    pub fn set_wrap(&self, wrap: bool) {
+        self.inner.wrap.set(wrap);
+    }
+
+    /// Enable type-ahead navigation using the labels returned by `label_fn`.
+    ///
+    /// The function is called with the items of the chain.
+    pub fn set_type_ahead<F>(&self, label_fn: F)
+    where
+        F: Fn(&gtk::Widget) -> Option<String> + 'static,
+    {
+        *self.inner.label_fn.borrow_mut() = Some(Box::new(label_fn));
+    }
+
+    /// Send a message whenever an item is activated by the keyboard.
+    ///
+    /// The function receives the index of the activated item.
+    pub fn forward_activated<M, F>(&self, sender: &Sender<M>, f: F)
+    where
+        M: 'static,
+        F: Fn(usize) -> M + 'static,
+    {
+        let sender = sender.clone();
+        *self.inner.activate_fn.borrow_mut() = Some(Box::new(move |index| sender.emit(f(index))));
+    }
+
+    /// Returns the items of the chain.
+    #[must_use]
+    pub fn items(&self) -> Vec<gtk::Widget> {
+        items(&self.container)
+    }
+
+    /// Returns the index of the item that contains the focus.
+    #[must_use]
+    pub fn current(&self) -> Option<usize> {
+        current_index(&self.container)
+    }
+
+    /// Move the focus to the item at `index`.
+    ///
+    /// Returns `false` if the item doesn't exist or can't be focused.
+    #[must_use]
+    pub fn focus(&self, index: usize) -> bool {
+        focus_index(&self.container, &self.inner, index)
+    }
+}
+
+impl Drop for FocusChain {
+    fn drop(&mut self) {
+        self.container.remove_controller(&self.key_controller);
+        self.container.remove_controller(&self.focus_controller);
+    }
+}
+
+fn items(container: &gtk::Widget) -> Vec<gtk::Widget> {
+    let mut items = Vec::new();
+    let mut child = container.first_child();
+    while let Some(widget) = child {
+        child = widget.next_sibling();
+        if widget.is_visible() && widget.is_sensitive() {
+            items.push(widget);
+        }
+    }
+    items
+}
+
+fn current_index(container: &gtk::Widget) -> Option<usize> {
+    let focus = container.root()?.focus()?;
+    items(container)
+        .iter()
+        .position(|item| &focus == item || focus.is_ancestor(item))
+}
+
+fn focus_index(container: &gtk::Widget, inner: &Inner, index: usize) -> bool {
+    let Some(item) = items(container).into_iter().nth(index) else {
+        return false;
+    };
+
+    // Containers that can't be focused pass the focus to their children.
+    let focused = item.grab_focus() || item.child_focus(gtk::DirectionType::TabForward);
+    if focused {
+        inner.last_focused.set(Some(index));
+    }
+    focused
+}
+
+fn handle_key(
+    container: &gtk::Widget,
+    inner: &Inner,
+    orientation: gtk::Orientation,
+    time: u32,
+    key: gdk::Key,
+    modifiers: gdk::ModifierType,
+) -> Propagation {
+    let len = items(container).len();
+    if len == 0 {
+        return Propagation::Proceed;
+    }
+    let current = current_index(container);
+
+    let (previous_key, next_key) = match orientation {
+        gtk::Orientation::Horizontal if container.direction() == gtk::TextDirection::Rtl => {
+            (gdk::Key::Right, gdk::Key::Left)
+        }
+        gtk::Orientation::Horizontal => (gdk::Key::Left, gdk::Key::Right),
+        _ => (gdk::Key::Up, gdk::Key::Down),
+    };
+    let wrap = inner.wrap.get();
+
+    let target = match key {
+        gdk::Key::Home | gdk::Key::KP_Home => Some(0),
+        gdk::Key::End | gdk::Key::KP_End => Some(len - 1),
+        key if key == next_key => match current {
+            Some(index) if index + 1 < len => Some(index + 1),
+            Some(_) if wrap => Some(0),
+            Some(_) => None,
+            None => Some(0),
+        },
+        key if key == previous_key => match current {
+            Some(index) if index > 0 => Some(index - 1),
+            Some(_) if wrap => Some(len - 1),
+            Some(_) => None,
+            None => Some(len - 1),
+        },
+        gdk::Key::Return | gdk::Key::KP_Enter | gdk::Key::ISO_Enter | gdk::Key::space => {
+            if let (Some(index), Some(activate)) = (current, &*inner.activate_fn.borrow()) {
+                activate(index);
+                return Propagation::Stop;
+            }
+            return Propagation::Proceed;
+        }
+        _ => {
+            return type_ahead(container, inner, current, time, key, modifiers);
+        }
+    };
+
+    if let Some(index) = target {
+        focus_index(container, inner, index);
+    }
+
+    // Don't let the window move the focus out of the container.
+    Propagation::Stop
+}
+
+fn type_ahead(
+    container: &gtk::Widget,
+    inner: &Inner,
+    current: Option<usize>,
+    time: u32,
+    key: gdk::Key,
+    modifiers: gdk::ModifierType,
+) -> Propagation {
+    let label_fn = inner.label_fn.borrow();
+    let Some(label_fn) = &*label_fn else {
+        return Propagation::Proceed;
+    };
+
+    if modifiers.intersects(
+        gdk::ModifierType::CONTROL_MASK
+            | gdk::ModifierType::ALT_MASK
+            | gdk::ModifierType::SUPER_MASK,
+    ) {
+        return Propagation::Proceed;
+    }
+    let Some(ch) = key.to_unicode().filter(|ch| !ch.is_control()) else {
+        return Propagation::Proceed;
+    };
+
+    let mut text = inner.type_ahead.borrow_mut();
+    if time.wrapping_sub(inner.last_key_time.replace(time)) > TYPE_AHEAD_TIMEOUT {
+        text.clear();
+    }
+    text.extend(ch.to_lowercase());
+
+    // Typing the first character again moves to the next match.
+    let start = match current {
+        Some(index) if text.chars().count() == 1 => index + 1,
+        Some(index) => index,
+        None => 0,
+    };
+
+    let items = items(container);
+    let len = items.len();
+    let found = (0..len)
+        .map(|offset| (start + offset) % len)
+        .find(|&index| {
+            label_fn(&items[index]).is_some_and(|label| label.to_lowercase().starts_with(&*text))
+        });
+
+    if let Some(index) = found {
+        drop(text);
+        focus_index(container, inner, index);
+        Propagation::Stop
+    } else {
+        Propagation::Proceed
+    }
+}
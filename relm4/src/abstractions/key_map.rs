@@ -0,0 +1,107 @@
+//! Map keys to messages.
+
+use std::fmt::Debug;
+
+use gtk::gdk;
+use gtk::glib::Propagation;
+
+use crate::Sender;
+
+/// Modifiers that are compared when looking up a key.
+///
+/// Other modifiers like `Caps Lock` are ignored.
+const MODIFIERS: gdk::ModifierType = gdk::ModifierType::CONTROL_MASK
+    .union(gdk::ModifierType::SHIFT_MASK)
+    .union(gdk::ModifierType::ALT_MASK)
+    .union(gdk::ModifierType::SUPER_MASK)
+    .union(gdk::ModifierType::META_MASK)
+    .union(gdk::ModifierType::HYPER_MASK);
+
+struct KeyBinding<M> {
+    key: gdk::Key,
+    modifiers: gdk::ModifierType,
+    msg: Box<dyn Fn() -> M>,
+}
+
+/// Maps keys with modifiers to messages.
+///
+/// Usually created with the [`keymap!`](crate::keymap) macro or
+/// the `#[keys(...)]` attribute of widgets in the `view!` macro,
+/// which validate the accelerator syntax at compile time.
+///
+/// Letters match regardless of their case, so `Ctrl+a`
+/// matches `Ctrl+A` as well if `Caps Lock` is active.
+pub struct KeyMap<M> {
+    bindings: Vec<KeyBinding<M>>,
+}
+
+impl<M> Debug for KeyMap<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.bindings
+                    .iter()
+                    .map(|binding| (binding.key, binding.modifiers)),
+            )
+            .finish()
+    }
+}
+
+impl<M: 'static> Default for KeyMap<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: 'static> KeyMap<M> {
+    /// Create an empty key map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Send the message returned by `msg` if `key` is pressed with exactly the given `modifiers`.
+    #[must_use]
+    pub fn bind<F>(mut self, key: gdk::Key, modifiers: gdk::ModifierType, msg: F) -> Self
+    where
+        F: Fn() -> M + 'static,
+    {
+        self.bindings.push(KeyBinding {
+            key: key.to_lower(),
+            modifiers: modifiers & MODIFIERS,
+            msg: Box::new(msg),
+        });
+        self
+    }
+
+    /// Returns the message bound to the key or [`None`] if the key isn't bound.
+    #[must_use]
+    pub fn lookup(&self, key: gdk::Key, modifiers: gdk::ModifierType) -> Option<M> {
+        let key = key.to_lower();
+        let modifiers = modifiers & MODIFIERS;
+        self.bindings
+            .iter()
+            .find(|binding| binding.key == key && binding.modifiers == modifiers)
+            .map(|binding| (binding.msg)())
+    }
+
+    /// Create a key controller that sends the bound messages.
+    ///
+    /// Key presses that aren't bound are propagated to other controllers.
+    #[must_use]
+    pub fn into_controller(self, sender: &Sender<M>) -> gtk::EventControllerKey {
+        let controller = gtk::EventControllerKey::new();
+        let sender = sender.clone();
+        controller.connect_key_pressed(move |_, key, _, modifiers| {
+            if let Some(msg) = self.lookup(key, modifiers) {
+                sender.emit(msg);
+                Propagation::Stop
+            } else {
+                Propagation::Proceed
+            }
+        });
+        controller
+    }
+}
@@ -1,12 +1,20 @@
 //! A module for convenient abstractions over gtk-rs.
 
 pub mod drawing;
+mod focus_chain;
+mod key_map;
+mod popover_host;
+pub mod snapshot;
 
 #[cfg(feature = "libadwaita")]
 #[cfg_attr(docsrs, doc(cfg(feature = "libadwaita")))]
 mod toaster;
 
 pub use drawing::{DrawContext, DrawHandler};
+pub use focus_chain::FocusChain;
+pub use key_map::KeyMap;
+pub use popover_host::{PopoverHost, PopoverSpec};
+pub use snapshot::{SnapshotArea, SnapshotHandler};
 
 #[cfg(feature = "libadwaita")]
 #[cfg_attr(docsrs, doc(cfg(feature = "libadwaita")))]
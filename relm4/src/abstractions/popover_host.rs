@@ -0,0 +1,212 @@
+//! Create popovers on demand and anchor them to named widgets.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gtk::gdk;
+use gtk::glib;
+use gtk::prelude::{Cast, IsA, PopoverExt, WidgetExt};
+
+/// Describes a popover that is shown by a [`PopoverHost`].
+#[derive(Debug, Clone)]
+pub struct PopoverSpec {
+    /// The name of the widget the popover points to.
+    pub anchor: String,
+    /// The content of the popover.
+    pub content: gtk::Widget,
+    /// The preferred side of the anchor to show the popover at.
+    pub position: gtk::PositionType,
+    /// Whether the popover closes when clicking outside of it.
+    pub autohide: bool,
+    /// Point to a rectangle inside the anchor instead of the whole anchor.
+    pub pointing_to: Option<gdk::Rectangle>,
+}
+
+impl PopoverSpec {
+    /// Create a new spec for a popover that shows `content`
+    /// below the widget registered as `anchor`.
+    #[must_use]
+    pub fn new(anchor: impl Into<String>, content: &impl IsA<gtk::Widget>) -> Self {
+        Self {
+            anchor: anchor.into(),
+            content: content.as_ref().clone(),
+            position: gtk::PositionType::Bottom,
+            autohide: true,
+            pointing_to: None,
+        }
+    }
+
+    /// Set the preferred side of the anchor.
+    #[must_use]
+    pub fn position(mut self, position: gtk::PositionType) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set whether the popover closes when clicking outside of it.
+    #[must_use]
+    pub fn autohide(mut self, autohide: bool) -> Self {
+        self.autohide = autohide;
+        self
+    }
+
+    /// Point to a rectangle inside the anchor.
+    #[must_use]
+    pub fn pointing_to(mut self, rect: gdk::Rectangle) -> Self {
+        self.pointing_to = Some(rect);
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    anchors: HashMap<String, gtk::Widget>,
+    open: HashMap<String, gtk::Popover>,
+}
+
+/// Manages popovers that are created on demand, for example
+/// tooltips, pickers or inline editors.
+///
+/// Widgets are registered under a name, usually the `#[name]`
+/// of the widget in the `view!` macro. Once a popover is requested
+/// for a name, the host creates it, attaches it to the widget and
+/// removes it again once it's dismissed. Only one popover is
+/// shown per anchor, showing another one replaces the first.
+///
+/// Like [`Toaster`](super::Toaster), the host is meant to be stored
+/// in the model, so popovers can be shown from the update function.
+///
+/// ```no_run
+/// # use relm4::gtk;
+/// # use relm4::abstractions::{PopoverHost, PopoverSpec};
+/// let save_button = gtk::Button::with_label("Save");
+///
+/// let popovers = PopoverHost::new();
+/// popovers.register("save_button", &save_button);
+///
+/// // Later, for example in the update function:
+/// let content = gtk::Label::new(Some("Saved!"));
+/// popovers.show(PopoverSpec::new("save_button", &content));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PopoverHost {
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl PopoverHost {
+    /// Create a new host without any anchors.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `widget` as anchor under the given name.
+    ///
+    /// An existing anchor with the same name is replaced
+    /// and its popover is closed.
+    pub fn register(&self, name: impl Into<String>, widget: &impl IsA<gtk::Widget>) {
+        let name = name.into();
+        self.hide(&name);
+        self.inner
+            .borrow_mut()
+            .anchors
+            .insert(name, widget.as_ref().clone());
+    }
+
+    /// Remove the anchor with the given name and close its popover.
+    pub fn unregister(&self, name: &str) {
+        self.hide(name);
+        self.inner.borrow_mut().anchors.remove(name);
+    }
+
+    /// Show a popover as described by `spec`.
+    ///
+    /// If the content is already shown in another popover,
+    /// it's moved to the new one.
+    ///
+    /// Nothing is shown if no anchor is registered under the name.
+    pub fn show(&self, spec: PopoverSpec) {
+        let Some(anchor) = self.inner.borrow().anchors.get(&spec.anchor).cloned() else {
+            return;
+        };
+        self.hide(&spec.anchor);
+
+        // Re-parent the content if it's still in use.
+        if let Some(parent) = spec.content.parent() {
+            if let Some(popover) = parent.downcast_ref::<gtk::Popover>() {
+                popover.set_child(None::<&gtk::Widget>);
+            } else {
+                spec.content.unparent();
+            }
+        }
+
+        let popover = gtk::Popover::new();
+        popover.set_child(Some(&spec.content));
+        popover.set_position(spec.position);
+        popover.set_autohide(spec.autohide);
+        popover.set_pointing_to(spec.pointing_to.as_ref());
+        popover.set_parent(&anchor);
+
+        {
+            let inner = Rc::downgrade(&self.inner);
+            let name = spec.anchor.clone();
+            popover.connect_closed(move |popover| {
+                if let Some(inner) = inner.upgrade() {
+                    let mut inner = inner.borrow_mut();
+                    if inner.open.get(&name) == Some(popover) {
+                        inner.open.remove(&name);
+                    }
+                }
+
+                // Unparenting during the signal emission isn't safe.
+                let popover = popover.clone();
+                glib::idle_add_local_once(move || {
+                    popover.set_child(None::<&gtk::Widget>);
+                    popover.unparent();
+                });
+            });
+        }
+
+        self.inner
+            .borrow_mut()
+            .open
+            .insert(spec.anchor, popover.clone());
+        popover.popup();
+    }
+
+    /// Close the popover of the anchor with the given name.
+    pub fn hide(&self, name: &str) {
+        // Release the borrow first because closing emits signals.
+        let popover = self.inner.borrow_mut().open.remove(name);
+        if let Some(popover) = popover {
+            popover.popdown();
+        }
+    }
+
+    /// Close all popovers.
+    pub fn hide_all(&self) {
+        let popovers: Vec<gtk::Popover> = self
+            .inner
+            .borrow_mut()
+            .open
+            .drain()
+            .map(|(_, p)| p)
+            .collect();
+        for popover in popovers {
+            popover.popdown();
+        }
+    }
+
+    /// Returns the popover that is shown for the anchor with the given name.
+    #[must_use]
+    pub fn popover(&self, name: &str) -> Option<gtk::Popover> {
+        self.inner.borrow().open.get(name).cloned()
+    }
+
+    /// Returns [`true`] if a popover is shown for the anchor with the given name.
+    #[must_use]
+    pub fn is_open(&self, name: &str) -> bool {
+        self.inner.borrow().open.contains_key(name)
+    }
+}
@@ -0,0 +1,228 @@
+//! Utility to draw with the GPU accelerated [`gtk::Snapshot`] API in a Relm4 application.
+//!
+//! Unlike [`DrawHandler`](super::DrawHandler), which draws with cairo on an image
+//! surface in software, a [`SnapshotHandler`] keeps a retained tree of render nodes
+//! that GTK renders with its GPU renderer.
+//! The tree is split into named layers that can be rebuilt individually, so
+//! a component only needs to redraw the layers that are affected by a message.
+//!
+//! ```ignore
+//! // In `update`
+//! match msg {
+//!     Msg::Resize => {
+//!         self.handler.update_layer("background", |snapshot, width, height| {
+//!             // draw the background
+//!         });
+//!     }
+//!     Msg::Move(x, y) => {
+//!         self.handler.update_layer("cursor", |snapshot, _, _| {
+//!             // only the cursor layer is rebuilt
+//!         });
+//!     }
+//! }
+//! ```
+
+use gtk::glib;
+use gtk::gsk;
+use gtk::prelude::{ObjectExt, SnapshotExt, WidgetExt};
+use gtk::subclass::prelude::ObjectSubclassIsExt;
+
+glib::wrapper! {
+    /// A widget that presents the layers of a [`SnapshotHandler`].
+    pub struct SnapshotArea(ObjectSubclass<imp::SnapshotArea>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for SnapshotArea {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl SnapshotArea {
+    /// Connect to the `resize` signal that is emitted
+    /// when the size of the area changed.
+    pub fn connect_resize<F>(&self, f: F) -> glib::SignalHandlerId
+    where
+        F: Fn(&Self, i32, i32) + 'static,
+    {
+        self.connect_closure(
+            "resize",
+            false,
+            glib::closure_local!(move |area: &Self, width: i32, height: i32| {
+                f(area, width, height);
+            }),
+        )
+    }
+}
+
+/// Manager for drawing operations with render nodes.
+#[derive(Debug)]
+#[must_use]
+pub struct SnapshotHandler {
+    area: SnapshotArea,
+}
+
+impl Default for SnapshotHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotHandler {
+    /// Create a new [`SnapshotHandler`].
+    pub fn new() -> Self {
+        Self::new_with_area(SnapshotArea::default())
+    }
+
+    /// Create a new [`SnapshotHandler`] with an existing [`SnapshotArea`].
+    pub fn new_with_area(area: SnapshotArea) -> Self {
+        Self { area }
+    }
+
+    /// Rebuild the layer with the given `name`.
+    ///
+    /// `draw` receives a new [`gtk::Snapshot`] along with the width and height
+    /// of the [`SnapshotArea`] in pixels.
+    /// New layers are drawn on top of the existing layers,
+    /// existing layers keep their position.
+    pub fn update_layer<F>(&mut self, name: &str, draw: F)
+    where
+        F: FnOnce(&gtk::Snapshot, f32, f32),
+    {
+        let snapshot = gtk::Snapshot::new();
+        draw(&snapshot, self.width() as f32, self.height() as f32);
+        let node = snapshot.to_node();
+
+        let mut layers = self.area.imp().layers.borrow_mut();
+        if let Some(layer) = layers.iter_mut().find(|(layer, _)| layer == name) {
+            layer.1 = node;
+        } else {
+            layers.push((name.to_owned(), node));
+        }
+        drop(layers);
+
+        self.area.queue_draw();
+    }
+
+    /// Remove the layer with the given `name`.
+    pub fn remove_layer(&mut self, name: &str) {
+        self.area
+            .imp()
+            .layers
+            .borrow_mut()
+            .retain(|(layer, _)| layer != name);
+        self.area.queue_draw();
+    }
+
+    /// Remove all layers.
+    pub fn clear(&mut self) {
+        self.area.imp().layers.borrow_mut().clear();
+        self.area.queue_draw();
+    }
+
+    /// Get the names of all layers from bottom to top.
+    #[must_use]
+    pub fn layers(&self) -> Vec<String> {
+        self.area
+            .imp()
+            .layers
+            .borrow()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Get the render node of the layer with the given `name`.
+    ///
+    /// Returns [`None`] if the layer doesn't exist or is empty.
+    #[must_use]
+    pub fn layer_node(&self, name: &str) -> Option<gsk::RenderNode> {
+        self.area
+            .imp()
+            .layers
+            .borrow()
+            .iter()
+            .find(|(layer, _)| layer == name)
+            .and_then(|(_, node)| node.clone())
+    }
+
+    /// Get the width of the [`SnapshotArea`] in pixels.
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.area.width()
+    }
+
+    /// Get the height of the [`SnapshotArea`] in pixels.
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.area.height()
+    }
+
+    /// Get the [`SnapshotArea`] of the [`SnapshotHandler`].
+    #[must_use]
+    pub fn area(&self) -> &SnapshotArea {
+        &self.area
+    }
+}
+
+#[allow(missing_docs)]
+mod imp {
+    use std::cell::{Cell, RefCell};
+    use std::sync::OnceLock;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::glib::{self, types::StaticType};
+    use gtk::gsk;
+    use gtk::prelude::{ObjectExt, SnapshotExt};
+    use gtk::subclass::prelude::{
+        ObjectImpl, ObjectSubclass, ObjectSubclassExt, WidgetImpl, WidgetImplExt,
+    };
+
+    #[derive(Debug, Default)]
+    pub struct SnapshotArea {
+        /// The named layers from bottom to top.
+        pub(super) layers: RefCell<Vec<(String, Option<gsk::RenderNode>)>>,
+        /// The size of the last allocation.
+        size: Cell<(i32, i32)>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SnapshotArea {
+        const NAME: &'static str = "RelmSnapshotArea";
+        type Type = super::SnapshotArea;
+        type ParentType = gtk::Widget;
+    }
+
+    impl ObjectImpl for SnapshotArea {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| {
+                vec![Signal::builder("resize")
+                    .param_types([i32::static_type(), i32::static_type()])
+                    .build()]
+            })
+        }
+    }
+
+    impl WidgetImpl for SnapshotArea {
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+            if self.size.replace((width, height)) != (width, height) {
+                self.obj().emit_by_name::<()>("resize", &[&width, &height]);
+            }
+        }
+
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            for node in self
+                .layers
+                .borrow()
+                .iter()
+                .filter_map(|(_, node)| node.as_ref())
+            {
+                snapshot.append_node(node);
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+use gtk::glib;
+use gtk::prelude::{FromVariant, StaticVariantType};
+
+/// Error returned when the value of an action doesn't match its declared type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    /// The action has no state or the activation had no target value.
+    MissingValue {
+        /// The name of the action.
+        action: String,
+        /// The expected variant type.
+        expected: glib::VariantType,
+    },
+    /// The variant type of the value doesn't match the declared type.
+    TypeMismatch {
+        /// The name of the action.
+        action: String,
+        /// The expected variant type.
+        expected: glib::VariantType,
+        /// The variant type of the actual value.
+        actual: glib::VariantType,
+    },
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingValue { action, expected } => {
+                write!(f, "action `{action}` expected a value of type `{expected}`")
+            }
+            Self::TypeMismatch {
+                action,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "action `{action}` expected a value of type `{expected}` but got `{actual}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+/// Convert an optional variant of the action `action` into `T`.
+pub(super) fn from_variant<T: FromVariant + StaticVariantType>(
+    action: &str,
+    variant: Option<&glib::Variant>,
+) -> Result<T, ActionError> {
+    let expected = T::static_variant_type().into_owned();
+    let Some(variant) = variant else {
+        return Err(ActionError::MissingValue {
+            action: action.to_owned(),
+            expected,
+        });
+    };
+
+    variant.get().ok_or_else(|| ActionError::TypeMismatch {
+        action: action.to_owned(),
+        expected,
+        actual: variant.type_().to_owned(),
+    })
+}
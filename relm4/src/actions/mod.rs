@@ -1,7 +1,7 @@
 //! Action utility.
 
 use gtk::gio;
-use gtk::prelude::{ActionExt, ActionMapExt, FromVariant, StaticVariantType, ToVariant, WidgetExt};
+use gtk::prelude::{ActionExt, ActionMapExt, Cast, FromVariant, IsA, StaticVariantType, ToVariant};
 
 use std::marker::PhantomData;
 
@@ -9,10 +9,21 @@ use std::marker::PhantomData;
 pub mod traits;
 pub use traits::*;
 
+mod error;
+pub use error::ActionError;
+
+mod property_action;
+pub use property_action::RelmPropertyAction;
+
+pub mod toolbar;
+
 #[macro_export]
 /// Create a new type that implements [`ActionGroupName`].
+///
+/// Attributes like doc comments are applied to the type.
 macro_rules! new_action_group {
-    ($vis:vis $ty:ident, $name:expr) => {
+    ($(#[$attr:meta])* $vis:vis $ty:ident, $name:expr) => {
+        $(#[$attr])*
         $vis struct $ty;
 
         impl relm4::actions::ActionGroupName for $ty {
@@ -23,8 +34,26 @@ macro_rules! new_action_group {
 
 #[macro_export]
 /// Create a new type that implements [`ActionName`] without state or target type.
+///
+/// Attributes like doc comments are applied to the type.
+/// The [`label`](ActionName::LABEL), [`icon`](ActionName::ICON_NAME)
+/// and [`tooltip`](ActionName::TOOLTIP) can be added at the end:
+///
+/// ```
+/// relm4::new_action_group!(WindowActionGroup, "win");
+/// relm4::new_stateless_action!(
+///     /// Saves the document.
+///     SaveAction,
+///     WindowActionGroup,
+///     "save",
+///     label = "Save",
+///     icon = "document-save-symbolic",
+///     tooltip = "Save the document",
+/// );
+/// ```
 macro_rules! new_stateless_action {
-    ($vis:vis $ty:ident, $group:ty, $name:expr) => {
+    ($(#[$attr:meta])* $vis:vis $ty:ident, $group:ty, $name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $(#[$attr])*
         $vis struct $ty;
 
         impl relm4::actions::ActionName for $ty {
@@ -33,6 +62,8 @@ macro_rules! new_stateless_action {
             type State = ();
 
             const NAME: &'static str = $name;
+
+            $(relm4::__action_metadata!($key, $value);)*
         }
     };
 }
@@ -41,8 +72,12 @@ macro_rules! new_stateless_action {
 /// Create a new type that implements [`ActionName`] with state and target type.
 ///
 /// The state stores the state of this action and the target type is passed by callers of the action.
+///
+/// Attributes like doc comments are applied to the type and,
+/// like in [`new_stateless_action!`], a label, an icon and a tooltip can be added at the end.
 macro_rules! new_stateful_action {
-    ($vis:vis $ty:ident, $group:ty, $name:expr, $value:ty, $state:ty) => {
+    ($(#[$attr:meta])* $vis:vis $ty:ident, $group:ty, $name:expr, $value:ty, $state:ty $(, $key:ident = $meta:expr)* $(,)?) => {
+        $(#[$attr])*
         $vis struct $ty;
 
         impl relm4::actions::ActionName for $ty {
@@ -51,10 +86,26 @@ macro_rules! new_stateful_action {
             type State = $state;
 
             const NAME: &'static str = $name;
+
+            $(relm4::__action_metadata!($key, $meta);)*
         }
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __action_metadata {
+    (label, $value:expr) => {
+        const LABEL: ::std::option::Option<&'static str> = ::std::option::Option::Some($value);
+    };
+    (icon, $value:expr) => {
+        const ICON_NAME: ::std::option::Option<&'static str> = ::std::option::Option::Some($value);
+    };
+    (tooltip, $value:expr) => {
+        const TOOLTIP: ::std::option::Option<&'static str> = ::std::option::Option::Some($value);
+    };
+}
+
 /// A type safe action that wraps around [`gio::SimpleAction`].
 pub struct RelmAction<Name: ActionName> {
     name: PhantomData<Name>,
@@ -103,11 +154,19 @@ where
             gio::SimpleAction::new_stateful(Name::NAME, Some(&ty), &start_value.to_variant());
 
         action.connect_activate(move |action, variant| {
-            let value = variant.unwrap().get().unwrap();
-            let mut state = action.state().unwrap().get().unwrap();
-
-            callback(action, &mut state, value);
-            action.set_state(&state.to_variant());
+            let values = error::from_variant(Name::NAME, variant).and_then(|value| {
+                Ok((
+                    value,
+                    error::from_variant(Name::NAME, action.state().as_ref())?,
+                ))
+            });
+            match values {
+                Ok((value, mut state)) => {
+                    callback(action, &mut state, value);
+                    action.set_state(&state.to_variant());
+                }
+                Err(error) => tracing::error!("Couldn't activate action: {error}"),
+            }
         });
 
         Self {
@@ -130,9 +189,13 @@ where
         let action = gio::SimpleAction::new_stateful(Name::NAME, None, &start_value.to_variant());
 
         action.connect_activate(move |action, _variant| {
-            let mut state = action.state().unwrap().get().unwrap();
-            callback(action, &mut state);
-            action.set_state(&state.to_variant());
+            match error::from_variant(Name::NAME, action.state().as_ref()) {
+                Ok(mut state) => {
+                    callback(action, &mut state);
+                    action.set_state(&state.to_variant());
+                }
+                Err(error) => tracing::error!("Couldn't activate action: {error}"),
+            }
         });
 
         Self {
@@ -156,8 +219,10 @@ where
         let action = gio::SimpleAction::new(Name::NAME, Some(&ty));
 
         action.connect_activate(move |action, variant| {
-            let value = variant.unwrap().get().unwrap();
-            callback(action, value);
+            match error::from_variant(Name::NAME, variant) {
+                Ok(value) => callback(action, value),
+                Err(error) => tracing::error!("Couldn't activate action: {error}"),
+            }
         });
 
         Self {
@@ -217,6 +282,36 @@ where
     }
 }
 
+impl<Name: ActionName> RelmAction<Name>
+where
+    Name::State: ToVariant + FromVariant,
+{
+    /// Returns the current state of the action.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state doesn't have the type of [`ActionName::State`].
+    /// Use [`Self::try_state()`] to handle this case.
+    #[must_use]
+    pub fn state(&self) -> Name::State {
+        self.try_state().unwrap()
+    }
+
+    /// Returns the current state of the action or an error
+    /// if the state doesn't have the type of [`ActionName::State`].
+    ///
+    /// This can happen if the underlying [`gio::SimpleAction`]
+    /// was modified directly.
+    pub fn try_state(&self) -> Result<Name::State, ActionError> {
+        error::from_variant(Name::NAME, self.action.state().as_ref())
+    }
+
+    /// Sets the state of the action.
+    pub fn set_state(&self, state: &Name::State) {
+        self.action.set_state(&state.to_variant());
+    }
+}
+
 impl<Name: ActionName> RelmAction<Name> {
     /// Sets the action as enabled or disabled.
     ///
@@ -235,11 +330,56 @@ impl<Name: ActionName> RelmAction<Name> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about an action, mostly useful for debugging
+/// and for listing actions that were composed from multiple sources.
+pub struct ActionInfo {
+    /// The name of the action without group prefix.
+    pub name: String,
+    /// The type of the target value or [`None`] for actions without target value.
+    pub parameter_type: Option<gtk::glib::VariantType>,
+    /// The type of the state or [`None`] for stateless actions.
+    pub state_type: Option<gtk::glib::VariantType>,
+    /// Whether the action is enabled.
+    pub enabled: bool,
+}
+
+impl ActionInfo {
+    /// Collect information about an action.
+    #[must_use]
+    pub fn new(action: &impl IsA<gio::Action>) -> Self {
+        Self {
+            name: action.name().into(),
+            parameter_type: action.parameter_type(),
+            state_type: action.state_type(),
+            enabled: action.is_enabled(),
+        }
+    }
+
+    /// Collect information about all actions of an action group,
+    /// for example a group inserted into a widget.
+    #[must_use]
+    pub fn list(group: &impl IsA<gio::ActionGroup>) -> Vec<Self> {
+        use gtk::prelude::ActionGroupExt;
+
+        group
+            .list_actions()
+            .into_iter()
+            .map(|name| Self {
+                parameter_type: group.action_parameter_type(&name),
+                state_type: group.action_state_type(&name),
+                enabled: group.is_action_enabled(&name),
+                name: name.into(),
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 /// A type-safe action group that wraps around [`gio::SimpleActionGroup`].
 pub struct RelmActionGroup<GroupName: ActionGroupName> {
     group_name: PhantomData<GroupName>,
-    actions: Vec<gio::SimpleAction>,
+    actions: Vec<gio::Action>,
 }
 
 impl<GroupName: ActionGroupName> RelmActionGroup<GroupName> {
@@ -251,26 +391,63 @@ impl<GroupName: ActionGroupName> RelmActionGroup<GroupName> {
 
     /// Add an action to the group.
     pub fn add_action<Name: ActionName>(&mut self, action: RelmAction<Name>) {
-        self.actions.push(action.action);
+        self.actions.push(action.action.upcast());
+    }
+
+    /// Add a property action to the group.
+    pub fn add_property_action<Name: ActionName>(&mut self, action: RelmPropertyAction<Name>) {
+        self.actions
+            .push(gio::PropertyAction::from(action).upcast());
+    }
+
+    /// Move all actions of `other` into this group.
+    ///
+    /// Actions with the same name replace the existing ones
+    /// once the group is registered.
+    pub fn merge<Other: ActionGroupName>(&mut self, other: RelmActionGroup<Other>) {
+        self.actions.extend(other.actions);
+    }
+
+    /// Use the actions of this group under the prefix of `NewName`.
+    ///
+    /// This is useful to include actions of reusable components
+    /// or plugins in the scope of an application.
+    #[must_use]
+    pub fn rename<NewName: ActionGroupName>(self) -> RelmActionGroup<NewName> {
+        RelmActionGroup {
+            group_name: PhantomData,
+            actions: self.actions,
+        }
+    }
+
+    /// Returns information about the added actions.
+    #[must_use]
+    pub fn action_infos(&self) -> Vec<ActionInfo> {
+        self.actions.iter().map(ActionInfo::new).collect()
     }
 
     /// Register the added actions at application level.
     pub fn register_for_main_application(self) {
-        let app = crate::main_application();
+        self.register_for_application(&crate::main_application());
+    }
+
+    /// Register the added actions at the level of the given application.
+    ///
+    /// Application actions use the `app` prefix, regardless of the group name.
+    pub fn register_for_application(self, app: &impl IsA<gtk::Application>) {
         for action in self.actions {
-            app.add_action(&action);
+            app.as_ref().add_action(&action);
         }
     }
 
     /// Register the added actions for a certain widget.
+    ///
+    /// See also [`ActionGroupPlus::insert_relm_action_group()`].
     pub fn register_for_widget<W>(self, widget: W)
     where
         W: AsRef<gtk::Widget>,
     {
-        let group = self.into_action_group();
-        widget
-            .as_ref()
-            .insert_action_group(GroupName::NAME, Some(&group));
+        widget.as_ref().insert_relm_action_group(self);
     }
 
     /// Convert [`RelmActionGroup`] into a [`gio::SimpleActionGroup`].
@@ -295,7 +472,10 @@ where
     {
         Self {
             group_name: PhantomData,
-            actions: iter.into_iter().map(Into::into).collect(),
+            actions: iter
+                .into_iter()
+                .map(|action| action.into().upcast())
+                .collect(),
         }
     }
 }
@@ -0,0 +1,131 @@
+use std::marker::PhantomData;
+
+use gtk::prelude::{ActionExt, IsA, StaticVariantType};
+use gtk::{gio, glib};
+
+use super::{ActionError, ActionName};
+
+/// A type safe action that wraps around [`gio::PropertyAction`].
+///
+/// The action mirrors a property of an object, for example the
+/// `visible` property of a sidebar. Activating the action changes
+/// the property and changing the property updates the action state.
+///
+/// The state type of the action must match the type of the property
+/// and the target type must match the parameter type of the action,
+/// which is [`()`] for boolean properties and the property type otherwise.
+///
+/// ```no_run
+/// # use relm4::gtk;
+/// # use relm4::actions::{RelmActionGroup, RelmPropertyAction};
+/// relm4::new_action_group!(WindowActionGroup, "win");
+/// relm4::new_stateful_action!(ShowSidebar, WindowActionGroup, "show-sidebar", (), bool);
+///
+/// let sidebar = gtk::Box::default();
+/// let action: RelmPropertyAction<ShowSidebar> = RelmPropertyAction::new(&sidebar, "visible");
+///
+/// let mut group = RelmActionGroup::<WindowActionGroup>::new();
+/// group.add_property_action(action);
+/// ```
+pub struct RelmPropertyAction<Name: ActionName> {
+    name: PhantomData<Name>,
+    action: gio::PropertyAction,
+}
+
+impl<Name: ActionName> Clone for RelmPropertyAction<Name> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            action: self.action.clone(),
+        }
+    }
+}
+
+impl<Name: ActionName> std::fmt::Debug for RelmPropertyAction<Name> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelmPropertyAction")
+            .field("name", &self.name)
+            .field("action", &self.action)
+            .finish()
+    }
+}
+
+impl<Name: ActionName> From<RelmPropertyAction<Name>> for gio::PropertyAction {
+    fn from(value: RelmPropertyAction<Name>) -> Self {
+        value.action
+    }
+}
+
+impl<Name: ActionName> RelmPropertyAction<Name>
+where
+    Name::State: StaticVariantType,
+    Name::Target: StaticVariantType,
+{
+    /// Create a new action for the property `property_name` of `object`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property doesn't exist or if its type doesn't
+    /// match the state or target type of the action.
+    /// Use [`Self::try_new()`] to handle type mismatches.
+    #[must_use]
+    pub fn new(object: &impl IsA<glib::Object>, property_name: &str) -> Self {
+        match Self::try_new(object, property_name) {
+            Ok(action) => action,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new action for the property `property_name` of `object`
+    /// or return an error if the property type doesn't match the
+    /// state or target type of the action.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property doesn't exist.
+    pub fn try_new(
+        object: &impl IsA<glib::Object>,
+        property_name: &str,
+    ) -> Result<Self, ActionError> {
+        let action = gio::PropertyAction::new(Name::NAME, object, property_name);
+
+        let state_type = action
+            .state_type()
+            .expect("property actions always have a state");
+        check_type::<Name::State>(Name::NAME, &state_type)?;
+
+        let parameter_type = action
+            .parameter_type()
+            .unwrap_or_else(|| glib::VariantTy::UNIT.to_owned());
+        check_type::<Name::Target>(Name::NAME, &parameter_type)?;
+
+        Ok(Self {
+            name: PhantomData,
+            action,
+        })
+    }
+}
+
+impl<Name: ActionName> RelmPropertyAction<Name> {
+    /// Returns the inner [`gio::PropertyAction`].
+    #[must_use]
+    pub fn gio_action(&self) -> &gio::PropertyAction {
+        &self.action
+    }
+}
+
+fn check_type<T: StaticVariantType>(
+    action: &str,
+    actual: &glib::VariantTy,
+) -> Result<(), ActionError> {
+    let expected = T::static_variant_type();
+    if *expected == *actual {
+        Ok(())
+    } else {
+        Err(ActionError::TypeMismatch {
+            action: action.to_owned(),
+            expected: expected.into_owned(),
+            actual: actual.to_owned(),
+        })
+    }
+}
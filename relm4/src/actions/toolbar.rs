@@ -0,0 +1,194 @@
+//! Toolbar widgets created from actions.
+//!
+//! The widgets take their icon, label and tooltip from the [`ActionName`]
+//! (see [`new_stateless_action!`](crate::new_stateless_action!)).
+//! The tooltip also contains the first keyboard accelerator of the action,
+//! so accelerators should be set before the widgets are created.
+//! Because the widgets activate the actions by name, they are
+//! insensitive while their action is disabled or missing.
+//!
+//! The [`toolbar!`](crate::toolbar!) macro fills a header bar with these widgets.
+
+use gtk::gio;
+use gtk::prelude::{ButtonExt, Cast, GtkApplicationExt, IsA, WidgetExt};
+
+use super::{ActionName, ActionablePlus, EmptyType};
+
+/// A button that activates the stateless action `A`.
+///
+/// It shows the icon of the action, or the label if the action has no icon.
+#[must_use]
+pub fn button<A>() -> gtk::Button
+where
+    A: ActionName,
+    A::Target: EmptyType,
+{
+    let button = gtk::Button::new();
+    button.set_stateless_action::<A>(&());
+    match A::ICON_NAME {
+        Some(icon_name) => button.set_icon_name(icon_name),
+        None => button.set_label(A::LABEL.unwrap_or(A::NAME)),
+    }
+    button.set_tooltip_text(tooltip::<A>().as_deref());
+    button
+}
+
+/// A toggle button that shows and changes the boolean state of the action `A`.
+#[must_use]
+pub fn toggle_button<A>() -> gtk::ToggleButton
+where
+    A: ActionName<State = bool>,
+    A::Target: EmptyType,
+{
+    let button = gtk::ToggleButton::new();
+    button.set_stateless_action::<A>(&());
+    match A::ICON_NAME {
+        Some(icon_name) => button.set_icon_name(icon_name),
+        None => button.set_label(A::LABEL.unwrap_or(A::NAME)),
+    }
+    button.set_tooltip_text(tooltip::<A>().as_deref());
+    button
+}
+
+/// A button with an icon that opens a menu.
+#[must_use]
+pub fn menu_button(icon_name: &str, menu: &impl IsA<gio::MenuModel>) -> gtk::MenuButton {
+    gtk::MenuButton::builder()
+        .icon_name(icon_name)
+        .menu_model(menu)
+        .build()
+}
+
+/// A button that activates the stateless action `A`,
+/// with an arrow next to it that opens a menu.
+#[cfg(feature = "libadwaita")]
+#[cfg_attr(docsrs, doc(cfg(feature = "libadwaita")))]
+#[must_use]
+pub fn split_button<A>(menu: &impl IsA<gio::MenuModel>) -> adw::SplitButton
+where
+    A: ActionName,
+    A::Target: EmptyType,
+{
+    let button = adw::SplitButton::builder().menu_model(menu).build();
+    button.set_stateless_action::<A>(&());
+    match A::ICON_NAME {
+        Some(icon_name) => button.set_icon_name(icon_name),
+        None => button.set_label(A::LABEL.unwrap_or(A::NAME)),
+    }
+    button.set_tooltip_text(tooltip::<A>().as_deref());
+    button
+}
+
+/// The tooltip of `A` with its first accelerator, for example "Save (Ctrl+S)".
+///
+/// Falls back to the label if the action has no tooltip.
+#[must_use]
+pub fn tooltip<A: ActionName>() -> Option<String> {
+    let text = A::TOOLTIP.or(A::LABEL)?;
+    let accelerator = crate::main_application()
+        .accels_for_action(&A::action_name())
+        .first()
+        .and_then(|accelerator| gtk::accelerator_parse(accelerator.as_str()))
+        .map(|(key, modifiers)| gtk::accelerator_get_label(key, modifiers));
+
+    Some(match accelerator {
+        Some(accelerator) => format!("{text} ({accelerator})"),
+        None => text.to_owned(),
+    })
+}
+
+#[doc(hidden)]
+pub fn __upcast(widget: impl IsA<gtk::Widget>) -> gtk::Widget {
+    widget.upcast()
+}
+
+/// Fill a header bar with widgets for actions.
+///
+/// The first argument is a [`gtk::HeaderBar`], an `adw::HeaderBar`
+/// or anything else with `pack_start()` and `pack_end()` methods.
+/// `start`, `title` and `end` are optional, but must be in this order.
+/// The widgets of `end` are shown in the listed order.
+///
+/// The following items are supported:
+///
+/// + `button(Action)`: a [`button()`] for a stateless action.
+/// + `toggle(Action)`: a [`toggle_button()`] for an action with a boolean state.
+/// + `menu("icon-name", &menu_model)`: a [`menu_button()`].
+/// + `split(Action, &menu_model)`: a [`split_button()`], requires the `libadwaita` feature.
+/// + `widget(&widget)`: any other widget.
+///
+/// ```no_run
+/// # use relm4::gtk;
+/// relm4::new_action_group!(WindowActionGroup, "win");
+/// relm4::new_stateless_action!(
+///     NewAction,
+///     WindowActionGroup,
+///     "new",
+///     label = "New",
+///     icon = "tab-new-symbolic",
+/// );
+/// relm4::new_stateful_action!(
+///     SidebarAction,
+///     WindowActionGroup,
+///     "sidebar",
+///     (),
+///     bool,
+///     icon = "sidebar-show-symbolic",
+///     tooltip = "Show Sidebar",
+/// );
+///
+/// # let primary_menu = gtk::gio::Menu::new();
+/// let header_bar = gtk::HeaderBar::new();
+/// relm4::toolbar!(header_bar,
+///     start: [button(NewAction), toggle(SidebarAction)],
+///     end: [menu("open-menu-symbolic", &primary_menu)],
+/// );
+/// ```
+#[macro_export]
+macro_rules! toolbar {
+    (
+        $bar:expr
+        $(, start: [$($start:ident($($start_args:tt)*)),* $(,)?])?
+        $(, title: $title:expr)?
+        $(, end: [$($end:ident($($end_args:tt)*)),* $(,)?])?
+        $(,)?
+    ) => {{
+        #[allow(unused_variables)]
+        let bar = &$bar;
+        $($(
+            bar.pack_start(&$crate::__toolbar_item!($start $($start_args)*));
+        )*)?
+        $(
+            bar.set_title_widget(::std::option::Option::Some(&$title));
+        )?
+        $(
+            // `pack_end()` adds widgets from the end.
+            let end: ::std::vec::Vec<$crate::gtk::Widget> = ::std::vec![$(
+                $crate::actions::toolbar::__upcast($crate::__toolbar_item!($end $($end_args)*))
+            ),*];
+            for widget in end.iter().rev() {
+                bar.pack_end(widget);
+            }
+        )?
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __toolbar_item {
+    (button $action:ty) => {
+        $crate::actions::toolbar::button::<$action>()
+    };
+    (toggle $action:ty) => {
+        $crate::actions::toolbar::toggle_button::<$action>()
+    };
+    (menu $icon_name:expr, $menu:expr) => {
+        $crate::actions::toolbar::menu_button($icon_name, $menu)
+    };
+    (split $action:ty, $menu:expr) => {
+        $crate::actions::toolbar::split_button::<$action>($menu)
+    };
+    (widget $widget:expr) => {
+        ::std::clone::Clone::clone($widget)
+    };
+}
@@ -1,4 +1,9 @@
-use gtk::prelude::ToVariant;
+use std::cell::RefCell;
+
+use gtk::prelude::{ActionMapExt, Cast, IsA, ObjectExt, ToVariant, WidgetExt};
+use gtk::{gio, glib};
+
+use super::RelmActionGroup;
 
 /// Trait used to specify the group name in [`ActionName`].
 pub trait ActionGroupName {
@@ -29,6 +34,15 @@ pub trait ActionName {
     /// The name of the action.
     const NAME: &'static str;
 
+    /// A short label, for example for buttons.
+    const LABEL: Option<&'static str> = None;
+
+    /// The name of an icon that represents the action.
+    const ICON_NAME: Option<&'static str> = None;
+
+    /// A longer description of the action, used as tooltip.
+    const TOOLTIP: Option<&'static str> = None;
+
     /// The full action name (group.action).
     #[must_use]
     fn action_name() -> String {
@@ -82,3 +96,113 @@ impl<W: gtk::prelude::GtkApplicationExt> AccelsPlus for W {
         self.set_accels_for_action(A::action_name().as_str(), accel_codes);
     }
 }
+
+thread_local! {
+    /// Action groups inserted with [`ActionGroupPlus`], used to look up
+    /// actions in the scope of a widget because GTK doesn't expose them.
+    static WIDGET_GROUPS: RefCell<Vec<(glib::WeakRef<gtk::Widget>, &'static str, gio::SimpleActionGroup)>> =
+        RefCell::default();
+}
+
+/// Type safe interface for inserting action groups into widgets.
+///
+/// The prefix of the group is always taken from [`ActionGroupName::NAME`],
+/// so actions are registered under the same name they are activated with.
+pub trait ActionGroupPlus {
+    /// Insert an action group, so the actions can be activated
+    /// by this widget and all of its children.
+    fn insert_relm_action_group<G: ActionGroupName>(&self, group: RelmActionGroup<G>);
+
+    /// Remove the action group with the name of `G`.
+    fn remove_relm_action_group<G: ActionGroupName>(&self);
+
+    /// Look up the action `A` in the scope of this widget.
+    ///
+    /// Like during activation, the groups of the widget and its ancestors
+    /// are searched first. Actions of the `win` group are also searched in
+    /// [`gtk::ApplicationWindow`]s and actions of the `app` group
+    /// in the [main application](crate::main_application()).
+    ///
+    /// Only groups inserted with [`ActionGroupPlus`] or
+    /// [`RelmActionGroup::register_for_widget()`] are found.
+    fn relm_action<A: ActionName>(&self) -> Option<gio::Action>;
+
+    /// Enable or disable the action `A` in the scope of this widget.
+    ///
+    /// Returns [`false`] if the action wasn't found or can't be disabled.
+    fn set_relm_action_enabled<A: ActionName>(&self, enabled: bool) -> bool;
+}
+
+impl<W: IsA<gtk::Widget>> ActionGroupPlus for W {
+    fn insert_relm_action_group<G: ActionGroupName>(&self, group: RelmActionGroup<G>) {
+        let group = group.into_action_group();
+        self.insert_action_group(G::NAME, Some(&group));
+
+        let widget = self.as_ref();
+        WIDGET_GROUPS.with(|groups| {
+            let mut groups = groups.borrow_mut();
+            groups.retain(|(weak, name, _)| {
+                weak.upgrade()
+                    .is_some_and(|w| !(&w == widget && *name == G::NAME))
+            });
+            groups.push((widget.downgrade(), G::NAME, group));
+        });
+    }
+
+    fn remove_relm_action_group<G: ActionGroupName>(&self) {
+        self.insert_action_group(G::NAME, None::<&gio::ActionGroup>);
+
+        let widget = self.as_ref();
+        WIDGET_GROUPS.with(|groups| {
+            groups.borrow_mut().retain(|(weak, name, _)| {
+                weak.upgrade()
+                    .is_some_and(|w| !(&w == widget && *name == G::NAME))
+            });
+        });
+    }
+
+    fn relm_action<A: ActionName>(&self) -> Option<gio::Action> {
+        let group_name = A::Group::NAME;
+
+        let mut current = Some(self.as_ref().clone());
+        while let Some(widget) = current {
+            let action = WIDGET_GROUPS.with(|groups| {
+                groups
+                    .borrow()
+                    .iter()
+                    .filter(|(weak, name, _)| {
+                        *name == group_name && weak.upgrade().as_ref() == Some(&widget)
+                    })
+                    .find_map(|(_, _, group)| group.lookup_action(A::NAME))
+            });
+            if action.is_some() {
+                return action;
+            }
+
+            if group_name == "win" {
+                if let Some(window) = widget.downcast_ref::<gtk::ApplicationWindow>() {
+                    if let Some(action) = window.lookup_action(A::NAME) {
+                        return Some(action);
+                    }
+                }
+            }
+            current = widget.parent();
+        }
+
+        if group_name == "app" {
+            crate::main_application().lookup_action(A::NAME)
+        } else {
+            None
+        }
+    }
+
+    fn set_relm_action_enabled<A: ActionName>(&self, enabled: bool) -> bool {
+        let action = self
+            .relm_action::<A>()
+            .and_then(|action| action.downcast::<gio::SimpleAction>().ok());
+        if let Some(action) = &action {
+            action.set_enabled(enabled);
+        }
+        action.is_some()
+    }
+}
@@ -1,10 +1,16 @@
 use gtk::glib;
-use gtk::prelude::{ApplicationExt, ApplicationExtManual, Cast, GtkApplicationExt, IsA, WidgetExt};
+use gtk::prelude::{
+    ApplicationExt, ApplicationExtManual, Cast, GtkApplicationExt, IsA, ObjectExt, StaticType,
+    WidgetExt,
+};
 use std::fmt::Debug;
 
 use crate::component::{AsyncComponent, AsyncComponentBuilder, AsyncComponentController};
+use crate::crash::CrashReport;
 use crate::runtime_util::shutdown_all;
-use crate::{Component, ComponentBuilder, ComponentController, MessageBroker, RUNTIME};
+use crate::{
+    Component, ComponentBuilder, ComponentController, MainWindowRegistry, MessageBroker, RUNTIME,
+};
 
 use std::cell::Cell;
 
@@ -44,6 +50,13 @@ impl<M: Debug + 'static> RelmApp<M> {
     }
 
     /// Create a Relm4 application with a provided [`gtk::Application`].
+    ///
+    /// The application can also be an instance of a subclass of [`gtk::Application`]
+    /// or `adw::Application`, for example to override virtual functions like `dbus_register`.
+    /// Relm4 launches the main component in a handler of the `startup` signal,
+    /// which runs after the `startup` virtual function of the subclass.
+    /// If the subclass overrides `activate`, Relm4 doesn't connect to the
+    /// `activate` signal, so the subclass is responsible for presenting the window.
     pub fn from_app(app: impl IsA<gtk::Application>) -> Self {
         let app = app.upcast();
         crate::set_main_application(app.clone());
@@ -63,6 +76,43 @@ impl<M: Debug + 'static> RelmApp<M> {
         self
     }
 
+    /// Calls `handler` with a [`CrashReport`] when the application panics
+    /// or GLib logs a critical message.
+    ///
+    /// The hooks are installed immediately and the default panic hook
+    /// still runs after the handler. Critical messages are only reported
+    /// after [`log_writer::install()`](crate::log_writer::install) was called.
+    /// See [`crash`](crate::crash) for details.
+    #[must_use]
+    pub fn with_crash_handler<F>(self, handler: F) -> Self
+    where
+        F: Fn(&CrashReport) + Send + Sync + 'static,
+    {
+        crate::crash::set_handler(handler);
+        self
+    }
+
+    /// Calls `func` when the application starts up, before the main component is created.
+    ///
+    /// This is the right place to register resources, load CSS
+    /// or set up actions and accelerators, because GTK is already initialized
+    /// but no widgets were created yet.
+    /// The application is only started up once, even if it's activated multiple times.
+    #[must_use]
+    pub fn on_startup<F>(self, func: F) -> Self
+    where
+        F: FnOnce(&gtk::Application) + 'static,
+    {
+        let func = Cell::new(Some(func));
+        // Runs before the handler of `run()` because it's connected first.
+        self.app.connect_startup(move |app| {
+            if let Some(func) = func.take() {
+                func(app);
+            }
+        });
+        self
+    }
+
     /// Add command line arguments to run with.
     #[must_use]
     pub fn with_args(mut self, args: Vec<String>) -> Self {
@@ -149,6 +199,33 @@ impl<M: Debug + 'static> RelmApp<M> {
     where
         C: Component<Input = M>,
         C::Root: AsRef<gtk::Window>,
+    {
+        self.run_with_window_setup::<C>(payload, |_| ());
+    }
+
+    /// Runs the application with the root window of the main component as
+    /// Wayland layer surface, returns once the application is closed.
+    ///
+    /// See [`layer_shell`](crate::layer_shell) for details.
+    #[cfg(feature = "layer_shell")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "layer_shell")))]
+    pub fn run_layer_shell<C>(self, payload: C::Init, config: crate::layer_shell::LayerShellConfig)
+    where
+        C: Component<Input = M>,
+        C::Root: AsRef<gtk::Window>,
+    {
+        self.run_with_window_setup::<C>(payload, move |window| {
+            config.apply(window);
+        });
+    }
+
+    fn run_with_window_setup<C>(
+        self,
+        payload: C::Init,
+        window_setup: impl Fn(&gtk::Window) + 'static,
+    ) where
+        C: Component<Input = M>,
+        C::Root: AsRef<gtk::Window>,
     {
         let Self {
             app,
@@ -170,22 +247,27 @@ impl<M: Debug + 'static> RelmApp<M> {
 
                 // Run late initialization for transient windows for example.
                 crate::late_initialization::run_late_init();
+                crate::config::apply_on_startup();
 
                 let mut controller = connector.detach();
                 let window = controller.widget();
+                window_setup(window.as_ref());
                 app.add_window(window.as_ref());
+                MainWindowRegistry::register(window.as_ref());
 
                 controller.detach_runtime();
             }
         });
 
-        app.connect_activate(move |app| {
-            if let Some(window) = app.active_window() {
-                if visible {
-                    window.set_visible(true);
+        if !overrides_activate(&app) {
+            app.connect_activate(move |app| {
+                if let Some(window) = app.active_window() {
+                    if visible {
+                        window.set_visible(true);
+                    }
                 }
-            }
-        });
+            });
+        }
 
         let _guard = RUNTIME.enter();
         if let Some(args) = args {
@@ -194,6 +276,12 @@ impl<M: Debug + 'static> RelmApp<M> {
             app.run();
         }
 
+        #[cfg(feature = "leak_diagnostics")]
+        crate::diagnostics::report_leaks();
+
+        // Save remaining changes while components are still alive
+        crate::autosave::flush_all();
+
         // Make sure everything is shut down
         shutdown_all();
         glib::MainContext::ref_thread_default().iteration(true);
@@ -204,6 +292,36 @@ impl<M: Debug + 'static> RelmApp<M> {
     where
         C: AsyncComponent<Input = M>,
         C::Root: AsRef<gtk::Window>,
+    {
+        self.run_async_with_window_setup::<C>(payload, |_| ());
+    }
+
+    /// Runs the application with the root window of the main component as
+    /// Wayland layer surface, returns once the application is closed.
+    ///
+    /// See [`layer_shell`](crate::layer_shell) for details.
+    #[cfg(feature = "layer_shell")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "layer_shell")))]
+    pub fn run_layer_shell_async<C>(
+        self,
+        payload: C::Init,
+        config: crate::layer_shell::LayerShellConfig,
+    ) where
+        C: AsyncComponent<Input = M>,
+        C::Root: AsRef<gtk::Window>,
+    {
+        self.run_async_with_window_setup::<C>(payload, move |window| {
+            config.apply(window);
+        });
+    }
+
+    fn run_async_with_window_setup<C>(
+        self,
+        payload: C::Init,
+        window_setup: impl Fn(&gtk::Window) + 'static,
+    ) where
+        C: AsyncComponent<Input = M>,
+        C::Root: AsRef<gtk::Window>,
     {
         let Self {
             app,
@@ -225,22 +343,27 @@ impl<M: Debug + 'static> RelmApp<M> {
 
                 // Run late initialization for transient windows for example.
                 crate::late_initialization::run_late_init();
+                crate::config::apply_on_startup();
 
                 let mut controller = connector.detach();
                 let window = controller.widget();
+                window_setup(window.as_ref());
                 app.add_window(window.as_ref());
+                MainWindowRegistry::register(window.as_ref());
 
                 controller.detach_runtime();
             }
         });
 
-        app.connect_activate(move |app| {
-            if let Some(window) = app.active_window() {
-                if set_visible {
-                    window.set_visible(true);
+        if !overrides_activate(&app) {
+            app.connect_activate(move |app| {
+                if let Some(window) = app.active_window() {
+                    if set_visible {
+                        window.set_visible(true);
+                    }
                 }
-            }
-        });
+            });
+        }
 
         let _guard = RUNTIME.enter();
         if let Some(args) = args {
@@ -249,8 +372,38 @@ impl<M: Debug + 'static> RelmApp<M> {
             app.run();
         }
 
+        #[cfg(feature = "leak_diagnostics")]
+        crate::diagnostics::report_leaks();
+
+        // Save remaining changes while components are still alive
+        crate::autosave::flush_all();
+
         // Make sure everything is shut down
         shutdown_all();
         glib::MainContext::ref_thread_default().iteration(true);
     }
 }
+
+/// Returns `true` if `app` is an instance of a subclass that overrides
+/// the `activate` virtual function.
+fn overrides_activate(app: &gtk::Application) -> bool {
+    fn activate_fn(class: &glib::Class<gtk::Application>) -> Option<usize> {
+        class
+            .as_ref()
+            .parent_class
+            .activate
+            .map(|func| func as usize)
+    }
+
+    #[cfg(feature = "libadwaita")]
+    let base_type = if app.is::<adw::Application>() {
+        adw::Application::static_type()
+    } else {
+        gtk::Application::static_type()
+    };
+    #[cfg(not(feature = "libadwaita"))]
+    let base_type = gtk::Application::static_type();
+
+    glib::Class::<gtk::Application>::from_type(base_type)
+        .is_some_and(|base_class| activate_fn(app.class()) != activate_fn(&base_class))
+}
@@ -0,0 +1,393 @@
+//! Save changes automatically in the background.
+//!
+//! Components [`register()`](Autosave::register) a function that tells whether
+//! they have unsaved changes and an async function that saves them.
+//! After [`Autosave::mark_changed()`] was called, the service waits until no
+//! changes happened for [`AutosaveSettings::delay`] and saves all components
+//! that have unsaved changes. Failed saves are retried with an increasing delay.
+//!
+//! Each service publishes its progress as [`AutosaveStatus`], so a status bar can show it:
+//!
+//! ```no_run
+//! # use relm4::autosave::{Autosave, AutosaveStatus};
+//! # #[derive(Debug)]
+//! # enum Msg { Autosave(AutosaveStatus) }
+//! # fn init(autosave: &Autosave, sender: relm4::Sender<Msg>) {
+//! autosave.subscribe(&sender, |status| Msg::Autosave(status.clone()));
+//! # }
+//! ```
+//!
+//! Remaining changes are saved when [`RelmApp::run()`](crate::RelmApp::run) returns
+//! and when the [`AutosaveHandle`] of a component is dropped.
+//! Handles that are dropped after the application quit, for example while
+//! the components shut down, save synchronously because the main loop doesn't run anymore.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use futures::lock::Mutex;
+use gtk::glib;
+
+use crate::Sender;
+
+type SaveFuture = Pin<Box<dyn Future<Output = Result<(), String>>>>;
+type SaveFn = Rc<dyn Fn() -> SaveFuture>;
+type Subscriber = Box<dyn Fn(&AutosaveStatus) -> bool>;
+
+thread_local! {
+    static SERVICES: RefCell<Vec<Weak<Inner>>> = RefCell::default();
+    /// Set once the application quit and the main loop stopped.
+    static QUIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The progress of an [`Autosave`] service.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AutosaveStatus {
+    /// All changes are saved.
+    #[default]
+    Saved,
+    /// Changes will be saved after the delay.
+    Modified,
+    /// Changes are being saved.
+    Saving,
+    /// Saving failed and will be retried.
+    Error(String),
+}
+
+/// Configuration for an [`Autosave`] service.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to manually specify, which is configured as such:
+///
+/// - `delay` is set to 2 seconds.
+/// - `retry_delay` is set to 5 seconds and doubles after every failure.
+/// - `max_retry_delay` is set to 5 minutes.
+#[derive(Debug, Clone, Copy)]
+pub struct AutosaveSettings {
+    /// How long to wait after the last change before saving.
+    pub delay: Duration,
+    /// How long to wait before retrying after the first failure.
+    pub retry_delay: Duration,
+    /// The longest delay between retries.
+    pub max_retry_delay: Duration,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_secs(2),
+            retry_delay: Duration::from_secs(5),
+            max_retry_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+struct Entry {
+    id: u64,
+    is_dirty: Box<dyn Fn() -> bool>,
+    save: SaveFn,
+}
+
+struct Inner {
+    settings: AutosaveSettings,
+    status: RefCell<AutosaveStatus>,
+    subscribers: RefCell<Vec<Subscriber>>,
+    entries: RefCell<Vec<Entry>>,
+    /// Entries whose handle was dropped with unsaved changes.
+    removed: RefCell<Vec<Entry>>,
+    next_id: Cell<u64>,
+    timer: RefCell<Option<glib::JoinHandle<()>>>,
+    failures: Cell<u32>,
+    /// Makes sure only one save runs at a time.
+    lock: Mutex<()>,
+}
+
+/// A service that saves the changes of registered components in the background.
+///
+/// Cloning the service returns another handle to the same service.
+/// Must be used from the main thread.
+#[derive(Clone)]
+pub struct Autosave {
+    inner: Rc<Inner>,
+}
+
+impl fmt::Debug for Autosave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Autosave")
+            .field("settings", &self.inner.settings)
+            .field("entries", &self.inner.entries.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self::new(AutosaveSettings::default())
+    }
+}
+
+impl Autosave {
+    /// Create a new service.
+    #[must_use]
+    pub fn new(settings: AutosaveSettings) -> Self {
+        let inner = Rc::new(Inner {
+            settings,
+            status: RefCell::default(),
+            subscribers: RefCell::default(),
+            entries: RefCell::default(),
+            removed: RefCell::default(),
+            next_id: Cell::new(0),
+            timer: RefCell::default(),
+            failures: Cell::new(0),
+            lock: Mutex::new(()),
+        });
+        SERVICES.with(|services| {
+            let mut services = services.borrow_mut();
+            services.retain(|service| service.strong_count() > 0);
+            services.push(Rc::downgrade(&inner));
+        });
+        Self { inner }
+    }
+
+    /// Register a component.
+    ///
+    /// `is_dirty` returns `true` if there are unsaved changes and `save` saves them.
+    /// `save` is expected to reset the state that `is_dirty` checks.
+    /// The component stays registered until the returned handle is dropped.
+    pub fn register<D, S, Fut, E>(&self, is_dirty: D, save: S) -> AutosaveHandle
+    where
+        D: Fn() -> bool + 'static,
+        S: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<(), E>> + 'static,
+        E: fmt::Display,
+    {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+
+        let save: SaveFn = Rc::new(move || {
+            let future = save();
+            Box::pin(async move { future.await.map_err(|error| error.to_string()) })
+        });
+        self.inner.entries.borrow_mut().push(Entry {
+            id,
+            is_dirty: Box::new(is_dirty),
+            save,
+        });
+
+        AutosaveHandle {
+            inner: Rc::downgrade(&self.inner),
+            id,
+        }
+    }
+
+    /// Save after the delay, unless more changes happen in the meantime.
+    pub fn mark_changed(&self) {
+        self.inner.set_status(AutosaveStatus::Modified);
+        self.inner.schedule(self.inner.settings.delay);
+    }
+
+    /// Save all changes right away.
+    ///
+    /// Returns the first error, all other components are saved anyway.
+    pub async fn flush(&self) -> Result<(), String> {
+        self.inner.cancel_timer();
+        self.inner.save().await
+    }
+
+    /// Returns the current status.
+    #[must_use]
+    pub fn status(&self) -> AutosaveStatus {
+        self.inner.status.borrow().clone()
+    }
+
+    /// Send the current status and all of its changes.
+    ///
+    /// The subscription ends when the receiver of `sender` is dropped,
+    /// for example when the component shuts down.
+    pub fn subscribe<Msg, F>(&self, sender: &Sender<Msg>, f: F)
+    where
+        F: Fn(&AutosaveStatus) -> Msg + 'static,
+        Msg: 'static,
+    {
+        sender.emit(f(&self.inner.status.borrow()));
+
+        let sender = sender.clone();
+        self.inner
+            .subscribers
+            .borrow_mut()
+            .push(Box::new(move |status| sender.send(f(status)).is_ok()));
+    }
+}
+
+impl Inner {
+    fn set_status(&self, status: AutosaveStatus) {
+        // Only notify subscribers about actual changes.
+        if *self.status.borrow() != status {
+            self.status.replace(status.clone());
+            self.subscribers
+                .borrow_mut()
+                .retain(|subscriber| subscriber(&status));
+        }
+    }
+
+    fn cancel_timer(&self) {
+        if let Some(timer) = self.timer.take() {
+            timer.abort();
+        }
+    }
+
+    fn schedule(self: &Rc<Self>, delay: Duration) {
+        let weak = Rc::downgrade(self);
+        let timer = crate::spawn_local(async move {
+            crate::time::sleep(delay).await;
+            if let Some(inner) = weak.upgrade() {
+                inner.timer.take();
+                inner.save_in_background();
+            }
+        });
+        if let Some(previous) = self.timer.replace(Some(timer)) {
+            previous.abort();
+        }
+    }
+
+    fn save_in_background(self: &Rc<Self>) {
+        let inner = self.clone();
+        crate::spawn_local(async move {
+            if inner.save().await.is_err() {
+                let failures = inner.failures.get().saturating_add(1);
+                inner.failures.set(failures);
+                inner.schedule(inner.retry_delay(failures));
+            }
+        });
+    }
+
+    /// The delay before the next retry, which doubles after every failure.
+    fn retry_delay(&self, failures: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(failures.saturating_sub(1));
+        self.settings
+            .retry_delay
+            .saturating_mul(factor)
+            .min(self.settings.max_retry_delay)
+    }
+
+    async fn save(&self) -> Result<(), String> {
+        let _guard = self.lock.lock().await;
+
+        let removed = self.removed.take();
+        let dirty: Vec<SaveFn> = removed
+            .iter()
+            .chain(self.entries.borrow().iter())
+            .filter(|entry| (entry.is_dirty)())
+            .map(|entry| entry.save.clone())
+            .collect();
+
+        let mut result = Ok(());
+        if !dirty.is_empty() {
+            self.set_status(AutosaveStatus::Saving);
+            for save in dirty {
+                if let Err(error) = save().await {
+                    tracing::warn!("Autosave failed: {error}");
+                    result = result.and(Err(error));
+                }
+            }
+        }
+        // Removed entries that failed are retried with the others.
+        self.removed
+            .borrow_mut()
+            .extend(removed.into_iter().filter(|entry| (entry.is_dirty)()));
+
+        match &result {
+            Ok(()) => {
+                self.failures.set(0);
+                // New changes happened while saving.
+                if self.timer.borrow().is_some() {
+                    self.set_status(AutosaveStatus::Modified);
+                } else {
+                    self.set_status(AutosaveStatus::Saved);
+                }
+            }
+            Err(error) => self.set_status(AutosaveStatus::Error(error.clone())),
+        }
+        result
+    }
+}
+
+/// Keeps a component registered at an [`Autosave`] service.
+///
+/// Unsaved changes are saved in the background when the handle is dropped,
+/// or right away if the application already quit.
+#[must_use = "the component is unregistered when the handle is dropped"]
+pub struct AutosaveHandle {
+    inner: Weak<Inner>,
+    id: u64,
+}
+
+impl fmt::Debug for AutosaveHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutosaveHandle")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AutosaveHandle {
+    /// Save after the delay of the service, see [`Autosave::mark_changed()`].
+    pub fn mark_changed(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            Autosave { inner }.mark_changed();
+        }
+    }
+}
+
+impl Drop for AutosaveHandle {
+    fn drop(&mut self) {
+        let Some(inner) = self.inner.upgrade() else {
+            return;
+        };
+        let entry = {
+            let mut entries = inner.entries.borrow_mut();
+            let index = entries.iter().position(|entry| entry.id == self.id);
+            index.map(|index| entries.remove(index))
+        };
+
+        if let Some(entry) = entry.filter(|entry| (entry.is_dirty)()) {
+            inner.removed.borrow_mut().push(entry);
+            if QUIT.get() {
+                // Spawned futures wouldn't run anymore.
+                glib::MainContext::ref_thread_default().block_on(async move {
+                    // Errors are already logged.
+                    inner.save().await.ok();
+                });
+            } else {
+                inner.save_in_background();
+            }
+        }
+    }
+}
+
+/// Save the changes of all services, called after the application quit.
+///
+/// Handles that are dropped afterwards save synchronously.
+pub(crate) fn flush_all() {
+    QUIT.set(true);
+    let services: Vec<Rc<Inner>> = SERVICES.with(|services| {
+        services
+            .borrow_mut()
+            .drain(..)
+            .filter_map(|service| service.upgrade())
+            .collect()
+    });
+    if services.is_empty() {
+        return;
+    }
+
+    glib::MainContext::ref_thread_default().block_on(async move {
+        for inner in services {
+            // Errors are already logged.
+            Autosave { inner }.flush().await.ok();
+        }
+    });
+}
@@ -0,0 +1,166 @@
+use gtk::prelude::{CheckButtonExt, EditableExt, ToggleButtonExt};
+
+use crate::Sender;
+
+/// Connects fields of a model with the widgets of a form.
+///
+/// This trait is usually implemented with the `Bindable` derive macro.
+pub trait Bindable {
+    /// The widgets that show the fields.
+    type Widgets;
+
+    /// Identifies a bound field.
+    type Field: std::fmt::Debug + Copy + Eq + 'static;
+
+    /// Show the values of the model in the widgets and send `f(field)`
+    /// whenever the user edits a field.
+    ///
+    /// The model is updated by calling [`update_field()`](Self::update_field)
+    /// with the field when the message is received.
+    fn bind_to_widgets<Msg, F>(&self, widgets: &Self::Widgets, sender: &Sender<Msg>, f: F)
+    where
+        F: Fn(Self::Field) -> Msg + 'static,
+        Msg: 'static;
+
+    /// Read the value of `field` from its widget.
+    fn update_field(&mut self, field: Self::Field, widgets: &Self::Widgets);
+
+    /// Show the values of the model in the widgets,
+    /// for example after the model was changed programmatically.
+    ///
+    /// Only widgets whose value differs from the model are updated.
+    fn sync_widgets(&self, widgets: &Self::Widgets);
+}
+
+/// A widget that edits a value of type `T` in a form, see [`Bindable`].
+pub trait FormWidget<T: PartialEq> {
+    /// Returns the current value.
+    fn form_value(&self) -> T;
+
+    /// Set the value.
+    fn set_form_value(&self, value: &T);
+
+    /// Call `f` whenever the user changes the value.
+    fn connect_form_changed<F: Fn() + 'static>(&self, f: F);
+
+    /// Set the value if it differs from the current one,
+    /// so the widget doesn't notify about unchanged values.
+    fn sync_form_value(&self, value: &T) {
+        if self.form_value() != *value {
+            self.set_form_value(value);
+        }
+    }
+}
+
+macro_rules! impl_form_widget_editable {
+    ($($ty:ty),+) => {
+        $(
+            impl FormWidget<String> for $ty {
+                fn form_value(&self) -> String {
+                    self.text().into()
+                }
+
+                fn set_form_value(&self, value: &String) {
+                    self.set_text(value);
+                }
+
+                fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+                    self.connect_changed(move |_| f());
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_form_widget_active {
+    ($($ty:ty),+) => {
+        $(
+            impl FormWidget<bool> for $ty {
+                fn form_value(&self) -> bool {
+                    self.is_active()
+                }
+
+                fn set_form_value(&self, value: &bool) {
+                    self.set_active(*value);
+                }
+
+                fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+                    self.connect_active_notify(move |_| f());
+                }
+            }
+        )+
+    };
+}
+
+impl_form_widget_editable!(
+    gtk::Entry,
+    gtk::PasswordEntry,
+    gtk::SearchEntry,
+    gtk::Text,
+    gtk::EditableLabel
+);
+impl_form_widget_active!(gtk::Switch, gtk::CheckButton, gtk::ToggleButton);
+
+#[cfg(all(feature = "libadwaita", feature = "gnome_43"))]
+impl_form_widget_editable!(adw::EntryRow, adw::PasswordEntryRow);
+#[cfg(all(feature = "libadwaita", feature = "gnome_45"))]
+impl_form_widget_active!(adw::SwitchRow);
+
+impl FormWidget<f64> for gtk::SpinButton {
+    fn form_value(&self) -> f64 {
+        self.value()
+    }
+
+    fn set_form_value(&self, value: &f64) {
+        self.set_value(*value);
+    }
+
+    fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+        self.connect_value_changed(move |_| f());
+    }
+}
+
+impl FormWidget<i32> for gtk::SpinButton {
+    fn form_value(&self) -> i32 {
+        self.value_as_int()
+    }
+
+    fn set_form_value(&self, value: &i32) {
+        self.set_value(f64::from(*value));
+    }
+
+    fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+        self.connect_value_changed(move |_| f());
+    }
+}
+
+#[cfg(all(feature = "libadwaita", feature = "gnome_45"))]
+impl FormWidget<f64> for adw::SpinRow {
+    fn form_value(&self) -> f64 {
+        self.value()
+    }
+
+    fn set_form_value(&self, value: &f64) {
+        self.set_value(*value);
+    }
+
+    fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+        self.connect_value_notify(move |_| f());
+    }
+}
+
+#[cfg(all(feature = "libadwaita", feature = "gnome_45"))]
+impl FormWidget<i32> for adw::SpinRow {
+    fn form_value(&self) -> i32 {
+        // Rounded like `gtk::SpinButton::value_as_int()`.
+        self.value().round() as i32
+    }
+
+    fn set_form_value(&self, value: &i32) {
+        self.set_value(f64::from(*value));
+    }
+
+    fn connect_form_changed<F: Fn() + 'static>(&self, f: F) {
+        self.connect_value_notify(move |_| f());
+    }
+}
@@ -10,16 +10,36 @@
 //! To find out which widgets are currently supported and which property is considered as primary,
 //! please look at the list implementers for [`ConnectBinding`].
 //! Contributions to add support for more widgets are always welcome.
+//!
+//! Forms can connect the fields of their model with the widgets of the same name
+//! with the [`Bindable`] trait, which is usually derived.
 
 mod bindings;
+mod form;
 mod widgets;
 
 pub use bindings::*;
+pub use form::{Bindable, FormWidget};
 
 use std::ops::{Deref, DerefMut};
 
 use gtk::{glib, prelude::IsA};
 
+/// Exposes fields of a model as properties of a companion GObject.
+///
+/// This trait is usually implemented with the `GlibProps` derive macro.
+pub trait GlibProps {
+    /// The companion GObject.
+    type Props: IsA<glib::Object>;
+
+    /// Create a new companion object with the current values of the model.
+    #[must_use]
+    fn props(&self) -> Self::Props;
+
+    /// Update all properties of the companion object that don't match the model anymore.
+    fn sync_props(&self, props: &Self::Props);
+}
+
 /// A trait that allows type-safe bindings between to the primary properties of two objects.
 pub trait ConnectBinding {
     /// The type of the primary property.
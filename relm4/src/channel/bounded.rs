@@ -0,0 +1,238 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use flume::{SendTimeoutError, TrySendError};
+
+use super::{Receiver, Sender, Source};
+
+/// Interval in which a blocked sender checks whether the receiver was dropped.
+const BLOCK_TIMEOUT: Duration = Duration::from_millis(100);
+
+type CoalesceFn<T> = Arc<dyn Fn(&T, &T) -> bool + Send + Sync>;
+
+/// Decides what happens if a message is sent to a full [`bounded_channel()`].
+pub enum OverflowStrategy<T> {
+    /// Block the sender until there's room in the queue.
+    ///
+    /// Blocking the main thread would freeze the application,
+    /// so messages sent from the main thread drop the oldest
+    /// queued message instead.
+    Block,
+    /// Drop the oldest queued message.
+    DropOldest,
+    /// Replace a queued message for which the function returns [`true`]
+    /// when called with the queued and the new message.
+    /// Drops the oldest queued message if no message matches.
+    ///
+    /// Use [`OverflowStrategy::coalesce_by_key()`] to compare messages by a key.
+    Coalesce(CoalesceFn<T>),
+}
+
+impl<T> OverflowStrategy<T> {
+    /// Replace a queued message with the same key
+    /// or drop the oldest message if no message has the same key.
+    ///
+    /// ```
+    /// # use relm4::OverflowStrategy;
+    /// enum Msg {
+    ///     Progress(u32, f64),
+    ///     Cancel,
+    /// }
+    ///
+    /// // Only keep the latest progress of each task.
+    /// let strategy = OverflowStrategy::coalesce_by_key(|msg: &Msg| match msg {
+    ///     Msg::Progress(task, _) => Some(*task),
+    ///     Msg::Cancel => None,
+    /// });
+    /// ```
+    pub fn coalesce_by_key<K, F>(key: F) -> Self
+    where
+        K: PartialEq,
+        F: Fn(&T) -> K + Send + Sync + 'static,
+    {
+        Self::Coalesce(Arc::new(move |queued, new| key(queued) == key(new)))
+    }
+}
+
+impl<T> Clone for OverflowStrategy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Block => Self::Block,
+            Self::DropOldest => Self::DropOldest,
+            Self::Coalesce(same) => Self::Coalesce(same.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for OverflowStrategy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block => f.write_str("Block"),
+            Self::DropOldest => f.write_str("DropOldest"),
+            Self::Coalesce(_) => f.write_str("Coalesce"),
+        }
+    }
+}
+
+/// A snapshot of the queue of a channel, see [`Sender::metrics()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueMetrics {
+    /// Number of queued messages.
+    pub len: usize,
+    /// Maximum number of queued messages or [`None`] for unbounded channels.
+    pub capacity: Option<usize>,
+    /// Number of messages that were dropped because the queue was full.
+    pub dropped: usize,
+    /// Number of queued messages that were replaced by a newer message.
+    pub coalesced: usize,
+}
+
+/// Create a bounded channel that holds at most `capacity` messages.
+///
+/// If the queue is full, new messages are handled according to `strategy`.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+#[must_use]
+pub fn bounded_channel<T>(
+    capacity: usize,
+    strategy: OverflowStrategy<T>,
+) -> (Sender<T>, Receiver<T>) {
+    assert!(
+        capacity > 0,
+        "Bounded channels need a capacity of at least 1"
+    );
+    let (tx, rx) = flume::bounded(capacity);
+    let overflow = Overflow {
+        strategy,
+        receiver: rx.clone(),
+        lock: Mutex::new(()),
+        dropped: AtomicUsize::new(0),
+        coalesced: AtomicUsize::new(0),
+    };
+    (
+        Sender(tx, Some(Arc::new(overflow))),
+        Receiver(rx, Source::default()),
+    )
+}
+
+/// Handles messages that are sent to a full bounded channel.
+pub(super) struct Overflow<T> {
+    strategy: OverflowStrategy<T>,
+    /// Used to remove queued messages.
+    receiver: flume::Receiver<T>,
+    /// Prevents concurrent senders from removing messages at the same time.
+    lock: Mutex<()>,
+    dropped: AtomicUsize,
+    coalesced: AtomicUsize,
+}
+
+impl<T> Overflow<T> {
+    /// Returns [`true`] if all receivers except the one of the overflow handler were dropped.
+    fn is_disconnected(&self) -> bool {
+        self.receiver.sender_count() == 0 || self.receiver.receiver_count() <= 1
+    }
+
+    pub(super) fn send(&self, sender: &flume::Sender<T>, message: T) -> Result<(), T> {
+        if self.is_disconnected() {
+            return Err(message);
+        }
+
+        let guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut message = match sender.try_send(message) {
+            Ok(()) => return Ok(()),
+            Err(TrySendError::Disconnected(message)) => return Err(message),
+            Err(TrySendError::Full(message)) => message,
+        };
+
+        match &self.strategy {
+            OverflowStrategy::Block if !gtk::glib::MainContext::default().is_owner() => {
+                drop(guard);
+                loop {
+                    match sender.send_timeout(message, BLOCK_TIMEOUT) {
+                        Ok(()) => return Ok(()),
+                        Err(SendTimeoutError::Timeout(msg)) if !self.is_disconnected() => {
+                            message = msg;
+                        }
+                        Err(
+                            SendTimeoutError::Timeout(msg) | SendTimeoutError::Disconnected(msg),
+                        ) => {
+                            return Err(msg);
+                        }
+                    }
+                }
+            }
+            OverflowStrategy::Coalesce(same) => {
+                let mut queued: Vec<T> = self.receiver.try_iter().collect();
+                if let Some(slot) = queued.iter_mut().find(|queued| same(queued, &message)) {
+                    *slot = message;
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    if !queued.is_empty() {
+                        queued.remove(0);
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    queued.push(message);
+                }
+                for message in queued {
+                    // There's room for all messages because they were queued before.
+                    if let Err(TrySendError::Disconnected(message) | TrySendError::Full(message)) =
+                        sender.try_send(message)
+                    {
+                        return Err(message);
+                    }
+                }
+                Ok(())
+            }
+            OverflowStrategy::Block | OverflowStrategy::DropOldest => loop {
+                if self.receiver.try_recv().is_ok() {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                match sender.try_send(message) {
+                    Ok(()) => return Ok(()),
+                    Err(TrySendError::Disconnected(msg)) => return Err(msg),
+                    Err(TrySendError::Full(msg)) => message = msg,
+                }
+            },
+        }
+    }
+
+    pub(super) fn metrics(&self, sender: &flume::Sender<T>) -> QueueMetrics {
+        QueueMetrics {
+            len: sender.len(),
+            capacity: sender.capacity(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bounded_channel, OverflowStrategy};
+
+    #[test]
+    fn overflow() {
+        let (sender, receiver) = bounded_channel(2, OverflowStrategy::DropOldest);
+        for value in 0..4 {
+            sender.send(value).unwrap();
+        }
+        assert_eq!(sender.metrics().dropped, 2);
+        assert_eq!(receiver.0.drain().collect::<Vec<_>>(), [2, 3]);
+
+        let strategy = OverflowStrategy::coalesce_by_key(|(key, _): &(u8, u8)| *key);
+        let (sender, receiver) = bounded_channel(2, strategy);
+        for message in [(0, 0), (1, 0), (0, 1), (2, 0)] {
+            sender.send(message).unwrap();
+        }
+        let metrics = sender.metrics();
+        assert_eq!((metrics.len, metrics.coalesced, metrics.dropped), (2, 1, 1));
+        assert_eq!(receiver.0.drain().collect::<Vec<_>>(), [(1, 0), (2, 0)]);
+
+        drop(receiver);
+        assert!(sender.send((0, 0)).is_err());
+    }
+}
@@ -3,13 +3,57 @@
 
 //! Contains various flavors of channels to send messages between components and workers.
 
-use std::fmt::Debug;
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
 use std::future::Future;
-use std::sync::Arc;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures::{Stream, StreamExt};
+use gtk::glib::ControlFlow;
+use gtk::prelude::{IsA, WidgetExt, WidgetExtManual};
 
 use crate::component::AsyncComponent;
 use crate::factory::{AsyncFactoryComponent, FactoryComponent};
-use crate::{Component, Sender, ShutdownReceiver};
+use crate::{Component, InputPriority, PrioritySender, Sender, ShutdownReceiver};
+
+/// Number of stream items that are buffered by [`ComponentSender::subscribe_stream()`]
+/// before the stream is no longer polled.
+const STREAM_BUFFER_SIZE: usize = 32;
+
+/// A command that was queued instead of being spawned on the runtime.
+pub(crate) type QueuedCommand = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The commands queued by a [`MockComponentSender`](crate::test::MockComponentSender).
+#[derive(Clone, Default)]
+pub(crate) struct CommandQueue(Arc<Mutex<Vec<QueuedCommand>>>);
+
+impl CommandQueue {
+    fn push(&self, command: QueuedCommand) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(command);
+    }
+
+    /// Removes all queued commands.
+    pub(crate) fn take(&self) -> Vec<QueuedCommand> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+impl Debug for CommandQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
 
 // Contains senders used by components and factories internally.
 #[derive(Debug)]
@@ -20,11 +64,15 @@ where
 {
     /// Emits component inputs.
     input: Sender<Input>,
+    /// Emits component inputs with a high or low priority.
+    priority: PrioritySender<Input>,
     /// Emits component outputs.
     output: Sender<Output>,
     /// Emits command outputs.
     command: Sender<CommandOutput>,
     shutdown: ShutdownReceiver,
+    /// Queues commands instead of spawning them if the sender is mocked.
+    commands: Option<CommandQueue>,
 }
 
 impl<Input, Output, CommandOutput> ComponentSenderInner<Input, Output, CommandOutput>
@@ -66,6 +114,21 @@ where
         self.input.send(message).expect("The runtime of the component was shutdown. Maybe you accidentally dropped a controller?");
     }
 
+    /// Emit an input to the component with the given priority.
+    fn input_priority(&self, message: Input, priority: InputPriority) {
+        let sender = match priority {
+            InputPriority::High => &self.priority.high,
+            InputPriority::Normal => &self.input,
+            InputPriority::Low => &self.priority.low,
+        };
+        sender.send(message).expect("The runtime of the component was shutdown. Maybe you accidentally dropped a controller?");
+    }
+
+    /// Emit an input to the component that replaces a queued input with the same key.
+    fn input_coalesced<K: Hash>(&self, key: K, message: Input) {
+        self.priority.send_coalesced(key, message).expect("The runtime of the component was shutdown. Maybe you accidentally dropped a controller?");
+    }
+
     /// This is not public because factories can unwrap the result
     /// because they keep the output receiver alive internally.
     fn output(&self, message: Output) -> Result<(), Output> {
@@ -82,9 +145,26 @@ where
     {
         let recipient = self.shutdown.clone();
         let sender = self.command.clone();
-        crate::spawn(async move {
+        let future = async move {
+            #[cfg(feature = "metrics")]
+            let start = std::time::Instant::now();
             cmd(sender, recipient).await;
-        });
+            #[cfg(feature = "metrics")]
+            crate::telemetry::command(std::any::type_name::<CommandOutput>(), start.elapsed());
+        };
+        self.spawn_or_queue(future);
+    }
+
+    /// Spawns a command on the runtime or queues it if the sender is mocked.
+    fn spawn_or_queue<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if let Some(commands) = &self.commands {
+            commands.push(Box::pin(future));
+        } else {
+            crate::spawn(future);
+        }
     }
 
     /// Spawns a synchronous command.
@@ -99,7 +179,17 @@ where
         Cmd: FnOnce(Sender<CommandOutput>) + Send + 'static,
     {
         let sender = self.command.clone();
-        crate::spawn_blocking(move || cmd(sender));
+        if self.commands.is_some() {
+            self.spawn_or_queue(async move { cmd(sender) });
+        } else {
+            crate::spawn_blocking(move || {
+                #[cfg(feature = "metrics")]
+                let start = std::time::Instant::now();
+                cmd(sender);
+                #[cfg(feature = "metrics")]
+                crate::telemetry::command(std::any::type_name::<CommandOutput>(), start.elapsed());
+            });
+        }
     }
 
     /// Spawns a future that will be dropped as soon as the factory component is shut down.
@@ -124,11 +214,118 @@ where
     where
         Cmd: FnOnce() -> CommandOutput + Send + 'static,
     {
-        let handle = crate::spawn_blocking(cmd);
-        self.oneshot_command(async move { handle.await.unwrap() })
+        if self.commands.is_some() {
+            self.oneshot_command(async move { cmd() });
+        } else {
+            let handle = crate::spawn_blocking(cmd);
+            self.oneshot_command(async move { handle.await.unwrap() });
+        }
+    }
+
+    /// Performs a request on the runtime and forwards the deserialized response as input.
+    #[cfg(feature = "reqwest")]
+    fn fetch_json<T, F>(&self, request: crate::net::JsonRequest, mapper: F)
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+        F: FnOnce(Result<T, crate::net::FetchError>) -> Input + 'static,
+        Input: 'static,
+    {
+        crate::net::fetch_json(request, self.input.clone(), self.shutdown.clone(), mapper);
+    }
+
+    /// Runs a keyring request on the runtime and forwards the result as input.
+    #[cfg(feature = "secrets")]
+    fn secret_request<T, Fut, F>(&self, request: Fut, mapper: F)
+    where
+        T: Send + 'static,
+        Fut: Future<Output = Result<T, crate::secrets::SecretError>> + Send + 'static,
+        F: FnOnce(Result<T, crate::secrets::SecretError>) -> Input + 'static,
+        Input: 'static,
+    {
+        crate::secrets::forward(request, self.input.clone(), self.shutdown.clone(), mapper);
+    }
+
+    /// Calls `mapper` on each frame of the frame clock of `widget`
+    /// and sends the result as input.
+    fn on_frame<F>(&self, widget: &gtk::Widget, mapper: F) -> gtk::TickCallbackId
+    where
+        F: FnMut(i64) -> Input + 'static,
+        Input: 'static,
+    {
+        let input = self.input.clone();
+        let mapper = RefCell::new(mapper);
+        widget.add_tick_callback(move |widget, frame_clock| {
+            // Hidden widgets don't need to be animated.
+            if !widget.is_mapped() {
+                return ControlFlow::Continue;
+            }
+            if input
+                .send((mapper.borrow_mut())(frame_clock.frame_time()))
+                .is_err()
+            {
+                // The component was shut down.
+                ControlFlow::Break
+            } else {
+                ControlFlow::Continue
+            }
+        })
+    }
+
+    /// Spawns a stream on the runtime and forwards its items as inputs.
+    fn subscribe_stream<S, F>(&self, stream: S, mut mapper: F)
+    where
+        S: Stream + Send + 'static,
+        S::Item: Send,
+        F: FnMut(S::Item) -> Input + 'static,
+        Input: 'static,
+    {
+        // The stream is only polled while there's room in the buffer,
+        // so a fast stream can't flood the input queue of the component.
+        let (tx, rx) = flume::bounded(STREAM_BUFFER_SIZE);
+
+        crate::spawn(
+            self.shutdown
+                .clone()
+                .register(async move {
+                    let mut stream = std::pin::pin!(stream);
+                    while let Some(item) = stream.next().await {
+                        if tx.send_async(item).await.is_err() {
+                            break;
+                        }
+                    }
+                })
+                .drop_on_shutdown(),
+        );
+
+        let input = self.input.clone();
+        crate::spawn_local(
+            self.shutdown
+                .clone()
+                .register(async move {
+                    while let Ok(item) = rx.recv_async().await {
+                        if input.send(mapper(item)).is_err() {
+                            break;
+                        }
+                        // Let the component process the input
+                        // before taking the next item from the buffer.
+                        yield_to_main_loop().await;
+                    }
+                })
+                .drop_on_shutdown(),
+        );
     }
 }
 
+/// Resolves once the main loop is idle, so inputs
+/// that were sent before have been processed.
+async fn yield_to_main_loop() {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    gtk::glib::idle_add_local_once(move || {
+        tx.send(()).ok();
+    });
+    rx.await.ok();
+}
+
 macro_rules! sender_impl {
     ($name:ident, $trait:ident) => {
         /// Contains senders to send and receive messages from a [`Component`].
@@ -140,6 +337,7 @@ macro_rules! sender_impl {
         impl<C: $trait> $name<C> {
             pub(crate) fn new(
                 input: Sender<C::Input>,
+                priority: PrioritySender<C::Input>,
                 output: Sender<C::Output>,
                 command: Sender<C::CommandOutput>,
                 shutdown: ShutdownReceiver,
@@ -147,9 +345,11 @@ macro_rules! sender_impl {
                 Self {
                     shared: Arc::new(ComponentSenderInner {
                         input,
+                        priority,
                         output,
                         command,
                         shutdown,
+                        commands: None,
                     }),
                 }
             }
@@ -186,6 +386,34 @@ macro_rules! sender_impl {
                 self.shared.input(message);
             }
 
+            /// Emit an input to the component with the given [`InputPriority`].
+            ///
+            /// Messages with a higher priority are processed before queued messages
+            /// with a lower priority, so urgent messages like cancellation or
+            /// user interaction don't have to wait for bulk updates.
+            /// To prevent starvation, a message with a lower priority is still
+            /// processed after a couple of messages with a higher priority were
+            /// handled in a row. Messages with the same priority keep their order.
+            pub fn input_priority(&self, message: C::Input, priority: InputPriority) {
+                self.shared.input_priority(message, priority);
+            }
+
+            /// Emit an input to the component that replaces a queued input
+            /// with the same key that wasn't processed yet.
+            ///
+            /// This is useful for messages like progress reports or cursor
+            /// positions where only the latest value matters.
+            /// The message keeps the position of the first queued message with the same key
+            /// and is processed with [`InputPriority::Normal`], but it isn't ordered
+            /// relative to messages sent with [`input()`](Self::input).
+            ///
+            /// ```ignore
+            /// sender.input_coalesced(("progress", task_id), AppMsg::Progress(task_id, fraction));
+            /// ```
+            pub fn input_coalesced<K: Hash>(&self, key: K, message: C::Input) {
+                self.shared.input_coalesced(key, message);
+            }
+
             /// Emit an output to the component.
             ///
             /// Returns [`Err`] if all receivers were dropped,
@@ -240,6 +468,133 @@ macro_rules! sender_impl {
             {
                 self.shared.spawn_oneshot_command(cmd)
             }
+
+            /// Spawns a stream on the runtime and maps each of its items to an input message.
+            ///
+            /// Only a small number of items is buffered. If the component doesn't keep up
+            /// with the stream, the stream isn't polled until there's room in the buffer again.
+            /// The stream is dropped as soon as the component is shut down.
+            ///
+            /// This is useful to feed streams of websockets, server-sent events
+            /// or other channels into the update loop.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called from a thread that doesn't run the component.
+            pub fn subscribe_stream<S, F>(&self, stream: S, mapper: F)
+            where
+                S: Stream + Send + 'static,
+                S::Item: Send,
+                F: FnMut(S::Item) -> C::Input + 'static,
+            {
+                self.shared.subscribe_stream(stream, mapper)
+            }
+
+            /// Maps each frame of the frame clock of `widget` to an input message.
+            ///
+            /// `mapper` receives the frame time in microseconds, see
+            /// [`FrameClock::frame_time()`](gtk::gdk::FrameClock::frame_time).
+            /// Frames are only delivered while `widget` is mapped, so hidden
+            /// animations don't burn CPU time like free-running timers would.
+            /// The callback is removed once the component is shut down or
+            /// when the returned [`TickCallbackId`](gtk::TickCallbackId) is removed.
+            ///
+            /// ```ignore
+            /// sender.on_frame(&widgets.canvas, AppMsg::Tick);
+            /// ```
+            pub fn on_frame<F>(
+                &self,
+                widget: &impl IsA<gtk::Widget>,
+                mapper: F,
+            ) -> gtk::TickCallbackId
+            where
+                F: FnMut(i64) -> C::Input + 'static,
+            {
+                self.shared.on_frame(widget.as_ref(), mapper)
+            }
+
+            /// Performs a HTTP request on the runtime, deserializes the JSON response
+            /// and maps the result to an input message.
+            ///
+            /// The request is canceled as soon as the component is shut down.
+            /// See [`net`](crate::net) for an example.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called from a thread that doesn't run the component.
+            #[cfg(feature = "reqwest")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+            pub fn fetch_json<T, F>(&self, request: impl Into<crate::net::JsonRequest>, mapper: F)
+            where
+                T: serde::de::DeserializeOwned + Send + 'static,
+                F: FnOnce(Result<T, crate::net::FetchError>) -> C::Input + 'static,
+            {
+                self.shared.fetch_json(request.into(), mapper)
+            }
+
+            /// Stores a secret in the keyring on the runtime and maps the result to an input message.
+            ///
+            /// A secret with the same attributes is replaced.
+            /// See [`secrets`](crate::secrets) for an example.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called from a thread that doesn't run the component.
+            #[cfg(feature = "secrets")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "secrets")))]
+            pub fn store_secret<S, F>(
+                &self,
+                label: &str,
+                attributes: crate::secrets::Attributes,
+                secret: S,
+                mapper: F,
+            ) where
+                S: AsRef<[u8]> + Send + 'static,
+                F: FnOnce(Result<(), crate::secrets::SecretError>) -> C::Input + 'static,
+            {
+                let label = label.to_owned();
+                self.shared.secret_request(
+                    async move { crate::secrets::store(&label, &attributes, secret).await },
+                    mapper,
+                )
+            }
+
+            /// Looks up a secret in the keyring on the runtime and maps the result to an input message.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called from a thread that doesn't run the component.
+            #[cfg(feature = "secrets")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "secrets")))]
+            pub fn lookup_secret<F>(&self, attributes: crate::secrets::Attributes, mapper: F)
+            where
+                F: FnOnce(
+                        Result<Option<crate::secrets::Secret>, crate::secrets::SecretError>,
+                    ) -> C::Input
+                    + 'static,
+            {
+                self.shared.secret_request(
+                    async move { crate::secrets::lookup(&attributes).await },
+                    mapper,
+                )
+            }
+
+            /// Deletes secrets from the keyring on the runtime and maps the result to an input message.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called from a thread that doesn't run the component.
+            #[cfg(feature = "secrets")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "secrets")))]
+            pub fn delete_secret<F>(&self, attributes: crate::secrets::Attributes, mapper: F)
+            where
+                F: FnOnce(Result<(), crate::secrets::SecretError>) -> C::Input + 'static,
+            {
+                self.shared.secret_request(
+                    async move { crate::secrets::delete(&attributes).await },
+                    mapper,
+                )
+            }
         }
 
         impl<C: $trait> Clone for $name<C> {
@@ -253,6 +608,30 @@ macro_rules! sender_impl {
 }
 
 sender_impl!(ComponentSender, Component);
+
+impl<C: Component> ComponentSender<C> {
+    /// Creates a sender that queues commands instead of spawning them.
+    pub(crate) fn mock(
+        input: Sender<C::Input>,
+        priority: PrioritySender<C::Input>,
+        output: Sender<C::Output>,
+        command: Sender<C::CommandOutput>,
+        shutdown: ShutdownReceiver,
+        commands: CommandQueue,
+    ) -> Self {
+        Self {
+            shared: Arc::new(ComponentSenderInner {
+                input,
+                priority,
+                output,
+                command,
+                shutdown,
+                commands: Some(commands),
+            }),
+        }
+    }
+}
+
 sender_impl!(AsyncComponentSender, AsyncComponent);
 sender_impl!(FactorySender, FactoryComponent);
 sender_impl!(AsyncFactorySender, AsyncFactoryComponent);
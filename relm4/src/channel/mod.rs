@@ -1,30 +1,43 @@
+mod bounded;
 mod component;
+mod payload;
+mod priority;
+mod responder;
 /// Cancellation mechanism used by Relm4.
 pub mod shutdown;
 
+pub use bounded::{bounded_channel, OverflowStrategy, QueueMetrics};
+pub(crate) use component::CommandQueue;
 pub use component::{AsyncComponentSender, AsyncFactorySender, ComponentSender, FactorySender};
+pub use payload::{LargePayload, WeakPayload};
+pub use priority::InputPriority;
+pub(crate) use priority::{priority_channel, CoalescedInputs, PriorityReceiver, PrioritySender};
+pub use responder::{Responder, Response};
 
 // Copyright 2022 System76 <info@system76.com>
 // SPDX-License-Identifier: MIT or Apache-2.0
 
 use std::fmt;
+use std::sync::Arc;
 
 use flume::r#async::RecvStream;
 
+use bounded::Overflow;
+
 /// Create an unbounded channel to send messages
 /// between different parts of you application.
 #[must_use]
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     let (tx, rx) = flume::unbounded();
-    (Sender(tx), Receiver(rx))
+    (Sender(tx, None), Receiver(rx, Source::default()))
 }
 
 /// A Relm4 sender sends messages to a component or worker.
-pub struct Sender<T>(pub(crate) flume::Sender<T>);
+pub struct Sender<T>(pub(crate) flume::Sender<T>, Option<Arc<Overflow<T>>>);
 
 impl<T> From<flume::Sender<T>> for Sender<T> {
     fn from(sender: flume::Sender<T>) -> Self {
-        Self(sender)
+        Self(sender, None)
     }
 }
 
@@ -44,13 +57,55 @@ impl<T> Sender<T> {
     /// If all receivers where dropped, [`Err`] is returned
     /// with the content of the message.
     pub fn send(&self, message: T) -> Result<(), T> {
-        self.0.send(message).map_err(|e| e.into_inner())
+        if let Some(overflow) = &self.1 {
+            overflow.send(&self.0, message)
+        } else {
+            self.0.send(message).map_err(|e| e.into_inner())
+        }
+    }
+
+    /// Returns a snapshot of the message queue of the channel.
+    ///
+    /// Useful to find components that can't keep up with their inputs.
+    #[must_use]
+    pub fn metrics(&self) -> QueueMetrics {
+        if let Some(overflow) = &self.1 {
+            overflow.metrics(&self.0)
+        } else {
+            QueueMetrics {
+                len: self.0.len(),
+                ..QueueMetrics::default()
+            }
+        }
+    }
+
+    /// Sends a message that carries a [`Responder`] and
+    /// returns a future that resolves to the response.
+    ///
+    /// The future resolves to [`None`] if the receiver was dropped
+    /// or didn't respond.
+    ///
+    /// ```
+    /// # use relm4::Responder;
+    /// # #[derive(Debug)]
+    /// # enum ChildMsg { GetName(Responder<String>) }
+    /// # async fn example(sender: relm4::Sender<ChildMsg>) {
+    /// let name: Option<String> = sender.request(ChildMsg::GetName).await;
+    /// # }
+    /// ```
+    pub fn request<R, F>(&self, message: F) -> Response<R>
+    where
+        F: FnOnce(Responder<R>) -> T,
+    {
+        let (responder, response) = Responder::new();
+        self.emit(message(responder));
+        response
     }
 }
 
 impl<T> Clone for Sender<T> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self(self.0.clone(), self.1.clone())
     }
 }
 
@@ -61,9 +116,21 @@ impl<T> fmt::Debug for Sender<T> {
 }
 
 /// A Relm4 receiver receives messages from a component or worker.
-pub struct Receiver<T>(pub(crate) flume::Receiver<T>);
+pub struct Receiver<T>(pub(crate) flume::Receiver<T>, Source);
+
+/// The component that sends the messages of a [`Receiver`].
+///
+/// This is used to record links in the component graph.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Source(#[cfg(feature = "component_graph")] Option<u64>);
 
 impl<T> Receiver<T> {
+    /// Marks the messages of this receiver as outputs of a component.
+    #[cfg(feature = "component_graph")]
+    pub(crate) fn with_source(self, node: &crate::debug::Node) -> Self {
+        Self(self.0, Source(Some(node.id())))
+    }
+
     /// Receives a message from a component or worker.
     ///
     /// Returns [`None`] if all senders have been disconnected.
@@ -96,14 +163,102 @@ impl<T> Receiver<T> {
         Output: 'static,
     {
         let sender = sender.into();
+        #[cfg(feature = "component_graph")]
+        let messages = crate::debug::link(self.1 .0, &sender);
         while let Some(event) = self.recv().await {
             if sender.send(transformer(event)).is_err() {
                 return;
             }
+            #[cfg(feature = "component_graph")]
+            count(messages.as_deref());
+        }
+    }
+}
+
+impl<T: 'static> Receiver<T> {
+    /// Forwards the events for which `filter` returns [`Some`] to another channel.
+    pub async fn forward_filtered<Filter, Output>(
+        self,
+        sender: impl Into<Sender<Output>>,
+        filter: Filter,
+    ) where
+        Filter: (Fn(T) -> Option<Output>) + 'static,
+        Output: 'static,
+    {
+        let sender = sender.into();
+        #[cfg(feature = "component_graph")]
+        let messages = crate::debug::link(self.1 .0, &sender);
+        while let Some(event) = self.recv().await {
+            if let Some(event) = filter(event) {
+                if sender.send(event).is_err() {
+                    return;
+                }
+                #[cfg(feature = "component_graph")]
+                count(messages.as_deref());
+            }
+        }
+    }
+
+    /// Forwards events to one of two channels, depending on the [`OutputRoute`]
+    /// returned by `router`.
+    pub async fn forward_split<Router, First, Second>(
+        self,
+        first: impl Into<Sender<First>>,
+        second: impl Into<Sender<Second>>,
+        router: Router,
+    ) where
+        Router: (Fn(T) -> OutputRoute<First, Second>) + 'static,
+        First: 'static,
+        Second: 'static,
+    {
+        let first = first.into();
+        let second = second.into();
+        #[cfg(feature = "component_graph")]
+        let (first_messages, second_messages) = (
+            crate::debug::link(self.1 .0, &first),
+            crate::debug::link(self.1 .0, &second),
+        );
+        while let Some(event) = self.recv().await {
+            let is_closed = match router(event) {
+                OutputRoute::First(event) => {
+                    #[cfg(feature = "component_graph")]
+                    count(first_messages.as_deref());
+                    first.send(event).is_err()
+                }
+                OutputRoute::Second(event) => {
+                    #[cfg(feature = "component_graph")]
+                    count(second_messages.as_deref());
+                    second.send(event).is_err()
+                }
+                OutputRoute::Ignore => false,
+            };
+            if is_closed {
+                return;
+            }
         }
     }
 }
 
+/// Counts a message that was forwarded through a link of the component graph.
+#[cfg(feature = "component_graph")]
+fn count(messages: Option<&std::sync::atomic::AtomicU64>) {
+    if let Some(messages) = messages {
+        messages.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Decides which channel receives an event that is forwarded
+/// with [`Receiver::forward_split()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputRoute<A, B> {
+    /// Send the event to the first channel.
+    First(A),
+    /// Send the event to the second channel.
+    Second(B),
+    /// Drop the event.
+    Ignore,
+}
+
 impl<T> fmt::Debug for Receiver<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Receiver").field(&self.0).finish()
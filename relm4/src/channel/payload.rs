@@ -0,0 +1,255 @@
+use std::fmt;
+use std::mem::size_of_val;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Bytes of all [`LargePayload`]s that are still alive.
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+type Storage<T> = Box<dyn Deref<Target = T> + Send + Sync>;
+type ReleaseCallback = Box<dyn FnOnce() + Send>;
+
+struct Inner<T: ?Sized + 'static> {
+    data: Storage<T>,
+    bytes: usize,
+    on_release: Mutex<Vec<ReleaseCallback>>,
+}
+
+impl<T: ?Sized + 'static> Drop for Inner<T> {
+    fn drop(&mut self) {
+        LIVE_BYTES.fetch_sub(self.bytes, Ordering::Relaxed);
+        let callbacks =
+            std::mem::take(self.on_release.get_mut().unwrap_or_else(|e| e.into_inner()));
+        for callback in callbacks {
+            callback();
+        }
+    }
+}
+
+/// A shared handle to a large buffer, such as an image or a dataset,
+/// that is passed between workers, commands and components without copying it.
+///
+/// Cloning a payload only clones the handle. The buffer is released
+/// once the last handle is dropped, for example after the UI dropped
+/// the message that carried it.
+/// Use [`LargePayload::on_release()`] or [`WeakPayload`] to track this.
+///
+/// Payloads of bytes can be passed to GTK without copying as well
+/// because they implement [`AsRef<[u8]>`](AsRef):
+///
+/// ```
+/// # use relm4::LargePayload;
+/// # use gtk::glib;
+/// let payload = LargePayload::from_vec(vec![0_u8; 1024]);
+/// let bytes = glib::Bytes::from_owned(payload.clone());
+/// assert_eq!(bytes.len(), 1024);
+/// ```
+pub struct LargePayload<T: ?Sized + 'static> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Send + Sync + 'static> LargePayload<T> {
+    /// Move a value into a new payload.
+    ///
+    /// Only the shallow size of the value is counted in [`LargePayload::live_bytes()`],
+    /// use [`LargePayload::with_size()`] to count the heap memory of the value as well.
+    pub fn new(value: T) -> Self {
+        let bytes = size_of_val(&value);
+        Self::with_size(value, bytes)
+    }
+
+    /// Move a value into a new payload and count it with the given size.
+    pub fn with_size(value: T, bytes: usize) -> Self {
+        Self::from_storage(Box::new(Box::new(value)), bytes)
+    }
+}
+
+impl<E: Send + Sync + 'static> LargePayload<[E]> {
+    /// Move the elements of a vector into a new payload without copying them.
+    #[must_use]
+    pub fn from_vec(vec: Vec<E>) -> Self {
+        let bytes = size_of_val(vec.as_slice());
+        Self::from_storage(Box::new(vec), bytes)
+    }
+}
+
+#[cfg(feature = "memmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap")))]
+impl LargePayload<[u8]> {
+    /// Map a file into memory and use it as payload.
+    ///
+    /// The file isn't read until its content is accessed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be mapped.
+    ///
+    /// # Safety
+    ///
+    /// The content of the payload changes if the file is modified
+    /// by this or another process while it's mapped,
+    /// which is undefined behavior, see [`memmap2::Mmap`].
+    pub unsafe fn map_file(file: &std::fs::File) -> std::io::Result<Self> {
+        let map = memmap2::Mmap::map(file)?;
+        let bytes = map.len();
+        Ok(Self::from_storage(Box::new(map), bytes))
+    }
+}
+
+impl<T: ?Sized + 'static> LargePayload<T> {
+    fn from_storage(data: Storage<T>, bytes: usize) -> Self {
+        LIVE_BYTES.fetch_add(bytes, Ordering::Relaxed);
+        Self {
+            inner: Arc::new(Inner {
+                data,
+                bytes,
+                on_release: Mutex::default(),
+            }),
+        }
+    }
+
+    /// The size of the payload in bytes.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.inner.bytes
+    }
+
+    /// The number of handles to this payload.
+    #[must_use]
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    /// Returns [`true`] if both handles point to the same payload.
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Call a function once the payload is released,
+    /// after the last handle was dropped.
+    pub fn on_release<F: FnOnce() + Send + 'static>(&self, func: F) {
+        self.inner
+            .on_release
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Box::new(func));
+    }
+
+    /// Create a weak handle that doesn't keep the payload alive.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakPayload<T> {
+        WeakPayload {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// The total size of all payloads that weren't released yet.
+    ///
+    /// This is useful to find payloads that are kept alive by accident.
+    #[must_use]
+    pub fn live_bytes() -> usize {
+        LIVE_BYTES.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: ?Sized + 'static> Clone for LargePayload<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Deref for LargePayload<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner.data
+    }
+}
+
+impl<T: ?Sized + AsRef<[u8]> + 'static> AsRef<[u8]> for LargePayload<T> {
+    fn as_ref(&self) -> &[u8] {
+        (**self).as_ref()
+    }
+}
+
+impl<T: ?Sized + 'static> fmt::Debug for LargePayload<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LargePayload")
+            .field("type", &std::any::type_name::<T>())
+            .field("size", &self.inner.bytes)
+            .field("handles", &self.handle_count())
+            .finish()
+    }
+}
+
+/// A weak handle to a [`LargePayload`] that doesn't keep it alive.
+pub struct WeakPayload<T: ?Sized + 'static> {
+    inner: Weak<Inner<T>>,
+}
+
+impl<T: ?Sized + 'static> WeakPayload<T> {
+    /// Get a strong handle if the payload wasn't released yet.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<LargePayload<T>> {
+        self.inner.upgrade().map(|inner| LargePayload { inner })
+    }
+
+    /// Returns [`true`] if the payload was released.
+    #[must_use]
+    pub fn is_released(&self) -> bool {
+        self.inner.strong_count() == 0
+    }
+}
+
+impl<T: ?Sized + 'static> Clone for WeakPayload<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> fmt::Debug for WeakPayload<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakPayload")
+            .field("type", &std::any::type_name::<T>())
+            .field("released", &self.is_released())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use super::LargePayload;
+
+    #[test]
+    fn release() {
+        let payload = LargePayload::from_vec(vec![1_u32, 2, 3]);
+        assert_eq!(payload.size(), 12);
+        assert_eq!(&*payload, &[1, 2, 3]);
+
+        let released = Arc::new(AtomicBool::new(false));
+        let flag = released.clone();
+        payload.on_release(move || flag.store(true, Ordering::SeqCst));
+
+        let weak = payload.downgrade();
+        let handle = payload.clone();
+        assert!(handle.ptr_eq(&payload));
+        assert_eq!(payload.handle_count(), 2);
+
+        std::thread::spawn(move || drop(handle)).join().unwrap();
+        assert!(!released.load(Ordering::SeqCst));
+        assert!(weak.upgrade().is_some());
+
+        drop(payload);
+        assert!(released.load(Ordering::SeqCst));
+        assert!(weak.is_released());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use crate::{Receiver, Sender};
+
+/// The latest coalesced input message for each key.
+pub(crate) type CoalescedInputs<T> = Arc<Mutex<HashMap<u64, T>>>;
+
+/// The priority of an input message.
+///
+/// Messages with a higher priority are processed before messages
+/// with a lower priority that were sent earlier.
+/// Messages with the same priority are always processed in the order they were sent.
+///
+/// See [`ComponentSender::input_priority()`](crate::ComponentSender::input_priority).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputPriority {
+    /// For bulk updates that can wait, for example progress reports.
+    Low,
+    /// The priority of all messages sent with [`Sender::send()`].
+    #[default]
+    Normal,
+    /// For urgent messages, for example user interaction or cancellation.
+    High,
+}
+
+/// Senders for the input messages that don't use the normal priority
+/// or that are coalesced.
+#[derive(Debug)]
+pub(crate) struct PrioritySender<T> {
+    pub(crate) high: Sender<T>,
+    pub(crate) low: Sender<T>,
+    coalesced: CoalescedInputs<T>,
+    /// Queues the keys of coalesced messages.
+    keys: Sender<u64>,
+}
+
+impl<T> PrioritySender<T> {
+    /// Queues a message that replaces a queued message with the same key.
+    pub(crate) fn send_coalesced<K: Hash>(&self, key: K, message: T) -> Result<(), T> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let mut coalesced = self.coalesced.lock().unwrap_or_else(|e| e.into_inner());
+        if coalesced.insert(key, message).is_none() && self.keys.send(key).is_err() {
+            Err(coalesced.remove(&key).expect("Message was just inserted"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            high: self.high.clone(),
+            low: self.low.clone(),
+            coalesced: self.coalesced.clone(),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+/// Receivers for the input messages that don't use the normal priority
+/// or that are coalesced.
+#[derive(Debug)]
+pub(crate) struct PriorityReceiver<T> {
+    pub(crate) high: Receiver<T>,
+    pub(crate) low: Receiver<T>,
+    pub(crate) coalesced: CoalescedInputs<T>,
+    /// Yields the keys of coalesced messages in the order they were first queued.
+    pub(crate) keys: Receiver<u64>,
+}
+
+/// Create the channels for input messages with a high or low priority
+/// and for coalesced input messages.
+pub(crate) fn priority_channel<T>() -> (PrioritySender<T>, PriorityReceiver<T>) {
+    let (high, high_receiver) = crate::channel();
+    let (low, low_receiver) = crate::channel();
+    let (keys, keys_receiver) = crate::channel();
+    let coalesced = CoalescedInputs::default();
+    (
+        PrioritySender {
+            high,
+            low,
+            coalesced: coalesced.clone(),
+            keys,
+        },
+        PriorityReceiver {
+            high: high_receiver,
+            low: low_receiver,
+            coalesced,
+            keys: keys_receiver,
+        },
+    )
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::oneshot;
+
+/// Sends the response to a request, usually part of an input message.
+///
+/// Components answer requests by calling [`Responder::respond()`]
+/// with the requested data. Created by [`Sender::request()`](super::Sender::request).
+///
+/// ```
+/// use relm4::Responder;
+///
+/// #[derive(Debug)]
+/// enum ChildMsg {
+///     GetName(Responder<String>),
+/// }
+/// ```
+pub struct Responder<T>(oneshot::Sender<T>);
+
+impl<T> Responder<T> {
+    /// Create a new responder and the future resolving to its response.
+    pub fn new() -> (Self, Response<T>) {
+        let (sender, receiver) = oneshot::channel();
+        (Self(sender), Response(receiver))
+    }
+
+    /// Send the response.
+    ///
+    /// If the requester isn't waiting anymore, [`Err`]
+    /// is returned with the response.
+    pub fn respond(self, response: T) -> Result<(), T> {
+        self.0.send(response)
+    }
+
+    /// Returns [`true`] if the requester isn't waiting anymore.
+    #[must_use]
+    pub fn is_canceled(&self) -> bool {
+        self.0.is_canceled()
+    }
+}
+
+impl<T> fmt::Debug for Responder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Responder").finish()
+    }
+}
+
+/// A future that resolves to the response of a [`Responder`].
+///
+/// Resolves to [`None`] if the [`Responder`] was dropped
+/// without sending a response.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Response<T>(oneshot::Receiver<T>);
+
+impl<T> Future for Response<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(Result::ok)
+    }
+}
+
+impl<T> fmt::Debug for Response<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Response").finish()
+    }
+}
@@ -6,21 +6,50 @@ use super::super::MessageBroker;
 use super::{AsyncComponent, AsyncComponentParts, AsyncConnector};
 use crate::channel::AsyncComponentSender;
 use crate::{
-    late_initialization, GuardedReceiver, Receiver, RelmContainerExt, RelmWidgetExt,
-    RuntimeSenders, Sender,
+    late_initialization, GuardedReceiver, InputReceiver, OverflowStrategy, Receiver,
+    RelmContainerExt, RelmWidgetExt, RuntimeSenders, Sender,
 };
+use futures::{pin_mut, FutureExt};
 use gtk::glib;
-use gtk::prelude::{GtkWindowExt, NativeDialogExt};
+use gtk::prelude::{GtkWindowExt, NativeDialogExt, WidgetExt};
 use std::any;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use tracing::info_span;
 
+/// Determines how an [`AsyncComponent`] handles inputs
+/// that arrive while an update is still running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum UpdatePolicy {
+    /// Handle inputs one after another.
+    /// New inputs wait until the running update has finished.
+    #[default]
+    Queue,
+    /// Cancel the running update once a new input arrives
+    /// and handle the new input instead.
+    ///
+    /// The update future is dropped at its current `.await`,
+    /// so changes that were made to the model until then are kept,
+    /// but the view is only updated after the latest input.
+    /// This is useful for inputs like search queries where
+    /// only the result of the latest input is relevant.
+    /// To cancel only the updates of certain inputs,
+    /// use [`AsyncComponentBuilder::supersede()`] instead.
+    Latest,
+}
+
+type SupersedeRule<I> = Box<dyn Fn(&I) -> bool>;
+
 /// A component that is ready for docking and launch.
-#[derive(Debug)]
 pub struct AsyncComponentBuilder<C: AsyncComponent> {
     /// The root widget of the component.
     pub root: C::Root,
     priority: glib::Priority,
+    update_policy: UpdatePolicy,
+    supersede_rules: Vec<SupersedeRule<C::Input>>,
+    input_bound: Option<(usize, OverflowStrategy<C::Input>)>,
 
     pub(super) component: PhantomData<C>,
 }
@@ -31,11 +60,26 @@ impl<C: AsyncComponent> Default for AsyncComponentBuilder<C> {
         Self {
             root: C::init_root(),
             priority: glib::Priority::default(),
+            update_policy: UpdatePolicy::default(),
+            supersede_rules: Vec::new(),
+            input_bound: None,
             component: PhantomData,
         }
     }
 }
 
+impl<C: AsyncComponent> Debug for AsyncComponentBuilder<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncComponentBuilder")
+            .field("root", &self.root)
+            .field("priority", &self.priority)
+            .field("update_policy", &self.update_policy)
+            .field("supersede_rules", &self.supersede_rules.len())
+            .field("input_bound", &self.input_bound)
+            .finish()
+    }
+}
+
 impl<C: AsyncComponent> AsyncComponentBuilder<C> {
     /// Configure the root widget before launching.
     #[must_use]
@@ -60,6 +104,66 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
         self.priority = priority;
         self
     }
+
+    /// Change how inputs are handled that arrive while an update is running.
+    ///
+    /// By default, inputs are queued, see [`UpdatePolicy`].
+    #[must_use]
+    pub fn update_policy(mut self, policy: UpdatePolicy) -> Self {
+        self.update_policy = policy;
+        self
+    }
+
+    /// Cancel running updates once they are superseded by newer inputs.
+    ///
+    /// If both the input of a running update and a new input match `rule`,
+    /// the running update is cancelled at its current `.await`.
+    /// Queued inputs that match the rule are dropped as well,
+    /// so only the latest of these inputs is handled.
+    /// Other inputs are still handled in order.
+    ///
+    /// This can be called multiple times to add independent rules.
+    ///
+    /// ```ignore
+    /// let component = Search::builder()
+    ///     .supersede(|msg| matches!(msg, SearchMsg::Query(_)))
+    ///     .launch(());
+    /// ```
+    #[must_use]
+    pub fn supersede<F>(mut self, rule: F) -> Self
+    where
+        F: Fn(&C::Input) -> bool + 'static,
+    {
+        self.supersede_rules.push(Box::new(rule));
+        self
+    }
+
+    /// Limit the number of queued input messages to `capacity`.
+    ///
+    /// Inputs are unbounded by default, so a component that can't keep up
+    /// with its inputs uses more and more memory. With a bounded queue,
+    /// new inputs are handled according to `strategy` once the queue is full.
+    /// Use [`Sender::metrics()`] to inspect the queue.
+    ///
+    /// This has no effect when the component is launched with a [`MessageBroker`]
+    /// because the broker creates the input channel.
+    ///
+    /// # Panics
+    ///
+    /// Launching the component panics if `capacity` is zero.
+    #[must_use]
+    pub fn bounded_input(mut self, capacity: usize, strategy: OverflowStrategy<C::Input>) -> Self {
+        self.input_bound = Some((capacity, strategy));
+        self
+    }
+
+    /// Creates the input channel, bounded if configured by [`Self::bounded_input()`].
+    pub(super) fn input_channel(&mut self) -> (Sender<C::Input>, Receiver<C::Input>) {
+        match self.input_bound.take() {
+            Some((capacity, strategy)) => crate::bounded_channel(capacity, strategy),
+            None => crate::channel(),
+        }
+    }
 }
 
 impl<C: AsyncComponent> AsyncComponentBuilder<C>
@@ -75,6 +179,19 @@ where
     }
 }
 
+impl<C: AsyncComponent> AsyncComponentBuilder<C>
+where
+    C::Root: AsRef<gtk::EventController>,
+{
+    /// Add the component's root event controller to a given widget.
+    #[must_use]
+    pub fn attach_controller_to(self, widget: &impl AsRef<gtk::Widget>) -> Self {
+        widget.as_ref().add_controller(self.root.as_ref().clone());
+
+        self
+    }
+}
+
 impl<C: AsyncComponent> AsyncComponentBuilder<C>
 where
     C::Root: AsRef<gtk::Window> + Clone,
@@ -134,9 +251,9 @@ where
 
 impl<C: AsyncComponent> AsyncComponentBuilder<C> {
     /// Starts the component, passing ownership to a future attached to a [gtk::glib::MainContext].
-    pub fn launch(self, payload: C::Init) -> AsyncConnector<C> {
+    pub fn launch(mut self, payload: C::Init) -> AsyncConnector<C> {
         // Used for all events to be processed by this component's internal service.
-        let (input_sender, input_receiver) = crate::channel::<C::Input>();
+        let (input_sender, input_receiver) = self.input_channel();
 
         self.launch_with_input_channel(payload, input_sender, input_receiver)
     }
@@ -152,11 +269,17 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
         broker: &MessageBroker<C::Input>,
     ) -> AsyncConnector<C> {
         let (input_sender, input_receiver) = broker.get_channel();
-        self.launch_with_input_channel(
+        #[cfg(feature = "component_graph")]
+        let broker_sender = input_sender.clone();
+        let connector = self.launch_with_input_channel(
             payload,
             input_sender,
             input_receiver.expect("Message broker launched multiple times"),
-        )
+        );
+
+        #[cfg(feature = "component_graph")]
+        crate::debug::set_broker(&broker_sender);
+        connector
     }
 
     fn launch_with_input_channel(
@@ -165,7 +288,13 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
         input_sender: Sender<C::Input>,
         input_receiver: Receiver<C::Input>,
     ) -> AsyncConnector<C> {
-        let Self { root, priority, .. } = self;
+        let Self {
+            root,
+            priority,
+            update_policy,
+            supersede_rules,
+            ..
+        } = self;
         let temp_widgets = C::init_loading_widgets(root.clone());
 
         let RuntimeSenders {
@@ -179,9 +308,13 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
             mut shutdown_event,
         } = RuntimeSenders::<C::Output, C::CommandOutput>::new();
 
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
         // Encapsulates the senders used by component methods.
         let component_sender = AsyncComponentSender::new(
             input_sender.clone(),
+            priority_sender,
             output_sender.clone(),
             cmd_sender,
             shutdown_recipient,
@@ -189,6 +322,23 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
 
         let rt_root = root.clone();
 
+        #[cfg(any(feature = "leak_diagnostics", feature = "component_graph"))]
+        let root_widget = C::root_widget(&root);
+
+        #[cfg(feature = "leak_diagnostics")]
+        let diagnostics_id =
+            crate::diagnostics::register(any::type_name::<C>(), root_widget.clone());
+
+        #[cfg(feature = "component_graph")]
+        let graph_node = crate::debug::register(
+            any::type_name::<C>(),
+            crate::debug::NodeKind::AsyncComponent,
+            root_widget,
+            &input_sender,
+        );
+        #[cfg(feature = "component_graph")]
+        let output_receiver = output_receiver.with_source(&graph_node);
+
         // Spawns the component's service. It will receive both `Self::Input` and
         // `Self::CommandOutput` messages. It will spawn commands as requested by
         // updates, and send `Self::Output` messages externally.
@@ -197,7 +347,9 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
             drop(temp_widgets);
 
             let mut cmd = GuardedReceiver::new(cmd_receiver);
-            let mut input = GuardedReceiver::new(input_receiver);
+            let mut input = InputReceiver::new(input_receiver, priority_receiver);
+            // Inputs that were received while an update was running.
+            let mut pending = VecDeque::new();
 
             loop {
                 futures::select!(
@@ -209,15 +361,65 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
                             widgets,
                         } = &mut state;
 
-                        let span = info_span!(
-                            "update_with_view",
-                            input=?message,
-                            component=any::type_name::<C>(),
-                            id=model.id(),
-                        );
-                        let _enter = span.enter();
-
-                        model.update_with_view(widgets, message, component_sender.clone(), &rt_root).await;
+                        pending.push_back(message);
+                        while let Some(message) = pending.pop_front() {
+                            let supersedable: Vec<bool> =
+                                supersede_rules.iter().map(|rule| rule(&message)).collect();
+                            let cancellable = update_policy == UpdatePolicy::Latest
+                                || supersedable.contains(&true);
+
+                            let span = info_span!(
+                                "update_with_view",
+                                input=?message,
+                                component=any::type_name::<C>(),
+                                id=model.id(),
+                            );
+                            let _enter = span.enter();
+                            crate::crash::record(any::type_name::<C>(), &message);
+                            crate::config::log_message(any::type_name::<C>(), &message);
+
+                            #[cfg(feature = "component_graph")]
+                            graph_node.input();
+
+                            #[cfg(feature = "metrics")]
+                            let start = std::time::Instant::now();
+                            let update = crate::mainloop::in_update(
+                                any::type_name::<C>(),
+                                model.update_with_view(widgets, message, component_sender.clone(), &rt_root),
+                            );
+                            if !cancellable {
+                                update.await;
+                                #[cfg(feature = "metrics")]
+                                crate::telemetry::update("update_with_view", any::type_name::<C>(), start.elapsed());
+                                continue;
+                            }
+
+                            // Receive newer inputs while the update is running.
+                            let update = update.fuse();
+                            pin_mut!(update);
+                            loop {
+                                futures::select!(
+                                    () = update => break,
+                                    newer = input => {
+                                        let superseded = supersede_rules.iter().zip(&supersedable).any(
+                                            |(rule, &running)| running && rule(&newer),
+                                        );
+                                        pending.retain(|queued| {
+                                            !supersede_rules.iter().any(|rule| rule(queued) && rule(&newer))
+                                        });
+                                        pending.push_back(newer);
+
+                                        if superseded || update_policy == UpdatePolicy::Latest {
+                                            tracing::debug!("Update cancelled by newer input");
+                                            break;
+                                        }
+                                    }
+                                );
+                            }
+
+                            #[cfg(feature = "metrics")]
+                            crate::telemetry::update("update_with_view", any::type_name::<C>(), start.elapsed());
+                        }
                     }
 
                     // Handles responses from a command.
@@ -235,7 +437,11 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
                         );
                         let _enter = span.enter();
 
+                        #[cfg(feature = "metrics")]
+                        let start = std::time::Instant::now();
                         model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root).await;
+                        #[cfg(feature = "metrics")]
+                        crate::telemetry::update("update_cmd_with_view", any::type_name::<C>(), start.elapsed());
                     }
 
                     // Triggered when the component is destroyed
@@ -249,6 +455,12 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
 
                         shutdown_notifier.shutdown();
 
+                        #[cfg(feature = "leak_diagnostics")]
+                        crate::diagnostics::shutdown(diagnostics_id);
+
+                        #[cfg(feature = "component_graph")]
+                        graph_node.shutdown();
+
                         return;
                     }
                 );
@@ -5,7 +5,7 @@
 use super::{
     stream::AsyncComponentStream, AsyncComponent, AsyncComponentController, AsyncController,
 };
-use crate::{Receiver, Sender, ShutdownOnDrop};
+use crate::{OutputRoute, Receiver, Sender, ShutdownOnDrop};
 use std::fmt::{self, Debug};
 
 /// Contains the post-launch input sender and output receivers with the root widget.
@@ -48,6 +48,58 @@ impl<C: AsyncComponent> AsyncConnector<C> {
         }
     }
 
+    /// Forwards the output events for which `filter` returns [`Some`]
+    /// to the designated sender and drops the others.
+    pub fn forward_filtered<X: 'static, F: (Fn(C::Output) -> Option<X>) + 'static>(
+        self,
+        sender_: &Sender<X>,
+        filter: F,
+    ) -> AsyncController<C> {
+        let Self {
+            widget,
+            sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        crate::spawn_local(receiver.forward_filtered(sender_.clone(), filter));
+
+        AsyncController {
+            widget,
+            sender,
+            shutdown_on_drop,
+        }
+    }
+
+    /// Forwards output events to one of two senders,
+    /// for example the parent and a sibling component.
+    pub fn forward_split<A, B, F>(
+        self,
+        first: &Sender<A>,
+        second: &Sender<B>,
+        router: F,
+    ) -> AsyncController<C>
+    where
+        A: 'static,
+        B: 'static,
+        F: (Fn(C::Output) -> OutputRoute<A, B>) + 'static,
+    {
+        let Self {
+            widget,
+            sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        crate::spawn_local(receiver.forward_split(first.clone(), second.clone(), router));
+
+        AsyncController {
+            widget,
+            sender,
+            shutdown_on_drop,
+        }
+    }
+
     /// Given a mutable closure, captures the receiver for handling.
     pub fn connect_receiver<F: FnMut(&mut Sender<C::Input>, C::Output) + 'static>(
         self,
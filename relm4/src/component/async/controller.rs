@@ -4,7 +4,7 @@
 
 use std::fmt::{self, Debug};
 
-use crate::{Sender, ShutdownOnDrop};
+use crate::{Responder, Response, Sender, ShutdownOnDrop};
 
 use super::AsyncComponent;
 
@@ -15,6 +15,17 @@ pub trait AsyncComponentController<C: AsyncComponent> {
         self.sender().send(event).unwrap();
     }
 
+    /// Sends an input that carries a [`Responder`] to the component and
+    /// returns a future that resolves to the response.
+    ///
+    /// See [`Sender::request()`] for details.
+    fn request<R, F>(&self, message: F) -> Response<R>
+    where
+        F: FnOnce(Responder<R>) -> C::Input,
+    {
+        self.sender().request(message)
+    }
+
     /// Provides access to the component's sender.
     fn sender(&self) -> &Sender<C::Input>;
 
@@ -4,7 +4,7 @@ mod controller;
 mod stream;
 mod traits;
 
-pub use builder::AsyncComponentBuilder;
+pub use builder::{AsyncComponentBuilder, UpdatePolicy};
 pub use connector::AsyncConnector;
 pub use controller::{AsyncComponentController, AsyncController};
 pub use stream::AsyncComponentStream;
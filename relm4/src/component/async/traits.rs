@@ -30,6 +30,10 @@ pub trait AsyncComponent: Sized + 'static {
     type Init;
 
     /// The top-level widget of the component.
+    ///
+    /// The root doesn't have to be a widget, components can also
+    /// use other objects like a [`gtk::gio::Menu`] or a [`gtk::EventController`]
+    /// as root, see [`AsyncComponentBuilder::attach_controller_to()`](crate::component::AsyncComponentBuilder::attach_controller_to).
     type Root: Debug + Clone;
 
     /// The type that's used for storing widgets created for this component.
@@ -45,6 +49,17 @@ pub trait AsyncComponent: Sized + 'static {
     #[must_use]
     fn init_root() -> Self::Root;
 
+    /// Returns the root as widget, if it is one.
+    ///
+    /// This is used by diagnostics that inspect the widgets of components
+    /// and is implemented automatically by the component macro.
+    /// Implement it for manually written components with a widget as root,
+    /// for example by returning `Some(root.clone().upcast())`.
+    #[allow(unused)]
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        None
+    }
+
     /// Allows you to initialize the root widget with a temporary value
     /// as a placeholder until the [`init()`](AsyncComponent::init)
     /// future completes.
@@ -177,6 +192,17 @@ pub trait SimpleAsyncComponent: Sized + 'static {
     #[must_use]
     fn init_root() -> Self::Root;
 
+    /// Returns the root as widget, if it is one.
+    ///
+    /// This is used by diagnostics that inspect the widgets of components
+    /// and is implemented automatically by the component macro.
+    /// Implement it for manually written components with a widget as root,
+    /// for example by returning `Some(root.clone().upcast())`.
+    #[allow(unused)]
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        None
+    }
+
     /// Allows you to initialize the root widget with a temporary value
     /// as a placeholder until the [`init()`](AsyncComponent::init)
     /// future completes.
@@ -241,6 +267,10 @@ where
         C::init_root()
     }
 
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        C::root_widget(root)
+    }
+
     fn init_loading_widgets(root: Self::Root) -> Option<LoadingWidgets> {
         C::init_loading_widgets(root)
     }
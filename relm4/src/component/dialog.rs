@@ -0,0 +1,131 @@
+use std::cell::Cell;
+use std::future::Future;
+
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use gtk::glib::Propagation;
+use gtk::prelude::{GtkWindowExt, ObjectExt};
+
+use crate::component::ComponentStream;
+use crate::{Component, ComponentController, MainWindowRegistry};
+
+/// Opens dialog components and resolves their first output as a future.
+///
+/// Each dialog is launched on demand, made modal and transient for the window
+/// of the parent widget and shut down after it sent its first output or
+/// after it was closed by the user.
+///
+/// The returned futures aren't [`Send`], so they can be awaited in the update
+/// function of async components but not in commands.
+///
+/// ```ignore
+/// let confirmed = self.dialogs.open::<ConfirmDialog>("Delete file?".into()).await;
+/// if confirmed == Some(ConfirmOutput::Accept) {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DialogController {
+    parent: gtk::Widget,
+}
+
+impl DialogController {
+    /// Create a new controller for dialogs of the window that contains `parent`.
+    ///
+    /// If `parent` isn't inside a window once a dialog is opened,
+    /// the [current main window](MainWindowRegistry::current) is used instead.
+    #[must_use]
+    pub fn new(parent: &impl AsRef<gtk::Widget>) -> Self {
+        Self {
+            parent: parent.as_ref().clone(),
+        }
+    }
+
+    /// The window that dialogs are transient for.
+    #[must_use]
+    pub fn parent_window(&self) -> Option<gtk::Window> {
+        MainWindowRegistry::transient_parent_for(&self.parent)
+    }
+
+    /// Launch the dialog component `C` and wait for its first output.
+    ///
+    /// Resolves to [`None`] if the dialog is closed without sending an output.
+    pub fn open<C>(&self, init: C::Init) -> impl Future<Output = Option<C::Output>>
+    where
+        C: Component,
+        C::Root: AsRef<gtk::Window>,
+    {
+        let connector = C::builder().launch(init);
+        let window: gtk::Window = connector.widget().as_ref().clone();
+
+        window.set_transient_for(self.parent_window().as_ref());
+        window.set_modal(true);
+
+        let (closed_sender, closed_receiver) = oneshot::channel();
+        let closed_sender = Cell::new(Some(closed_sender));
+        let handler = window.connect_close_request(move |_| {
+            if let Some(sender) = closed_sender.take() {
+                sender.send(()).ok();
+            }
+            Propagation::Proceed
+        });
+
+        let stream = connector.into_stream();
+        window.present();
+
+        async move {
+            let output = first_output(stream, closed_receiver).await;
+
+            window.disconnect(handler);
+            window.destroy();
+            output
+        }
+    }
+
+    /// Launch the dialog component `C` with an [`adw::Dialog`] as root and wait for its first output.
+    ///
+    /// The dialog is presented inside the window of the parent widget.
+    /// Resolves to [`None`] if the dialog is closed without sending an output.
+    #[cfg(all(feature = "libadwaita", feature = "gnome_46"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "libadwaita", feature = "gnome_46"))))]
+    pub fn open_adw<C>(&self, init: C::Init) -> impl Future<Output = Option<C::Output>>
+    where
+        C: Component,
+        C::Root: AsRef<adw::Dialog>,
+    {
+        use adw::prelude::AdwDialogExt;
+
+        let connector = C::builder().launch(init);
+        let dialog: adw::Dialog = connector.widget().as_ref().clone();
+
+        let (closed_sender, closed_receiver) = oneshot::channel();
+        let closed_sender = Cell::new(Some(closed_sender));
+        let handler = dialog.connect_closed(move |_| {
+            if let Some(sender) = closed_sender.take() {
+                sender.send(()).ok();
+            }
+        });
+
+        let stream = connector.into_stream();
+        dialog.present(Some(&self.parent));
+
+        async move {
+            let output = first_output(stream, closed_receiver).await;
+
+            dialog.disconnect(handler);
+            dialog.force_close();
+            output
+        }
+    }
+}
+
+/// Wait for the first output of a dialog or until it was closed.
+async fn first_output<C: Component>(
+    stream: ComponentStream<C>,
+    closed_receiver: oneshot::Receiver<()>,
+) -> Option<C::Output> {
+    match future::select(Box::pin(stream.recv_one()), closed_receiver).await {
+        Either::Left((output, _)) => output,
+        Either::Right(_) => None,
+    }
+}
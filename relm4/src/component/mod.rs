@@ -10,20 +10,31 @@ mod r#async;
 /// Message broker
 mod message_broker;
 
+/// Dialogs that resolve their output as future.
+mod dialog;
+
 /// A simpler version of components that does work
 /// in the background.
 pub mod worker;
 
+#[cfg(feature = "process_worker")]
+#[cfg_attr(docsrs, doc(cfg(feature = "process_worker")))]
+pub mod process_worker;
+
+#[doc(hidden)]
+pub mod root_widget;
+
+pub use dialog::DialogController;
 pub use message_broker::MessageBroker;
 
 pub use sync::{
     CommandFuture, Component, ComponentBuilder, ComponentController, ComponentParts,
-    ComponentStream, Connector, Controller, SimpleComponent, StateWatcher,
+    ComponentStream, Connector, Controller, SimpleComponent, StateWatcher, UpdateContinuation,
 };
 
 pub use r#async::{
     AsyncComponent, AsyncComponentBuilder, AsyncComponentController, AsyncComponentParts,
-    AsyncComponentStream, AsyncConnector, AsyncController, SimpleAsyncComponent,
+    AsyncComponentStream, AsyncConnector, AsyncController, SimpleAsyncComponent, UpdatePolicy,
 };
 
 pub use crate::channel::AsyncComponentSender;
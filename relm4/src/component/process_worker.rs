@@ -0,0 +1,266 @@
+//! Workers that run in a separate process.
+//!
+//! Some workloads should be isolated from the application, for example
+//! native libraries that might crash or parsers that need a lot of memory.
+//! A [`ProcessWorker`] runs in a helper process and exchanges JSON encoded
+//! messages with the application over the standard input and output of that process.
+//!
+//! The helper binary calls [`run_process_worker()`] in its `main` function,
+//! while the application launches a [`ProcessWorkerProxy`] like any other worker:
+//!
+//! ```ignore
+//! let worker = ProcessWorkerProxy::<Parser>::builder()
+//!     .detach_worker(ProcessWorkerSettings::new(Command::new("parser-helper"), parser_init))
+//!     .forward(sender.input_sender(), |output| match output {
+//!         ProcessWorkerOutput::Output(output) => AppMsg::Parsed(output),
+//!         ProcessWorkerOutput::Failed(err) => AppMsg::ParserFailed(err.to_string()),
+//!     });
+//!
+//! worker.emit(ParserMsg::Parse(path));
+//! ```
+
+use std::fmt::{self, Debug};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Component, ComponentParts, ComponentSender, Sender};
+
+/// A worker that runs in a separate process.
+///
+/// All messages are encoded as JSON, so they need to implement
+/// [`Serialize`] and [`Deserialize`](serde::Deserialize).
+pub trait ProcessWorker: Sized + 'static {
+    /// The initial parameters that will be used to build the worker state.
+    type Init: Serialize + DeserializeOwned + Send + 'static;
+    /// The type of inputs that this worker shall receive.
+    type Input: Serialize + DeserializeOwned + Send + Debug + 'static;
+    /// The type of outputs that this worker shall send.
+    type Output: Serialize + DeserializeOwned + Send + Debug + 'static;
+
+    /// Defines the initial state of the worker.
+    fn init(init: Self::Init, sender: &ProcessWorkerSender<Self>) -> Self;
+
+    /// Defines how inputs will be processed.
+    fn update(&mut self, message: Self::Input, sender: &ProcessWorkerSender<Self>);
+}
+
+/// Sends the outputs of a [`ProcessWorker`] to the application.
+pub struct ProcessWorkerSender<W: ProcessWorker> {
+    worker: PhantomData<W>,
+}
+
+impl<W: ProcessWorker> Debug for ProcessWorkerSender<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessWorkerSender").finish()
+    }
+}
+
+impl<W: ProcessWorker> ProcessWorkerSender<W> {
+    /// Sends an output to the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output couldn't be encoded
+    /// or the application closed the connection.
+    pub fn output(&self, message: W::Output) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        write_message(&mut stdout, &message)
+    }
+}
+
+/// Runs a [`ProcessWorker`] in the current process.
+///
+/// Call this function in the `main` function of the helper binary.
+/// It returns once the application closed the connection.
+/// The standard output of the process is used for messages,
+/// so the worker must not print anything to it.
+///
+/// # Errors
+///
+/// Returns an error if reading from the application fails
+/// or a message can't be decoded.
+pub fn run_process_worker<W: ProcessWorker>() -> io::Result<()> {
+    let mut lines = io::stdin().lock().lines();
+    let init = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => return Err(io::ErrorKind::UnexpectedEof.into()),
+    };
+
+    let sender = ProcessWorkerSender {
+        worker: PhantomData,
+    };
+    let mut worker = W::init(init, &sender);
+
+    for line in lines {
+        let message = serde_json::from_str(&line?)?;
+        worker.update(message, &sender);
+    }
+    Ok(())
+}
+
+/// Settings to launch a [`ProcessWorkerProxy`].
+#[derive(Debug)]
+pub struct ProcessWorkerSettings<W: ProcessWorker> {
+    /// The command that starts the helper process.
+    ///
+    /// The standard input and output of the command are replaced by pipes.
+    pub command: Command,
+    /// The initial parameters of the worker.
+    pub init: W::Init,
+}
+
+impl<W: ProcessWorker> ProcessWorkerSettings<W> {
+    /// Create new settings from the command of the helper process
+    /// and the initial parameters of the worker.
+    pub fn new(command: Command, init: W::Init) -> Self {
+        Self { command, init }
+    }
+}
+
+/// The output of a [`ProcessWorkerProxy`].
+#[derive(Debug)]
+pub enum ProcessWorkerOutput<O> {
+    /// An output of the worker.
+    Output(O),
+    /// The helper process couldn't be started or its output couldn't be read.
+    Failed(io::Error),
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum ProcessWorkerEvent<O> {
+    Output(O),
+    Failed(io::Error),
+    Exited,
+}
+
+/// Component that forwards messages to a [`ProcessWorker`]
+/// that runs in a helper process.
+///
+/// Launch it with [`detach_worker()`](crate::ComponentBuilder::detach_worker)
+/// to get the same API as for other workers.
+/// The helper process is killed when the worker is shut down.
+pub struct ProcessWorkerProxy<W: ProcessWorker> {
+    /// The helper process, [`None`] if it couldn't be spawned.
+    child: Option<Child>,
+    stdin: Option<BufWriter<ChildStdin>>,
+    worker: PhantomData<W>,
+}
+
+impl<W: ProcessWorker> Debug for ProcessWorkerProxy<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessWorkerProxy")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: ProcessWorker> Component for ProcessWorkerProxy<W> {
+    type CommandOutput = ProcessWorkerEvent<W::Output>;
+    type Init = ProcessWorkerSettings<W>;
+    type Input = W::Input;
+    type Output = ProcessWorkerOutput<W::Output>;
+    type Root = ();
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {}
+
+    fn init(
+        settings: Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let ProcessWorkerSettings { mut command, init } = settings;
+        let mut child = match command.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                sender.output(ProcessWorkerOutput::Failed(err)).ok();
+                let model = Self {
+                    child: None,
+                    stdin: None,
+                    worker: PhantomData,
+                };
+                return ComponentParts { model, widgets: () };
+            }
+        };
+
+        let stdout = child.stdout.take().expect("Stdout is piped");
+        sender.spawn_command(move |sender| {
+            for line in BufReader::new(stdout).lines() {
+                let event = match line.and_then(|line| Ok(serde_json::from_str(&line)?)) {
+                    Ok(output) => ProcessWorkerEvent::Output(output),
+                    Err(err) => ProcessWorkerEvent::Failed(err),
+                };
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+            sender.emit(ProcessWorkerEvent::Exited);
+        });
+
+        let mut model = Self {
+            stdin: child.stdin.take().map(BufWriter::new),
+            child: Some(child),
+            worker: PhantomData,
+        };
+        model.send(&init);
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>, _root: &()) {
+        self.send(&message);
+    }
+
+    fn update_cmd(
+        &mut self,
+        event: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &(),
+    ) {
+        match event {
+            ProcessWorkerEvent::Output(output) => {
+                sender.output(ProcessWorkerOutput::Output(output)).ok();
+            }
+            ProcessWorkerEvent::Failed(err) => {
+                sender.output(ProcessWorkerOutput::Failed(err)).ok();
+            }
+            ProcessWorkerEvent::Exited => {
+                let status = self.child.as_mut().map(Child::try_wait);
+                tracing::error!("Process of worker exited: {status:?}");
+                self.stdin = None;
+            }
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut (), _output: Sender<Self::Output>) {
+        self.stdin = None;
+        if let Some(child) = &mut self.child {
+            child.kill().ok();
+            child.wait().ok();
+        }
+    }
+}
+
+impl<W: ProcessWorker> ProcessWorkerProxy<W> {
+    fn send<T: Serialize>(&mut self, message: &T) {
+        if let Some(stdin) = &mut self.stdin {
+            if let Err(err) = write_message(stdin, message) {
+                tracing::error!("Couldn't send message to process worker: {err}");
+            }
+        } else {
+            tracing::warn!("Message was ignored because the process of the worker exited");
+        }
+    }
+}
+
+/// Writes a message as single line of JSON.
+fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
@@ -0,0 +1,53 @@
+//! Implementation of `root_widget()` for components generated by the component macro.
+//!
+//! The macro doesn't know whether the root is a widget, so the implementation
+//! is selected by method resolution on the concrete root type:
+//! widgets, types that dereference to a widget like widget templates,
+//! and everything else.
+//! Components can override the generated `root_widget()` by implementing it themselves.
+
+use std::ops::Deref;
+
+/// Wraps the root of a component to select an implementation.
+#[derive(Debug)]
+pub struct RootWidgetProbe<'a, T>(pub &'a T);
+
+/// Selected if the root is a widget.
+pub trait RootIsObject {
+    /// Returns the root as widget.
+    fn root_widget(&self) -> Option<gtk::Widget>;
+}
+
+impl<T: AsRef<gtk::Widget>> RootIsObject for &&RootWidgetProbe<'_, T> {
+    fn root_widget(&self) -> Option<gtk::Widget> {
+        Some(self.0.as_ref().clone())
+    }
+}
+
+/// Selected if the root dereferences to a widget.
+pub trait RootDerefsToObject {
+    /// Returns the root as widget.
+    fn root_widget(&self) -> Option<gtk::Widget>;
+}
+
+impl<T> RootDerefsToObject for &RootWidgetProbe<'_, T>
+where
+    T: Deref,
+    T::Target: AsRef<gtk::Widget>,
+{
+    fn root_widget(&self) -> Option<gtk::Widget> {
+        Some((**self.0).as_ref().clone())
+    }
+}
+
+/// Selected for all other roots.
+pub trait RootIsOther {
+    /// Returns [`None`] because the root isn't a widget.
+    fn root_widget(&self) -> Option<gtk::Widget>;
+}
+
+impl<T> RootIsOther for RootWidgetProbe<'_, T> {
+    fn root_widget(&self) -> Option<gtk::Widget> {
+        None
+    }
+}
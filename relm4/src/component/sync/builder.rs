@@ -5,11 +5,11 @@
 use super::super::MessageBroker;
 use super::{Component, ComponentParts, Connector, StateWatcher};
 use crate::{
-    late_initialization, ComponentSender, GuardedReceiver, Receiver, RelmContainerExt,
-    RelmWidgetExt, RuntimeSenders, Sender,
+    late_initialization, mainloop, ComponentSender, GuardedReceiver, InputReceiver,
+    OverflowStrategy, Receiver, RelmContainerExt, RelmWidgetExt, RuntimeSenders, Sender,
 };
 use gtk::glib;
-use gtk::prelude::{GtkWindowExt, NativeDialogExt};
+use gtk::prelude::{GtkWindowExt, NativeDialogExt, WidgetExt};
 use std::any;
 use std::cell::RefCell;
 use std::marker::PhantomData;
@@ -22,6 +22,7 @@ pub struct ComponentBuilder<C: Component> {
     /// The root widget of the component.
     pub root: C::Root,
     priority: glib::Priority,
+    input_bound: Option<(usize, OverflowStrategy<C::Input>)>,
 
     pub(super) component: PhantomData<C>,
 }
@@ -32,6 +33,7 @@ impl<C: Component> Default for ComponentBuilder<C> {
         Self {
             root: C::init_root(),
             priority: glib::Priority::default(),
+            input_bound: None,
             component: PhantomData,
         }
     }
@@ -61,6 +63,33 @@ impl<C: Component> ComponentBuilder<C> {
         self.priority = priority;
         self
     }
+
+    /// Limit the number of queued input messages to `capacity`.
+    ///
+    /// Inputs are unbounded by default, so a component that can't keep up
+    /// with its inputs uses more and more memory. With a bounded queue,
+    /// new inputs are handled according to `strategy` once the queue is full.
+    /// Use [`Sender::metrics()`] to inspect the queue.
+    ///
+    /// This has no effect when the component is launched with a [`MessageBroker`]
+    /// because the broker creates the input channel.
+    ///
+    /// # Panics
+    ///
+    /// Launching the component panics if `capacity` is zero.
+    #[must_use]
+    pub fn bounded_input(mut self, capacity: usize, strategy: OverflowStrategy<C::Input>) -> Self {
+        self.input_bound = Some((capacity, strategy));
+        self
+    }
+
+    /// Creates the input channel, bounded if configured by [`Self::bounded_input()`].
+    pub(crate) fn input_channel(&mut self) -> (Sender<C::Input>, Receiver<C::Input>) {
+        match self.input_bound.take() {
+            Some((capacity, strategy)) => crate::bounded_channel(capacity, strategy),
+            None => crate::channel(),
+        }
+    }
 }
 
 impl<C: Component> ComponentBuilder<C>
@@ -76,6 +105,19 @@ where
     }
 }
 
+impl<C: Component> ComponentBuilder<C>
+where
+    C::Root: AsRef<gtk::EventController>,
+{
+    /// Add the component's root event controller to a given widget.
+    #[must_use]
+    pub fn attach_controller_to(self, widget: &impl AsRef<gtk::Widget>) -> Self {
+        widget.as_ref().add_controller(self.root.as_ref().clone());
+
+        self
+    }
+}
+
 impl<C: Component> ComponentBuilder<C>
 where
     C::Root: AsRef<gtk::Window> + Clone,
@@ -135,9 +177,9 @@ where
 
 impl<C: Component> ComponentBuilder<C> {
     /// Starts the component, passing ownership to a future attached to a [gtk::glib::MainContext].
-    pub fn launch(self, payload: C::Init) -> Connector<C> {
+    pub fn launch(mut self, payload: C::Init) -> Connector<C> {
         // Used for all events to be processed by this component's internal service.
-        let (input_sender, input_receiver) = crate::channel::<C::Input>();
+        let (input_sender, input_receiver) = self.input_channel();
 
         self.launch_with_input_channel(payload, input_sender, input_receiver)
     }
@@ -153,11 +195,17 @@ impl<C: Component> ComponentBuilder<C> {
         broker: &MessageBroker<C::Input>,
     ) -> Connector<C> {
         let (input_sender, input_receiver) = broker.get_channel();
-        self.launch_with_input_channel(
+        #[cfg(feature = "component_graph")]
+        let broker_sender = input_sender.clone();
+        let connector = self.launch_with_input_channel(
             payload,
             input_sender,
             input_receiver.expect("Message broker launched multiple times"),
-        )
+        );
+
+        #[cfg(feature = "component_graph")]
+        crate::debug::set_broker(&broker_sender);
+        connector
     }
 
     fn launch_with_input_channel(
@@ -182,9 +230,13 @@ impl<C: Component> ComponentBuilder<C> {
         // Gets notifications when a component's model and view is updated externally.
         let (notifier, notifier_receiver) = crate::channel();
 
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
         // Encapsulates the senders used by component methods.
         let component_sender = ComponentSender::new(
             input_sender.clone(),
+            priority_sender,
             output_sender.clone(),
             cmd_sender,
             shutdown_recipient,
@@ -205,13 +257,30 @@ impl<C: Component> ComponentBuilder<C> {
         let rt_state = watcher.state.clone();
         let rt_root = root.clone();
 
+        #[cfg(any(feature = "leak_diagnostics", feature = "component_graph"))]
+        let root_widget = C::root_widget(&root);
+
+        #[cfg(feature = "leak_diagnostics")]
+        let diagnostics_id =
+            crate::diagnostics::register(any::type_name::<C>(), root_widget.clone());
+
+        #[cfg(feature = "component_graph")]
+        let graph_node = crate::debug::register(
+            any::type_name::<C>(),
+            crate::debug::NodeKind::Component,
+            root_widget,
+            &input_sender,
+        );
+        #[cfg(feature = "component_graph")]
+        let output_receiver = output_receiver.with_source(&graph_node);
+
         // Spawns the component's service. It will receive both `Self::Input` and
         // `Self::CommandOutput` messages. It will spawn commands as requested by
         // updates, and send `Self::Output` messages externally.
         crate::spawn_local_with_priority(priority, async move {
             let mut notifier = GuardedReceiver::new(notifier_receiver);
             let mut cmd = GuardedReceiver::new(cmd_receiver);
-            let mut input = GuardedReceiver::new(input_receiver);
+            let mut input = InputReceiver::new(input_receiver, priority_receiver);
             loop {
                 futures::select!(
                     // Performs the model update, checking if the update requested a command.
@@ -229,8 +298,20 @@ impl<C: Component> ComponentBuilder<C> {
                             id=model.id(),
                         );
                         let _enter = span.enter();
+                        crate::crash::record(any::type_name::<C>(), &message);
+                        crate::config::log_message(any::type_name::<C>(), &message);
+
+                        #[cfg(feature = "component_graph")]
+                        graph_node.input();
 
-                        model.update_with_view(widgets, message, component_sender.clone(), &rt_root);
+                        mainloop::measure(
+                            "update_with_view",
+                            any::type_name::<C>(),
+                            any::type_name::<C::Input>(),
+                            || {
+                                model.update_with_view(widgets, message, component_sender.clone(), &rt_root);
+                            },
+                        );
                     }
 
                     // Handles responses from a command.
@@ -248,7 +329,14 @@ impl<C: Component> ComponentBuilder<C> {
                         );
                         let _enter = span.enter();
 
-                        model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root);
+                        mainloop::measure(
+                            "update_cmd_with_view",
+                            any::type_name::<C>(),
+                            any::type_name::<C::CommandOutput>(),
+                            || {
+                                model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root);
+                            },
+                        );
                     }
 
                     // Triggered when the model and view have been updated externally.
@@ -258,7 +346,14 @@ impl<C: Component> ComponentBuilder<C> {
                             widgets,
                         } = &mut *rt_state.borrow_mut();
 
-                        model.update_view(widgets, component_sender.clone());
+                        mainloop::measure(
+                            "update_view",
+                            any::type_name::<C>(),
+                            "()",
+                            || {
+                                model.update_view(widgets, component_sender.clone());
+                            },
+                        );
                     }
 
                     // Triggered when the component is destroyed
@@ -272,6 +367,12 @@ impl<C: Component> ComponentBuilder<C> {
 
                         shutdown_notifier.shutdown();
 
+                        #[cfg(feature = "leak_diagnostics")]
+                        crate::diagnostics::shutdown(diagnostics_id);
+
+                        #[cfg(feature = "component_graph")]
+                        graph_node.shutdown();
+
                         return;
                     }
                 );
@@ -4,7 +4,7 @@
 
 use super::stream::ComponentStream;
 use super::{Component, ComponentController, Controller, StateWatcher};
-use crate::{Receiver, Sender};
+use crate::{OutputRoute, Receiver, Sender};
 use std::fmt::{self, Debug};
 
 /// Contains the post-launch input sender and output receivers with the root widget.
@@ -47,6 +47,58 @@ impl<C: Component> Connector<C> {
         }
     }
 
+    /// Forwards the output events for which `filter` returns [`Some`]
+    /// to the designated sender and drops the others.
+    pub fn forward_filtered<X: 'static, F: (Fn(C::Output) -> Option<X>) + 'static>(
+        self,
+        sender_: &Sender<X>,
+        filter: F,
+    ) -> Controller<C> {
+        let Self {
+            state,
+            widget,
+            sender,
+            receiver,
+        } = self;
+
+        crate::spawn_local(receiver.forward_filtered(sender_.clone(), filter));
+
+        Controller {
+            state,
+            widget,
+            sender,
+        }
+    }
+
+    /// Forwards output events to one of two senders,
+    /// for example the parent and a sibling component.
+    pub fn forward_split<A, B, F>(
+        self,
+        first: &Sender<A>,
+        second: &Sender<B>,
+        router: F,
+    ) -> Controller<C>
+    where
+        A: 'static,
+        B: 'static,
+        F: (Fn(C::Output) -> OutputRoute<A, B>) + 'static,
+    {
+        let Self {
+            state,
+            widget,
+            sender,
+            receiver,
+        } = self;
+
+        crate::spawn_local(receiver.forward_split(first.clone(), second.clone(), router));
+
+        Controller {
+            state,
+            widget,
+            sender,
+        }
+    }
+
     /// Given a mutable closure, captures the receiver for handling.
     pub fn connect_receiver<F: FnMut(&mut Sender<C::Input>, C::Output) + 'static>(
         self,
@@ -0,0 +1,40 @@
+use std::fmt::{self, Debug};
+
+use super::Component;
+use crate::ComponentSender;
+
+type ContinuationFn<C> =
+    Box<dyn FnOnce(&mut C, ComponentSender<C>, &<C as Component>::Root) + Send>;
+
+/// The remaining part of an update function that is resumed
+/// once an awaited future has finished.
+///
+/// Continuations are generated by the `#[update(async)]` attribute
+/// of the `component` macro, which uses them as command output
+/// of the component.
+pub struct UpdateContinuation<C: Component> {
+    inner: ContinuationFn<C>,
+}
+
+impl<C: Component> UpdateContinuation<C> {
+    #[doc(hidden)]
+    pub fn new<F>(func: F) -> Self
+    where
+        F: FnOnce(&mut C, ComponentSender<C>, &C::Root) + Send + 'static,
+    {
+        Self {
+            inner: Box::new(func),
+        }
+    }
+
+    /// Run the remaining part of the update function.
+    pub fn resume(self, model: &mut C, sender: ComponentSender<C>, root: &C::Root) {
+        (self.inner)(model, sender, root);
+    }
+}
+
+impl<C: Component> Debug for UpdateContinuation<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateContinuation").finish_non_exhaustive()
+    }
+}
@@ -5,7 +5,7 @@
 use std::cell::Ref;
 use std::fmt::{self, Debug};
 
-use crate::Sender;
+use crate::{Responder, Response, Sender};
 
 use super::{Component, StateWatcher};
 
@@ -16,6 +16,17 @@ pub trait ComponentController<C: Component> {
         self.sender().send(event).unwrap();
     }
 
+    /// Sends an input that carries a [`Responder`] to the component and
+    /// returns a future that resolves to the response.
+    ///
+    /// See [`Sender::request()`] for details.
+    fn request<R, F>(&self, message: F) -> Response<R>
+    where
+        F: FnOnce(Responder<R>) -> C::Input,
+    {
+        self.sender().request(message)
+    }
+
     /// Provides access to the component's sender.
     fn sender(&self) -> &Sender<C::Input>;
 
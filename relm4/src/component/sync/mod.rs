@@ -4,6 +4,7 @@
 
 mod builder;
 mod connector;
+mod continuation;
 mod controller;
 mod state_watcher;
 mod stream;
@@ -11,6 +12,7 @@ mod traits;
 
 pub use builder::ComponentBuilder;
 pub use connector::Connector;
+pub use continuation::UpdateContinuation;
 pub use controller::{ComponentController, Controller};
 pub use state_watcher::StateWatcher;
 pub use stream::ComponentStream;
@@ -29,6 +29,10 @@ pub trait Component: Sized + 'static {
     type Init;
 
     /// The top-level widget of the component.
+    ///
+    /// The root doesn't have to be a widget, components can also
+    /// use other objects like a [`gtk::gio::Menu`] or a [`gtk::EventController`]
+    /// as root, see [`ComponentBuilder::attach_controller_to()`](crate::component::ComponentBuilder::attach_controller_to).
     type Root: Debug + Clone;
 
     /// The type that's used for storing widgets created for this component.
@@ -43,6 +47,17 @@ pub trait Component: Sized + 'static {
     /// Initializes the root widget.
     fn init_root() -> Self::Root;
 
+    /// Returns the root as widget, if it is one.
+    ///
+    /// This is used by diagnostics that inspect the widgets of components
+    /// and is implemented automatically by the component macro.
+    /// Implement it for manually written components with a widget as root,
+    /// for example by returning `Some(root.clone().upcast())`.
+    #[allow(unused)]
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        None
+    }
+
     /// Creates the initial model and view, docking it into the component.
     fn init(
         init: Self::Init,
@@ -151,6 +166,17 @@ pub trait SimpleComponent: Sized + 'static {
     /// Initializes the root widget
     fn init_root() -> Self::Root;
 
+    /// Returns the root as widget, if it is one.
+    ///
+    /// This is used by diagnostics that inspect the widgets of components
+    /// and is implemented automatically by the component macro.
+    /// Implement it for manually written components with a widget as root,
+    /// for example by returning `Some(root.clone().upcast())`.
+    #[allow(unused)]
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        None
+    }
+
     /// Creates the initial model and view, docking it into the component.
     fn init(
         init: Self::Init,
@@ -193,6 +219,10 @@ where
         C::init_root()
     }
 
+    fn root_widget(root: &Self::Root) -> Option<gtk::Widget> {
+        C::root_widget(root)
+    }
+
     fn init(
         init: Self::Init,
         root: Self::Root,
@@ -6,10 +6,12 @@ use gtk::glib;
 use tracing::info_span;
 
 use crate::{
-    Component, ComponentBuilder, ComponentParts, ComponentSender, GuardedReceiver, Receiver,
-    RuntimeSenders, Sender, ShutdownOnDrop, SimpleComponent,
+    Component, ComponentBuilder, ComponentParts, ComponentSender, GuardedReceiver, InputReceiver,
+    Receiver, RuntimeSenders, Sender, ShutdownOnDrop, SimpleComponent,
 };
+use std::any::Any;
 use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
 use std::{any, thread};
 
 /// Receives inputs and outputs in the background.
@@ -73,11 +75,10 @@ where
 {
     /// Starts a worker on a separate thread,
     /// passing ownership to a future attached to a [gtk::glib::MainContext].
-    pub fn detach_worker(self, payload: C::Init) -> WorkerHandle<C> {
-        let Self { root, .. } = self;
-
+    pub fn detach_worker(mut self, payload: C::Init) -> WorkerHandle<C> {
         // Used for all events to be processed by this component's internal service.
-        let (input_sender, input_receiver) = crate::channel::<C::Input>();
+        let (input_sender, input_receiver) = self.input_channel();
+        let Self { root, .. } = self;
 
         let RuntimeSenders {
             output_sender,
@@ -90,9 +91,13 @@ where
             mut shutdown_event,
         } = RuntimeSenders::<C::Output, C::CommandOutput>::new();
 
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
         // Encapsulates the senders used by component methods.
         let component_sender = ComponentSender::new(
             input_sender.clone(),
+            priority_sender,
             output_sender.clone(),
             cmd_sender,
             shutdown_recipient,
@@ -100,6 +105,16 @@ where
 
         let mut state = C::init(payload, root, component_sender.clone());
 
+        #[cfg(feature = "component_graph")]
+        let graph_node = crate::debug::register(
+            any::type_name::<C>(),
+            crate::debug::NodeKind::Worker,
+            None,
+            &input_sender,
+        );
+        #[cfg(feature = "component_graph")]
+        let output_receiver = output_receiver.with_source(&graph_node);
+
         thread::spawn(move || {
             let context = glib::MainContext::thread_default().unwrap_or_default();
 
@@ -108,7 +123,7 @@ where
             // updates, and send `Self::Output` messages externally.
             context.block_on(async move {
                 let mut cmd = GuardedReceiver::new(cmd_receiver);
-                let mut input = GuardedReceiver::new(input_receiver);
+                let mut input = InputReceiver::new(input_receiver, priority_receiver);
 
                 loop {
                     futures::select!(
@@ -128,7 +143,14 @@ where
                             );
                             let _enter = span.enter();
 
+                            #[cfg(feature = "component_graph")]
+                            graph_node.input();
+
+                            #[cfg(feature = "metrics")]
+                            let start = std::time::Instant::now();
                             model.update_with_view(widgets, message, component_sender.clone(), &root);
+                            #[cfg(feature = "metrics")]
+                            crate::telemetry::update("update_with_view", any::type_name::<C>(), start.elapsed());
                         }
 
                         // Handles responses from a command.
@@ -146,7 +168,11 @@ where
                             );
                             let _enter = span.enter();
 
+                            #[cfg(feature = "metrics")]
+                            let start = std::time::Instant::now();
                             model.update_cmd_with_view(widgets, message, component_sender.clone(), &root);
+                            #[cfg(feature = "metrics")]
+                            crate::telemetry::update("update_cmd_with_view", any::type_name::<C>(), start.elapsed());
                         },
 
                         // Triggered when the component is destroyed
@@ -160,6 +186,9 @@ where
 
                             shutdown_notifier.shutdown();
 
+                            #[cfg(feature = "component_graph")]
+                            graph_node.shutdown();
+
                             return;
                         }
                     );
@@ -280,3 +309,391 @@ impl<W: Component> WorkerController<W> {
         self.shutdown_on_drop.deactivate();
     }
 }
+
+/// A worker that can be restarted after it panicked.
+///
+/// Supervised workers are started with
+/// [`ComponentBuilder::detach_supervised_worker()`].
+pub trait SupervisedWorker: Component<Root = (), Widgets = ()> {
+    /// A snapshot of the state of the worker that survives a panic.
+    ///
+    /// This can be a copy of the relevant state or a serialized form of it.
+    type Snapshot: Clone + Send + 'static;
+
+    /// Takes a snapshot of the state after an input or command output
+    /// was handled successfully.
+    ///
+    /// Returning [`None`] keeps the previous snapshot.
+    fn snapshot(&self) -> Option<Self::Snapshot>;
+
+    /// Restores the state from the last snapshot
+    /// after the worker was initialized again.
+    fn restore(&mut self, snapshot: Self::Snapshot);
+}
+
+/// Determines whether a supervised worker is restarted after it panicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Never restart the worker.
+    Never,
+    /// Restart the worker at most the given number of times.
+    Limited(usize),
+    /// Always restart the worker.
+    Always,
+}
+
+impl RestartPolicy {
+    const fn allows(self, restarts: usize) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Limited(max) => restarts < max,
+            Self::Always => true,
+        }
+    }
+}
+
+/// Events of a supervised worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerEvent<O> {
+    /// An output of the worker.
+    Output(O),
+    /// The worker panicked.
+    ///
+    /// If it isn't restarted, inputs are ignored from now on.
+    Down {
+        /// The panic message.
+        message: String,
+        /// Whether the worker will be restarted.
+        restarting: bool,
+    },
+    /// The worker was initialized again and restored from its last snapshot.
+    Restarted {
+        /// The number of restarts so far.
+        restarts: usize,
+    },
+}
+
+impl<C> ComponentBuilder<C>
+where
+    C: SupervisedWorker + Send,
+    C::Init: Clone + Send,
+    C::Input: Send,
+    C::Output: Send,
+    C::CommandOutput: Send,
+{
+    /// Starts a supervised worker on a separate thread.
+    ///
+    /// In contrast to [`detach_worker()`](ComponentBuilder::detach_worker),
+    /// panics while handling inputs or command outputs don't stop the worker silently.
+    /// Instead, [`WorkerEvent::Down`] is sent and, if `policy` allows it,
+    /// the worker is initialized again with a clone of `payload`
+    /// and restored from its last snapshot, see [`SupervisedWorker`].
+    ///
+    /// Commands that were started before the panic keep running
+    /// and their outputs are handled by the restarted worker.
+    /// Panics can only be caught if the application is compiled with `panic = "unwind"`.
+    pub fn detach_supervised_worker(
+        mut self,
+        payload: C::Init,
+        policy: RestartPolicy,
+    ) -> SupervisedWorkerHandle<C> {
+        // Used for all events to be processed by this component's internal service.
+        let (input_sender, input_receiver) = self.input_channel();
+        let Self { root, .. } = self;
+        let (event_sender, event_receiver) = crate::channel::<WorkerEvent<C::Output>>();
+
+        let RuntimeSenders {
+            output_sender,
+            output_receiver,
+            cmd_sender,
+            cmd_receiver,
+            shutdown_notifier,
+            shutdown_recipient,
+            shutdown_on_drop,
+            mut shutdown_event,
+        } = RuntimeSenders::<C::Output, C::CommandOutput>::new();
+
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
+        // Encapsulates the senders used by component methods.
+        let component_sender = ComponentSender::new(
+            input_sender.clone(),
+            priority_sender,
+            output_sender.clone(),
+            cmd_sender,
+            shutdown_recipient,
+        );
+
+        let state = C::init(payload.clone(), root, component_sender.clone());
+
+        #[cfg(feature = "component_graph")]
+        let graph_node = crate::debug::register(
+            any::type_name::<C>(),
+            crate::debug::NodeKind::Worker,
+            None,
+            &input_sender,
+        );
+        #[cfg(feature = "component_graph")]
+        let event_receiver = event_receiver.with_source(&graph_node);
+
+        thread::spawn(move || {
+            let context = glib::MainContext::thread_default().unwrap_or_default();
+
+            context.block_on(async move {
+                let mut supervisor = Supervisor {
+                    state: Some(state),
+                    snapshot: None,
+                    init: payload,
+                    policy,
+                    restarts: 0,
+                    sender: component_sender,
+                    outputs: output_receiver.0.clone(),
+                    events: event_sender,
+                };
+
+                let mut cmd = GuardedReceiver::new(cmd_receiver);
+                let mut input = InputReceiver::new(input_receiver, priority_receiver);
+                let mut output = GuardedReceiver::new(output_receiver);
+
+                loop {
+                    futures::select!(
+                        message = input => {
+                            let span = info_span!(
+                                "update_with_view",
+                                input=?message,
+                                component=any::type_name::<C>(),
+                            );
+                            let _enter = span.enter();
+
+                            #[cfg(feature = "component_graph")]
+                            graph_node.input();
+
+                            #[cfg(feature = "metrics")]
+                            let start = std::time::Instant::now();
+                            supervisor.run(|model, sender| {
+                                model.update_with_view(&mut (), message, sender, &root);
+                            });
+                            #[cfg(feature = "metrics")]
+                            crate::telemetry::update("update_with_view", any::type_name::<C>(), start.elapsed());
+                        }
+
+                        message = cmd => {
+                            let span = info_span!(
+                                "update_cmd_with_view",
+                                cmd_output=?message,
+                                component=any::type_name::<C>(),
+                            );
+                            let _enter = span.enter();
+
+                            #[cfg(feature = "metrics")]
+                            let start = std::time::Instant::now();
+                            supervisor.run(|model, sender| {
+                                model.update_cmd_with_view(&mut (), message, sender, &root);
+                            });
+                            #[cfg(feature = "metrics")]
+                            crate::telemetry::update("update_cmd_with_view", any::type_name::<C>(), start.elapsed());
+                        }
+
+                        message = output => {
+                            supervisor.events.emit(WorkerEvent::Output(message));
+                        }
+
+                        // Triggered when the component is destroyed
+                        _ = shutdown_event => {
+                            if let Some(mut state) = supervisor.state.take() {
+                                state.model.shutdown(&mut state.widgets, output_sender);
+                            }
+                            supervisor.forward_outputs();
+
+                            shutdown_notifier.shutdown();
+
+                            #[cfg(feature = "component_graph")]
+                            graph_node.shutdown();
+
+                            return;
+                        }
+                    );
+                }
+            });
+        });
+
+        SupervisedWorkerHandle {
+            sender: input_sender,
+            receiver: event_receiver,
+            shutdown_on_drop,
+        }
+    }
+}
+
+/// Keeps the state of a supervised worker and restarts it after panics.
+struct Supervisor<C: SupervisedWorker> {
+    /// The state of the worker or [`None`] if it's down.
+    state: Option<ComponentParts<C>>,
+    snapshot: Option<C::Snapshot>,
+    init: C::Init,
+    policy: RestartPolicy,
+    restarts: usize,
+    sender: ComponentSender<C>,
+    /// Used to forward the outputs that were sent before a panic.
+    outputs: flume::Receiver<C::Output>,
+    events: Sender<WorkerEvent<C::Output>>,
+}
+
+impl<C> Supervisor<C>
+where
+    C: SupervisedWorker,
+    C::Init: Clone,
+{
+    fn run<F: FnOnce(&mut C, ComponentSender<C>)>(&mut self, func: F) {
+        let Some(state) = &mut self.state else {
+            tracing::warn!("Message was ignored because the worker is down");
+            return;
+        };
+
+        let sender = self.sender.clone();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            func(&mut state.model, sender);
+            state.model.snapshot()
+        }));
+
+        match result {
+            Ok(snapshot) => {
+                if snapshot.is_some() {
+                    self.snapshot = snapshot;
+                }
+            }
+            Err(payload) => {
+                self.state = None;
+                self.report_down(&*payload);
+                self.restart();
+            }
+        }
+    }
+
+    fn restart(&mut self) {
+        while self.policy.allows(self.restarts) {
+            self.restarts += 1;
+
+            let init = self.init.clone();
+            let sender = self.sender.clone();
+            let snapshot = self.snapshot.clone();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut state = C::init(init, (), sender);
+                if let Some(snapshot) = snapshot {
+                    state.model.restore(snapshot);
+                }
+                state
+            }));
+
+            match result {
+                Ok(state) => {
+                    self.state = Some(state);
+                    self.events.emit(WorkerEvent::Restarted {
+                        restarts: self.restarts,
+                    });
+                    return;
+                }
+                Err(payload) => self.report_down(&*payload),
+            }
+        }
+    }
+
+    fn report_down(&self, payload: &(dyn Any + Send)) {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("Unknown panic"));
+        tracing::error!("Worker {} panicked: {message}", any::type_name::<C>());
+
+        self.forward_outputs();
+        self.events.emit(WorkerEvent::Down {
+            message,
+            restarting: self.policy.allows(self.restarts),
+        });
+    }
+
+    /// Forwards the outputs that were already sent,
+    /// so they arrive before events of the supervisor.
+    fn forward_outputs(&self) {
+        for output in self.outputs.try_iter() {
+            self.events.emit(WorkerEvent::Output(output));
+        }
+    }
+}
+
+/// Handle to a supervised worker task in the background.
+#[derive(Debug)]
+pub struct SupervisedWorkerHandle<W: Component> {
+    // Sends inputs to the worker.
+    sender: Sender<W::Input>,
+    // Where the worker and its supervisor will send events to.
+    receiver: Receiver<WorkerEvent<W::Output>>,
+    // Shutdown the worker when this is dropped
+    shutdown_on_drop: ShutdownOnDrop,
+}
+
+impl<W: Component> SupervisedWorkerHandle<W>
+where
+    W::Input: 'static,
+    W::Output: 'static,
+{
+    /// Given a mutable closure, captures the receiver for handling.
+    pub fn connect_receiver<F>(self, mut func: F) -> WorkerController<W>
+    where
+        F: FnMut(&mut Sender<W::Input>, WorkerEvent<W::Output>) + 'static,
+    {
+        let Self {
+            sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        let mut sender_ = sender.clone();
+        crate::spawn_local(async move {
+            while let Some(event) = receiver.recv().await {
+                func(&mut sender_, event);
+            }
+        });
+
+        WorkerController {
+            sender,
+            shutdown_on_drop,
+        }
+    }
+
+    /// Forwards events to the designated sender.
+    pub fn forward<X: 'static, F: (Fn(WorkerEvent<W::Output>) -> X) + 'static>(
+        self,
+        sender: &Sender<X>,
+        transform: F,
+    ) -> WorkerController<W> {
+        let Self {
+            sender: own_sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        crate::spawn_local(receiver.forward(sender.clone(), transform));
+        WorkerController {
+            sender: own_sender,
+            shutdown_on_drop,
+        }
+    }
+
+    /// Ignore events from the worker and finish the builder.
+    #[must_use]
+    pub fn detach(self) -> WorkerController<W> {
+        let Self {
+            sender,
+            shutdown_on_drop,
+            ..
+        } = self;
+
+        WorkerController {
+            sender,
+            shutdown_on_drop,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Runtime configuration from environment variables.
+//!
+//! Some debugging and runtime options of Relm4 can be changed
+//! without recompiling the application:
+//!
+//! | Variable             | Effect                                                          |
+//! |----------------------|-----------------------------------------------------------------|
+//! | `RELM4_LOG_MESSAGES` | Log each message handled by a component as [`tracing`] event.   |
+//! | `RELM4_INSPECTOR`    | Open the GTK inspector when the application starts.             |
+//! | `RELM4_BACKEND`      | Restrict the GDK backends, for example `wayland` or `x11`.      |
+//! | `RELM4_THREADS`      | The number of threads of the runtime for background tasks.      |
+//!
+//! Flags accept `1`, `true`, `yes` and `on`. The variables are read once,
+//! when the configuration is used for the first time. The application can
+//! override them with [`set()`] or [`update()`], for example to disable the inspector
+//! in release builds:
+//!
+//! ```
+//! relm4::config::update(|config| {
+//!     config.inspector &= cfg!(debug_assertions);
+//! });
+//! ```
+//!
+//! Options that affect the initialization must be set before [`RelmApp::new()`](crate::RelmApp::new)
+//! and before any background task is spawned.
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+/// The target of the [`tracing`] events of `RELM4_LOG_MESSAGES`.
+pub const MESSAGES_TARGET: &str = "relm4::messages";
+
+static CONFIG: Lazy<RwLock<Config>> = Lazy::new(|| {
+    let config = Config::from_env();
+    LOG_MESSAGES.store(config.log_messages, Ordering::Relaxed);
+    RwLock::new(config)
+});
+
+/// Mirrors [`Config::log_messages`], because it's checked for every message.
+static LOG_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// The runtime configuration of Relm4, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Config {
+    /// Log each message handled by a component (`RELM4_LOG_MESSAGES`).
+    pub log_messages: bool,
+    /// Open the GTK inspector on startup (`RELM4_INSPECTOR`).
+    pub inspector: bool,
+    /// The allowed GDK backends, separated by commas (`RELM4_BACKEND`).
+    pub backend: Option<String>,
+    /// The number of threads for background tasks (`RELM4_THREADS`).
+    ///
+    /// [`RELM_THREADS`](crate::RELM_THREADS) takes precedence, if it's set.
+    pub threads: Option<usize>,
+}
+
+impl Config {
+    /// Reads the configuration from the environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |name| {
+            var(name).is_some_and(|value| {
+                matches!(
+                    value.to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+        };
+
+        Self {
+            log_messages: flag("RELM4_LOG_MESSAGES"),
+            inspector: flag("RELM4_INSPECTOR"),
+            backend: var("RELM4_BACKEND").filter(|backend| !backend.is_empty()),
+            threads: var("RELM4_THREADS").and_then(|threads| match threads.parse() {
+                Ok(threads) if threads > 0 => Some(threads),
+                _ => {
+                    tracing::warn!(threads, "Ignoring invalid value of `RELM4_THREADS`");
+                    None
+                }
+            }),
+        }
+    }
+}
+
+/// Returns the current configuration.
+#[must_use]
+pub fn get() -> Config {
+    CONFIG.read().unwrap().clone()
+}
+
+/// Replaces the configuration, including the values read from the environment.
+pub fn set(config: Config) {
+    update(|current| *current = config);
+}
+
+/// Changes the configuration.
+pub fn update(f: impl FnOnce(&mut Config)) {
+    let mut config = CONFIG.write().unwrap();
+    f(&mut config);
+    LOG_MESSAGES.store(config.log_messages, Ordering::Relaxed);
+}
+
+/// Applies the options that must be set before GTK is initialized.
+pub(crate) fn apply_before_init() {
+    if let Some(backend) = &get().backend {
+        gtk::gdk::set_allowed_backends(backend);
+    }
+}
+
+/// Applies the options that need an initialized application.
+pub(crate) fn apply_on_startup() {
+    if get().inspector {
+        gtk::Window::set_interactive_debugging(true);
+    }
+}
+
+/// Logs a message that is about to be handled by a component, if enabled.
+pub(crate) fn log_message(component: &'static str, message: &dyn Debug) {
+    Lazy::force(&CONFIG);
+    if LOG_MESSAGES.load(Ordering::Relaxed) {
+        tracing::info!(target: MESSAGES_TARGET, component, "{message:?}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Config;
+
+    #[test]
+    fn from_vars() {
+        let config = Config::from_vars(|name| {
+            match name {
+                "RELM4_LOG_MESSAGES" => Some("Yes"),
+                "RELM4_INSPECTOR" => Some("0"),
+                "RELM4_BACKEND" => Some("wayland,x11"),
+                "RELM4_THREADS" => Some("4"),
+                _ => None,
+            }
+            .map(ToOwned::to_owned)
+        });
+        assert!(config.log_messages);
+        assert!(!config.inspector);
+        assert_eq!(config.backend.as_deref(), Some("wayland,x11"));
+        assert_eq!(config.threads, Some(4));
+
+        let config = Config::from_vars(|name| (name == "RELM4_THREADS").then(|| "0".to_owned()));
+        assert_eq!(config, Config::default());
+    }
+}
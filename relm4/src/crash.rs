@@ -0,0 +1,264 @@
+//! Crash reports with the context of the application.
+//!
+//! [`RelmApp::with_crash_handler()`](crate::RelmApp::with_crash_handler) installs
+//! hooks for panics and critical GLib log messages. Critical GLib messages are only
+//! reported after the log writer of Relm4 was installed with
+//! [`log_writer::install()`](crate::log_writer::install).
+//! When one of the hooks is triggered,
+//! the handler receives a [`CrashReport`] with the last messages that were handled
+//! by components, the component that was updated at that time and the versions
+//! of the system and GTK. The handler can, for example, write the report to a file
+//! or show it in a dialog:
+//!
+//! ```no_run
+//! # use relm4::RelmApp;
+//! relm4::log_writer::install();
+//! let app = RelmApp::<()>::new("org.relm4.Example").with_crash_handler(|report| {
+//!     let path = std::env::temp_dir().join("example-crash.txt");
+//!     std::fs::write(path, report.to_string()).ok();
+//! });
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use gtk::glib::{LogLevel, LogWriterOutput};
+
+/// The number of messages kept for crash reports.
+pub const RECENT_MESSAGES: usize = 32;
+
+type Handler = Arc<dyn Fn(&CrashReport) + Send + Sync>;
+
+static HANDLER: Mutex<Option<Handler>> = Mutex::new(None);
+/// Avoids formatting messages if no handler is installed.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static MESSAGES: Mutex<VecDeque<RecentMessage>> = Mutex::new(VecDeque::new());
+
+/// What triggered a [`CrashReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CrashKind {
+    /// A panic.
+    Panic {
+        /// The file and line of the panic, if known.
+        location: Option<String>,
+    },
+    /// A critical or error message was logged by GLib, GTK or another library.
+    Critical {
+        /// The log domain, like `Gtk`.
+        domain: Option<String>,
+    },
+}
+
+/// A message that was handled by a component shortly before the crash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentMessage {
+    /// The type name of the component.
+    pub component: &'static str,
+    /// The debug representation of the message.
+    pub message: String,
+    /// When the component started to handle the message.
+    pub time: SystemTime,
+}
+
+/// The context of a crash, see the [module documentation](self).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CrashReport {
+    /// What triggered the report.
+    pub kind: CrashKind,
+    /// The panic or log message.
+    pub message: String,
+    /// The name of the thread the crash happened on.
+    pub thread: Option<String>,
+    /// The components whose updates were running, the innermost last.
+    pub components: Vec<&'static str>,
+    /// The last messages handled by components, the latest last.
+    pub recent_messages: Vec<RecentMessage>,
+    /// The running components, if the report was created on the main thread.
+    #[cfg(feature = "component_graph")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "component_graph")))]
+    pub component_graph: Option<crate::debug::ComponentGraph>,
+    /// The operating system and architecture, like `linux x86_64`.
+    pub os: String,
+    /// The version of the GTK library the application runs with.
+    pub gtk_version: String,
+    /// The version of Relm4.
+    pub relm4_version: &'static str,
+}
+
+impl CrashReport {
+    fn new(kind: CrashKind, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            thread: std::thread::current().name().map(ToOwned::to_owned),
+            components: crate::mainloop::running_updates(),
+            recent_messages: MESSAGES
+                .lock()
+                .map(|messages| messages.iter().cloned().collect())
+                .unwrap_or_default(),
+            #[cfg(feature = "component_graph")]
+            component_graph: gtk::is_initialized_main_thread()
+                .then(crate::debug::export_component_graph),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            gtk_version: format!(
+                "{}.{}.{}",
+                gtk::major_version(),
+                gtk::minor_version(),
+                gtk::micro_version()
+            ),
+            relm4_version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CrashKind::Panic { location } => {
+                write!(f, "Panic")?;
+                if let Some(location) = location {
+                    write!(f, " at {location}")?;
+                }
+            }
+            CrashKind::Critical { domain } => {
+                write!(f, "Critical message")?;
+                if let Some(domain) = domain {
+                    write!(f, " from {domain}")?;
+                }
+            }
+        }
+        if let Some(thread) = &self.thread {
+            write!(f, " on thread '{thread}'")?;
+        }
+        writeln!(f, ": {}", self.message)?;
+
+        writeln!(
+            f,
+            "Relm4 {}, GTK {}, {}",
+            self.relm4_version, self.gtk_version, self.os
+        )?;
+
+        if !self.components.is_empty() {
+            writeln!(f, "\nRunning updates:")?;
+            for component in &self.components {
+                writeln!(f, "  {component}")?;
+            }
+        }
+
+        if !self.recent_messages.is_empty() {
+            writeln!(f, "\nRecent messages:")?;
+            for message in &self.recent_messages {
+                writeln!(f, "  {}: {}", message.component, message.message)?;
+            }
+        }
+
+        #[cfg(feature = "component_graph")]
+        if let Some(graph) = &self.component_graph {
+            writeln!(f, "\nComponents:\n{}", graph.to_dot())?;
+        }
+        Ok(())
+    }
+}
+
+/// Installs the hooks that call `handler`.
+///
+/// Hooks are only installed once, later calls replace the handler.
+pub(crate) fn set_handler<F>(handler: F)
+where
+    F: Fn(&CrashReport) + Send + Sync + 'static,
+{
+    let handler: Handler = Arc::new(handler);
+    let first = HANDLER.lock().unwrap().replace(handler).is_none();
+    ENABLED.store(true, Ordering::Relaxed);
+    if !first {
+        return;
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_owned()
+        };
+        let location = info.location().map(ToString::to_string);
+        report(CrashReport::new(CrashKind::Panic { location }, message));
+        default_hook(info);
+    }));
+
+    crate::log_writer::add_hook(|level, fields| {
+        if matches!(level, LogLevel::Critical | LogLevel::Error) {
+            let message = crate::log_writer::field(fields, "MESSAGE").unwrap_or_default();
+            let domain = crate::log_writer::field(fields, "GLIB_DOMAIN").map(ToOwned::to_owned);
+            report(CrashReport::new(
+                CrashKind::Critical { domain },
+                message.to_owned(),
+            ));
+        }
+        LogWriterOutput::Unhandled
+    });
+}
+
+fn report(report: CrashReport) {
+    // Clone the handler, so it can crash itself without a deadlock.
+    let handler = HANDLER.lock().ok().and_then(|handler| handler.clone());
+    if let Some(handler) = handler {
+        handler(&report);
+    }
+}
+
+/// Records a message that is about to be handled by a component.
+pub(crate) fn record(component: &'static str, message: &dyn Debug) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let message = RecentMessage {
+        component,
+        message: format!("{message:?}"),
+        time: SystemTime::now(),
+    };
+    if let Ok(mut messages) = MESSAGES.lock() {
+        if messages.len() == RECENT_MESSAGES {
+            messages.pop_front();
+        }
+        messages.push_back(message);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{record, CrashKind, CrashReport, ENABLED, RECENT_MESSAGES};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn recent_messages() {
+        ENABLED.store(true, Ordering::Relaxed);
+        for i in 0..RECENT_MESSAGES + 2 {
+            record("app::App", &i);
+        }
+
+        let report = {
+            let _guard = crate::mainloop::enter_update("app::App");
+            CrashReport::new(
+                CrashKind::Panic { location: None },
+                "Out of bounds".to_owned(),
+            )
+        };
+        assert_eq!(report.components, ["app::App"]);
+        assert_eq!(report.recent_messages.len(), RECENT_MESSAGES);
+        assert_eq!(report.recent_messages[0].message, "2");
+
+        let text = report.to_string();
+        assert!(text.starts_with("Panic"));
+        assert!(text.contains(": Out of bounds\n"));
+        assert!(text.contains("  app::App: 33\n"));
+    }
+}
@@ -0,0 +1,464 @@
+//! Visualization of the component hierarchy of running applications.
+//!
+//! With the `component_graph` feature, Relm4 tracks all components and workers
+//! that are launched on the main thread together with the links between them.
+//! [`export_component_graph()`] returns a snapshot of this graph that can be
+//! rendered with [Graphviz](https://graphviz.org) or processed as JSON.
+//!
+//! The hierarchy is derived from the widget tree: a component is the child
+//! of the component whose root widget contains its root widget or whose window
+//! its window is transient for. Forwarded outputs are recorded as links between
+//! components and count the number of messages that were sent through them.
+//!
+//! Factory elements aren't tracked.
+//!
+//! ```
+//! # gtk::init().unwrap();
+//! let graph = relm4::debug::export_component_graph();
+//! std::fs::write("components.dot", graph.to_dot()).ok();
+//! # std::fs::remove_file("components.dot").ok();
+//! ```
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use gtk::glib::object::{Cast, ObjectExt};
+use gtk::glib::WeakRef;
+use gtk::prelude::{GtkWindowExt, WidgetExt};
+
+use crate::Sender;
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    entries: Vec<Entry>,
+    links: Vec<Link>,
+    next_id: u64,
+}
+
+impl Registry {
+    /// Removes the components that were shut down and their links.
+    fn prune(&mut self) {
+        self.entries
+            .retain(|entry| entry.state.running.load(Ordering::Relaxed));
+        let entries = &self.entries;
+        self.links.retain(|link| {
+            [link.from, link.to]
+                .iter()
+                .all(|id| entries.iter().any(|entry| entry.id == *id))
+        });
+    }
+
+    /// Returns the component that receives the messages of `sender`.
+    fn find<T: 'static>(&self, sender: &Sender<T>) -> Option<u64> {
+        self.entries.iter().find_map(|entry| {
+            let input = entry.input.downcast_ref::<flume::WeakSender<T>>()?;
+            input
+                .upgrade()
+                .is_some_and(|input| input.same_channel(&sender.0))
+                .then_some(entry.id)
+        })
+    }
+}
+
+struct Entry {
+    id: u64,
+    component: &'static str,
+    kind: NodeKind,
+    broker: bool,
+    root: Option<WeakRef<gtk::Widget>>,
+    /// The weak input sender of the component.
+    input: Box<dyn Any>,
+    state: Arc<NodeState>,
+}
+
+impl std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("id", &self.id)
+            .field("component", &self.component)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct Link {
+    from: u64,
+    to: u64,
+    messages: Arc<AtomicU64>,
+}
+
+#[derive(Debug)]
+struct NodeState {
+    inputs: AtomicU64,
+    running: AtomicBool,
+}
+
+/// A handle of a component runtime that is tracked in the component graph.
+///
+/// The runtime might run on a different thread, so the handle doesn't
+/// access the registry directly.
+#[derive(Debug, Clone)]
+pub(crate) struct Node {
+    id: u64,
+    state: Arc<NodeState>,
+}
+
+impl Node {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Counts an input that was processed by the component.
+    pub(crate) fn input(&self) {
+        self.state.inputs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Marks the runtime of the component as shut down.
+    pub(crate) fn shutdown(&self) {
+        self.state.running.store(false, Ordering::Relaxed);
+    }
+}
+
+/// The kind of a [`ComponentNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// A [`Component`](crate::Component).
+    Component,
+    /// An [`AsyncComponent`](crate::component::AsyncComponent).
+    AsyncComponent,
+    /// A component that runs as worker on another thread.
+    Worker,
+}
+
+impl NodeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Component => "component",
+            Self::AsyncComponent => "async_component",
+            Self::Worker => "worker",
+        }
+    }
+}
+
+/// A running component in the [`ComponentGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentNode {
+    /// Identifies the component in the graph.
+    pub id: u64,
+    /// The type name of the component.
+    pub component: &'static str,
+    /// The kind of the component.
+    pub kind: NodeKind,
+    /// Whether the component was launched with a [`MessageBroker`](crate::MessageBroker).
+    pub broker: bool,
+    /// The number of inputs the component has processed.
+    pub inputs: u64,
+}
+
+/// The kind of a [`ComponentEdge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EdgeKind {
+    /// The widgets of the target are part of the widgets of the source.
+    Child,
+    /// The outputs of the source are forwarded to the target.
+    Output {
+        /// The number of forwarded messages.
+        messages: u64,
+    },
+}
+
+/// A relation between two components in the [`ComponentGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentEdge {
+    /// The id of the source node.
+    pub from: u64,
+    /// The id of the target node.
+    pub to: u64,
+    /// The kind of the relation.
+    pub kind: EdgeKind,
+}
+
+/// A snapshot of the running components, see [`export_component_graph()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentGraph {
+    /// The running components.
+    pub nodes: Vec<ComponentNode>,
+    /// The relations between the components.
+    pub edges: Vec<ComponentEdge>,
+}
+
+impl ComponentGraph {
+    /// Renders the graph in the DOT language of Graphviz.
+    ///
+    /// Child relations are drawn as solid and output links as dashed edges.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph components {\n    node [shape=box];\n");
+        for node in &self.nodes {
+            let name = escape(short_name(node.component));
+            let mut label = format!("{name}\\n{} inputs", node.inputs);
+            if node.broker {
+                label.push_str("\\nbroker");
+            }
+            let shape = match node.kind {
+                NodeKind::Worker => ", shape=ellipse",
+                _ => "",
+            };
+            writeln!(output, "    n{} [label=\"{label}\"{shape}];", node.id).unwrap();
+        }
+        for edge in &self.edges {
+            match edge.kind {
+                EdgeKind::Child => writeln!(output, "    n{} -> n{};", edge.from, edge.to),
+                EdgeKind::Output { messages } => writeln!(
+                    output,
+                    "    n{} -> n{} [style=dashed, label=\"{messages}\"];",
+                    edge.from, edge.to
+                ),
+            }
+            .unwrap();
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Renders the graph as JSON object with `nodes` and `edges` arrays.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    r#"{{"id":{},"component":"{}","kind":"{}","broker":{},"inputs":{}}}"#,
+                    node.id,
+                    escape(node.component),
+                    node.kind.as_str(),
+                    node.broker,
+                    node.inputs
+                )
+            })
+            .collect();
+        let edges: Vec<String> = self
+            .edges
+            .iter()
+            .map(|edge| match edge.kind {
+                EdgeKind::Child => {
+                    format!(
+                        r#"{{"from":{},"to":{},"kind":"child"}}"#,
+                        edge.from, edge.to
+                    )
+                }
+                EdgeKind::Output { messages } => format!(
+                    r#"{{"from":{},"to":{},"kind":"output","messages":{messages}}}"#,
+                    edge.from, edge.to
+                ),
+            })
+            .collect();
+        format!(
+            r#"{{"nodes":[{}],"edges":[{}]}}"#,
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+/// Starts tracking a component runtime.
+pub(crate) fn register<T: 'static>(
+    component: &'static str,
+    kind: NodeKind,
+    root: Option<gtk::Widget>,
+    input: &Sender<T>,
+) -> Node {
+    let state = Arc::new(NodeState {
+        inputs: AtomicU64::new(0),
+        running: AtomicBool::new(true),
+    });
+
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.prune();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.entries.push(Entry {
+            id,
+            component,
+            kind,
+            broker: false,
+            root: root.as_ref().map(ObjectExt::downgrade),
+            input: Box::new(input.0.downgrade()),
+            state: state.clone(),
+        });
+        Node { id, state }
+    })
+}
+
+/// Marks the component that receives the messages of `sender` as launched with a broker.
+pub(crate) fn set_broker<T: 'static>(sender: &Sender<T>) {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        if let Some(id) = registry.find(sender) {
+            if let Some(entry) = registry.entries.iter_mut().find(|entry| entry.id == id) {
+                entry.broker = true;
+            }
+        }
+    });
+}
+
+/// Records that the outputs of the component `from` are forwarded to `sender`.
+///
+/// Returns the counter of the forwarded messages, if the link is tracked.
+pub(crate) fn link<T: 'static>(from: Option<u64>, sender: &Sender<T>) -> Option<Arc<AtomicU64>> {
+    let from = from?;
+    REGISTRY
+        .try_with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let to = registry.find(sender)?;
+            let messages = Arc::new(AtomicU64::new(0));
+            registry.links.push(Link {
+                from,
+                to,
+                messages: messages.clone(),
+            });
+            Some(messages)
+        })
+        .ok()
+        .flatten()
+}
+
+/// Returns a snapshot of the components running on the main thread.
+///
+/// Only components that were launched on the current thread are included,
+/// so this should be called from the main thread.
+#[must_use]
+pub fn export_component_graph() -> ComponentGraph {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.prune();
+
+        let nodes = registry
+            .entries
+            .iter()
+            .map(|entry| ComponentNode {
+                id: entry.id,
+                component: entry.component,
+                kind: entry.kind,
+                broker: entry.broker,
+                inputs: entry.state.inputs.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        let roots: HashMap<gtk::Widget, u64> = registry
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.root.as_ref()?.upgrade()?, entry.id)))
+            .collect();
+
+        let mut edges: Vec<ComponentEdge> = roots
+            .iter()
+            .filter_map(|(root, id)| {
+                let mut widget = next_ancestor(root);
+                while let Some(current) = widget {
+                    if let Some(parent) = roots.get(&current) {
+                        return Some(ComponentEdge {
+                            from: *parent,
+                            to: *id,
+                            kind: EdgeKind::Child,
+                        });
+                    }
+                    widget = next_ancestor(&current);
+                }
+                None
+            })
+            .collect();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+
+        edges.extend(registry.links.iter().map(|link| ComponentEdge {
+            from: link.from,
+            to: link.to,
+            kind: EdgeKind::Output {
+                messages: link.messages.load(Ordering::Relaxed),
+            },
+        }));
+
+        ComponentGraph { nodes, edges }
+    })
+}
+
+/// Returns the parent of a widget or the window a window is transient for.
+fn next_ancestor(widget: &gtk::Widget) -> Option<gtk::Widget> {
+    widget.parent().or_else(|| {
+        widget
+            .downcast_ref::<gtk::Window>()?
+            .transient_for()
+            .map(Cast::upcast)
+    })
+}
+
+/// Removes the module path of a type name but keeps generics.
+fn short_name(type_name: &str) -> &str {
+    let end = type_name.find('<').unwrap_or(type_name.len());
+    type_name[..end]
+        .rfind("::")
+        .map_or(type_name, |start| &type_name[start + 2..])
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ComponentEdge, ComponentGraph, ComponentNode, EdgeKind, NodeKind};
+
+    #[test]
+    fn formats() {
+        let graph = ComponentGraph {
+            nodes: vec![
+                ComponentNode {
+                    id: 0,
+                    component: "app::App",
+                    kind: NodeKind::Component,
+                    broker: false,
+                    inputs: 3,
+                },
+                ComponentNode {
+                    id: 1,
+                    component: "app::Loader",
+                    kind: NodeKind::Worker,
+                    broker: true,
+                    inputs: 1,
+                },
+            ],
+            edges: vec![ComponentEdge {
+                from: 1,
+                to: 0,
+                kind: EdgeKind::Output { messages: 2 },
+            }],
+        };
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(r#"n0 [label="App\n3 inputs"];"#));
+        assert!(dot.contains(r#"n1 [label="Loader\n1 inputs\nbroker", shape=ellipse];"#));
+        assert!(dot.contains(r#"n1 -> n0 [style=dashed, label="2"];"#));
+
+        assert_eq!(
+            graph.to_json(),
+            concat!(
+                r#"{"nodes":[{"id":0,"component":"app::App","kind":"component","broker":false,"inputs":3},"#,
+                r#"{"id":1,"component":"app::Loader","kind":"worker","broker":true,"inputs":1}],"#,
+                r#""edges":[{"from":1,"to":0,"kind":"output","messages":2}]}"#
+            )
+        );
+    }
+}
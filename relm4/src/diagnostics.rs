@@ -0,0 +1,231 @@
+//! Diagnostics to find leaked components and widgets.
+//!
+//! A common mistake is to drop the controller of a component
+//! while its root widget is still shown, which leaves a widget
+//! behind that doesn't react to any inputs anymore.
+//! The opposite mistake is to remove the root widget from its parent
+//! but to keep the controller, so the component keeps running
+//! and holds on to all its widgets.
+//!
+//! With the `leak_diagnostics` feature, Relm4 tracks the runtimes and root widgets
+//! of all components that run on the main thread and records both cases as
+//! [`LeakCandidate`]s. The candidates are reported as warnings via [`tracing`]
+//! when the application shuts down.
+//! [`component_stats()`] shows the number of widgets and event controllers
+//! per component type.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+use gtk::glib::object::ObjectExt;
+use gtk::glib::{self, WeakRef};
+use gtk::prelude::{ListModelExt, WidgetExt};
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    entries: HashMap<u64, Entry>,
+    candidates: Vec<LeakCandidate>,
+    next_id: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    component: &'static str,
+    root: Option<WeakRef<gtk::Widget>>,
+    /// Windows and popovers don't have a parent.
+    toplevel: bool,
+    running: bool,
+    /// Whether this component was already recorded as leak candidate.
+    reported: bool,
+}
+
+/// Identifies a component runtime that is tracked by the diagnostics.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DiagnosticsId(u64);
+
+/// Describes why a component might be leaked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LeakKind {
+    /// The runtime of the component was shut down,
+    /// but its root widget is still part of the widget tree.
+    ParentedAfterShutdown,
+    /// The root widget was removed from the widget tree
+    /// or destroyed, but the runtime of the component is still running.
+    DetachedWhileRunning,
+}
+
+/// A component that might be leaked, see [`leak_candidates()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakCandidate {
+    /// The type name of the component.
+    pub component: &'static str,
+    /// Why the component might be leaked.
+    pub kind: LeakKind,
+}
+
+/// Statistics of all live components of a type, see [`component_stats()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentStats {
+    /// The type name of the component.
+    pub component: &'static str,
+    /// Number of running runtimes.
+    pub runtimes: usize,
+    /// Number of root widgets that are still alive.
+    pub roots: usize,
+    /// Number of widgets in the widget trees of all root widgets.
+    pub widgets: usize,
+    /// Number of event controllers attached to these widgets.
+    pub controllers: usize,
+}
+
+/// Starts tracking a component runtime.
+pub(crate) fn register(component: &'static str, root: Option<gtk::Widget>) -> DiagnosticsId {
+    let id = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.entries.insert(
+            id,
+            Entry {
+                component,
+                root: root.as_ref().map(ObjectExt::downgrade),
+                toplevel: root.as_ref().is_some_and(is_toplevel),
+                running: true,
+                reported: false,
+            },
+        );
+        id
+    });
+
+    if let Some(root) = root {
+        root.connect_notify_local(Some("parent"), move |root, _| {
+            if root.parent().is_none() {
+                schedule_check(id);
+            }
+        });
+        root.connect_destroy(move |_| schedule_check(id));
+    }
+    DiagnosticsId(id)
+}
+
+/// Marks the runtime of a component as shut down.
+pub(crate) fn shutdown(id: DiagnosticsId) {
+    REGISTRY.with(|registry| {
+        if let Some(entry) = registry.borrow_mut().entries.get_mut(&id.0) {
+            entry.running = false;
+        }
+    });
+    schedule_check(id.0);
+}
+
+/// Checks the component once pending changes of the widget tree were applied.
+fn schedule_check(id: u64) {
+    glib::idle_add_local_once(move || check(id));
+}
+
+fn check(id: u64) {
+    REGISTRY.with(|registry| {
+        let registry = &mut *registry.borrow_mut();
+        let Some(entry) = registry.entries.get_mut(&id) else {
+            return;
+        };
+
+        let root = entry.root.as_ref().map(WeakRef::upgrade);
+        let kind = match (&root, entry.running) {
+            (Some(Some(root)), false) if root.parent().is_some() => {
+                Some(LeakKind::ParentedAfterShutdown)
+            }
+            (Some(Some(root)), true) if root.parent().is_none() && !entry.toplevel => {
+                Some(LeakKind::DetachedWhileRunning)
+            }
+            (Some(None), true) if !entry.toplevel => Some(LeakKind::DetachedWhileRunning),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            if !entry.reported {
+                entry.reported = true;
+                tracing::debug!(component = entry.component, ?kind, "Possible leak");
+                registry.candidates.push(LeakCandidate {
+                    component: entry.component,
+                    kind,
+                });
+            }
+        } else if !entry.running && !matches!(root, Some(Some(_))) {
+            // Nothing left to track.
+            registry.entries.remove(&id);
+        }
+    });
+}
+
+fn is_toplevel(widget: &gtk::Widget) -> bool {
+    widget.is::<gtk::Window>() || widget.is::<gtk::Popover>()
+}
+
+/// Returns all components that were detected as possibly leaked.
+#[must_use]
+pub fn leak_candidates() -> Vec<LeakCandidate> {
+    REGISTRY.with(|registry| registry.borrow().candidates.clone())
+}
+
+/// Returns statistics about the live components of each type,
+/// sorted by type name.
+#[must_use]
+pub fn component_stats() -> Vec<ComponentStats> {
+    REGISTRY.with(|registry| {
+        let mut stats: BTreeMap<&'static str, ComponentStats> = BTreeMap::new();
+        for entry in registry.borrow().entries.values() {
+            let root = entry.root.as_ref().and_then(WeakRef::upgrade);
+            if !entry.running && root.is_none() {
+                continue;
+            }
+
+            let stats = stats
+                .entry(entry.component)
+                .or_insert_with(|| ComponentStats {
+                    component: entry.component,
+                    ..ComponentStats::default()
+                });
+            stats.runtimes += usize::from(entry.running);
+            if let Some(root) = root {
+                stats.roots += 1;
+                count_widgets(&root, stats);
+            }
+        }
+        stats.into_values().collect()
+    })
+}
+
+fn count_widgets(widget: &gtk::Widget, stats: &mut ComponentStats) {
+    stats.widgets += 1;
+    stats.controllers += widget.observe_controllers().n_items() as usize;
+
+    let mut child = widget.first_child();
+    while let Some(widget) = child {
+        count_widgets(&widget, stats);
+        child = widget.next_sibling();
+    }
+}
+
+/// Reports all leak candidates as warnings.
+pub fn report_leaks() {
+    for candidate in leak_candidates() {
+        match candidate.kind {
+            LeakKind::ParentedAfterShutdown => tracing::warn!(
+                component = candidate.component,
+                "The runtime of the component was shut down, but its root widget is still shown. \
+                Maybe you accidentally dropped a controller?"
+            ),
+            LeakKind::DetachedWhileRunning => tracing::warn!(
+                component = candidate.component,
+                "The root widget of the component was removed, but its runtime is still running. \
+                Maybe you forgot to drop a controller?"
+            ),
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! Monitors of the default display and window placement per monitor.
+//!
+//! [`subscribe()`] forwards a [`MonitorEvent`] to a component whenever a monitor
+//! is connected, disconnected or changes its geometry, scale factor or refresh rate.
+//! [`subscribe_scale_factor()`] forwards the scale factor of a single widget,
+//! which changes when its window is moved to a monitor with a different scale.
+//!
+//! [`WindowPlacement`] remembers the monitor, the size and the state of a window,
+//! so it can be restored on the same monitor if it's still connected:
+//!
+//! ```no_run
+//! # use relm4::gtk;
+//! use relm4::display::WindowPlacement;
+//!
+//! # let window = gtk::Window::new();
+//! let placement = WindowPlacement::save(&window);
+//! // Store the placement and restore it on the next start.
+//! placement.restore(&window);
+//! ```
+//!
+//! Windows can only be moved to another monitor in fullscreen mode,
+//! because the position of normal windows is chosen by the window manager.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::gdk;
+use gtk::prelude::{
+    DisplayExt, GtkWindowExt, IsA, ListModelExt, ListModelExtManual, MonitorExt, NativeExt,
+    SurfaceExt, WidgetExt,
+};
+
+use crate::Sender;
+
+type Subscriber = Box<dyn Fn(&MonitorEvent) -> bool>;
+
+thread_local! {
+    static DISPLAY: RefCell<Option<Rc<Display>>> = const { RefCell::new(None) };
+}
+
+/// The properties of a monitor at the time it was read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorInfo {
+    /// The name of the connector, for example "HDMI-1".
+    ///
+    /// This is the most stable way to identify a monitor,
+    /// but it isn't known on all platforms.
+    pub connector: Option<String>,
+    /// The manufacturer of the monitor.
+    pub manufacturer: Option<String>,
+    /// The model name of the monitor.
+    pub model: Option<String>,
+    /// The x coordinate in the coordinate space of the display.
+    pub x: i32,
+    /// The y coordinate in the coordinate space of the display.
+    pub y: i32,
+    /// The width in application pixels.
+    pub width: i32,
+    /// The height in application pixels.
+    pub height: i32,
+    /// The integer scale factor, for example 2 on most high DPI monitors.
+    pub scale_factor: i32,
+    /// The refresh rate in milli-Hertz, or 0 if it's unknown.
+    pub refresh_rate: i32,
+}
+
+impl MonitorInfo {
+    fn new(monitor: &gdk::Monitor) -> Self {
+        let geometry = monitor.geometry();
+        Self {
+            connector: monitor.connector().map(Into::into),
+            manufacturer: monitor.manufacturer().map(Into::into),
+            model: monitor.model().map(Into::into),
+            x: geometry.x(),
+            y: geometry.y(),
+            width: geometry.width(),
+            height: geometry.height(),
+            scale_factor: monitor.scale_factor(),
+            refresh_rate: monitor.refresh_rate(),
+        }
+    }
+
+    /// Returns the monitor if it's still connected.
+    #[must_use]
+    pub fn monitor(&self) -> Option<gdk::Monitor> {
+        find_monitor(self.connector.as_deref()?)
+    }
+}
+
+/// A change of the monitors of the default display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonitorEvent {
+    /// A monitor was connected.
+    Added(MonitorInfo),
+    /// A monitor was disconnected.
+    Removed(MonitorInfo),
+    /// The geometry, scale factor or refresh rate of a monitor changed.
+    Changed(MonitorInfo),
+}
+
+/// Watches the monitors of the default display.
+struct Display {
+    monitors: RefCell<Vec<(gdk::Monitor, MonitorInfo)>>,
+    subscribers: RefCell<Vec<Subscriber>>,
+    list: gtk::gio::ListModel,
+}
+
+impl Display {
+    fn get() -> Option<Rc<Self>> {
+        DISPLAY.with(|display| {
+            let mut display = display.borrow_mut();
+            if display.is_none() {
+                let list = gdk::Display::default()?.monitors();
+                let new_display = Rc::new(Self {
+                    monitors: RefCell::default(),
+                    subscribers: RefCell::default(),
+                    list,
+                });
+                new_display.start();
+                *display = Some(new_display);
+            }
+            display.clone()
+        })
+    }
+
+    fn start(self: &Rc<Self>) {
+        let weak = Rc::downgrade(self);
+        self.list.connect_items_changed(move |_, _, _, _| {
+            if let Some(display) = weak.upgrade() {
+                display.sync();
+            }
+        });
+        self.sync();
+    }
+
+    fn current_monitors(&self) -> Vec<gdk::Monitor> {
+        self.list
+            .iter::<gdk::Monitor>()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// Compare the known monitors with the current ones.
+    fn sync(self: &Rc<Self>) {
+        let current = self.current_monitors();
+        let mut events = Vec::new();
+
+        {
+            let mut monitors = self.monitors.borrow_mut();
+            monitors.retain(|(monitor, info)| {
+                let connected = current.contains(monitor);
+                if !connected {
+                    events.push(MonitorEvent::Removed(info.clone()));
+                }
+                connected
+            });
+
+            for monitor in current {
+                if monitors.iter().any(|(known, _)| known == &monitor) {
+                    continue;
+                }
+
+                let weak = Rc::downgrade(self);
+                let changed = move |monitor: &gdk::Monitor| {
+                    if let Some(display) = weak.upgrade() {
+                        display.changed(monitor);
+                    }
+                };
+                monitor.connect_geometry_notify(changed.clone());
+                monitor.connect_scale_factor_notify(changed.clone());
+                monitor.connect_refresh_rate_notify(changed);
+
+                let info = MonitorInfo::new(&monitor);
+                events.push(MonitorEvent::Added(info.clone()));
+                monitors.push((monitor, info));
+            }
+        }
+
+        for event in events {
+            self.notify(&event);
+        }
+    }
+
+    fn changed(&self, monitor: &gdk::Monitor) {
+        let info = MonitorInfo::new(monitor);
+        let changed = {
+            let mut monitors = self.monitors.borrow_mut();
+            match monitors.iter_mut().find(|(known, _)| known == monitor) {
+                Some((_, known_info)) if *known_info != info => {
+                    *known_info = info.clone();
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        if changed {
+            self.notify(&MonitorEvent::Changed(info));
+        }
+    }
+
+    fn notify(&self, event: &MonitorEvent) {
+        self.subscribers
+            .borrow_mut()
+            .retain(|subscriber| subscriber(event));
+    }
+}
+
+/// Returns the monitors of the default display.
+///
+/// Returns an empty list if GTK isn't initialized yet.
+#[must_use]
+pub fn monitors() -> Vec<MonitorInfo> {
+    let Some(display) = Display::get() else {
+        return Vec::new();
+    };
+    let monitors = display.monitors.borrow();
+    monitors.iter().map(|(_, info)| info.clone()).collect()
+}
+
+/// Send the connected monitors as [`MonitorEvent::Added`] and all following changes.
+///
+/// The subscription ends when the receiver of `sender` is dropped,
+/// for example when the component shuts down.
+/// Must be called from the main thread after GTK was initialized.
+pub fn subscribe<Msg, F>(sender: &Sender<Msg>, f: F)
+where
+    F: Fn(&MonitorEvent) -> Msg + 'static,
+    Msg: 'static,
+{
+    let Some(display) = Display::get() else {
+        tracing::error!("Can't watch monitors without a default display");
+        return;
+    };
+
+    for (_, info) in &*display.monitors.borrow() {
+        sender.emit(f(&MonitorEvent::Added(info.clone())));
+    }
+
+    let sender = sender.clone();
+    display
+        .subscribers
+        .borrow_mut()
+        .push(Box::new(move |event| sender.send(f(event)).is_ok()));
+}
+
+/// Send the scale factor of `widget` and all of its changes.
+///
+/// The scale factor changes when the window of the widget is moved
+/// to a monitor with a different scale, so images can be reloaded in
+/// the matching resolution.
+pub fn subscribe_scale_factor<Msg, F>(widget: &impl IsA<gtk::Widget>, sender: &Sender<Msg>, f: F)
+where
+    F: Fn(i32) -> Msg + 'static,
+    Msg: 'static,
+{
+    sender.emit(f(widget.scale_factor()));
+
+    let sender = sender.clone();
+    widget.connect_scale_factor_notify(move |widget| {
+        // Ignore send errors because the component might be shut down.
+        sender.send(f(widget.scale_factor())).ok();
+    });
+}
+
+/// Returns the connected monitor with the given connector name.
+#[must_use]
+pub fn find_monitor(connector: &str) -> Option<gdk::Monitor> {
+    let display = Display::get()?;
+    let monitors = display.monitors.borrow();
+    monitors
+        .iter()
+        .find(|(_, info)| info.connector.as_deref() == Some(connector))
+        .map(|(monitor, _)| monitor.clone())
+}
+
+/// Returns the monitor that shows most of `window`.
+///
+/// Returns [`None`] if the window isn't shown yet.
+#[must_use]
+pub fn monitor_of(window: &impl IsA<gtk::Window>) -> Option<gdk::Monitor> {
+    let surface = window.as_ref().surface()?;
+    surface.display().monitor_at_surface(&surface)
+}
+
+/// Show `window` in fullscreen mode on the monitor with the given connector name,
+/// for example for presentations or kiosks.
+///
+/// Returns `false` and doesn't change the window if the monitor isn't connected.
+pub fn fullscreen_on(window: &impl IsA<gtk::Window>, connector: &str) -> bool {
+    match find_monitor(connector) {
+        Some(monitor) => {
+            window.fullscreen_on_monitor(&monitor);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The monitor, size and state of a window.
+///
+/// The fields are public, so the placement can be stored in any format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowPlacement {
+    /// The connector name of the monitor that showed the window.
+    pub connector: Option<String>,
+    /// The width of the window when it's neither maximized nor fullscreen.
+    pub width: i32,
+    /// The height of the window when it's neither maximized nor fullscreen.
+    pub height: i32,
+    /// The window is maximized.
+    pub maximized: bool,
+    /// The window is in fullscreen mode.
+    pub fullscreen: bool,
+}
+
+impl WindowPlacement {
+    /// Read the placement of `window`.
+    #[must_use]
+    pub fn save(window: &impl IsA<gtk::Window>) -> Self {
+        let (width, height) = window.default_size();
+        Self {
+            connector: monitor_of(window)
+                .and_then(|monitor| monitor.connector())
+                .map(Into::into),
+            width,
+            height,
+            maximized: window.is_maximized(),
+            fullscreen: window.is_fullscreen(),
+        }
+    }
+
+    /// Apply the placement to `window`.
+    ///
+    /// A fullscreen window is restored on its monitor if it's still connected
+    /// and on the current monitor otherwise.
+    pub fn restore(&self, window: &impl IsA<gtk::Window>) {
+        if self.width > 0 && self.height > 0 {
+            window.set_default_size(self.width, self.height);
+        }
+
+        if self.maximized {
+            window.maximize();
+        }
+
+        if self.fullscreen {
+            let on_monitor = self
+                .connector
+                .as_deref()
+                .is_some_and(|connector| fullscreen_on(window, connector));
+            if !on_monitor {
+                window.fullscreen();
+            }
+        }
+    }
+}
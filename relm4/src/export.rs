@@ -0,0 +1,220 @@
+//! Export of widgets as PDF or SVG documents.
+//!
+//! The widgets are rendered with GSK into a cairo surface, so the documents
+//! look like the application, without reimplementing the drawing in cairo.
+//! Text and most other content stay vector graphics.
+//!
+//! ```no_run
+//! # use relm4::export::{render_pages_to_pdf, render_to_svg, PageLayout};
+//! # let content = gtk::Box::default();
+//! render_to_svg(&content, "view.svg").unwrap();
+//! let pages = render_pages_to_pdf(&content, "view.pdf", &PageLayout::A4).unwrap();
+//! ```
+//!
+//! Only widgets that are shown in a window can be rendered. For scrollable
+//! content, pass the child of the [`gtk::ScrolledWindow`] to render all of it.
+//! List views only create the rows that are visible, so they can't be exported completely.
+
+use std::fmt::Display;
+use std::path::Path;
+
+use gtk::prelude::{IsA, PaintableExt, SnapshotExt, WidgetExt};
+use gtk::{graphene, gsk};
+
+/// Error returned when a widget can't be exported.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExportError {
+    /// The widget has no size, because it isn't shown.
+    NotAllocated,
+    /// The widget didn't draw anything.
+    Empty,
+    /// Cairo couldn't create or write the document.
+    Cairo(cairo::Error),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAllocated => write!(f, "the widget isn't shown"),
+            Self::Empty => write!(f, "the widget didn't draw anything"),
+            Self::Cairo(error) => write!(f, "cairo error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cairo(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<cairo::Error> for ExportError {
+    fn from(error: cairo::Error) -> Self {
+        Self::Cairo(error)
+    }
+}
+
+/// The size of the pages of [`render_pages_to_pdf()`], in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageLayout {
+    /// The width of a page.
+    pub width: f64,
+    /// The height of a page.
+    pub height: f64,
+    /// The margin on each side of a page.
+    pub margin: f64,
+}
+
+impl PageLayout {
+    /// A4 paper with a margin of half an inch.
+    pub const A4: Self = Self {
+        width: 595.0,
+        height: 842.0,
+        margin: 36.0,
+    };
+
+    /// US letter paper with a margin of half an inch.
+    pub const LETTER: Self = Self {
+        width: 612.0,
+        height: 792.0,
+        margin: 36.0,
+    };
+
+    /// Splits content of the given size into pages.
+    ///
+    /// Content that is wider than a page is scaled down to fit.
+    /// Returns the scale and the vertical offset of each page in the coordinates of the content.
+    #[must_use]
+    pub fn paginate(&self, content_width: f64, content_height: f64) -> (f64, Vec<f64>) {
+        let printable_width = self.width - 2.0 * self.margin;
+        let printable_height = self.height - 2.0 * self.margin;
+        let scale = (printable_width / content_width).min(1.0);
+        let page_height = printable_height / scale;
+
+        let mut offsets = vec![0.0];
+        let mut offset = page_height;
+        while offset < content_height {
+            offsets.push(offset);
+            offset += page_height;
+        }
+        (scale, offsets)
+    }
+}
+
+impl Default for PageLayout {
+    fn default() -> Self {
+        Self::A4
+    }
+}
+
+/// Renders `widget` into a single page PDF document of the same size.
+pub fn render_to_pdf(
+    widget: &impl IsA<gtk::Widget>,
+    path: impl AsRef<Path>,
+) -> Result<(), ExportError> {
+    let (node, width, height) = render_node(widget.as_ref())?;
+    let surface = cairo::PdfSurface::new(width, height, path)?;
+    draw(&surface, &node)?;
+    finish(&surface)
+}
+
+/// Renders `widget` into an SVG document of the same size.
+pub fn render_to_svg(
+    widget: &impl IsA<gtk::Widget>,
+    path: impl AsRef<Path>,
+) -> Result<(), ExportError> {
+    let (node, width, height) = render_node(widget.as_ref())?;
+    let surface = cairo::SvgSurface::new(width, height, Some(path))?;
+    draw(&surface, &node)?;
+    finish(&surface)
+}
+
+/// Renders `widget` into a PDF document that is split into pages of the given layout.
+///
+/// Returns the number of pages.
+pub fn render_pages_to_pdf(
+    widget: &impl IsA<gtk::Widget>,
+    path: impl AsRef<Path>,
+    layout: &PageLayout,
+) -> Result<usize, ExportError> {
+    let (node, width, height) = render_node(widget.as_ref())?;
+    let (scale, offsets) = layout.paginate(width, height);
+    let surface = cairo::PdfSurface::new(layout.width, layout.height, path)?;
+    let cr = cairo::Context::new(&surface)?;
+
+    for offset in &offsets {
+        cr.save()?;
+        cr.translate(layout.margin, layout.margin);
+        cr.rectangle(
+            0.0,
+            0.0,
+            layout.width - 2.0 * layout.margin,
+            layout.height - 2.0 * layout.margin,
+        );
+        cr.clip();
+        cr.scale(scale, scale);
+        cr.translate(0.0, -offset);
+        node.draw(&cr);
+        cr.restore()?;
+        cr.show_page()?;
+    }
+
+    drop(cr);
+    finish(&surface)?;
+    Ok(offsets.len())
+}
+
+fn render_node(widget: &gtk::Widget) -> Result<(gsk::RenderNode, f64, f64), ExportError> {
+    let width = f64::from(widget.width());
+    let height = f64::from(widget.height());
+    if width <= 0.0 || height <= 0.0 {
+        return Err(ExportError::NotAllocated);
+    }
+
+    let paintable = gtk::WidgetPaintable::new(Some(widget));
+    let snapshot = gtk::Snapshot::new();
+    // Clip the content to the widget like a window does.
+    snapshot.push_clip(&graphene::Rect::new(0.0, 0.0, width as f32, height as f32));
+    paintable.snapshot(&snapshot, width, height);
+    snapshot.pop();
+    let node = snapshot.to_node().ok_or(ExportError::Empty)?;
+    Ok((node, width, height))
+}
+
+fn draw(surface: &cairo::Surface, node: &gsk::RenderNode) -> Result<(), ExportError> {
+    let cr = cairo::Context::new(surface)?;
+    node.draw(&cr);
+    cr.status()?;
+    Ok(())
+}
+
+fn finish(surface: &cairo::Surface) -> Result<(), ExportError> {
+    surface.finish();
+    surface.status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::PageLayout;
+
+    #[test]
+    fn paginate() {
+        let layout = PageLayout {
+            width: 120.0,
+            height: 220.0,
+            margin: 10.0,
+        };
+
+        assert_eq!(layout.paginate(50.0, 100.0), (1.0, vec![0.0]));
+        assert_eq!(
+            layout.paginate(100.0, 450.0),
+            (1.0, vec![0.0, 200.0, 400.0])
+        );
+        assert_eq!(layout.paginate(200.0, 800.0), (0.5, vec![0.0, 400.0]));
+    }
+}
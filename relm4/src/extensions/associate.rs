@@ -0,0 +1,119 @@
+use gtk::gio;
+use gtk::prelude::{EntryExt, IsA, RangeExt, ScaleButtonExt, TextViewExt, WidgetExt};
+
+/// Objects that aren't widgets, but belong to a widget.
+///
+/// Objects with the `#[object]` attribute in the `view!` macro are associated
+/// with their parent widget using this trait instead of being added as child,
+/// for example event controllers are added to the widget and
+/// adjustments are set as adjustment of the widget.
+pub trait RelmAssociateExt<W> {
+    /// Associate the object with `widget`.
+    fn associate(&self, widget: &W);
+}
+
+macro_rules! associate_controller_impl {
+    ($($type:ty),+) => {
+        $(
+            impl<W: IsA<gtk::Widget>> RelmAssociateExt<W> for $type {
+                fn associate(&self, widget: &W) {
+                    widget.add_controller(self.clone());
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! associate_impl {
+    ($object:ty, $method:ident, $($type:ty),+) => {
+        $(
+            impl RelmAssociateExt<$type> for $object {
+                fn associate(&self, widget: &$type) {
+                    widget.$method(self);
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! associate_option_impl {
+    ($object:ty, $method:ident, $($type:ty),+) => {
+        $(
+            impl RelmAssociateExt<$type> for $object {
+                fn associate(&self, widget: &$type) {
+                    widget.$method(Some(self));
+                }
+            }
+        )+
+    }
+}
+
+associate_controller_impl!(
+    gtk::DragSource,
+    gtk::DropControllerMotion,
+    gtk::DropTarget,
+    gtk::DropTargetAsync,
+    gtk::EventControllerFocus,
+    gtk::EventControllerKey,
+    gtk::EventControllerLegacy,
+    gtk::EventControllerMotion,
+    gtk::EventControllerScroll,
+    gtk::GestureClick,
+    gtk::GestureDrag,
+    gtk::GestureLongPress,
+    gtk::GesturePan,
+    gtk::GestureRotate,
+    gtk::GestureStylus,
+    gtk::GestureSwipe,
+    gtk::GestureZoom,
+    gtk::PadController,
+    gtk::ShortcutController
+);
+
+impl<W: IsA<gtk::Widget>> RelmAssociateExt<W> for gtk::SizeGroup {
+    fn associate(&self, widget: &W) {
+        self.add_widget(widget);
+    }
+}
+
+associate_impl!(
+    gtk::Adjustment,
+    set_adjustment,
+    gtk::Scale,
+    gtk::ScaleButton,
+    gtk::SpinButton
+);
+associate_option_impl!(gtk::Adjustment, set_adjustment, gtk::Scrollbar);
+
+associate_option_impl!(
+    gio::Menu,
+    set_menu_model,
+    gtk::MenuButton,
+    gtk::PopoverMenu,
+    gtk::PopoverMenuBar
+);
+
+associate_option_impl!(gtk::TextBuffer, set_buffer, gtk::TextView);
+associate_impl!(gtk::EntryBuffer, set_buffer, gtk::Entry, gtk::Text);
+
+associate_option_impl!(
+    gtk::SingleSelection,
+    set_model,
+    gtk::ListView,
+    gtk::GridView,
+    gtk::ColumnView
+);
+associate_option_impl!(
+    gtk::MultiSelection,
+    set_model,
+    gtk::ListView,
+    gtk::GridView,
+    gtk::ColumnView
+);
+associate_option_impl!(
+    gtk::NoSelection,
+    set_model,
+    gtk::ListView,
+    gtk::GridView,
+    gtk::ColumnView
+);
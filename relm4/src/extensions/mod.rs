@@ -1,3 +1,4 @@
+mod associate;
 mod container;
 mod iter_children;
 mod object_ext;
@@ -8,6 +9,7 @@ mod set_child;
 mod tests;
 mod widget_ext;
 
+pub use associate::RelmAssociateExt;
 pub use container::RelmContainerExt;
 pub use iter_children::RelmIterChildrenExt;
 pub use object_ext::RelmObjectExt;
@@ -7,15 +7,16 @@ use super::{AsyncFactoryComponent, AsyncFactoryHandle};
 
 use crate::channel::AsyncFactorySender;
 use crate::factory::{DataGuard, DynamicIndex, FactoryView};
-use crate::runtime_util::GuardedReceiver;
+use crate::runtime_util::{GuardedReceiver, InputReceiver};
 use crate::shutdown::ShutdownSender;
-use crate::{shutdown, Receiver, Sender};
+use crate::{shutdown, PriorityReceiver, Receiver, Sender};
 
 pub(super) struct AsyncFactoryBuilder<C: AsyncFactoryComponent> {
     init: C::Init,
     pub(super) root_widget: C::Root,
     pub(super) component_sender: AsyncFactorySender<C>,
     input_receiver: Receiver<C::Input>,
+    priority_receiver: PriorityReceiver<C::Input>,
     cmd_receiver: Receiver<C::CommandOutput>,
     shutdown_notifier: ShutdownSender,
 }
@@ -34,9 +35,17 @@ where
         // Notifies the component's child commands that it is now deceased.
         let (shutdown_notifier, shutdown_receiver) = shutdown::channel();
 
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
         // Encapsulates the senders used by component methods.
-        let component_sender =
-            AsyncFactorySender::new(input_sender, output_sender, cmd_sender, shutdown_receiver);
+        let component_sender = AsyncFactorySender::new(
+            input_sender,
+            priority_sender,
+            output_sender,
+            cmd_sender,
+            shutdown_receiver,
+        );
 
         let root_widget = C::init_root();
 
@@ -45,6 +54,7 @@ where
             root_widget,
             component_sender,
             input_receiver,
+            priority_receiver,
             cmd_receiver,
             shutdown_notifier,
         }
@@ -60,6 +70,7 @@ where
             root_widget,
             component_sender,
             input_receiver,
+            priority_receiver,
             cmd_receiver,
             shutdown_notifier,
             init,
@@ -83,6 +94,7 @@ where
                 root: root_widget.clone(),
                 returned_widget: returned_widget.clone(),
                 input_receiver,
+                priority_receiver,
                 cmd_receiver,
                 notifier_receiver,
             };
@@ -116,6 +128,7 @@ impl<C: AsyncFactoryComponent> std::fmt::Debug for AsyncFactoryBuilder<C> {
             .field("root_widget", &self.root_widget)
             .field("component_sender", &"<AsyncComponentSender<C>>")
             .field("input_receiver", &self.input_receiver)
+            .field("priority_receiver", &self.priority_receiver)
             .field("cmd_receiver", &self.cmd_receiver)
             .field("shutdown_notifier", &self.shutdown_notifier)
             .finish()
@@ -129,6 +142,7 @@ struct FutureData<C: AsyncFactoryComponent> {
     root: C::Root,
     returned_widget: <C::ParentWidget as FactoryView>::ReturnedWidget,
     input_receiver: Receiver<C::Input>,
+    priority_receiver: PriorityReceiver<C::Input>,
     cmd_receiver: Receiver<C::CommandOutput>,
     notifier_receiver: Receiver<()>,
 }
@@ -143,6 +157,7 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
             returned_widget,
             cmd_receiver,
             input_receiver,
+            priority_receiver,
             notifier_receiver,
         } = self;
 
@@ -163,7 +178,7 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
             |mut model, mut widgets| async move {
                 let mut notifier = GuardedReceiver::new(notifier_receiver);
                 let mut cmd = GuardedReceiver::new(cmd_receiver);
-                let mut input = GuardedReceiver::new(input_receiver);
+                let mut input = InputReceiver::new(input_receiver, priority_receiver);
                 loop {
                     futures::select!(
                         // Performs the model update, checking if the update requested a command.
@@ -564,6 +564,13 @@ where
     ///
     /// Also, only modified elements will be updated.
     fn render_changes(&mut self) {
+        #[cfg(feature = "metrics")]
+        crate::telemetry::factory_size(
+            std::any::type_name::<C>(),
+            self.rendered_state.len(),
+            self.model_state.len(),
+        );
+
         let mut first_position_change_idx = None;
 
         let components = &mut self.components;
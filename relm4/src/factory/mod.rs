@@ -22,7 +22,8 @@ pub use r#async::{
 pub use sync::{
     CloneableFactoryComponent, FactoryComponent, FactoryHashMap, FactoryHashMapBuilder,
     FactoryHashMapConnector, FactoryVecDeque, FactoryVecDequeBuilder, FactoryVecDequeConnector,
-    FactoryVecDequeGuard,
+    FactoryVecDequeGuard, RelmSignalListItemFactory, RelmSignalListItemFactoryBuilder,
+    RelmSignalListItemFactoryConnector,
 };
 
 pub use crate::channel::{AsyncFactorySender, FactorySender};
@@ -2,7 +2,9 @@ use super::{FactoryComponent, FactoryHandle};
 
 use crate::factory::{DataGuard, FactorySender, FactoryView};
 use crate::shutdown::ShutdownSender;
-use crate::{shutdown, GuardedReceiver, Receiver, Sender};
+use crate::{
+    mainloop, shutdown, GuardedReceiver, InputReceiver, PriorityReceiver, Receiver, Sender,
+};
 
 use std::any;
 
@@ -14,6 +16,7 @@ pub(super) struct FactoryBuilder<C: FactoryComponent> {
     pub(super) root_widget: C::Root,
     pub(super) component_sender: FactorySender<C>,
     pub(super) input_receiver: Receiver<C::Input>,
+    pub(super) priority_receiver: PriorityReceiver<C::Input>,
     pub(super) cmd_receiver: Receiver<C::CommandOutput>,
     pub(super) shutdown_notifier: ShutdownSender,
 }
@@ -29,9 +32,17 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
         // Notifies the component's child commands that it is now deceased.
         let (shutdown_notifier, shutdown_receiver) = shutdown::channel();
 
+        // Receives inputs that were sent with a high or low priority.
+        let (priority_sender, priority_receiver) = crate::priority_channel::<C::Input>();
+
         // Encapsulates the senders used by component methods.
-        let component_sender =
-            FactorySender::new(input_sender, output_sender, cmd_sender, shutdown_receiver);
+        let component_sender = FactorySender::new(
+            input_sender,
+            priority_sender,
+            output_sender,
+            cmd_sender,
+            shutdown_receiver,
+        );
 
         let data = Box::new(C::init_model(init, index, component_sender.clone()));
         let root_widget = data.init_root();
@@ -41,6 +52,7 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             root_widget,
             component_sender,
             input_receiver,
+            priority_receiver,
             cmd_receiver,
             shutdown_notifier,
         }
@@ -57,6 +69,7 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             root_widget,
             component_sender,
             input_receiver,
+            priority_receiver,
             cmd_receiver,
             shutdown_notifier,
         } = self;
@@ -86,7 +99,7 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
                 async move {
                     let mut notifier = GuardedReceiver::new(notifier_receiver);
                     let mut cmd = GuardedReceiver::new(cmd_receiver);
-                    let mut input = GuardedReceiver::new(input_receiver);
+                    let mut input = InputReceiver::new(input_receiver, priority_receiver);
                     loop {
                         futures::select!(
                             // Performs the model update, checking if the update requested a command.
@@ -99,8 +112,17 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
                                     id=model.id(),
                                 );
                                 let _enter = span.enter();
+                                crate::crash::record(any::type_name::<C>(), &message);
+                                crate::config::log_message(any::type_name::<C>(), &message);
 
-                                model.update_with_view(&mut widgets, message, component_sender.clone());
+                                mainloop::measure(
+                                    "update_with_view",
+                                    any::type_name::<C>(),
+                                    any::type_name::<C::Input>(),
+                                    || {
+                                        model.update_with_view(&mut widgets, message, component_sender.clone());
+                                    },
+                                );
                             }
 
                             // Handles responses from a command.
@@ -113,12 +135,26 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
                                 );
                                 let _enter = span.enter();
 
-                                model.update_cmd_with_view(&mut widgets, message, component_sender.clone());
+                                mainloop::measure(
+                                    "update_cmd_with_view",
+                                    any::type_name::<C>(),
+                                    any::type_name::<C::CommandOutput>(),
+                                    || {
+                                        model.update_cmd_with_view(&mut widgets, message, component_sender.clone());
+                                    },
+                                );
                             }
 
                             // Triggered when the model and view have been updated externally.
                             _ = notifier => {
-                                model.update_view(&mut widgets, component_sender.clone());
+                                mainloop::measure(
+                                    "update_view",
+                                    any::type_name::<C>(),
+                                    "()",
+                                    || {
+                                        model.update_view(&mut widgets, component_sender.clone());
+                                    },
+                                );
                             }
                         );
                     }
@@ -272,6 +272,9 @@ where
 
     /// Clears the map, removing all factory components.
     pub fn clear(&mut self) {
+        #[cfg(feature = "metrics")]
+        crate::telemetry::factory_size(std::any::type_name::<C>(), self.inner.len(), 0);
+
         for (_, handle) in self.inner.drain() {
             self.widget.factory_remove(&handle.returned_widget);
         }
@@ -341,6 +344,8 @@ where
         let component = builder.launch(&key, returned_widget);
 
         assert!(self.inner.insert(key, component).is_none());
+        #[cfg(feature = "metrics")]
+        crate::telemetry::factory_size(std::any::type_name::<C>(), 0, 1);
 
         existing
     }
@@ -349,6 +354,8 @@ where
     pub fn remove(&mut self, key: &K) -> Option<C> {
         if let Some(handle) = self.inner.remove(key) {
             self.widget.factory_remove(&handle.returned_widget);
+            #[cfg(feature = "metrics")]
+            crate::telemetry::factory_size(std::any::type_name::<C>(), 1, 0);
             Some(handle.data.into_inner())
         } else {
             None
@@ -0,0 +1,340 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use gtk::glib;
+use gtk::prelude::{Cast, ListItemExt};
+
+use crate::factory::{DynamicIndex, FactoryComponent, FactoryView};
+use crate::{Receiver, Sender};
+
+use super::{FactoryBuilder, FactoryHandle};
+
+/// Function that creates the init value of a component from an item of the list model.
+type InitFn<C> = dyn Fn(&glib::Object) -> <C as FactoryComponent>::Init;
+
+/// The state of a single [`gtk::ListItem`].
+struct ItemState<C: FactoryComponent> {
+    /// Updated whenever the position of the list item changes.
+    index: DynamicIndex,
+    /// The component of the bound item.
+    ///
+    /// It's kept alive after unbinding, so binding the same item again
+    /// (which happens often while scrolling) doesn't rebuild the component.
+    component: Option<(glib::Object, FactoryHandle<C>)>,
+}
+
+type ItemStates<C> = Rc<RefCell<HashMap<gtk::ListItem, ItemState<C>>>>;
+
+/// A [`gtk::SignalListItemFactory`] that uses a [`FactoryComponent`]
+/// as the item widget of [`gtk::ListView`], [`gtk::GridView`],
+/// [`gtk::ColumnView`] or any other widget that uses a [`gtk::ListItemFactory`].
+///
+/// In contrast to the typed views, this works with arbitrary list models.
+/// A component is launched once an item of the model is bound to a list item
+/// and is shut down once the list item is torn down or bound to another item.
+/// The [`DynamicIndex`] of each component tracks the position of its list item.
+///
+/// The component's [`FactoryComponent::ParentWidget`] must use [`gtk::Widget`]
+/// as children, like [`gtk::Box`], so existing factory components can be reused.
+/// The root widget of the component is also passed as returned widget.
+#[derive(Debug)]
+pub struct RelmSignalListItemFactory<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    factory: gtk::SignalListItemFactory,
+    items: ItemStates<C>,
+}
+
+impl<C> RelmSignalListItemFactory<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+    C::ParentWidget: FactoryView<Children = gtk::Widget, ReturnedWidget = gtk::Widget>,
+{
+    /// Create a builder for this factory.
+    #[must_use]
+    pub fn builder() -> RelmSignalListItemFactoryBuilder<C> {
+        RelmSignalListItemFactoryBuilder::new()
+    }
+
+    fn new(init: Box<InitFn<C>>, output_sender: Sender<C::Output>) -> Self {
+        let factory = gtk::SignalListItemFactory::new();
+        let items: ItemStates<C> = Rc::default();
+        let init: Rc<InitFn<C>> = init.into();
+
+        {
+            let items = items.clone();
+            factory.connect_setup(move |_, list_item| {
+                let list_item = list_item.downcast_ref::<gtk::ListItem>().unwrap();
+                let index = DynamicIndex::new(list_item.position() as usize);
+
+                let position_index = index.clone();
+                list_item.connect_position_notify(move |list_item| {
+                    position_index.set_value(list_item.position() as usize);
+                });
+
+                items.borrow_mut().insert(
+                    list_item.clone(),
+                    ItemState {
+                        index,
+                        component: None,
+                    },
+                );
+            });
+        }
+
+        {
+            let items = items.clone();
+            factory.connect_bind(move |_, list_item| {
+                let list_item = list_item.downcast_ref::<gtk::ListItem>().unwrap();
+                let Some(item) = list_item.item() else {
+                    return;
+                };
+
+                // Take the component out, so components are shut down
+                // and launched without holding the borrow.
+                let (index, previous) = {
+                    let mut items = items.borrow_mut();
+                    let Some(state) = items.get_mut(list_item) else {
+                        return;
+                    };
+                    state.index.set_value(list_item.position() as usize);
+                    (state.index.clone(), state.component.take())
+                };
+
+                let component = match previous {
+                    // Recycle the component if the same item is bound again.
+                    Some((bound_item, handle)) if bound_item == item => {
+                        list_item.set_child(Some(&handle.returned_widget));
+                        (bound_item, handle)
+                    }
+                    previous => {
+                        // Shut down the component of the previous item before
+                        // launching the new one.
+                        drop(previous);
+
+                        let builder =
+                            FactoryBuilder::new(&index, init(&item), output_sender.clone());
+                        let widget = AsRef::<gtk::Widget>::as_ref(&builder.root_widget).clone();
+                        list_item.set_child(Some(&widget));
+                        let handle = builder.launch(&index, widget);
+                        (item, handle)
+                    }
+                };
+
+                let mut component = Some(component);
+                if let Some(state) = items.borrow_mut().get_mut(list_item) {
+                    state.component = component.take();
+                }
+                // The list item might have been torn down by the component meanwhile.
+                drop(component);
+            });
+        }
+
+        {
+            let items = items.clone();
+            factory.connect_unbind(move |_, list_item| {
+                let list_item = list_item.downcast_ref::<gtk::ListItem>().unwrap();
+                // The component stays alive, so it can be recycled
+                // if the same item is bound again.
+                if items.borrow().contains_key(list_item) {
+                    list_item.set_child(None::<&gtk::Widget>);
+                }
+            });
+        }
+
+        {
+            let items = items.clone();
+            factory.connect_teardown(move |_, list_item| {
+                let list_item = list_item.downcast_ref::<gtk::ListItem>().unwrap();
+                // Remove the state first, so the component is
+                // shut down without holding the borrow.
+                let state = items.borrow_mut().remove(list_item);
+                list_item.set_child(None::<&gtk::Widget>);
+                drop(state);
+            });
+        }
+
+        Self { factory, items }
+    }
+
+    /// Returns the underlying [`gtk::SignalListItemFactory`].
+    #[must_use]
+    pub fn factory(&self) -> &gtk::SignalListItemFactory {
+        &self.factory
+    }
+
+    /// Returns the number of components that are currently alive.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items
+            .borrow()
+            .values()
+            .filter(|state| state.component.is_some())
+            .count()
+    }
+
+    /// Returns [`true`] if no components are alive.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Send a message to the component of the given item of the list model.
+    ///
+    /// Returns [`false`] if no component is alive for the item.
+    pub fn send(&self, item: &impl AsRef<glib::Object>, msg: C::Input) -> bool {
+        let item = item.as_ref();
+        self.items
+            .borrow()
+            .values()
+            .filter_map(|state| state.component.as_ref())
+            .find(|(bound_item, _)| bound_item == item)
+            .is_some_and(|(_, handle)| handle.input.send(msg).is_ok())
+    }
+
+    /// Send a message to all components that are currently alive.
+    pub fn broadcast(&self, msg: C::Input)
+    where
+        C::Input: Clone,
+    {
+        for (_, handle) in self
+            .items
+            .borrow()
+            .values()
+            .filter_map(|state| state.component.as_ref())
+        {
+            handle.input.send(msg.clone()).ok();
+        }
+    }
+}
+
+impl<C> AsRef<gtk::ListItemFactory> for RelmSignalListItemFactory<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    fn as_ref(&self) -> &gtk::ListItemFactory {
+        self.factory.upcast_ref()
+    }
+}
+
+impl<C> std::fmt::Debug for ItemState<C>
+where
+    C: FactoryComponent,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemState")
+            .field("index", &self.index)
+            .field("component", &self.component)
+            .finish()
+    }
+}
+
+/// A builder-pattern struct for building a [`RelmSignalListItemFactory`].
+#[derive(Debug)]
+pub struct RelmSignalListItemFactoryBuilder<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    _component: PhantomData<C>,
+}
+
+impl<C> Default for RelmSignalListItemFactoryBuilder<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> RelmSignalListItemFactoryBuilder<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    /// Create a builder for this factory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _component: PhantomData,
+        }
+    }
+
+    /// Launch the factory.
+    ///
+    /// The `init` function creates the init value of
+    /// a component from an item of the list model.
+    pub fn launch<F>(self, init: F) -> RelmSignalListItemFactoryConnector<C>
+    where
+        F: Fn(&glib::Object) -> C::Init + 'static,
+    {
+        let (output_sender, output_receiver) = crate::channel();
+
+        RelmSignalListItemFactoryConnector {
+            init: Box::new(init),
+            output_sender,
+            output_receiver,
+        }
+    }
+}
+
+/// Second stage of the builder-pattern for building a [`RelmSignalListItemFactory`].
+pub struct RelmSignalListItemFactoryConnector<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    init: Box<InitFn<C>>,
+    output_sender: Sender<C::Output>,
+    output_receiver: Receiver<C::Output>,
+}
+
+impl<C> std::fmt::Debug for RelmSignalListItemFactoryConnector<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelmSignalListItemFactoryConnector")
+            .field("output_sender", &self.output_sender)
+            .field("output_receiver", &self.output_receiver)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> RelmSignalListItemFactoryConnector<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+    C::ParentWidget: FactoryView<Children = gtk::Widget, ReturnedWidget = gtk::Widget>,
+{
+    /// Forwards output events from the components to the designated sender.
+    pub fn forward<F, Msg>(self, sender_: &Sender<Msg>, f: F) -> RelmSignalListItemFactory<C>
+    where
+        F: Fn(C::Output) -> Msg + Send + 'static,
+        C::Output: Send,
+        Msg: Send + 'static,
+    {
+        let Self {
+            init,
+            output_sender,
+            output_receiver,
+        } = self;
+
+        let sender_clone = sender_.clone();
+        crate::spawn(async move {
+            while let Some(msg) = output_receiver.recv().await {
+                if sender_clone.send(f(msg)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        RelmSignalListItemFactory::new(init, output_sender)
+    }
+
+    /// Ignore output events from the components and take the factory.
+    #[must_use]
+    pub fn detach(self) -> RelmSignalListItemFactory<C> {
+        RelmSignalListItemFactory::new(self.init, self.output_sender)
+    }
+}
@@ -2,6 +2,7 @@ mod builder;
 mod collections;
 mod component_storage;
 mod handle;
+mod list_item_factory;
 mod traits;
 
 use builder::FactoryBuilder;
@@ -11,4 +12,7 @@ pub use collections::{
     FactoryHashMap, FactoryHashMapBuilder, FactoryHashMapConnector, FactoryVecDeque,
     FactoryVecDequeBuilder, FactoryVecDequeConnector, FactoryVecDequeGuard,
 };
+pub use list_item_factory::{
+    RelmSignalListItemFactory, RelmSignalListItemFactoryBuilder, RelmSignalListItemFactoryConnector,
+};
 pub use traits::{CloneableFactoryComponent, FactoryComponent};
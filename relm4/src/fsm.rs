@@ -0,0 +1,79 @@
+//! Typed state machines for component models.
+//!
+//! Instead of tracking the state of a component with several boolean
+//! flags, the state is stored as an enum that implements [`StateMachine`].
+//! Usually, the trait is implemented with [`#[derive(StateMachine)]`](crate::StateMachine)
+//! which also generates a method for each transition.
+//!
+//! ```
+//! use relm4::fsm::StateMachine;
+//!
+//! #[derive(Debug, PartialEq, relm4::StateMachine)]
+//! enum Connection {
+//!     #[transitions(Connecting)]
+//!     Disconnected,
+//!     #[transitions(Connected, Disconnected)]
+//!     Connecting { attempt: u32 },
+//!     #[transitions(Disconnected)]
+//!     Connected(String),
+//! }
+//!
+//! let mut state = Connection::Disconnected;
+//! state.to_connecting(1).unwrap();
+//! state.to_connected("relm4.org".into()).unwrap();
+//!
+//! let error = state.to_connecting(2).unwrap_err();
+//! assert_eq!(error.to_string(), "invalid transition from `Connected` to `Connecting`");
+//! assert!(state.is_connected());
+//! ```
+//!
+//! Because the state is a regular enum, views can match
+//! on it exhaustively, for example in `#[watch]` properties.
+
+use std::fmt;
+
+/// A state machine with a fixed set of allowed transitions.
+pub trait StateMachine: Sized {
+    /// The name of the current state.
+    fn state_name(&self) -> &'static str;
+
+    /// Returns [`true`] if a transition from the current state to `next` is allowed.
+    fn can_transition_to(&self, next: &Self) -> bool;
+
+    /// Change the state to `next` if the transition is allowed.
+    ///
+    /// Otherwise, the state stays the same and an error is returned,
+    /// which can be sent as output of a component for example.
+    fn transition(&mut self, next: Self) -> Result<(), InvalidTransition> {
+        if self.can_transition_to(&next) {
+            *self = next;
+            Ok(())
+        } else {
+            Err(InvalidTransition {
+                from: self.state_name(),
+                to: next.state_name(),
+            })
+        }
+    }
+}
+
+/// Error returned when a transition isn't allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    /// The state the transition started from.
+    pub from: &'static str,
+    /// The rejected target state.
+    pub to: &'static str,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid transition from `{}` to `{}`",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
@@ -0,0 +1,238 @@
+//! Game controller input for kiosks, media centers and emulator frontends.
+//!
+//! [`Gamepad::start()`] reads all connected controllers with [gilrs](https://docs.rs/gilrs)
+//! on a background thread of the runtime and forwards a [`GamepadEvent`] to a component
+//! for each button press and release and when controllers are connected or disconnected.
+//! Axis movements are throttled, so sticks don't flood the component with inputs:
+//!
+//! ```no_run
+//! use relm4::gamepad::{Gamepad, GamepadEvent, GamepadSettings};
+//!
+//! #[derive(Debug)]
+//! enum Msg {
+//!     Gamepad(GamepadEvent),
+//! }
+//!
+//! # fn init(sender: relm4::Sender<Msg>) {
+//! // Store the service in the model, it stops when it's dropped.
+//! let gamepad = Gamepad::start(GamepadSettings::default(), &sender, Msg::Gamepad);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use gilrs::{EventType, Gilrs};
+
+use crate::Sender;
+
+/// Re-export of gilrs
+pub use gilrs;
+pub use gilrs::{Axis, Button, GamepadId};
+
+/// How long the thread waits for events before it checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A connected controller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadInfo {
+    /// The id of the controller while it's connected.
+    pub id: GamepadId,
+    /// The name of the controller, for example "Xbox Controller".
+    pub name: String,
+    /// The UUID of the controller model, which is stable across connections.
+    pub uuid: [u8; 16],
+}
+
+impl GamepadInfo {
+    fn new(gamepad: &gilrs::Gamepad<'_>) -> Self {
+        Self {
+            id: gamepad.id(),
+            name: gamepad.name().to_owned(),
+            uuid: gamepad.uuid(),
+        }
+    }
+}
+
+/// Input of a controller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    /// A controller was connected.
+    Connected(GamepadInfo),
+    /// A controller was disconnected.
+    Disconnected(GamepadId),
+    /// A button was pressed.
+    ButtonPressed(GamepadId, Button),
+    /// A button was released.
+    ButtonReleased(GamepadId, Button),
+    /// An axis moved to a value between -1.0 and 1.0.
+    AxisChanged(GamepadId, Axis, f32),
+}
+
+/// The configuration of a [`Gamepad`].
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to
+/// configure yourself. It's configured as such:
+///
+/// + Axis changes are sent at most every 50 milliseconds
+/// + Changes smaller than 0.01 are ignored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GamepadSettings {
+    /// The minimal time between two [`GamepadEvent::AxisChanged`] of the same axis.
+    pub axis_interval: Duration,
+    /// The minimal change of an axis that is sent.
+    pub axis_threshold: f32,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            axis_interval: Duration::from_millis(50),
+            axis_threshold: 0.01,
+        }
+    }
+}
+
+/// Reads the connected controllers in the background.
+///
+/// The service stops when this is dropped.
+#[derive(Debug)]
+#[must_use = "the service stops when this is dropped"]
+pub struct Gamepad {
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for Gamepad {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Gamepad {
+    /// Send the connected controllers as [`GamepadEvent::Connected`]
+    /// and all following [`GamepadEvent`]s to `sender`.
+    ///
+    /// The service also stops when the receiver of `sender` is dropped,
+    /// for example when the component shuts down.
+    pub fn start<Msg, F>(settings: GamepadSettings, sender: &Sender<Msg>, f: F) -> Self
+    where
+        F: Fn(GamepadEvent) -> Msg + Send + 'static,
+        Msg: Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let sender = sender.clone();
+        let thread_stop = stop.clone();
+        // Gilrs isn't `Send` on all platforms, so it's created on the thread that reads it.
+        crate::spawn_blocking(move || {
+            let gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(gilrs::Error::NotImplemented(_)) => {
+                    tracing::warn!("Game controllers aren't supported on this platform");
+                    return;
+                }
+                Err(error) => {
+                    tracing::error!("Can't read game controllers: {error}");
+                    return;
+                }
+            };
+            Reader::new(gilrs, settings).run(&thread_stop, |event| sender.send(f(event)).is_ok());
+        });
+
+        Self { stop }
+    }
+}
+
+/// The state of the background thread.
+struct Reader {
+    gilrs: Gilrs,
+    settings: GamepadSettings,
+    /// The last sent value and the time it was sent of each axis.
+    sent_axes: HashMap<(GamepadId, Axis), (f32, Instant)>,
+    /// Axis values that weren't sent yet because of the throttling.
+    pending_axes: HashMap<(GamepadId, Axis), f32>,
+}
+
+impl Reader {
+    fn new(gilrs: Gilrs, settings: GamepadSettings) -> Self {
+        Self {
+            gilrs,
+            settings,
+            sent_axes: HashMap::new(),
+            pending_axes: HashMap::new(),
+        }
+    }
+
+    /// Read events until `stop` is set or `send` returns `false`.
+    fn run(mut self, stop: &AtomicBool, send: impl Fn(GamepadEvent) -> bool) {
+        for (_, gamepad) in self.gilrs.gamepads() {
+            if !send(GamepadEvent::Connected(GamepadInfo::new(&gamepad))) {
+                return;
+            }
+        }
+
+        let timeout = POLL_INTERVAL.min(self.settings.axis_interval);
+        while !stop.load(Ordering::Relaxed) {
+            let event = self.gilrs.next_event_blocking(Some(timeout));
+            let mut events = event
+                .and_then(|event| self.translate(event.id, event.event))
+                .into_iter()
+                .collect::<Vec<_>>();
+            events.extend(self.flush_axes());
+
+            for event in events {
+                if !send(event) {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn translate(&mut self, id: GamepadId, event: EventType) -> Option<GamepadEvent> {
+        match event {
+            EventType::Connected => Some(GamepadEvent::Connected(GamepadInfo::new(
+                &self.gilrs.gamepad(id),
+            ))),
+            EventType::Disconnected => {
+                self.sent_axes.retain(|(axis_id, _), _| *axis_id != id);
+                self.pending_axes.retain(|(axis_id, _), _| *axis_id != id);
+                Some(GamepadEvent::Disconnected(id))
+            }
+            EventType::ButtonPressed(button, _) => Some(GamepadEvent::ButtonPressed(id, button)),
+            EventType::ButtonReleased(button, _) => Some(GamepadEvent::ButtonReleased(id, button)),
+            EventType::AxisChanged(axis, value, _) => {
+                self.pending_axes.insert((id, axis), value);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the pending axis changes whose interval has passed.
+    fn flush_axes(&mut self) -> Vec<GamepadEvent> {
+        let now = Instant::now();
+        let settings = self.settings;
+        let sent_axes = &mut self.sent_axes;
+        let mut events = Vec::new();
+
+        self.pending_axes.retain(|&(id, axis), &mut value| {
+            match sent_axes.get(&(id, axis)) {
+                Some((_, sent)) if now.duration_since(*sent) < settings.axis_interval => {
+                    return true;
+                }
+                // Always send the rest position, so the axis doesn't get stuck.
+                Some((sent_value, _))
+                    if (value - sent_value).abs() < settings.axis_threshold && value != 0.0 => {}
+                _ => {
+                    sent_axes.insert((id, axis), (value, now));
+                    events.push(GamepadEvent::AxisChanged(id, axis, value));
+                }
+            }
+            false
+        });
+
+        events
+    }
+}
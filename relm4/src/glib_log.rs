@@ -0,0 +1,84 @@
+//! Forwarding of GLib log messages to [`tracing`].
+//!
+//! GTK and other libraries based on GLib print warnings and critical messages
+//! directly to the console. [`GlibLogBridge`] turns them into [`tracing`] events
+//! instead, so they end up in the same place as the logs of the application.
+//!
+//! Each event has the fields `domain`, like `Gtk`, and `component`, which is the
+//! type name of the component whose update was running when the message was logged.
+//! This makes it much easier to find the code that caused a `Gtk-CRITICAL`.
+//!
+//! ```
+//! relm4::glib_log::GlibLogBridge::new().install();
+//! ```
+
+use gtk::glib::{LogField, LogLevel, LogWriterOutput};
+
+/// The target of all [`tracing`] events created from GLib log messages.
+pub const TARGET: &str = "glib";
+
+/// Forwards GLib log messages to [`tracing`], see the [module documentation](self).
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct GlibLogBridge {
+    keep_default_output: bool,
+}
+
+impl Default for GlibLogBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlibLogBridge {
+    /// Creates a bridge that replaces the default output of GLib.
+    pub fn new() -> Self {
+        Self {
+            keep_default_output: false,
+        }
+    }
+
+    /// If `true`, GLib still prints the messages to the console,
+    /// in addition to the [`tracing`] events.
+    pub fn keep_default_output(mut self, keep: bool) -> Self {
+        self.keep_default_output = keep;
+        self
+    }
+
+    /// Starts forwarding log messages.
+    ///
+    /// This installs the log writer of Relm4 with [`log_writer::install()`](crate::log_writer::install),
+    /// which can only be done once per process and prevents other code
+    /// from setting its own log writer. See its documentation for details.
+    ///
+    /// Installing the bridge multiple times forwards each message multiple times.
+    pub fn install(self) {
+        let keep_default_output = self.keep_default_output;
+        crate::log_writer::add_hook(move |level, fields| {
+            forward(level, fields);
+            if keep_default_output {
+                LogWriterOutput::Unhandled
+            } else {
+                LogWriterOutput::Handled
+            }
+        });
+        crate::log_writer::install();
+    }
+}
+
+fn forward(level: LogLevel, fields: &[LogField<'_>]) {
+    let message = crate::log_writer::field(fields, "MESSAGE").unwrap_or_default();
+    let domain = crate::log_writer::field(fields, "GLIB_DOMAIN").unwrap_or_default();
+    let component = crate::mainloop::running_updates().last().copied();
+
+    match level {
+        LogLevel::Error | LogLevel::Critical => {
+            tracing::error!(target: TARGET, domain, component, "{message}");
+        }
+        LogLevel::Warning => tracing::warn!(target: TARGET, domain, component, "{message}"),
+        LogLevel::Message | LogLevel::Info => {
+            tracing::info!(target: TARGET, domain, component, "{message}");
+        }
+        LogLevel::Debug => tracing::debug!(target: TARGET, domain, component, "{message}"),
+    }
+}
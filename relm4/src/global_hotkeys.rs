@@ -0,0 +1,247 @@
+//! System-wide keyboard shortcuts that work while the application isn't focused.
+//!
+//! The shortcuts are registered with the
+//! [GlobalShortcuts portal](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.GlobalShortcuts.html),
+//! which asks the user to confirm or change them and works on Wayland and in sandboxes.
+//! [`register()`] forwards a [`HotkeyEvent`] to a component whenever a shortcut is pressed
+//! or released as long as the returned [`GlobalHotkeys`] are alive:
+//!
+//! ```no_run
+//! use relm4::global_hotkeys::{self, Hotkey, HotkeyEvent};
+//!
+//! #[derive(Debug)]
+//! enum Msg {
+//!     Hotkey(HotkeyEvent),
+//! }
+//!
+//! # async fn init(sender: relm4::Sender<Msg>) {
+//! let hotkeys = [Hotkey::new("record", "Start or stop recording").trigger("CTRL+ALT+R")];
+//! match global_hotkeys::register(&hotkeys, &sender, Msg::Hotkey).await {
+//!     // Store the hotkeys in the model.
+//!     Ok(_hotkeys) => (),
+//!     Err(error) => tracing::warn!("No global hotkeys: {error}"),
+//! }
+//! # }
+//! ```
+//!
+//! Desktops without the portal, including plain X11 sessions,
+//! report [`HotkeyError::Unsupported`], so applications should offer
+//! other ways to trigger the actions.
+
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+use gtk::gio;
+use gtk::glib::prelude::ToVariant;
+use gtk::glib::{self, Variant, VariantDict, VariantTy};
+
+use crate::portal::{self, Response, PORTAL_NAME, PORTAL_PATH};
+use crate::Sender;
+
+const SHORTCUTS_INTERFACE: &str = "org.freedesktop.portal.GlobalShortcuts";
+
+/// A shortcut to register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    id: String,
+    description: String,
+    trigger: Option<String>,
+}
+
+impl Hotkey {
+    /// Create a shortcut with an id that identifies it in [`HotkeyEvent`]s
+    /// and a description for the user.
+    #[must_use]
+    pub fn new(id: &str, description: &str) -> Self {
+        Self {
+            id: id.to_owned(),
+            description: description.to_owned(),
+            trigger: None,
+        }
+    }
+
+    /// Suggest keys for the shortcut, for example "CTRL+ALT+R".
+    ///
+    /// The desktop may assign other keys, for example if they are already used.
+    #[must_use]
+    pub fn trigger(mut self, trigger: &str) -> Self {
+        self.trigger = Some(trigger.to_owned());
+        self
+    }
+
+    fn portal_variant(&self) -> Variant {
+        let options = VariantDict::new(None);
+        options.insert_value("description", &self.description.to_variant());
+        if let Some(trigger) = &self.trigger {
+            options.insert_value("preferred_trigger", &trigger.to_variant());
+        }
+        Variant::tuple_from_iter([self.id.to_variant(), options.end()])
+    }
+}
+
+/// A shortcut was used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyEvent {
+    /// The shortcut with this id was pressed.
+    Activated(String),
+    /// The shortcut with this id was released.
+    Deactivated(String),
+}
+
+/// An error that occurred while registering shortcuts.
+#[derive(Debug)]
+pub enum HotkeyError {
+    /// The desktop doesn't provide the GlobalShortcuts portal.
+    Unsupported,
+    /// The user declined the shortcuts.
+    Cancelled,
+    /// The portal failed.
+    Portal(glib::Error),
+}
+
+impl fmt::Display for HotkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => f.write_str("global shortcuts are not supported"),
+            Self::Cancelled => f.write_str("the shortcuts were declined"),
+            Self::Portal(error) => write!(f, "the portal failed: {error}"),
+        }
+    }
+}
+
+impl Error for HotkeyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Portal(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<glib::Error> for HotkeyError {
+    fn from(error: glib::Error) -> Self {
+        if portal::is_unsupported(&error) {
+            Self::Unsupported
+        } else {
+            Self::Portal(error)
+        }
+    }
+}
+
+/// Registered shortcuts, which are removed when this is dropped.
+#[must_use = "the shortcuts are removed when this is dropped"]
+pub struct GlobalHotkeys {
+    connection: gio::DBusConnection,
+    session: String,
+    subscriptions: Vec<gio::SignalSubscriptionId>,
+}
+
+impl fmt::Debug for GlobalHotkeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalHotkeys")
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for GlobalHotkeys {
+    fn drop(&mut self) {
+        for subscription in self.subscriptions.drain(..) {
+            self.connection.signal_unsubscribe(subscription);
+        }
+        portal::close_session(&self.connection, &self.session);
+    }
+}
+
+/// Register `hotkeys` and send their [`HotkeyEvent`]s to `sender`.
+///
+/// The desktop may ask the user to confirm the shortcuts first.
+/// Must be called from the main thread.
+pub async fn register<Msg, F>(
+    hotkeys: &[Hotkey],
+    sender: &Sender<Msg>,
+    f: F,
+) -> Result<GlobalHotkeys, HotkeyError>
+where
+    F: Fn(HotkeyEvent) -> Msg + 'static,
+    Msg: 'static,
+{
+    let connection = gio::bus_get_future(gio::BusType::Session).await?;
+
+    let results = request(&connection, "CreateSession", |token| {
+        let options = portal::request_options(token);
+        options.insert_value("session_handle_token", &token.to_variant());
+        vec![options.end()]
+    })
+    .await?;
+    let session = portal::session_handle(&results).ok_or(HotkeyError::Unsupported)?;
+
+    let mut hotkeys_guard = GlobalHotkeys {
+        connection: connection.clone(),
+        session: session.clone(),
+        subscriptions: Vec::new(),
+    };
+
+    let f = Rc::new(f);
+    for (member, activated) in [("Activated", true), ("Deactivated", false)] {
+        let session = session.clone();
+        let sender = sender.clone();
+        let f = f.clone();
+        let subscription = connection.signal_subscribe(
+            Some(PORTAL_NAME),
+            Some(SHORTCUTS_INTERFACE),
+            Some(member),
+            Some(PORTAL_PATH),
+            Some(&hotkeys_guard.session),
+            gio::DBusSignalFlags::NONE,
+            move |_, _, _, _, _, parameters| {
+                if parameters.child_value(0).str() != Some(session.as_str()) {
+                    return;
+                }
+                let Some(id) = parameters.child_value(1).str().map(str::to_owned) else {
+                    return;
+                };
+                let event = if activated {
+                    HotkeyEvent::Activated(id)
+                } else {
+                    HotkeyEvent::Deactivated(id)
+                };
+                // Ignore send errors because the component might be shut down.
+                sender.send(f(event)).ok();
+            },
+        );
+        hotkeys_guard.subscriptions.push(subscription);
+    }
+
+    let shortcuts = Variant::array_from_iter_with_type(
+        VariantTy::new("(sa{sv})").unwrap(),
+        hotkeys.iter().map(Hotkey::portal_variant),
+    );
+    let session_path = glib::variant::ObjectPath::try_from(session.as_str())
+        .map_err(|_| HotkeyError::Unsupported)?;
+    request(&connection, "BindShortcuts", |token| {
+        vec![
+            session_path.to_variant(),
+            shortcuts,
+            "".to_variant(),
+            portal::request_options(token).end(),
+        ]
+    })
+    .await?;
+
+    Ok(hotkeys_guard)
+}
+
+/// Call a method of the GlobalShortcuts portal and wait for its results.
+async fn request(
+    connection: &gio::DBusConnection,
+    method: &str,
+    parameters: impl FnOnce(&str) -> Vec<Variant>,
+) -> Result<VariantDict, HotkeyError> {
+    match portal::request(connection, SHORTCUTS_INTERFACE, method, parameters).await? {
+        Response::Success(results) => Ok(results),
+        Response::Cancelled => Err(HotkeyError::Cancelled),
+        Response::Failed => Err(HotkeyError::Unsupported),
+    }
+}
@@ -0,0 +1,247 @@
+//! Wayland layer surfaces for panels, notifications and launchers.
+//!
+//! A [`LayerShellConfig`] turns a window into a layer surface with
+//! [gtk4-layer-shell](https://github.com/wmww/gtk4-layer-shell), so it's placed
+//! by the compositor at an edge of the screen instead of being a normal window.
+//! [`RelmApp::run_layer_shell()`](crate::RelmApp::run_layer_shell) applies the
+//! configuration to the root window of the main component.
+//!
+//! ```no_run
+//! use relm4::layer_shell::{Anchors, ExclusiveZone, Layer, LayerShellConfig};
+//!
+//! // A panel at the top of the screen.
+//! let config = LayerShellConfig::new("panel")
+//!     .layer(Layer::Top)
+//!     .anchors(Anchors::TOP)
+//!     .exclusive_zone(ExclusiveZone::Auto);
+//! ```
+//!
+//! Layer surfaces are only supported by some Wayland compositors.
+//! Elsewhere, the window is shown as a normal window.
+
+use gtk::prelude::IsA;
+use gtk4_layer_shell::{Edge, LayerShell};
+
+pub use gtk4_layer_shell::{KeyboardMode, Layer};
+
+/// The edges of the screen a layer surface is attached to.
+///
+/// A surface anchored to two opposite edges is stretched between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Anchors {
+    /// Attach to the top edge.
+    pub top: bool,
+    /// Attach to the bottom edge.
+    pub bottom: bool,
+    /// Attach to the left edge.
+    pub left: bool,
+    /// Attach to the right edge.
+    pub right: bool,
+}
+
+impl Anchors {
+    /// Centered, not attached to any edge.
+    pub const NONE: Self = Self::new(false, false, false, false);
+    /// Attached to all edges, covering the whole screen.
+    pub const ALL: Self = Self::new(true, true, true, true);
+    /// A horizontal bar at the top.
+    pub const TOP: Self = Self::new(true, false, true, true);
+    /// A horizontal bar at the bottom.
+    pub const BOTTOM: Self = Self::new(false, true, true, true);
+    /// A vertical bar at the left.
+    pub const LEFT: Self = Self::new(true, true, true, false);
+    /// A vertical bar at the right.
+    pub const RIGHT: Self = Self::new(true, true, false, true);
+
+    /// Create anchors from the edges.
+    #[must_use]
+    pub const fn new(top: bool, bottom: bool, left: bool, right: bool) -> Self {
+        Self {
+            top,
+            bottom,
+            left,
+            right,
+        }
+    }
+}
+
+/// The distance of a layer surface to the edges it's anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Margins {
+    /// The margin to the top edge.
+    pub top: i32,
+    /// The margin to the bottom edge.
+    pub bottom: i32,
+    /// The margin to the left edge.
+    pub left: i32,
+    /// The margin to the right edge.
+    pub right: i32,
+}
+
+impl Margins {
+    /// The same margin for all edges.
+    #[must_use]
+    pub const fn all(margin: i32) -> Self {
+        Self {
+            top: margin,
+            bottom: margin,
+            left: margin,
+            right: margin,
+        }
+    }
+}
+
+/// The area that a layer surface reserves, so other windows don't overlap it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExclusiveZone {
+    /// Don't reserve space, but move away from the exclusive zones of other surfaces.
+    #[default]
+    None,
+    /// Reserve the size of the surface, which is what panels usually want.
+    Auto,
+    /// Reserve a fixed number of pixels at the anchored edge.
+    Fixed(i32),
+    /// Ignore the exclusive zones of other surfaces, for example for overlays.
+    Ignore,
+}
+
+/// The configuration of a layer surface.
+///
+/// The configuration object provides a [`Default`] implementation for any fields you don't want to
+/// configure yourself. It's configured as such:
+///
+/// + No namespace
+/// + [`Layer::Top`]
+/// + [`Anchors::NONE`] and no margins
+/// + [`ExclusiveZone::None`]
+/// + [`KeyboardMode::None`], so the surface doesn't receive keyboard input
+/// + Shown on the monitor chosen by the compositor
+#[derive(Debug, Clone)]
+pub struct LayerShellConfig {
+    /// The namespace that compositors use to identify the surface, for example "panel".
+    pub namespace: Option<String>,
+    /// The layer of the surface.
+    pub layer: Layer,
+    /// The edges the surface is attached to.
+    pub anchors: Anchors,
+    /// The margins to the anchored edges.
+    pub margins: Margins,
+    /// The reserved area.
+    pub exclusive_zone: ExclusiveZone,
+    /// Whether the surface receives keyboard input.
+    pub keyboard_mode: KeyboardMode,
+    /// The connector name of the monitor, for example "HDMI-1",
+    /// see [`display::find_monitor()`](crate::display::find_monitor).
+    pub monitor: Option<String>,
+}
+
+impl Default for LayerShellConfig {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            layer: Layer::Top,
+            anchors: Anchors::NONE,
+            margins: Margins::default(),
+            exclusive_zone: ExclusiveZone::None,
+            keyboard_mode: KeyboardMode::None,
+            monitor: None,
+        }
+    }
+}
+
+impl LayerShellConfig {
+    /// Create a configuration with a namespace.
+    #[must_use]
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: Some(namespace.to_owned()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the layer.
+    #[must_use]
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Set the anchored edges.
+    #[must_use]
+    pub fn anchors(mut self, anchors: Anchors) -> Self {
+        self.anchors = anchors;
+        self
+    }
+
+    /// Set the margins.
+    #[must_use]
+    pub fn margins(mut self, margins: Margins) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Set the reserved area.
+    #[must_use]
+    pub fn exclusive_zone(mut self, exclusive_zone: ExclusiveZone) -> Self {
+        self.exclusive_zone = exclusive_zone;
+        self
+    }
+
+    /// Set whether the surface receives keyboard input.
+    #[must_use]
+    pub fn keyboard_mode(mut self, keyboard_mode: KeyboardMode) -> Self {
+        self.keyboard_mode = keyboard_mode;
+        self
+    }
+
+    /// Show the surface on the monitor with the given connector name.
+    #[must_use]
+    pub fn monitor(mut self, connector: &str) -> Self {
+        self.monitor = Some(connector.to_owned());
+        self
+    }
+
+    /// Turn `window` into a layer surface.
+    ///
+    /// This has to be called before the window is shown.
+    /// Returns `false` if the compositor doesn't support layer surfaces.
+    pub fn apply(&self, window: &impl IsA<gtk::Window>) -> bool {
+        if !gtk4_layer_shell::is_supported() {
+            tracing::warn!("Layer surfaces aren't supported, showing a normal window");
+            return false;
+        }
+
+        let window = window.as_ref();
+        window.init_layer_shell();
+        window.set_namespace(self.namespace.as_deref());
+        window.set_layer(self.layer);
+        window.set_keyboard_mode(self.keyboard_mode);
+
+        let edges = [
+            (Edge::Top, self.anchors.top, self.margins.top),
+            (Edge::Bottom, self.anchors.bottom, self.margins.bottom),
+            (Edge::Left, self.anchors.left, self.margins.left),
+            (Edge::Right, self.anchors.right, self.margins.right),
+        ];
+        for (edge, anchor, margin) in edges {
+            window.set_anchor(edge, anchor);
+            window.set_margin(edge, margin);
+        }
+
+        match self.exclusive_zone {
+            ExclusiveZone::None => window.set_exclusive_zone(0),
+            ExclusiveZone::Auto => window.auto_exclusive_zone_enable(),
+            ExclusiveZone::Fixed(size) => window.set_exclusive_zone(size),
+            ExclusiveZone::Ignore => window.set_exclusive_zone(-1),
+        }
+
+        if let Some(connector) = &self.monitor {
+            match crate::display::find_monitor(connector) {
+                Some(monitor) => window.set_monitor(Some(&monitor)),
+                None => tracing::warn!("Monitor {connector} isn't connected"),
+            }
+        }
+
+        true
+    }
+}
@@ -35,16 +35,72 @@ mod app;
 mod channel;
 mod extensions;
 pub(crate) mod late_initialization;
+mod main_window;
+mod portal;
 mod runtime_util;
 
 pub mod abstractions;
 pub mod actions;
+pub mod autosave;
 pub mod binding;
 pub mod component;
+pub mod config;
+pub mod crash;
+#[cfg(feature = "component_graph")]
+#[cfg_attr(docsrs, doc(cfg(feature = "component_graph")))]
+pub mod debug;
+#[cfg(feature = "leak_diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "leak_diagnostics")))]
+pub mod diagnostics;
+pub mod display;
+#[cfg(feature = "export")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export")))]
+pub mod export;
 pub mod factory;
+pub mod fsm;
+#[cfg(feature = "gamepad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gamepad")))]
+pub mod gamepad;
+pub mod glib_log;
+pub mod global_hotkeys;
+#[cfg(feature = "layer_shell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layer_shell")))]
+pub mod layer_shell;
+#[cfg(feature = "live_reload")]
+#[cfg_attr(docsrs, doc(cfg(feature = "live_reload")))]
+pub mod live_reload;
 pub mod loading_widgets;
+pub mod log_writer;
+pub mod mainloop;
+#[cfg(feature = "reqwest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+pub mod net;
+pub mod network;
+#[cfg(feature = "persist")]
+#[cfg_attr(docsrs, doc(cfg(feature = "persist")))]
+pub mod persist;
+pub mod power;
+pub mod presentation;
+pub mod print;
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub mod screen_capture;
+#[cfg(feature = "secrets")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secrets")))]
+pub mod secrets;
 pub mod shared_state;
+#[cfg(feature = "spellcheck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "spellcheck")))]
+pub mod spell;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub mod telemetry;
+pub mod test;
+pub mod time;
 pub mod typed_view;
+#[cfg(feature = "validation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "validation")))]
+pub mod validation;
 
 pub use channel::ComponentSender;
 pub use channel::*;
@@ -54,6 +110,7 @@ pub use component::{
     SimpleComponent,
 };
 pub use extensions::*;
+pub use main_window::MainWindowRegistry;
 pub use shared_state::{Reducer, Reducible, SharedState};
 pub use shutdown::ShutdownReceiver;
 
@@ -62,14 +119,15 @@ pub use tokio::task::JoinHandle;
 
 use gtk::prelude::{Cast, IsA};
 use once_cell::sync::{Lazy, OnceCell};
-use runtime_util::{GuardedReceiver, RuntimeSenders, ShutdownOnDrop};
+use runtime_util::{GuardedReceiver, InputReceiver, RuntimeSenders, ShutdownOnDrop};
 use std::cell::Cell;
 use std::future::Future;
 use tokio::runtime::Runtime;
 
 /// Defines how many threads that Relm4 should use for background tasks.
 ///
-/// NOTE: The default thread count is 1.
+/// NOTE: The default thread count is 1 or the value of
+/// `RELM4_THREADS`, see [`config`].
 pub static RELM_THREADS: OnceCell<usize> = OnceCell::new();
 
 /// Defines the maximum number of background threads to spawn for handling blocking tasks.
@@ -114,6 +172,7 @@ fn set_main_application(app: impl IsA<gtk::Application>) {
 }
 
 fn init() {
+    config::apply_before_init();
     gtk::init().unwrap();
     #[cfg(feature = "libadwaita")]
     adw::init().unwrap();
@@ -182,7 +241,7 @@ where
 static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
-        .worker_threads(*RELM_THREADS.get_or_init(|| 1))
+        .worker_threads(*RELM_THREADS.get_or_init(|| config::get().threads.unwrap_or(1)))
         .max_blocking_threads(*RELM_BLOCKING_THREADS.get_or_init(|| 512))
         .build()
         .unwrap()
@@ -0,0 +1,274 @@
+//! Experimental live reloading of `view!` layouts.
+//!
+//! With the `live_reload` feature, the [`component`](crate::component) macro
+//! registers the widgets of each component along with its layout, which is
+//! embedded in the binary. The layout contains all properties that are
+//! assigned to literal values or enum variants, like `set_label: "Increment"`
+//! or `set_halign: gtk::Align::Center`.
+//!
+//! If the `RELM4_DEV_DIR` environment variable is set while the application runs,
+//! Relm4 writes the layout of each component to a file in that directory,
+//! named after the module path and the type of the component,
+//! for example `my_app.sidebar.Sidebar.layout`.
+//! Existing files are kept, so they should be removed before the application starts.
+//! Relm4 then watches the layout files and applies changed properties
+//! to the widgets of running components.
+//! `cargo relm dev` from the `relm4-cli` crate runs the application, rebuilds it
+//! whenever a file changes and writes the layouts of the new build to the files.
+//!
+//! Only properties of existing widgets are updated. Added or removed widgets,
+//! expressions and the logic of the component require a restart.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use gtk::glib::{self, ParamSpec, Type, Value, WeakRef};
+use gtk::prelude::{ObjectExt, ToValue};
+use once_cell::sync::Lazy;
+
+/// The environment variable with the directory of the layout files.
+const DEV_DIR_ENV: &str = "RELM4_DEV_DIR";
+
+/// How often the layout files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static DEV_DIR: Lazy<Option<PathBuf>> = Lazy::new(|| std::env::var_os(DEV_DIR_ENV).map(Into::into));
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    components: Vec<Entry>,
+    watching: bool,
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// The name of the layout file without extension.
+    layout: String,
+    widgets: HashMap<&'static str, WeakRef<gtk::Widget>>,
+}
+
+impl Entry {
+    fn is_alive(&self) -> bool {
+        self.widgets
+            .values()
+            .any(|widget| widget.upgrade().is_some())
+    }
+}
+
+/// Returns [`true`] if the live reload mode is active,
+/// which means that `RELM4_DEV_DIR` is set.
+#[must_use]
+pub fn is_active() -> bool {
+    DEV_DIR.is_some()
+}
+
+/// Registers the widgets and the layout record of a component.
+///
+/// This is called by the code generated by the [`component`](crate::component) macro.
+#[doc(hidden)]
+pub fn register(record: &'static str, widgets: &[(&'static str, Option<gtk::Widget>)]) {
+    let Some(dir) = DEV_DIR.as_ref() else {
+        return;
+    };
+    let Some((component, layout)) = parse_record(record) else {
+        tracing::warn!("Invalid layout record for live reloading");
+        return;
+    };
+
+    let name = layout_name(component);
+    let path = dir.join(format!("{name}.layout"));
+    if !path.exists() {
+        if let Err(error) =
+            std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&path, layout))
+        {
+            tracing::warn!(?path, %error, "Couldn't write layout file");
+        }
+    }
+
+    let entry = Entry {
+        layout: name,
+        widgets: widgets
+            .iter()
+            .filter_map(|(name, widget)| Some((*name, widget.as_ref()?.downgrade())))
+            .collect(),
+    };
+
+    let start_watching = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.components.retain(Entry::is_alive);
+        registry.components.push(entry);
+        !std::mem::replace(&mut registry.watching, true)
+    });
+
+    if start_watching {
+        watch(dir);
+    }
+}
+
+/// Splits a layout record into the path of the component and its layout.
+fn parse_record(record: &str) -> Option<(&str, &str)> {
+    // The prefix isn't stored as one string, so `cargo relm dev`
+    // doesn't mistake it for a record when scanning the binary.
+    record
+        .strip_prefix('\0')?
+        .strip_prefix("relm4-layout")?
+        .strip_prefix('\0')?
+        .strip_suffix('\0')?
+        .split_once('\0')
+}
+
+/// Returns the name of the layout file of a component,
+/// which is its path with dots as separators.
+fn layout_name(component: &str) -> String {
+    component.replace("::", ".")
+}
+
+fn watch(dir: &'static Path) {
+    tracing::info!(?dir, "Watching layout files for live reloading");
+
+    let mut modified = scan(dir);
+    glib::timeout_add_local(POLL_INTERVAL, move || {
+        let current = scan(dir);
+        for (path, time) in &current {
+            if modified.get(path) != Some(time) {
+                reload(path);
+            }
+        }
+        modified = current;
+        glib::ControlFlow::Continue
+    });
+}
+
+/// Returns the modification times of all layout files.
+fn scan(dir: &Path) -> HashMap<PathBuf, SystemTime> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "layout"))
+        .filter_map(|path| {
+            let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+fn reload(path: &Path) {
+    let (Some(name), Ok(layout)) = (
+        path.file_stem().and_then(|stem| stem.to_str()),
+        std::fs::read_to_string(path),
+    ) else {
+        return;
+    };
+
+    REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+        for entry in registry.components.iter().filter(|e| e.layout == name) {
+            for line in layout.lines() {
+                let mut fields = line.splitn(3, '\t');
+                let (Some(widget), Some(property), Some(value)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+
+                if let Some(widget) = entry.widgets.get(widget).and_then(WeakRef::upgrade) {
+                    apply(&widget, property, value);
+                }
+            }
+        }
+    });
+    tracing::debug!(component = name, "Reloaded layout");
+}
+
+fn apply(widget: &gtk::Widget, property: &str, value: &str) {
+    let Some(pspec) = widget.find_property(property) else {
+        return;
+    };
+    if !pspec.flags().contains(glib::ParamFlags::WRITABLE) {
+        return;
+    }
+
+    match parse_value(&pspec, value) {
+        Some(value) => widget.set_property_from_value(property, &value),
+        None => tracing::warn!(
+            property,
+            value,
+            "Can't apply layout value to property of type {}",
+            pspec.value_type()
+        ),
+    }
+}
+
+/// Converts a layout value to the type of the property.
+fn parse_value(pspec: &ParamSpec, value: &str) -> Option<Value> {
+    let ty = pspec.value_type();
+    let (kind, value) = value.split_once(':')?;
+
+    match kind {
+        "s" if ty == Type::STRING => Some(unescape(value).to_value()),
+        "b" if ty == Type::BOOL => value.parse::<bool>().ok().map(|v| v.to_value()),
+        "i" | "f" => parse_number(ty, value),
+        "e" if ty.is_a(Type::ENUM) => glib::EnumClass::with_type(ty)?.to_value_by_nick(value),
+        _ => None,
+    }
+}
+
+fn parse_number(ty: Type, value: &str) -> Option<Value> {
+    Some(match ty {
+        Type::I32 => value.parse::<i32>().ok()?.to_value(),
+        Type::U32 => value.parse::<u32>().ok()?.to_value(),
+        Type::I64 => value.parse::<i64>().ok()?.to_value(),
+        Type::U64 => value.parse::<u64>().ok()?.to_value(),
+        Type::F32 => value.parse::<f32>().ok()?.to_value(),
+        Type::F64 => value.parse::<f64>().ok()?.to_value(),
+        _ => return None,
+    })
+}
+
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => output.push('\t'),
+                Some('n') => output.push('\n'),
+                Some('0') => output.push('\0'),
+                Some(c) => output.push(c),
+                None => output.push('\\'),
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{layout_name, parse_record, unescape};
+
+    #[test]
+    fn layout_names() {
+        assert_eq!(
+            parse_record("\0relm4-layout\0my_app::sidebar::Sidebar\0label\tlabel\ts:Hi\n\0"),
+            Some(("my_app::sidebar::Sidebar", "label\tlabel\ts:Hi\n"))
+        );
+        assert_eq!(parse_record("my_app::Sidebar\0"), None);
+        assert_eq!(
+            layout_name("my_app::sidebar::Sidebar"),
+            "my_app.sidebar.Sidebar"
+        );
+        assert_eq!(unescape(r"a\tb\\n\0"), "a\tb\\n\0");
+    }
+}
@@ -0,0 +1,64 @@
+//! A shared GLib log writer.
+//!
+//! GLib only supports a single log writer per process, so all parts
+//! of Relm4 that inspect GLib log messages, like [crash reports](crate::crash),
+//! register hooks here. The writer isn't installed automatically,
+//! because the application or another library might set its own writer.
+//! Call [`install()`] to enable these features.
+
+use std::sync::{Mutex, Once};
+
+use gtk::glib::{self, LogField, LogLevel, LogWriterOutput};
+
+type Hook = Box<dyn Fn(LogLevel, &[LogField<'_>]) -> LogWriterOutput + Send + Sync>;
+
+static HOOKS: Mutex<Vec<Hook>> = Mutex::new(Vec::new());
+static INSTALL: Once = Once::new();
+
+/// Installs the log writer of Relm4 for the whole process.
+///
+/// GLib allows setting the log writer only once per process,
+/// so this only has an effect on the first call. Afterwards, other code
+/// can't set a log writer anymore. If other code already set a log writer
+/// with [`glib::log_set_writer_func()`], GLib aborts the process.
+/// Messages that were logged before the call aren't seen by Relm4.
+pub fn install() {
+    INSTALL.call_once(|| glib::log_set_writer_func(write));
+}
+
+/// Adds a hook that is called for each GLib log message
+/// once the writer was installed with [`install()`].
+///
+/// If a hook returns [`LogWriterOutput::Handled`], the message isn't written
+/// by the default log writer. All hooks are called in any case.
+pub(crate) fn add_hook<F>(hook: F)
+where
+    F: Fn(LogLevel, &[LogField<'_>]) -> LogWriterOutput + Send + Sync + 'static,
+{
+    HOOKS.lock().unwrap().push(Box::new(hook));
+}
+
+fn write(level: LogLevel, fields: &[LogField<'_>]) -> LogWriterOutput {
+    let mut handled = false;
+    // A hook that logs itself would deadlock, so the message is passed
+    // on to the default writer in that case.
+    if let Ok(hooks) = HOOKS.try_lock() {
+        for hook in hooks.iter() {
+            handled |= hook(level, fields) == LogWriterOutput::Handled;
+        }
+    }
+
+    if handled {
+        LogWriterOutput::Handled
+    } else {
+        glib::log_writer_default(level, fields)
+    }
+}
+
+/// Returns the value of a field of a log message.
+pub(crate) fn field<'a>(fields: &'a [LogField<'_>], key: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .find(|field| field.key() == key)
+        .and_then(LogField::value_str)
+}
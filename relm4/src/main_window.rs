@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+
+use gtk::glib;
+use gtk::prelude::{GtkApplicationExt, GtkWindowExt, IsA, NativeDialogExt, ObjectExt, WidgetExt};
+
+use crate::RelmWidgetExt;
+
+/// A registered main window and the handlers that keep the registry up to date.
+struct Entry {
+    window: glib::WeakRef<gtk::Window>,
+    handlers: Vec<glib::SignalHandlerId>,
+}
+
+thread_local! {
+    static MAIN_WINDOWS: RefCell<Vec<Entry>> = RefCell::default();
+}
+
+/// Keeps track of the main windows of the application,
+/// so dialogs can find a transient parent automatically.
+///
+/// The root window of the component launched by [`RelmApp`](crate::RelmApp)
+/// is registered automatically. Applications with multiple main windows
+/// should register the other windows as well.
+///
+/// The dialogs of `relm4-components` use the most recently active
+/// main window as transient parent if no parent was set explicitly.
+#[derive(Debug)]
+pub struct MainWindowRegistry;
+
+impl MainWindowRegistry {
+    /// Register a main window.
+    ///
+    /// Windows are removed automatically once they are destroyed.
+    pub fn register(window: &impl IsA<gtk::Window>) {
+        let window = window.as_ref();
+        if Self::windows().contains(window) {
+            return;
+        }
+
+        let active = window.connect_is_active_notify(|window| {
+            if window.is_active() {
+                // Move the window to the end of the list.
+                MAIN_WINDOWS.with(|windows| {
+                    let mut windows = windows.borrow_mut();
+                    let index = windows
+                        .iter()
+                        .position(|entry| entry.window.upgrade().as_ref() == Some(window));
+                    if let Some(index) = index {
+                        let entry = windows.remove(index);
+                        windows.push(entry);
+                    }
+                });
+            }
+        });
+        let destroy = window.connect_destroy(|window| Self::unregister(window));
+
+        MAIN_WINDOWS.with(|windows| {
+            windows.borrow_mut().push(Entry {
+                window: window.downgrade(),
+                handlers: vec![active, destroy],
+            });
+        });
+    }
+
+    /// Remove a main window from the registry.
+    ///
+    /// This also disconnects the signal handlers the registry added to the window.
+    pub fn unregister(window: &impl IsA<gtk::Window>) {
+        let window = window.as_ref();
+        let entry = MAIN_WINDOWS.with(|windows| {
+            let mut windows = windows.borrow_mut();
+            let index = windows
+                .iter()
+                .position(|entry| entry.window.upgrade().as_ref() == Some(window))?;
+            Some(windows.remove(index))
+        });
+
+        // Disconnect outside of the borrow of the registry.
+        if let Some(entry) = entry {
+            for handler in entry.handlers {
+                window.disconnect(handler);
+            }
+        }
+    }
+
+    /// Returns all registered main windows that are still alive.
+    #[must_use]
+    pub fn windows() -> Vec<gtk::Window> {
+        MAIN_WINDOWS.with(|windows| {
+            let mut windows = windows.borrow_mut();
+            windows.retain(|entry| entry.window.upgrade().is_some());
+            windows
+                .iter()
+                .filter_map(|entry| entry.window.upgrade())
+                .collect()
+        })
+    }
+
+    /// Returns the most recently active main window.
+    ///
+    /// Falls back to the active window of the
+    /// [main application](crate::main_application()) if no
+    /// main window was registered.
+    #[must_use]
+    pub fn current() -> Option<gtk::Window> {
+        Self::windows()
+            .pop()
+            .or_else(|| crate::main_application().active_window())
+    }
+
+    /// Returns the window that contains `widget` or
+    /// the [current main window](Self::current) otherwise.
+    #[must_use]
+    pub fn transient_parent_for(widget: &impl IsA<gtk::Widget>) -> Option<gtk::Window> {
+        widget
+            .as_ref()
+            .toplevel_window()
+            .filter(|window| window != widget.as_ref())
+            .or_else(Self::current)
+    }
+
+    /// Make `window` transient for the current main window
+    /// unless it already has a transient parent.
+    pub fn set_transient_parent(window: &impl IsA<gtk::Window>) {
+        let window = window.as_ref();
+        if window.transient_for().is_none() {
+            if let Some(parent) = Self::current().filter(|parent| parent != window) {
+                window.set_transient_for(Some(&parent));
+            }
+        }
+    }
+
+    /// Make a native `dialog` transient for the current main window
+    /// unless it already has a transient parent.
+    pub fn set_transient_parent_native(dialog: &impl IsA<gtk::NativeDialog>) {
+        let dialog = dialog.as_ref();
+        if dialog.transient_for().is_none() {
+            dialog.set_transient_for(Self::current().as_ref());
+        }
+    }
+}
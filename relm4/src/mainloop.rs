@@ -0,0 +1,239 @@
+//! Metrics of the updates that run on the main loop.
+//!
+//! Long updates block the main loop and cause dropped frames.
+//! [`stats()`] shows how much time was spent in updates and with
+//! [`set_frame_budget()`], every update that takes longer than
+//! the budget is reported as warning via [`tracing`]:
+//!
+//! ```
+//! # use std::time::Duration;
+//! // Warn about updates that take more than half a frame at 60 FPS.
+//! relm4::mainloop::set_frame_budget(Some(Duration::from_millis(8)));
+//! ```
+//!
+//! The updates and view updates of [`Component`](crate::Component)s
+//! and [`FactoryComponent`](crate::factory::FactoryComponent)s are measured.
+//! With the `metrics` feature, they are also recorded as metrics,
+//! see [`telemetry`](crate::telemetry).
+
+use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static UPDATES: AtomicU64 = AtomicU64::new(0);
+static TOTAL_NANOS: AtomicU64 = AtomicU64::new(0);
+static MAX_NANOS: AtomicU64 = AtomicU64::new(0);
+static OVER_BUDGET: AtomicU64 = AtomicU64::new(0);
+/// The frame budget in nanoseconds or zero if the watchdog is disabled.
+static BUDGET_NANOS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The components whose updates are running on this thread.
+    static RUNNING: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Statistics of the updates that ran on the main loop,
+/// see [`stats()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MainLoopStats {
+    /// Number of measured updates.
+    pub updates: u64,
+    /// The time spent in all updates.
+    pub total_time: Duration,
+    /// The time of the longest update.
+    pub max_time: Duration,
+    /// Number of updates that exceeded the frame budget.
+    pub over_budget: u64,
+}
+
+impl MainLoopStats {
+    /// The average time of an update.
+    #[must_use]
+    pub fn average_time(&self) -> Duration {
+        let nanos = duration_nanos(self.total_time).checked_div(self.updates);
+        Duration::from_nanos(nanos.unwrap_or(0))
+    }
+}
+
+/// Returns the statistics of all updates since the start
+/// of the application or the last call of [`reset_stats()`].
+#[must_use]
+pub fn stats() -> MainLoopStats {
+    MainLoopStats {
+        updates: UPDATES.load(Ordering::Relaxed),
+        total_time: Duration::from_nanos(TOTAL_NANOS.load(Ordering::Relaxed)),
+        max_time: Duration::from_nanos(MAX_NANOS.load(Ordering::Relaxed)),
+        over_budget: OVER_BUDGET.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets the statistics returned by [`stats()`].
+pub fn reset_stats() {
+    UPDATES.store(0, Ordering::Relaxed);
+    TOTAL_NANOS.store(0, Ordering::Relaxed);
+    MAX_NANOS.store(0, Ordering::Relaxed);
+    OVER_BUDGET.store(0, Ordering::Relaxed);
+}
+
+/// Sets the time a single update may take before a warning is emitted.
+///
+/// The warning includes the name of the component and the type of the message.
+/// [`None`] disables the watchdog, which is the default.
+pub fn set_frame_budget(budget: Option<Duration>) {
+    let nanos = budget.map_or(0, |budget| duration_nanos(budget).max(1));
+    BUDGET_NANOS.store(nanos, Ordering::Relaxed);
+}
+
+/// Returns the frame budget set by [`set_frame_budget()`].
+#[must_use]
+pub fn frame_budget() -> Option<Duration> {
+    match BUDGET_NANOS.load(Ordering::Relaxed) {
+        0 => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+/// Runs and measures an update of a component.
+pub(crate) fn measure<R>(
+    kind: &'static str,
+    component: &'static str,
+    message_type: &'static str,
+    update: impl FnOnce() -> R,
+) -> R {
+    let _guard = enter_update(component);
+    let start = Instant::now();
+    let result = update();
+    let elapsed = start.elapsed();
+
+    #[cfg(feature = "metrics")]
+    crate::telemetry::update(kind, component, elapsed);
+
+    let nanos = duration_nanos(elapsed);
+    UPDATES.fetch_add(1, Ordering::Relaxed);
+    TOTAL_NANOS.fetch_add(nanos, Ordering::Relaxed);
+    MAX_NANOS.fetch_max(nanos, Ordering::Relaxed);
+
+    if let Some(budget) = frame_budget() {
+        if elapsed > budget {
+            OVER_BUDGET.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                component,
+                message_type,
+                ?elapsed,
+                ?budget,
+                "`{kind}` exceeded the frame budget"
+            );
+        }
+    }
+    result
+}
+
+/// Marks the update of a component as running until the guard is dropped.
+pub(crate) fn enter_update(component: &'static str) -> UpdateGuard {
+    RUNNING.with(|running| running.borrow_mut().push(component));
+    UpdateGuard(component)
+}
+
+/// Marks the update of a component as running while `update` is polled.
+///
+/// Other components can run while the update is suspended,
+/// so the update is only entered for each poll.
+pub(crate) async fn in_update<F: Future>(component: &'static str, update: F) -> F::Output {
+    let mut update = pin!(update);
+    poll_fn(|cx| {
+        let _guard = enter_update(component);
+        update.as_mut().poll(cx)
+    })
+    .await
+}
+
+/// Returns the components whose updates are running on this thread,
+/// the innermost last.
+pub(crate) fn running_updates() -> Vec<&'static str> {
+    RUNNING
+        .try_with(|running| running.borrow().clone())
+        .unwrap_or_default()
+}
+
+/// Returned by [`enter_update()`].
+#[derive(Debug)]
+pub(crate) struct UpdateGuard(&'static str);
+
+impl Drop for UpdateGuard {
+    fn drop(&mut self) {
+        // Updates of async components can end in any order.
+        let _ = RUNNING.try_with(|running| {
+            let mut running = running.borrow_mut();
+            if let Some(position) = running.iter().rposition(|c| *c == self.0) {
+                running.remove(position);
+            }
+        });
+    }
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+    use std::task::Context;
+    use std::time::Duration;
+
+    use futures::channel::oneshot;
+    use futures::executor::block_on;
+    use futures::task::noop_waker;
+
+    use super::{enter_update, in_update, measure, running_updates, set_frame_budget, stats};
+
+    #[test]
+    fn frame_budget() {
+        set_frame_budget(Some(Duration::from_millis(1)));
+        let value = measure("update", "Component", "Msg", || {
+            std::thread::sleep(Duration::from_millis(5));
+            42
+        });
+        assert_eq!(value, 42);
+
+        let stats = stats();
+        assert!(stats.updates >= 1);
+        assert!(stats.over_budget >= 1);
+        assert!(stats.max_time >= Duration::from_millis(5));
+        set_frame_budget(None);
+    }
+
+    #[test]
+    fn running_updates_out_of_order() {
+        let first = enter_update("First");
+        let second = enter_update("Second");
+        assert_eq!(running_updates(), ["First", "Second"]);
+
+        // Async updates can finish in any order.
+        drop(first);
+        assert_eq!(running_updates(), ["Second"]);
+        drop(second);
+        assert!(running_updates().is_empty());
+    }
+
+    #[test]
+    fn update_is_entered_per_poll() {
+        let (sender, receiver) = oneshot::channel::<()>();
+        let mut update = Box::pin(in_update("app::App", async {
+            receiver.await.ok();
+            running_updates()
+        }));
+
+        let waker = noop_waker();
+        assert!(update
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker))
+            .is_pending());
+        assert!(running_updates().is_empty());
+
+        sender.send(()).unwrap();
+        assert_eq!(block_on(update), ["app::App"]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Helpers for HTTP requests with typed JSON responses.
+//!
+//! Requests are usually sent with [`ComponentSender::fetch_json()`],
+//! which performs them on the runtime and forwards the deserialized
+//! response as an input message.
+//!
+//! ```no_run
+//! # use relm4::prelude::*;
+//! # use relm4::net::{JsonRequest, FetchError};
+//! # use std::time::Duration;
+//! #[derive(Debug, serde::Deserialize)]
+//! struct Repository {
+//!     stargazers_count: u32,
+//! }
+//!
+//! #[derive(Debug)]
+//! enum Msg {
+//!     Load,
+//!     Loaded(Result<Repository, FetchError>),
+//! }
+//! # struct App;
+//! # impl SimpleComponent for App {
+//! #     type Init = ();
+//! #     type Input = Msg;
+//! #     type Output = ();
+//! #     type Root = ();
+//! #     type Widgets = ();
+//! #     fn init_root() {}
+//! #     fn init(_: (), _: (), _: ComponentSender<Self>) -> ComponentParts<Self> {
+//! #         ComponentParts { model: App, widgets: () }
+//! #     }
+//!
+//! fn update(&mut self, msg: Msg, sender: ComponentSender<Self>) {
+//!     match msg {
+//!         Msg::Load => sender.fetch_json(
+//!             JsonRequest::get("https://api.github.com/repos/Relm4/Relm4")
+//!                 .header("User-Agent", "relm4")
+//!                 .timeout(Duration::from_secs(10)),
+//!             Msg::Loaded,
+//!         ),
+//!         Msg::Loaded(repository) => println!("{repository:?}"),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`ComponentSender::fetch_json()`]: crate::ComponentSender::fetch_json
+
+use std::time::Duration;
+
+use futures::future::Either;
+use once_cell::sync::Lazy;
+use reqwest::{Client, IntoUrl, Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::ShutdownReceiver;
+
+/// Re-export of reqwest
+pub use reqwest;
+
+/// Error returned if a request fails, returns an error status
+/// or its response can't be deserialized.
+pub type FetchError = reqwest::Error;
+
+/// Client shared by all requests that weren't created from a custom [`RequestBuilder`].
+static CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// A HTTP request whose response is deserialized from JSON.
+///
+/// Use [`From<RequestBuilder>`] to send requests with a custom [`Client`].
+#[derive(Debug)]
+#[must_use]
+pub struct JsonRequest {
+    builder: RequestBuilder,
+}
+
+impl From<RequestBuilder> for JsonRequest {
+    fn from(builder: RequestBuilder) -> Self {
+        Self { builder }
+    }
+}
+
+impl JsonRequest {
+    /// Create a request with the given method.
+    pub fn new<U: IntoUrl>(method: Method, url: U) -> Self {
+        CLIENT.request(method, url).into()
+    }
+
+    /// Create a `GET` request.
+    pub fn get<U: IntoUrl>(url: U) -> Self {
+        Self::new(Method::GET, url)
+    }
+
+    /// Create a `POST` request with a JSON body.
+    pub fn post<U: IntoUrl, B: Serialize + ?Sized>(url: U, body: &B) -> Self {
+        Self::new(Method::POST, url).json(body)
+    }
+
+    /// Add a header to the request.
+    ///
+    /// Invalid header names or values are reported
+    /// as [`FetchError`] once the request is sent.
+    pub fn header(self, key: &str, value: &str) -> Self {
+        self.builder.header(key, value).into()
+    }
+
+    /// Enable bearer authentication.
+    pub fn bearer_auth<T: std::fmt::Display>(self, token: T) -> Self {
+        self.builder.bearer_auth(token).into()
+    }
+
+    /// Add query parameters to the URL.
+    pub fn query<T: Serialize + ?Sized>(self, query: &T) -> Self {
+        self.builder.query(query).into()
+    }
+
+    /// Set a JSON body.
+    pub fn json<T: Serialize + ?Sized>(self, body: &T) -> Self {
+        self.builder.json(body).into()
+    }
+
+    /// Set a timeout for the whole request, including reading the response.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.builder.timeout(timeout).into()
+    }
+
+    /// Send the request and deserialize the response.
+    ///
+    /// Responses with an error status are returned as [`Err`].
+    pub async fn send<T: DeserializeOwned>(self) -> Result<T, FetchError> {
+        self.builder.send().await?.error_for_status()?.json().await
+    }
+}
+
+/// Perform the request on the runtime and forward the result as input.
+pub(crate) fn fetch_json<T, Input, F>(
+    request: JsonRequest,
+    input: crate::Sender<Input>,
+    shutdown: ShutdownReceiver,
+    mapper: F,
+) where
+    T: DeserializeOwned + Send + 'static,
+    Input: 'static,
+    F: FnOnce(Result<T, FetchError>) -> Input + 'static,
+{
+    // The request is canceled as soon as the component shuts down.
+    let handle = crate::spawn(shutdown.register(request.send::<T>()).wait());
+
+    crate::spawn_local(async move {
+        if let Ok(Either::Right(result)) = handle.await {
+            input.send(mapper(result)).ok();
+        }
+    });
+}
@@ -0,0 +1,137 @@
+//! Watch the network connectivity to implement offline modes.
+//!
+//! [`NETWORK_STATUS`] is a [`SharedState`] that is kept up to date with
+//! [`gio::NetworkMonitor`] once [`start_monitor()`] was called.
+//! [`subscribe()`] does both and sends the current status right away:
+//!
+//! ```no_run
+//! # use relm4::network::NetworkStatus;
+//! # #[derive(Debug)]
+//! # enum Msg { Network(NetworkStatus) }
+//! # fn init(sender: relm4::Sender<Msg>) {
+//! relm4::network::subscribe(&sender, |status| Msg::Network(*status));
+//! # }
+//! ```
+//!
+//! Subscribers are only notified when the status actually changes.
+
+use std::cell::Cell;
+
+use gtk::gio;
+use gtk::prelude::{NetworkMonitorExt, ObjectExt};
+
+use crate::{Sender, SharedState};
+
+/// The current network status, updated by [`start_monitor()`].
+pub static NETWORK_STATUS: SharedState<NetworkStatus> = SharedState::new();
+
+thread_local! {
+    static STARTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// How well the system is connected, see [`gio::NetworkConnectivity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Only the local machine is reachable.
+    Local,
+    /// Only the local network is reachable.
+    Limited,
+    /// A captive portal blocks access to the internet.
+    Portal,
+    /// The internet is reachable.
+    #[default]
+    Full,
+}
+
+impl From<gio::NetworkConnectivity> for Connectivity {
+    fn from(connectivity: gio::NetworkConnectivity) -> Self {
+        match connectivity {
+            gio::NetworkConnectivity::Local => Self::Local,
+            gio::NetworkConnectivity::Limited => Self::Limited,
+            gio::NetworkConnectivity::Portal => Self::Portal,
+            _ => Self::Full,
+        }
+    }
+}
+
+/// The state of the network connection.
+///
+/// Until the monitor is started, the network is assumed to be available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkStatus {
+    /// The system has a default route.
+    pub available: bool,
+    /// How well the system is connected.
+    pub connectivity: Connectivity,
+    /// The connection is metered, so large downloads should be avoided.
+    pub metered: bool,
+}
+
+impl Default for NetworkStatus {
+    fn default() -> Self {
+        Self {
+            available: true,
+            connectivity: Connectivity::Full,
+            metered: false,
+        }
+    }
+}
+
+impl NetworkStatus {
+    /// Returns `true` if the internet is reachable.
+    #[must_use]
+    pub fn is_online(&self) -> bool {
+        self.available && self.connectivity == Connectivity::Full
+    }
+
+    fn from_monitor(monitor: &gio::NetworkMonitor) -> Self {
+        Self {
+            available: monitor.is_network_available(),
+            connectivity: monitor.connectivity().into(),
+            metered: monitor.is_network_metered(),
+        }
+    }
+}
+
+/// Keep [`NETWORK_STATUS`] up to date with the default [`gio::NetworkMonitor`].
+///
+/// Calling this more than once has no effect.
+/// The monitor emits its signals on the thread that started it,
+/// so this should be called from the main thread.
+pub fn start_monitor() {
+    if STARTED.with(|started| started.replace(true)) {
+        return;
+    }
+
+    let monitor = gio::NetworkMonitor::default();
+    update(&monitor);
+
+    monitor.connect_network_changed(|monitor, _| update(monitor));
+    monitor.connect_notify_local(Some("connectivity"), |monitor, _| update(monitor));
+    monitor.connect_notify_local(Some("network-metered"), |monitor, _| update(monitor));
+}
+
+/// Returns the current network status and starts the monitor if needed.
+#[must_use]
+pub fn status() -> NetworkStatus {
+    start_monitor();
+    *NETWORK_STATUS.read()
+}
+
+/// Start the monitor, send the current status and forward all changes.
+pub fn subscribe<Msg, F>(sender: &Sender<Msg>, f: F)
+where
+    F: Fn(&NetworkStatus) -> Msg + 'static + Send + Sync,
+    Msg: Send + 'static,
+{
+    sender.emit(f(&status()));
+    NETWORK_STATUS.subscribe(sender, f);
+}
+
+fn update(monitor: &gio::NetworkMonitor) {
+    let status = NetworkStatus::from_monitor(monitor);
+    // Only notify subscribers about actual changes.
+    if *NETWORK_STATUS.read() != status {
+        *NETWORK_STATUS.write() = status;
+    }
+}
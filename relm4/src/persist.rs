@@ -0,0 +1,268 @@
+//! Versioned persistence of component and session state.
+//!
+//! Types that implement [`Persistent`] are stored as JSON together with their version.
+//! When a newer version of the application loads older data, fields that were added
+//! later get their default value and migration functions can rewrite the data first.
+//! Loading never panics, broken or unknown data is reported as [`PersistError`].
+//!
+//! The trait is usually implemented with [`#[derive(Persistent)]`](crate::Persistent):
+//!
+//! ```
+//! use relm4::persist::{self, PersistError};
+//! use relm4::persist::serde_json::Value;
+//!
+//! #[derive(Debug, PartialEq, relm4::Persistent)]
+//! #[persistent(version = 3, migrate = migrate_window)]
+//! struct WindowState {
+//!     width: i32,
+//!     height: i32,
+//!     // Added in version 2, older data uses the default value.
+//!     #[since(2)]
+//!     maximized: bool,
+//!     #[since(3, default = default_zoom)]
+//!     zoom: f64,
+//! }
+//!
+//! fn default_zoom() -> f64 {
+//!     1.0
+//! }
+//!
+//! /// Version 1 stored the size as `[width, height]`.
+//! fn migrate_window(version: u32, data: &mut Value) -> Result<(), PersistError> {
+//!     if version < 2 {
+//!         let size = data["size"].take();
+//!         let Some([width, height]) = size.as_array().map(Vec::as_slice) else {
+//!             return Err(PersistError::Migration(format!("invalid size {size}")));
+//!         };
+//!         data["width"] = width.clone();
+//!         data["height"] = height.clone();
+//!     }
+//!     Ok(())
+//! }
+//!
+//! let old = r#"{ "version": 1, "data": { "size": [800, 600] } }"#;
+//! let state: WindowState = persist::from_str(old).unwrap();
+//! assert_eq!(state, WindowState { width: 800, height: 600, maximized: false, zoom: 1.0 });
+//!
+//! let saved = persist::to_string(&state).unwrap();
+//! assert_eq!(persist::from_str::<WindowState>(&saved).unwrap(), state);
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Re-export of serde_json
+pub use serde_json;
+
+/// A type whose persisted data has a version.
+pub trait Persistent: Sized {
+    /// The current version of the data.
+    const VERSION: u32;
+
+    /// Serialize the current version of the data.
+    fn to_value(&self) -> Result<Value, PersistError>;
+
+    /// Deserialize data that was saved with the given version,
+    /// which is never newer than [`Self::VERSION`].
+    fn from_value(version: u32, value: Value) -> Result<Self, PersistError>;
+}
+
+/// Error returned if data can't be persisted or restored.
+#[derive(Debug)]
+pub enum PersistError {
+    /// Reading or writing the file failed.
+    Io(io::Error),
+    /// The data isn't valid JSON or has an unexpected structure.
+    Json(serde_json::Error),
+    /// A field couldn't be serialized or deserialized.
+    Field {
+        /// The name of the field.
+        name: &'static str,
+        /// The reason.
+        source: serde_json::Error,
+    },
+    /// A field that is expected in this version is missing.
+    MissingField(&'static str),
+    /// The data was saved by a newer version of the application.
+    UnsupportedVersion {
+        /// The version of the data.
+        found: u32,
+        /// The newest version that is supported.
+        supported: u32,
+    },
+    /// A migration function failed.
+    Migration(String),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "persisted data can't be accessed: {error}"),
+            Self::Json(error) => write!(f, "persisted data is invalid: {error}"),
+            Self::Field { name, source } => write!(f, "field `{name}` is invalid: {source}"),
+            Self::MissingField(name) => write!(f, "field `{name}` is missing"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "data of version {found} is newer than the supported version {supported}"
+            ),
+            Self::Migration(message) => write!(f, "migration failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Field { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// Serialize `value` together with its version.
+pub fn to_string<T: Persistent>(value: &T) -> Result<String, PersistError> {
+    let envelope = serde_json::json!({
+        "version": T::VERSION,
+        "data": value.to_value()?,
+    });
+    Ok(serde_json::to_string_pretty(&envelope)?)
+}
+
+/// Deserialize data that was created by [`to_string()`], migrating it if needed.
+pub fn from_str<T: Persistent>(text: &str) -> Result<T, PersistError> {
+    let mut envelope = __private::into_object(serde_json::from_str(text)?)?;
+    let version: u32 = __private::required(&mut envelope, "version")?;
+    let data = envelope
+        .remove("data")
+        .ok_or(PersistError::MissingField("data"))?;
+
+    if version > T::VERSION {
+        return Err(PersistError::UnsupportedVersion {
+            found: version,
+            supported: T::VERSION,
+        });
+    }
+    T::from_value(version, data)
+}
+
+/// Save `value` to a file.
+///
+/// The data is written to a temporary file first,
+/// so the old data stays intact if writing fails.
+pub fn save<T: Persistent>(path: impl AsRef<Path>, value: &T) -> Result<(), PersistError> {
+    let path = path.as_ref();
+    let text = to_string(value)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, text)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+/// Load a value from a file.
+///
+/// Returns [`None`] if the file doesn't exist.
+pub fn load<T: Persistent>(path: impl AsRef<Path>) -> Result<Option<T>, PersistError> {
+    match fs::read_to_string(path) {
+        Ok(text) => from_str(&text).map(Some),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Load a value from a file or use the default value if the file
+/// doesn't exist or can't be restored.
+///
+/// Errors are logged as warning.
+pub fn load_or_default<T: Persistent + Default>(path: impl AsRef<Path>) -> T {
+    let path = path.as_ref();
+    match load(path) {
+        Ok(value) => value.unwrap_or_default(),
+        Err(error) => {
+            tracing::warn!(
+                "Using defaults because {} can't be restored: {error}",
+                path.display()
+            );
+            T::default()
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use serde::de::{DeserializeOwned, Error};
+    use serde::Serialize;
+    use serde_json::{Map, Value};
+
+    use super::PersistError;
+
+    pub fn into_object(value: Value) -> Result<Map<String, Value>, PersistError> {
+        match value {
+            Value::Object(object) => Ok(object),
+            _ => Err(PersistError::Json(serde_json::Error::custom(
+                "expected the data to be an object",
+            ))),
+        }
+    }
+
+    pub fn serialize<T: Serialize>(
+        object: &mut Map<String, Value>,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), PersistError> {
+        let value =
+            serde_json::to_value(value).map_err(|source| PersistError::Field { name, source })?;
+        object.insert(name.to_owned(), value);
+        Ok(())
+    }
+
+    /// Deserialize a field that exists since the first version.
+    pub fn required<T: DeserializeOwned>(
+        object: &mut Map<String, Value>,
+        name: &'static str,
+    ) -> Result<T, PersistError> {
+        let value = object
+            .remove(name)
+            .ok_or(PersistError::MissingField(name))?;
+        serde_json::from_value(value).map_err(|source| PersistError::Field { name, source })
+    }
+
+    /// Deserialize a field that was added in version `since`.
+    pub fn since<T: DeserializeOwned>(
+        object: &mut Map<String, Value>,
+        name: &'static str,
+        version: u32,
+        since: u32,
+        default: impl FnOnce() -> T,
+    ) -> Result<T, PersistError> {
+        match object.remove(name) {
+            Some(value) => {
+                serde_json::from_value(value).map_err(|source| PersistError::Field { name, source })
+            }
+            None if version < since => Ok(default()),
+            None => Err(PersistError::MissingField(name)),
+        }
+    }
+}
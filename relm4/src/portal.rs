@@ -0,0 +1,142 @@
+//! Requests to the [XDG desktop portal](https://flatpak.github.io/xdg-desktop-portal/).
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use futures::channel::oneshot;
+use gtk::gio;
+use gtk::glib::prelude::ToVariant;
+use gtk::glib::{self, Variant, VariantDict};
+
+pub(crate) const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+pub(crate) const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+const SESSION_INTERFACE: &str = "org.freedesktop.portal.Session";
+
+/// The `response` of a request that the user cancelled.
+const RESPONSE_CANCELLED: u32 = 1;
+
+static NEXT_TOKEN: AtomicU32 = AtomicU32::new(0);
+
+/// The result of a portal request.
+pub(crate) enum Response {
+    /// The request succeeded with these results.
+    Success(VariantDict),
+    /// The user cancelled the request.
+    Cancelled,
+    /// The request failed in another way.
+    Failed,
+}
+
+/// Returns a new token that identifies a request or session.
+pub(crate) fn new_token() -> String {
+    format!(
+        "relm4_{}_{}",
+        std::process::id(),
+        NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Returns the options of a request with the `handle_token`.
+pub(crate) fn request_options(token: &str) -> VariantDict {
+    let options = VariantDict::new(None);
+    options.insert_value("handle_token", &token.to_variant());
+    options
+}
+
+/// Returns the session handle of the results of a `CreateSession` request.
+pub(crate) fn session_handle(results: &VariantDict) -> Option<String> {
+    // Older portals return the handle as string instead of object path.
+    results
+        .lookup_value("session_handle", None)
+        .and_then(|handle| handle.str().map(str::to_owned))
+}
+
+/// Call a portal method and wait for the `Response` signal of its request.
+///
+/// `parameters` receives the token that must be used as `handle_token`.
+pub(crate) async fn request(
+    connection: &gio::DBusConnection,
+    interface: &str,
+    method: &str,
+    parameters: impl FnOnce(&str) -> Vec<Variant>,
+) -> Result<Response, glib::Error> {
+    let token = new_token();
+    let sender_name = connection
+        .unique_name()
+        .ok_or_else(|| {
+            glib::Error::new(gio::IOErrorEnum::NotConnected, "Not connected to the bus")
+        })?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    let request_path = format!("{PORTAL_PATH}/request/{sender_name}/{token}");
+
+    // Subscribe before calling the method, so the response can't be missed.
+    let (response_sender, response_receiver) = oneshot::channel();
+    let response_sender = Cell::new(Some(response_sender));
+    let subscription = connection.signal_subscribe(
+        Some(PORTAL_NAME),
+        Some(REQUEST_INTERFACE),
+        Some("Response"),
+        Some(&request_path),
+        None,
+        gio::DBusSignalFlags::NONE,
+        move |_, _, _, _, _, parameters| {
+            let response = parameters.child_value(0).get::<u32>().unwrap_or(u32::MAX);
+            let results = VariantDict::new(Some(&parameters.child_value(1)));
+            if let Some(sender) = response_sender.take() {
+                sender.send((response, results)).ok();
+            }
+        },
+    );
+
+    let result = connection
+        .call_future(
+            Some(PORTAL_NAME),
+            PORTAL_PATH,
+            interface,
+            method,
+            Some(&Variant::tuple_from_iter(parameters(&token))),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await;
+    let response = match result {
+        Ok(_) => response_receiver.await.ok(),
+        Err(error) => {
+            connection.signal_unsubscribe(subscription);
+            return Err(error);
+        }
+    };
+    connection.signal_unsubscribe(subscription);
+
+    Ok(match response {
+        Some((0, results)) => Response::Success(results),
+        Some((RESPONSE_CANCELLED, _)) => Response::Cancelled,
+        _ => Response::Failed,
+    })
+}
+
+/// Close a portal session without waiting for the result.
+pub(crate) fn close_session(connection: &gio::DBusConnection, session: &str) {
+    connection.call(
+        Some(PORTAL_NAME),
+        session,
+        SESSION_INTERFACE,
+        "Close",
+        None,
+        None,
+        gio::DBusCallFlags::NONE,
+        -1,
+        None::<&gio::Cancellable>,
+        |_| (),
+    );
+}
+
+/// Returns `true` if the error means that the portal or its interface isn't available.
+pub(crate) fn is_unsupported(error: &glib::Error) -> bool {
+    error.matches(gio::DBusError::ServiceUnknown)
+        || error.matches(gio::DBusError::UnknownInterface)
+        || error.matches(gio::DBusError::UnknownMethod)
+}
@@ -0,0 +1,328 @@
+//! Battery and power saving state and inhibition of suspend and idle.
+//!
+//! [`subscribe()`] forwards the [`PowerStatus`] to a component whenever it changes.
+//! The battery is read from [UPower](https://upower.freedesktop.org/) on the system bus
+//! and power saving from `gio::PowerProfileMonitor` with the `gnome_42` feature, which uses
+//! the power profile portal in sandboxed applications. Without it, power saving is read
+//! from the [power profile portal](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.PowerProfileMonitor.html)
+//! directly.
+//!
+//! [`InhibitGuard`] prevents suspending or idling as long as it's alive,
+//! so storing it in the model of a component ties it to the lifetime of the component:
+//!
+//! ```no_run
+//! # use relm4::power::InhibitGuard;
+//! # use relm4::gtk;
+//! struct Exporter {
+//!     inhibit: Option<InhibitGuard>,
+//! }
+//!
+//! impl Exporter {
+//!     fn start(&mut self) {
+//!         self.inhibit = Some(InhibitGuard::suspend("Exporting video"));
+//!     }
+//!
+//!     fn finish(&mut self) {
+//!         self.inhibit = None;
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use gtk::gio;
+use gtk::prelude::{DBusProxyExt, GtkApplicationExt, IsA, ObjectExt};
+
+use crate::Sender;
+
+#[cfg(feature = "gnome_42")]
+use gtk::prelude::PowerProfileMonitorExt;
+
+const UPOWER_NAME: &str = "org.freedesktop.UPower";
+const UPOWER_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const UPOWER_DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+
+#[cfg(not(feature = "gnome_42"))]
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+#[cfg(not(feature = "gnome_42"))]
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+#[cfg(not(feature = "gnome_42"))]
+const PORTAL_POWER_PROFILE_INTERFACE: &str = "org.freedesktop.portal.PowerProfileMonitor";
+
+/// The `State` of a UPower device that is discharging.
+const STATE_DISCHARGING: u32 = 2;
+/// The `State` of a UPower device that will discharge soon.
+const STATE_PENDING_DISCHARGE: u32 = 6;
+
+type Subscriber = Box<dyn Fn(&PowerStatus) -> bool>;
+
+thread_local! {
+    static MONITOR: RefCell<Option<Rc<Monitor>>> = const { RefCell::new(None) };
+}
+
+/// The power state of the system.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PowerStatus {
+    /// The system runs on battery.
+    pub on_battery: bool,
+    /// The charge of the battery in percent, if the system has a battery.
+    pub battery_level: Option<f64>,
+    /// The user enabled power saving, so background work should be reduced.
+    pub power_saver: bool,
+}
+
+/// Watches UPower and the power profile monitor.
+struct Monitor {
+    status: RefCell<PowerStatus>,
+    subscribers: RefCell<Vec<Subscriber>>,
+    battery: RefCell<Option<gio::DBusProxy>>,
+    #[cfg(feature = "gnome_42")]
+    power_profile: gio::PowerProfileMonitor,
+    /// The power profile portal, which older GIO versions don't wrap.
+    #[cfg(not(feature = "gnome_42"))]
+    power_profile: RefCell<Option<gio::DBusProxy>>,
+}
+
+impl Monitor {
+    fn get() -> Rc<Self> {
+        MONITOR.with(|monitor| {
+            monitor
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    let monitor = Rc::new(Self::new());
+                    monitor.start();
+                    monitor
+                })
+                .clone()
+        })
+    }
+
+    fn new() -> Self {
+        Self {
+            status: RefCell::default(),
+            subscribers: RefCell::default(),
+            battery: RefCell::default(),
+            #[cfg(feature = "gnome_42")]
+            power_profile: gio::PowerProfileMonitor::get_default(),
+            #[cfg(not(feature = "gnome_42"))]
+            power_profile: RefCell::default(),
+        }
+    }
+
+    fn start(self: &Rc<Self>) {
+        #[cfg(feature = "gnome_42")]
+        {
+            let weak = Rc::downgrade(self);
+            self.power_profile
+                .connect_power_saver_enabled_notify(move |_| {
+                    if let Some(monitor) = weak.upgrade() {
+                        monitor.update();
+                    }
+                });
+        }
+
+        #[cfg(not(feature = "gnome_42"))]
+        self.connect_proxy(
+            gio::BusType::Session,
+            PORTAL_NAME,
+            PORTAL_PATH,
+            PORTAL_POWER_PROFILE_INTERFACE,
+            |monitor| &monitor.power_profile,
+        );
+
+        self.connect_proxy(
+            gio::BusType::System,
+            UPOWER_NAME,
+            UPOWER_DEVICE_PATH,
+            UPOWER_DEVICE_INTERFACE,
+            |monitor| &monitor.battery,
+        );
+
+        self.update();
+    }
+
+    /// Create a proxy in the background, store it with `slot`
+    /// and update the status whenever its properties change.
+    fn connect_proxy(
+        self: &Rc<Self>,
+        bus: gio::BusType,
+        name: &'static str,
+        path: &'static str,
+        interface: &'static str,
+        slot: fn(&Self) -> &RefCell<Option<gio::DBusProxy>>,
+    ) {
+        let weak = Rc::downgrade(self);
+        crate::spawn_local(async move {
+            let proxy = gio::DBusProxy::for_bus_future(
+                bus,
+                gio::DBusProxyFlags::NONE,
+                None,
+                name,
+                path,
+                interface,
+            )
+            .await;
+            let Some(monitor) = weak.upgrade() else {
+                return;
+            };
+            match proxy {
+                Ok(proxy) => {
+                    let weak = Rc::downgrade(&monitor);
+                    proxy.connect_local("g-properties-changed", false, move |_| {
+                        if let Some(monitor) = weak.upgrade() {
+                            monitor.update();
+                        }
+                        None
+                    });
+                    slot(&monitor).replace(Some(proxy));
+                    monitor.update();
+                }
+                Err(error) => tracing::debug!("{interface} is not available: {error}"),
+            }
+        });
+    }
+
+    fn read(&self) -> PowerStatus {
+        let mut status = PowerStatus::default();
+
+        if let Some(battery) = &*self.battery.borrow() {
+            let property = |name: &str| battery.cached_property(name);
+            let present = property("IsPresent")
+                .and_then(|value| value.get::<bool>())
+                .unwrap_or(false);
+            if present {
+                status.battery_level = property("Percentage").and_then(|value| value.get());
+                status.on_battery = property("State")
+                    .and_then(|value| value.get::<u32>())
+                    .is_some_and(|state| {
+                        matches!(state, STATE_DISCHARGING | STATE_PENDING_DISCHARGE)
+                    });
+            }
+        }
+
+        #[cfg(feature = "gnome_42")]
+        {
+            status.power_saver = self.power_profile.is_power_saver_enabled();
+        }
+        #[cfg(not(feature = "gnome_42"))]
+        if let Some(power_profile) = &*self.power_profile.borrow() {
+            status.power_saver = power_profile
+                .cached_property("power-saver-enabled")
+                .and_then(|value| value.get::<bool>())
+                .unwrap_or(false);
+        }
+
+        status
+    }
+
+    fn update(&self) {
+        let status = self.read();
+        if self.status.replace(status) != status {
+            self.subscribers
+                .borrow_mut()
+                .retain(|subscriber| subscriber(&status));
+        }
+    }
+}
+
+/// Returns the current power status.
+///
+/// The battery is only known once UPower was reached,
+/// which happens asynchronously after the first call.
+#[must_use]
+pub fn status() -> PowerStatus {
+    *Monitor::get().status.borrow()
+}
+
+/// Send the current power status and all of its changes.
+///
+/// The subscription ends when the receiver of `sender` is dropped,
+/// for example when the component shuts down.
+/// Must be called from the main thread.
+pub fn subscribe<Msg, F>(sender: &Sender<Msg>, f: F)
+where
+    F: Fn(&PowerStatus) -> Msg + 'static,
+    Msg: 'static,
+{
+    let monitor = Monitor::get();
+    sender.emit(f(&monitor.status.borrow()));
+
+    let sender = sender.clone();
+    monitor
+        .subscribers
+        .borrow_mut()
+        .push(Box::new(move |status| sender.send(f(status)).is_ok()));
+}
+
+/// Prevents the session from suspending, idling, logging out or switching users
+/// while it's alive, see [`gtk::Application::inhibit()`].
+#[must_use = "the inhibition ends when the guard is dropped"]
+pub struct InhibitGuard {
+    application: gtk::Application,
+    cookie: u32,
+}
+
+impl fmt::Debug for InhibitGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InhibitGuard")
+            .field("cookie", &self.cookie)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InhibitGuard {
+    /// Inhibit the given actions of the session for the [`main_application()`](crate::main_application).
+    ///
+    /// The reason is shown to the user, for example when they try to log out.
+    /// Passing the window that requested the inhibition helps the session
+    /// to show it to the user.
+    pub fn new(
+        window: Option<&impl IsA<gtk::Window>>,
+        flags: gtk::ApplicationInhibitFlags,
+        reason: &str,
+    ) -> Self {
+        let application = crate::main_application();
+        let cookie = application.inhibit(window, flags, Some(reason));
+        if cookie == 0 {
+            tracing::debug!("The session doesn't support inhibiting {flags:?}");
+        }
+        Self {
+            application,
+            cookie,
+        }
+    }
+
+    /// Prevent suspending, for example while exporting a file.
+    pub fn suspend(reason: &str) -> Self {
+        Self::new(
+            None::<&gtk::Window>,
+            gtk::ApplicationInhibitFlags::SUSPEND,
+            reason,
+        )
+    }
+
+    /// Prevent idling and suspending, for example while playing a video.
+    pub fn idle(reason: &str) -> Self {
+        Self::new(
+            None::<&gtk::Window>,
+            gtk::ApplicationInhibitFlags::IDLE | gtk::ApplicationInhibitFlags::SUSPEND,
+            reason,
+        )
+    }
+
+    /// Returns `true` if the session accepted the inhibition.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.cookie != 0
+    }
+}
+
+impl Drop for InhibitGuard {
+    fn drop(&mut self) {
+        if self.cookie != 0 {
+            self.application.uninhibit(self.cookie);
+        }
+    }
+}
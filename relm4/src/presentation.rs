@@ -0,0 +1,268 @@
+//! Fullscreen presentation mode for media players, slide shows and similar apps.
+//!
+//! [`PresentationMode`] shows a window in fullscreen mode, hides widgets like
+//! the header bar and inhibits idling while it's active.
+//! The mode also follows the window, so it ends when the window leaves fullscreen
+//! in any other way.
+//!
+//! The stateful `win.fullscreen` action toggles the mode for [`gtk::ApplicationWindow`]s,
+//! so it can be used by buttons, menus and keyboard shortcuts.
+//! An existing `win.fullscreen` action of the window is kept, though.
+//!
+//! ```no_run
+//! # use relm4::gtk;
+//! # use gtk::prelude::GtkApplicationExt;
+//! use relm4::presentation::PresentationMode;
+//!
+//! # let window = gtk::ApplicationWindow::builder().build();
+//! # let header_bar = gtk::HeaderBar::new();
+//! let presentation = PresentationMode::new(&window)
+//!     .hide(&header_bar)
+//!     .inhibit_idle(true);
+//!
+//! relm4::main_application().set_accels_for_action("win.fullscreen", &["F11"]);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+use gtk::prelude::{ActionMapExt, Cast, GtkWindowExt, IsA, ObjectExt, WidgetExt};
+use gtk::{gio, glib};
+
+use crate::power::InhibitGuard;
+use crate::Sender;
+
+/// The name of the action in the `win` group.
+const ACTION_NAME: &str = "fullscreen";
+
+type Subscriber = Box<dyn Fn(bool) -> bool>;
+
+/// Switches a window into fullscreen presentation mode.
+///
+/// Cloning the value gives another handle to the same mode.
+#[derive(Clone)]
+pub struct PresentationMode {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    window: glib::WeakRef<gtk::Window>,
+    action: gio::SimpleAction,
+    /// The action was added to the window and is removed again on drop.
+    registered: Cell<bool>,
+    fullscreened_handler: Cell<Option<glib::SignalHandlerId>>,
+    /// The hidden widgets and whether they were visible before.
+    hidden: RefCell<Vec<(glib::WeakRef<gtk::Widget>, bool)>>,
+    inhibit_idle: Cell<bool>,
+    inhibit: RefCell<Option<InhibitGuard>>,
+    active: Cell<bool>,
+    subscribers: RefCell<Vec<Subscriber>>,
+}
+
+impl fmt::Debug for PresentationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresentationMode")
+            .field("active", &self.inner.active.get())
+            .field("inhibit_idle", &self.inner.inhibit_idle.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PresentationMode {
+    /// Create the presentation mode of `window`.
+    ///
+    /// If the window is a [`gtk::ApplicationWindow`],
+    /// the `win.fullscreen` action is added to it, unless the window already
+    /// has such an action. Otherwise, add [`action()`](Self::action) to an action group yourself.
+    ///
+    /// The action is removed again when the last handle is dropped.
+    #[must_use]
+    pub fn new(window: &impl IsA<gtk::Window>) -> Self {
+        let window = window.as_ref();
+        let action = gio::SimpleAction::new_stateful(ACTION_NAME, None, &false.into());
+
+        let inner = Rc::new(Inner {
+            window: window.downgrade(),
+            action,
+            registered: Cell::new(false),
+            fullscreened_handler: Cell::new(None),
+            hidden: RefCell::default(),
+            inhibit_idle: Cell::new(false),
+            inhibit: RefCell::default(),
+            active: Cell::new(false),
+            subscribers: RefCell::default(),
+        });
+
+        let weak = Rc::downgrade(&inner);
+        inner.action.connect_change_state(move |_, state| {
+            if let (Some(inner), Some(active)) =
+                (weak.upgrade(), state.and_then(|s| s.get::<bool>()))
+            {
+                inner.set_active(active);
+            }
+        });
+
+        let weak = Rc::downgrade(&inner);
+        let handler = window.connect_fullscreened_notify(move |window| {
+            if let Some(inner) = weak.upgrade() {
+                inner.update(window.is_fullscreen());
+            }
+        });
+        inner.fullscreened_handler.set(Some(handler));
+
+        if let Some(window) = window.downcast_ref::<gtk::ApplicationWindow>() {
+            if window.lookup_action(ACTION_NAME).is_some() {
+                tracing::warn!("Keeping the existing `win.{ACTION_NAME}` action of the window");
+            } else {
+                window.add_action(&inner.action);
+                inner.registered.set(true);
+            }
+        }
+
+        Self { inner }
+    }
+
+    /// Hide `widget` while the mode is active, for example the header bar.
+    ///
+    /// The widget gets its previous visibility back afterwards.
+    #[must_use]
+    pub fn hide(self, widget: &impl IsA<gtk::Widget>) -> Self {
+        let widget = widget.as_ref();
+        let visible = widget.is_visible();
+        if self.inner.active.get() {
+            widget.set_visible(false);
+        }
+        self.inner
+            .hidden
+            .borrow_mut()
+            .push((widget.downgrade(), visible));
+        self
+    }
+
+    /// Prevent the session from idling and locking the screen while the mode is active.
+    #[must_use]
+    pub fn inhibit_idle(self, inhibit: bool) -> Self {
+        self.inner.inhibit_idle.set(inhibit);
+        self.inner.update_inhibit();
+        self
+    }
+
+    /// The stateful `fullscreen` action that toggles the mode.
+    #[must_use]
+    pub fn action(&self) -> gio::SimpleAction {
+        self.inner.action.clone()
+    }
+
+    /// Returns `true` if the mode is active.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.inner.active.get()
+    }
+
+    /// Enter or leave the mode.
+    pub fn set_active(&self, active: bool) {
+        self.inner.set_active(active);
+    }
+
+    /// Enter the mode if it's inactive or leave it otherwise.
+    pub fn toggle(&self) {
+        self.set_active(!self.is_active());
+    }
+
+    /// Send whether the mode is active and all changes of it,
+    /// so components can adapt their layout.
+    ///
+    /// The subscription ends when the receiver of `sender` is dropped,
+    /// for example when the component shuts down.
+    pub fn subscribe<Msg, F>(&self, sender: &Sender<Msg>, f: F)
+    where
+        F: Fn(bool) -> Msg + 'static,
+        Msg: 'static,
+    {
+        sender.emit(f(self.is_active()));
+
+        let sender = sender.clone();
+        self.inner
+            .subscribers
+            .borrow_mut()
+            .push(Box::new(move |active| sender.send(f(active)).is_ok()));
+    }
+}
+
+impl Inner {
+    fn set_active(&self, active: bool) {
+        let Some(window) = self.window.upgrade() else {
+            return;
+        };
+
+        // The changes are applied once the window was (un)fullscreened.
+        if active {
+            window.fullscreen();
+        } else {
+            window.unfullscreen();
+        }
+    }
+
+    fn update(&self, active: bool) {
+        if self.active.replace(active) == active {
+            return;
+        }
+
+        self.action.set_state(&active.into());
+
+        for (widget, visible) in &mut *self.hidden.borrow_mut() {
+            if let Some(widget) = widget.upgrade() {
+                if active {
+                    *visible = widget.is_visible();
+                    widget.set_visible(false);
+                } else {
+                    widget.set_visible(*visible);
+                }
+            }
+        }
+
+        self.update_inhibit();
+
+        self.subscribers
+            .borrow_mut()
+            .retain(|subscriber| subscriber(active));
+    }
+
+    fn update_inhibit(&self) {
+        let inhibit = self.active.get() && self.inhibit_idle.get();
+        let mut guard = self.inhibit.borrow_mut();
+        if inhibit && guard.is_none() {
+            let window = self.window.upgrade();
+            *guard = Some(InhibitGuard::new(
+                window.as_ref(),
+                gtk::ApplicationInhibitFlags::IDLE,
+                "Presentation",
+            ));
+        } else if !inhibit {
+            *guard = None;
+        }
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let Some(window) = self.window.upgrade() else {
+            return;
+        };
+
+        if let Some(handler) = self.fullscreened_handler.take() {
+            window.disconnect(handler);
+        }
+
+        if self.registered.get() {
+            if let Some(window) = window.downcast_ref::<gtk::ApplicationWindow>() {
+                // Don't remove an action that was added by someone else in the meantime.
+                let action = window.lookup_action(ACTION_NAME);
+                if action.as_ref() == Some(self.action.upcast_ref()) {
+                    window.remove_action(ACTION_NAME);
+                }
+            }
+        }
+    }
+}
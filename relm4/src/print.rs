@@ -0,0 +1,583 @@
+//! Printing with [`gtk::PrintOperation`].
+//!
+//! A [`PrintJob`] draws its pages with cairo or into a [`gtk::Snapshot`].
+//! It can be sent to a printer with [`PrintJob::run()`], which reports the progress
+//! with [`PrintProgress`] updates, or shown in a [`PrintPreview`] first:
+//!
+//! ```no_run
+//! # use relm4::print::{PageInfo, PrintJob};
+//! let job = PrintJob::new(|cr, page: &PageInfo| {
+//!     cr.move_to(0.0, 20.0);
+//!     cr.show_text(&format!("Page {} of {}", page.page + 1, page.pages)).ok();
+//! })
+//! .title("Report")
+//! .n_pages(3)
+//! .persist("report");
+//!
+//! job.run(None, |progress| println!("{progress:?}")).unwrap();
+//! ```
+//!
+//! Jobs with a key passed to [`PrintJob::persist()`] remember the page setup and
+//! print settings, so the dialog starts with the choices the user made last time.
+//! They are stored in the configuration directory of the user.
+
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use gtk::prelude::{
+    BoxExt, ButtonExt, DrawingAreaExtManual, GtkWindowExt, PrintOperationExt, SnapshotExt,
+    WidgetExt,
+};
+use gtk::{cairo, glib};
+
+use crate::{Component, ComponentParts, ComponentSender};
+
+type DrawFn = Rc<dyn Fn(&cairo::Context, &PageInfo)>;
+type PaginateFn = Rc<dyn Fn(f64, f64) -> usize>;
+
+/// The page that is drawn.
+///
+/// Sizes are in points and describe the printable area inside the margins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageInfo {
+    /// The index of the page, starting at 0.
+    pub page: usize,
+    /// The number of pages.
+    pub pages: usize,
+    /// The width of the printable area.
+    pub width: f64,
+    /// The height of the printable area.
+    pub height: f64,
+}
+
+/// The progress of a [`PrintJob`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PrintProgress {
+    /// The print dialog was confirmed and the job is set up.
+    Preparing,
+    /// A page is drawn.
+    Rendering {
+        /// The index of the page, starting at 0.
+        page: usize,
+        /// The number of pages.
+        pages: usize,
+    },
+    /// The document is sent to the printer.
+    Sending,
+    /// The job was sent and is handled by the printer.
+    Finished,
+    /// The user cancelled the dialog.
+    Cancelled,
+    /// Printing failed.
+    Failed(String),
+}
+
+/// A document that can be printed, see the [module documentation](self).
+#[derive(Clone)]
+#[must_use]
+pub struct PrintJob {
+    title: Option<String>,
+    n_pages: usize,
+    paginate: Option<PaginateFn>,
+    key: Option<String>,
+    draw: DrawFn,
+}
+
+impl Debug for PrintJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrintJob")
+            .field("title", &self.title)
+            .field("n_pages", &self.n_pages)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PrintJob {
+    /// Creates a job with a single page that draws its pages with cairo.
+    ///
+    /// The origin of the cairo context is the top left corner of the printable area.
+    pub fn new<F>(draw: F) -> Self
+    where
+        F: Fn(&cairo::Context, &PageInfo) + 'static,
+    {
+        Self {
+            title: None,
+            n_pages: 1,
+            paginate: None,
+            key: None,
+            draw: Rc::new(draw),
+        }
+    }
+
+    /// Creates a job with a single page that draws its pages into a [`gtk::Snapshot`].
+    pub fn from_snapshot<F>(draw: F) -> Self
+    where
+        F: Fn(&gtk::Snapshot, &PageInfo) + 'static,
+    {
+        Self::new(move |cr, page| {
+            let snapshot = gtk::Snapshot::new();
+            draw(&snapshot, page);
+            if let Some(node) = snapshot.to_node() {
+                node.draw(cr);
+            }
+        })
+    }
+
+    /// Sets the name of the job, which is shown by the printing system.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the number of pages.
+    pub fn n_pages(mut self, n_pages: usize) -> Self {
+        self.n_pages = n_pages;
+        self
+    }
+
+    /// Calculates the number of pages from the size of the printable area,
+    /// once the user selected the paper.
+    pub fn paginate<F>(mut self, paginate: F) -> Self
+    where
+        F: Fn(f64, f64) -> usize + 'static,
+    {
+        self.paginate = Some(Rc::new(paginate));
+        self
+    }
+
+    /// Remembers the page setup and print settings under `key`.
+    pub fn persist(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Returns the number of pages for a printable area of the given size.
+    #[must_use]
+    pub fn page_count(&self, width: f64, height: f64) -> usize {
+        self.paginate
+            .as_ref()
+            .map_or(self.n_pages, |paginate| paginate(width, height))
+            .max(1)
+    }
+
+    /// Draws a page.
+    pub fn draw_page(&self, cr: &cairo::Context, page: &PageInfo) {
+        (self.draw)(cr, page);
+    }
+
+    /// Returns the page setup this job starts with.
+    #[must_use]
+    pub fn page_setup(&self) -> gtk::PageSetup {
+        self.key
+            .as_deref()
+            .map_or_else(gtk::PageSetup::new, page_setup)
+    }
+
+    /// Shows the print dialog and prints the job.
+    ///
+    /// The dialog doesn't block, `on_progress` is called as printing proceeds
+    /// and receives [`PrintProgress::Finished`], [`PrintProgress::Cancelled`]
+    /// or [`PrintProgress::Failed`] last.
+    pub fn run<F>(&self, parent: Option<&gtk::Window>, on_progress: F) -> Result<(), glib::Error>
+    where
+        F: Fn(PrintProgress) + 'static,
+    {
+        let operation = gtk::PrintOperation::new();
+        operation.set_allow_async(true);
+        operation.set_unit(gtk::Unit::Points);
+        operation.set_embed_page_setup(true);
+        if let Some(title) = &self.title {
+            operation.set_job_name(title);
+        }
+        operation.set_default_page_setup(Some(&self.page_setup()));
+        if let Some(key) = &self.key {
+            operation.set_print_settings(
+                gtk::PrintSettings::from_file(path(key, "settings"))
+                    .ok()
+                    .as_ref(),
+            );
+        }
+
+        let on_progress = Rc::new(on_progress);
+        let pages = Rc::new(Cell::new(1));
+
+        {
+            let job = self.clone();
+            let pages = pages.clone();
+            operation.connect_begin_print(move |operation, context| {
+                let count = job.page_count(context.width(), context.height());
+                pages.set(count);
+                operation.set_n_pages(i32::try_from(count).unwrap_or(i32::MAX));
+            });
+        }
+        {
+            let job = self.clone();
+            let on_progress = on_progress.clone();
+            operation.connect_draw_page(move |_, context, page| {
+                let page = PageInfo {
+                    page: usize::try_from(page).unwrap_or_default(),
+                    pages: pages.get(),
+                    width: context.width(),
+                    height: context.height(),
+                };
+                on_progress(PrintProgress::Rendering {
+                    page: page.page,
+                    pages: page.pages,
+                });
+                job.draw_page(&context.cairo_context(), &page);
+            });
+        }
+        {
+            let on_progress = on_progress.clone();
+            operation.connect_status_changed(move |operation| match operation.status() {
+                gtk::PrintStatus::Preparing => on_progress(PrintProgress::Preparing),
+                gtk::PrintStatus::SendingData => on_progress(PrintProgress::Sending),
+                _ => {}
+            });
+        }
+        {
+            let key = self.key.clone();
+            operation.connect_done(move |operation, result| match result {
+                gtk::PrintOperationResult::Apply => {
+                    if let Some(key) = &key {
+                        save(key, operation);
+                    }
+                    on_progress(PrintProgress::Finished);
+                }
+                gtk::PrintOperationResult::Cancel => on_progress(PrintProgress::Cancelled),
+                gtk::PrintOperationResult::Error => {
+                    let error = operation.error().map(|error| error.to_string());
+                    on_progress(PrintProgress::Failed(error.unwrap_or_default()));
+                }
+                _ => {}
+            });
+        }
+
+        operation.run(gtk::PrintOperationAction::PrintDialog, parent)?;
+        Ok(())
+    }
+}
+
+/// Returns the page setup that was saved under `key` or the default page setup.
+#[must_use]
+pub fn page_setup(key: &str) -> gtk::PageSetup {
+    gtk::PageSetup::from_file(path(key, "page-setup")).unwrap_or_default()
+}
+
+/// Shows the page setup dialog and saves the result under `key`.
+pub fn run_page_setup_dialog<F>(parent: Option<&gtk::Window>, key: &str, done: F)
+where
+    F: FnOnce(&gtk::PageSetup) + 'static,
+{
+    let settings = gtk::PrintSettings::from_file(path(key, "settings")).unwrap_or_default();
+    // The callback is always called on the main thread.
+    let done = glib::thread_guard::ThreadGuard::new(done);
+    let key = key.to_owned();
+    gtk::print_run_page_setup_dialog_async(
+        parent,
+        Some(&page_setup(&key)),
+        &settings,
+        move |page_setup| {
+            save_page_setup(&key, page_setup);
+            (done.into_inner())(page_setup);
+        },
+    );
+}
+
+fn path(key: &str, extension: &str) -> PathBuf {
+    let application = glib::prgname().map_or_else(|| "relm4".to_owned(), |name| name.to_string());
+    glib::user_config_dir()
+        .join(application)
+        .join("print")
+        .join(format!("{key}.{extension}"))
+}
+
+fn save_page_setup(key: &str, page_setup: &gtk::PageSetup) {
+    let path = path(key, "page-setup");
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    if let Err(error) = page_setup.to_file(path) {
+        tracing::warn!("Couldn't save page setup: {error}");
+    }
+}
+
+fn save(key: &str, operation: &gtk::PrintOperation) {
+    save_page_setup(key, &operation.default_page_setup());
+    if let Some(settings) = operation.print_settings() {
+        if let Err(error) = settings.to_file(path(key, "settings")) {
+            tracing::warn!("Couldn't save print settings: {error}");
+        }
+    }
+}
+
+/// Messages of a [`PrintPreview`].
+#[derive(Debug)]
+pub enum PrintPreviewMsg {
+    /// Show the previous page.
+    PreviousPage,
+    /// Show the next page.
+    NextPage,
+    /// Show the page setup dialog.
+    PageSetup,
+    /// Show the print dialog and close the preview.
+    Print,
+    /// Close the preview.
+    Close,
+    #[doc(hidden)]
+    PageSetupChanged(gtk::PageSetup),
+}
+
+/// Messages that are sent by a [`PrintPreview`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrintPreviewOutput {
+    /// The progress of the job, after it was printed from the preview.
+    Progress(PrintProgress),
+    /// The preview was closed.
+    Closed,
+}
+
+/// A dialog that shows the pages of a [`PrintJob`] before they are printed.
+///
+/// The dialog is shown when the component is initialized.
+#[derive(Debug)]
+pub struct PrintPreview {
+    job: PrintJob,
+    page_setup: gtk::PageSetup,
+    page: Rc<Cell<usize>>,
+    pages: usize,
+}
+
+/// The widgets of a [`PrintPreview`].
+#[derive(Debug)]
+pub struct PrintPreviewWidgets {
+    area: gtk::DrawingArea,
+    label: gtk::Label,
+    previous: gtk::Button,
+    next: gtk::Button,
+}
+
+impl PrintPreview {
+    fn paginate(&mut self) {
+        self.pages = self.job.page_count(
+            self.page_setup.page_width(gtk::Unit::Points),
+            self.page_setup.page_height(gtk::Unit::Points),
+        );
+        self.page.set(self.page.get().min(self.pages - 1));
+    }
+}
+
+impl Component for PrintPreview {
+    type CommandOutput = ();
+    type Input = PrintPreviewMsg;
+    type Output = PrintPreviewOutput;
+    type Init = PrintJob;
+    type Root = gtk::Window;
+    type Widgets = PrintPreviewWidgets;
+
+    fn init_root() -> Self::Root {
+        gtk::Window::builder()
+            .title("Print Preview")
+            .modal(true)
+            .default_width(600)
+            .default_height(800)
+            .build()
+    }
+
+    fn init(
+        job: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut model = Self {
+            page_setup: job.page_setup(),
+            job,
+            page: Rc::default(),
+            pages: 1,
+        };
+        model.paginate();
+
+        let previous = gtk::Button::from_icon_name("go-previous-symbolic");
+        let next = gtk::Button::from_icon_name("go-next-symbolic");
+        let label = gtk::Label::new(None);
+        let page_setup = gtk::Button::with_label("Page Setup");
+        let print = gtk::Button::with_label("Print");
+        print.add_css_class("suggested-action");
+
+        let navigation = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        navigation.append(&previous);
+        navigation.append(&label);
+        navigation.append(&next);
+
+        let header = gtk::HeaderBar::new();
+        header.pack_start(&navigation);
+        header.pack_end(&print);
+        header.pack_end(&page_setup);
+        root.set_titlebar(Some(&header));
+
+        let area = gtk::DrawingArea::new();
+        root.set_child(Some(&area));
+
+        {
+            let job = model.job.clone();
+            let page = model.page.clone();
+            let page_setup = model.page_setup.clone();
+            let pages = model.pages;
+            area.set_draw_func(move |_, cr, width, height| {
+                draw_preview(&job, &page_setup, page.get(), pages, cr, width, height);
+            });
+        }
+
+        {
+            let sender = sender.clone();
+            previous.connect_clicked(move |_| sender.input(PrintPreviewMsg::PreviousPage));
+        }
+        {
+            let sender = sender.clone();
+            next.connect_clicked(move |_| sender.input(PrintPreviewMsg::NextPage));
+        }
+        {
+            let sender = sender.clone();
+            page_setup.connect_clicked(move |_| sender.input(PrintPreviewMsg::PageSetup));
+        }
+        {
+            let sender = sender.clone();
+            print.connect_clicked(move |_| sender.input(PrintPreviewMsg::Print));
+        }
+        root.connect_close_request(move |_| {
+            sender.input(PrintPreviewMsg::Close);
+            glib::Propagation::Stop
+        });
+
+        let widgets = PrintPreviewWidgets {
+            area,
+            label,
+            previous,
+            next,
+        };
+        model.update_view(&widgets);
+        root.present();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            PrintPreviewMsg::PreviousPage => {
+                self.page.set(self.page.get().saturating_sub(1));
+            }
+            PrintPreviewMsg::NextPage => {
+                self.page.set((self.page.get() + 1).min(self.pages - 1));
+            }
+            PrintPreviewMsg::PageSetup => {
+                let key = self.job.key.clone().unwrap_or_default();
+                let sender = sender.clone();
+                run_page_setup_dialog(Some(root), &key, move |page_setup| {
+                    sender.input(PrintPreviewMsg::PageSetupChanged(page_setup.clone()));
+                });
+            }
+            PrintPreviewMsg::PageSetupChanged(page_setup) => {
+                self.page_setup = page_setup;
+                self.paginate();
+                let job = self.job.clone();
+                let page = self.page.clone();
+                let page_setup = self.page_setup.clone();
+                let pages = self.pages;
+                widgets.area.set_draw_func(move |_, cr, width, height| {
+                    draw_preview(&job, &page_setup, page.get(), pages, cr, width, height);
+                });
+            }
+            PrintPreviewMsg::Print => {
+                let output = sender.output_sender().clone();
+                let result = self
+                    .job
+                    .run(root.transient_for().as_ref(), move |progress| {
+                        output.emit(PrintPreviewOutput::Progress(progress));
+                    });
+                if let Err(error) = result {
+                    sender
+                        .output(PrintPreviewOutput::Progress(PrintProgress::Failed(
+                            error.to_string(),
+                        )))
+                        .ok();
+                }
+                root.set_visible(false);
+            }
+            PrintPreviewMsg::Close => {
+                root.set_visible(false);
+                sender.output(PrintPreviewOutput::Closed).ok();
+            }
+        }
+        self.update_view(widgets);
+    }
+}
+
+impl PrintPreview {
+    fn update_view(&self, widgets: &PrintPreviewWidgets) {
+        let page = self.page.get();
+        widgets
+            .label
+            .set_label(&format!("{} / {}", page + 1, self.pages));
+        widgets.previous.set_sensitive(page > 0);
+        widgets.next.set_sensitive(page + 1 < self.pages);
+        widgets.area.queue_draw();
+    }
+}
+
+/// Draws a page centered on a gray background.
+fn draw_preview(
+    job: &PrintJob,
+    page_setup: &gtk::PageSetup,
+    page: usize,
+    pages: usize,
+    cr: &cairo::Context,
+    width: i32,
+    height: i32,
+) {
+    const MARGIN: f64 = 24.0;
+
+    let paper_width = page_setup.paper_width(gtk::Unit::Points);
+    let paper_height = page_setup.paper_height(gtk::Unit::Points);
+    let scale = ((f64::from(width) - 2.0 * MARGIN) / paper_width)
+        .min((f64::from(height) - 2.0 * MARGIN) / paper_height);
+    if scale <= 0.0 {
+        return;
+    }
+
+    cr.set_source_rgb(0.5, 0.5, 0.5);
+    cr.paint().ok();
+
+    cr.translate(
+        (f64::from(width) - paper_width * scale) / 2.0,
+        (f64::from(height) - paper_height * scale) / 2.0,
+    );
+    cr.scale(scale, scale);
+    cr.rectangle(0.0, 0.0, paper_width, paper_height);
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.fill().ok();
+
+    let info = PageInfo {
+        page,
+        pages,
+        width: page_setup.page_width(gtk::Unit::Points),
+        height: page_setup.page_height(gtk::Unit::Points),
+    };
+    cr.translate(
+        page_setup.left_margin(gtk::Unit::Points),
+        page_setup.top_margin(gtk::Unit::Points),
+    );
+    cr.rectangle(0.0, 0.0, info.width, info.height);
+    cr.clip();
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    job.draw_page(cr, &info);
+}
@@ -8,7 +8,7 @@ use tokio::sync::mpsc;
 
 use crate::{
     shutdown::{self, ShutdownSender},
-    Receiver, Sender, ShutdownReceiver,
+    CoalescedInputs, PriorityReceiver, Receiver, Sender, ShutdownReceiver,
 };
 
 /// Stores the shutdown senders of all components ever created during
@@ -181,6 +181,11 @@ where
             sender_dropped: false,
         }
     }
+
+    /// Returns [`true`] if no messages are queued.
+    fn is_empty(&self) -> bool {
+        self.receive_stream.is_empty()
+    }
 }
 
 impl<'a, T> Future for GuardedReceiver<'a, T>
@@ -216,3 +221,149 @@ impl<'a, T> FusedFuture for GuardedReceiver<'a, T> {
         self.sender_dropped
     }
 }
+
+/// Number of messages with a higher priority that are processed in a row
+/// before a queued message with a lower priority gets its turn.
+const STARVATION_LIMIT: usize = 8;
+
+/// Index of the level of [`InputPriority::Normal`](crate::InputPriority::Normal) messages.
+const NORMAL_LEVEL: usize = 1;
+
+/// Receives the input messages of a component from all priority levels.
+///
+/// Messages with a higher priority are yielded first, but a level
+/// that was passed over [`STARVATION_LIMIT`] times while it had queued
+/// messages is served before all other levels.
+/// Coalesced messages are part of the level of normal messages.
+pub(super) struct InputReceiver<'a, T>
+where
+    T: 'static,
+{
+    /// The receivers ordered from the highest to the lowest priority.
+    levels: [GuardedReceiver<'a, T>; 3],
+    /// How often a queued message of each level was passed over.
+    passed_over: [usize; 3],
+    /// Yields the keys of coalesced messages.
+    keys: GuardedReceiver<'a, u64>,
+    coalesced: CoalescedInputs<T>,
+}
+
+impl<'a, T> InputReceiver<'a, T>
+where
+    T: 'static,
+{
+    pub(super) fn new(normal: Receiver<T>, priority: PriorityReceiver<T>) -> Self {
+        Self {
+            levels: [
+                GuardedReceiver::new(priority.high),
+                GuardedReceiver::new(normal),
+                GuardedReceiver::new(priority.low),
+            ],
+            passed_over: [0; 3],
+            keys: GuardedReceiver::new(priority.keys),
+            coalesced: priority.coalesced,
+        }
+    }
+
+    fn is_level_empty(&self, level: usize) -> bool {
+        self.levels[level].is_empty() && (level != NORMAL_LEVEL || self.keys.is_empty())
+    }
+
+    fn poll_level(&mut self, level: usize, cx: &mut std::task::Context<'_>) -> Poll<T> {
+        let poll = std::pin::Pin::new(&mut self.levels[level]).poll(cx);
+        if poll.is_ready() || level != NORMAL_LEVEL {
+            return poll;
+        }
+
+        while let Poll::Ready(key) = std::pin::Pin::new(&mut self.keys).poll(cx) {
+            let mut coalesced = self.coalesced.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(value) = coalesced.remove(&key) {
+                return Poll::Ready(value);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<'a, T> Future for InputReceiver<'a, T>
+where
+    T: 'static,
+{
+    type Output = T;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let passed_over = this.passed_over;
+        // Starving levels first, starting with the lowest priority.
+        let starving = (0..3)
+            .rev()
+            .filter(|&level| passed_over[level] >= STARVATION_LIMIT);
+
+        for level in starving.chain(0..3) {
+            if let Poll::Ready(value) = this.poll_level(level, cx) {
+                this.passed_over[level] = 0;
+                for lower in level + 1..3 {
+                    if !this.is_level_empty(lower) {
+                        this.passed_over[lower] += 1;
+                    }
+                }
+                return Poll::Ready(value);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<'a, T> FusedFuture for InputReceiver<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.levels.iter().all(FusedFuture::is_terminated) && self.keys.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{InputReceiver, STARVATION_LIMIT};
+
+    #[test]
+    fn input_priority() {
+        let (normal, normal_receiver) = crate::channel();
+        let (priority, priority_receiver) = crate::priority_channel();
+        let mut input = InputReceiver::new(normal_receiver, priority_receiver);
+
+        normal.send(1).unwrap();
+        normal.send(2).unwrap();
+        priority.low.send(3).unwrap();
+        priority.high.send(0).unwrap();
+        let received: Vec<i32> = (0..4).map(|_| block_on(&mut input)).collect();
+        assert_eq!(received, [0, 1, 2, 3]);
+
+        priority.low.send(-1).unwrap();
+        for value in 0..20 {
+            priority.high.send(value).unwrap();
+        }
+        let received: Vec<i32> = (0..=STARVATION_LIMIT)
+            .map(|_| block_on(&mut input))
+            .collect();
+        assert_eq!(received.last(), Some(&-1));
+    }
+
+    #[test]
+    fn input_coalesced() {
+        let (_normal, normal_receiver) = crate::channel();
+        let (priority, priority_receiver) = crate::priority_channel();
+        let mut input = InputReceiver::new(normal_receiver, priority_receiver);
+
+        priority.send_coalesced("a", 1).unwrap();
+        priority.send_coalesced("b", 2).unwrap();
+        priority.send_coalesced("a", 3).unwrap();
+        assert_eq!(block_on(&mut input), 3);
+        priority.send_coalesced("a", 4).unwrap();
+        assert_eq!(block_on(&mut input), 2);
+        assert_eq!(block_on(&mut input), 4);
+    }
+}
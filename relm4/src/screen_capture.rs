@@ -0,0 +1,440 @@
+//! Screenshots and screencasts through the XDG desktop portal.
+//!
+//! The [Screenshot](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Screenshot.html)
+//! and [ScreenCast](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.ScreenCast.html)
+//! portals let the user choose what to capture, so they work on Wayland and in sandboxes.
+//! [`request_screenshot()`] and [`request_screencast()`] send the result to a component:
+//!
+//! ```no_run
+//! use relm4::screen_capture::{self, CaptureError, Screenshot, ScreenshotOptions};
+//!
+//! #[derive(Debug)]
+//! enum Msg {
+//!     Captured(Result<Screenshot, CaptureError>),
+//! }
+//!
+//! # fn update(sender: relm4::Sender<Msg>) {
+//! screen_capture::request_screenshot(ScreenshotOptions::default(), &sender, Msg::Captured);
+//! # }
+//! ```
+//!
+//! A [`Screencast`] only gives access to the PipeWire streams of the chosen sources.
+//! They can be recorded with the `ScreenRecorder` component of `relm4-components`
+//! or consumed with GStreamer's `pipewiresrc` or PipeWire directly.
+
+use std::error::Error;
+use std::fmt;
+use std::os::fd::{AsFd, BorrowedFd, FromRawFd, OwnedFd};
+
+use gtk::gdk;
+use gtk::gio::{self, prelude::UnixFDListExtManual};
+use gtk::glib::prelude::ToVariant;
+use gtk::glib::{self, Variant, VariantDict};
+
+use crate::portal::{self, Response};
+use crate::Sender;
+
+const SCREENSHOT_INTERFACE: &str = "org.freedesktop.portal.Screenshot";
+const SCREENCAST_INTERFACE: &str = "org.freedesktop.portal.ScreenCast";
+
+/// An error that occurred while capturing the screen.
+#[derive(Debug)]
+pub enum CaptureError {
+    /// The desktop doesn't provide the portal.
+    Unsupported,
+    /// The user cancelled the capture.
+    Cancelled,
+    /// The portal failed.
+    Portal(glib::Error),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => f.write_str("screen capture is not supported"),
+            Self::Cancelled => f.write_str("the capture was cancelled"),
+            Self::Portal(error) => write!(f, "the portal failed: {error}"),
+        }
+    }
+}
+
+impl Error for CaptureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Portal(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<glib::Error> for CaptureError {
+    fn from(error: glib::Error) -> Self {
+        if portal::is_unsupported(&error) {
+            Self::Unsupported
+        } else {
+            Self::Portal(error)
+        }
+    }
+}
+
+/// The options of a screenshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScreenshotOptions {
+    /// Let the user choose the area, window or monitor and confirm the screenshot.
+    pub interactive: bool,
+    /// Make the dialog of the portal modal.
+    pub modal: bool,
+}
+
+impl ScreenshotOptions {
+    /// Let the user choose what to capture.
+    #[must_use]
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Make the dialog of the portal modal.
+    #[must_use]
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+}
+
+/// A captured screenshot.
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    /// The URI of the image file, which is owned by the application.
+    pub uri: String,
+    /// The image.
+    pub texture: gdk::Texture,
+}
+
+/// Take a screenshot.
+///
+/// Must be called from the main thread.
+pub async fn screenshot(options: ScreenshotOptions) -> Result<Screenshot, CaptureError> {
+    let connection = gio::bus_get_future(gio::BusType::Session).await?;
+
+    let results = request(&connection, SCREENSHOT_INTERFACE, "Screenshot", |token| {
+        let dict = portal::request_options(token);
+        dict.insert_value("interactive", &options.interactive.to_variant());
+        dict.insert_value("modal", &options.modal.to_variant());
+        vec!["".to_variant(), dict.end()]
+    })
+    .await?;
+    let uri = results
+        .lookup::<String>("uri")
+        .ok()
+        .flatten()
+        .ok_or(CaptureError::Unsupported)?;
+
+    let texture = gdk::Texture::from_file(&gio::File::for_uri(&uri))?;
+    Ok(Screenshot { uri, texture })
+}
+
+/// Take a screenshot and send the result to `sender`.
+///
+/// Must be called from the main thread.
+pub fn request_screenshot<Msg, F>(options: ScreenshotOptions, sender: &Sender<Msg>, f: F)
+where
+    F: FnOnce(Result<Screenshot, CaptureError>) -> Msg + 'static,
+    Msg: 'static,
+{
+    let sender = sender.clone();
+    crate::spawn_local(async move {
+        // Ignore send errors because the component might be shut down.
+        sender.send(f(screenshot(options).await)).ok();
+    });
+}
+
+/// The kinds of sources the user can choose for a screencast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureSources {
+    /// Whole monitors.
+    pub monitor: bool,
+    /// Single windows.
+    pub window: bool,
+}
+
+impl CaptureSources {
+    /// Only monitors.
+    pub const MONITOR: Self = Self {
+        monitor: true,
+        window: false,
+    };
+    /// Only windows.
+    pub const WINDOW: Self = Self {
+        monitor: false,
+        window: true,
+    };
+    /// Monitors and windows.
+    pub const ALL: Self = Self {
+        monitor: true,
+        window: true,
+    };
+
+    fn bits(self) -> u32 {
+        u32::from(self.monitor) | u32::from(self.window) << 1
+    }
+}
+
+impl Default for CaptureSources {
+    fn default() -> Self {
+        Self::MONITOR
+    }
+}
+
+/// How the cursor appears in a screencast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorMode {
+    /// The cursor isn't visible.
+    Hidden,
+    /// The cursor is drawn into the stream.
+    #[default]
+    Embedded,
+    /// The cursor is sent as metadata of the stream.
+    Metadata,
+}
+
+impl CursorMode {
+    fn bits(self) -> u32 {
+        match self {
+            Self::Hidden => 1,
+            Self::Embedded => 2,
+            Self::Metadata => 4,
+        }
+    }
+}
+
+/// The options of a screencast.
+///
+/// The default lets the user choose a single monitor and embeds the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScreencastOptions {
+    /// The kinds of sources the user can choose.
+    pub sources: CaptureSources,
+    /// Let the user choose more than one source.
+    pub multiple: bool,
+    /// How the cursor appears.
+    pub cursor_mode: CursorMode,
+}
+
+impl ScreencastOptions {
+    /// Set the kinds of sources the user can choose.
+    #[must_use]
+    pub fn sources(mut self, sources: CaptureSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Let the user choose more than one source.
+    #[must_use]
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Set how the cursor appears.
+    #[must_use]
+    pub fn cursor_mode(mut self, cursor_mode: CursorMode) -> Self {
+        self.cursor_mode = cursor_mode;
+        self
+    }
+}
+
+/// A PipeWire stream of a screencast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreencastStream {
+    /// The id of the PipeWire node, for example the `path` of `pipewiresrc`.
+    pub node_id: u32,
+    /// The position of the source in the compositor space, if it's known.
+    pub position: Option<(i32, i32)>,
+    /// The size of the stream, if it's known.
+    pub size: Option<(i32, i32)>,
+}
+
+impl ScreencastStream {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let node_id = variant.child_value(0).get::<u32>()?;
+        let properties = VariantDict::new(Some(&variant.child_value(1)));
+        Some(Self {
+            node_id,
+            position: properties.lookup("position").ok().flatten(),
+            size: properties.lookup("size").ok().flatten(),
+        })
+    }
+}
+
+/// A running screencast, which is stopped when this is dropped.
+#[must_use = "the screencast is stopped when this is dropped"]
+pub struct Screencast {
+    connection: gio::DBusConnection,
+    session: String,
+    streams: Vec<ScreencastStream>,
+    fd: OwnedFd,
+}
+
+impl fmt::Debug for Screencast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Screencast")
+            .field("session", &self.session)
+            .field("streams", &self.streams)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Screencast {
+    fn drop(&mut self) {
+        portal::close_session(&self.connection, &self.session);
+    }
+}
+
+impl Screencast {
+    /// The streams of the sources the user chose.
+    #[must_use]
+    pub fn streams(&self) -> &[ScreencastStream] {
+        &self.streams
+    }
+
+    /// The file descriptor of the PipeWire remote that gives access to the streams,
+    /// for example the `fd` of `pipewiresrc`.
+    #[must_use]
+    pub fn pipewire_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+/// Let the user choose sources and start a screencast of them.
+///
+/// Must be called from the main thread.
+pub async fn screencast(options: ScreencastOptions) -> Result<Screencast, CaptureError> {
+    let connection = gio::bus_get_future(gio::BusType::Session).await?;
+
+    let results = request(
+        &connection,
+        SCREENCAST_INTERFACE,
+        "CreateSession",
+        |token| {
+            let dict = portal::request_options(token);
+            dict.insert_value("session_handle_token", &token.to_variant());
+            vec![dict.end()]
+        },
+    )
+    .await?;
+    let session = portal::session_handle(&results).ok_or(CaptureError::Unsupported)?;
+    let session_path = glib::variant::ObjectPath::try_from(session.as_str())
+        .map_err(|_| CaptureError::Unsupported)?;
+
+    // Close the session if one of the following steps fails.
+    let guard = SessionGuard {
+        connection: &connection,
+        session: &session,
+    };
+
+    request(
+        &connection,
+        SCREENCAST_INTERFACE,
+        "SelectSources",
+        |token| {
+            let dict = portal::request_options(token);
+            dict.insert_value("types", &options.sources.bits().to_variant());
+            dict.insert_value("multiple", &options.multiple.to_variant());
+            dict.insert_value("cursor_mode", &options.cursor_mode.bits().to_variant());
+            vec![session_path.to_variant(), dict.end()]
+        },
+    )
+    .await?;
+
+    let results = request(&connection, SCREENCAST_INTERFACE, "Start", |token| {
+        vec![
+            session_path.to_variant(),
+            "".to_variant(),
+            portal::request_options(token).end(),
+        ]
+    })
+    .await?;
+    let streams = results
+        .lookup_value("streams", None)
+        .map(|streams| {
+            streams
+                .iter()
+                .filter_map(|stream| ScreencastStream::from_variant(&stream))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (result, fd_list) = connection
+        .call_with_unix_fd_list_future(
+            Some(portal::PORTAL_NAME),
+            portal::PORTAL_PATH,
+            SCREENCAST_INTERFACE,
+            "OpenPipeWireRemote",
+            Some(&Variant::tuple_from_iter([
+                session_path.to_variant(),
+                VariantDict::new(None).end(),
+            ])),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::UnixFDList>,
+        )
+        .await?;
+    let index = result
+        .child_value(0)
+        .get::<glib::variant::Handle>()
+        .ok_or(CaptureError::Unsupported)?;
+    let fd = fd_list.ok_or(CaptureError::Unsupported)?.get(index.0)?;
+    // SAFETY: `UnixFDList::get()` returns a duplicate that is owned by the caller.
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    std::mem::forget(guard);
+    Ok(Screencast {
+        connection,
+        session,
+        streams,
+        fd,
+    })
+}
+
+/// Let the user choose sources, start a screencast of them and send it to `sender`.
+///
+/// Must be called from the main thread.
+pub fn request_screencast<Msg, F>(options: ScreencastOptions, sender: &Sender<Msg>, f: F)
+where
+    F: FnOnce(Result<Screencast, CaptureError>) -> Msg + 'static,
+    Msg: 'static,
+{
+    let sender = sender.clone();
+    crate::spawn_local(async move {
+        // Ignore send errors because the component might be shut down.
+        sender.send(f(screencast(options).await)).ok();
+    });
+}
+
+/// Closes a session that hasn't been handed over to a [`Screencast`] yet.
+struct SessionGuard<'a> {
+    connection: &'a gio::DBusConnection,
+    session: &'a str,
+}
+
+impl Drop for SessionGuard<'_> {
+    fn drop(&mut self) {
+        portal::close_session(self.connection, self.session);
+    }
+}
+
+/// Call a portal method and wait for its results.
+async fn request(
+    connection: &gio::DBusConnection,
+    interface: &str,
+    method: &str,
+    parameters: impl FnOnce(&str) -> Vec<Variant>,
+) -> Result<VariantDict, CaptureError> {
+    match portal::request(connection, interface, method, parameters).await? {
+        Response::Success(results) => Ok(results),
+        Response::Cancelled => Err(CaptureError::Cancelled),
+        Response::Failed => Err(CaptureError::Unsupported),
+    }
+}
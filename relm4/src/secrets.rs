@@ -0,0 +1,168 @@
+//! Store passwords and tokens in the Secret Service of the desktop, like GNOME Keyring or KWallet.
+//!
+//! Secrets are identified by attributes. Like libsecret, the name of the schema is stored
+//! as `xdg:schema` attribute, so secrets of different applications don't collide.
+//! Sandboxed applications use the file backend of the
+//! [Secret portal](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Secret.html)
+//! instead, which is handled transparently by [oo7](https://docs.rs/oo7).
+//!
+//! Components usually call [`ComponentSender::store_secret()`], [`ComponentSender::lookup_secret()`]
+//! or [`ComponentSender::delete_secret()`], which access the keyring on the runtime and
+//! forward the result as an input message.
+//!
+//! ```no_run
+//! # use relm4::prelude::*;
+//! # use relm4::secrets::{Attributes, Secret, SecretError};
+//! #[derive(Debug)]
+//! enum Msg {
+//!     Load,
+//!     LoggedIn(String),
+//!     Stored(Result<(), SecretError>),
+//!     Restored(Result<Option<Secret>, SecretError>),
+//! }
+//! # struct App;
+//! # impl SimpleComponent for App {
+//! #     type Init = ();
+//! #     type Input = Msg;
+//! #     type Output = ();
+//! #     type Root = ();
+//! #     type Widgets = ();
+//! #     fn init_root() {}
+//! #     fn init(_: (), _: (), _: ComponentSender<Self>) -> ComponentParts<Self> {
+//! #         ComponentParts { model: App, widgets: () }
+//! #     }
+//!
+//! fn update(&mut self, msg: Msg, sender: ComponentSender<Self>) {
+//!     let attributes = Attributes::new("org.example.App").with("account", "alice");
+//!     match msg {
+//!         Msg::LoggedIn(token) => {
+//!             sender.store_secret("Example token", attributes, token, Msg::Stored);
+//!         }
+//!         Msg::Stored(result) => println!("{result:?}"),
+//!         // Sent once after startup.
+//!         Msg::Load => sender.lookup_secret(attributes, Msg::Restored),
+//!         Msg::Restored(Ok(Some(token))) => println!("Restored {} bytes", token.len()),
+//!         Msg::Restored(Ok(None)) => println!("Not logged in yet"),
+//!         Msg::Restored(Err(error)) => println!("Keyring unavailable: {error}"),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`ComponentSender::store_secret()`]: crate::ComponentSender::store_secret
+//! [`ComponentSender::lookup_secret()`]: crate::ComponentSender::lookup_secret
+//! [`ComponentSender::delete_secret()`]: crate::ComponentSender::delete_secret
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+
+use futures::future::Either;
+use tokio::sync::OnceCell;
+
+use crate::ShutdownReceiver;
+
+/// Re-export of oo7
+pub use oo7;
+
+/// Error returned if the keyring can't be opened or accessed.
+pub type SecretError = oo7::Error;
+
+/// A secret that is overwritten with zeros when it's dropped.
+pub type Secret = zeroize::Zeroizing<Vec<u8>>;
+
+/// The attribute that stores the name of the schema.
+const SCHEMA_ATTRIBUTE: &str = "xdg:schema";
+
+/// The keyring that is shared by all requests.
+static KEYRING: OnceCell<oo7::Keyring> = OnceCell::const_new();
+
+/// The attributes that identify a secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct Attributes {
+    inner: BTreeMap<String, String>,
+}
+
+impl Attributes {
+    /// Create attributes of a schema like `"org.example.App.Token"`.
+    pub fn new(schema: &str) -> Self {
+        let mut inner = BTreeMap::new();
+        inner.insert(SCHEMA_ATTRIBUTE.to_owned(), schema.to_owned());
+        Self { inner }
+    }
+
+    /// Add an attribute like the name of the account.
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.inner.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// The name of the schema.
+    #[must_use]
+    pub fn schema(&self) -> &str {
+        &self.inner[SCHEMA_ATTRIBUTE]
+    }
+
+    fn as_map(&self) -> HashMap<&str, &str> {
+        self.inner
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect()
+    }
+}
+
+async fn keyring() -> Result<&'static oo7::Keyring, SecretError> {
+    KEYRING.get_or_try_init(oo7::Keyring::new).await
+}
+
+/// Store a secret, replacing the secret with the same attributes.
+///
+/// The label is shown in keyring managers like Seahorse.
+pub async fn store(
+    label: &str,
+    attributes: &Attributes,
+    secret: impl AsRef<[u8]>,
+) -> Result<(), SecretError> {
+    keyring()
+        .await?
+        .create_item(label, &attributes.as_map(), secret, true)
+        .await
+}
+
+/// Look up the secret with the given attributes.
+///
+/// Returns [`None`] if no secret was stored with these attributes.
+pub async fn lookup(attributes: &Attributes) -> Result<Option<Secret>, SecretError> {
+    let items = keyring().await?.search_items(&attributes.as_map()).await?;
+    match items.first() {
+        Some(item) => Ok(Some(item.secret().await?)),
+        None => Ok(None),
+    }
+}
+
+/// Delete all secrets with the given attributes.
+pub async fn delete(attributes: &Attributes) -> Result<(), SecretError> {
+    keyring().await?.delete(&attributes.as_map()).await
+}
+
+/// Run a keyring request on the runtime and forward the result as input.
+pub(crate) fn forward<T, Input, Fut, F>(
+    request: Fut,
+    input: crate::Sender<Input>,
+    shutdown: ShutdownReceiver,
+    mapper: F,
+) where
+    T: Send + 'static,
+    Fut: Future<Output = Result<T, SecretError>> + Send + 'static,
+    Input: 'static,
+    F: FnOnce(Result<T, SecretError>) -> Input + 'static,
+{
+    // The request is canceled as soon as the component shuts down.
+    let handle = crate::spawn(shutdown.register(request).wait());
+
+    crate::spawn_local(async move {
+        if let Ok(Either::Right(result)) = handle.await {
+            input.send(mapper(result)).ok();
+        }
+    });
+}
@@ -0,0 +1,634 @@
+//! Spell checking of text inputs with [Enchant](https://rrthomas.github.io/enchant/).
+//!
+//! [`attach()`] underlines misspelled words in a [`gtk::Entry`] or [`gtk::TextView`]
+//! and adds suggestions to its context menu. In the `view!` macro, the
+//! `#[spellcheck]` attribute does the same:
+//!
+//! ```ignore
+//! view! {
+//!     #[spellcheck(lang = "auto")]
+//!     gtk::TextView {}
+//! }
+//! ```
+//!
+//! Each widget gets the action group `spell` with these actions:
+//!
+//! | Action           | Parameter | Effect                                          |
+//! |------------------|-----------|-------------------------------------------------|
+//! | `spell.enabled`  | `b`       | Enables or disables spell checking (stateful).  |
+//! | `spell.language` | `s`       | Changes the language (stateful).                |
+//! | `spell.replace`  | `s`       | Replaces the misspelled word at the cursor.     |
+//! | `spell.add`      |           | Adds the word at the cursor to the dictionary.  |
+//!
+//! Enchant is loaded at runtime. If it isn't installed, spell checking is silently disabled.
+
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::fmt::{self, Debug};
+use std::ops::Range;
+use std::rc::Rc;
+
+use gtk::prelude::{
+    ActionMapExt, EditableExt, EntryExt, FromVariant, IsA, ObjectExt, TextBufferExt, TextViewExt,
+    ToVariant, WidgetExt,
+};
+use gtk::{gio, glib, pango};
+
+/// The language that is detected from the locale.
+pub const AUTO: &str = "auto";
+
+/// The number of suggestions shown in the context menu.
+const SUGGESTIONS: usize = 5;
+
+/// The tag of misspelled words in text views.
+const TAG: &str = "relm4-misspelled";
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const LIBRARIES: &[&str] = &["libenchant-2.so.2", "libenchant-2.so"];
+#[cfg(target_os = "macos")]
+const LIBRARIES: &[&str] = &["libenchant-2.2.dylib", "libenchant-2.dylib"];
+#[cfg(windows)]
+const LIBRARIES: &[&str] = &["libenchant-2-2.dll", "libenchant-2.dll"];
+
+type DescribeFn =
+    unsafe extern "C" fn(*const c_char, *const c_char, *const c_char, *const c_char, *mut c_void);
+
+/// The functions of the Enchant library.
+struct Enchant {
+    broker: *mut c_void,
+    broker_free: unsafe extern "C" fn(*mut c_void),
+    request_dict: unsafe extern "C" fn(*mut c_void, *const c_char) -> *mut c_void,
+    free_dict: unsafe extern "C" fn(*mut c_void, *mut c_void),
+    dict_exists: unsafe extern "C" fn(*mut c_void, *const c_char) -> c_int,
+    list_dicts: unsafe extern "C" fn(*mut c_void, DescribeFn, *mut c_void),
+    check: unsafe extern "C" fn(*mut c_void, *const c_char, isize) -> c_int,
+    suggest:
+        unsafe extern "C" fn(*mut c_void, *const c_char, isize, *mut usize) -> *mut *mut c_char,
+    free_string_list: unsafe extern "C" fn(*mut c_void, *mut *mut c_char),
+    add: unsafe extern "C" fn(*mut c_void, *const c_char, isize),
+    // Keeps the functions valid, so it's dropped last.
+    _library: libloading::Library,
+}
+
+thread_local! {
+    static ENCHANT: Option<Rc<Enchant>> = Enchant::load().map(Rc::new);
+}
+
+impl Enchant {
+    fn load() -> Option<Self> {
+        let library = LIBRARIES
+            .iter()
+            // SAFETY: Enchant has no initialization routines with preconditions.
+            .find_map(|name| unsafe { libloading::Library::new(name) }.ok())?;
+
+        macro_rules! symbol {
+            ($name:literal) => {
+                // SAFETY: The types of the fields match the signatures of Enchant 2.
+                *unsafe { library.get(concat!($name, "\0").as_bytes()) }.ok()?
+            };
+        }
+
+        let broker_init: unsafe extern "C" fn() -> *mut c_void = symbol!("enchant_broker_init");
+        let mut enchant = Self {
+            broker: std::ptr::null_mut(),
+            broker_free: symbol!("enchant_broker_free"),
+            request_dict: symbol!("enchant_broker_request_dict"),
+            free_dict: symbol!("enchant_broker_free_dict"),
+            dict_exists: symbol!("enchant_broker_dict_exists"),
+            list_dicts: symbol!("enchant_broker_list_dicts"),
+            check: symbol!("enchant_dict_check"),
+            suggest: symbol!("enchant_dict_suggest"),
+            free_string_list: symbol!("enchant_dict_free_string_list"),
+            add: symbol!("enchant_dict_add"),
+            _library: library,
+        };
+        // SAFETY: The broker is freed when `enchant` is dropped.
+        enchant.broker = unsafe { broker_init() };
+        (!enchant.broker.is_null()).then_some(enchant)
+    }
+
+    fn get() -> Option<Rc<Self>> {
+        ENCHANT.with(Clone::clone)
+    }
+
+    fn dict_exists(&self, language: &str) -> bool {
+        let Ok(tag) = CString::new(language) else {
+            return false;
+        };
+        // SAFETY: The broker is valid and the tag is a C string.
+        unsafe { (self.dict_exists)(self.broker, tag.as_ptr()) != 0 }
+    }
+}
+
+impl Drop for Enchant {
+    fn drop(&mut self) {
+        if !self.broker.is_null() {
+            // SAFETY: All dictionaries hold a reference to `self`, so they are already freed.
+            unsafe { (self.broker_free)(self.broker) };
+        }
+    }
+}
+
+/// A dictionary of a language.
+pub struct Dictionary {
+    enchant: Rc<Enchant>,
+    dict: *mut c_void,
+    language: String,
+}
+
+impl Debug for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dictionary")
+            .field("language", &self.language)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Dictionary {
+    /// Loads the dictionary of a language like `en_US` or [`AUTO`].
+    ///
+    /// Returns [`None`] if Enchant or the dictionary isn't installed.
+    #[must_use]
+    pub fn new(language: &str) -> Option<Self> {
+        let enchant = Enchant::get()?;
+        let language = if language == AUTO {
+            default_language()?
+        } else {
+            language.to_owned()
+        };
+        let tag = CString::new(language.as_str()).ok()?;
+        // SAFETY: The broker is valid and the tag is a C string.
+        let dict = unsafe { (enchant.request_dict)(enchant.broker, tag.as_ptr()) };
+        (!dict.is_null()).then_some(Self {
+            enchant,
+            dict,
+            language,
+        })
+    }
+
+    /// Returns the language of the dictionary.
+    #[must_use]
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Returns `true` if `word` is spelled correctly.
+    #[must_use]
+    pub fn check(&self, word: &str) -> bool {
+        // SAFETY: The length of the word is passed, so it doesn't need to be a C string.
+        let result =
+            unsafe { (self.enchant.check)(self.dict, word.as_ptr().cast(), word.len() as isize) };
+        // Negative results are errors, which aren't shown to the user.
+        result <= 0
+    }
+
+    /// Returns suggestions for a misspelled word.
+    #[must_use]
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut len = 0;
+        // SAFETY: The length of the word is passed, so it doesn't need to be a C string.
+        let list = unsafe {
+            (self.enchant.suggest)(
+                self.dict,
+                word.as_ptr().cast(),
+                word.len() as isize,
+                &mut len,
+            )
+        };
+        if list.is_null() {
+            return Vec::new();
+        }
+
+        // SAFETY: Enchant returned `len` C strings, which are freed afterwards.
+        unsafe {
+            let suggestions = (0..len)
+                .map(|i| CStr::from_ptr(*list.add(i)).to_string_lossy().into_owned())
+                .collect();
+            (self.enchant.free_string_list)(self.dict, list);
+            suggestions
+        }
+    }
+
+    /// Adds a word to the personal dictionary of the user.
+    pub fn add(&self, word: &str) {
+        // SAFETY: The length of the word is passed, so it doesn't need to be a C string.
+        unsafe { (self.enchant.add)(self.dict, word.as_ptr().cast(), word.len() as isize) };
+    }
+
+    /// Returns the byte ranges of the misspelled words in `text`.
+    #[must_use]
+    pub fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+        words(text)
+            .into_iter()
+            .filter(|word| !self.check(&text[word.clone()]))
+            .collect()
+    }
+}
+
+impl Drop for Dictionary {
+    fn drop(&mut self) {
+        // SAFETY: The dictionary was requested from this broker.
+        unsafe { (self.enchant.free_dict)(self.enchant.broker, self.dict) };
+    }
+}
+
+/// Returns the languages that have a dictionary, like `en_US`.
+#[must_use]
+pub fn languages() -> Vec<String> {
+    unsafe extern "C" fn describe(
+        tag: *const c_char,
+        _provider_name: *const c_char,
+        _provider_desc: *const c_char,
+        _provider_file: *const c_char,
+        languages: *mut c_void,
+    ) {
+        let languages = &mut *languages.cast::<Vec<String>>();
+        languages.push(CStr::from_ptr(tag).to_string_lossy().into_owned());
+    }
+
+    let Some(enchant) = Enchant::get() else {
+        return Vec::new();
+    };
+    let mut languages: Vec<String> = Vec::new();
+    // SAFETY: `describe` is only called during this call.
+    unsafe {
+        (enchant.list_dicts)(
+            enchant.broker,
+            describe,
+            std::ptr::addr_of_mut!(languages).cast(),
+        );
+    }
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Returns the language of the locale of the user, if it has a dictionary.
+#[must_use]
+pub fn default_language() -> Option<String> {
+    let enchant = Enchant::get()?;
+    glib::language_names()
+        .iter()
+        // Strip the encoding and modifier, like in `de_DE.UTF-8@euro`.
+        .map(|name| name.split(['.', '@']).next().unwrap_or_default())
+        .filter(|name| !matches!(*name, "C" | "POSIX" | ""))
+        .find(|name| enchant.dict_exists(name))
+        .map(ToOwned::to_owned)
+}
+
+/// Widgets that can be spell checked with [`attach()`].
+pub trait SpellCheckable: private::Sealed {}
+
+impl SpellCheckable for gtk::Entry {}
+impl SpellCheckable for gtk::TextView {}
+
+mod private {
+    use std::ops::Range;
+
+    use gtk::gio;
+    use gtk::prelude::IsA;
+
+    pub trait Sealed: IsA<gtk::Widget> {
+        fn text(&self) -> String;
+        /// The byte index of the cursor.
+        fn cursor(&self, text: &str) -> usize;
+        fn underline(&self, text: &str, words: &[Range<usize>]);
+        fn replace(&self, text: &str, word: Range<usize>, replacement: &str);
+        fn connect_text_changed<F: Fn(&Self) + 'static>(&self, f: F);
+        fn connect_cursor_moved<F: Fn(&Self) + 'static>(&self, f: F);
+        fn set_menu(&self, menu: &gio::Menu);
+    }
+}
+
+impl private::Sealed for gtk::Entry {
+    fn text(&self) -> String {
+        EditableExt::text(self).into()
+    }
+
+    fn cursor(&self, text: &str) -> usize {
+        byte_index(text, self.position())
+    }
+
+    fn underline(&self, _text: &str, words: &[Range<usize>]) {
+        let attributes = pango::AttrList::new();
+        for word in words {
+            let mut attribute = pango::AttrInt::new_underline(pango::Underline::Error);
+            attribute.set_start_index(word.start as u32);
+            attribute.set_end_index(word.end as u32);
+            attributes.insert(attribute);
+        }
+        self.set_attributes(&attributes);
+    }
+
+    fn replace(&self, text: &str, word: Range<usize>, replacement: &str) {
+        let start = char_offset(text, word.start);
+        self.delete_text(start, char_offset(text, word.end));
+        let mut position = start;
+        self.insert_text(replacement, &mut position);
+        self.set_position(position);
+    }
+
+    fn connect_text_changed<F: Fn(&Self) + 'static>(&self, f: F) {
+        self.connect_changed(f);
+    }
+
+    fn connect_cursor_moved<F: Fn(&Self) + 'static>(&self, f: F) {
+        self.connect_notify_local(Some("cursor-position"), move |entry, _| f(entry));
+    }
+
+    fn set_menu(&self, menu: &gio::Menu) {
+        self.set_extra_menu(Some(menu));
+    }
+}
+
+impl private::Sealed for gtk::TextView {
+    fn text(&self) -> String {
+        let (start, end) = self.buffer().bounds();
+        // Include hidden characters and placeholders of child widgets,
+        // so byte indices can be converted to offsets of iterators.
+        self.buffer().slice(&start, &end, true).into()
+    }
+
+    fn cursor(&self, text: &str) -> usize {
+        byte_index(text, self.buffer().cursor_position())
+    }
+
+    fn underline(&self, text: &str, words: &[Range<usize>]) {
+        let buffer = self.buffer();
+        let tag = buffer.tag_table().lookup(TAG).unwrap_or_else(|| {
+            let tag = gtk::TextTag::builder()
+                .name(TAG)
+                .underline(pango::Underline::Error)
+                .build();
+            buffer.tag_table().add(&tag);
+            tag
+        });
+
+        let (start, end) = buffer.bounds();
+        buffer.remove_tag(&tag, &start, &end);
+        for word in words {
+            buffer.apply_tag(
+                &tag,
+                &buffer.iter_at_offset(char_offset(text, word.start)),
+                &buffer.iter_at_offset(char_offset(text, word.end)),
+            );
+        }
+    }
+
+    fn replace(&self, text: &str, word: Range<usize>, replacement: &str) {
+        let buffer = self.buffer();
+        let mut start = buffer.iter_at_offset(char_offset(text, word.start));
+        let mut end = buffer.iter_at_offset(char_offset(text, word.end));
+        buffer.begin_user_action();
+        buffer.delete(&mut start, &mut end);
+        buffer.insert(&mut start, replacement);
+        buffer.end_user_action();
+    }
+
+    fn connect_text_changed<F: Fn(&Self) + 'static>(&self, f: F) {
+        let view = self.downgrade();
+        self.buffer().connect_changed(move |_| {
+            if let Some(view) = view.upgrade() {
+                f(&view);
+            }
+        });
+    }
+
+    fn connect_cursor_moved<F: Fn(&Self) + 'static>(&self, f: F) {
+        let view = self.downgrade();
+        self.buffer()
+            .connect_notify_local(Some("cursor-position"), move |_, _| {
+                if let Some(view) = view.upgrade() {
+                    f(&view);
+                }
+            });
+    }
+
+    fn set_menu(&self, menu: &gio::Menu) {
+        self.set_extra_menu(Some(menu));
+    }
+}
+
+/// The spell checking state of a widget.
+struct State {
+    dictionary: RefCell<Option<Dictionary>>,
+    enabled: Cell<bool>,
+    /// The misspelled word at the cursor.
+    word: RefCell<Option<(Range<usize>, String)>>,
+    suggestions: gio::Menu,
+}
+
+/// Enables spell checking for `widget`, see the [module documentation](self).
+///
+/// `language` is a language like `en_US` or [`AUTO`]. The underline of misspelled
+/// words replaces the attributes of entries. For text views, the buffer must be
+/// set before spell checking is attached.
+pub fn attach<W: SpellCheckable>(widget: &W, language: &str) {
+    let state = Rc::new(State {
+        dictionary: RefCell::new(Dictionary::new(language)),
+        enabled: Cell::new(true),
+        word: RefCell::default(),
+        suggestions: gio::Menu::new(),
+    });
+
+    let options = gio::Menu::new();
+    options.append(Some("Add to Dictionary"), Some("spell.add"));
+    options.append(Some("Check Spelling"), Some("spell.enabled"));
+    let menu = gio::Menu::new();
+    menu.append_section(None, &state.suggestions);
+    menu.append_section(None, &options);
+    widget.set_menu(&menu);
+
+    let group = gio::SimpleActionGroup::new();
+    let weak = widget.downgrade();
+
+    let enabled = gio::SimpleAction::new_stateful("enabled", None, &true.to_variant());
+    {
+        let state = state.clone();
+        let weak = weak.clone();
+        enabled.connect_change_state(move |action, value| {
+            if let (Some(widget), Some(enabled)) =
+                (weak.upgrade(), value.and_then(bool::from_variant))
+            {
+                action.set_state(&enabled.to_variant());
+                state.enabled.set(enabled);
+                refresh(&widget, &state);
+            }
+        });
+    }
+    group.add_action(&enabled);
+
+    let language = gio::SimpleAction::new_stateful(
+        "language",
+        Some(glib::VariantTy::STRING),
+        &language.to_variant(),
+    );
+    {
+        let state = state.clone();
+        let weak = weak.clone();
+        language.connect_change_state(move |action, value| {
+            if let (Some(widget), Some(language)) =
+                (weak.upgrade(), value.and_then(String::from_variant))
+            {
+                action.set_state(&language.to_variant());
+                state.dictionary.replace(Dictionary::new(&language));
+                refresh(&widget, &state);
+            }
+        });
+    }
+    group.add_action(&language);
+
+    let replace = gio::SimpleAction::new("replace", Some(glib::VariantTy::STRING));
+    {
+        let state = state.clone();
+        let weak = weak.clone();
+        replace.connect_activate(move |_, value| {
+            let replacement = value.and_then(String::from_variant);
+            let word = state.word.take();
+            if let (Some(widget), Some(replacement), Some((range, _))) =
+                (weak.upgrade(), replacement, word)
+            {
+                widget.replace(&widget.text(), range, &replacement);
+            }
+        });
+    }
+    group.add_action(&replace);
+
+    let add = gio::SimpleAction::new("add", None);
+    {
+        let state = state.clone();
+        add.connect_activate(move |_, _| {
+            let word = state.word.borrow().clone();
+            if let (Some(widget), Some((_, word))) = (weak.upgrade(), word) {
+                if let Some(dictionary) = &*state.dictionary.borrow() {
+                    dictionary.add(&word);
+                }
+                refresh(&widget, &state);
+            }
+        });
+    }
+    group.add_action(&add);
+
+    widget.insert_action_group("spell", Some(&group));
+
+    {
+        let state = state.clone();
+        widget.connect_text_changed(move |widget| refresh(widget, &state));
+    }
+    {
+        let state = state.clone();
+        widget.connect_cursor_moved(move |widget| update_suggestions(widget, &state));
+    }
+    refresh(widget, &state);
+}
+
+/// Enables or disables spell checking of a widget that was passed to [`attach()`].
+pub fn set_enabled(widget: &impl IsA<gtk::Widget>, enabled: bool) {
+    widget
+        .activate_action("spell.enabled", Some(&enabled.to_variant()))
+        .ok();
+}
+
+/// Changes the language of a widget that was passed to [`attach()`].
+pub fn set_language(widget: &impl IsA<gtk::Widget>, language: &str) {
+    widget
+        .activate_action("spell.language", Some(&language.to_variant()))
+        .ok();
+}
+
+fn refresh<W: SpellCheckable>(widget: &W, state: &State) {
+    let text = widget.text();
+    let misspelled = match &*state.dictionary.borrow() {
+        Some(dictionary) if state.enabled.get() => dictionary.misspelled(&text),
+        _ => Vec::new(),
+    };
+    widget.underline(&text, &misspelled);
+    update_suggestions(widget, state);
+}
+
+fn update_suggestions<W: SpellCheckable>(widget: &W, state: &State) {
+    state.suggestions.remove_all();
+    state.word.replace(None);
+
+    let dictionary = state.dictionary.borrow();
+    let Some(dictionary) = dictionary.as_ref().filter(|_| state.enabled.get()) else {
+        return;
+    };
+
+    let text = widget.text();
+    let cursor = widget.cursor(&text);
+    let Some(range) = words(&text)
+        .into_iter()
+        .find(|word| word.contains(&cursor) || word.end == cursor)
+    else {
+        return;
+    };
+    let word = &text[range.clone()];
+    if dictionary.check(word) {
+        return;
+    }
+
+    for suggestion in dictionary.suggest(word).into_iter().take(SUGGESTIONS) {
+        let item = gio::MenuItem::new(Some(&suggestion), None);
+        item.set_action_and_target_value(Some("spell.replace"), Some(&suggestion.to_variant()));
+        state.suggestions.append_item(&item);
+    }
+    state.word.replace(Some((range, word.to_owned())));
+}
+
+/// Returns the byte ranges of the words in `text`.
+///
+/// Apostrophes between letters are part of a word, like in "don't".
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let next_is_letter = chars.peek().is_some_and(|(_, next)| next.is_alphabetic());
+        let in_word =
+            c.is_alphabetic() || (start.is_some() && matches!(c, '\'' | '’') && next_is_letter);
+        match (in_word, start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                words.push(word_start..index);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        words.push(word_start..text.len());
+    }
+    words
+}
+
+fn char_offset(text: &str, byte: usize) -> i32 {
+    text[..byte].chars().count() as i32
+}
+
+fn byte_index(text: &str, offset: i32) -> usize {
+    text.char_indices()
+        .nth(offset.max(0) as usize)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{byte_index, char_offset, words};
+
+    #[test]
+    fn split_words() {
+        let text = "Don't splt 2words, ’tis größer";
+        let words: Vec<&str> = words(text).into_iter().map(|word| &text[word]).collect();
+        assert_eq!(words, ["Don't", "splt", "words", "tis", "größer"]);
+    }
+
+    #[test]
+    fn offsets() {
+        let text = "größer text";
+        assert_eq!(char_offset(text, 8), 6);
+        assert_eq!(byte_index(text, 6), 8);
+        assert_eq!(byte_index(text, 100), text.len());
+    }
+}
@@ -0,0 +1,55 @@
+//! Metrics of components for the [`metrics`] facade.
+//!
+//! With the `metrics` feature, Relm4 records the metrics below. They are exported
+//! by the recorder the application installs, for example `metrics-exporter-prometheus`
+//! or an OpenTelemetry bridge. Nothing is recorded if no recorder is installed.
+//!
+//! | Name                               | Type      | Labels              |
+//! |------------------------------------|-----------|---------------------|
+//! | [`MESSAGES`]                       | counter   | `component`, `kind` |
+//! | [`UPDATE_DURATION`]                | histogram | `component`, `kind` |
+//! | [`COMMAND_DURATION`]               | histogram | `command`           |
+//! | [`FACTORY_SIZE`]                   | gauge     | `component`         |
+//!
+//! The `component` label is the type name of the component and `kind` is the kind
+//! of the update, like `update_with_view` or `update_cmd_with_view`.
+//! Commands are labeled by the type name of their output, because they run
+//! independently of the component that started them.
+
+use std::time::Duration;
+
+/// Counts the messages handled by components.
+pub const MESSAGES: &str = "relm4_messages_total";
+
+/// The time it took components to handle a message, in seconds.
+pub const UPDATE_DURATION: &str = "relm4_update_duration_seconds";
+
+/// The time commands took to complete, in seconds.
+pub const COMMAND_DURATION: &str = "relm4_command_duration_seconds";
+
+/// The number of elements in factories.
+pub const FACTORY_SIZE: &str = "relm4_factory_size";
+
+/// Records an update of a component.
+pub(crate) fn update(kind: &'static str, component: &'static str, elapsed: Duration) {
+    let labels = [("component", component), ("kind", kind)];
+    metrics::counter!(MESSAGES, &labels).increment(1);
+    metrics::histogram!(UPDATE_DURATION, &labels).record(elapsed);
+}
+
+/// Records a finished command.
+pub(crate) fn command(command: &'static str, elapsed: Duration) {
+    metrics::histogram!(COMMAND_DURATION, "command" => command).record(elapsed);
+}
+
+/// Records that elements were added to or removed from a factory.
+///
+/// Factories of the same type share a gauge, so only the change is recorded.
+pub(crate) fn factory_size(component: &'static str, old: usize, new: usize) {
+    let gauge = metrics::gauge!(FACTORY_SIZE, "component" => component);
+    if new > old {
+        gauge.increment((new - old) as f64);
+    } else if old > new {
+        gauge.decrement((old - new) as f64);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use gtk::prelude::{ButtonExt, Cast, CheckButtonExt, ObjectExt, WidgetExt};
+
+/// An accessibility issue found by [`a11y_audit()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct A11yIssue {
+    /// The widget with the issue.
+    pub widget: gtk::Widget,
+    /// The path of the widget in the widget tree,
+    /// for example `GtkBox > GtkButton #save`.
+    pub path: String,
+    /// The kind of the issue.
+    pub kind: A11yIssueKind,
+}
+
+/// The kind of an [`A11yIssue`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum A11yIssueKind {
+    /// An interactive widget has neither a visible label,
+    /// nor a tooltip, nor a label that references it as mnemonic widget.
+    ///
+    /// Screen readers can only announce the role of such widgets,
+    /// which is common for buttons that only show an icon.
+    MissingLabel,
+    /// The widget is so transparent that its content
+    /// probably doesn't have enough contrast.
+    LowOpacity {
+        /// The combined opacity of the widget and its ancestors.
+        opacity: f64,
+    },
+    /// An interactive widget can't be reached with the keyboard
+    /// because neither it nor its children can receive the focus.
+    UnreachableFocusable,
+    /// Another label uses the same mnemonic key.
+    DuplicateMnemonic {
+        /// The mnemonic key.
+        key: char,
+        /// The path of the widget that uses the key first.
+        first: String,
+    },
+}
+
+/// The result of [`a11y_audit()`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct A11yReport {
+    /// All issues in the order of the widget tree.
+    pub issues: Vec<A11yIssue>,
+}
+
+impl A11yReport {
+    /// Returns [`true`] if no issues were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the issues of the widget with the given name.
+    #[must_use]
+    pub fn issues_of(&self, widget_name: &str) -> Vec<&A11yIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.widget.widget_name() == widget_name)
+            .collect()
+    }
+}
+
+/// Opacity below which text is considered hard to read.
+const MIN_OPACITY: f64 = 0.5;
+
+/// Checks the visible widgets below `root` for common accessibility issues.
+///
+/// The checks are heuristics that only use public widget state,
+/// so labels set directly as accessible properties aren't detected.
+/// Internal children of interactive widgets, like the button of a
+/// [`gtk::MenuButton`], are checked as part of their parent.
+///
+/// ```
+/// # use relm4::test::{a11y_audit, A11yIssueKind};
+/// # gtk::init().unwrap();
+/// let button = gtk::Button::from_icon_name("edit-delete-symbolic");
+///
+/// let report = a11y_audit(&button);
+/// assert_eq!(report.issues[0].kind, A11yIssueKind::MissingLabel);
+/// ```
+#[must_use]
+pub fn a11y_audit(root: &impl gtk::glib::object::IsA<gtk::Widget>) -> A11yReport {
+    let mut audit = Audit::default();
+    audit.visit(root.as_ref(), "", 1.0, true);
+    A11yReport {
+        issues: audit.issues,
+    }
+}
+
+#[derive(Default)]
+struct Audit {
+    issues: Vec<A11yIssue>,
+    /// The first label that uses a mnemonic key.
+    mnemonics: HashMap<char, String>,
+}
+
+impl Audit {
+    fn visit(&mut self, widget: &gtk::Widget, parent_path: &str, opacity: f64, can_focus: bool) {
+        if !widget.is_visible() {
+            return;
+        }
+
+        let path = widget_path(widget, parent_path);
+        let opacity = opacity * widget.opacity();
+        let can_focus = can_focus && widget.can_focus();
+
+        if opacity < MIN_OPACITY && has_text(widget) {
+            self.report(widget, &path, A11yIssueKind::LowOpacity { opacity });
+        }
+        if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+            self.check_mnemonic(label, &path);
+        }
+
+        if is_interactive(widget) {
+            if !has_label(widget) {
+                self.report(widget, &path, A11yIssueKind::MissingLabel);
+            }
+            if widget.is_sensitive() && !(can_focus && is_reachable(widget)) {
+                self.report(widget, &path, A11yIssueKind::UnreachableFocusable);
+            }
+            // Internal children are covered by the checks above,
+            // only the mnemonics of their labels need to be checked.
+            self.visit_labels(widget, &path);
+            return;
+        }
+
+        let mut child = widget.first_child();
+        while let Some(widget) = child {
+            self.visit(&widget, &path, opacity, can_focus);
+            child = widget.next_sibling();
+        }
+    }
+
+    fn visit_labels(&mut self, widget: &gtk::Widget, parent_path: &str) {
+        let mut child = widget.first_child();
+        while let Some(widget) = child {
+            if widget.is_visible() {
+                let path = widget_path(&widget, parent_path);
+                if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+                    self.check_mnemonic(label, &path);
+                }
+                self.visit_labels(&widget, &path);
+            }
+            child = widget.next_sibling();
+        }
+    }
+
+    fn check_mnemonic(&mut self, label: &gtk::Label, path: &str) {
+        if !label.uses_underline() {
+            return;
+        }
+        let Some(key) = label.mnemonic_keyval().to_lower().to_unicode() else {
+            return;
+        };
+
+        if let Some(first) = self.mnemonics.get(&key) {
+            let kind = A11yIssueKind::DuplicateMnemonic {
+                key,
+                first: first.clone(),
+            };
+            self.report(label.upcast_ref(), path, kind);
+        } else {
+            self.mnemonics.insert(key, path.to_owned());
+        }
+    }
+
+    fn report(&mut self, widget: &gtk::Widget, path: &str, kind: A11yIssueKind) {
+        self.issues.push(A11yIssue {
+            widget: widget.clone(),
+            path: path.to_owned(),
+            kind,
+        });
+    }
+}
+
+fn widget_path(widget: &gtk::Widget, parent_path: &str) -> String {
+    let type_name = widget.type_().name();
+    let mut path = if parent_path.is_empty() {
+        type_name.to_owned()
+    } else {
+        format!("{parent_path} > {type_name}")
+    };
+
+    let name = widget.widget_name();
+    if name != type_name {
+        path.push_str(" #");
+        path.push_str(&name);
+    }
+    path
+}
+
+fn is_interactive(widget: &gtk::Widget) -> bool {
+    widget.is::<gtk::Button>()
+        || widget.is::<gtk::MenuButton>()
+        || widget.is::<gtk::CheckButton>()
+        || widget.is::<gtk::Switch>()
+        || widget.is::<gtk::Scale>()
+        || widget.is::<gtk::SpinButton>()
+        || widget.is::<gtk::DropDown>()
+        || widget.is::<gtk::TextView>()
+        || widget.is::<gtk::Editable>()
+}
+
+fn has_label(widget: &gtk::Widget) -> bool {
+    let is_set = |text: Option<gtk::glib::GString>| text.is_some_and(|text| !text.is_empty());
+
+    if is_set(widget.tooltip_text()) || !widget.list_mnemonic_labels().is_empty() {
+        return true;
+    }
+    if let Some(button) = widget.downcast_ref::<gtk::Button>() {
+        if is_set(button.label()) {
+            return true;
+        }
+    }
+    if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+        if is_set(check_button.label()) {
+            return true;
+        }
+    }
+    has_text(widget)
+}
+
+/// Returns [`true`] if the widget or one of its children shows a label with text.
+fn has_text(widget: &gtk::Widget) -> bool {
+    if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+        return !label.text().is_empty();
+    }
+
+    let mut child = widget.first_child();
+    while let Some(widget) = child {
+        if widget.is_visible() && has_text(&widget) {
+            return true;
+        }
+        child = widget.next_sibling();
+    }
+    false
+}
+
+/// Returns [`true`] if the widget or one of its children can receive the focus.
+fn is_reachable(widget: &gtk::Widget) -> bool {
+    if !widget.can_focus() {
+        return false;
+    }
+    if widget.is_focusable() {
+        return true;
+    }
+
+    let mut child = widget.first_child();
+    while let Some(widget) = child {
+        if widget.is_visible() && is_reachable(&widget) {
+            return true;
+        }
+        child = widget.next_sibling();
+    }
+    false
+}
@@ -0,0 +1,322 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use super::MockComponentSender;
+use crate::Component;
+
+/// Number of random bytes that are available to generate a single message.
+const BYTES_PER_MESSAGE: usize = 256;
+
+/// Maximum number of messages a component may send to itself
+/// while processing a single generated message.
+const MAX_FOLLOW_UPS: usize = 1_000;
+
+/// Configuration of [`fuzz_with_config()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Number of random message sequences.
+    pub runs: usize,
+    /// Maximum number of messages per sequence.
+    pub max_steps: usize,
+    /// The seed of the random generator.
+    ///
+    /// [`None`] uses a random seed that is printed
+    /// if an invariant is violated.
+    pub seed: Option<u64>,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            runs: 100,
+            max_steps: 50,
+            seed: None,
+        }
+    }
+}
+
+/// Sends random sequences of input messages to a component and checks
+/// `invariant` after each message, see [`fuzz_with_config()`].
+///
+/// ```ignore
+/// relm4::test::fuzz(
+///     || (Player::default(), ()),
+///     |player| {
+///         if player.position <= player.duration {
+///             Ok(())
+///         } else {
+///             Err(format!("Position {:?} is after the end", player.position))
+///         }
+///     },
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics with the shortest failing sequence that was found
+/// if the invariant is violated or the update panics.
+pub fn fuzz<C, I, F>(init: I, invariant: F)
+where
+    C: Component,
+    C::Input: for<'a> Arbitrary<'a> + Clone,
+    I: Fn() -> (C, C::Root),
+    F: Fn(&C) -> Result<(), String>,
+{
+    fuzz_with_config(FuzzConfig::default(), init, invariant);
+}
+
+/// Sends random sequences of input messages to a component and checks
+/// `invariant` after each message.
+///
+/// `init` creates the model and root for each sequence.
+/// The messages are processed with [`Component::update()`] and a
+/// [`MockComponentSender`], so no widgets are needed.
+/// Inputs that the component sends to itself and the outputs of
+/// its commands are processed before the next random message.
+///
+/// If the invariant is violated or the update panics, the failing sequence
+/// is shrunk by removing messages as long as it still fails.
+///
+/// # Panics
+///
+/// Panics with the shortest failing sequence that was found
+/// if the invariant is violated or the update panics.
+pub fn fuzz_with_config<C, I, F>(config: FuzzConfig, init: I, invariant: F)
+where
+    C: Component,
+    C::Input: for<'a> Arbitrary<'a> + Clone,
+    I: Fn() -> (C, C::Root),
+    F: Fn(&C) -> Result<(), String>,
+{
+    let seed = config
+        .seed
+        .unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+
+    for _ in 0..config.runs {
+        let sequence = generate::<C::Input>(&mut rng, config.max_steps);
+        if let Err(failure) = run(&init, &invariant, &sequence) {
+            let mut sequence = sequence;
+            sequence.truncate(failure.step + 1);
+            let (sequence, failure) = shrink(&init, &invariant, sequence, failure);
+            panic!(
+                "Invariant violated after {} message(s) (seed {seed}): {failure}\nSequence: {sequence:#?}",
+                sequence.len()
+            );
+        }
+    }
+}
+
+/// Why a sequence of messages failed.
+#[derive(Debug)]
+struct Failure {
+    /// The index of the message that failed.
+    step: usize,
+    reason: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+fn generate<T: for<'a> Arbitrary<'a>>(rng: &mut SplitMix64, max_steps: usize) -> Vec<T> {
+    let mut bytes = vec![0; BYTES_PER_MESSAGE];
+    let len = rng.next_below(max_steps + 1);
+
+    (0..len)
+        .filter_map(|_| {
+            bytes.fill_with(|| rng.next() as u8);
+            T::arbitrary(&mut Unstructured::new(&bytes)).ok()
+        })
+        .collect()
+}
+
+fn run<C, I, F>(init: &I, invariant: &F, sequence: &[C::Input]) -> Result<(), Failure>
+where
+    C: Component,
+    C::Input: Clone,
+    I: Fn() -> (C, C::Root),
+    F: Fn(&C) -> Result<(), String>,
+{
+    let (mut model, root) = init();
+    let mock = MockComponentSender::<C>::new();
+
+    for (step, message) in sequence.iter().enumerate() {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            model.update(message.clone(), mock.sender(), &root);
+            let mut follow_ups = 0;
+            loop {
+                let inputs = mock.inputs();
+                let commands = mock.resolve_commands();
+                if inputs.is_empty() && commands.is_empty() {
+                    break;
+                }
+
+                follow_ups += inputs.len() + commands.len();
+                if follow_ups > MAX_FOLLOW_UPS {
+                    return Err(format!(
+                        "The component sent more than {MAX_FOLLOW_UPS} messages to itself"
+                    ));
+                }
+                for input in inputs {
+                    model.update(input, mock.sender(), &root);
+                }
+                for output in commands {
+                    model.update_cmd(output, mock.sender(), &root);
+                }
+            }
+            invariant(&model)
+        }));
+
+        let reason = match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(reason)) => reason,
+            Err(payload) => panic_message(&*payload),
+        };
+        return Err(Failure { step, reason });
+    }
+    Ok(())
+}
+
+/// Removes chunks of messages from a failing sequence as long as it still fails.
+fn shrink<C, I, F>(
+    init: &I,
+    invariant: &F,
+    mut sequence: Vec<C::Input>,
+    mut failure: Failure,
+) -> (Vec<C::Input>, Failure)
+where
+    C: Component,
+    C::Input: Clone,
+    I: Fn() -> (C, C::Root),
+    F: Fn(&C) -> Result<(), String>,
+{
+    let mut chunk = sequence.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < sequence.len() {
+            let end = (start + chunk).min(sequence.len());
+            let mut candidate = sequence.clone();
+            candidate.drain(start..end);
+
+            if let Err(new_failure) = run(init, invariant, &candidate) {
+                candidate.truncate(new_failure.step + 1);
+                sequence = candidate;
+                failure = new_failure;
+            } else {
+                start = end;
+            }
+        }
+        chunk /= 2;
+    }
+    (sequence, failure)
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("The update panicked: {message}")
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("The update panicked: {message}")
+    } else {
+        "The update panicked".to_owned()
+    }
+}
+
+/// A small and fast random generator, good enough to generate test data.
+#[derive(Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use super::{fuzz_with_config, FuzzConfig};
+    use crate::{Component, ComponentParts, ComponentSender};
+
+    #[derive(Debug, Default)]
+    struct Stack(Vec<u8>);
+
+    #[derive(Debug, Clone)]
+    enum StackMsg {
+        Push(u8),
+        Pop,
+    }
+
+    impl<'a> Arbitrary<'a> for StackMsg {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(if u.arbitrary()? {
+                Self::Push(u.arbitrary()?)
+            } else {
+                Self::Pop
+            })
+        }
+    }
+
+    impl Component for Stack {
+        type CommandOutput = ();
+        type Input = StackMsg;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(_: (), _: (), _: ComponentSender<Self>) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Self::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: StackMsg, _: ComponentSender<Self>, _: &()) {
+            match message {
+                StackMsg::Push(value) => self.0.push(value),
+                StackMsg::Pop => {
+                    self.0.pop();
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invariant violated after 3 message(s)")]
+    fn fuzz_shrinks() {
+        let config = FuzzConfig {
+            runs: 1_000,
+            seed: Some(42),
+            ..FuzzConfig::default()
+        };
+        fuzz_with_config(
+            config,
+            || (Stack::default(), ()),
+            |stack| {
+                if stack.0.len() < 3 {
+                    Ok(())
+                } else {
+                    Err("The stack is too large".to_owned())
+                }
+            },
+        );
+    }
+}
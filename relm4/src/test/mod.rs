@@ -0,0 +1,340 @@
+//! Utilities for testing components.
+//!
+//! [`LoopDriver`] runs the main loop step by step, so tests don't depend
+//! on real time and don't need to sleep:
+//!
+//! ```ignore
+//! let driver = LoopDriver::new();
+//! let search = Search::builder().launch(()).detach();
+//!
+//! search.emit(SearchMsg::Query("relm".into()));
+//! // The search is debounced by 300ms with `relm4::time::sleep()`.
+//! driver.advance_by(Duration::from_millis(299));
+//! assert!(search.model().results.is_none());
+//! driver.advance_by(Duration::from_millis(1));
+//! assert!(search.model().results.is_some());
+//! ```
+//!
+//! [`MockComponentSender`] captures the messages sent by a component,
+//! so its `update()` logic can be tested without GTK:
+//!
+//! ```ignore
+//! let mock = MockComponentSender::<Counter>::new();
+//! let mut model = Counter { value: 0 };
+//!
+//! model.update(CounterMsg::Increment, mock.sender(), &root);
+//! assert_eq!(model.value, 1);
+//! assert_eq!(mock.outputs(), [CounterOutput::Changed(1)]);
+//! ```
+
+mod a11y;
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+mod fuzz;
+mod user_sim;
+mod widget_tree;
+
+pub use a11y::{a11y_audit, A11yIssue, A11yIssueKind, A11yReport};
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+pub use fuzz::{fuzz, fuzz_with_config, FuzzConfig};
+pub use user_sim::UserSim;
+#[doc(hidden)]
+pub use user_sim::{ViewNameOther, ViewNameProbe, ViewNameWidget};
+pub use widget_tree::widget_tree_to_string;
+
+use std::fmt;
+use std::time::Duration;
+
+use gtk::glib;
+
+use crate::shutdown::ShutdownSender;
+use crate::{time, CommandQueue, Component, ComponentSender, PriorityReceiver, Receiver};
+
+/// Maximum number of iterations of [`LoopDriver::advance_until_idle()`].
+const MAX_IDLE_ITERATIONS: usize = 100_000;
+
+/// Drives the main loop of the current thread in tests.
+///
+/// While a driver exists, the timers of the [`time`](crate::time) module use
+/// virtual time that only advances with [`LoopDriver::advance_by()`].
+/// Multiple drivers on the same thread share the virtual time,
+/// which switches back to real time once the last driver is dropped.
+/// Timers of GLib and tasks that run in the background, like commands,
+/// still use real time.
+#[derive(Debug)]
+pub struct LoopDriver {
+    context: glib::MainContext,
+}
+
+impl Default for LoopDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoopDriver {
+    /// Creates a driver for the main context of the current thread
+    /// and switches the [`time`](crate::time) module to virtual time.
+    #[must_use]
+    pub fn new() -> Self {
+        time::start_virtual_time();
+        Self {
+            context: glib::MainContext::ref_thread_default(),
+        }
+    }
+
+    /// Runs at most `n` iterations of the main loop without blocking.
+    ///
+    /// Returns the number of iterations that dispatched any events.
+    #[must_use]
+    pub fn pump_n(&self, n: usize) -> usize {
+        (0..n).filter(|_| self.context.iteration(false)).count()
+    }
+
+    /// Runs the main loop until no events are pending anymore.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the main loop never becomes idle,
+    /// for example because of an idle callback that is never removed.
+    pub fn advance_until_idle(&self) {
+        self.run_until_idle();
+    }
+
+    fn run_until_idle(&self) {
+        let mut iterations = 0;
+        while self.context.pending() {
+            self.context.iteration(false);
+            iterations += 1;
+            assert!(
+                iterations < MAX_IDLE_ITERATIONS,
+                "The main loop didn't become idle after {MAX_IDLE_ITERATIONS} iterations"
+            );
+        }
+    }
+
+    /// Advances the virtual time by `duration` and runs the main loop
+    /// until it's idle after every timer that expired.
+    pub fn advance_by(&self, duration: Duration) {
+        let target = time::virtual_now() + duration;
+        self.run_until_idle();
+        while time::advance_to_next_deadline(target) {
+            self.run_until_idle();
+        }
+        time::set_virtual_now(target);
+        self.run_until_idle();
+    }
+
+    /// Returns the virtual time of the current thread.
+    ///
+    /// Drivers that exist at the same time share one clock,
+    /// which starts at zero when the first of them is created.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        time::virtual_now()
+    }
+}
+
+impl Drop for LoopDriver {
+    fn drop(&mut self) {
+        time::stop_virtual_time();
+    }
+}
+
+/// A [`ComponentSender`] for tests that captures all messages
+/// instead of sending them to a running component.
+///
+/// Commands aren't spawned but queued until they are resolved
+/// with [`MockComponentSender::resolve_commands()`].
+/// Streams, frame callbacks and requests are still spawned,
+/// because they need a runtime anyway.
+pub struct MockComponentSender<C: Component> {
+    sender: ComponentSender<C>,
+    input: Receiver<C::Input>,
+    priority: PriorityReceiver<C::Input>,
+    output: Receiver<C::Output>,
+    command: Receiver<C::CommandOutput>,
+    commands: CommandQueue,
+    _shutdown: ShutdownSender,
+}
+
+impl<C: Component> fmt::Debug for MockComponentSender<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockComponentSender")
+            .field("commands", &self.commands)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Component> Default for MockComponentSender<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Component> MockComponentSender<C> {
+    /// Creates a new mocked sender.
+    #[must_use]
+    pub fn new() -> Self {
+        let (input_sender, input) = crate::channel();
+        let (priority_sender, priority) = crate::priority_channel();
+        let (output_sender, output) = crate::channel();
+        let (command_sender, command) = crate::channel();
+        let (shutdown, shutdown_receiver) = crate::shutdown::channel();
+        let commands = CommandQueue::default();
+
+        let sender = ComponentSender::mock(
+            input_sender,
+            priority_sender,
+            output_sender,
+            command_sender,
+            shutdown_receiver,
+            commands.clone(),
+        );
+
+        Self {
+            sender,
+            input,
+            priority,
+            output,
+            command,
+            commands,
+            _shutdown: shutdown,
+        }
+    }
+
+    /// Returns the sender that should be passed to the component.
+    #[must_use]
+    pub fn sender(&self) -> ComponentSender<C> {
+        self.sender.clone()
+    }
+
+    /// Takes all captured input messages.
+    ///
+    /// The messages are ordered by their [`InputPriority`](crate::InputPriority).
+    /// Unlike the runtime, messages with a low priority are always returned last.
+    #[must_use]
+    pub fn inputs(&self) -> Vec<C::Input> {
+        let mut inputs: Vec<C::Input> = self.priority.high.0.try_iter().collect();
+        inputs.extend(self.input.0.try_iter());
+        {
+            let mut coalesced = self
+                .priority
+                .coalesced
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            inputs.extend(
+                self.priority
+                    .keys
+                    .0
+                    .try_iter()
+                    .filter_map(|key| coalesced.remove(&key)),
+            );
+        }
+        inputs.extend(self.priority.low.0.try_iter());
+        inputs
+    }
+
+    /// Takes all captured output messages.
+    #[must_use]
+    pub fn outputs(&self) -> Vec<C::Output> {
+        self.output.0.try_iter().collect()
+    }
+
+    /// Returns the number of commands that weren't resolved yet.
+    #[must_use]
+    pub fn pending_commands(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Runs all queued commands to completion and
+    /// takes the command outputs they sent.
+    ///
+    /// Command outputs sent directly with
+    /// [`ComponentSender::command_sender()`] are included as well.
+    /// Commands are run on the current thread, so commands that wait
+    /// for the component to shut down never complete.
+    #[must_use]
+    pub fn resolve_commands(&self) -> Vec<C::CommandOutput> {
+        for command in self.commands.take() {
+            futures::executor::block_on(command);
+        }
+        self.command_outputs()
+    }
+
+    /// Drops all queued commands without running them and
+    /// returns how many commands were dropped.
+    #[must_use]
+    pub fn drop_commands(&self) -> usize {
+        self.commands.take().len()
+    }
+
+    /// Takes the command outputs that were sent without resolving
+    /// any queued commands.
+    #[must_use]
+    pub fn command_outputs(&self) -> Vec<C::CommandOutput> {
+        self.command.0.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockComponentSender;
+    use crate::{Component, ComponentParts, ComponentSender, InputPriority};
+
+    #[derive(Debug)]
+    struct Counter(u32);
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum CounterMsg {
+        Increment,
+        Reset,
+    }
+
+    impl Component for Counter {
+        type CommandOutput = u32;
+        type Input = CounterMsg;
+        type Output = u32;
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(_: (), _: (), _: ComponentSender<Self>) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Self(0),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: CounterMsg, sender: ComponentSender<Self>, _: &()) {
+            match message {
+                CounterMsg::Increment => self.0 += 1,
+                CounterMsg::Reset => self.0 = 0,
+            }
+            sender.output(self.0).unwrap();
+            let value = self.0;
+            sender.oneshot_command(async move { value * 2 });
+        }
+    }
+
+    #[test]
+    fn mock_sender() {
+        let mock = MockComponentSender::<Counter>::new();
+        let mut model = Counter(0);
+
+        model.update(CounterMsg::Increment, mock.sender(), &());
+        model.update(CounterMsg::Increment, mock.sender(), &());
+        assert_eq!(mock.outputs(), [1, 2]);
+        assert_eq!(mock.pending_commands(), 2);
+        assert_eq!(mock.resolve_commands(), [2, 4]);
+        assert_eq!(mock.pending_commands(), 0);
+
+        let sender = mock.sender();
+        sender.input(CounterMsg::Increment);
+        sender.input_priority(CounterMsg::Reset, InputPriority::High);
+        assert_eq!(mock.inputs(), [CounterMsg::Reset, CounterMsg::Increment]);
+    }
+}
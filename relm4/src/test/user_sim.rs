@@ -0,0 +1,292 @@
+use gtk::glib::object::IsA;
+use gtk::glib::translate::IntoGlib;
+use gtk::prelude::{
+    AdjustmentExt, Cast, EditableExt, ListModelExtManual, ObjectExt, TextBufferExt, TextViewExt,
+    WidgetExt,
+};
+use gtk::{gdk, glib};
+
+use super::LoopDriver;
+use crate::Receiver;
+
+/// Simulates user input on the widgets of a component.
+///
+/// The input is delivered to the event controllers and signals
+/// of the widgets, so the same handlers run as for real user input.
+/// After each action, the main loop runs until it's idle,
+/// so the resulting inputs of components are already processed.
+///
+/// Widgets can be passed directly, for example the widgets
+/// marked with `#[name]` in the `view!` macro, or looked up
+/// with [`UserSim::find()`].
+///
+/// ```ignore
+/// let (tx, rx) = relm4::channel();
+/// let counter = Counter::builder().launch(0).forward(&tx, |output| output);
+/// let sim = UserSim::new(counter.widget());
+///
+/// sim.click(&counter.widgets().increment);
+/// assert_eq!(sim.next_output(&rx), Some(CounterOutput::Changed(1)));
+///
+/// sim.type_text(&sim.find("name_entry").unwrap(), "Relm4");
+/// sim.press_key(&sim.find("name_entry").unwrap(), gdk::Key::Return, gdk::ModifierType::empty());
+/// ```
+#[derive(Debug)]
+pub struct UserSim {
+    root: gtk::Widget,
+    driver: LoopDriver,
+}
+
+impl UserSim {
+    /// Creates a simulator for the widget tree of `root`.
+    ///
+    /// This creates a [`LoopDriver`], so the timers of the
+    /// [`time`](crate::time) module use virtual time.
+    #[must_use]
+    pub fn new(root: &impl IsA<gtk::Widget>) -> Self {
+        Self {
+            root: root.clone().upcast(),
+            driver: LoopDriver::new(),
+        }
+    }
+
+    /// Returns the driver of the main loop, for example to advance the virtual time.
+    #[must_use]
+    pub fn driver(&self) -> &LoopDriver {
+        &self.driver
+    }
+
+    /// Returns the first widget below the root with the given name.
+    ///
+    /// The name is either the name of the widget in the `view!` macro,
+    /// like `#[name(name_entry)]`, or the widget name set with
+    /// [`WidgetExt::set_widget_name()`].
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<gtk::Widget> {
+        find_widget(&self.root, name)
+    }
+
+    /// Clicks on `widget` with the primary button.
+    ///
+    /// Activatable widgets like buttons, check buttons or switches are activated.
+    /// Other widgets receive the click through their [`gtk::GestureClick`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widget can't receive input or doesn't handle clicks.
+    pub fn click(&self, widget: &impl IsA<gtk::Widget>) {
+        let widget = widget.as_ref();
+        assert_receives_input(widget);
+
+        if !widget.activate() {
+            let x = f64::from(widget.width()) / 2.0;
+            let y = f64::from(widget.height()) / 2.0;
+            let gestures: Vec<gtk::GestureClick> = controllers(widget);
+            assert!(
+                !gestures.is_empty(),
+                "{} doesn't handle clicks",
+                widget.type_().name()
+            );
+            for gesture in gestures {
+                gesture.emit_by_name::<()>("pressed", &[&1i32, &x, &y]);
+                gesture.emit_by_name::<()>("released", &[&1i32, &x, &y]);
+            }
+        }
+        self.driver.advance_until_idle();
+    }
+
+    /// Presses and releases a key while `widget` has the focus.
+    ///
+    /// The key is delivered to the [`gtk::EventControllerKey`]s of `widget`
+    /// and its ancestors until one of them handles it.
+    /// [`gdk::Key::Return`] activates widgets like entries if the key isn't handled otherwise.
+    ///
+    /// Returns [`true`] if the key was handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widget can't receive input.
+    pub fn press_key(
+        &self,
+        widget: &impl IsA<gtk::Widget>,
+        key: gdk::Key,
+        modifiers: gdk::ModifierType,
+    ) -> bool {
+        let widget = widget.as_ref();
+        assert_receives_input(widget);
+        widget.grab_focus();
+
+        let keyval = key.into_glib();
+        let mut handled = false;
+        let mut current = Some(widget.clone());
+        while let Some(widget) = current {
+            for controller in controllers::<gtk::EventControllerKey>(&widget) {
+                handled |=
+                    controller.emit_by_name::<bool>("key-pressed", &[&keyval, &0u32, &modifiers]);
+                controller.emit_by_name::<()>("key-released", &[&keyval, &0u32, &modifiers]);
+            }
+            if handled {
+                break;
+            }
+            current = widget.parent();
+        }
+
+        if !handled && matches!(key, gdk::Key::Return | gdk::Key::KP_Enter) {
+            handled = widget.activate();
+        }
+        self.driver.advance_until_idle();
+        handled
+    }
+
+    /// Types `text` at the cursor position of an editable widget
+    /// like an entry or of a text view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widget can't receive input or isn't editable.
+    pub fn type_text(&self, widget: &impl IsA<gtk::Widget>, text: &str) {
+        let widget = widget.as_ref();
+        assert_receives_input(widget);
+        widget.grab_focus();
+
+        if let Some(editable) = widget.dynamic_cast_ref::<gtk::Editable>() {
+            assert!(editable.is_editable(), "The editable isn't editable");
+            let mut position = editable.position();
+            editable.insert_text(text, &mut position);
+            editable.set_position(position);
+        } else if let Some(text_view) = widget.downcast_ref::<gtk::TextView>() {
+            assert!(text_view.is_editable(), "The text view isn't editable");
+            text_view.buffer().insert_at_cursor(text);
+        } else {
+            panic!("{} isn't editable", widget.type_().name());
+        }
+        self.driver.advance_until_idle();
+    }
+
+    /// Scrolls over `widget` by the given number of steps.
+    ///
+    /// The scroll is delivered to the [`gtk::EventControllerScroll`]s of `widget`
+    /// and its ancestors until one of them handles it.
+    /// Scrolled windows move their adjustments by one step increment per step.
+    ///
+    /// Returns [`true`] if the scroll was handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widget can't receive input.
+    pub fn scroll(&self, widget: &impl IsA<gtk::Widget>, dx: f64, dy: f64) -> bool {
+        let widget = widget.as_ref();
+        assert_receives_input(widget);
+
+        let mut handled = false;
+        let mut current = Some(widget.clone());
+        while let Some(widget) = current {
+            if let Some(scrolled_window) = widget.downcast_ref::<gtk::ScrolledWindow>() {
+                scroll_adjustment(&scrolled_window.hadjustment(), dx);
+                scroll_adjustment(&scrolled_window.vadjustment(), dy);
+                handled = true;
+                break;
+            }
+
+            for controller in controllers::<gtk::EventControllerScroll>(&widget) {
+                handled |= controller.emit_by_name::<bool>("scroll", &[&dx, &dy]);
+            }
+            if handled {
+                break;
+            }
+            current = widget.parent();
+        }
+        self.driver.advance_until_idle();
+        handled
+    }
+
+    /// Runs the main loop until `receiver` yields a message,
+    /// for example the outputs of a component that were forwarded to it.
+    ///
+    /// Returns [`None`] if the main loop becomes idle without a message.
+    #[must_use]
+    pub fn next_output<T>(&self, receiver: &Receiver<T>) -> Option<T> {
+        loop {
+            if let Ok(message) = receiver.0.try_recv() {
+                return Some(message);
+            }
+            if self.driver.pump_n(1) == 0 {
+                return receiver.0.try_recv().ok();
+            }
+        }
+    }
+}
+
+fn assert_receives_input(widget: &gtk::Widget) {
+    assert!(
+        widget.is_visible() && widget.is_sensitive(),
+        "{} can't receive input because it's hidden or insensitive",
+        widget.type_().name()
+    );
+}
+
+fn controllers<T: IsA<gtk::EventController> + IsA<glib::Object>>(widget: &gtk::Widget) -> Vec<T> {
+    widget
+        .observe_controllers()
+        .iter::<glib::Object>()
+        .filter_map(|controller| controller.ok()?.downcast().ok())
+        .collect()
+}
+
+fn scroll_adjustment(adjustment: &gtk::Adjustment, steps: f64) {
+    if steps != 0.0 {
+        adjustment.set_value(adjustment.value() + steps * adjustment.step_increment());
+    }
+}
+
+/// Key of the data that stores the name of a widget in the `view!` macro.
+const VIEW_NAME_KEY: &str = "relm4-view-name";
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct ViewNameProbe<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ViewNameWidget {
+    fn set_view_name(&self, name: &'static str);
+}
+
+impl<T: IsA<gtk::Widget>> ViewNameWidget for &ViewNameProbe<'_, T> {
+    fn set_view_name(&self, name: &'static str) {
+        // SAFETY: The data is only read as `&'static str` by `view_name()`.
+        unsafe { self.0.set_data(VIEW_NAME_KEY, name) };
+    }
+}
+
+#[doc(hidden)]
+pub trait ViewNameOther {
+    fn set_view_name(&self, name: &'static str);
+}
+
+impl<T> ViewNameOther for ViewNameProbe<'_, T> {
+    fn set_view_name(&self, _name: &'static str) {}
+}
+
+fn view_name(widget: &gtk::Widget) -> Option<&'static str> {
+    // SAFETY: The data is always stored as `&'static str` by `set_view_name()`.
+    unsafe {
+        widget
+            .data::<&'static str>(VIEW_NAME_KEY)
+            .map(|name| *name.as_ref())
+    }
+}
+
+fn find_widget(widget: &gtk::Widget, name: &str) -> Option<gtk::Widget> {
+    if widget.widget_name() == name || view_name(widget) == Some(name) {
+        return Some(widget.clone());
+    }
+
+    let mut child = widget.first_child();
+    while let Some(widget) = child {
+        if let Some(found) = find_widget(&widget, name) {
+            return Some(found);
+        }
+        child = widget.next_sibling();
+    }
+    None
+}
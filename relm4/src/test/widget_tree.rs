@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use gtk::glib::{self, ParamSpec, Value};
+use gtk::prelude::{ObjectExt, WidgetExt};
+
+/// Properties that are included in the output if they don't have their default value.
+const PROPERTIES: &[&str] = &[
+    "label",
+    "text",
+    "title",
+    "subtitle",
+    "icon-name",
+    "placeholder-text",
+    "tooltip-text",
+    "orientation",
+    "spacing",
+    "active",
+    "value",
+    "visible",
+    "sensitive",
+    "hexpand",
+    "vexpand",
+    "halign",
+    "valign",
+    "css-classes",
+];
+
+/// Returns a textual representation of the widget tree of `root`.
+///
+/// Each widget is printed with its type, its name if it was set
+/// and a set of common properties that don't have their default value.
+/// Children are indented by two spaces:
+///
+/// ```
+/// # use gtk::prelude::{BoxExt, WidgetExt};
+/// # gtk::init().unwrap();
+/// let root = gtk::Box::new(gtk::Orientation::Vertical, 5);
+/// let title = gtk::Label::new(Some("Counter"));
+/// title.set_widget_name("title");
+/// root.append(&title);
+///
+/// assert_eq!(
+///     relm4::test::widget_tree_to_string(&root),
+///     "\
+/// GtkBox
+///   orientation: vertical
+///   spacing: 5
+///   GtkLabel #title
+///     label: \"Counter\"
+/// "
+/// );
+/// ```
+///
+/// The output is stable, so it can be compared to a golden file
+/// to check the structure of a view after sending messages
+/// without comparing pixels.
+#[must_use]
+pub fn widget_tree_to_string(root: &impl glib::object::IsA<gtk::Widget>) -> String {
+    let mut output = String::new();
+    write_widget(&mut output, root.as_ref(), 0);
+    output
+}
+
+fn write_widget(output: &mut String, widget: &gtk::Widget, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let type_name = widget.type_().name();
+
+    output.push_str(&indent);
+    output.push_str(type_name);
+    let name = widget.widget_name();
+    if name != type_name {
+        write!(output, " #{name}").unwrap();
+    }
+    output.push('\n');
+
+    for pspec in PROPERTIES
+        .iter()
+        .filter_map(|name| widget.find_property(name))
+    {
+        if let Some(value) = changed_value(widget, &pspec) {
+            writeln!(output, "{indent}  {}: {value}", pspec.name()).unwrap();
+        }
+    }
+
+    let mut child = widget.first_child();
+    while let Some(widget) = child {
+        write_widget(output, &widget, depth + 1);
+        child = widget.next_sibling();
+    }
+}
+
+/// Returns the formatted value of the property unless it has its default value.
+fn changed_value(widget: &gtk::Widget, pspec: &ParamSpec) -> Option<String> {
+    if !pspec.flags().contains(glib::ParamFlags::READABLE) {
+        return None;
+    }
+
+    let value = format_value(&widget.property_value(pspec.name()));
+    (value != format_value(pspec.default_value())).then_some(value)
+}
+
+fn format_value(value: &Value) -> String {
+    if let Ok(string) = value.get::<Option<String>>() {
+        return string.map_or_else(|| "null".to_owned(), |string| format!("{string:?}"));
+    }
+    if let Ok(strings) = value.get::<Vec<String>>() {
+        return format!("{strings:?}");
+    }
+    if let Some((_, enum_value)) = glib::EnumValue::from_value(value) {
+        return enum_value.nick().to_owned();
+    }
+    if let Some((_, flags)) = glib::FlagsValue::from_value(value) {
+        let nicks: Vec<_> = flags.iter().map(|flag| flag.nick()).collect();
+        return nicks.join(" | ");
+    }
+    if let Ok(number) = value.get::<f64>() {
+        return format!("{number}");
+    }
+    if let Ok(number) = value.get::<f32>() {
+        return format!("{number}");
+    }
+
+    // Covers booleans, integers and other types that can be transformed to strings.
+    value
+        .transform::<String>()
+        .ok()
+        .and_then(|value| value.get::<Option<String>>().ok().flatten())
+        .unwrap_or_else(|| format!("<{}>", value.type_()))
+}
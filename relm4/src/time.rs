@@ -0,0 +1,199 @@
+//! Timers that can be controlled in tests.
+//!
+//! The timers of this module behave like their GLib counterparts,
+//! but they use virtual time while a [`LoopDriver`](crate::test::LoopDriver)
+//! is active, so tests of debounced inputs or timeouts don't need to wait.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use gtk::glib;
+
+thread_local! {
+    static VIRTUAL_TIME: RefCell<Option<VirtualTime>> = RefCell::default();
+}
+
+/// The state of the virtual clock of a thread.
+#[derive(Debug, Default)]
+struct VirtualTime {
+    now: Duration,
+    /// Sleeping futures ordered by their deadline and creation.
+    timers: BTreeMap<(Duration, u64), Waker>,
+    next_id: u64,
+    /// Number of drivers that use the virtual clock.
+    drivers: usize,
+}
+
+/// A future that resolves once the given duration has passed.
+///
+/// Created by [`sleep()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    inner: SleepInner,
+}
+
+enum SleepInner {
+    Real(Pin<Box<dyn Future<Output = ()>>>),
+    Virtual { deadline: Duration, id: u64 },
+}
+
+impl fmt::Debug for Sleep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            SleepInner::Real(_) => f.debug_struct("Sleep").finish_non_exhaustive(),
+            SleepInner::Virtual { deadline, .. } => f
+                .debug_struct("Sleep")
+                .field("deadline", deadline)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &mut self.inner {
+            SleepInner::Real(future) => future.as_mut().poll(cx),
+            SleepInner::Virtual { deadline, id } => VIRTUAL_TIME.with(|time| {
+                match &mut *time.borrow_mut() {
+                    Some(time) if time.now < *deadline => {
+                        time.timers.insert((*deadline, *id), cx.waker().clone());
+                        Poll::Pending
+                    }
+                    // The virtual clock was stopped or the deadline has passed.
+                    _ => Poll::Ready(()),
+                }
+            }),
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let SleepInner::Virtual { deadline, id } = self.inner {
+            VIRTUAL_TIME.with(|time| {
+                if let Some(time) = &mut *time.borrow_mut() {
+                    time.timers.remove(&(deadline, id));
+                }
+            });
+        }
+    }
+}
+
+/// Returns a future that resolves after `duration`.
+///
+/// Use this instead of [`glib::timeout_future()`] to make the timer
+/// controllable by a [`LoopDriver`](crate::test::LoopDriver).
+pub fn sleep(duration: Duration) -> Sleep {
+    let deadline = VIRTUAL_TIME.with(|time| {
+        time.borrow_mut().as_mut().map(|time| {
+            let id = time.next_id;
+            time.next_id += 1;
+            (time.now + duration, id)
+        })
+    });
+
+    let inner = match deadline {
+        Some((deadline, id)) => SleepInner::Virtual { deadline, id },
+        None => SleepInner::Real(Box::pin(glib::timeout_future(duration))),
+    };
+    Sleep { inner }
+}
+
+/// Calls `func` on the main thread after `duration`.
+///
+/// Use this instead of [`glib::timeout_add_local_once()`] to make the timer
+/// controllable by a [`LoopDriver`](crate::test::LoopDriver).
+/// Abort the returned handle to cancel the timeout.
+pub fn timeout_once<F>(duration: Duration, func: F) -> glib::JoinHandle<()>
+where
+    F: FnOnce() + 'static,
+{
+    let sleep = sleep(duration);
+    crate::spawn_local(async move {
+        sleep.await;
+        func();
+    })
+}
+
+/// Switches the current thread to virtual time starting at zero.
+///
+/// If the thread already uses virtual time, the clock is shared
+/// until [`stop_virtual_time()`] was called for every start.
+pub(crate) fn start_virtual_time() {
+    VIRTUAL_TIME.with(|time| {
+        time.borrow_mut()
+            .get_or_insert_with(VirtualTime::default)
+            .drivers += 1;
+    });
+}
+
+/// Switches the current thread back to real time and wakes all sleeping futures
+/// once the last driver stopped.
+pub(crate) fn stop_virtual_time() {
+    let timers = VIRTUAL_TIME.with(|time| {
+        let mut time = time.borrow_mut();
+        let virtual_time = time.as_mut()?;
+        virtual_time.drivers -= 1;
+        if virtual_time.drivers > 0 {
+            return None;
+        }
+        time.take().map(|time| time.timers)
+    });
+    for waker in timers.into_iter().flat_map(BTreeMap::into_values) {
+        waker.wake();
+    }
+}
+
+/// Returns the current virtual time.
+pub(crate) fn virtual_now() -> Duration {
+    VIRTUAL_TIME.with(|time| {
+        time.borrow()
+            .as_ref()
+            .map_or(Duration::ZERO, |time| time.now)
+    })
+}
+
+/// Moves the virtual clock to the next deadline that isn't after `limit`
+/// and wakes the futures that wait for it.
+///
+/// Returns [`false`] if there's no such deadline.
+pub(crate) fn advance_to_next_deadline(limit: Duration) -> bool {
+    let wakers = VIRTUAL_TIME.with(|time| {
+        let mut time = time.borrow_mut();
+        let time = time.as_mut()?;
+        let (&(deadline, _), _) = time.timers.first_key_value()?;
+        if deadline > limit {
+            return None;
+        }
+
+        time.now = deadline;
+        let later = time.timers.split_off(&(deadline, u64::MAX));
+        Some(std::mem::replace(&mut time.timers, later))
+    });
+
+    match wakers {
+        Some(wakers) => {
+            for waker in wakers.into_values() {
+                waker.wake();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Moves the virtual clock forward to `now`.
+pub(crate) fn set_virtual_now(now: Duration) {
+    VIRTUAL_TIME.with(|time| {
+        if let Some(time) = &mut *time.borrow_mut() {
+            time.now = time.now.max(now);
+        }
+    });
+}
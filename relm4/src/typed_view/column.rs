@@ -1,16 +1,23 @@
 //! Idiomatic and high-level abstraction over [`gtk::ColumnView`].
 
 use super::{get_mut_value, get_value, Filter, OrdFn, RelmSelectionExt, TypedListItem};
+use crate::Sender;
 use gtk::{
-    gio, glib,
-    prelude::{Cast, CastNone, IsA, ListItemExt, ListModelExt, ObjectExt},
+    gdk, gio, glib,
+    prelude::{
+        ActionMapExt, Cast, CastNone, CheckButtonExt, EditableExt, EventControllerExt, IsA,
+        ListItemExt, ListModelExt, ObjectExt, WidgetExt,
+    },
 };
 use std::{
     any::Any,
+    cell::{Cell, RefCell},
     cmp::Ordering,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{Debug, Display},
+    hash::Hash,
     marker::PhantomData,
+    rc::Rc,
 };
 
 /// An item of a [`TypedColumnView`].
@@ -107,6 +114,520 @@ where
     }
 }
 
+/// A widget that can be used to edit the cells of an [`EditableColumn`].
+pub trait CellEditor: IsA<gtk::Widget> {
+    /// The value that is edited.
+    type Value;
+
+    /// Whether the edit is committed when the editor loses focus.
+    const COMMIT_ON_FOCUS_LEAVE: bool = true;
+
+    /// Create a new editor widget.
+    fn new_editor() -> Self;
+
+    /// Show the given value in the editor.
+    fn set_editor_value(&self, value: &Self::Value);
+
+    /// Read the value from the editor.
+    ///
+    /// Returning [`None`] cancels the edit, for example because the input is invalid.
+    fn editor_value(&self) -> Option<Self::Value>;
+
+    /// Connect a callback that commits the edit as soon as the user changes the value.
+    ///
+    /// This is useful for editors that don't receive text input, such as [`gtk::DropDown`].
+    fn connect_commit<F: Fn() + 'static>(&self, _commit: F) {}
+}
+
+impl CellEditor for gtk::Entry {
+    type Value = String;
+
+    fn new_editor() -> Self {
+        Self::new()
+    }
+
+    fn set_editor_value(&self, value: &Self::Value) {
+        self.set_text(value);
+    }
+
+    fn editor_value(&self) -> Option<Self::Value> {
+        Some(self.text().into())
+    }
+}
+
+impl CellEditor for gtk::SpinButton {
+    type Value = f64;
+
+    fn new_editor() -> Self {
+        Self::with_range(f64::from(i32::MIN), f64::from(i32::MAX), 1.0)
+    }
+
+    fn set_editor_value(&self, value: &Self::Value) {
+        self.set_value(*value);
+    }
+
+    fn editor_value(&self) -> Option<Self::Value> {
+        self.update();
+        Some(self.value())
+    }
+}
+
+impl CellEditor for gtk::DropDown {
+    type Value = u32;
+
+    const COMMIT_ON_FOCUS_LEAVE: bool = false;
+
+    fn new_editor() -> Self {
+        Self::default()
+    }
+
+    fn set_editor_value(&self, value: &Self::Value) {
+        self.set_selected(*value);
+    }
+
+    fn editor_value(&self) -> Option<Self::Value> {
+        let selected = self.selected();
+        (selected != gtk::INVALID_LIST_POSITION).then_some(selected)
+    }
+
+    fn connect_commit<F: Fn() + 'static>(&self, commit: F) {
+        self.connect_selected_notify(move |_| commit());
+    }
+}
+
+/// A column of a [`TypedColumnView`] whose cells can be edited by the user.
+///
+/// Cells show the formatted value in a `gtk::Label` and switch to the
+/// [`EditableColumn::Editor`] while they are edited.
+pub trait EditableColumn: 'static {
+    /// Item of the model
+    type Item: Any;
+    /// Value of the column
+    type Value: PartialOrd + Display + Debug;
+    /// Widget used for editing the value
+    type Editor: CellEditor<Value = Self::Value>;
+
+    /// Name of the column
+    const COLUMN_NAME: &'static str;
+    /// Whether to enable the sorting for this column
+    const ENABLE_SORT: bool = false;
+    /// Whether to enable resizing for this column
+    const ENABLE_RESIZE: bool = false;
+    /// Whether to enable automatic expanding for this column
+    const ENABLE_EXPAND: bool = false;
+
+    /// Get the value that this column represents.
+    fn get_cell_value(item: &Self::Item) -> Self::Value;
+    /// Write an edited value back to the item.
+    fn set_cell_value(item: &mut Self::Item, value: Self::Value);
+    /// Format the value for presentation in the text cell.
+    fn format_cell_value(value: &Self::Value) -> String {
+        value.to_string()
+    }
+    /// Create the editor widget for a cell.
+    ///
+    /// Override this to configure the editor, for example
+    /// to set the model of a [`gtk::DropDown`].
+    #[must_use]
+    fn create_editor() -> Self::Editor {
+        Self::Editor::new_editor()
+    }
+}
+
+/// Event emitted by a [`TypedColumnView`] once the value of an
+/// [`EditableColumn`] was edited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellEdited<V> {
+    /// Position of the edited item in the visible list
+    /// (see [`TypedColumnView::get_visible()`]).
+    pub row: u32,
+    /// Name of the edited column.
+    pub column: &'static str,
+    /// The new value of the cell.
+    pub value: V,
+}
+
+const LABEL_PAGE: &str = "label";
+const EDITOR_PAGE: &str = "editor";
+const EDIT_ACTION_GROUP: &str = "relm4-cell";
+const EDIT_ACTION: &str = "relm4-cell.edit";
+const EDITABLE_CELL_CSS: &str = "relm4-editable-cell";
+const EDITABLE_CELL_KEY: &str = "relm4-editable-cell";
+
+struct EditableCell<C: EditableColumn> {
+    list_item: glib::WeakRef<gtk::ListItem>,
+    store: glib::WeakRef<gio::ListStore>,
+    stack: gtk::Stack,
+    label: gtk::Label,
+    editor: C::Editor,
+    on_edited: Box<dyn Fn(CellEdited<C::Value>)>,
+}
+
+impl<C: EditableColumn> EditableCell<C> {
+    fn new<F>(
+        list_item: &gtk::ListItem,
+        store: glib::WeakRef<gio::ListStore>,
+        on_edited: F,
+    ) -> Rc<Self>
+    where
+        F: Fn(CellEdited<C::Value>) + 'static,
+    {
+        let stack = gtk::Stack::new();
+        stack.add_css_class(EDITABLE_CELL_CSS);
+
+        let label = gtk::Label::new(None);
+        label.set_xalign(0.0);
+        stack.add_named(&label, Some(LABEL_PAGE));
+
+        let editor = C::create_editor();
+        stack.add_named(&editor, Some(EDITOR_PAGE));
+
+        let cell = Rc::new(Self {
+            list_item: list_item.downgrade(),
+            store,
+            stack,
+            label,
+            editor,
+            on_edited: Box::new(on_edited),
+        });
+        cell.connect_signals();
+        cell
+    }
+
+    fn connect_signals(self: &Rc<Self>) {
+        let group = gio::SimpleActionGroup::new();
+        let edit = gio::SimpleAction::new("edit", None);
+        edit.connect_activate(glib::clone!(
+            #[weak(rename_to = cell)]
+            self,
+            move |_, _| cell.begin()
+        ));
+        group.add_action(&edit);
+        self.stack
+            .insert_action_group(EDIT_ACTION_GROUP, Some(&group));
+
+        let click = gtk::GestureClick::new();
+        click.connect_pressed(glib::clone!(
+            #[weak(rename_to = cell)]
+            self,
+            move |_, n_press, _, _| {
+                if n_press == 2 {
+                    cell.begin();
+                }
+            }
+        ));
+        self.label.add_controller(click);
+
+        let keys = gtk::EventControllerKey::new();
+        keys.set_propagation_phase(gtk::PropagationPhase::Capture);
+        keys.connect_key_pressed(glib::clone!(
+            #[weak(rename_to = cell)]
+            self,
+            #[upgrade_or]
+            glib::Propagation::Proceed,
+            move |_, key, _, _| match key {
+                gdk::Key::Return | gdk::Key::KP_Enter => {
+                    cell.commit();
+                    glib::Propagation::Stop
+                }
+                gdk::Key::Tab | gdk::Key::ISO_Left_Tab => {
+                    // Look up the next cell before the store is notified about the edit,
+                    // because the notification can rebind the row.
+                    let adjacent = cell.adjacent(key == gdk::Key::Tab);
+                    cell.commit();
+                    if let Some(adjacent) = adjacent {
+                        adjacent.activate_action(EDIT_ACTION, None).ok();
+                    }
+                    glib::Propagation::Stop
+                }
+                gdk::Key::Escape => {
+                    cell.cancel();
+                    glib::Propagation::Stop
+                }
+                _ => glib::Propagation::Proceed,
+            }
+        ));
+        self.editor.add_controller(keys);
+
+        if C::Editor::COMMIT_ON_FOCUS_LEAVE {
+            let focus = gtk::EventControllerFocus::new();
+            focus.connect_leave(glib::clone!(
+                #[weak(rename_to = cell)]
+                self,
+                move |_| cell.commit()
+            ));
+            self.editor.add_controller(focus);
+        }
+
+        self.editor.connect_commit(glib::clone!(
+            #[weak(rename_to = cell)]
+            self,
+            move || cell.commit()
+        ));
+    }
+
+    fn is_editing(&self) -> bool {
+        self.stack.visible_child_name().as_deref() == Some(EDITOR_PAGE)
+    }
+
+    fn begin(&self) {
+        let Some(obj) = self.list_item.upgrade().and_then(|item| item.item()) else {
+            return;
+        };
+
+        let value = C::get_cell_value(&get_value::<C::Item>(&obj));
+        self.editor.set_editor_value(&value);
+        self.stack.set_visible_child_name(EDITOR_PAGE);
+        self.editor.grab_focus();
+    }
+
+    fn commit(&self) {
+        if !self.is_editing() {
+            return;
+        }
+        self.stack.set_visible_child_name(LABEL_PAGE);
+
+        let Some(list_item) = self.list_item.upgrade() else {
+            return;
+        };
+        let (Some(obj), Some(value)) = (list_item.item(), self.editor.editor_value()) else {
+            return;
+        };
+
+        let row = list_item.position();
+        let value = {
+            let mut item = get_mut_value::<C::Item>(&obj);
+            C::set_cell_value(&mut item, value);
+            C::get_cell_value(&item)
+        };
+        self.label.set_label(&C::format_cell_value(&value));
+
+        // Notify the store, so other columns, sorters and filters see the new value.
+        if let Some(store) = self.store.upgrade() {
+            if let Some(position) = store.find(&obj) {
+                store.items_changed(position, 1, 1);
+            }
+        }
+
+        (self.on_edited)(CellEdited {
+            row,
+            column: C::COLUMN_NAME,
+            value,
+        });
+    }
+
+    fn cancel(&self) {
+        self.stack.set_visible_child_name(LABEL_PAGE);
+    }
+
+    /// Returns the next or previous editable cell in the same row.
+    fn adjacent(&self, forward: bool) -> Option<gtk::Widget> {
+        let mut cell = self.stack.parent();
+        while let Some(current) = cell {
+            cell = if forward {
+                current.next_sibling()
+            } else {
+                current.prev_sibling()
+            };
+
+            if let Some(child) = cell.as_ref().and_then(WidgetExt::first_child) {
+                if child.has_css_class(EDITABLE_CELL_CSS) {
+                    return Some(child);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A column of a [`TypedColumnView`] that shows a check button
+/// for selecting items (see [`TypedColumnView::append_selection_column()`]).
+///
+/// In contrast to the selection model of the view, the selection
+/// is stored as a set of keys that identify the items.
+pub trait SelectionColumn: 'static {
+    /// Item of the model
+    type Item: Any;
+    /// Key that identifies an item in the selection set
+    type Key: Clone + Eq + Hash + Debug + 'static;
+
+    /// Name of the column
+    const COLUMN_NAME: &'static str = "";
+
+    /// Get the key of an item.
+    fn key(item: &Self::Item) -> Self::Key;
+}
+
+struct CheckSelectionState<C: SelectionColumn> {
+    selected: RefCell<HashSet<C::Key>>,
+    store: gio::ListStore,
+    /// The items that pass the filters of the view.
+    visible: gio::ListModel,
+    header: gtk::CheckButton,
+    rows: RefCell<Vec<glib::WeakRef<gtk::ListItem>>>,
+    updating: Cell<bool>,
+    on_changed: Box<dyn Fn(HashSet<C::Key>)>,
+}
+
+impl<C: SelectionColumn> CheckSelectionState<C> {
+    fn key_of(list_item: &gtk::ListItem) -> Option<C::Key> {
+        list_item
+            .item()
+            .map(|obj| C::key(&get_value::<C::Item>(&obj)))
+    }
+
+    fn all_keys(&self) -> Vec<C::Key> {
+        Self::keys_of(self.store.upcast_ref())
+    }
+
+    fn visible_keys(&self) -> Vec<C::Key> {
+        Self::keys_of(&self.visible)
+    }
+
+    fn keys_of(model: &gio::ListModel) -> Vec<C::Key> {
+        (0..model.n_items())
+            .filter_map(|position| model.item(position))
+            .map(|obj| C::key(&get_value::<C::Item>(&obj)))
+            .collect()
+    }
+
+    fn update_row(&self, list_item: &gtk::ListItem) {
+        if let (Some(check), Some(key)) = (
+            list_item.child().and_downcast::<gtk::CheckButton>(),
+            Self::key_of(list_item),
+        ) {
+            self.updating.set(true);
+            check.set_active(self.selected.borrow().contains(&key));
+            self.updating.set(false);
+        }
+    }
+
+    /// Returns the number of selected visible items and the number of visible items.
+    fn count(&self) -> (usize, usize) {
+        let keys = self.visible_keys();
+        let selected = self.selected.borrow();
+        let selected_count = keys.iter().filter(|key| selected.contains(key)).count();
+        (selected_count, keys.len())
+    }
+
+    fn all_selected(&self) -> bool {
+        let (selected, all) = self.count();
+        all > 0 && selected == all
+    }
+
+    /// Shows whether none, some or all visible items are selected.
+    fn update_header(&self) {
+        let (selected, all) = self.count();
+        let all_selected = all > 0 && selected == all;
+        let some_selected = !all_selected && selected > 0;
+
+        // A mixed header stays inactive, so clicking it selects all items.
+        self.updating.set(true);
+        self.header.set_inconsistent(some_selected);
+        self.header.set_active(all_selected);
+        self.updating.set(false);
+    }
+
+    fn changed(&self) {
+        self.rows.borrow_mut().retain(|row| {
+            if let Some(list_item) = row.upgrade() {
+                self.update_row(&list_item);
+                true
+            } else {
+                false
+            }
+        });
+        self.update_header();
+        (self.on_changed)(self.selected.borrow().clone());
+    }
+}
+
+/// Handle to the selection of a column added with
+/// [`TypedColumnView::append_selection_column()`].
+pub struct CheckSelection<C: SelectionColumn> {
+    state: Rc<CheckSelectionState<C>>,
+}
+
+impl<C: SelectionColumn> Debug for CheckSelection<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckSelection")
+            .field("selected", &self.state.selected)
+            .field("header", &self.state.header)
+            .finish()
+    }
+}
+
+impl<C: SelectionColumn> Clone for CheckSelection<C> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<C: SelectionColumn> CheckSelection<C> {
+    /// The tri-state check button for selecting all or no visible items.
+    ///
+    /// It is active if all items that pass the filters of the view are selected
+    /// and inconsistent if only some of them are selected.
+    /// Since column headers of [`gtk::ColumnView`] can only show text,
+    /// the check button needs to be placed by the application,
+    /// for example in a toolbar above the view.
+    #[must_use]
+    pub fn header_check_button(&self) -> &gtk::CheckButton {
+        &self.state.header
+    }
+
+    /// Returns the keys of all selected items.
+    #[must_use]
+    pub fn selected(&self) -> HashSet<C::Key> {
+        self.state.selected.borrow().clone()
+    }
+
+    /// Returns true if the item with the given key is selected.
+    #[must_use]
+    pub fn is_selected(&self, key: &C::Key) -> bool {
+        self.state.selected.borrow().contains(key)
+    }
+
+    /// Select or unselect the item with the given key.
+    pub fn set_selected(&self, key: C::Key, selected: bool) {
+        if selected {
+            self.state.selected.borrow_mut().insert(key);
+        } else {
+            self.state.selected.borrow_mut().remove(&key);
+        }
+        self.state.changed();
+    }
+
+    /// Select all items of the view that pass its filters.
+    pub fn select_all(&self) {
+        let keys = self.state.visible_keys();
+        self.state.selected.borrow_mut().extend(keys);
+        self.state.changed();
+    }
+
+    /// Unselect all items of the view that pass its filters.
+    ///
+    /// Selected items that are hidden by the filters stay selected.
+    pub fn unselect_visible(&self) {
+        let keys = self.state.visible_keys();
+        {
+            let mut selected = self.state.selected.borrow_mut();
+            for key in &keys {
+                selected.remove(key);
+            }
+        }
+        self.state.changed();
+    }
+
+    /// Unselect all items.
+    pub fn unselect_all(&self) {
+        self.state.selected.borrow_mut().clear();
+        self.state.changed();
+    }
+}
+
 /// A high-level wrapper around [`gio::ListStore`],
 /// [`gtk::SignalListItemFactory`] and [`gtk::ColumnView`].
 ///
@@ -245,11 +766,245 @@ where
             C::teardown(list_item);
         });
 
-        let sort_fn = C::sort_fn();
+        self.push_column(
+            C::COLUMN_NAME,
+            factory,
+            C::ENABLE_RESIZE,
+            C::ENABLE_EXPAND,
+            C::sort_fn(),
+        );
+    }
+
+    /// Append an editable column to this typed view.
+    ///
+    /// Editing starts with a double click on a cell and is committed
+    /// with <kbd>Enter</kbd> or canceled with <kbd>Escape</kbd>.
+    /// <kbd>Tab</kbd> and <kbd>Shift</kbd>+<kbd>Tab</kbd> commit the edit
+    /// and continue editing the next or previous editable cell of the row.
+    ///
+    /// After the value was written back to the item with
+    /// [`EditableColumn::set_cell_value()`], the view is notified about the
+    /// changed item, so other columns, sorters and filters are updated.
+    /// Then, a [`CellEdited`] event is transformed and sent to the designated sender.
+    pub fn append_editable_column<C, M, F>(&mut self, sender: &Sender<M>, transform: F)
+    where
+        C: EditableColumn<Item = T>,
+        M: 'static,
+        F: Fn(CellEdited<C::Value>) -> M + 'static,
+    {
+        let sender = sender.clone();
+        let transform = Rc::new(transform);
+        let store = self.store.downgrade();
+
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(move |_, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Needs to be ListItem");
+
+            let sender = sender.clone();
+            let transform = transform.clone();
+            let cell = EditableCell::<C>::new(list_item, store.clone(), move |event| {
+                sender.emit(transform(event));
+            });
+            list_item.set_child(Some(&cell.stack));
+
+            // The list item keeps the cell alive, the cell only holds a weak reference to it.
+            unsafe { list_item.set_data(EDITABLE_CELL_KEY, cell) };
+        });
+
+        factory.connect_bind(move |_, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Needs to be ListItem");
+
+            let stack = list_item.child().and_downcast::<gtk::Stack>().unwrap();
+            let obj = list_item.item().unwrap();
+            let value = get_value::<T>(&obj);
+
+            // Recycled cells might still show an editor.
+            stack.set_visible_child_name(LABEL_PAGE);
+            let label = stack
+                .child_by_name(LABEL_PAGE)
+                .and_downcast::<gtk::Label>()
+                .unwrap();
+            label.set_label(&C::format_cell_value(&C::get_cell_value(&value)));
+        });
+
+        factory.connect_teardown(move |_, list_item| {
+            unsafe { list_item.steal_data::<Rc<EditableCell<C>>>(EDITABLE_CELL_KEY) };
+        });
 
-        let c = gtk::ColumnViewColumn::new(Some(C::COLUMN_NAME), Some(factory));
-        c.set_resizable(C::ENABLE_RESIZE);
-        c.set_expand(C::ENABLE_EXPAND);
+        let sort_fn: OrdFn<T> = if C::ENABLE_SORT {
+            Some(Box::new(|a, b| {
+                let a = C::get_cell_value(a);
+                let b = C::get_cell_value(b);
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            }))
+        } else {
+            None
+        };
+
+        self.push_column(
+            C::COLUMN_NAME,
+            factory,
+            C::ENABLE_RESIZE,
+            C::ENABLE_EXPAND,
+            sort_fn,
+        );
+    }
+
+    /// Append a column with check buttons for selecting items.
+    ///
+    /// Each time the selection changes, the set of selected keys is transformed
+    /// and sent to the designated sender.
+    /// Keys of items that are removed from the view are removed from the selection.
+    ///
+    /// The returned [`CheckSelection`] gives access to the selection
+    /// and the tri-state check button for selecting all or no items.
+    pub fn append_selection_column<C, M, F>(
+        &mut self,
+        sender: &Sender<M>,
+        transform: F,
+    ) -> CheckSelection<C>
+    where
+        C: SelectionColumn<Item = T>,
+        M: 'static,
+        F: Fn(HashSet<C::Key>) -> M + 'static,
+    {
+        let sender = sender.clone();
+        let state = Rc::new(CheckSelectionState::<C> {
+            selected: RefCell::default(),
+            store: self.store.clone(),
+            visible: self
+                .selection_model
+                .upcast_ref::<gtk::SelectionModel>()
+                .clone()
+                .upcast(),
+            header: gtk::CheckButton::new(),
+            rows: RefCell::default(),
+            updating: Cell::new(false),
+            on_changed: Box::new(move |selected| sender.emit(transform(selected))),
+        });
+
+        let selection = CheckSelection {
+            state: state.clone(),
+        };
+
+        state.header.connect_toggled(glib::clone!(
+            #[weak]
+            state,
+            move |_| {
+                if !state.updating.get() {
+                    let all_selected = state.all_selected();
+                    let selection = CheckSelection { state };
+                    if all_selected {
+                        selection.unselect_visible();
+                    } else {
+                        selection.select_all();
+                    }
+                }
+            }
+        ));
+
+        self.store.connect_items_changed(glib::clone!(
+            #[weak]
+            state,
+            move |_, _, removed, _| {
+                if removed > 0 {
+                    let keys: HashSet<C::Key> = state.all_keys().into_iter().collect();
+                    let len = state.selected.borrow().len();
+                    state.selected.borrow_mut().retain(|key| keys.contains(key));
+
+                    if state.selected.borrow().len() != len {
+                        state.changed();
+                    }
+                }
+            }
+        ));
+
+        // The header only reflects the visible items, which change with the filters.
+        state.visible.connect_items_changed(glib::clone!(
+            #[weak]
+            state,
+            move |_, _, _, _| state.update_header()
+        ));
+        state.update_header();
+
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(glib::clone!(
+            #[strong]
+            state,
+            move |_, list_item| {
+                let list_item = list_item
+                    .downcast_ref::<gtk::ListItem>()
+                    .expect("Needs to be ListItem");
+
+                let check = gtk::CheckButton::new();
+                check.connect_toggled(glib::clone!(
+                    #[weak]
+                    state,
+                    #[weak]
+                    list_item,
+                    move |check| {
+                        if state.updating.get() {
+                            return;
+                        }
+                        if let Some(key) = CheckSelectionState::<C>::key_of(&list_item) {
+                            if check.is_active() {
+                                state.selected.borrow_mut().insert(key);
+                            } else {
+                                state.selected.borrow_mut().remove(&key);
+                            }
+                            state.changed();
+                        }
+                    }
+                ));
+
+                list_item.set_child(Some(&check));
+                state.rows.borrow_mut().push(list_item.downgrade());
+            }
+        ));
+
+        factory.connect_bind(glib::clone!(
+            #[strong]
+            state,
+            move |_, list_item| {
+                let list_item = list_item
+                    .downcast_ref::<gtk::ListItem>()
+                    .expect("Needs to be ListItem");
+
+                state.update_row(list_item);
+            }
+        ));
+
+        factory.connect_teardown(glib::clone!(
+            #[strong]
+            state,
+            move |_, _| {
+                state
+                    .rows
+                    .borrow_mut()
+                    .retain(|row| row.upgrade().is_some());
+            }
+        ));
+
+        self.push_column(C::COLUMN_NAME, factory, false, false, None);
+
+        selection
+    }
+
+    fn push_column(
+        &mut self,
+        name: &'static str,
+        factory: gtk::SignalListItemFactory,
+        resizable: bool,
+        expand: bool,
+        sort_fn: OrdFn<T>,
+    ) {
+        let c = gtk::ColumnViewColumn::new(Some(name), Some(factory));
+        c.set_resizable(resizable);
+        c.set_expand(expand);
 
         if let Some(sort_fn) = sort_fn {
             c.set_sorter(Some(&gtk::CustomSorter::new(move |first, second| {
@@ -261,7 +1016,7 @@ where
         }
 
         self.view.append_column(&c);
-        self.columns.insert(C::COLUMN_NAME, c);
+        self.columns.insert(name, c);
     }
 
     /// Add a function to filter the stored items.
@@ -413,3 +1168,5 @@ where
         self.store.remove_all();
     }
 }
+
+super::impl_typed_view!(TypedColumnView, Any);
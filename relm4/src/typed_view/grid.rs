@@ -332,3 +332,5 @@ where
         self.store.remove_all();
     }
 }
+
+super::impl_typed_view!(TypedGridView, RelmGridItem);
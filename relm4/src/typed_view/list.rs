@@ -3,9 +3,9 @@
 use super::{get_mut_value, get_value, Filter, OrdFn, RelmSelectionExt, TypedListItem};
 use gtk::{
     gio, glib,
-    prelude::{Cast, CastNone, IsA, ListItemExt, ListModelExt, ObjectExt},
+    prelude::{BoxExt, Cast, CastNone, IsA, ListItemExt, ListModelExt, ObjectExt, WidgetExt},
 };
-use std::{any::Any, cmp::Ordering, marker::PhantomData};
+use std::{any::Any, cell::Cell, cmp::Ordering, marker::PhantomData, rc::Rc};
 
 /// An item of a [`TypedListView`].
 pub trait RelmListItem: Any {
@@ -15,6 +15,13 @@ pub trait RelmListItem: Any {
     /// The widgets created for the list item.
     type Widgets;
 
+    /// Whether rows can be expanded to show a detail widget
+    /// below the widgets created in [`RelmListItem::setup()`].
+    ///
+    /// Rows are expanded and collapsed when they are activated
+    /// or by calling [`TypedListView::set_expanded()`].
+    const EXPANDABLE: bool = false;
+
     /// Construct the widgets.
     fn setup(list_item: &gtk::ListItem) -> (Self::Root, Self::Widgets);
 
@@ -26,6 +33,105 @@ pub trait RelmListItem: Any {
 
     /// Undo the steps of [`RelmListItem::setup()`] if necessary.
     fn teardown(_list_item: &gtk::ListItem) {}
+
+    /// Create the detail widget that is revealed once the row is expanded.
+    ///
+    /// This method is called lazily when the item is expanded for the first time
+    /// and the widget is kept for the lifetime of the item.
+    /// If the detail widget is the root of a component, store its controller
+    /// in the item to keep the component alive.
+    ///
+    /// Returning [`None`] means that the item can't be expanded.
+    fn detail_widget(&mut self) -> Option<gtk::Widget> {
+        None
+    }
+}
+
+const EXPANDED_KEY: &str = "relm4-expanded";
+const DETAIL_KEY: &str = "relm4-detail";
+const REVEALER_KEY: &str = "relm4-revealer";
+
+/// Returns the root widget created by [`RelmListItem::setup()`].
+fn item_root<T: RelmListItem>(list_item: &gtk::ListItem) -> T::Root {
+    let child = list_item.child();
+    let root = if T::EXPANDABLE {
+        child.and_then(|container| container.first_child())
+    } else {
+        child
+    };
+    root.and_downcast::<T::Root>().unwrap()
+}
+
+fn item_revealer(list_item: &gtk::ListItem) -> gtk::Revealer {
+    list_item
+        .child()
+        .and_then(|container| container.last_child())
+        .and_downcast::<gtk::Revealer>()
+        .unwrap()
+}
+
+fn is_expanded(obj: &glib::Object) -> bool {
+    unsafe {
+        obj.data::<bool>(EXPANDED_KEY)
+            .is_some_and(|expanded| *expanded.as_ref())
+    }
+}
+
+fn detail_of(obj: &glib::Object) -> Option<gtk::Widget> {
+    unsafe {
+        obj.data::<gtk::Widget>(DETAIL_KEY)
+            .map(|detail| detail.as_ref().clone())
+    }
+}
+
+fn show_detail(revealer: &gtk::Revealer, detail: Option<&gtk::Widget>) {
+    if let Some(detail) = detail {
+        // The detail widget might still be attached to a recycled row.
+        if let Some(parent) = detail.parent().and_downcast::<gtk::Revealer>() {
+            if &parent != revealer {
+                parent.set_child(None::<&gtk::Widget>);
+            }
+        }
+    }
+    revealer.set_child(detail);
+}
+
+fn set_item_expanded<T: RelmListItem>(
+    store: &gio::ListStore,
+    obj: &glib::Object,
+    expanded: bool,
+    exclusive: bool,
+) {
+    let detail = match detail_of(obj) {
+        Some(detail) => detail,
+        None if expanded => {
+            let Some(detail) = get_mut_value::<T>(obj).detail_widget() else {
+                return;
+            };
+            unsafe { obj.set_data(DETAIL_KEY, detail.clone()) };
+            detail
+        }
+        // Items that were never expanded have nothing to collapse.
+        None => return,
+    };
+
+    if expanded && exclusive {
+        for position in 0..store.n_items() {
+            if let Some(other) = store.item(position) {
+                if &other != obj && is_expanded(&other) {
+                    set_item_expanded::<T>(store, &other, false, false);
+                }
+            }
+        }
+    }
+
+    unsafe { obj.set_data(EXPANDED_KEY, expanded) };
+
+    if let Some(revealer) = unsafe { obj.data::<gtk::Revealer>(REVEALER_KEY) } {
+        let revealer = unsafe { revealer.as_ref() };
+        show_detail(revealer, Some(&detail));
+        revealer.set_reveal_child(expanded);
+    }
 }
 
 /// A high-level wrapper around [`gio::ListStore`],
@@ -43,6 +149,7 @@ pub struct TypedListView<T, S> {
     filters: Vec<Filter>,
     active_model: gio::ListModel,
     base_model: gio::ListModel,
+    exclusive_expansion: Rc<Cell<bool>>,
     _ty: PhantomData<*const T>,
 }
 
@@ -55,6 +162,7 @@ impl<T: std::fmt::Debug, S: std::fmt::Debug> std::fmt::Debug for TypedListView<T
             .field("active_model", &self.active_model)
             .field("base_model", &self.base_model)
             .field("selection_model", &self.selection_model)
+            .field("exclusive_expansion", &self.exclusive_expansion)
             .finish()
     }
 }
@@ -104,7 +212,19 @@ where
 
             let (root, widgets) = T::setup(list_item);
             unsafe { root.set_data("widgets", widgets) };
-            list_item.set_child(Some(&root));
+
+            if T::EXPANDABLE {
+                let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+                container.append(&root);
+
+                let revealer = gtk::Revealer::new();
+                revealer.set_transition_type(gtk::RevealerTransitionType::SlideDown);
+                container.append(&revealer);
+
+                list_item.set_child(Some(&container));
+            } else {
+                list_item.set_child(Some(&root));
+            }
         });
 
         factory.connect_bind(move |_, list_item| {
@@ -112,19 +232,24 @@ where
                 .downcast_ref::<gtk::ListItem>()
                 .expect("Needs to be ListItem");
 
-            let widget = list_item
-                .downcast_ref::<gtk::ListItem>()
-                .expect("Needs to be ListItem")
-                .child();
-
             let obj = list_item.item().unwrap();
-            let mut obj = get_mut_value::<T>(&obj);
-
-            let mut root = widget.and_downcast::<T::Root>().unwrap();
+            let mut root = item_root::<T>(list_item);
 
             let mut widgets = unsafe { root.steal_data("widgets") }.unwrap();
-            obj.bind(&mut widgets, &mut root);
+            get_mut_value::<T>(&obj).bind(&mut widgets, &mut root);
             unsafe { root.set_data("widgets", widgets) };
+
+            if T::EXPANDABLE {
+                let revealer = item_revealer(list_item);
+
+                // Restore the expansion state of the item without animation.
+                revealer.set_transition_type(gtk::RevealerTransitionType::None);
+                show_detail(&revealer, detail_of(&obj).as_ref());
+                revealer.set_reveal_child(is_expanded(&obj));
+                revealer.set_transition_type(gtk::RevealerTransitionType::SlideDown);
+
+                unsafe { obj.set_data(REVEALER_KEY, revealer) };
+            }
         });
 
         factory.connect_unbind(move |_, list_item| {
@@ -132,19 +257,18 @@ where
                 .downcast_ref::<gtk::ListItem>()
                 .expect("Needs to be ListItem");
 
-            let widget = list_item
-                .downcast_ref::<gtk::ListItem>()
-                .expect("Needs to be ListItem")
-                .child();
-
             let obj = list_item.item().unwrap();
-            let mut obj = get_mut_value::<T>(&obj);
-
-            let mut root = widget.and_downcast::<T::Root>().unwrap();
+            let mut root = item_root::<T>(list_item);
 
             let mut widgets = unsafe { root.steal_data("widgets") }.unwrap();
-            obj.unbind(&mut widgets, &mut root);
+            get_mut_value::<T>(&obj).unbind(&mut widgets, &mut root);
             unsafe { root.set_data("widgets", widgets) };
+
+            if T::EXPANDABLE {
+                if let Some(revealer) = unsafe { obj.steal_data::<gtk::Revealer>(REVEALER_KEY) } {
+                    revealer.set_child(None::<&gtk::Widget>);
+                }
+            }
         });
 
         factory.connect_teardown(move |_, list_item| {
@@ -176,17 +300,56 @@ where
         let selection_model = S::new_model(base_model.clone());
         let view = gtk::ListView::new(Some(selection_model.clone()), Some(factory));
 
+        let exclusive_expansion = Rc::new(Cell::new(false));
+
+        if T::EXPANDABLE {
+            let store = store.clone();
+            let exclusive = exclusive_expansion.clone();
+            view.connect_activate(move |view, position| {
+                if let Some(obj) = view.model().and_then(|model| model.item(position)) {
+                    set_item_expanded::<T>(&store, &obj, !is_expanded(&obj), exclusive.get());
+                }
+            });
+        }
+
         Self {
             store,
             view,
             filters: Vec::new(),
             active_model: base_model.clone(),
             base_model,
+            exclusive_expansion,
             _ty: PhantomData,
             selection_model,
         }
     }
 
+    /// Expand or collapse the item at the specified position.
+    ///
+    /// This has no effect if [`RelmListItem::EXPANDABLE`] is `false`
+    /// or the item doesn't provide a [`RelmListItem::detail_widget()`].
+    pub fn set_expanded(&self, position: u32, expanded: bool) {
+        if T::EXPANDABLE {
+            if let Some(obj) = self.store.item(position) {
+                set_item_expanded::<T>(&self.store, &obj, expanded, self.exclusive_expansion.get());
+            }
+        }
+    }
+
+    /// Returns true if the item at the specified position is expanded.
+    pub fn is_expanded(&self, position: u32) -> bool {
+        self.store
+            .item(position)
+            .is_some_and(|obj| is_expanded(&obj))
+    }
+
+    /// If `true`, expanding an item collapses all other items.
+    ///
+    /// By default, multiple items can be expanded at the same time.
+    pub fn set_exclusive_expansion(&self, exclusive: bool) {
+        self.exclusive_expansion.set(exclusive);
+    }
+
     /// Add a function to filter the stored items.
     /// Returning `false` will simply hide the item.
     ///
@@ -332,3 +495,5 @@ where
         self.store.remove_all();
     }
 }
+
+super::impl_typed_view!(TypedListView, RelmListItem);
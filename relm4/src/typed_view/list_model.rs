@@ -0,0 +1,364 @@
+//! A [`gio::ListModel`] for arbitrary Rust types.
+
+use super::{get_mut_value, get_value, TypedListItem, TypedView};
+use gtk::{
+    gio, glib,
+    prelude::{Cast, ListModelExt},
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Range, RangeBounds},
+};
+
+/// A list of arbitrary Rust values that can be used
+/// as [`gio::ListModel`] by any GTK widget.
+///
+/// The values don't need to be GObjects. Each value is stored
+/// inside a [`glib::BoxedAnyObject`], so the items can be accessed
+/// in list item factories with [`glib::BoxedAnyObject::borrow()`].
+///
+/// The interface is very similar to [`Vec`]. All structural changes
+/// are applied to the model immediately. Changes of the values themselves
+/// are tracked with [`Self::update()`] or [`Self::mark_changed()`]
+/// and announced to the widgets with [`Self::notify_changes()`],
+/// so widgets rebind the changed items.
+///
+/// ```
+/// # use relm4::gtk;
+/// # use relm4::typed_view::list_model::RelmListModel;
+/// # gtk::init().unwrap();
+/// let mut model: RelmListModel<u32> = (0..10).collect();
+/// model.splice(2..4, [20, 30, 40]);
+/// model.update(0, |value| *value += 1);
+/// model.notify_changes();
+///
+/// let list_view = gtk::ListView::new(Some(model.no_selection()), None::<gtk::ListItemFactory>);
+/// # drop(list_view);
+/// assert_eq!(model.to_vec(), [1, 1, 20, 30, 40, 4, 5, 6, 7, 8, 9]);
+/// ```
+pub struct RelmListModel<T> {
+    store: gio::ListStore,
+    changed: RefCell<BTreeSet<u32>>,
+    _ty: PhantomData<*const T>,
+}
+
+impl<T> Debug for RelmListModel<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelmListModel")
+            .field("store", &self.store)
+            .field("changed", &self.changed)
+            .finish()
+    }
+}
+
+impl<T: 'static> Default for RelmListModel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> FromIterator<T> for RelmListModel<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut model = Self::new();
+        model.extend_from_iter(iter);
+        model
+    }
+}
+
+impl<T: 'static> From<Vec<T>> for RelmListModel<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T> AsRef<gio::ListModel> for RelmListModel<T> {
+    fn as_ref(&self) -> &gio::ListModel {
+        self.store.upcast_ref()
+    }
+}
+
+impl<T: 'static> RelmListModel<T> {
+    /// Create an empty model.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            store: gio::ListStore::new::<glib::BoxedAnyObject>(),
+            changed: RefCell::default(),
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the underlying [`gio::ListStore`].
+    ///
+    /// Modifying the store directly bypasses the change tracking
+    /// and must only insert items of type [`glib::BoxedAnyObject`]
+    /// that contain a `T`.
+    #[must_use]
+    pub fn store(&self) -> &gio::ListStore {
+        &self.store
+    }
+
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> u32 {
+        self.store.n_items()
+    }
+
+    /// Returns true if the model is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the [`TypedListItem`] at the specified position.
+    ///
+    /// Returns [`None`] if the position is invalid.
+    /// Modifications through [`TypedListItem::borrow_mut()`]
+    /// are not tracked, use [`Self::update()`] instead.
+    #[must_use]
+    pub fn get(&self, position: u32) -> Option<TypedListItem<T>> {
+        self.store
+            .item(position)
+            .map(|obj| TypedListItem::new(obj.downcast::<glib::BoxedAnyObject>().unwrap()))
+    }
+
+    /// Add a new item at the end of the model.
+    pub fn push(&mut self, value: T) {
+        self.store.append(&glib::BoxedAnyObject::new(value));
+    }
+
+    /// Add new items from an iterator to the end of the model.
+    pub fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let objects: Vec<glib::BoxedAnyObject> =
+            values.into_iter().map(glib::BoxedAnyObject::new).collect();
+        self.store.extend_from_slice(&objects);
+    }
+
+    /// Insert an item at a specific position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is larger than the length of the model.
+    pub fn insert(&mut self, position: u32, value: T) {
+        assert!(position <= self.len(), "Position out of bounds");
+        self.shift_changes(position, 0, 1);
+        self.store
+            .insert(position, &glib::BoxedAnyObject::new(value));
+    }
+
+    /// Remove the item at a specific position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is out of bounds.
+    pub fn remove(&mut self, position: u32) {
+        assert!(position < self.len(), "Position out of bounds");
+        self.shift_changes(position, 1, 0);
+        self.store.remove(position);
+    }
+
+    /// Replace the items in `range` with the given values.
+    ///
+    /// The widgets are notified with a single signal, which is
+    /// much faster than removing and inserting the items one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn splice<R, I>(&mut self, range: R, values: I)
+    where
+        R: RangeBounds<u32>,
+        I: IntoIterator<Item = T>,
+    {
+        let Range { start, end } = self.range(range);
+        let objects: Vec<glib::BoxedAnyObject> =
+            values.into_iter().map(glib::BoxedAnyObject::new).collect();
+        self.shift_changes(start, end - start, objects.len() as u32);
+        self.store.splice(start, end - start, &objects);
+    }
+
+    /// Keep only the items for which `f` returns true.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut position = self.len();
+        while position > 0 {
+            position -= 1;
+            let keep = f(&get_value::<T>(&self.store.item(position).unwrap()));
+            if !keep {
+                self.remove(position);
+            }
+        }
+    }
+
+    /// Remove all items.
+    pub fn clear(&mut self) {
+        self.changed.borrow_mut().clear();
+        self.store.remove_all();
+    }
+
+    /// Modify the item at a specific position and mark it as changed.
+    ///
+    /// Returns [`None`] if the position is invalid.
+    pub fn update<F, R>(&self, position: u32, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let obj = self.store.item(position)?;
+        let result = f(&mut get_mut_value::<T>(&obj));
+        self.mark_changed(position);
+        Some(result)
+    }
+
+    /// Mark the item at a specific position as changed.
+    pub fn mark_changed(&self, position: u32) {
+        if position < self.len() {
+            self.changed.borrow_mut().insert(position);
+        }
+    }
+
+    /// Returns true if items were changed since the
+    /// last call of [`Self::notify_changes()`].
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !self.changed.borrow().is_empty()
+    }
+
+    /// Notify the widgets about all items that changed,
+    /// so they can bind the new values.
+    ///
+    /// Consecutive items are announced with a single signal.
+    pub fn notify_changes(&self) {
+        let changed = std::mem::take(&mut *self.changed.borrow_mut());
+
+        let mut range: Option<Range<u32>> = None;
+        for position in changed {
+            match &mut range {
+                Some(range) if range.end == position => range.end += 1,
+                _ => {
+                    if let Some(range) = range.replace(position..position + 1) {
+                        self.items_changed(range);
+                    }
+                }
+            }
+        }
+        if let Some(range) = range {
+            self.items_changed(range);
+        }
+    }
+
+    /// Clone all values into a [`Vec`].
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        (0..self.len())
+            .map(|position| get_value::<T>(&self.store.item(position).unwrap()).clone())
+            .collect()
+    }
+
+    /// Wrap the model in a [`gtk::NoSelection`].
+    #[must_use]
+    pub fn no_selection(&self) -> gtk::NoSelection {
+        gtk::NoSelection::new(Some(self.store.clone()))
+    }
+
+    /// Wrap the model in a [`gtk::SingleSelection`].
+    #[must_use]
+    pub fn single_selection(&self) -> gtk::SingleSelection {
+        gtk::SingleSelection::new(Some(self.store.clone()))
+    }
+
+    /// Wrap the model in a [`gtk::MultiSelection`].
+    #[must_use]
+    pub fn multi_selection(&self) -> gtk::MultiSelection {
+        gtk::MultiSelection::new(Some(self.store.clone()))
+    }
+
+    fn items_changed(&self, range: Range<u32>) {
+        let n_items = range.end - range.start;
+        self.store.items_changed(range.start, n_items, n_items);
+    }
+
+    fn range<R: RangeBounds<u32>>(&self, range: R) -> Range<u32> {
+        use std::ops::Bound;
+
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end + 1,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.len(),
+        };
+        assert!(
+            start <= end && end <= self.len(),
+            "Range {start}..{end} out of bounds"
+        );
+        start..end
+    }
+
+    /// Keep tracked changes at the correct positions
+    /// when items are removed or inserted.
+    fn shift_changes(&self, position: u32, removed: u32, added: u32) {
+        let mut changed = self.changed.borrow_mut();
+        if changed.is_empty() {
+            return;
+        }
+
+        *changed = changed
+            .iter()
+            .filter_map(|&index| {
+                if index < position {
+                    Some(index)
+                } else if index < position + removed {
+                    None
+                } else {
+                    Some(index - removed + added)
+                }
+            })
+            .collect();
+    }
+}
+
+impl<T: 'static> TypedView<T> for RelmListModel<T> {
+    fn len(&self) -> u32 {
+        Self::len(self)
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        Self::extend_from_iter(self, items);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RelmListModel;
+
+    #[gtk::test]
+    fn track_changes() {
+        let mut model: RelmListModel<u32> = (0..5).collect();
+
+        model.update(1, |value| *value = 10);
+        model.update(3, |value| *value = 30);
+        model.remove(0);
+        model.insert(0, 100);
+        model.splice(3..4, [1, 2]);
+        assert_eq!(model.to_vec(), [100, 10, 2, 1, 2, 4]);
+        assert_eq!(*model.changed.borrow(), [1].into());
+
+        model.notify_changes();
+        assert!(!model.has_changes());
+
+        model.retain(|value| value % 2 == 0);
+        assert_eq!(model.to_vec(), [100, 10, 2, 2, 4]);
+    }
+}
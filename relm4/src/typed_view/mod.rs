@@ -3,7 +3,11 @@
 pub mod column;
 pub mod grid;
 pub mod list;
+pub mod list_model;
 mod selection_ext;
+#[cfg(feature = "rusqlite")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rusqlite")))]
+pub mod sql;
 
 use self::selection_ext::RelmSelectionExt;
 use gtk::{glib, prelude::Cast};
@@ -20,6 +24,47 @@ struct Filter {
     filter: gtk::CustomFilter,
     model: gtk::FilterListModel,
 }
+
+/// Common interface of the typed views to manage their items.
+///
+/// This allows data providers such as `sql::SqlListAdapter`
+/// to fill any of the typed views.
+#[allow(clippy::len_without_is_empty)]
+pub trait TypedView<T> {
+    /// Returns the number of items (without filters).
+    fn len(&self) -> u32;
+
+    /// Add new items from an iterator to the end of the view.
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I);
+
+    /// Remove all items.
+    fn clear(&mut self);
+}
+
+macro_rules! impl_typed_view {
+    ($view:ident, $item_trait:path) => {
+        impl<T, S> $crate::typed_view::TypedView<T> for $view<T, S>
+        where
+            T: $item_trait,
+            S: $crate::typed_view::RelmSelectionExt,
+        {
+            fn len(&self) -> u32 {
+                Self::len(self)
+            }
+
+            fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+                Self::extend_from_iter(self, items);
+            }
+
+            fn clear(&mut self) {
+                Self::clear(self);
+            }
+        }
+    };
+}
+
+use impl_typed_view;
+
 /// And item of a [`list::TypedListView`].
 ///
 /// The interface is very similar to [`std::cell::RefCell`].
@@ -0,0 +1,213 @@
+//! Show the results of SQL queries in typed views.
+//!
+//! The [`SqlListAdapter`] loads the rows of a [`SqlQuery`] page by page
+//! into any [`TypedView`] and can poll the database for changes.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk::glib;
+use rusqlite::{Connection, Row};
+
+use super::TypedView;
+use crate::Sender;
+
+/// A query whose rows are mapped to the items of a typed view.
+pub trait SqlQuery: 'static {
+    /// The item type of the typed view.
+    type Item: 'static;
+
+    /// The `SELECT` statement that returns the rows.
+    ///
+    /// The statement must not contain a `LIMIT` clause or
+    /// a trailing semicolon because the adapter appends
+    /// `LIMIT` and `OFFSET` to load the rows page by page.
+    const QUERY: &'static str;
+
+    /// Convert a row into an item.
+    fn map_row(row: &Row<'_>) -> rusqlite::Result<Self::Item>;
+}
+
+/// Identifies the state of the database to detect changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DataVersion {
+    /// Changes by other connections.
+    data_version: i64,
+    /// Changes by this connection.
+    total_changes: i64,
+}
+
+/// Loads the rows of a [`SqlQuery`] into typed views.
+///
+/// Rows are loaded in pages of a fixed size with [`Self::load_more()`],
+/// for example whenever a [`gtk::ScrolledWindow`] reaches its end
+/// (see [`Self::connect_load_more()`]).
+/// Changes of the database can be observed with [`Self::watch_changes()`]
+/// and applied with [`Self::refresh()`].
+pub struct SqlListAdapter<Q> {
+    connection: Rc<Connection>,
+    page_size: u32,
+    loaded: u32,
+    version: Rc<Cell<Option<DataVersion>>>,
+    _query: PhantomData<Q>,
+}
+
+impl<Q> std::fmt::Debug for SqlListAdapter<Q> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlListAdapter")
+            .field("connection", &self.connection)
+            .field("page_size", &self.page_size)
+            .field("loaded", &self.loaded)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Q: SqlQuery> SqlListAdapter<Q> {
+    /// Create a new adapter that loads `page_size` rows at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is `0`.
+    #[must_use]
+    pub fn new(connection: Connection, page_size: u32) -> Self {
+        assert!(page_size > 0, "The page size must not be zero");
+
+        Self {
+            connection: Rc::new(connection),
+            page_size,
+            loaded: 0,
+            version: Rc::default(),
+            _query: PhantomData,
+        }
+    }
+
+    /// Returns the connection of the adapter, for example to modify the database.
+    #[must_use]
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Returns the number of rows loaded into the view.
+    #[must_use]
+    pub fn loaded(&self) -> u32 {
+        self.loaded
+    }
+
+    /// Returns the total number of rows returned by the query.
+    pub fn count(&self) -> rusqlite::Result<u32> {
+        let query = format!("SELECT COUNT(*) FROM ({})", Q::QUERY);
+        self.connection.query_row(&query, [], |row| row.get(0))
+    }
+
+    /// Load `limit` rows starting at `offset`.
+    pub fn rows(&self, offset: u32, limit: u32) -> rusqlite::Result<Vec<Q::Item>> {
+        let query = format!("{} LIMIT ?1 OFFSET ?2", Q::QUERY);
+        let mut statement = self.connection.prepare_cached(&query)?;
+        let rows = statement.query_map([limit, offset], |row| Q::map_row(row))?;
+        rows.collect()
+    }
+
+    /// Append the next page of rows to the view.
+    ///
+    /// Returns `false` if all rows were already loaded.
+    pub fn load_more<V: TypedView<Q::Item>>(&mut self, view: &mut V) -> rusqlite::Result<bool> {
+        if self.loaded == 0 {
+            self.version.set(Some(self.data_version()?));
+        }
+
+        let items = self.rows(self.loaded, self.page_size)?;
+        let len = items.len() as u32;
+        view.extend_from_iter(items);
+        self.loaded += len;
+
+        Ok(len > 0)
+    }
+
+    /// Reload all rows that were loaded so far, at least one page.
+    pub fn refresh<V: TypedView<Q::Item>>(&mut self, view: &mut V) -> rusqlite::Result<()> {
+        self.version.set(Some(self.data_version()?));
+
+        let items = self.rows(0, self.loaded.max(self.page_size))?;
+        self.loaded = items.len() as u32;
+        view.clear();
+        view.extend_from_iter(items);
+
+        Ok(())
+    }
+
+    /// Returns `true` if the database changed since the rows were loaded.
+    pub fn has_changed(&self) -> rusqlite::Result<bool> {
+        Ok(self.version.get() != Some(self.data_version()?))
+    }
+
+    /// Poll the database for changes and send a message if it changed
+    /// since the rows were loaded.
+    ///
+    /// Usually, the message should trigger a call of [`Self::refresh()`].
+    /// Polling stops once the returned [`glib::SourceId`] is removed
+    /// or the receiver of the messages is dropped.
+    pub fn watch_changes<M, F>(
+        &self,
+        interval: Duration,
+        sender: &Sender<M>,
+        message: F,
+    ) -> glib::SourceId
+    where
+        M: 'static,
+        F: Fn() -> M + 'static,
+    {
+        let connection = Rc::clone(&self.connection);
+        let version = Rc::clone(&self.version);
+        let sender = sender.clone();
+
+        glib::timeout_add_local(interval, move || {
+            match read_data_version(&connection) {
+                Ok(current) => {
+                    if version.get().is_some_and(|loaded| loaded != current) {
+                        // Only notify once per change.
+                        version.set(Some(current));
+                        if sender.send(message()).is_err() {
+                            return glib::ControlFlow::Break;
+                        }
+                    }
+                }
+                Err(err) => tracing::error!("Couldn't poll database for changes: {err}"),
+            }
+            glib::ControlFlow::Continue
+        })
+    }
+
+    /// Send a message whenever the scrolled window reaches its end,
+    /// so the next page can be loaded with [`Self::load_more()`].
+    pub fn connect_load_more<M, F>(
+        scrolled_window: &gtk::ScrolledWindow,
+        sender: &Sender<M>,
+        message: F,
+    ) -> glib::SignalHandlerId
+    where
+        M: 'static,
+        F: Fn() -> M + 'static,
+    {
+        let sender = sender.clone();
+        scrolled_window.connect_edge_reached(move |_, position| {
+            if position == gtk::PositionType::Bottom {
+                sender.emit(message());
+            }
+        })
+    }
+
+    fn data_version(&self) -> rusqlite::Result<DataVersion> {
+        read_data_version(&self.connection)
+    }
+}
+
+fn read_data_version(connection: &Connection) -> rusqlite::Result<DataVersion> {
+    let data_version = connection.query_row("PRAGMA data_version", [], |row| row.get(0))?;
+    let total_changes = connection.query_row("SELECT total_changes()", [], |row| row.get(0))?;
+    Ok(DataVersion {
+        data_version,
+        total_changes,
+    })
+}
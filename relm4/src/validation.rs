@@ -0,0 +1,517 @@
+//! Validation and input masks for text inputs.
+//!
+//! A [`Validator`] turns the text of an input into a typed value.
+//! [`RelmEntryExt::connect_validated()`] runs a validator whenever the text changes,
+//! marks invalid inputs with the `error` CSS class and passes the result to a callback.
+//! [`RelmEntryExt::set_input_mask()`] formats the text while the user types.
+//!
+//! In the `view!` macro, the `#[validate(...)]` and `#[mask(...)]` attributes do the same
+//! and send the typed result as input message:
+//!
+//! ```ignore
+//! view! {
+//!     #[mask(InputMask::date())]
+//!     #[validate(InputMask::date(), Msg::Date)]
+//!     gtk::Entry {},
+//!
+//!     #[validate(MaxLength::new(3).then(Numeric::<u8>::new()), Msg::Age)]
+//!     gtk::Entry {},
+//! }
+//! ```
+//!
+//! Lengths are counted in grapheme clusters, so an emoji like "👩‍👩‍👧" counts as one character.
+
+use std::cell::Cell;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use gtk::glib;
+use gtk::prelude::{Cast, EditableExt, IsA, ObjectExt, WidgetExt};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The CSS class of inputs with invalid text.
+const ERROR_CLASS: &str = "error";
+/// The key of the tooltip an input had before a validation error replaced it.
+const TOOLTIP_KEY: &str = "relm4-validation-tooltip";
+
+/// The reason why a text is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    message: String,
+}
+
+impl ValidationError {
+    /// Create a new error with a message that is shown to the user.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// The message of this error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Turns the text of an input into a typed value.
+pub trait Validator: 'static {
+    /// The value of a valid text.
+    type Output;
+
+    /// Validate `text`.
+    fn validate(&self, text: &str) -> Result<Self::Output, ValidationError>;
+
+    /// Validate the text with `self` first and parse it with `next` if it's valid.
+    fn then<V: Validator>(self, next: V) -> Then<Self, V>
+    where
+        Self: Sized,
+    {
+        Then { first: self, next }
+    }
+
+    /// Replace the message of errors.
+    fn with_message(self, message: impl Into<String>) -> WithMessage<Self>
+    where
+        Self: Sized,
+    {
+        WithMessage {
+            validator: self,
+            message: message.into(),
+        }
+    }
+}
+
+impl<T, F> Validator for F
+where
+    F: Fn(&str) -> Result<T, ValidationError> + 'static,
+{
+    type Output = T;
+
+    fn validate(&self, text: &str) -> Result<T, ValidationError> {
+        self(text)
+    }
+}
+
+/// Two validators that run after each other, see [`Validator::then()`].
+#[derive(Debug, Clone)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<A: Validator, B: Validator> Validator for Then<A, B> {
+    type Output = B::Output;
+
+    fn validate(&self, text: &str) -> Result<B::Output, ValidationError> {
+        self.first.validate(text)?;
+        self.next.validate(text)
+    }
+}
+
+/// A validator with a custom error message, see [`Validator::with_message()`].
+#[derive(Debug, Clone)]
+pub struct WithMessage<V> {
+    validator: V,
+    message: String,
+}
+
+impl<V: Validator> Validator for WithMessage<V> {
+    type Output = V::Output;
+
+    fn validate(&self, text: &str) -> Result<V::Output, ValidationError> {
+        self.validator
+            .validate(text)
+            .map_err(|_| ValidationError::new(self.message.clone()))
+    }
+}
+
+/// Parses numbers like [`u32`] or [`f64`] with [`FromStr`].
+///
+/// Surrounding whitespace is ignored.
+pub struct Numeric<T> {
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Numeric<T> {
+    /// Create a new validator for numbers of type `T`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { _type: PhantomData }
+    }
+}
+
+impl<T> Default for Numeric<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Numeric<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for Numeric<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Numeric")
+            .field("type", &std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T> Validator for Numeric<T>
+where
+    T: FromStr + 'static,
+{
+    type Output = T;
+
+    fn validate(&self, text: &str) -> Result<T, ValidationError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ValidationError::new("Enter a number"));
+        }
+        text.parse()
+            .map_err(|_| ValidationError::new(format!("\"{text}\" is not a valid number")))
+    }
+}
+
+/// Accepts texts that match a regular expression.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: regex::Regex,
+}
+
+impl Pattern {
+    /// Create a new validator from a regular expression.
+    ///
+    /// Use `^` and `$` to match the whole text.
+    pub fn new(regex: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: regex::Regex::new(regex)?,
+        })
+    }
+}
+
+impl From<regex::Regex> for Pattern {
+    fn from(regex: regex::Regex) -> Self {
+        Self { regex }
+    }
+}
+
+impl Validator for Pattern {
+    type Output = String;
+
+    fn validate(&self, text: &str) -> Result<String, ValidationError> {
+        if self.regex.is_match(text) {
+            Ok(text.to_owned())
+        } else {
+            Err(ValidationError::new("Invalid format"))
+        }
+    }
+}
+
+/// Accepts texts with at most a number of characters.
+///
+/// Characters are counted as grapheme clusters, so combined emojis and letters with
+/// combining accents count as one character.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxLength {
+    max: usize,
+}
+
+impl MaxLength {
+    /// Create a new validator that accepts at most `max` characters.
+    #[must_use]
+    pub fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl Validator for MaxLength {
+    type Output = String;
+
+    fn validate(&self, text: &str) -> Result<String, ValidationError> {
+        let len = grapheme_len(text);
+        if len <= self.max {
+            Ok(text.to_owned())
+        } else {
+            Err(ValidationError::new(format!(
+                "Use at most {} characters ({len} entered)",
+                self.max
+            )))
+        }
+    }
+}
+
+/// Returns the number of grapheme clusters in `text`.
+#[must_use]
+pub fn grapheme_len(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Formats the text of an input while the user types.
+///
+/// In the pattern of a mask, `#` stands for a digit, `A` for a letter and `*` for
+/// any character. All other characters are inserted automatically.
+/// Typed characters that don't fit the pattern are dropped.
+///
+/// As validator, a mask accepts complete texts and returns the formatted text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMask {
+    pattern: Vec<char>,
+}
+
+impl InputMask {
+    /// Create a new mask from a pattern like `"(###) ###-####"`.
+    #[must_use]
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+        }
+    }
+
+    /// A mask for phone numbers like "(555) 123-4567".
+    #[must_use]
+    pub fn phone() -> Self {
+        Self::new("(###) ###-####")
+    }
+
+    /// A mask for dates like "31/12/2024".
+    #[must_use]
+    pub fn date() -> Self {
+        Self::new("##/##/####")
+    }
+
+    /// Format `text` according to the pattern.
+    ///
+    /// Literal characters of the pattern in `text` are kept,
+    /// so formatting a formatted text doesn't change it.
+    #[must_use]
+    pub fn apply(&self, text: &str) -> String {
+        let mut output = String::new();
+        let mut slot = 0;
+
+        for c in text.chars() {
+            if slot == self.pattern.len() {
+                break;
+            }
+            let next_placeholder = self.pattern[slot..]
+                .iter()
+                .position(|&p| is_placeholder(p))
+                .map_or(self.pattern.len(), |offset| slot + offset);
+            let literals = &self.pattern[slot..next_placeholder];
+
+            if let Some(offset) = literals.iter().position(|&literal| literal == c) {
+                // The user typed a literal character themselves.
+                output.extend(&literals[..=offset]);
+                slot += offset + 1;
+            } else if next_placeholder < self.pattern.len()
+                && fits(self.pattern[next_placeholder], c)
+            {
+                output.extend(literals);
+                output.push(c);
+                slot = next_placeholder + 1;
+            }
+        }
+        output
+    }
+
+    /// Returns the cursor position in the formatted text for the cursor
+    /// at the character offset `position` in `text`.
+    fn map_position(&self, text: &str, position: usize) -> usize {
+        let prefix: String = text.chars().take(position).collect();
+        self.apply(&prefix).chars().count()
+    }
+
+    /// Returns `true` if `text` fills the whole pattern.
+    #[must_use]
+    pub fn is_complete(&self, text: &str) -> bool {
+        self.apply(text).chars().count() == self.pattern.len()
+    }
+}
+
+fn is_placeholder(c: char) -> bool {
+    matches!(c, '#' | 'A' | '*')
+}
+
+/// Returns `true` if `c` can be typed into the placeholder `slot`.
+fn fits(slot: char, c: char) -> bool {
+    match slot {
+        '#' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        _ => !c.is_control(),
+    }
+}
+
+impl Validator for InputMask {
+    type Output = String;
+
+    fn validate(&self, text: &str) -> Result<String, ValidationError> {
+        if self.is_complete(text) {
+            Ok(self.apply(text))
+        } else {
+            let pattern: String = self.pattern.iter().collect();
+            Err(ValidationError::new(format!(
+                "Complete the format {pattern}"
+            )))
+        }
+    }
+}
+
+/// Validation and input masks for [`gtk::Editable`] widgets like [`gtk::Entry`].
+pub trait RelmEntryExt: IsA<gtk::Editable> + IsA<gtk::Widget> {
+    /// Validate the text whenever it changes and call `f` with the result.
+    ///
+    /// Invalid inputs get the `error` CSS class and the error message as tooltip.
+    /// The previous tooltip is restored once the text is valid again.
+    /// The text is also validated once right away.
+    fn connect_validated<V, F>(&self, validator: V, f: F) -> glib::SignalHandlerId
+    where
+        V: Validator,
+        F: Fn(Result<V::Output, ValidationError>) + 'static,
+    {
+        let validate = move |editable: &gtk::Editable| {
+            let result = validator.validate(&editable.text());
+            show_error(editable, result.as_ref().err());
+            f(result);
+        };
+
+        let editable = self.upcast_ref::<gtk::Editable>();
+        validate(editable);
+        editable.connect_changed(validate)
+    }
+
+    /// Format the text with `mask` whenever it changes.
+    ///
+    /// The cursor keeps its place relative to the typed characters,
+    /// so editing in the middle of the text works as expected.
+    fn set_input_mask(&self, mask: InputMask) -> glib::SignalHandlerId {
+        let editable = self.upcast_ref::<gtk::Editable>();
+        editable.set_max_width_chars(mask.pattern.len() as i32);
+
+        let applying = Rc::new(Cell::new(false));
+        editable.connect_changed(move |editable| {
+            if applying.get() {
+                return;
+            }
+            let text = editable.text();
+            let formatted = mask.apply(&text);
+            if formatted != text.as_str() {
+                let position = editable.position();
+                let position =
+                    mask.map_position(&text, usize::try_from(position).unwrap_or(0)) as i32;
+                applying.set(true);
+                editable.set_text(&formatted);
+                editable.set_position(position);
+                applying.set(false);
+
+                // The text widget moves the cursor after an insertion
+                // is complete, so restore it once more afterwards.
+                let editable = editable.downgrade();
+                glib::idle_add_local_once(move || {
+                    if let Some(editable) = editable.upgrade() {
+                        if editable.text() == formatted.as_str() {
+                            editable.set_position(position);
+                        }
+                    }
+                });
+            }
+        })
+    }
+
+    /// Remove error styling that was added by a validator.
+    fn clear_validation_error(&self) {
+        show_error(self.upcast_ref(), None);
+    }
+}
+
+impl<T: IsA<gtk::Editable> + IsA<gtk::Widget>> RelmEntryExt for T {}
+
+fn show_error(editable: &gtk::Editable, error: Option<&ValidationError>) {
+    let widget = editable.upcast_ref::<gtk::Widget>();
+    if let Some(error) = error {
+        if !widget.has_css_class(ERROR_CLASS) {
+            // SAFETY: The key is only used with this type.
+            unsafe { widget.set_data(TOOLTIP_KEY, widget.tooltip_markup()) };
+        }
+        widget.add_css_class(ERROR_CLASS);
+        widget.set_tooltip_text(Some(error.message()));
+    } else if widget.has_css_class(ERROR_CLASS) {
+        widget.remove_css_class(ERROR_CLASS);
+        // SAFETY: The key is only used with this type.
+        let tooltip = unsafe { widget.steal_data::<Option<glib::GString>>(TOOLTIP_KEY) };
+        widget.set_tooltip_markup(tooltip.flatten().as_deref());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{grapheme_len, InputMask, MaxLength, Numeric, Pattern, Validator};
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Numeric::<u8>::new().validate(" 42 "), Ok(42));
+        assert!(Numeric::<u8>::new().validate("256").is_err());
+        assert!(Numeric::<f64>::new().validate("").is_err());
+    }
+
+    #[test]
+    fn graphemes() {
+        assert_eq!(grapheme_len("👩‍👩‍👧e\u{301}"), 2);
+        assert!(MaxLength::new(2).validate("👩‍👩‍👧e\u{301}").is_ok());
+        assert!(MaxLength::new(1).validate("👩‍👩‍👧e\u{301}").is_err());
+    }
+
+    #[test]
+    fn composition() {
+        let validator = MaxLength::new(3)
+            .then(Numeric::<u16>::new())
+            .with_message("Enter up to three digits");
+        assert_eq!(validator.validate("123"), Ok(123));
+        let error = validator.validate("1234").unwrap_err();
+        assert_eq!(error.message(), "Enter up to three digits");
+
+        let pattern = Pattern::new(r"^[a-z]+$").unwrap();
+        assert!(pattern.validate("abc").is_ok());
+        assert!(pattern.validate("ab1").is_err());
+    }
+
+    #[test]
+    fn masks() {
+        let phone = InputMask::phone();
+        assert_eq!(phone.apply("555"), "(555");
+        assert_eq!(phone.apply("5551234567"), "(555) 123-4567");
+        assert_eq!(phone.apply("(555) 123-4567"), "(555) 123-4567");
+        assert_eq!(phone.apply("55a5"), "(555");
+        assert!(phone.is_complete("5551234567"));
+
+        let date = InputMask::date();
+        assert_eq!(date.apply("3112"), "31/12");
+        assert_eq!(date.validate("31122024"), Ok("31/12/2024".to_owned()));
+        assert!(date.validate("3112").is_err());
+    }
+
+    #[test]
+    fn mask_positions() {
+        let phone = InputMask::phone();
+        // Typing the first digit moves the cursor behind the inserted parenthesis.
+        assert_eq!(phone.map_position("5", 1), 2);
+        // Inserting a digit in the middle keeps the cursor behind it.
+        assert_eq!(phone.map_position("(555) 1923-4567", 8), 8);
+        assert_eq!(phone.map_position("(555) 123-4567", 0), 0);
+        assert_eq!(phone.map_position("(555) 123-4567", 14), 14);
+    }
+}
@@ -0,0 +1,135 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use relm4::autosave::{Autosave, AutosaveSettings, AutosaveStatus};
+use relm4::test::LoopDriver;
+
+// Everything runs in one test because the services use the main context of the thread.
+#[test]
+fn debounce_and_retry() {
+    let driver = LoopDriver::new();
+    let autosave = Autosave::new(AutosaveSettings {
+        delay: Duration::from_secs(1),
+        retry_delay: Duration::from_secs(2),
+        max_retry_delay: Duration::from_secs(3),
+    });
+    let (sender, statuses) = relm4::channel();
+    autosave.subscribe(&sender, AutosaveStatus::clone);
+
+    let dirty = Rc::new(Cell::new(false));
+    let fail = Rc::new(Cell::new(false));
+    let saves = Rc::new(Cell::new(0));
+
+    let handle = autosave.register(
+        {
+            let dirty = dirty.clone();
+            move || dirty.get()
+        },
+        {
+            let (dirty, fail, saves) = (dirty.clone(), fail.clone(), saves.clone());
+            move || {
+                let (dirty, fail, saves) = (dirty.clone(), fail.clone(), saves.clone());
+                async move {
+                    saves.set(saves.get() + 1);
+                    if fail.get() {
+                        Err("disk full")
+                    } else {
+                        dirty.set(false);
+                        Ok(())
+                    }
+                }
+            }
+        },
+    );
+
+    // Changes are debounced.
+    dirty.set(true);
+    handle.mark_changed();
+    driver.advance_by(Duration::from_millis(600));
+    handle.mark_changed();
+    driver.advance_by(Duration::from_millis(600));
+    assert_eq!(saves.get(), 0);
+    assert_eq!(autosave.status(), AutosaveStatus::Modified);
+
+    driver.advance_by(Duration::from_millis(400));
+    assert_eq!(saves.get(), 1);
+    assert_eq!(autosave.status(), AutosaveStatus::Saved);
+
+    // Subscribers get the current status and every change once.
+    for status in [
+        AutosaveStatus::Saved,
+        AutosaveStatus::Modified,
+        AutosaveStatus::Saving,
+        AutosaveStatus::Saved,
+    ] {
+        assert_eq!(statuses.recv_sync(), Some(status));
+    }
+
+    // Failures are retried with backoff.
+    dirty.set(true);
+    fail.set(true);
+    handle.mark_changed();
+    driver.advance_by(Duration::from_secs(1));
+    assert_eq!(saves.get(), 2);
+    assert_eq!(
+        autosave.status(),
+        AutosaveStatus::Error("disk full".to_owned())
+    );
+
+    driver.advance_by(Duration::from_secs(2));
+    assert_eq!(saves.get(), 3);
+    // The delay is doubled, but limited by the maximum.
+    driver.advance_by(Duration::from_secs(2));
+    assert_eq!(saves.get(), 3);
+    driver.advance_by(Duration::from_secs(1));
+    assert_eq!(saves.get(), 4);
+
+    fail.set(false);
+    driver.advance_by(Duration::from_secs(3));
+    assert_eq!(saves.get(), 5);
+    assert_eq!(autosave.status(), AutosaveStatus::Saved);
+
+    // Nothing is saved without changes.
+    driver.advance_by(Duration::from_secs(10));
+    assert_eq!(saves.get(), 5);
+
+    // Dropping the handle saves remaining changes.
+    dirty.set(true);
+    drop(handle);
+    driver.advance_until_idle();
+    assert_eq!(saves.get(), 6);
+
+    // Failed saves of dropped handles are retried by the service.
+    let handle = autosave.register(
+        {
+            let dirty = dirty.clone();
+            move || dirty.get()
+        },
+        {
+            let (dirty, fail, saves) = (dirty.clone(), fail.clone(), saves.clone());
+            move || {
+                let (dirty, fail, saves) = (dirty.clone(), fail.clone(), saves.clone());
+                async move {
+                    saves.set(saves.get() + 1);
+                    if fail.get() {
+                        Err("disk full")
+                    } else {
+                        dirty.set(false);
+                        Ok(())
+                    }
+                }
+            }
+        },
+    );
+    dirty.set(true);
+    fail.set(true);
+    drop(handle);
+    driver.advance_until_idle();
+    assert_eq!(saves.get(), 7);
+
+    fail.set(false);
+    futures::executor::block_on(autosave.flush()).unwrap();
+    assert_eq!(saves.get(), 8);
+    assert!(!dirty.get());
+}